@@ -0,0 +1,24 @@
+#[derive(Default)]
+pub struct About {
+    pub open: bool,
+}
+
+impl About {
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        egui::Window::new("About")
+            .open(&mut self.open)
+            .default_pos(egui::pos2(400.0, 200.0))
+            .show(ctx, |ui| {
+                ui.heading(env!("CARGO_PKG_NAME"));
+                ui.label(format!("Version {}", env!("CARGO_PKG_VERSION")));
+                ui.label(format!(
+                    "By {}",
+                    env!("CARGO_PKG_AUTHORS").replace(':', ", ")
+                ));
+                ui.label("A playground for egui 0.3 on wgpu 0.6 and winit 0.23.");
+                ui.add(
+                    egui::Hyperlink::new("https://github.com/evopen/egui-demo").text("Source code"),
+                );
+            });
+    }
+}
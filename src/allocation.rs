@@ -0,0 +1,312 @@
+const HISTORY: usize = 240;
+const TAG_COUNT: usize = 4;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Tag {
+    Other,
+    Scene,
+    Ui,
+    Render,
+}
+
+impl Tag {
+    const ALL: [Tag; TAG_COUNT] = [Tag::Other, Tag::Scene, Tag::Ui, Tag::Render];
+
+    fn name(self) -> &'static str {
+        match self {
+            Tag::Other => "other",
+            Tag::Scene => "scene",
+            Tag::Ui => "ui",
+            Tag::Render => "render",
+        }
+    }
+}
+
+struct Counters {
+    allocations: std::sync::atomic::AtomicU64,
+    frees: std::sync::atomic::AtomicU64,
+    allocated: std::sync::atomic::AtomicU64,
+    freed: std::sync::atomic::AtomicU64,
+}
+
+impl Counters {
+    const fn new() -> Self {
+        Self {
+            allocations: std::sync::atomic::AtomicU64::new(0),
+            frees: std::sync::atomic::AtomicU64::new(0),
+            allocated: std::sync::atomic::AtomicU64::new(0),
+            freed: std::sync::atomic::AtomicU64::new(0),
+        }
+    }
+}
+
+static COUNTERS: [Counters; TAG_COUNT] = [
+    Counters::new(),
+    Counters::new(),
+    Counters::new(),
+    Counters::new(),
+];
+
+thread_local! {
+    static CURRENT_TAG: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+fn counters() -> &'static Counters {
+    let tag = CURRENT_TAG.try_with(|tag| tag.get()).unwrap_or(0);
+    &COUNTERS[tag]
+}
+
+fn record_alloc(size: usize) {
+    let counters = counters();
+    counters
+        .allocations
+        .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    counters
+        .allocated
+        .fetch_add(size as u64, std::sync::atomic::Ordering::Relaxed);
+}
+
+fn record_free(size: usize) {
+    let counters = counters();
+    counters
+        .frees
+        .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    counters
+        .freed
+        .fetch_add(size as u64, std::sync::atomic::Ordering::Relaxed);
+}
+
+pub struct Counting;
+
+unsafe impl std::alloc::GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        let ptr = std::alloc::System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: std::alloc::Layout) -> *mut u8 {
+        let ptr = std::alloc::System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        std::alloc::System.dealloc(ptr, layout);
+        record_free(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: std::alloc::Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = std::alloc::System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record_free(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+pub struct Scope {
+    previous: usize,
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        let previous = self.previous;
+        CURRENT_TAG.try_with(|tag| tag.set(previous)).ok();
+    }
+}
+
+pub fn scope(tag: Tag) -> Scope {
+    let index = Tag::ALL
+        .iter()
+        .position(|candidate| *candidate == tag)
+        .unwrap();
+    let previous = CURRENT_TAG
+        .try_with(|current| current.replace(index))
+        .unwrap_or(0);
+    Scope { previous }
+}
+
+pub fn enabled() -> bool {
+    cfg!(feature = "alloc-profiler")
+}
+
+#[derive(Clone, Copy, Default)]
+struct Totals {
+    allocations: u64,
+    frees: u64,
+    allocated: u64,
+    freed: u64,
+}
+
+impl Totals {
+    fn delta(&self, previous: &Totals) -> Totals {
+        Totals {
+            allocations: self.allocations - previous.allocations,
+            frees: self.frees - previous.frees,
+            allocated: self.allocated - previous.allocated,
+            freed: self.freed - previous.freed,
+        }
+    }
+}
+
+fn snapshot() -> [Totals; TAG_COUNT] {
+    let mut totals = [Totals::default(); TAG_COUNT];
+    for (total, counters) in totals.iter_mut().zip(COUNTERS.iter()) {
+        let load = |counter: &std::sync::atomic::AtomicU64| {
+            counter.load(std::sync::atomic::Ordering::Relaxed)
+        };
+        *total = Totals {
+            allocations: load(&counters.allocations),
+            frees: load(&counters.frees),
+            allocated: load(&counters.allocated),
+            freed: load(&counters.freed),
+        };
+    }
+    totals
+}
+
+pub struct AllocationPanel {
+    pub open: bool,
+    previous: [Totals; TAG_COUNT],
+    frames: std::collections::VecDeque<[Totals; TAG_COUNT]>,
+    zero_streak: u64,
+    best_streak: u64,
+}
+
+impl Default for AllocationPanel {
+    fn default() -> Self {
+        Self {
+            open: false,
+            previous: snapshot(),
+            frames: std::collections::VecDeque::with_capacity(HISTORY),
+            zero_streak: 0,
+            best_streak: 0,
+        }
+    }
+}
+
+impl AllocationPanel {
+    pub fn end_frame(&mut self) {
+        let current = snapshot();
+        let mut frame = [Totals::default(); TAG_COUNT];
+        for ((delta, now), before) in frame
+            .iter_mut()
+            .zip(current.iter())
+            .zip(self.previous.iter())
+        {
+            *delta = now.delta(before);
+        }
+        self.previous = current;
+        if frame.iter().all(|totals| totals.allocations == 0) {
+            self.zero_streak += 1;
+            self.best_streak = self.best_streak.max(self.zero_streak);
+        } else {
+            self.zero_streak = 0;
+        }
+        if self.frames.len() == HISTORY {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        let last = self.frames.back().copied().unwrap_or_default();
+        let frame_count = self.frames.len().max(1) as f64;
+        let mut averages = [0.0f64; TAG_COUNT];
+        for frame in self.frames.iter() {
+            for (average, totals) in averages.iter_mut().zip(frame.iter()) {
+                *average += totals.allocations as f64 / frame_count;
+            }
+        }
+        let per_frame: Vec<u64> = self
+            .frames
+            .iter()
+            .map(|frame| frame.iter().map(|totals| totals.allocations).sum())
+            .collect();
+        let live: i64 = self
+            .previous
+            .iter()
+            .map(|totals| totals.allocated as i64 - totals.freed as i64)
+            .sum();
+        let zero_streak = self.zero_streak;
+        let best_streak = self.best_streak;
+        let mut reset_requested = false;
+        crate::dock::Panel::new("Allocations")
+            .open(&mut self.open)
+            .default_pos(egui::pos2(380.0, 120.0))
+            .show(ctx, |ui| {
+                if !enabled() {
+                    ui.label(
+                        "Build with --features alloc-profiler to install the counting allocator.",
+                    );
+                    return;
+                }
+                ui.label(format!(
+                    "Live heap: {}",
+                    crate::duplicates::format_size(live.max(0) as u64)
+                ));
+                ui.label(format!(
+                    "Zero-allocation frames: {} in a row (best {})",
+                    zero_streak, best_streak
+                ));
+                ui.separator();
+                ui.columns(5, |columns| {
+                    for (column, header) in ["tag", "allocs", "frees", "bytes", "avg allocs"]
+                        .iter()
+                        .enumerate()
+                    {
+                        columns[column].label(*header);
+                    }
+                    for ((tag, totals), average) in
+                        Tag::ALL.iter().zip(last.iter()).zip(averages.iter())
+                    {
+                        columns[0].label(tag.name());
+                        columns[1].label(totals.allocations.to_string());
+                        columns[2].label(totals.frees.to_string());
+                        columns[3].label(crate::duplicates::format_size(totals.allocated));
+                        columns[4].label(format!("{:.1}", average));
+                    }
+                });
+
+                ui.label("Allocations per frame");
+                let rect = ui.allocate_space(egui::vec2(HISTORY as f32 * 1.5, 60.0));
+                let painter = ui.painter();
+                painter.rect_filled(rect, 2.0, egui::Srgba::new(20, 20, 24, 255));
+                let max = per_frame.iter().copied().max().unwrap_or(0).max(1) as f32;
+                let width = rect.width() / HISTORY as f32;
+                for (index, count) in per_frame.iter().enumerate() {
+                    if *count == 0 {
+                        continue;
+                    }
+                    let height = (*count as f32 / max) * rect.height();
+                    let x = rect.min.x + index as f32 * width;
+                    painter.rect_filled(
+                        egui::Rect::from_min_max(
+                            egui::pos2(x, rect.max.y - height),
+                            egui::pos2(x + width, rect.max.y),
+                        ),
+                        0.0,
+                        egui::Srgba::new(240, 140, 60, 255),
+                    );
+                }
+                ui.label(format!(
+                    "peak {} allocations in one frame",
+                    per_frame.iter().copied().max().unwrap_or(0)
+                ));
+                if ui.button("Reset streak").clicked {
+                    reset_requested = true;
+                }
+            });
+        if reset_requested {
+            self.zero_streak = 0;
+            self.best_streak = 0;
+            self.frames.clear();
+        }
+    }
+}
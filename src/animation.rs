@@ -0,0 +1,314 @@
+const PREVIEW_AREA: [f32; 2] = [360.0, 270.0];
+const MAX_BYTES: usize = 256 << 20;
+const MIN_DELAY_MS: f64 = 20.0;
+const DEFAULT_DELAY_MS: f64 = 100.0;
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+const PNG_HEADER_BYTES: usize = 4096;
+
+fn extension(path: &std::path::Path) -> String {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map_or(String::new(), str::to_lowercase)
+}
+
+fn has_animation_control(data: &[u8]) -> bool {
+    if !data.starts_with(&PNG_SIGNATURE) {
+        return false;
+    }
+    let mut offset = PNG_SIGNATURE.len();
+    while offset + 8 <= data.len() {
+        let length = u32::from_be_bytes([
+            data[offset],
+            data[offset + 1],
+            data[offset + 2],
+            data[offset + 3],
+        ]) as usize;
+        match &data[offset + 4..offset + 8] {
+            b"acTL" => return true,
+            b"IDAT" => return false,
+            _ => offset += 12 + length,
+        }
+    }
+    false
+}
+
+pub fn is_animation(path: &std::path::Path) -> bool {
+    match extension(path).as_str() {
+        "gif" => true,
+        "png" | "apng" => {
+            let mut header = Vec::new();
+            std::fs::File::open(path)
+                .and_then(|file| {
+                    std::io::Read::read_to_end(
+                        &mut std::io::Read::take(file, PNG_HEADER_BYTES as u64),
+                        &mut header,
+                    )
+                })
+                .is_ok()
+                && has_animation_control(&header)
+        }
+        _ => false,
+    }
+}
+
+fn delay(frame: &image::Frame) -> std::time::Duration {
+    let (numer, denom) = frame.delay().numer_denom_ms();
+    let ms = numer as f64 / denom.max(1) as f64;
+    let ms = if ms < MIN_DELAY_MS {
+        DEFAULT_DELAY_MS
+    } else {
+        ms
+    };
+    std::time::Duration::from_secs_f64(ms / 1000.0)
+}
+
+struct Decoded {
+    name: String,
+    dimensions: [u32; 2],
+    frames: Vec<(image::RgbaImage, std::time::Duration)>,
+    still: bool,
+}
+
+impl Decoded {
+    fn duration(&self) -> std::time::Duration {
+        self.frames.iter().map(|(_, delay)| *delay).sum()
+    }
+}
+
+fn decode(path: &std::path::Path, progress: &crate::jobs::Progress) -> Result<Decoded, String> {
+    progress.set(0.0, "reading");
+    let file = std::fs::File::open(path).map_err(|err| err.to_string())?;
+    let reader = std::io::BufReader::new(file);
+    let mut frames = Vec::new();
+    let mut still = false;
+    if extension(path) == "gif" {
+        let decoder = image::gif::GifDecoder::new(reader).map_err(|err| err.to_string())?;
+        let mut bytes = 0;
+        for frame in image::AnimationDecoder::into_frames(decoder) {
+            progress.check()?;
+            let frame = frame.map_err(|err| err.to_string())?;
+            let delay = delay(&frame);
+            let buffer = frame.into_buffer();
+            bytes += buffer.as_raw().len();
+            if bytes > MAX_BYTES {
+                return Err(format!("more than {} MB of frames", MAX_BYTES >> 20));
+            }
+            frames.push((buffer, delay));
+            progress.set(0.5, format!("decoded {} frames", frames.len()));
+        }
+    } else {
+        // The image crate in use has no APNG decoder, so only the default image is shown.
+        let image = image::load(reader, image::ImageFormat::Png).map_err(|err| err.to_string())?;
+        frames.push((
+            image.to_rgba(),
+            std::time::Duration::from_secs_f64(DEFAULT_DELAY_MS / 1000.0),
+        ));
+        still = true;
+    }
+    let dimensions = match frames.first() {
+        Some((image, _)) => [image.width(), image.height()],
+        None => return Err("the file has no frames".to_owned()),
+    };
+    Ok(Decoded {
+        name: path
+            .file_name()
+            .map_or(String::new(), |name| name.to_string_lossy().into_owned()),
+        dimensions,
+        frames,
+        still,
+    })
+}
+
+#[derive(Default)]
+pub struct AnimationPlayer {
+    pub open: bool,
+    path: String,
+    loading: Option<crate::jobs::Handle<Decoded>>,
+    animation: Option<Result<Decoded, String>>,
+    loads: u64,
+    texture: Option<(u64, crate::ui_pass::AnimatedTexture)>,
+    playing: bool,
+    frame: usize,
+    elapsed: std::time::Duration,
+    last_tick: Option<std::time::Instant>,
+    visible: bool,
+}
+
+impl AnimationPlayer {
+    pub fn open_path(&mut self, path: &std::path::Path) {
+        self.path = path.display().to_string();
+        let name = path
+            .file_name()
+            .map_or(String::new(), |name| name.to_string_lossy().into_owned());
+        let path = path.to_path_buf();
+        self.loading = Some(crate::jobs::spawn(
+            format!("Decode {}", name),
+            move |progress| decode(&path, progress),
+        ));
+        self.open = true;
+    }
+
+    pub fn recreate_gpu_resources(&mut self) {
+        self.texture = None;
+    }
+
+    fn poll(&mut self) {
+        if let Some(result) = self.loading.as_ref().and_then(crate::jobs::Handle::poll) {
+            self.loading = None;
+            if let Err(err) = &result {
+                log::warn!("cannot play {}: {}", self.path, err);
+            }
+            self.animation = Some(result);
+            self.loads += 1;
+            self.playing = true;
+            self.frame = 0;
+            self.elapsed = std::time::Duration::default();
+        }
+    }
+
+    fn advance(&mut self) {
+        let now = std::time::Instant::now();
+        let last_tick = self.last_tick.replace(now).unwrap_or(now);
+        let frames = match &self.animation {
+            Some(Ok(animation)) if self.playing && animation.frames.len() > 1 => &animation.frames,
+            _ => return,
+        };
+        self.elapsed += now - last_tick;
+        while self.elapsed >= frames[self.frame].1 {
+            self.elapsed -= frames[self.frame].1;
+            self.frame = (self.frame + 1) % frames.len();
+        }
+    }
+
+    pub fn upload(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        ui_pass: &mut crate::ui_pass::RenderPass,
+    ) {
+        let shown = match &self.animation {
+            Some(Ok(animation)) if self.visible => Some(animation),
+            _ => None,
+        };
+        let stale = match &self.texture {
+            Some((loads, _)) => shown.is_none() || *loads != self.loads,
+            None => false,
+        };
+        if stale {
+            if let Some((_, texture)) = self.texture.take() {
+                ui_pass.free_animation(texture);
+            }
+        }
+        let animation = match shown {
+            Some(animation) => animation,
+            None => return,
+        };
+        let loads = self.loads;
+        self.texture.get_or_insert_with(|| {
+            (
+                loads,
+                ui_pass.register_animation(device, queue, &animation.frames),
+            )
+        });
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        self.poll();
+        self.advance();
+        let mut open = self.open;
+        let mut visible = false;
+        let texture = match &self.texture {
+            Some((loads, texture)) if *loads == self.loads => {
+                Some(texture.texture(self.frame.min(texture.frame_count() - 1)))
+            }
+            _ => None,
+        };
+        let loading = self.loading.is_some();
+        let animation = &self.animation;
+        let playing = &mut self.playing;
+        let frame = &mut self.frame;
+        let elapsed = &mut self.elapsed;
+        crate::dock::Panel::new("Animation")
+            .open(&mut open)
+            .default_pos(egui::pos2(380.0, 140.0))
+            .show(ctx, |ui| {
+                if loading {
+                    ui.label("Decoding...");
+                }
+                let animation = match animation {
+                    Some(Ok(animation)) => animation,
+                    Some(Err(err)) => {
+                        ui.label(format!("Cannot play the file: {}", err));
+                        return;
+                    }
+                    None => {
+                        ui.label("Drop a GIF or APNG on the window to play it");
+                        return;
+                    }
+                };
+                let count = animation.frames.len();
+                ui.label(format!(
+                    "{}  {} x {}  {} frames  {:.2} s",
+                    animation.name,
+                    animation.dimensions[0],
+                    animation.dimensions[1],
+                    count,
+                    animation.duration().as_secs_f32()
+                ));
+                if animation.still {
+                    ui.label("APNG frames cannot be decoded yet, showing the default image");
+                }
+                let size = egui::vec2(
+                    animation.dimensions[0].max(1) as f32,
+                    animation.dimensions[1].max(1) as f32,
+                );
+                let rect = ui.allocate_space(egui::vec2(PREVIEW_AREA[0], PREVIEW_AREA[1]));
+                ui.painter()
+                    .rect_filled(rect, 0.0, egui::Srgba::new(24, 24, 28, 255));
+                let scale = (PREVIEW_AREA[0] / size.x).min(PREVIEW_AREA[1] / size.y);
+                visible = true;
+                if let Some(texture) = texture {
+                    crate::ui_pass::paint_user_texture(
+                        ui.painter(),
+                        texture,
+                        egui::Rect::from_center_size(rect.center(), size * scale),
+                        egui::color::WHITE,
+                    );
+                }
+                if count < 2 {
+                    return;
+                }
+                ui.horizontal(|ui| {
+                    if ui.button(if *playing { "Pause" } else { "Play" }).clicked {
+                        *playing = !*playing;
+                    }
+                    if ui.button("<").clicked {
+                        *playing = false;
+                        *frame = (*frame + count - 1) % count;
+                        *elapsed = std::time::Duration::default();
+                    }
+                    if ui.button(">").clicked {
+                        *playing = false;
+                        *frame = (*frame + 1) % count;
+                        *elapsed = std::time::Duration::default();
+                    }
+                    ui.label(format!(
+                        "Frame {} of {}, {} ms",
+                        *frame + 1,
+                        count,
+                        animation.frames[*frame].1.as_millis()
+                    ));
+                });
+                let mut scrub = *frame as f32;
+                ui.add(egui::Slider::f32(&mut scrub, 0.0..=(count - 1) as f32).text("frame"));
+                let scrubbed = (scrub.round() as usize).min(count - 1);
+                if scrubbed != *frame {
+                    *playing = false;
+                    *frame = scrubbed;
+                    *elapsed = std::time::Duration::default();
+                }
+            });
+        self.open = open;
+        self.visible = visible && open;
+    }
+}
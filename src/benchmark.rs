@@ -0,0 +1,475 @@
+const TARGET_SIZE: u32 = 1024;
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+const PASSES_PER_BATCH: u64 = 8;
+const COMPUTE_INVOCATIONS: u32 = 1 << 20;
+const WORKGROUP_SIZE: u32 = 256;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Workload {
+    FillRate,
+    Alu,
+    Texture,
+    Compute,
+}
+
+impl Workload {
+    pub const ALL: [Workload; 4] = [
+        Workload::FillRate,
+        Workload::Alu,
+        Workload::Texture,
+        Workload::Compute,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Workload::FillRate => "Fill rate",
+            Workload::Alu => "ALU fragment",
+            Workload::Texture => "Texture sampling",
+            Workload::Compute => "Compute",
+        }
+    }
+
+    fn unit(self) -> &'static str {
+        match self {
+            Workload::FillRate => "Gpixels/s",
+            Workload::Alu => "Giterations/s",
+            Workload::Texture => "Gsamples/s",
+            Workload::Compute => "GFLOPS",
+        }
+    }
+
+    fn work_per_pass(self) -> f64 {
+        let pixels = (TARGET_SIZE * TARGET_SIZE) as f64;
+        match self {
+            Workload::FillRate => pixels,
+            Workload::Alu => pixels * 256.0,
+            Workload::Texture => pixels * 16.0,
+            Workload::Compute => COMPUTE_INVOCATIONS as f64 * 512.0 * 4.0 * 4.0,
+        }
+    }
+}
+
+struct Score {
+    workload: Workload,
+    passes: u64,
+    seconds: f64,
+}
+
+impl Score {
+    fn value(&self) -> f64 {
+        if self.seconds <= 0.0 {
+            0.0
+        } else {
+            self.workload.work_per_pass() * self.passes as f64 / self.seconds / 1e9
+        }
+    }
+}
+
+struct Run {
+    pending: Vec<Workload>,
+    workload: Workload,
+    started: std::time::Instant,
+    warmed_up: bool,
+    passes: u64,
+    seconds: f64,
+}
+
+impl Run {
+    fn begin(workload: Workload, pending: Vec<Workload>) -> Self {
+        Self {
+            pending,
+            workload,
+            started: std::time::Instant::now(),
+            warmed_up: false,
+            passes: 0,
+            seconds: 0.0,
+        }
+    }
+}
+
+pub struct Settings {
+    pub duration: f32,
+    pub enabled: [bool; 4],
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            duration: 2.0,
+            enabled: [true; 4],
+        }
+    }
+}
+
+pub struct Benchmark {
+    pub open: bool,
+    pub settings: Settings,
+    start_requested: bool,
+    cancel_requested: bool,
+    run: Option<Run>,
+    results: Vec<Score>,
+    best: [f64; 4],
+    target: wgpu::TextureView,
+    source: wgpu::Texture,
+    source_uploaded: bool,
+    fill: (crate::blit::Blitter, wgpu::BindGroup),
+    alu: (crate::blit::Blitter, wgpu::BindGroup),
+    texture: (crate::blit::Blitter, wgpu::BindGroup),
+    compute_pipeline: wgpu::ComputePipeline,
+    compute_bind_group: wgpu::BindGroup,
+}
+
+impl Benchmark {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let extent = wgpu::Extent3d {
+            width: TARGET_SIZE,
+            height: TARGET_SIZE,
+            depth: 1,
+        };
+        let target = device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some("Benchmark Target"),
+                size: extent,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: FORMAT,
+                usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+            })
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let source = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Benchmark Source"),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: FORMAT,
+            usage: wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST,
+        });
+        let source_view = source.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let additive = wgpu::BlendDescriptor {
+            src_factor: wgpu::BlendFactor::One,
+            dst_factor: wgpu::BlendFactor::One,
+            operation: wgpu::BlendOperation::Add,
+        };
+        let fill = crate::blit::Blitter::with_blend(
+            device,
+            FORMAT,
+            "bench_fill.frag",
+            include_str!("shaders/bench_fill.frag"),
+            additive,
+        );
+        let alu = crate::blit::Blitter::new(
+            device,
+            FORMAT,
+            "bench_alu.frag",
+            include_str!("shaders/bench_alu.frag"),
+        );
+        let texture = crate::blit::Blitter::new(
+            device,
+            FORMAT,
+            "bench_texture.frag",
+            include_str!("shaders/bench_texture.frag"),
+        );
+        let fill_bind_group = fill.bind(device, &source_view);
+        let alu_bind_group = alu.bind(device, &source_view);
+        let texture_bind_group = texture.bind(device, &source_view);
+
+        let compute_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Benchmark Compute Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStage::COMPUTE,
+                ty: wgpu::BindingType::StorageBuffer {
+                    dynamic: false,
+                    min_binding_size: None,
+                    readonly: false,
+                },
+                count: None,
+            }],
+        });
+        let compute_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Benchmark Compute Buffer"),
+            size: COMPUTE_INVOCATIONS as wgpu::BufferAddress * 16,
+            usage: wgpu::BufferUsage::STORAGE,
+            mapped_at_creation: false,
+        });
+        let compute_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Benchmark Compute Bind Group"),
+            layout: &compute_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer(compute_buffer.slice(..)),
+            }],
+        });
+        let cs_module = crate::shader::compile_builtin(
+            device,
+            "bench_compute.comp",
+            include_str!("shaders/bench_compute.comp"),
+            shaderc::ShaderKind::Compute,
+        );
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Benchmark Compute Pipeline Layout"),
+            bind_group_layouts: &[&compute_layout],
+            push_constant_ranges: &[],
+        });
+        let compute_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Benchmark Compute Pipeline"),
+            layout: Some(&pipeline_layout),
+            compute_stage: wgpu::ProgrammableStageDescriptor {
+                module: &cs_module,
+                entry_point: "main",
+            },
+        });
+
+        Self {
+            open: false,
+            settings: Settings::default(),
+            start_requested: false,
+            cancel_requested: false,
+            run: None,
+            results: Vec::new(),
+            best: [0.0; 4],
+            target,
+            source,
+            source_uploaded: false,
+            fill: (fill, fill_bind_group),
+            alu: (alu, alu_bind_group),
+            texture: (texture, texture_bind_group),
+            compute_pipeline,
+            compute_bind_group,
+        }
+    }
+
+    pub fn recreate_gpu_resources(&mut self, device: &wgpu::Device) {
+        let settings = std::mem::take(&mut self.settings);
+        let results = std::mem::take(&mut self.results);
+        let best = self.best;
+        let open = self.open;
+        *self = Self::new(device);
+        self.settings = settings;
+        self.results = results;
+        self.best = best;
+        self.open = open;
+    }
+
+    pub fn running(&self) -> bool {
+        self.run.is_some() || self.start_requested
+    }
+
+    fn upload_source(&mut self, queue: &wgpu::Queue) {
+        let mut state = 0x2545_f491u32;
+        let pixels: Vec<u8> = (0..TARGET_SIZE * TARGET_SIZE * 4)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                (state >> 24) as u8
+            })
+            .collect();
+        queue.write_texture(
+            wgpu::TextureCopyView {
+                texture: &self.source,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            &pixels,
+            wgpu::TextureDataLayout {
+                offset: 0,
+                bytes_per_row: 4 * TARGET_SIZE,
+                rows_per_image: TARGET_SIZE,
+            },
+            wgpu::Extent3d {
+                width: TARGET_SIZE,
+                height: TARGET_SIZE,
+                depth: 1,
+            },
+        );
+        self.source_uploaded = true;
+    }
+
+    fn encode(&self, encoder: &mut wgpu::CommandEncoder, workload: Workload) {
+        let (blitter, bind_group) = match workload {
+            Workload::FillRate => &self.fill,
+            Workload::Alu => &self.alu,
+            Workload::Texture => &self.texture,
+            Workload::Compute => {
+                let mut compute_pass = encoder.begin_compute_pass();
+                compute_pass.set_pipeline(&self.compute_pipeline);
+                compute_pass.set_bind_group(0, &self.compute_bind_group, &[]);
+                for _ in 0..PASSES_PER_BATCH {
+                    compute_pass.dispatch(COMPUTE_INVOCATIONS / WORKGROUP_SIZE, 1, 1);
+                }
+                return;
+            }
+        };
+        for _ in 0..PASSES_PER_BATCH {
+            blitter.draw_over(encoder, bind_group, &self.target);
+        }
+    }
+
+    pub fn step(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        if self.start_requested {
+            self.start_requested = false;
+            let mut pending: Vec<Workload> = Workload::ALL
+                .iter()
+                .zip(self.settings.enabled.iter())
+                .filter(|(_, enabled)| **enabled)
+                .map(|(workload, _)| *workload)
+                .rev()
+                .collect();
+            self.run = pending.pop().map(|first| Run::begin(first, pending));
+        }
+        if self.cancel_requested {
+            self.cancel_requested = false;
+            self.run = None;
+        }
+        let workload = match &self.run {
+            Some(run) => run.workload,
+            None => return,
+        };
+        if !self.source_uploaded {
+            self.upload_source(queue);
+        }
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Benchmark Encoder"),
+        });
+        self.encode(&mut encoder, workload);
+        let time = std::time::Instant::now();
+        queue.submit(std::iter::once(encoder.finish()));
+        device.poll(wgpu::Maintain::Wait);
+        let elapsed = time.elapsed().as_secs_f64();
+
+        let duration = self.settings.duration as f64;
+        let run = self.run.as_mut().unwrap();
+        if run.warmed_up {
+            run.passes += PASSES_PER_BATCH;
+            run.seconds += elapsed;
+        } else {
+            run.warmed_up = true;
+        }
+        if run.started.elapsed().as_secs_f64() < duration {
+            return;
+        }
+        let score = Score {
+            workload,
+            passes: run.passes,
+            seconds: run.seconds,
+        };
+        log::info!(
+            "benchmark {}: {:.2} {} over {} passes",
+            workload.name(),
+            score.value(),
+            workload.unit(),
+            score.passes
+        );
+        let index = Workload::ALL.iter().position(|w| *w == workload).unwrap();
+        self.best[index] = self.best[index].max(score.value());
+        self.results.push(score);
+        let mut pending = std::mem::take(&mut run.pending);
+        self.run = pending.pop().map(|next| Run::begin(next, pending));
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        let mut open = self.open;
+        let running = self.run.as_ref().map(|run| {
+            (
+                run.workload,
+                run.started.elapsed().as_secs_f32(),
+                run.pending.len(),
+            )
+        });
+        let settings = &mut self.settings;
+        let start_requested = &mut self.start_requested;
+        let cancel_requested = &mut self.cancel_requested;
+        let results = &self.results;
+        let best = &self.best;
+        let mut clear_requested = false;
+        crate::dock::Panel::new("GPU Benchmark")
+            .open(&mut open)
+            .default_pos(egui::pos2(360.0, 120.0))
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "Each workload renders {} passes per batch into a {}x{} target.",
+                    PASSES_PER_BATCH, TARGET_SIZE, TARGET_SIZE
+                ));
+                ui.label("Batches are timed from submit until the device is idle.");
+                for (workload, enabled) in Workload::ALL.iter().zip(settings.enabled.iter_mut()) {
+                    ui.checkbox(
+                        enabled,
+                        format!("{} ({})", workload.name(), workload.unit()),
+                    );
+                }
+                ui.add(
+                    egui::Slider::f32(&mut settings.duration, 0.5..=10.0)
+                        .text("seconds per workload"),
+                );
+                match running {
+                    Some((workload, elapsed, pending)) => {
+                        ui.label(format!(
+                            "Running {} ({:.1} / {:.1} s, {} more queued)",
+                            workload.name(),
+                            elapsed,
+                            settings.duration,
+                            pending
+                        ));
+                        if ui.button("Cancel").clicked {
+                            *cancel_requested = true;
+                        }
+                    }
+                    None => {
+                        ui.horizontal(|ui| {
+                            if ui.button("Run").clicked {
+                                *start_requested = true;
+                            }
+                            if !results.is_empty() && ui.button("Clear results").clicked {
+                                clear_requested = true;
+                            }
+                        });
+                    }
+                }
+
+                if !results.is_empty() {
+                    ui.separator();
+                    for score in results.iter().rev().take(12) {
+                        let index = Workload::ALL
+                            .iter()
+                            .position(|w| *w == score.workload)
+                            .unwrap();
+                        let value = score.value();
+                        ui.label(format!(
+                            "{}: {:.2} {} ({} passes in {:.2} s)",
+                            score.workload.name(),
+                            value,
+                            score.workload.unit(),
+                            score.passes,
+                            score.seconds
+                        ));
+                        let rect = ui.allocate_space(egui::vec2(240.0, 6.0));
+                        let fraction = if best[index] > 0.0 {
+                            (value / best[index]) as f32
+                        } else {
+                            0.0
+                        };
+                        ui.painter()
+                            .rect_filled(rect, 2.0, egui::Srgba::new(60, 60, 60, 255));
+                        ui.painter().rect_filled(
+                            egui::Rect::from_min_size(
+                                rect.min,
+                                egui::vec2(rect.width() * fraction, rect.height()),
+                            ),
+                            2.0,
+                            egui::Srgba::new(90, 170, 250, 255),
+                        );
+                    }
+                }
+            });
+        if clear_requested {
+            self.results.clear();
+            self.best = [0.0; 4];
+        }
+        self.open = open;
+    }
+}
@@ -0,0 +1,342 @@
+pub struct Blitter {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+}
+
+impl Blitter {
+    pub fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        name: &str,
+        fragment: &str,
+    ) -> Self {
+        Self::with_blend(
+            device,
+            format,
+            name,
+            fragment,
+            wgpu::BlendDescriptor::REPLACE,
+        )
+    }
+
+    pub fn with_blend(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        name: &str,
+        fragment: &str,
+        color_blend: wgpu::BlendDescriptor,
+    ) -> Self {
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Blit Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::SampledTexture {
+                        dimension: wgpu::TextureViewDimension::D2,
+                        component_type: wgpu::TextureComponentType::Float,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler { comparison: false },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline = fullscreen_pipeline(
+            device,
+            name,
+            &[&bind_group_layout],
+            name,
+            fragment,
+            format,
+            color_blend,
+        );
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Blit Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+            sampler,
+        }
+    }
+
+    pub fn bind(&self, device: &wgpu::Device, source: &wgpu::TextureView) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Blit Bind Group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(source),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        })
+    }
+
+    pub fn draw(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        bind_group: &wgpu::BindGroup,
+        target: &wgpu::TextureView,
+    ) {
+        self.encode(
+            encoder,
+            bind_group,
+            target,
+            wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+            None,
+        );
+    }
+
+    pub fn draw_over(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        bind_group: &wgpu::BindGroup,
+        target: &wgpu::TextureView,
+    ) {
+        self.encode(encoder, bind_group, target, wgpu::LoadOp::Load, None);
+    }
+
+    pub fn draw_in(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        bind_group: &wgpu::BindGroup,
+        target: &wgpu::TextureView,
+        viewport: egui::Rect,
+    ) {
+        self.encode(
+            encoder,
+            bind_group,
+            target,
+            wgpu::LoadOp::Load,
+            Some(viewport),
+        );
+    }
+
+    fn encode(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        bind_group: &wgpu::BindGroup,
+        target: &wgpu::TextureView,
+        load: wgpu::LoadOp<wgpu::Color>,
+        viewport: Option<egui::Rect>,
+    ) {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: target,
+                resolve_target: None,
+                ops: wgpu::Operations { load, store: true },
+            }],
+            depth_stencil_attachment: None,
+        });
+        if let Some(viewport) = viewport {
+            set_viewport(&mut render_pass, viewport);
+        }
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+pub struct Canvas {
+    format: wgpu::TextureFormat,
+    target: Option<(winit::dpi::PhysicalSize<u32>, wgpu::TextureView, usize)>,
+}
+
+impl Canvas {
+    pub fn new(format: wgpu::TextureFormat) -> Self {
+        Self {
+            format,
+            target: None,
+        }
+    }
+
+    pub fn begin(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        ui_pass: &mut crate::ui_pass::RenderPass,
+        size: winit::dpi::PhysicalSize<u32>,
+    ) -> &wgpu::TextureView {
+        if self.target.as_ref().map(|(target_size, _, _)| *target_size) != Some(size) {
+            let view = device
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: Some("Canvas"),
+                    size: wgpu::Extent3d {
+                        width: size.width.max(1),
+                        height: size.height.max(1),
+                        depth: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: self.format,
+                    usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::SAMPLED,
+                })
+                .create_view(&wgpu::TextureViewDescriptor::default());
+            let id = match self.target.take() {
+                Some((_, _, id)) => {
+                    ui_pass.update_native_texture(device, id, &view);
+                    id
+                }
+                None => ui_pass.register_native_texture(device, &view),
+            };
+            self.target = Some((size, view, id));
+        }
+        let (_, view, _) = self.target.as_ref().unwrap();
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+        view
+    }
+
+    pub fn paint(&self, ui: &egui::Ui, rect: egui::Rect) {
+        let (size, _, id) = match &self.target {
+            Some(target) => target,
+            None => return,
+        };
+        let physical = to_physical(rect, ui.ctx().pixels_per_point() as f64);
+        let screen = egui::vec2(size.width as f32, size.height as f32);
+        crate::ui_pass::paint_user_texture_region(
+            ui.painter(),
+            *id,
+            rect,
+            egui::Rect::from_min_max(
+                egui::pos2(physical.min.x / screen.x, physical.min.y / screen.y),
+                egui::pos2(physical.max.x / screen.x, physical.max.y / screen.y),
+            ),
+            egui::color::WHITE,
+        );
+    }
+}
+
+pub fn to_physical(rect: egui::Rect, scale_factor: f64) -> egui::Rect {
+    let scale = scale_factor as f32;
+    egui::Rect::from_min_max(
+        egui::pos2(rect.min.x * scale, rect.min.y * scale),
+        egui::pos2(rect.max.x * scale, rect.max.y * scale),
+    )
+}
+
+pub fn fits(rect: egui::Rect, size: winit::dpi::PhysicalSize<u32>) -> bool {
+    let screen = egui::Rect::from_min_size(
+        egui::Pos2::default(),
+        egui::vec2(size.width as f32, size.height as f32),
+    );
+    rect.width() >= 1.0
+        && rect.height() >= 1.0
+        && screen.contains(rect.min)
+        && screen.contains(rect.max)
+}
+
+pub fn set_viewport(render_pass: &mut wgpu::RenderPass, viewport: egui::Rect) {
+    render_pass.set_viewport(
+        viewport.min.x,
+        viewport.min.y,
+        viewport.width(),
+        viewport.height(),
+        0.0,
+        1.0,
+    );
+}
+
+pub fn fullscreen_pipeline(
+    device: &wgpu::Device,
+    label: &str,
+    bind_group_layouts: &[&wgpu::BindGroupLayout],
+    fragment_name: &str,
+    fragment: &str,
+    format: wgpu::TextureFormat,
+    color_blend: wgpu::BlendDescriptor,
+) -> wgpu::RenderPipeline {
+    let fs_module = crate::shader::compile_builtin(
+        device,
+        fragment_name,
+        fragment,
+        shaderc::ShaderKind::Fragment,
+    );
+    fullscreen_pipeline_with_module(
+        device,
+        label,
+        bind_group_layouts,
+        &fs_module,
+        format,
+        color_blend,
+    )
+}
+
+pub fn fullscreen_pipeline_with_module(
+    device: &wgpu::Device,
+    label: &str,
+    bind_group_layouts: &[&wgpu::BindGroupLayout],
+    fs_module: &wgpu::ShaderModule,
+    format: wgpu::TextureFormat,
+    color_blend: wgpu::BlendDescriptor,
+) -> wgpu::RenderPipeline {
+    let vs_module = crate::shader::compile_builtin(
+        device,
+        "fullscreen.vert",
+        include_str!("shaders/fullscreen.vert"),
+        shaderc::ShaderKind::Vertex,
+    );
+
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some(label),
+        bind_group_layouts,
+        push_constant_ranges: &[],
+    });
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(&pipeline_layout),
+        vertex_stage: wgpu::ProgrammableStageDescriptor {
+            module: &vs_module,
+            entry_point: "main",
+        },
+        fragment_stage: Some(wgpu::ProgrammableStageDescriptor {
+            module: fs_module,
+            entry_point: "main",
+        }),
+        rasterization_state: Some(wgpu::RasterizationStateDescriptor::default()),
+        primitive_topology: wgpu::PrimitiveTopology::TriangleList,
+        color_states: &[wgpu::ColorStateDescriptor {
+            format,
+            color_blend,
+            alpha_blend: wgpu::BlendDescriptor::REPLACE,
+            write_mask: wgpu::ColorWrite::ALL,
+        }],
+        depth_stencil_state: None,
+        vertex_state: wgpu::VertexStateDescriptor {
+            index_format: wgpu::IndexFormat::Uint16,
+            vertex_buffers: &[],
+        },
+        sample_count: 1,
+        sample_mask: !0,
+        alpha_to_coverage_enabled: false,
+    })
+}
@@ -0,0 +1,357 @@
+const MAX_LEVELS: usize = 6;
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    threshold: f32,
+    knee: f32,
+    intensity: f32,
+    padding: f32,
+}
+
+pub struct Settings {
+    pub enabled: bool,
+    pub threshold: f32,
+    pub knee: f32,
+    pub intensity: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold: 1.0,
+            knee: 0.5,
+            intensity: 0.6,
+        }
+    }
+}
+
+struct Chain {
+    levels: Vec<wgpu::TextureView>,
+    prefilter: wgpu::BindGroup,
+    downsample: Vec<wgpu::BindGroup>,
+    upsample: Vec<wgpu::BindGroup>,
+    combine: Option<wgpu::BindGroup>,
+}
+
+pub struct Bloom {
+    pub settings: Settings,
+    pub timings: Vec<(String, f32)>,
+    layout: wgpu::BindGroupLayout,
+    uniform_buffer: wgpu::Buffer,
+    sampler: wgpu::Sampler,
+    prefilter_pipeline: wgpu::RenderPipeline,
+    downsample_pipeline: wgpu::RenderPipeline,
+    upsample_pipeline: wgpu::RenderPipeline,
+    combine_pipeline: wgpu::RenderPipeline,
+    chain: Chain,
+}
+
+impl Bloom {
+    pub fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        size: winit::dpi::PhysicalSize<u32>,
+        source: &wgpu::TextureView,
+    ) -> Self {
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Bloom Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::SampledTexture {
+                        dimension: wgpu::TextureViewDimension::D2,
+                        component_type: wgpu::TextureComponentType::Float,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler { comparison: false },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::UniformBuffer {
+                        dynamic: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let additive = wgpu::BlendDescriptor {
+            src_factor: wgpu::BlendFactor::One,
+            dst_factor: wgpu::BlendFactor::One,
+            operation: wgpu::BlendOperation::Add,
+        };
+        let prefilter_pipeline = crate::blit::fullscreen_pipeline(
+            device,
+            "Bloom Prefilter Pipeline",
+            &[&layout],
+            "bloom_prefilter.frag",
+            include_str!("shaders/bloom_prefilter.frag"),
+            format,
+            wgpu::BlendDescriptor::REPLACE,
+        );
+        let downsample_pipeline = crate::blit::fullscreen_pipeline(
+            device,
+            "Bloom Downsample Pipeline",
+            &[&layout],
+            "bloom_downsample.frag",
+            include_str!("shaders/bloom_downsample.frag"),
+            format,
+            wgpu::BlendDescriptor::REPLACE,
+        );
+        let upsample_pipeline = crate::blit::fullscreen_pipeline(
+            device,
+            "Bloom Upsample Pipeline",
+            &[&layout],
+            "bloom_upsample.frag",
+            include_str!("shaders/bloom_upsample.frag"),
+            format,
+            additive.clone(),
+        );
+        let combine_pipeline = crate::blit::fullscreen_pipeline(
+            device,
+            "Bloom Combine Pipeline",
+            &[&layout],
+            "bloom_combine.frag",
+            include_str!("shaders/bloom_combine.frag"),
+            format,
+            additive,
+        );
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Bloom Uniforms"),
+            size: std::mem::size_of::<Uniforms>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Bloom Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let chain = create_chain(
+            device,
+            &layout,
+            &sampler,
+            &uniform_buffer,
+            format,
+            size,
+            source,
+        );
+
+        Self {
+            settings: Settings::default(),
+            timings: Vec::new(),
+            layout,
+            uniform_buffer,
+            sampler,
+            prefilter_pipeline,
+            downsample_pipeline,
+            upsample_pipeline,
+            combine_pipeline,
+            chain,
+        }
+    }
+
+    pub fn resize(
+        &mut self,
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        size: winit::dpi::PhysicalSize<u32>,
+        source: &wgpu::TextureView,
+    ) {
+        self.chain = create_chain(
+            device,
+            &self.layout,
+            &self.sampler,
+            &self.uniform_buffer,
+            format,
+            size,
+            source,
+        );
+    }
+
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+    ) {
+        self.timings.clear();
+        let combine = match &self.chain.combine {
+            Some(combine) if self.settings.enabled => combine,
+            _ => return,
+        };
+
+        let uniforms = Uniforms {
+            threshold: self.settings.threshold,
+            knee: self.settings.knee.max(1e-3),
+            intensity: self.settings.intensity,
+            padding: 0.0,
+        };
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+
+        let start = std::time::Instant::now();
+        draw(
+            encoder,
+            &self.prefilter_pipeline,
+            &self.chain.prefilter,
+            &self.chain.levels[0],
+            true,
+        );
+        self.timings
+            .push(("prefilter".to_owned(), start.elapsed().as_secs_f32()));
+
+        let start = std::time::Instant::now();
+        for (level, bind_group) in self.chain.downsample.iter().enumerate() {
+            draw(
+                encoder,
+                &self.downsample_pipeline,
+                bind_group,
+                &self.chain.levels[level + 1],
+                true,
+            );
+        }
+        self.timings
+            .push(("downsample".to_owned(), start.elapsed().as_secs_f32()));
+
+        let start = std::time::Instant::now();
+        for (level, bind_group) in self.chain.upsample.iter().enumerate().rev() {
+            draw(
+                encoder,
+                &self.upsample_pipeline,
+                bind_group,
+                &self.chain.levels[level],
+                false,
+            );
+        }
+        self.timings
+            .push(("upsample".to_owned(), start.elapsed().as_secs_f32()));
+
+        let start = std::time::Instant::now();
+        draw(encoder, &self.combine_pipeline, combine, target, false);
+        self.timings
+            .push(("combine".to_owned(), start.elapsed().as_secs_f32()));
+    }
+
+    pub fn levels(&self) -> usize {
+        self.chain.levels.len()
+    }
+
+    pub fn level_view(&self, level: usize) -> Option<&wgpu::TextureView> {
+        self.chain.levels.get(level)
+    }
+}
+
+fn draw(
+    encoder: &mut wgpu::CommandEncoder,
+    pipeline: &wgpu::RenderPipeline,
+    bind_group: &wgpu::BindGroup,
+    target: &wgpu::TextureView,
+    clear: bool,
+) {
+    let load = if clear {
+        wgpu::LoadOp::Clear(wgpu::Color::BLACK)
+    } else {
+        wgpu::LoadOp::Load
+    };
+    let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+            attachment: target,
+            resolve_target: None,
+            ops: wgpu::Operations { load, store: true },
+        }],
+        depth_stencil_attachment: None,
+    });
+    render_pass.set_pipeline(pipeline);
+    render_pass.set_bind_group(0, bind_group, &[]);
+    render_pass.draw(0..3, 0..1);
+}
+
+fn create_chain(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    sampler: &wgpu::Sampler,
+    uniform_buffer: &wgpu::Buffer,
+    format: wgpu::TextureFormat,
+    size: winit::dpi::PhysicalSize<u32>,
+    source: &wgpu::TextureView,
+) -> Chain {
+    let bind = |view: &wgpu::TextureView| {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Bloom Bind Group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Buffer(uniform_buffer.slice(..)),
+                },
+            ],
+        })
+    };
+
+    let mut levels = Vec::new();
+    let mut level_size = winit::dpi::PhysicalSize::new(size.width / 2, size.height / 2);
+    while levels.len() < MAX_LEVELS && level_size.width >= 2 && level_size.height >= 2 {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(&format!("Bloom Level {}", levels.len())),
+            size: wgpu::Extent3d {
+                width: level_size.width,
+                height: level_size.height,
+                depth: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::SAMPLED,
+        });
+        levels.push(texture.create_view(&wgpu::TextureViewDescriptor::default()));
+        level_size = winit::dpi::PhysicalSize::new(level_size.width / 2, level_size.height / 2);
+    }
+
+    let prefilter = bind(source);
+    let downsample = levels[..levels.len().saturating_sub(1)]
+        .iter()
+        .map(&bind)
+        .collect();
+    let upsample = levels.iter().skip(1).map(&bind).collect();
+    let combine = levels.first().map(bind);
+
+    Chain {
+        levels,
+        prefilter,
+        downsample,
+        upsample,
+        combine,
+    }
+}
@@ -0,0 +1,416 @@
+pub const COUNTS: [u32; 4] = [1024, 2048, 4096, 8192];
+const WORKGROUP_SIZE: u32 = 64;
+const VIEW_SIZE: f32 = 360.0;
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Boid {
+    position: [f32; 2],
+    velocity: [f32; 2],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    counts: [u32; 4],
+    weights: [f32; 4],
+    radii: [f32; 4],
+}
+
+pub struct Settings {
+    pub count: u32,
+    pub cohesion: f32,
+    pub alignment: f32,
+    pub separation: f32,
+    pub perception: f32,
+    pub separation_distance: f32,
+    pub max_speed: f32,
+    pub paused: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            count: 2048,
+            cohesion: 0.6,
+            alignment: 1.0,
+            separation: 1.5,
+            perception: 0.1,
+            separation_distance: 0.03,
+            max_speed: 0.4,
+            paused: false,
+        }
+    }
+}
+
+struct Flock {
+    count: u32,
+    buffers: Vec<wgpu::Buffer>,
+    bind_groups: [wgpu::BindGroup; 2],
+}
+
+impl Flock {
+    fn new(
+        device: &wgpu::Device,
+        count: u32,
+        seed: u32,
+        layout: &wgpu::BindGroupLayout,
+        uniform_buffer: &wgpu::Buffer,
+    ) -> Self {
+        let mut state = seed | 1;
+        let mut random = || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as f32 / u32::MAX as f32 * 2.0 - 1.0
+        };
+        let boids: Vec<Boid> = (0..count)
+            .map(|_| Boid {
+                position: [random(), random()],
+                velocity: [random() * 0.1, random() * 0.1],
+            })
+            .collect();
+        let buffers: Vec<wgpu::Buffer> = (0..2)
+            .map(|_| {
+                wgpu::util::DeviceExt::create_buffer_init(
+                    device,
+                    &wgpu::util::BufferInitDescriptor {
+                        label: Some("Boids"),
+                        contents: bytemuck::cast_slice(&boids),
+                        usage: wgpu::BufferUsage::STORAGE | wgpu::BufferUsage::VERTEX,
+                    },
+                )
+            })
+            .collect();
+        let bind_group = |source: usize| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Boids Bind Group"),
+                layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::Buffer(uniform_buffer.slice(..)),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Buffer(buffers[source].slice(..)),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::Buffer(buffers[1 - source].slice(..)),
+                    },
+                ],
+            })
+        };
+        let bind_groups = [bind_group(0), bind_group(1)];
+
+        Self {
+            count,
+            buffers,
+            bind_groups,
+        }
+    }
+}
+
+pub struct Boids {
+    pub open: bool,
+    pub settings: Settings,
+    pub steps: u64,
+    reset_requested: bool,
+    viewport: Option<egui::Rect>,
+    canvas: crate::blit::Canvas,
+    last_frame: Option<std::time::Instant>,
+    seed: u32,
+    current: usize,
+    step_pipeline: wgpu::ComputePipeline,
+    render_pipeline: wgpu::RenderPipeline,
+    layout: wgpu::BindGroupLayout,
+    uniform_buffer: wgpu::Buffer,
+    shape_buffer: wgpu::Buffer,
+    flock: Flock,
+}
+
+impl Boids {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let settings = Settings::default();
+        let storage_entry = |binding, readonly| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStage::COMPUTE,
+            ty: wgpu::BindingType::StorageBuffer {
+                dynamic: false,
+                min_binding_size: None,
+                readonly,
+            },
+            count: None,
+        };
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Boids Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::COMPUTE,
+                    ty: wgpu::BindingType::UniformBuffer {
+                        dynamic: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                storage_entry(1, true),
+                storage_entry(2, false),
+            ],
+        });
+        let step_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Boids Step Pipeline Layout"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let cs_module = crate::shader::compile_builtin(
+            device,
+            "boids.comp",
+            include_str!("shaders/boids.comp"),
+            shaderc::ShaderKind::Compute,
+        );
+        let step_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Boids Step Pipeline"),
+            layout: Some(&step_layout),
+            compute_stage: wgpu::ProgrammableStageDescriptor {
+                module: &cs_module,
+                entry_point: "main",
+            },
+        });
+
+        let render_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Boids Render Pipeline Layout"),
+            bind_group_layouts: &[],
+            push_constant_ranges: &[],
+        });
+        let vs_module = crate::shader::compile_builtin(
+            device,
+            "boids.vert",
+            include_str!("shaders/boids.vert"),
+            shaderc::ShaderKind::Vertex,
+        );
+        let fs_module = crate::shader::compile_builtin(
+            device,
+            "boids.frag",
+            include_str!("shaders/boids.frag"),
+            shaderc::ShaderKind::Fragment,
+        );
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Boids Render Pipeline"),
+            layout: Some(&render_layout),
+            vertex_stage: wgpu::ProgrammableStageDescriptor {
+                module: &vs_module,
+                entry_point: "main",
+            },
+            fragment_stage: Some(wgpu::ProgrammableStageDescriptor {
+                module: &fs_module,
+                entry_point: "main",
+            }),
+            rasterization_state: Some(wgpu::RasterizationStateDescriptor::default()),
+            primitive_topology: wgpu::PrimitiveTopology::TriangleList,
+            color_states: &[wgpu::ColorStateDescriptor {
+                format,
+                color_blend: wgpu::BlendDescriptor::REPLACE,
+                alpha_blend: wgpu::BlendDescriptor::REPLACE,
+                write_mask: wgpu::ColorWrite::ALL,
+            }],
+            depth_stencil_state: None,
+            vertex_state: wgpu::VertexStateDescriptor {
+                index_format: wgpu::IndexFormat::Uint16,
+                vertex_buffers: &[
+                    wgpu::VertexBufferDescriptor {
+                        stride: std::mem::size_of::<Boid>() as wgpu::BufferAddress,
+                        step_mode: wgpu::InputStepMode::Instance,
+                        attributes: &wgpu::vertex_attr_array![0 => Float2, 1 => Float2],
+                    },
+                    wgpu::VertexBufferDescriptor {
+                        stride: std::mem::size_of::<[f32; 2]>() as wgpu::BufferAddress,
+                        step_mode: wgpu::InputStepMode::Vertex,
+                        attributes: &wgpu::vertex_attr_array![2 => Float2],
+                    },
+                ],
+            },
+            sample_count: 1,
+            sample_mask: !0,
+            alpha_to_coverage_enabled: false,
+        });
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Boids Uniforms"),
+            size: std::mem::size_of::<Uniforms>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let shape: [[f32; 2]; 3] = [[0.012, 0.0], [-0.008, 0.006], [-0.008, -0.006]];
+        let shape_buffer = wgpu::util::DeviceExt::create_buffer_init(
+            device,
+            &wgpu::util::BufferInitDescriptor {
+                label: Some("Boid Shape"),
+                contents: bytemuck::cast_slice(&shape),
+                usage: wgpu::BufferUsage::VERTEX,
+            },
+        );
+        let seed = 1;
+        let flock = Flock::new(device, settings.count, seed, &layout, &uniform_buffer);
+
+        Self {
+            open: false,
+            settings,
+            steps: 0,
+            reset_requested: false,
+            viewport: None,
+            canvas: crate::blit::Canvas::new(format),
+            last_frame: None,
+            seed,
+            current: 0,
+            step_pipeline,
+            render_pipeline,
+            layout,
+            uniform_buffer,
+            shape_buffer,
+            flock,
+        }
+    }
+
+    pub fn recreate_gpu_resources(&mut self, device: &wgpu::Device, format: wgpu::TextureFormat) {
+        let settings = std::mem::take(&mut self.settings);
+        let open = self.open;
+        *self = Self::new(device, format);
+        self.settings = settings;
+        self.open = open;
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        let mut open = self.open;
+        let mut viewport = None;
+        let steps = self.steps;
+        let settings = &mut self.settings;
+        let reset_requested = &mut self.reset_requested;
+        let canvas = &self.canvas;
+        crate::dock::Panel::new("Boids")
+            .open(&mut open)
+            .default_pos(egui::pos2(420.0, 60.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Agents");
+                    for count in COUNTS.iter() {
+                        ui.radio_value(&mut settings.count, *count, count.to_string());
+                    }
+                });
+                ui.add(egui::Slider::f32(&mut settings.cohesion, 0.0..=3.0).text("cohesion"));
+                ui.add(egui::Slider::f32(&mut settings.alignment, 0.0..=3.0).text("alignment"));
+                ui.add(egui::Slider::f32(&mut settings.separation, 0.0..=3.0).text("separation"));
+                ui.add(egui::Slider::f32(&mut settings.perception, 0.02..=0.3).text("perception"));
+                ui.add(
+                    egui::Slider::f32(&mut settings.separation_distance, 0.005..=0.1)
+                        .text("separation distance"),
+                );
+                ui.add(egui::Slider::f32(&mut settings.max_speed, 0.05..=1.0).text("max speed"));
+                ui.horizontal(|ui| {
+                    let pause = if settings.paused { "Resume" } else { "Pause" };
+                    if ui.button(pause).clicked {
+                        settings.paused = !settings.paused;
+                    }
+                    if ui.button("Reset").clicked {
+                        *reset_requested = true;
+                    }
+                });
+                ui.label(format!("{} steps simulated on the GPU", steps));
+                let rect = ui.allocate_space(egui::vec2(VIEW_SIZE, VIEW_SIZE));
+                canvas.paint(ui, rect);
+                viewport = Some(rect);
+            });
+        self.open = open;
+        self.viewport = viewport.filter(|_| open);
+    }
+
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        ui_pass: &mut crate::ui_pass::RenderPass,
+        size: winit::dpi::PhysicalSize<u32>,
+        scale_factor: f64,
+    ) {
+        let now = std::time::Instant::now();
+        let dt = self
+            .last_frame
+            .map(|last| (now - last).as_secs_f32())
+            .unwrap_or(0.0)
+            .min(0.05);
+        self.last_frame = Some(now);
+        let viewport = match self.viewport {
+            Some(rect) => crate::blit::to_physical(rect, scale_factor),
+            None => return,
+        };
+
+        if self.reset_requested || self.flock.count != self.settings.count {
+            self.seed = self
+                .seed
+                .wrapping_mul(1_664_525)
+                .wrapping_add(1_013_904_223);
+            self.flock = Flock::new(
+                device,
+                self.settings.count,
+                self.seed,
+                &self.layout,
+                &self.uniform_buffer,
+            );
+            self.current = 0;
+            self.steps = 0;
+            self.reset_requested = false;
+        }
+
+        if !self.settings.paused && dt > 0.0 {
+            let settings = &self.settings;
+            let uniforms = Uniforms {
+                counts: [self.flock.count, 0, 0, 0],
+                weights: [
+                    settings.cohesion,
+                    settings.alignment,
+                    settings.separation,
+                    settings.max_speed,
+                ],
+                radii: [settings.perception, settings.separation_distance, dt, 0.0],
+            };
+            uploader.write_buffer(
+                device,
+                encoder,
+                &self.uniform_buffer,
+                0,
+                bytemuck::bytes_of(&uniforms),
+            );
+            {
+                let mut compute_pass = encoder.begin_compute_pass();
+                compute_pass.set_pipeline(&self.step_pipeline);
+                compute_pass.set_bind_group(0, &self.flock.bind_groups[self.current], &[]);
+                compute_pass.dispatch(self.flock.count.div_ceil(WORKGROUP_SIZE), 1, 1);
+            }
+            self.current = 1 - self.current;
+            self.steps += 1;
+        }
+
+        if !crate::blit::fits(viewport, size) {
+            return;
+        }
+        let target = self.canvas.begin(device, encoder, ui_pass, size);
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+        crate::blit::set_viewport(&mut render_pass, viewport);
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.set_vertex_buffer(0, self.flock.buffers[self.current].slice(..));
+        render_pass.set_vertex_buffer(1, self.shape_buffer.slice(..));
+        render_pass.draw(0..3, 0..self.flock.count);
+    }
+}
@@ -0,0 +1,230 @@
+const FLIGHT_DURATION: f32 = 0.8;
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct Bookmark {
+    pub name: String,
+    pub target: [f32; 3],
+    pub distance: f32,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+impl Bookmark {
+    fn from_camera(name: String, camera: &crate::scene::Camera) -> Self {
+        Self {
+            name,
+            target: camera.target.into(),
+            distance: camera.distance,
+            yaw: camera.yaw,
+            pitch: camera.pitch,
+        }
+    }
+}
+
+struct Flight {
+    from: Bookmark,
+    to: Bookmark,
+    elapsed: f32,
+}
+
+pub fn key_index(key: winit::event::VirtualKeyCode) -> Option<usize> {
+    let keys = [
+        winit::event::VirtualKeyCode::Key1,
+        winit::event::VirtualKeyCode::Key2,
+        winit::event::VirtualKeyCode::Key3,
+        winit::event::VirtualKeyCode::Key4,
+        winit::event::VirtualKeyCode::Key5,
+        winit::event::VirtualKeyCode::Key6,
+        winit::event::VirtualKeyCode::Key7,
+        winit::event::VirtualKeyCode::Key8,
+        winit::event::VirtualKeyCode::Key9,
+    ];
+    keys.iter().position(|candidate| *candidate == key)
+}
+
+fn orientation(yaw: f32, pitch: f32) -> glam::Quat {
+    glam::Quat::from_rotation_y(yaw) * glam::Quat::from_rotation_x(-pitch)
+}
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Bookmarks {
+    pub items: Vec<Bookmark>,
+    #[serde(skip)]
+    flight: Option<Flight>,
+    #[serde(skip)]
+    last_frame: Option<std::time::Instant>,
+}
+
+impl Bookmarks {
+    fn path() -> Option<std::path::PathBuf> {
+        dirs::config_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")).join("bookmarks.json"))
+    }
+
+    pub fn load() -> Self {
+        let path = match Self::path() {
+            Some(path) => path,
+            None => return Self::default(),
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|err| {
+                log::warn!("ignoring invalid bookmarks in {}: {}", path.display(), err);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self) {
+        let path = match Self::path() {
+            Some(path) => path,
+            None => return,
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&path, serde_json::to_string_pretty(self).unwrap()));
+        match result {
+            Ok(()) => log::info!("saved {} bookmarks to {}", self.items.len(), path.display()),
+            Err(err) => log::warn!("cannot save bookmarks to {}: {}", path.display(), err),
+        }
+    }
+
+    pub fn add(&mut self, name: String, camera: &crate::scene::Camera) {
+        self.items.push(Bookmark::from_camera(name, camera));
+    }
+
+    pub fn fly_to(&mut self, index: usize, camera: &crate::scene::Camera) {
+        if let Some(to) = self.items.get(index) {
+            self.flight = Some(Flight {
+                from: Bookmark::from_camera(String::new(), camera),
+                to: to.clone(),
+                elapsed: 0.0,
+            });
+        }
+    }
+
+    pub fn is_flying(&self) -> bool {
+        self.flight.is_some()
+    }
+
+    pub fn update(&mut self, camera: &mut crate::scene::Camera) {
+        let now = std::time::Instant::now();
+        let dt = self
+            .last_frame
+            .map(|last| (now - last).as_secs_f32())
+            .unwrap_or(0.0);
+        self.last_frame = Some(now);
+
+        let flight = match &mut self.flight {
+            Some(flight) => flight,
+            None => return,
+        };
+        flight.elapsed += dt;
+        let t = (flight.elapsed / FLIGHT_DURATION).min(1.0);
+        let t = t * t * (3.0 - 2.0 * t);
+        let (from, to) = (&flight.from, &flight.to);
+
+        camera.target = glam::Vec3::from(from.target).lerp(glam::Vec3::from(to.target), t);
+        camera.distance = from.distance + (to.distance - from.distance) * t;
+        let rotation = orientation(from.yaw, from.pitch).slerp(orientation(to.yaw, to.pitch), t);
+        let direction = rotation * glam::Vec3::unit_z();
+        camera.yaw = direction.x.atan2(direction.z);
+        camera.pitch = direction.y.clamp(-1.0, 1.0).asin();
+
+        if flight.elapsed >= FLIGHT_DURATION {
+            camera.yaw = to.yaw;
+            camera.pitch = to.pitch;
+            self.flight = None;
+        }
+    }
+}
+
+pub struct BookmarksPanel {
+    pub open: bool,
+    name: String,
+}
+
+impl Default for BookmarksPanel {
+    fn default() -> Self {
+        Self {
+            open: false,
+            name: "Bookmark 1".to_owned(),
+        }
+    }
+}
+
+impl BookmarksPanel {
+    pub fn show(
+        &mut self,
+        ctx: &std::sync::Arc<egui::Context>,
+        bookmarks: &mut Bookmarks,
+        scene: &mut crate::scene::Scene,
+    ) {
+        let name = &mut self.name;
+        crate::dock::Panel::new("Camera Bookmarks")
+            .open(&mut self.open)
+            .default_pos(egui::pos2(560.0, 80.0))
+            .show(ctx, |ui| {
+                egui::CollapsingHeader::new("Current view").show(ui, |ui| {
+                    let camera = &mut scene.camera;
+                    ui.add(
+                        egui::Slider::f32(
+                            &mut camera.yaw,
+                            -std::f32::consts::PI..=std::f32::consts::PI,
+                        )
+                        .text("yaw"),
+                    );
+                    ui.add(egui::Slider::f32(&mut camera.pitch, -1.5..=1.5).text("pitch"));
+                    ui.add(egui::Slider::f32(&mut camera.distance, 1.0..=60.0).text("distance"));
+                    ui.add(egui::Slider::f32(&mut camera.target.x, -15.0..=15.0).text("target x"));
+                    ui.add(egui::Slider::f32(&mut camera.target.y, -5.0..=10.0).text("target y"));
+                    ui.add(egui::Slider::f32(&mut camera.target.z, -15.0..=15.0).text("target z"));
+                });
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::new(name));
+                    if ui.button("Save view").clicked && !name.is_empty() {
+                        bookmarks.add(name.clone(), &scene.camera);
+                        *name = format!("Bookmark {}", bookmarks.items.len() + 1);
+                    }
+                });
+                ui.separator();
+
+                let mut fly_to = None;
+                let mut removed = None;
+                egui::CollapsingHeader::new(format!("Bookmarks ({})", bookmarks.items.len()))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        for (index, bookmark) in bookmarks.items.iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                let key = if index < 9 {
+                                    format!("[{}] ", index + 1)
+                                } else {
+                                    String::new()
+                                };
+                                if ui.button(format!("{}{}", key, bookmark.name)).clicked {
+                                    fly_to = Some(index);
+                                }
+                                if ui.button("Remove").clicked {
+                                    removed = Some(index);
+                                }
+                            });
+                        }
+                    });
+                if let Some(index) = fly_to {
+                    bookmarks.fly_to(index, &scene.camera);
+                }
+                if let Some(index) = removed {
+                    bookmarks.items.remove(index);
+                }
+                if bookmarks.items.is_empty() {
+                    ui.label("No bookmarks saved");
+                } else {
+                    ui.label("Number keys 1-9 fly to a bookmark");
+                }
+                if bookmarks.is_flying() {
+                    ui.label("Flying...");
+                }
+            });
+    }
+}
@@ -0,0 +1,112 @@
+const MAX_IDLE_FRAMES: u64 = 300;
+
+struct Entry<T> {
+    value: T,
+    last_used: u64,
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct CacheStats {
+    pub pipelines: usize,
+    pub bind_groups: usize,
+    pub hits: u32,
+    pub misses: u32,
+    pub evicted: u32,
+}
+
+#[derive(Default)]
+pub struct Cache {
+    pub stats: CacheStats,
+    pipelines: std::collections::HashMap<u64, Entry<wgpu::RenderPipeline>>,
+    bind_groups: std::collections::HashMap<u64, Entry<wgpu::BindGroup>>,
+    frame: u64,
+    hits: u32,
+    misses: u32,
+}
+
+pub fn key(value: &impl std::hash::Hash) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    value.hash(&mut hasher);
+    std::hash::Hasher::finish(&hasher)
+}
+
+impl Cache {
+    pub fn pipeline(
+        &mut self,
+        key: u64,
+        create: impl FnOnce() -> wgpu::RenderPipeline,
+    ) -> &wgpu::RenderPipeline {
+        lookup(
+            &mut self.pipelines,
+            self.frame,
+            &mut self.hits,
+            &mut self.misses,
+            key,
+            create,
+        )
+    }
+
+    pub fn bind_group(
+        &mut self,
+        key: u64,
+        create: impl FnOnce() -> wgpu::BindGroup,
+    ) -> &wgpu::BindGroup {
+        lookup(
+            &mut self.bind_groups,
+            self.frame,
+            &mut self.hits,
+            &mut self.misses,
+            key,
+            create,
+        )
+    }
+
+    pub fn end_frame(&mut self) {
+        let frame = self.frame;
+        let before = self.pipelines.len() + self.bind_groups.len();
+        self.pipelines
+            .retain(|_, entry| frame - entry.last_used < MAX_IDLE_FRAMES);
+        self.bind_groups
+            .retain(|_, entry| frame - entry.last_used < MAX_IDLE_FRAMES);
+        self.stats = CacheStats {
+            pipelines: self.pipelines.len(),
+            bind_groups: self.bind_groups.len(),
+            hits: self.hits,
+            misses: self.misses,
+            evicted: (before - self.pipelines.len() - self.bind_groups.len()) as u32,
+        };
+        self.hits = 0;
+        self.misses = 0;
+        self.frame += 1;
+    }
+
+    pub fn clear(&mut self) {
+        self.pipelines.clear();
+        self.bind_groups.clear();
+    }
+}
+
+fn lookup<'a, T>(
+    entries: &'a mut std::collections::HashMap<u64, Entry<T>>,
+    frame: u64,
+    hits: &mut u32,
+    misses: &mut u32,
+    key: u64,
+    create: impl FnOnce() -> T,
+) -> &'a T {
+    let entry = match entries.entry(key) {
+        std::collections::hash_map::Entry::Occupied(entry) => {
+            *hits += 1;
+            entry.into_mut()
+        }
+        std::collections::hash_map::Entry::Vacant(entry) => {
+            *misses += 1;
+            entry.insert(Entry {
+                value: create(),
+                last_used: frame,
+            })
+        }
+    };
+    entry.last_used = frame;
+    &entry.value
+}
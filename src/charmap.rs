@@ -0,0 +1,323 @@
+const PAGE: u32 = 256;
+const COLUMNS: usize = 16;
+const CELL: f32 = 24.0;
+const MAX_RESULTS: usize = 256;
+const BLOCKS: [(&str, u32, u32); 27] = [
+    ("Basic Latin", 0x20, 0x7f),
+    ("Latin-1 Supplement", 0xa0, 0xff),
+    ("Latin Extended-A", 0x100, 0x17f),
+    ("Greek and Coptic", 0x370, 0x3ff),
+    ("Cyrillic", 0x400, 0x4ff),
+    ("Hebrew", 0x590, 0x5ff),
+    ("Arabic", 0x600, 0x6ff),
+    ("Devanagari", 0x900, 0x97f),
+    ("Thai", 0xe00, 0xe7f),
+    ("General Punctuation", 0x2000, 0x206f),
+    ("Currency Symbols", 0x20a0, 0x20cf),
+    ("Letterlike Symbols", 0x2100, 0x214f),
+    ("Arrows", 0x2190, 0x21ff),
+    ("Mathematical Operators", 0x2200, 0x22ff),
+    ("Miscellaneous Technical", 0x2300, 0x23ff),
+    ("Box Drawing", 0x2500, 0x257f),
+    ("Block Elements", 0x2580, 0x259f),
+    ("Geometric Shapes", 0x25a0, 0x25ff),
+    ("Miscellaneous Symbols", 0x2600, 0x26ff),
+    ("Dingbats", 0x2700, 0x27bf),
+    ("CJK Symbols and Punctuation", 0x3000, 0x303f),
+    ("Hiragana", 0x3040, 0x309f),
+    ("Katakana", 0x30a0, 0x30ff),
+    ("CJK Unified Ideographs", 0x4e00, 0x9fff),
+    ("Hangul Syllables", 0xac00, 0xd7af),
+    ("Miscellaneous Symbols and Pictographs", 0x1f300, 0x1f5ff),
+    ("Emoticons", 0x1f600, 0x1f64f),
+];
+
+struct Face {
+    name: String,
+    font: Option<rusttype::Font<'static>>,
+}
+
+impl Face {
+    fn new(name: String, data: &'static [u8]) -> Self {
+        let font = rusttype::Font::try_from_bytes(data);
+        if font.is_none() {
+            log::warn!("cannot parse {} for glyph lookup", name);
+        }
+        Self { name, font }
+    }
+
+    fn covers(&self, character: char) -> bool {
+        self.font
+            .as_ref()
+            .is_some_and(|font| font.glyph(character).id().0 != 0)
+    }
+}
+
+struct Chain {
+    proportional: Vec<Face>,
+    monospace: Vec<Face>,
+}
+
+impl Chain {
+    fn supplier(faces: &[Face], character: char) -> &str {
+        faces
+            .iter()
+            .find(|face| face.covers(character))
+            .map_or("no font (shown as a replacement box)", |face| {
+                face.name.as_str()
+            })
+    }
+}
+
+#[derive(Default)]
+pub struct CharMap {
+    pub open: bool,
+    block: usize,
+    page: u32,
+    query: String,
+    results: Option<Vec<char>>,
+    selected: Option<char>,
+    key: Vec<usize>,
+    chain: Option<Chain>,
+}
+
+fn describe(character: char) -> String {
+    unicode_names2::name(character)
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| "<unnamed>".to_owned())
+}
+
+impl CharMap {
+    fn refresh_chain(&mut self, ctx: &egui::Context, fonts: &crate::fonts::FontsPanel) {
+        let definitions = ctx.fonts().definitions().clone();
+        let family = |key| definitions.ttf_data.get(&key).copied().unwrap_or(&[][..]);
+        let proportional = family(egui::paint::fonts::FontFamily::VariableWidth);
+        let monospace = family(egui::paint::fonts::FontFamily::Monospace);
+        let key: Vec<usize> = [proportional, monospace]
+            .iter()
+            .map(|data| data.as_ptr() as usize)
+            .collect();
+        if key == self.key && self.chain.is_some() {
+            return;
+        }
+        self.key = key;
+        let primary = |label: &str, data: &'static [u8]| {
+            let name = fonts
+                .describe_font(data)
+                .unwrap_or_else(|| format!("built-in {} font", label));
+            Face::new(name, data)
+        };
+        let chain = Chain {
+            proportional: vec![primary("proportional", proportional)],
+            monospace: vec![primary("monospace", monospace)],
+        };
+        log::info!("glyph lookup chain has {} fonts", chain.proportional.len());
+        self.chain = Some(chain);
+    }
+
+    fn search(&mut self) {
+        let query = self.query.trim().to_uppercase();
+        if query.is_empty() {
+            self.results = None;
+            return;
+        }
+        let mut results = Vec::new();
+        let code = query.trim_start_matches("U+");
+        if !code.is_empty()
+            && code.len() <= 6
+            && code.chars().all(|digit| digit.is_ascii_hexdigit())
+        {
+            if let Some(character) = u32::from_str_radix(code, 16)
+                .ok()
+                .and_then(std::char::from_u32)
+            {
+                results.push(character);
+            }
+        }
+        for (_, start, end) in BLOCKS.iter() {
+            for character in (*start..=*end).filter_map(std::char::from_u32) {
+                if results.len() == MAX_RESULTS {
+                    break;
+                }
+                if unicode_names2::name(character)
+                    .is_some_and(|name| name.to_string().contains(&query))
+                {
+                    results.push(character);
+                }
+            }
+        }
+        log::info!("character search {:?} found {}", query, results.len());
+        self.results = Some(results);
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, fonts: &crate::fonts::FontsPanel) {
+        if !self.open {
+            return;
+        }
+        self.refresh_chain(ctx, fonts);
+
+        let (_, start, end) = BLOCKS[self.block];
+        let pages = (end - start) / PAGE + 1;
+        self.page = self.page.min(pages - 1);
+        let characters: Vec<char> = match &self.results {
+            Some(results) => results.clone(),
+            None => {
+                let first = start + self.page * PAGE;
+                (first..=end.min(first + PAGE - 1))
+                    .filter_map(std::char::from_u32)
+                    .collect()
+            }
+        };
+
+        let mut open = self.open;
+        let mut search_requested = false;
+        let mut clear_requested = false;
+        let block = &mut self.block;
+        let page = &mut self.page;
+        let query = &mut self.query;
+        let searching = self.results.is_some();
+        let selected = &mut self.selected;
+        let chain = &self.chain;
+        crate::dock::Panel::new("Character Map")
+            .open(&mut open)
+            .default_pos(egui::pos2(340.0, 100.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Name or U+code");
+                    ui.add(egui::TextEdit::new(query));
+                    if ui.button("Search").clicked {
+                        search_requested = true;
+                    }
+                    if searching && ui.button("Clear").clicked {
+                        clear_requested = true;
+                    }
+                });
+                if searching {
+                    ui.label(format!("{} matches", characters.len()));
+                } else {
+                    egui::CollapsingHeader::new(BLOCKS[*block].0).show(ui, |ui| {
+                        egui::ScrollArea::from_max_height(160.0).show(ui, |ui| {
+                            for (index, (name, start, end)) in BLOCKS.iter().enumerate() {
+                                let label = format!("{} (U+{:04X}..U+{:04X})", name, start, end);
+                                if ui.radio(*block == index, label).clicked {
+                                    *block = index;
+                                    *page = 0;
+                                }
+                            }
+                        });
+                    });
+                    if pages > 1 {
+                        ui.horizontal(|ui| {
+                            if ui.button("<").clicked && *page > 0 {
+                                *page -= 1;
+                            }
+                            ui.label(format!("page {} of {}", *page + 1, pages));
+                            if ui.button(">").clicked && *page + 1 < pages {
+                                *page += 1;
+                            }
+                        });
+                    }
+                }
+
+                let rows = characters.len().div_ceil(COLUMNS);
+                egui::ScrollArea::from_max_height(CELL * 10.0).show(ui, |ui| {
+                    let rect = ui.allocate_space(egui::vec2(
+                        CELL * COLUMNS as f32,
+                        CELL * rows.max(1) as f32,
+                    ));
+                    let painter = ui.painter();
+                    for (index, character) in characters.iter().enumerate() {
+                        let cell = egui::Rect::from_min_size(
+                            rect.min
+                                + egui::vec2(
+                                    (index % COLUMNS) as f32 * CELL,
+                                    (index / COLUMNS) as f32 * CELL,
+                                ),
+                            egui::vec2(CELL, CELL),
+                        );
+                        let response = ui.interact(
+                            cell,
+                            ui.make_persistent_id(("charmap_cell", *character)),
+                            egui::Sense::click(),
+                        );
+                        if response.clicked {
+                            *selected = Some(*character);
+                        }
+                        if *selected == Some(*character) {
+                            painter.rect_filled(cell, 2.0, egui::Srgba::new(60, 90, 140, 255));
+                        } else if response.hovered {
+                            painter.rect_filled(cell, 2.0, egui::Srgba::new(50, 50, 58, 255));
+                        }
+                        painter.text(
+                            cell.center(),
+                            (egui::Align::Center, egui::Align::Center),
+                            character.to_string(),
+                            egui::TextStyle::Body,
+                            egui::Srgba::new(230, 230, 230, 255),
+                        );
+                    }
+                });
+
+                if let Some(character) = *selected {
+                    ui.separator();
+                    let preview = ui.allocate_space(egui::vec2(CELL * 3.0, CELL * 2.0));
+                    ui.painter()
+                        .rect_filled(preview, 2.0, egui::Srgba::new(20, 20, 24, 255));
+                    ui.painter().text(
+                        preview.center(),
+                        (egui::Align::Center, egui::Align::Center),
+                        character.to_string(),
+                        egui::TextStyle::Heading,
+                        egui::Srgba::new(255, 255, 255, 255),
+                    );
+                    ui.add(
+                        egui::Label::new(format!(
+                            "{} {} {}",
+                            character,
+                            character.to_string().repeat(3),
+                            character
+                        ))
+                        .text_style(egui::TextStyle::Monospace),
+                    );
+                    ui.label(format!(
+                        "U+{:04X} {}",
+                        character as u32,
+                        describe(character)
+                    ));
+                    let mut utf8 = [0u8; 4];
+                    let bytes: Vec<String> = character
+                        .encode_utf8(&mut utf8)
+                        .bytes()
+                        .map(|byte| format!("{:02X}", byte))
+                        .collect();
+                    ui.label(format!("UTF-8: {}", bytes.join(" ")));
+                    if let Some(chain) = chain {
+                        ui.label(format!(
+                            "Proportional: {}",
+                            Chain::supplier(&chain.proportional, character)
+                        ));
+                        ui.label(format!(
+                            "Monospace: {}",
+                            Chain::supplier(&chain.monospace, character)
+                        ));
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.button("Copy character").clicked {
+                            ui.output().copied_text = character.to_string();
+                        }
+                        if ui.button("Copy code point").clicked {
+                            ui.output().copied_text = format!("U+{:04X}", character as u32);
+                        }
+                    });
+                }
+            });
+        if search_requested {
+            self.search();
+        }
+        if clear_requested {
+            self.results = None;
+            self.query.clear();
+        }
+        self.open = open;
+    }
+}
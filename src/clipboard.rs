@@ -0,0 +1,63 @@
+pub fn command(modifiers: winit::event::ModifiersState) -> bool {
+    if cfg!(target_os = "macos") {
+        modifiers.logo()
+    } else {
+        modifiers.ctrl()
+    }
+}
+
+pub struct Clipboard {
+    context: Option<copypasta::ClipboardContext>,
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        let context = match copypasta::ClipboardContext::new() {
+            Ok(context) => Some(context),
+            Err(err) => {
+                log::warn!("system clipboard is unavailable: {}", err);
+                None
+            }
+        };
+        Self { context }
+    }
+}
+
+impl Clipboard {
+    pub fn is_paste(
+        &self,
+        input: &winit::event::KeyboardInput,
+        modifiers: winit::event::ModifiersState,
+    ) -> bool {
+        if input.state != winit::event::ElementState::Pressed {
+            return false;
+        }
+        match input.virtual_keycode {
+            Some(winit::event::VirtualKeyCode::V) => command(modifiers),
+            Some(winit::event::VirtualKeyCode::Insert) => modifiers.shift(),
+            _ => false,
+        }
+    }
+
+    pub fn text(&mut self) -> Option<String> {
+        let context = self.context.as_mut()?;
+        match copypasta::ClipboardProvider::get_contents(context) {
+            Ok(text) => Some(text),
+            Err(err) => {
+                log::warn!("cannot read clipboard: {}", err);
+                None
+            }
+        }
+    }
+
+    pub fn set_text(&mut self, text: String) {
+        if text.is_empty() {
+            return;
+        }
+        if let Some(context) = self.context.as_mut() {
+            if let Err(err) = copypasta::ClipboardProvider::set_contents(context, text) {
+                log::warn!("cannot write clipboard: {}", err);
+            }
+        }
+    }
+}
@@ -0,0 +1,189 @@
+pub const CLUSTER_X: u32 = 16;
+pub const CLUSTER_Y: u32 = 9;
+pub const CLUSTER_Z: u32 = 24;
+pub const CLUSTER_COUNT: u32 = CLUSTER_X * CLUSTER_Y * CLUSTER_Z;
+pub const MAX_LIGHTS_PER_CLUSTER: u32 = 64;
+pub const MAX_LIGHTS: usize = 256;
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct GpuLight {
+    pub position_range: [f32; 4],
+    pub color_kind: [f32; 4],
+    pub direction_cos_outer: [f32; 4],
+    pub cos_inner: [f32; 4],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    inverse_projection: [f32; 16],
+    view: [f32; 16],
+    screen: [f32; 4],
+    counts: [u32; 4],
+}
+
+pub struct ClusteredLights {
+    pub heatmap: bool,
+    pipeline: wgpu::ComputePipeline,
+    bind_group: wgpu::BindGroup,
+    uniform_buffer: wgpu::Buffer,
+    pub light_buffer: wgpu::Buffer,
+    pub count_buffer: wgpu::Buffer,
+    pub index_buffer: wgpu::Buffer,
+}
+
+impl ClusteredLights {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let cs_module = crate::shader::compile_builtin(
+            device,
+            "cluster_lights.comp",
+            include_str!("shaders/cluster_lights.comp"),
+            shaderc::ShaderKind::Compute,
+        );
+
+        let storage_entry = |binding, readonly| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStage::COMPUTE,
+            ty: wgpu::BindingType::StorageBuffer {
+                dynamic: false,
+                min_binding_size: None,
+                readonly,
+            },
+            count: None,
+        };
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Light Clustering Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::COMPUTE,
+                    ty: wgpu::BindingType::UniformBuffer {
+                        dynamic: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                storage_entry(1, true),
+                storage_entry(2, false),
+                storage_entry(3, false),
+            ],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Light Clustering Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Light Clustering Pipeline"),
+            layout: Some(&pipeline_layout),
+            compute_stage: wgpu::ProgrammableStageDescriptor {
+                module: &cs_module,
+                entry_point: "main",
+            },
+        });
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Light Clustering Uniforms"),
+            size: std::mem::size_of::<Uniforms>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let light_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Local Lights"),
+            size: (MAX_LIGHTS * std::mem::size_of::<GpuLight>()) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::STORAGE | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let count_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Cluster Light Counts"),
+            size: (CLUSTER_COUNT as usize * std::mem::size_of::<u32>()) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::STORAGE,
+            mapped_at_creation: false,
+        });
+        let index_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Cluster Light Indices"),
+            size: (CLUSTER_COUNT as usize
+                * MAX_LIGHTS_PER_CLUSTER as usize
+                * std::mem::size_of::<u32>()) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Light Clustering Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(uniform_buffer.slice(..)),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Buffer(light_buffer.slice(..)),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Buffer(count_buffer.slice(..)),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::Buffer(index_buffer.slice(..)),
+                },
+            ],
+        });
+
+        Self {
+            heatmap: false,
+            pipeline,
+            bind_group,
+            uniform_buffer,
+            light_buffer,
+            count_buffer,
+            index_buffer,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        lights: &[GpuLight],
+        view: &glam::Mat4,
+        projection: &glam::Mat4,
+        size: winit::dpi::PhysicalSize<u32>,
+        near: f32,
+        far: f32,
+    ) {
+        let lights = &lights[..lights.len().min(MAX_LIGHTS)];
+        let uniforms = Uniforms {
+            inverse_projection: projection.inverse().to_cols_array(),
+            view: view.to_cols_array(),
+            screen: [size.width as f32, size.height as f32, near, far],
+            counts: [lights.len() as u32, 0, 0, 0],
+        };
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+        if !lights.is_empty() {
+            uploader.write_buffer(
+                device,
+                encoder,
+                &self.light_buffer,
+                0,
+                bytemuck::cast_slice(lights),
+            );
+        }
+
+        let mut compute_pass = encoder.begin_compute_pass();
+        compute_pass.set_pipeline(&self.pipeline);
+        compute_pass.set_bind_group(0, &self.bind_group, &[]);
+        compute_pass.dispatch(1, 1, CLUSTER_Z);
+    }
+}
@@ -0,0 +1,365 @@
+const PATTERN_SIZE: [f32; 2] = [384.0, 216.0];
+const MODE_SRGB: u32 = 0;
+const MODE_DISPLAY_P3: u32 = 1;
+const MODE_GAMMA: u32 = 2;
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    mode: u32,
+    gamma: f32,
+    padding: [f32; 2],
+}
+
+fn detect(name: &str) -> Option<crate::settings::ColorOutput> {
+    let name = name.to_lowercase();
+    let wide = [
+        "p3",
+        "retina",
+        "color lcd",
+        "pro display xdr",
+        "studio display",
+    ];
+    if wide.iter().any(|hint| name.contains(hint)) {
+        Some(crate::settings::ColorOutput::DisplayP3)
+    } else {
+        None
+    }
+}
+
+fn describe(output: crate::settings::ColorOutput) -> &'static str {
+    match output {
+        crate::settings::ColorOutput::Auto => "automatic",
+        crate::settings::ColorOutput::Srgb => "sRGB",
+        crate::settings::ColorOutput::DisplayP3 => "Display P3",
+        crate::settings::ColorOutput::Gamma => "custom gamma",
+    }
+}
+
+struct Target {
+    size: winit::dpi::PhysicalSize<u32>,
+    view: wgpu::TextureView,
+    bind_group: wgpu::BindGroup,
+}
+
+pub struct ColorManagement {
+    pub open: bool,
+    monitor: Option<String>,
+    detected: Option<crate::settings::ColorOutput>,
+    format: wgpu::TextureFormat,
+    layout: wgpu::BindGroupLayout,
+    pipeline: wgpu::RenderPipeline,
+    sampler: wgpu::Sampler,
+    uniform_buffer: wgpu::Buffer,
+    target: Option<Target>,
+    uniforms: Uniforms,
+    pattern_pipeline: wgpu::RenderPipeline,
+    viewport: Option<egui::Rect>,
+    canvas: crate::blit::Canvas,
+}
+
+impl ColorManagement {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Color Management Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::SampledTexture {
+                        dimension: wgpu::TextureViewDimension::D2,
+                        component_type: wgpu::TextureComponentType::Float,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler { comparison: false },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::UniformBuffer {
+                        dynamic: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let pipeline = crate::blit::fullscreen_pipeline(
+            device,
+            "Color Management Pipeline",
+            &[&layout],
+            "color_management.frag",
+            include_str!("shaders/color_management.frag"),
+            format,
+            wgpu::BlendDescriptor::REPLACE,
+        );
+        let pattern_pipeline = crate::blit::fullscreen_pipeline(
+            device,
+            "Test Pattern Pipeline",
+            &[],
+            "test_pattern.frag",
+            include_str!("shaders/test_pattern.frag"),
+            format,
+            wgpu::BlendDescriptor::REPLACE,
+        );
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Color Management Sampler"),
+            ..Default::default()
+        });
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Color Management Uniforms"),
+            size: std::mem::size_of::<Uniforms>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            open: false,
+            monitor: None,
+            detected: None,
+            format,
+            layout,
+            pipeline,
+            sampler,
+            uniform_buffer,
+            target: None,
+            uniforms: Uniforms {
+                mode: MODE_SRGB,
+                gamma: 2.2,
+                padding: [0.0; 2],
+            },
+            pattern_pipeline,
+            viewport: None,
+            canvas: crate::blit::Canvas::new(format),
+        }
+    }
+
+    pub fn recreate_gpu_resources(&mut self, device: &wgpu::Device, format: wgpu::TextureFormat) {
+        let open = self.open;
+        let monitor = self.monitor.take();
+        let detected = self.detected;
+        *self = Self::new(device, format);
+        self.open = open;
+        self.monitor = monitor;
+        self.detected = detected;
+    }
+
+    pub fn detect(&mut self, monitor: &winit::monitor::MonitorHandle) {
+        let name = monitor.name();
+        if name == self.monitor {
+            return;
+        }
+        self.detected = name.as_deref().and_then(detect);
+        log::info!(
+            "monitor {:?} uses {}",
+            name,
+            self.detected.map_or("an unreported color space", describe)
+        );
+        self.monitor = name;
+    }
+
+    fn resolve(&self, settings: &crate::settings::AppSettings) -> crate::settings::ColorOutput {
+        match settings.color_output {
+            crate::settings::ColorOutput::Auto => {
+                self.detected.unwrap_or(crate::settings::ColorOutput::Srgb)
+            }
+            output => output,
+        }
+    }
+
+    pub fn prepare(
+        &mut self,
+        device: &wgpu::Device,
+        size: winit::dpi::PhysicalSize<u32>,
+        settings: &crate::settings::AppSettings,
+    ) {
+        let mode = match self.resolve(settings) {
+            crate::settings::ColorOutput::DisplayP3 => MODE_DISPLAY_P3,
+            crate::settings::ColorOutput::Gamma => MODE_GAMMA,
+            crate::settings::ColorOutput::Auto | crate::settings::ColorOutput::Srgb => MODE_SRGB,
+        };
+        self.uniforms.mode = mode;
+        self.uniforms.gamma = settings.output_gamma.clamp(1.0, 3.0);
+        if mode == MODE_SRGB {
+            self.target = None;
+            return;
+        }
+        if self.target.as_ref().map(|target| target.size) == Some(size) {
+            return;
+        }
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Color Management Source"),
+            size: wgpu::Extent3d {
+                width: size.width.max(1),
+                height: size.height.max(1),
+                depth: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.format,
+            usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::SAMPLED,
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Color Management Bind Group"),
+            layout: &self.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Buffer(self.uniform_buffer.slice(..)),
+                },
+            ],
+        });
+        self.target = Some(Target {
+            size,
+            view,
+            bind_group,
+        });
+    }
+
+    pub fn target(&self) -> Option<&wgpu::TextureView> {
+        self.target.as_ref().map(|target| &target.view)
+    }
+
+    pub fn render_pattern(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        ui_pass: &mut crate::ui_pass::RenderPass,
+        size: winit::dpi::PhysicalSize<u32>,
+        scale_factor: f64,
+    ) {
+        let viewport = match self
+            .viewport
+            .map(|rect| crate::blit::to_physical(rect, scale_factor))
+        {
+            Some(viewport) if crate::blit::fits(viewport, size) => viewport,
+            _ => return,
+        };
+        let target = self.canvas.begin(device, encoder, ui_pass, size);
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+        crate::blit::set_viewport(&mut render_pass, viewport);
+        render_pass.set_pipeline(&self.pattern_pipeline);
+        render_pass.draw(0..3, 0..1);
+    }
+
+    pub fn apply(
+        &self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        output: &wgpu::TextureView,
+    ) {
+        let target = match &self.target {
+            Some(target) => target,
+            None => return,
+        };
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&self.uniforms),
+        );
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: output,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &target.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &std::sync::Arc<egui::Context>,
+        settings: &mut crate::settings::AppSettings,
+    ) {
+        let mut open = self.open;
+        let mut viewport = None;
+        let active = self.resolve(settings);
+        let monitor = &self.monitor;
+        let detected = self.detected;
+        let canvas = &self.canvas;
+        crate::dock::Panel::new("Color management")
+            .open(&mut open)
+            .default_pos(egui::pos2(440.0, 100.0))
+            .show(ctx, |ui| {
+                ui.label(match monitor {
+                    Some(name) => format!("Monitor: {}", name),
+                    None => "Monitor: unknown".to_owned(),
+                });
+                ui.label(match detected {
+                    Some(space) => format!("Detected {} from the monitor name", describe(space)),
+                    None => "The platform does not report a color space, assuming sRGB".to_owned(),
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Output");
+                    ui.radio_value(
+                        &mut settings.color_output,
+                        crate::settings::ColorOutput::Auto,
+                        "Auto",
+                    );
+                    ui.radio_value(
+                        &mut settings.color_output,
+                        crate::settings::ColorOutput::Srgb,
+                        "sRGB",
+                    );
+                    ui.radio_value(
+                        &mut settings.color_output,
+                        crate::settings::ColorOutput::DisplayP3,
+                        "Display P3",
+                    );
+                    ui.radio_value(
+                        &mut settings.color_output,
+                        crate::settings::ColorOutput::Gamma,
+                        "Custom gamma",
+                    );
+                });
+                if settings.color_output == crate::settings::ColorOutput::Gamma {
+                    ui.add(egui::Slider::f32(&mut settings.output_gamma, 1.0..=3.0).text("gamma"));
+                }
+                ui.label(format!("Applying {} in the final pass", describe(active)));
+                ui.label("Top: every step of the gray ramp should be distinct.");
+                ui.label("Middle: primaries and secondaries, then white.");
+                ui.label("Bottom: from a distance the middle patch should match the lines.");
+                let rect = ui.allocate_space(egui::vec2(PATTERN_SIZE[0], PATTERN_SIZE[1]));
+                canvas.paint(ui, rect);
+                viewport = Some(rect);
+            });
+        self.open = open;
+        self.viewport = viewport.filter(|_| open);
+    }
+}
@@ -0,0 +1,310 @@
+const HEIGHT: f32 = 220.0;
+const MAX_LINES: usize = 500;
+const ERROR_COLOR: egui::Srgba = egui::Srgba::new(240, 110, 100, 255);
+const COMMAND_COLOR: egui::Srgba = egui::Srgba::new(140, 190, 240, 255);
+
+pub type Handler = fn(&mut crate::Engine, &[&str]) -> Result<String, String>;
+
+struct Command {
+    name: &'static str,
+    usage: &'static str,
+    handler: Handler,
+}
+
+#[derive(Default)]
+pub struct Registry {
+    commands: Vec<Command>,
+}
+
+impl Registry {
+    pub fn register(&mut self, name: &'static str, usage: &'static str, handler: Handler) {
+        if self.commands.iter().any(|command| command.name == name) {
+            log::warn!("console command {} is registered twice", name);
+            return;
+        }
+        self.commands.push(Command {
+            name,
+            usage,
+            handler,
+        });
+        self.commands.sort_by_key(|command| command.name);
+    }
+
+    fn handler(&self, name: &str) -> Option<Handler> {
+        self.commands
+            .iter()
+            .find(|command| command.name == name)
+            .map(|command| command.handler)
+    }
+}
+
+pub fn parse_bool(value: &str) -> Result<bool, String> {
+    match value {
+        "on" | "true" | "1" | "yes" => Ok(true),
+        "off" | "false" | "0" | "no" => Ok(false),
+        _ => Err(format!("expected on or off, got {:?}", value)),
+    }
+}
+
+pub fn parse_count(value: Option<&str>) -> Result<usize, String> {
+    match value {
+        Some(value) => value
+            .parse()
+            .map_err(|_| format!("expected a count, got {:?}", value)),
+        None => Ok(1),
+    }
+}
+
+fn help(engine: &mut crate::Engine, _: &[&str]) -> Result<String, String> {
+    let lines: Vec<String> = engine
+        .console
+        .registry
+        .commands
+        .iter()
+        .map(|command| format!("{:12} {}", command.name, command.usage))
+        .collect();
+    Ok(lines.join("\n"))
+}
+
+fn clear(engine: &mut crate::Engine, _: &[&str]) -> Result<String, String> {
+    engine.console.lines.clear();
+    Ok(String::new())
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Command,
+    Output,
+    Error,
+}
+
+pub struct Console {
+    pub open: bool,
+    registry: Registry,
+    input: String,
+    lines: std::collections::VecDeque<(Kind, String)>,
+    history: Vec<String>,
+    recall: Option<usize>,
+    submitted: bool,
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        let mut registry = Registry::default();
+        registry.register("help", "list the available commands", help);
+        registry.register("clear", "clear the console output", clear);
+        crate::settings::register_commands(&mut registry);
+        crate::scene::register_commands(&mut registry);
+        crate::turntable::register_commands(&mut registry);
+        Self {
+            open: false,
+            registry,
+            input: String::new(),
+            lines: std::collections::VecDeque::new(),
+            history: Vec::new(),
+            recall: None,
+            submitted: false,
+        }
+    }
+}
+
+impl Console {
+    pub fn is_toggle(event: &winit::event::WindowEvent) -> bool {
+        match event {
+            winit::event::WindowEvent::ReceivedCharacter(character) => {
+                *character == '`' || *character == '~'
+            }
+            _ => false,
+        }
+    }
+
+    pub fn key_input(&mut self, input: &winit::event::KeyboardInput) {
+        if input.state != winit::event::ElementState::Pressed {
+            return;
+        }
+        match input.virtual_keycode {
+            Some(winit::event::VirtualKeyCode::Return)
+            | Some(winit::event::VirtualKeyCode::NumpadEnter) => self.submitted = true,
+            Some(winit::event::VirtualKeyCode::Up) if !self.history.is_empty() => {
+                let index = self
+                    .recall
+                    .map_or(self.history.len() - 1, |index| index.saturating_sub(1));
+                self.recall = Some(index);
+                self.input = self.history[index].clone();
+            }
+            Some(winit::event::VirtualKeyCode::Down) => match self.recall.map(|index| index + 1) {
+                Some(index) if index < self.history.len() => {
+                    self.recall = Some(index);
+                    self.input = self.history[index].clone();
+                }
+                _ => {
+                    self.recall = None;
+                    self.input.clear();
+                }
+            },
+            Some(winit::event::VirtualKeyCode::Escape) => self.open = false,
+            _ => {}
+        }
+    }
+
+    fn push(&mut self, kind: Kind, text: &str) {
+        for line in text.lines() {
+            if self.lines.len() == MAX_LINES {
+                self.lines.pop_front();
+            }
+            self.lines.push_back((kind, line.to_owned()));
+        }
+    }
+
+    pub fn take_command(&mut self) -> Option<String> {
+        if !std::mem::take(&mut self.submitted) {
+            return None;
+        }
+        let line = std::mem::take(&mut self.input).trim().to_owned();
+        if line.is_empty() {
+            return None;
+        }
+        self.push(Kind::Command, &format!("> {}", line));
+        if self.history.last() != Some(&line) {
+            self.history.push(line.clone());
+        }
+        self.recall = None;
+        Some(line)
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, screen: egui::Vec2) {
+        if !self.open {
+            return;
+        }
+        let input = &mut self.input;
+        let lines = &self.lines;
+        let submitted = &mut self.submitted;
+        egui::Area::new("console")
+            .fixed_pos(egui::pos2(0.0, 24.0))
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.allocate_space(egui::vec2(screen.x - 16.0, 0.0));
+                    egui::ScrollArea::from_max_height(HEIGHT).show(ui, |ui| {
+                        for (kind, line) in lines.iter() {
+                            let label = egui::Label::new(line.as_str())
+                                .text_style(egui::TextStyle::Monospace);
+                            ui.add(match kind {
+                                Kind::Command => label.text_color(COMMAND_COLOR),
+                                Kind::Error => label.text_color(ERROR_COLOR),
+                                Kind::Output => label,
+                            });
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(">");
+                        ui.add(egui::TextEdit::new(input));
+                        if ui.button("Run").clicked {
+                            *submitted = true;
+                        }
+                    });
+                    ui.label("Type help for a list of commands, ` or ~ closes the console.");
+                });
+            });
+    }
+}
+
+pub fn execute(engine: &mut crate::Engine, line: &str) {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let (name, args) = match words.split_first() {
+        Some((name, args)) => (*name, args),
+        None => return,
+    };
+    let result = match engine.console.registry.handler(name) {
+        Some(handler) => handler(engine, args),
+        None => Err(format!("unknown command {:?}, try help", name)),
+    };
+    match result {
+        Ok(output) => {
+            log::info!("console: {}", line);
+            engine.console.push(Kind::Output, &output);
+        }
+        Err(err) => {
+            log::warn!("console: {}: {}", line, err);
+            engine.console.push(Kind::Error, &err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_bool, parse_count, Console, Registry};
+    use winit::event::{ElementState, KeyboardInput, ModifiersState, VirtualKeyCode};
+
+    #[allow(deprecated)]
+    fn press(key: VirtualKeyCode) -> KeyboardInput {
+        KeyboardInput {
+            scancode: 0,
+            state: ElementState::Pressed,
+            virtual_keycode: Some(key),
+            modifiers: ModifiersState::empty(),
+        }
+    }
+
+    fn submit(console: &mut Console, line: &str) -> Option<String> {
+        console.input = line.to_owned();
+        console.key_input(&press(VirtualKeyCode::Return));
+        console.take_command()
+    }
+
+    #[test]
+    fn counts_default_to_one() {
+        assert_eq!(parse_count(None), Ok(1));
+        assert_eq!(parse_count(Some("12")), Ok(12));
+        assert!(parse_count(Some("-1")).is_err());
+        assert!(parse_count(Some("many")).is_err());
+    }
+
+    #[test]
+    fn booleans_accept_common_spellings() {
+        for value in &["on", "true", "1", "yes"] {
+            assert_eq!(parse_bool(value), Ok(true));
+        }
+        for value in &["off", "false", "0", "no"] {
+            assert_eq!(parse_bool(value), Ok(false));
+        }
+        assert!(parse_bool("maybe").is_err());
+    }
+
+    #[test]
+    fn commands_are_sorted_and_registered_once() {
+        let mut registry = Registry::default();
+        registry.register("zoom", "", |_, _| Ok("zoom".to_owned()));
+        registry.register("alpha", "", |_, _| Ok("alpha".to_owned()));
+        registry.register("zoom", "", |_, _| Ok("again".to_owned()));
+        let names: Vec<&str> = registry
+            .commands
+            .iter()
+            .map(|command| command.name)
+            .collect();
+        assert_eq!(names, ["alpha", "zoom"]);
+        assert!(registry.handler("zoom").is_some());
+        assert!(registry.handler("missing").is_none());
+    }
+
+    #[test]
+    fn submitted_lines_are_trimmed_and_kept_in_history() {
+        let mut console = Console::default();
+        assert_eq!(console.take_command(), None);
+        assert_eq!(submit(&mut console, "  help "), Some("help".to_owned()));
+        assert_eq!(submit(&mut console, "   "), None);
+        assert_eq!(submit(&mut console, "help"), Some("help".to_owned()));
+        assert_eq!(submit(&mut console, "clear"), Some("clear".to_owned()));
+        assert_eq!(console.history, ["help", "clear"]);
+
+        console.key_input(&press(VirtualKeyCode::Up));
+        assert_eq!(console.input, "clear");
+        console.key_input(&press(VirtualKeyCode::Up));
+        assert_eq!(console.input, "help");
+        console.key_input(&press(VirtualKeyCode::Up));
+        assert_eq!(console.input, "help");
+        console.key_input(&press(VirtualKeyCode::Down));
+        assert_eq!(console.input, "clear");
+        console.key_input(&press(VirtualKeyCode::Down));
+        assert_eq!(console.input, "");
+    }
+}
@@ -0,0 +1,494 @@
+const WORKERS: usize = 4;
+const EXTENSIONS: [&str; 7] = ["png", "jpg", "jpeg", "bmp", "gif", "tga", "tiff"];
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Format {
+    Png,
+    Jpeg,
+    Bmp,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Png => "png",
+            Format::Jpeg => "jpg",
+            Format::Bmp => "bmp",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Settings {
+    pub input: String,
+    pub output: String,
+    pub format: Format,
+    pub max_size: f32,
+    pub quality: f32,
+    pub overwrite: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            input: String::new(),
+            output: "converted".to_owned(),
+            format: Format::Jpeg,
+            max_size: 1024.0,
+            quality: 85.0,
+            overwrite: false,
+        }
+    }
+}
+
+#[derive(Clone)]
+enum FileState {
+    Queued,
+    Running(&'static str, f32),
+    Done {
+        input_bytes: u64,
+        output_bytes: u64,
+        size: [u32; 2],
+        time: std::time::Duration,
+    },
+    Skipped,
+    Failed(String),
+}
+
+struct Shared {
+    states: std::sync::Mutex<Vec<FileState>>,
+    next: std::sync::atomic::AtomicUsize,
+    paused: std::sync::atomic::AtomicBool,
+    cancelled: std::sync::atomic::AtomicBool,
+}
+
+impl Shared {
+    fn set(&self, index: usize, state: FileState) {
+        self.states.lock().unwrap()[index] = state;
+    }
+}
+
+struct Job {
+    files: Vec<std::path::PathBuf>,
+    shared: std::sync::Arc<Shared>,
+    workers: Vec<std::thread::JoinHandle<()>>,
+    started: std::time::Instant,
+}
+
+pub struct Report {
+    converted: usize,
+    skipped: usize,
+    failed: Vec<(String, String)>,
+    cancelled: usize,
+    input_bytes: u64,
+    output_bytes: u64,
+    elapsed: std::time::Duration,
+}
+
+#[derive(Default)]
+pub struct Converter {
+    pub open: bool,
+    pub settings: Settings,
+    pub browse_requested: bool,
+    job: Option<Job>,
+    report: Option<Report>,
+    status: String,
+    start_requested: bool,
+    cancel_requested: bool,
+}
+
+impl Converter {
+    pub fn drop_path(&mut self, path: &std::path::Path) {
+        if self.job.is_some() {
+            return;
+        }
+        let directory = if path.is_dir() {
+            path
+        } else {
+            match path.parent() {
+                Some(parent) => parent,
+                None => return,
+            }
+        };
+        self.settings.input = directory.display().to_string();
+        self.open = true;
+    }
+
+    fn output_directory(&self) -> std::path::PathBuf {
+        let output = std::path::Path::new(&self.settings.output);
+        if output.is_absolute() {
+            output.to_owned()
+        } else {
+            std::path::Path::new(&self.settings.input).join(output)
+        }
+    }
+
+    fn start(&mut self) {
+        let input = std::path::PathBuf::from(&self.settings.input);
+        let entries = match std::fs::read_dir(&input) {
+            Ok(entries) => entries,
+            Err(err) => {
+                self.status = format!("Cannot read {}: {}", input.display(), err);
+                return;
+            }
+        };
+        let mut files: Vec<std::path::PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .extension()
+                        .and_then(|extension| extension.to_str())
+                        .is_some_and(|extension| {
+                            EXTENSIONS.contains(&extension.to_lowercase().as_str())
+                        })
+            })
+            .collect();
+        files.sort();
+        if files.is_empty() {
+            self.status = format!("No images found in {}", input.display());
+            return;
+        }
+        let output = self.output_directory();
+        if let Err(err) = std::fs::create_dir_all(&output) {
+            self.status = format!("Cannot create {}: {}", output.display(), err);
+            return;
+        }
+
+        let shared = std::sync::Arc::new(Shared {
+            states: std::sync::Mutex::new(vec![FileState::Queued; files.len()]),
+            next: std::sync::atomic::AtomicUsize::new(0),
+            paused: std::sync::atomic::AtomicBool::new(false),
+            cancelled: std::sync::atomic::AtomicBool::new(false),
+        });
+        let workers = (0..WORKERS.min(files.len()))
+            .map(|worker| {
+                let shared = shared.clone();
+                let files = files.clone();
+                let output = output.clone();
+                let settings = self.settings.clone();
+                std::thread::Builder::new()
+                    .name(format!("image converter {}", worker))
+                    .spawn(move || loop {
+                        while shared.paused.load(std::sync::atomic::Ordering::Relaxed)
+                            && !shared.cancelled.load(std::sync::atomic::Ordering::Relaxed)
+                        {
+                            std::thread::sleep(std::time::Duration::from_millis(50));
+                        }
+                        if shared.cancelled.load(std::sync::atomic::Ordering::Relaxed) {
+                            break;
+                        }
+                        let index = shared
+                            .next
+                            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                        let path = match files.get(index) {
+                            Some(path) => path,
+                            None => break,
+                        };
+                        let state = convert(path, &output, &settings, |stage, progress| {
+                            shared.set(index, FileState::Running(stage, progress))
+                        })
+                        .unwrap_or_else(FileState::Failed);
+                        shared.set(index, state);
+                    })
+                    .unwrap()
+            })
+            .collect();
+
+        log::info!(
+            "converting {} images from {} with {} workers",
+            files.len(),
+            input.display(),
+            WORKERS
+        );
+        self.status.clear();
+        self.report = None;
+        self.job = Some(Job {
+            files,
+            shared,
+            workers,
+            started: std::time::Instant::now(),
+        });
+    }
+
+    fn poll(&mut self) {
+        let finished = match &self.job {
+            Some(job) => job
+                .shared
+                .states
+                .lock()
+                .unwrap()
+                .iter()
+                .all(|state| match state {
+                    FileState::Queued => job
+                        .shared
+                        .cancelled
+                        .load(std::sync::atomic::Ordering::Relaxed),
+                    FileState::Running(..) => false,
+                    _ => true,
+                }),
+            None => return,
+        };
+        if !finished {
+            return;
+        }
+        let job = self.job.take().unwrap();
+        for worker in job.workers {
+            worker.join().ok();
+        }
+
+        let mut report = Report {
+            converted: 0,
+            skipped: 0,
+            failed: Vec::new(),
+            cancelled: 0,
+            input_bytes: 0,
+            output_bytes: 0,
+            elapsed: job.started.elapsed(),
+        };
+        let states = job.shared.states.lock().unwrap();
+        for (path, state) in job.files.iter().zip(states.iter()) {
+            match state {
+                FileState::Done {
+                    input_bytes,
+                    output_bytes,
+                    ..
+                } => {
+                    report.converted += 1;
+                    report.input_bytes += input_bytes;
+                    report.output_bytes += output_bytes;
+                }
+                FileState::Skipped => report.skipped += 1,
+                FileState::Failed(err) => report.failed.push((file_name(path), err.clone())),
+                FileState::Queued | FileState::Running(..) => report.cancelled += 1,
+            }
+        }
+        log::info!(
+            "converted {} images in {} ms ({} failed, {} cancelled)",
+            report.converted,
+            report.elapsed.as_millis(),
+            report.failed.len(),
+            report.cancelled
+        );
+        self.report = Some(report);
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        if self.start_requested {
+            self.start_requested = false;
+            self.start();
+        }
+        if self.cancel_requested {
+            self.cancel_requested = false;
+            if let Some(job) = &self.job {
+                job.shared
+                    .cancelled
+                    .store(true, std::sync::atomic::Ordering::Relaxed);
+            }
+        }
+        self.poll();
+
+        let mut open = self.open;
+        let running = self.job.is_some();
+        let output = self.output_directory();
+        let settings = &mut self.settings;
+        let start_requested = &mut self.start_requested;
+        let cancel_requested = &mut self.cancel_requested;
+        let browse_requested = &mut self.browse_requested;
+        let job = &self.job;
+        let report = &self.report;
+        let status = &self.status;
+        crate::dock::Panel::new("Image Converter")
+            .open(&mut open)
+            .default_pos(egui::pos2(420.0, 120.0))
+            .show(ctx, |ui| {
+                ui.label("Drop a folder or image onto the window, or type a path.");
+                ui.horizontal(|ui| {
+                    ui.label("Input");
+                    ui.add(egui::TextEdit::new(&mut settings.input));
+                    if !running && ui.button("Open image...").clicked {
+                        *browse_requested = true;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Output");
+                    ui.add(egui::TextEdit::new(&mut settings.output));
+                });
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut settings.format, Format::Png, "PNG");
+                    ui.radio_value(&mut settings.format, Format::Jpeg, "JPEG");
+                    ui.radio_value(&mut settings.format, Format::Bmp, "BMP");
+                });
+                ui.add(egui::Slider::f32(&mut settings.max_size, 16.0..=4096.0).text("max size"));
+                if settings.format == Format::Jpeg {
+                    ui.add(egui::Slider::f32(&mut settings.quality, 1.0..=100.0).text("quality"));
+                }
+                ui.checkbox(&mut settings.overwrite, "Overwrite existing files");
+
+                match job {
+                    Some(job) => {
+                        let paused = job.shared.paused.load(std::sync::atomic::Ordering::Relaxed);
+                        ui.horizontal(|ui| {
+                            if ui.button(if paused { "Resume" } else { "Pause" }).clicked {
+                                job.shared
+                                    .paused
+                                    .store(!paused, std::sync::atomic::Ordering::Relaxed);
+                            }
+                            if ui.button("Cancel").clicked {
+                                *cancel_requested = true;
+                            }
+                        });
+                        let states = job.shared.states.lock().unwrap();
+                        let completed = states
+                            .iter()
+                            .filter(|state| {
+                                !matches!(state, FileState::Queued | FileState::Running(..))
+                            })
+                            .count();
+                        ui.label(format!(
+                            "{} / {} files, {:.1} s{}",
+                            completed,
+                            states.len(),
+                            job.started.elapsed().as_secs_f32(),
+                            if paused { " (paused)" } else { "" }
+                        ));
+                        progress_bar(ui, 240.0, completed as f32 / states.len() as f32);
+                        egui::ScrollArea::from_max_height(240.0).show(ui, |ui| {
+                            for (path, state) in job.files.iter().zip(states.iter()) {
+                                ui.horizontal(|ui| {
+                                    let progress = match state {
+                                        FileState::Queued => 0.0,
+                                        FileState::Running(_, progress) => *progress,
+                                        _ => 1.0,
+                                    };
+                                    progress_bar(ui, 60.0, progress);
+                                    ui.label(file_name(path));
+                                    ui.label(match state {
+                                        FileState::Queued => "queued".to_owned(),
+                                        FileState::Running(stage, _) => stage.to_string(),
+                                        FileState::Done { size, time, .. } => format!(
+                                            "{}x{} in {} ms",
+                                            size[0],
+                                            size[1],
+                                            time.as_millis()
+                                        ),
+                                        FileState::Skipped => "exists, skipped".to_owned(),
+                                        FileState::Failed(err) => err.clone(),
+                                    });
+                                });
+                            }
+                        });
+                    }
+                    None => {
+                        if ui.button("Convert").clicked {
+                            *start_requested = true;
+                        }
+                        ui.label(format!("Writing to {}", output.display()));
+                    }
+                }
+                if !status.is_empty() {
+                    ui.label(status.as_str());
+                }
+
+                if let (false, Some(report)) = (running, report) {
+                    ui.separator();
+                    ui.heading("Summary");
+                    ui.label(format!(
+                        "{} converted, {} skipped, {} failed, {} cancelled in {:.1} s",
+                        report.converted,
+                        report.skipped,
+                        report.failed.len(),
+                        report.cancelled,
+                        report.elapsed.as_secs_f32()
+                    ));
+                    if report.converted > 0 {
+                        ui.label(format!(
+                            "{:.1} MiB in, {:.1} MiB out ({:.0}%)",
+                            report.input_bytes as f32 / 1048576.0,
+                            report.output_bytes as f32 / 1048576.0,
+                            report.output_bytes as f32 / report.input_bytes.max(1) as f32 * 100.0
+                        ));
+                    }
+                    for (name, err) in report.failed.iter() {
+                        ui.label(format!("{}: {}", name, err));
+                    }
+                }
+            });
+        self.open = open;
+    }
+}
+
+fn file_name(path: &std::path::Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn progress_bar(ui: &mut egui::Ui, width: f32, progress: f32) {
+    let rect = ui.allocate_space(egui::vec2(width, 8.0));
+    let filled = egui::Rect::from_min_size(
+        rect.min,
+        egui::vec2(rect.width() * progress.clamp(0.0, 1.0), rect.height()),
+    );
+    ui.painter()
+        .rect_filled(rect, 2.0, egui::Srgba::new(60, 60, 60, 255));
+    ui.painter()
+        .rect_filled(filled, 2.0, egui::Srgba::new(90, 170, 250, 255));
+}
+
+fn convert(
+    path: &std::path::Path,
+    output: &std::path::Path,
+    settings: &Settings,
+    progress: impl Fn(&'static str, f32),
+) -> Result<FileState, String> {
+    let started = std::time::Instant::now();
+    let target = output
+        .join(path.file_stem().unwrap_or_default())
+        .with_extension(settings.format.extension());
+    if target.exists() && !settings.overwrite {
+        return Ok(FileState::Skipped);
+    }
+
+    progress("decoding", 0.1);
+    let input_bytes = std::fs::metadata(path)
+        .map_err(|err| err.to_string())?
+        .len();
+    let mut image = image::open(path).map_err(|err| err.to_string())?;
+
+    progress("resizing", 0.4);
+    let max_size = settings.max_size as u32;
+    let (width, height) = image::GenericImageView::dimensions(&image);
+    if width > max_size || height > max_size {
+        image = image.resize(max_size, max_size, image::imageops::FilterType::Lanczos3);
+    }
+
+    progress("encoding", 0.7);
+    match settings.format {
+        Format::Png => image
+            .save_with_format(&target, image::ImageFormat::Png)
+            .map_err(|err| err.to_string())?,
+        Format::Bmp => image
+            .save_with_format(&target, image::ImageFormat::Bmp)
+            .map_err(|err| err.to_string())?,
+        Format::Jpeg => {
+            let mut file = std::io::BufWriter::new(
+                std::fs::File::create(&target).map_err(|err| err.to_string())?,
+            );
+            image::jpeg::JpegEncoder::new_with_quality(&mut file, settings.quality as u8)
+                .encode_image(&image::DynamicImage::ImageRgb8(image.to_rgb()))
+                .map_err(|err| err.to_string())?;
+        }
+    }
+    let (width, height) = image::GenericImageView::dimensions(&image);
+    let output_bytes = std::fs::metadata(&target)
+        .map_err(|err| err.to_string())?
+        .len();
+
+    Ok(FileState::Done {
+        input_bytes,
+        output_bytes,
+        size: [width, height],
+        time: started.elapsed(),
+    })
+}
@@ -0,0 +1,175 @@
+#[derive(Clone, Copy, Debug)]
+pub struct Aabb {
+    pub min: glam::Vec3,
+    pub max: glam::Vec3,
+}
+
+impl Aabb {
+    pub fn from_points(points: impl Iterator<Item = glam::Vec3>) -> Self {
+        let mut min = glam::Vec3::splat(f32::INFINITY);
+        let mut max = glam::Vec3::splat(f32::NEG_INFINITY);
+        for point in points {
+            min = min.min(point);
+            max = max.max(point);
+        }
+        Self { min, max }
+    }
+
+    pub fn center(&self) -> glam::Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn extents(&self) -> glam::Vec3 {
+        (self.max - self.min) * 0.5
+    }
+
+    pub fn ray_distance(&self, origin: glam::Vec3, direction: glam::Vec3) -> Option<f32> {
+        let inverse = glam::Vec3::one() / direction;
+        let t0 = (self.min - origin) * inverse;
+        let t1 = (self.max - origin) * inverse;
+        let (near, far) = (t0.min(t1), t0.max(t1));
+        let near = near.x.max(near.y).max(near.z);
+        let far = far.x.min(far.y).min(far.z);
+        if far >= near.max(0.0) {
+            Some(near.max(0.0))
+        } else {
+            None
+        }
+    }
+
+    pub fn transformed(&self, transform: &glam::Mat4) -> Self {
+        let center = transform.transform_point3(self.center());
+        let extents = self.extents();
+        let columns = transform.to_cols_array_2d();
+        let mut world_extents = glam::Vec3::zero();
+        for (axis, extent) in [extents.x, extents.y, extents.z].iter().enumerate() {
+            let column = glam::Vec3::new(columns[axis][0], columns[axis][1], columns[axis][2]);
+            world_extents += column.abs() * *extent;
+        }
+        Self {
+            min: center - world_extents,
+            max: center + world_extents,
+        }
+    }
+}
+
+pub struct Frustum {
+    planes: [glam::Vec4; 6],
+}
+
+impl Frustum {
+    pub fn from_view_proj(view_proj: &glam::Mat4) -> Self {
+        let columns = view_proj.to_cols_array_2d();
+        let row =
+            |i: usize| glam::Vec4::new(columns[0][i], columns[1][i], columns[2][i], columns[3][i]);
+        let (r0, r1, r2, r3) = (row(0), row(1), row(2), row(3));
+
+        let mut planes = [r3 + r0, r3 - r0, r3 + r1, r3 - r1, r2, r3 - r2];
+        for plane in planes.iter_mut() {
+            let length = plane.truncate().length();
+            *plane /= length;
+        }
+        Self { planes }
+    }
+
+    pub fn intersects(&self, aabb: &Aabb) -> bool {
+        let center = aabb.center();
+        let extents = aabb.extents();
+        self.planes.iter().all(|plane| {
+            let normal = plane.truncate();
+            let radius = extents.dot(normal.abs());
+            normal.dot(center) + plane.w >= -radius
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Aabb, Frustum};
+
+    fn unit_box(center: glam::Vec3) -> Aabb {
+        Aabb {
+            min: center - glam::Vec3::one(),
+            max: center + glam::Vec3::one(),
+        }
+    }
+
+    fn camera() -> Frustum {
+        let view = glam::Mat4::look_at_rh(
+            glam::Vec3::new(0.0, 0.0, 5.0),
+            glam::Vec3::zero(),
+            glam::Vec3::unit_y(),
+        );
+        let projection = glam::Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0);
+        Frustum::from_view_proj(&(projection * view))
+    }
+
+    #[test]
+    fn bounds_enclose_every_point() {
+        let aabb = Aabb::from_points(
+            vec![
+                glam::Vec3::new(1.0, -2.0, 3.0),
+                glam::Vec3::new(-1.0, 4.0, 0.0),
+                glam::Vec3::new(0.0, 0.0, -5.0),
+            ]
+            .into_iter(),
+        );
+        assert_eq!(aabb.min, glam::Vec3::new(-1.0, -2.0, -5.0));
+        assert_eq!(aabb.max, glam::Vec3::new(1.0, 4.0, 3.0));
+        assert_eq!(aabb.center(), glam::Vec3::new(0.0, 1.0, -1.0));
+        assert_eq!(aabb.extents(), glam::Vec3::new(1.0, 3.0, 4.0));
+    }
+
+    #[test]
+    fn rays_report_the_entry_distance() {
+        let aabb = unit_box(glam::Vec3::zero());
+        let forward = glam::Vec3::new(0.0, 0.0, 1.0);
+        assert_eq!(
+            aabb.ray_distance(glam::Vec3::new(0.5, 0.5, -5.0), forward),
+            Some(4.0)
+        );
+        assert_eq!(
+            aabb.ray_distance(glam::Vec3::new(0.5, 0.5, 0.5), forward),
+            Some(0.0)
+        );
+        assert_eq!(
+            aabb.ray_distance(glam::Vec3::new(3.0, 0.5, -5.0), forward),
+            None
+        );
+        assert_eq!(
+            aabb.ray_distance(glam::Vec3::new(0.5, 0.5, -5.0), -forward),
+            None
+        );
+    }
+
+    #[test]
+    fn transformed_bounds_cover_the_moved_box() {
+        let aabb = unit_box(glam::Vec3::zero());
+        let moved = aabb.transformed(&glam::Mat4::from_translation(glam::Vec3::new(
+            2.0, 0.0, 0.0,
+        )));
+        assert_eq!(moved.min, glam::Vec3::new(1.0, -1.0, -1.0));
+        assert_eq!(moved.max, glam::Vec3::new(3.0, 1.0, 1.0));
+        let rotated = aabb.transformed(&glam::Mat4::from_rotation_y(std::f32::consts::FRAC_PI_4));
+        let diagonal = std::f32::consts::SQRT_2;
+        assert!((rotated.max.x - diagonal).abs() < 1e-5);
+        assert!((rotated.max.z - diagonal).abs() < 1e-5);
+        assert!((rotated.max.y - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn frustum_keeps_boxes_in_view() {
+        let frustum = camera();
+        assert!(frustum.intersects(&unit_box(glam::Vec3::zero())));
+        assert!(frustum.intersects(&unit_box(glam::Vec3::new(5.0, 0.0, 0.0))));
+    }
+
+    #[test]
+    fn frustum_rejects_boxes_outside_each_side() {
+        let frustum = camera();
+        assert!(!frustum.intersects(&unit_box(glam::Vec3::new(0.0, 0.0, 10.0))));
+        assert!(!frustum.intersects(&unit_box(glam::Vec3::new(50.0, 0.0, 0.0))));
+        assert!(!frustum.intersects(&unit_box(glam::Vec3::new(0.0, -50.0, 0.0))));
+        assert!(!frustum.intersects(&unit_box(glam::Vec3::new(0.0, 0.0, -200.0))));
+    }
+}
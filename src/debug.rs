@@ -0,0 +1,300 @@
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct LineVertex {
+    position: [f32; 3],
+    color: [f32; 4],
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Category {
+    Axes,
+    Bounds,
+    Lights,
+    Grid,
+    Selection,
+    Measurements,
+}
+
+impl Category {
+    pub const ALL: [Category; 6] = [
+        Category::Axes,
+        Category::Bounds,
+        Category::Lights,
+        Category::Grid,
+        Category::Selection,
+        Category::Measurements,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Category::Axes => "World axes",
+            Category::Bounds => "Object bounds",
+            Category::Lights => "Lights",
+            Category::Grid => "Ground grid",
+            Category::Selection => "Picked objects",
+            Category::Measurements => "Measurements",
+        }
+    }
+}
+
+pub struct DebugDraw {
+    pub enabled: [bool; 6],
+    pub lines_last_frame: usize,
+    category: Category,
+    vertices: Vec<LineVertex>,
+    pipeline: wgpu::RenderPipeline,
+    msaa_pipeline: wgpu::RenderPipeline,
+    vertex_buffer: Option<wgpu::Buffer>,
+    vertex_capacity: usize,
+    vertex_count: u32,
+}
+
+impl DebugDraw {
+    pub fn new(device: &wgpu::Device, layout: &wgpu::PipelineLayout) -> Self {
+        let vs_module = crate::shader::compile_builtin(
+            device,
+            "debug_line.vert",
+            include_str!("shaders/debug_line.vert"),
+            shaderc::ShaderKind::Vertex,
+        );
+        let fs_module = crate::shader::compile_builtin(
+            device,
+            "debug_line.frag",
+            include_str!("shaders/debug_line.frag"),
+            shaderc::ShaderKind::Fragment,
+        );
+
+        Self {
+            enabled: [true, false, false, false, true, true],
+            lines_last_frame: 0,
+            category: Category::Axes,
+            vertices: Vec::new(),
+            pipeline: create_pipeline(device, layout, &vs_module, &fs_module, 1),
+            msaa_pipeline: create_pipeline(
+                device,
+                layout,
+                &vs_module,
+                &fs_module,
+                crate::scene::MSAA_SAMPLES,
+            ),
+            vertex_buffer: None,
+            vertex_capacity: 0,
+            vertex_count: 0,
+        }
+    }
+
+    pub fn is_enabled(&self, category: Category) -> bool {
+        self.enabled[category as usize]
+    }
+
+    pub fn set_category(&mut self, category: Category) {
+        self.category = category;
+    }
+
+    pub fn line(&mut self, a: glam::Vec3, b: glam::Vec3, color: [f32; 4]) {
+        if !self.is_enabled(self.category) {
+            return;
+        }
+        self.vertices.push(LineVertex {
+            position: a.into(),
+            color,
+        });
+        self.vertices.push(LineVertex {
+            position: b.into(),
+            color,
+        });
+    }
+
+    pub fn aabb(&mut self, aabb: &crate::culling::Aabb, color: [f32; 4]) {
+        let corner = |i: usize| {
+            glam::Vec3::new(
+                if i & 1 == 0 { aabb.min.x } else { aabb.max.x },
+                if i & 2 == 0 { aabb.min.y } else { aabb.max.y },
+                if i & 4 == 0 { aabb.min.z } else { aabb.max.z },
+            )
+        };
+        for i in 0..8 {
+            for axis in [1, 2, 4].iter() {
+                if i & axis == 0 {
+                    self.line(corner(i), corner(i | axis), color);
+                }
+            }
+        }
+    }
+
+    pub fn upload(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+    ) -> bool {
+        self.lines_last_frame = self.vertices.len() / 2;
+        self.vertex_count = self.vertices.len() as u32;
+        if self.vertices.is_empty() {
+            return false;
+        }
+        if self.vertices.len() > self.vertex_capacity || self.vertex_buffer.is_none() {
+            self.vertex_capacity = self.vertices.len().max(1024).next_power_of_two();
+            self.vertex_buffer = Some(device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Debug Lines"),
+                size: (self.vertex_capacity * std::mem::size_of::<LineVertex>())
+                    as wgpu::BufferAddress,
+                usage: wgpu::BufferUsage::VERTEX | wgpu::BufferUsage::COPY_DST,
+                mapped_at_creation: false,
+            }));
+        }
+        uploader.write_buffer(
+            device,
+            encoder,
+            self.vertex_buffer.as_ref().unwrap(),
+            0,
+            bytemuck::cast_slice(&self.vertices),
+        );
+        self.vertices.clear();
+        true
+    }
+
+    pub fn draw<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>, msaa: bool) {
+        if self.vertex_count == 0 {
+            return;
+        }
+        if msaa {
+            render_pass.set_pipeline(&self.msaa_pipeline);
+        } else {
+            render_pass.set_pipeline(&self.pipeline);
+        }
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.as_ref().unwrap().slice(..));
+        render_pass.draw(0..self.vertex_count, 0..1);
+    }
+}
+
+fn create_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    vs_module: &wgpu::ShaderModule,
+    fs_module: &wgpu::ShaderModule,
+    sample_count: u32,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Debug Line Pipeline"),
+        layout: Some(layout),
+        vertex_stage: wgpu::ProgrammableStageDescriptor {
+            module: vs_module,
+            entry_point: "main",
+        },
+        fragment_stage: Some(wgpu::ProgrammableStageDescriptor {
+            module: fs_module,
+            entry_point: "main",
+        }),
+        rasterization_state: Some(wgpu::RasterizationStateDescriptor::default()),
+        primitive_topology: wgpu::PrimitiveTopology::LineList,
+        color_states: &[wgpu::ColorStateDescriptor {
+            format: crate::scene::HDR_FORMAT,
+            color_blend: wgpu::BlendDescriptor {
+                src_factor: wgpu::BlendFactor::SrcAlpha,
+                dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                operation: wgpu::BlendOperation::Add,
+            },
+            alpha_blend: wgpu::BlendDescriptor::REPLACE,
+            write_mask: wgpu::ColorWrite::COLOR,
+        }],
+        depth_stencil_state: Some(wgpu::DepthStencilStateDescriptor {
+            format: crate::scene::DEPTH_FORMAT,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::LessEqual,
+            stencil: wgpu::StencilStateDescriptor::default(),
+        }),
+        vertex_state: wgpu::VertexStateDescriptor {
+            index_format: wgpu::IndexFormat::Uint32,
+            vertex_buffers: &[wgpu::VertexBufferDescriptor {
+                stride: std::mem::size_of::<LineVertex>() as wgpu::BufferAddress,
+                step_mode: wgpu::InputStepMode::Vertex,
+                attributes: &wgpu::vertex_attr_array![0 => Float3, 1 => Float4],
+            }],
+        },
+        sample_count,
+        sample_mask: !0,
+        alpha_to_coverage_enabled: false,
+    })
+}
+
+#[derive(Default)]
+pub struct DebugPanel {
+    pub open: bool,
+}
+
+impl DebugPanel {
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, scene: &mut crate::scene::Scene) {
+        crate::dock::Panel::new("Debug")
+            .open(&mut self.open)
+            .show(ctx, |ui| {
+                for category in Category::ALL.iter() {
+                    ui.checkbox(
+                        &mut scene.debug.enabled[*category as usize],
+                        category.name(),
+                    );
+                }
+                ui.label(format!("Lines: {}", scene.debug.lines_last_frame));
+
+                egui::CollapsingHeader::new("Render targets").show(ui, |ui| {
+                    let mut outputs = vec![
+                        crate::visualizer::Output::Final,
+                        crate::visualizer::Output::SceneColor,
+                        crate::visualizer::Output::Depth,
+                        crate::visualizer::Output::Normals,
+                        crate::visualizer::Output::AmbientOcclusion,
+                    ];
+                    outputs.extend((0..scene.bloom.levels()).map(crate::visualizer::Output::Bloom));
+                    let settings = &mut scene.visualizer.settings;
+                    for output in outputs {
+                        if ui.radio(settings.output == output, output.name()).clicked {
+                            settings.set_output(output);
+                        }
+                    }
+                    if settings.output == crate::visualizer::Output::Final {
+                        return;
+                    }
+
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.radio_value(
+                            &mut settings.display,
+                            crate::visualizer::Display::Fullscreen,
+                            "Full screen",
+                        );
+                        ui.radio_value(
+                            &mut settings.display,
+                            crate::visualizer::Display::Inset,
+                            "Inset",
+                        );
+                    });
+                    ui.horizontal(|ui| {
+                        for (channel, name) in ["R", "G", "B", "A"].iter().enumerate() {
+                            ui.checkbox(&mut settings.channels[channel], *name);
+                        }
+                    });
+                    let max = match settings.output {
+                        crate::visualizer::Output::Depth => scene.camera.far,
+                        crate::visualizer::Output::SceneColor
+                        | crate::visualizer::Output::Bloom(_) => 16.0,
+                        _ => 1.0,
+                    };
+                    ui.add(egui::Slider::f32(&mut settings.range[0], 0.0..=max).text("min"));
+                    ui.add(egui::Slider::f32(&mut settings.range[1], 0.0..=max).text("max"));
+                    if scene.visualized_view().is_none() {
+                        ui.label("Not produced with the current settings");
+                    }
+                });
+
+                let cache = scene.cache.stats;
+                ui.label(format!(
+                    "GPU cache: {} pipelines, {} bind groups",
+                    cache.pipelines, cache.bind_groups
+                ));
+                ui.label(format!(
+                    "Hits: {}  Misses: {}  Evicted: {}",
+                    cache.hits, cache.misses, cache.evicted
+                ));
+            });
+    }
+}
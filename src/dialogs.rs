@@ -0,0 +1,104 @@
+pub const IMAGE_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "bmp", "gif"];
+pub const JSON_EXTENSIONS: [&str; 1] = ["json"];
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Purpose {
+    OpenImage,
+    ViewImage,
+    LoadSettings,
+    SaveSettingsAs,
+    SaveImageAs,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub purpose: Purpose,
+    pub path: Option<std::path::PathBuf>,
+}
+
+struct Filter {
+    name: String,
+    extensions: Vec<String>,
+}
+
+impl Filter {
+    fn new(name: &str, extensions: &[&str]) -> Self {
+        Self {
+            name: name.to_owned(),
+            extensions: extensions
+                .iter()
+                .map(|extension| extension.to_string())
+                .collect(),
+        }
+    }
+}
+
+fn build(filters: &[Filter]) -> rfd::FileDialog {
+    let mut dialog = rfd::FileDialog::new();
+    if let Ok(directory) = std::env::current_dir() {
+        dialog = dialog.set_directory(&directory);
+    }
+    for filter in filters.iter() {
+        let extensions: Vec<&str> = filter.extensions.iter().map(String::as_str).collect();
+        dialog = dialog.add_filter(&filter.name, &extensions);
+    }
+    dialog
+}
+
+#[derive(Default)]
+pub struct Dialogs {
+    proxy: Option<winit::event_loop::EventLoopProxy<crate::UserEvent>>,
+    pending: Option<Purpose>,
+}
+
+impl Dialogs {
+    pub fn set_proxy(&mut self, proxy: winit::event_loop::EventLoopProxy<crate::UserEvent>) {
+        self.proxy = Some(proxy);
+    }
+
+    pub fn pending(&self) -> Option<Purpose> {
+        self.pending
+    }
+
+    pub fn open(&mut self, purpose: Purpose, name: &str, extensions: &[&str]) {
+        let filters = vec![Filter::new(name, extensions)];
+        self.spawn(purpose, move || build(&filters).pick_file());
+    }
+
+    pub fn save(&mut self, purpose: Purpose, name: &str, extensions: &[&str]) {
+        let filters = vec![Filter::new(name, extensions)];
+        self.spawn(purpose, move || build(&filters).save_file());
+    }
+
+    pub fn finish(&mut self, response: &Response) {
+        if self.pending == Some(response.purpose) {
+            self.pending = None;
+        }
+    }
+
+    fn spawn<F>(&mut self, purpose: Purpose, dialog: F)
+    where
+        F: FnOnce() -> Option<std::path::PathBuf> + Send + 'static,
+    {
+        if self.pending.is_some() {
+            return;
+        }
+        let proxy = match &self.proxy {
+            Some(proxy) => proxy.clone(),
+            None => {
+                log::warn!("file dialogs are unavailable without an event loop");
+                return;
+            }
+        };
+        self.pending = Some(purpose);
+        std::thread::Builder::new()
+            .name("file dialog".to_owned())
+            .spawn(move || {
+                let path = dialog();
+                proxy
+                    .send_event(crate::UserEvent::FileDialog(Response { purpose, path }))
+                    .ok();
+            })
+            .unwrap();
+    }
+}
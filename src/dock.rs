@@ -0,0 +1,418 @@
+const MENU_HEIGHT: f32 = 24.0;
+const TAB_HEIGHT: f32 = 26.0;
+const SIDE_WIDTH: f32 = 340.0;
+const BOTTOM_HEIGHT: f32 = 240.0;
+const DROP_ZONE: f32 = 120.0;
+const GRIP_SIZE: f32 = 16.0;
+const TAB_BACKGROUND: egui::Srgba = egui::Srgba::new(30, 30, 34, 240);
+const GRIP_COLOR: egui::Srgba = egui::Srgba::new(150, 150, 160, 255);
+const DROP_FILL: egui::Srgba = egui::Srgba::new(20, 45, 70, 90);
+const DROP_OUTLINE: egui::Srgba = egui::Srgba::new(90, 170, 250, 255);
+
+#[derive(Clone, Copy, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+pub enum Side {
+    Left,
+    Right,
+    Bottom,
+}
+
+impl Side {
+    const ALL: [Side; 3] = [Side::Left, Side::Right, Side::Bottom];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Side::Left => "left",
+            Side::Right => "right",
+            Side::Bottom => "bottom",
+        }
+    }
+}
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+struct Layout {
+    docked: std::collections::BTreeMap<String, Side>,
+    active: [Option<String>; 3],
+}
+
+impl Layout {
+    fn path() -> Option<std::path::PathBuf> {
+        dirs::config_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")).join("dock.json"))
+    }
+
+    fn load() -> Self {
+        let path = match Self::path() {
+            Some(path) => path,
+            None => return Self::default(),
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|err| {
+                log::warn!(
+                    "ignoring invalid dock layout in {}: {}",
+                    path.display(),
+                    err
+                );
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    fn save(&self) {
+        let path = match Self::path() {
+            Some(path) => path,
+            None => return,
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&path, serde_json::to_string_pretty(self).unwrap()));
+        match result {
+            Ok(()) => log::info!("saved the dock layout to {}", path.display()),
+            Err(err) => log::warn!("cannot save the dock layout to {}: {}", path.display(), err),
+        }
+    }
+}
+
+struct State {
+    layout: Layout,
+    screen: egui::Vec2,
+    panels: [Vec<String>; 3],
+    occupied: [bool; 3],
+    dragging: Option<String>,
+}
+
+impl State {
+    fn new() -> Self {
+        Self {
+            layout: Layout::load(),
+            screen: egui::Vec2::default(),
+            panels: Default::default(),
+            occupied: [false; 3],
+            dragging: None,
+        }
+    }
+
+    fn bottom(&self) -> f32 {
+        self.screen.y - crate::status::HEIGHT
+    }
+
+    fn rect(&self, side: Side) -> egui::Rect {
+        let [left, right, _] = self.occupied;
+        let (screen, bottom) = (self.screen, self.bottom());
+        match side {
+            Side::Left => egui::Rect::from_min_max(
+                egui::pos2(0.0, MENU_HEIGHT),
+                egui::pos2(SIDE_WIDTH, bottom),
+            ),
+            Side::Right => egui::Rect::from_min_max(
+                egui::pos2(screen.x - SIDE_WIDTH, MENU_HEIGHT),
+                egui::pos2(screen.x, bottom),
+            ),
+            Side::Bottom => egui::Rect::from_min_max(
+                egui::pos2(if left { SIDE_WIDTH } else { 0.0 }, bottom - BOTTOM_HEIGHT),
+                egui::pos2(
+                    if right {
+                        screen.x - SIDE_WIDTH
+                    } else {
+                        screen.x
+                    },
+                    bottom,
+                ),
+            ),
+        }
+    }
+
+    fn central(&self) -> egui::Rect {
+        let [left, right, bottom] = self.occupied;
+        egui::Rect::from_min_max(
+            egui::pos2(if left { SIDE_WIDTH } else { 0.0 }, MENU_HEIGHT),
+            egui::pos2(
+                if right {
+                    self.screen.x - SIDE_WIDTH
+                } else {
+                    self.screen.x
+                },
+                if bottom {
+                    self.bottom() - BOTTOM_HEIGHT
+                } else {
+                    self.bottom()
+                },
+            ),
+        )
+    }
+
+    fn drop_zone(&self, side: Side) -> egui::Rect {
+        let (screen, bottom) = (self.screen, self.bottom());
+        match side {
+            Side::Left => egui::Rect::from_min_max(
+                egui::pos2(0.0, MENU_HEIGHT),
+                egui::pos2(DROP_ZONE, bottom),
+            ),
+            Side::Right => egui::Rect::from_min_max(
+                egui::pos2(screen.x - DROP_ZONE, MENU_HEIGHT),
+                egui::pos2(screen.x, bottom),
+            ),
+            Side::Bottom => egui::Rect::from_min_max(
+                egui::pos2(DROP_ZONE, bottom - DROP_ZONE),
+                egui::pos2(screen.x - DROP_ZONE, bottom),
+            ),
+        }
+    }
+
+    fn dock(&mut self, title: &str, side: Option<Side>) {
+        match side {
+            Some(side) => {
+                self.layout.docked.insert(title.to_owned(), side);
+                self.layout.active[side.index()] = Some(title.to_owned());
+            }
+            None => {
+                self.layout.docked.remove(title);
+            }
+        }
+        self.layout.save();
+    }
+}
+
+thread_local! {
+    static STATE: std::cell::RefCell<State> = std::cell::RefCell::new(State::new());
+}
+
+pub fn begin_frame(screen: egui::Vec2) {
+    STATE.with(|state| state.borrow_mut().screen = screen);
+}
+
+pub fn central() -> egui::Rect {
+    STATE.with(|state| state.borrow().central())
+}
+
+pub fn reset() {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.layout = Layout::default();
+        state.layout.save();
+    });
+    crate::status::message("Reset the panel layout");
+}
+
+fn grip(ui: &mut egui::Ui, title: &str) {
+    let rect = ui.allocate_space(egui::vec2(GRIP_SIZE, GRIP_SIZE));
+    let response = ui.interact(
+        rect,
+        ui.make_persistent_id(("dock_grip", title)),
+        egui::Sense::drag(),
+    );
+    for row in 0..3 {
+        let y = rect.top() + 4.0 + row as f32 * 4.0;
+        ui.painter().line_segment(
+            [
+                egui::pos2(rect.left() + 2.0, y),
+                egui::pos2(rect.right() - 2.0, y),
+            ],
+            egui::Stroke::new(1.5, GRIP_COLOR),
+        );
+    }
+    crate::status::hint_on_hover(&response, "Drag to a screen edge to dock, or away to float");
+    if response.active {
+        STATE.with(|state| state.borrow_mut().dragging = Some(title.to_owned()));
+    }
+}
+
+pub struct Panel<'a> {
+    title: String,
+    open: Option<&'a mut bool>,
+    default_pos: Option<egui::Pos2>,
+}
+
+impl<'a> Panel<'a> {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            open: None,
+            default_pos: None,
+        }
+    }
+
+    pub fn open(mut self, open: &'a mut bool) -> Self {
+        self.open = Some(open);
+        self
+    }
+
+    pub fn default_pos(mut self, default_pos: egui::Pos2) -> Self {
+        self.default_pos = Some(default_pos);
+        self
+    }
+
+    pub fn show(
+        self,
+        ctx: &std::sync::Arc<egui::Context>,
+        add_contents: impl FnOnce(&mut egui::Ui),
+    ) {
+        if self.open.as_ref().is_some_and(|open| !**open) {
+            return;
+        }
+        let title = self.title;
+        let side = STATE.with(|state| state.borrow().layout.docked.get(&title).copied());
+        let side = match side {
+            Some(side) => side,
+            None => {
+                let mut window = egui::Window::new(title.clone());
+                if let Some(open) = self.open {
+                    window = window.open(open);
+                }
+                if let Some(default_pos) = self.default_pos {
+                    window = window.default_pos(default_pos);
+                }
+                window.show(ctx, |ui| {
+                    grip(ui, &title);
+                    add_contents(ui);
+                });
+                return;
+            }
+        };
+
+        let (rect, active) = STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            state.panels[side.index()].push(title.clone());
+            let active = &mut state.layout.active[side.index()];
+            if active.is_none() {
+                *active = Some(title.clone());
+            }
+            let active = active.as_deref() == Some(title.as_str());
+            (state.rect(side), active)
+        });
+        if !active {
+            return;
+        }
+        let mut float = false;
+        let mut close = false;
+        let closable = self.open.is_some();
+        egui::Area::new(format!("dock_{}", side.name()))
+            .fixed_pos(rect.min + egui::vec2(0.0, TAB_HEIGHT))
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.allocate_space(egui::vec2(rect.width() - 16.0, 0.0));
+                    ui.horizontal(|ui| {
+                        grip(ui, &title);
+                        ui.label(title.as_str());
+                        if ui.button("Float").clicked {
+                            float = true;
+                        }
+                        if closable && ui.button("x").clicked {
+                            close = true;
+                        }
+                    });
+                    egui::ScrollArea::from_max_height(rect.height() - TAB_HEIGHT - 48.0)
+                        .show(ui, add_contents);
+                });
+            });
+        if float {
+            STATE.with(|state| state.borrow_mut().dock(&title, None));
+        }
+        if close {
+            if let Some(open) = self.open {
+                *open = false;
+            }
+        }
+    }
+}
+
+fn show_tabs(ctx: &std::sync::Arc<egui::Context>, state: &mut State) {
+    for side in Side::ALL.iter().copied() {
+        let panels = &state.panels[side.index()];
+        if panels.is_empty() {
+            continue;
+        }
+        let rect = state.rect(side);
+        let mut active = state.layout.active[side.index()]
+            .clone()
+            .unwrap_or_default();
+        let previous = active.clone();
+        egui::Area::new(format!("dock_tabs_{}", side.name()))
+            .fixed_pos(rect.min)
+            .show(ctx, |ui| {
+                ui.painter().rect_filled(
+                    egui::Rect::from_min_size(rect.min, egui::vec2(rect.width(), TAB_HEIGHT)),
+                    0.0,
+                    TAB_BACKGROUND,
+                );
+                ui.horizontal(|ui| {
+                    for title in panels.iter() {
+                        ui.radio_value(&mut active, title.clone(), title.as_str());
+                    }
+                });
+            });
+        if active != previous {
+            state.layout.active[side.index()] = Some(active);
+            state.layout.save();
+        }
+    }
+}
+
+fn show_drop_zones(ctx: &std::sync::Arc<egui::Context>, state: &mut State) {
+    let title = match &state.dragging {
+        Some(title) => title.clone(),
+        None => return,
+    };
+    let input = ctx.input();
+    let pointer = input.mouse.pos;
+    let target = pointer.and_then(|pointer| {
+        Side::ALL
+            .iter()
+            .copied()
+            .find(|side| state.drop_zone(*side).contains(pointer))
+    });
+    let painter = egui::Painter::new(
+        ctx.clone(),
+        egui::LayerId {
+            order: egui::Order::Foreground,
+            id: egui::Id::new("dock_drop_zones"),
+        },
+        ctx.input().screen_rect(),
+    );
+    for side in Side::ALL.iter().copied() {
+        let zone = state.drop_zone(side);
+        let selected = target == Some(side);
+        painter.rect_filled(zone, 4.0, DROP_FILL);
+        painter.rect_stroke(
+            zone.shrink(2.0),
+            4.0,
+            egui::Stroke::new(if selected { 3.0 } else { 1.0 }, DROP_OUTLINE),
+        );
+    }
+    if input.mouse.down {
+        return;
+    }
+    state.dragging = None;
+    let undocked = match (state.layout.docked.get(&title), pointer) {
+        (Some(side), Some(pointer)) => !state.rect(*side).contains(pointer),
+        _ => false,
+    };
+    if target.is_some() || undocked {
+        state.dock(&title, target);
+    }
+}
+
+pub fn end_frame(ctx: &std::sync::Arc<egui::Context>) {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        show_tabs(ctx, &mut state);
+        show_drop_zones(ctx, &mut state);
+        let panels = std::mem::take(&mut state.panels);
+        for side in Side::ALL.iter().copied() {
+            let panels = &panels[side.index()];
+            state.occupied[side.index()] = !panels.is_empty();
+            let active = &mut state.layout.active[side.index()];
+            if active
+                .as_ref()
+                .is_some_and(|active| !panels.contains(active))
+            {
+                *active = panels.first().cloned();
+            }
+        }
+    });
+}
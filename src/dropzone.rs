@@ -0,0 +1,129 @@
+const TEXT_EXTENSIONS: [&str; 14] = [
+    "txt", "md", "rs", "toml", "json", "ron", "yaml", "yml", "glsl", "vert", "frag", "comp",
+    "wgsl", "log",
+];
+const MODEL_EXTENSIONS: [&str; 2] = ["gltf", "glb"];
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Destination {
+    Animation,
+    Folder,
+    Font,
+    Image,
+    Model,
+    Text,
+    Unknown,
+}
+
+impl Destination {
+    fn describe(self) -> &'static str {
+        match self {
+            Destination::Animation => "Animation player",
+            Destination::Folder => "Image converter",
+            Destination::Font => "Fonts",
+            Destination::Image => "Image viewer",
+            Destination::Model => "glTF loader",
+            Destination::Text => "Text editor",
+            Destination::Unknown => "not supported",
+        }
+    }
+}
+
+pub fn destination(path: &std::path::Path) -> Destination {
+    if path.is_dir() {
+        return Destination::Folder;
+    }
+    if crate::fonts::is_font(path) {
+        return Destination::Font;
+    }
+    if crate::animation::is_animation(path) {
+        return Destination::Animation;
+    }
+    let extension = match path.extension().and_then(|extension| extension.to_str()) {
+        Some(extension) => extension.to_lowercase(),
+        None => return Destination::Unknown,
+    };
+    if image::ImageFormat::from_path(path).is_ok() {
+        Destination::Image
+    } else if MODEL_EXTENSIONS.contains(&extension.as_str()) {
+        Destination::Model
+    } else if TEXT_EXTENSIONS.contains(&extension.as_str()) {
+        Destination::Text
+    } else {
+        Destination::Unknown
+    }
+}
+
+#[derive(Default)]
+pub struct DropZone {
+    hovered: Vec<std::path::PathBuf>,
+}
+
+impl DropZone {
+    pub fn hover(&mut self, path: &std::path::Path) {
+        if !self.hovered.iter().any(|hovered| hovered == path) {
+            self.hovered.push(path.to_owned());
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        self.hovered.clear();
+    }
+
+    pub fn drop_path(&mut self, path: &std::path::Path) -> Destination {
+        self.hovered.clear();
+        let destination = destination(path);
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        let message = match destination {
+            Destination::Model => format!("{}: glTF import is not available in this build", name),
+            Destination::Unknown => format!("{}: no viewer for this file type", name),
+            other => format!("Sent {} to {}", name, other.describe()),
+        };
+        log::info!("dropped {}: {}", path.display(), message);
+        crate::status::message(message);
+        destination
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, screen: egui::Vec2) {
+        if !self.hovered.is_empty() {
+            let painter = egui::Painter::new(
+                ctx.clone(),
+                egui::LayerId {
+                    order: egui::Order::Foreground,
+                    id: egui::Id::new("drop_overlay"),
+                },
+                ctx.input().screen_rect(),
+            );
+            let rect = egui::Rect::from_min_size(egui::Pos2::default(), screen);
+            painter.rect_filled(rect, 0.0, egui::Srgba::new(0, 0, 0, 160));
+            painter.rect_stroke(
+                rect.shrink(12.0),
+                8.0,
+                egui::Stroke::new(3.0, egui::Srgba::new(90, 170, 250, 255)),
+            );
+            painter.text(
+                rect.center() - egui::vec2(0.0, 20.0),
+                (egui::Align::Center, egui::Align::Center),
+                "Drop to open".to_owned(),
+                egui::TextStyle::Heading,
+                egui::Srgba::new(255, 255, 255, 255),
+            );
+            for (index, path) in self.hovered.iter().enumerate() {
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                painter.text(
+                    rect.center() + egui::vec2(0.0, 10.0 + index as f32 * 18.0),
+                    (egui::Align::Center, egui::Align::Center),
+                    format!("{} -> {}", name, destination(path).describe()),
+                    egui::TextStyle::Body,
+                    egui::Srgba::new(220, 220, 220, 255),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,612 @@
+const WORKERS: usize = 4;
+const THUMBNAIL_SIZE: u32 = 16;
+const IMAGE_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "bmp", "gif"];
+
+pub fn format_size(bytes: u64) -> String {
+    let bytes = bytes as f64;
+    if bytes >= 1073741824.0 {
+        format!("{:.1} GiB", bytes / 1073741824.0)
+    } else if bytes >= 1048576.0 {
+        format!("{:.1} MiB", bytes / 1048576.0)
+    } else if bytes >= 1024.0 {
+        format!("{:.1} KiB", bytes / 1024.0)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+struct Entry {
+    path: std::path::PathBuf,
+    thumbnail: Option<image::RgbaImage>,
+    selected: bool,
+}
+
+struct Group {
+    size: u64,
+    hash: u64,
+    files: Vec<Entry>,
+}
+
+struct Trashed {
+    size: u64,
+    hash: u64,
+    entry: Entry,
+    location: std::path::PathBuf,
+}
+
+struct Progress {
+    stage: std::sync::Mutex<&'static str>,
+    files: std::sync::atomic::AtomicUsize,
+    candidates: std::sync::atomic::AtomicUsize,
+    hashed: std::sync::atomic::AtomicUsize,
+    cancelled: std::sync::atomic::AtomicBool,
+}
+
+struct Scan {
+    progress: std::sync::Arc<Progress>,
+    receiver: std::sync::mpsc::Receiver<Vec<Group>>,
+    started: std::time::Instant,
+}
+
+pub struct DuplicateFinder {
+    pub open: bool,
+    pub root: String,
+    pub min_size: f32,
+    scan: Option<Scan>,
+    groups: Vec<Group>,
+    undo: Vec<Vec<Trashed>>,
+    textures: std::collections::HashMap<(u64, u64), usize>,
+    status: String,
+    scan_requested: bool,
+    cancel_requested: bool,
+    delete_requested: bool,
+    confirm_delete: bool,
+    undo_requested: bool,
+}
+
+impl Default for DuplicateFinder {
+    fn default() -> Self {
+        Self {
+            open: false,
+            root: dirs::home_dir()
+                .map(|dir| dir.display().to_string())
+                .unwrap_or_default(),
+            min_size: 4.0,
+            scan: None,
+            groups: Vec::new(),
+            undo: Vec::new(),
+            textures: std::collections::HashMap::new(),
+            status: String::new(),
+            scan_requested: false,
+            cancel_requested: false,
+            delete_requested: false,
+            confirm_delete: false,
+            undo_requested: false,
+        }
+    }
+}
+
+fn walk(
+    root: &std::path::Path,
+    min_size: u64,
+    progress: &Progress,
+) -> Vec<(std::path::PathBuf, u64)> {
+    let mut files = Vec::new();
+    let mut stack = vec![root.to_owned()];
+    while let Some(directory) = stack.pop() {
+        if progress
+            .cancelled
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            break;
+        }
+        let entries = match std::fs::read_dir(&directory) {
+            Ok(entries) => entries,
+            Err(err) => {
+                log::warn!("skipping {}: {}", directory.display(), err);
+                continue;
+            }
+        };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let metadata = match entry.path().symlink_metadata() {
+                Ok(metadata) => metadata,
+                Err(_) => continue,
+            };
+            if metadata.is_dir() {
+                stack.push(entry.path());
+            } else if metadata.is_file() && metadata.len() >= min_size {
+                files.push((entry.path(), metadata.len()));
+                progress
+                    .files
+                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            }
+        }
+    }
+    files
+}
+
+fn hash_file(path: &std::path::Path) -> Option<u64> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = std::io::Read::read(&mut file, &mut buffer).ok()?;
+        if read == 0 {
+            break;
+        }
+        std::hash::Hasher::write(&mut hasher, &buffer[..read]);
+    }
+    Some(std::hash::Hasher::finish(&hasher))
+}
+
+fn thumbnail(path: &std::path::Path) -> Option<image::RgbaImage> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    if !IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+        return None;
+    }
+    Some(
+        image::open(path)
+            .ok()?
+            .thumbnail_exact(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+            .to_rgba(),
+    )
+}
+
+fn find_duplicates(
+    root: std::path::PathBuf,
+    min_size: u64,
+    progress: std::sync::Arc<Progress>,
+) -> Vec<Group> {
+    let files = walk(&root, min_size, &progress);
+
+    let mut by_size: std::collections::HashMap<u64, Vec<std::path::PathBuf>> =
+        std::collections::HashMap::new();
+    for (path, size) in files {
+        by_size.entry(size).or_default().push(path);
+    }
+    let candidates: std::sync::Arc<Vec<(u64, std::path::PathBuf)>> = std::sync::Arc::new(
+        by_size
+            .into_iter()
+            .filter(|(_, paths)| paths.len() > 1)
+            .flat_map(|(size, paths)| paths.into_iter().map(move |path| (size, path)))
+            .collect(),
+    );
+    progress
+        .candidates
+        .store(candidates.len(), std::sync::atomic::Ordering::Relaxed);
+    *progress.stage.lock().unwrap() = "Hashing";
+
+    let next = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let results = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let workers: Vec<_> = (0..WORKERS)
+        .map(|worker| {
+            let candidates = candidates.clone();
+            let next = next.clone();
+            let results = results.clone();
+            let progress = progress.clone();
+            std::thread::Builder::new()
+                .name(format!("duplicate hasher {}", worker))
+                .spawn(move || loop {
+                    if progress
+                        .cancelled
+                        .load(std::sync::atomic::Ordering::Relaxed)
+                    {
+                        break;
+                    }
+                    let index = next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    let (size, path) = match candidates.get(index) {
+                        Some(candidate) => candidate,
+                        None => break,
+                    };
+                    if let Some(hash) = hash_file(path) {
+                        results.lock().unwrap().push((*size, hash, path.clone()));
+                    }
+                    progress
+                        .hashed
+                        .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                })
+                .unwrap()
+        })
+        .collect();
+    for worker in workers {
+        worker.join().ok();
+    }
+
+    let mut by_hash: std::collections::HashMap<(u64, u64), Vec<std::path::PathBuf>> =
+        std::collections::HashMap::new();
+    for (size, hash, path) in std::mem::take(&mut *results.lock().unwrap()) {
+        by_hash.entry((size, hash)).or_default().push(path);
+    }
+    *progress.stage.lock().unwrap() = "Building previews";
+    let mut groups: Vec<Group> = by_hash
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .map(|((size, hash), mut paths)| {
+            paths.sort();
+            let preview = thumbnail(&paths[0]);
+            Group {
+                size,
+                hash,
+                files: paths
+                    .into_iter()
+                    .map(|path| Entry {
+                        path,
+                        thumbnail: preview.clone(),
+                        selected: false,
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+    groups.sort_by_key(|group| std::cmp::Reverse(group.size * (group.files.len() as u64 - 1)));
+    groups
+}
+
+fn trash_directory() -> Option<std::path::PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")).join("trash"))
+}
+
+fn move_file(from: &std::path::Path, to: &std::path::Path) -> std::io::Result<()> {
+    if std::fs::rename(from, to).is_err() {
+        std::fs::copy(from, to)?;
+        std::fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+impl DuplicateFinder {
+    fn start(&mut self) {
+        let root = std::path::PathBuf::from(&self.root);
+        if !root.is_dir() {
+            self.status = format!("{} is not a directory", root.display());
+            return;
+        }
+        let progress = std::sync::Arc::new(Progress {
+            stage: std::sync::Mutex::new("Scanning"),
+            files: std::sync::atomic::AtomicUsize::new(0),
+            candidates: std::sync::atomic::AtomicUsize::new(0),
+            hashed: std::sync::atomic::AtomicUsize::new(0),
+            cancelled: std::sync::atomic::AtomicBool::new(false),
+        });
+        let min_size = (self.min_size * 1024.0) as u64;
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let thread_progress = progress.clone();
+        std::thread::Builder::new()
+            .name("duplicate scanner".to_owned())
+            .spawn(move || {
+                sender
+                    .send(find_duplicates(root, min_size, thread_progress))
+                    .ok();
+            })
+            .unwrap();
+        self.status.clear();
+        self.groups.clear();
+        self.scan = Some(Scan {
+            progress,
+            receiver,
+            started: std::time::Instant::now(),
+        });
+    }
+
+    pub fn recreate_gpu_resources(&mut self) {
+        self.textures.clear();
+    }
+
+    pub fn upload(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        ui_pass: &mut crate::ui_pass::RenderPass,
+    ) {
+        let open = self.open;
+        let groups = &self.groups;
+        self.textures.retain(|key, texture| {
+            let shown = open
+                && groups
+                    .iter()
+                    .any(|group| group.files.len() > 1 && (group.size, group.hash) == *key);
+            if !shown {
+                ui_pass.free_user_texture(*texture);
+            }
+            shown
+        });
+        if !open {
+            return;
+        }
+        for group in groups.iter().filter(|group| group.files.len() > 1) {
+            if let Some(thumbnail) = &group.files[0].thumbnail {
+                self.textures
+                    .entry((group.size, group.hash))
+                    .or_insert_with(|| ui_pass.register_user_texture(device, queue, thumbnail));
+            }
+        }
+    }
+
+    fn poll(&mut self) {
+        let scan = match &self.scan {
+            Some(scan) => scan,
+            None => return,
+        };
+        if let Ok(groups) = scan.receiver.try_recv() {
+            let cancelled = scan
+                .progress
+                .cancelled
+                .load(std::sync::atomic::Ordering::Relaxed);
+            log::info!(
+                "found {} duplicate groups in {} ms",
+                groups.len(),
+                scan.started.elapsed().as_millis()
+            );
+            self.status = format!(
+                "{} {} files in {:.1} s",
+                if cancelled {
+                    "Cancelled after"
+                } else {
+                    "Scanned"
+                },
+                scan.progress
+                    .files
+                    .load(std::sync::atomic::Ordering::Relaxed),
+                scan.started.elapsed().as_secs_f32()
+            );
+            self.groups = groups;
+            self.scan = None;
+        }
+    }
+
+    fn delete_selected(&mut self) {
+        let directory = match trash_directory() {
+            Some(directory) => directory,
+            None => {
+                self.status = "No data directory to hold the trash".to_owned();
+                return;
+            }
+        };
+        if let Err(err) = std::fs::create_dir_all(&directory) {
+            self.status = format!("Cannot create {}: {}", directory.display(), err);
+            return;
+        }
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis();
+        let mut trashed = Vec::new();
+        let mut failed = 0;
+        let mut skipped = 0;
+        for group in self.groups.iter_mut() {
+            if group.files.iter().all(|entry| entry.selected) {
+                skipped += 1;
+                continue;
+            }
+            let (selected, kept): (Vec<Entry>, Vec<Entry>) = std::mem::take(&mut group.files)
+                .into_iter()
+                .partition(|entry| entry.selected);
+            group.files = kept;
+            for mut entry in selected {
+                let name = entry.path.file_name().unwrap_or_default().to_string_lossy();
+                let location = directory.join(format!("{}-{}-{}", stamp, trashed.len(), name));
+                match move_file(&entry.path, &location) {
+                    Ok(()) => {
+                        entry.selected = false;
+                        trashed.push(Trashed {
+                            size: group.size,
+                            hash: group.hash,
+                            entry,
+                            location,
+                        });
+                    }
+                    Err(err) => {
+                        log::warn!("failed to trash {}: {}", entry.path.display(), err);
+                        failed += 1;
+                        group.files.push(entry);
+                    }
+                }
+            }
+        }
+        self.groups.retain(|group| group.files.len() > 1);
+        self.status = format!(
+            "Moved {} files to {}{}{}",
+            trashed.len(),
+            directory.display(),
+            if failed > 0 {
+                format!(", {} failed", failed)
+            } else {
+                String::new()
+            },
+            if skipped > 0 {
+                format!(", skipped {} groups with every copy selected", skipped)
+            } else {
+                String::new()
+            }
+        );
+        if !trashed.is_empty() {
+            self.undo.push(trashed);
+        }
+    }
+
+    fn undo_delete(&mut self) {
+        let trashed = match self.undo.pop() {
+            Some(trashed) => trashed,
+            None => return,
+        };
+        let mut restored = 0;
+        for item in trashed {
+            if let Err(err) = move_file(&item.location, &item.entry.path) {
+                log::warn!("failed to restore {}: {}", item.entry.path.display(), err);
+                continue;
+            }
+            restored += 1;
+            match self
+                .groups
+                .iter_mut()
+                .find(|group| group.size == item.size && group.hash == item.hash)
+            {
+                Some(group) => group.files.push(item.entry),
+                None => self.groups.push(Group {
+                    size: item.size,
+                    hash: item.hash,
+                    files: vec![item.entry],
+                }),
+            }
+        }
+        self.status = format!("Restored {} files", restored);
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        if self.scan_requested {
+            self.scan_requested = false;
+            self.start();
+        }
+        if self.cancel_requested {
+            self.cancel_requested = false;
+            if let Some(scan) = &self.scan {
+                scan.progress
+                    .cancelled
+                    .store(true, std::sync::atomic::Ordering::Relaxed);
+            }
+        }
+        if self.delete_requested {
+            self.delete_requested = false;
+            self.delete_selected();
+        }
+        if self.undo_requested {
+            self.undo_requested = false;
+            self.undo_delete();
+        }
+        self.poll();
+
+        let mut open = self.open;
+        let root = &mut self.root;
+        let min_size = &mut self.min_size;
+        let scan = &self.scan;
+        let groups = &mut self.groups;
+        let textures = &self.textures;
+        let status = &self.status;
+        let can_undo = !self.undo.is_empty();
+        let scan_requested = &mut self.scan_requested;
+        let cancel_requested = &mut self.cancel_requested;
+        let delete_requested = &mut self.delete_requested;
+        let confirm_delete = &mut self.confirm_delete;
+        let undo_requested = &mut self.undo_requested;
+        crate::dock::Panel::new("Duplicate Finder")
+            .open(&mut open)
+            .default_pos(egui::pos2(440.0, 100.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Directory");
+                    ui.add(egui::TextEdit::new(root));
+                });
+                ui.add(egui::Slider::f32(min_size, 0.0..=10240.0).text("min size (KiB)"));
+                match scan {
+                    Some(scan) => {
+                        let progress = &scan.progress;
+                        let stage = *progress.stage.lock().unwrap();
+                        ui.label(format!(
+                            "{}: {} files, {} / {} hashed ({:.1} s)",
+                            stage,
+                            progress.files.load(std::sync::atomic::Ordering::Relaxed),
+                            progress.hashed.load(std::sync::atomic::Ordering::Relaxed),
+                            progress
+                                .candidates
+                                .load(std::sync::atomic::Ordering::Relaxed),
+                            scan.started.elapsed().as_secs_f32()
+                        ));
+                        if ui.button("Cancel").clicked {
+                            *cancel_requested = true;
+                        }
+                    }
+                    None => {
+                        if ui.button("Scan").clicked {
+                            *scan_requested = true;
+                        }
+                    }
+                }
+                if !status.is_empty() {
+                    ui.label(status.as_str());
+                }
+
+                let visible = groups.iter().filter(|group| group.files.len() > 1);
+                let wasted: u64 = visible
+                    .clone()
+                    .map(|group| group.size * (group.files.len() as u64 - 1))
+                    .sum();
+                ui.label(format!(
+                    "{} duplicate groups, {} reclaimable",
+                    visible.count(),
+                    format_size(wasted)
+                ));
+                egui::ScrollArea::from_max_height(320.0).show(ui, |ui| {
+                    for group in groups.iter_mut().filter(|group| group.files.len() > 1) {
+                        let title = format!(
+                            "{} copies of {} ({:016x})",
+                            group.files.len(),
+                            format_size(group.size),
+                            group.hash
+                        );
+                        egui::CollapsingHeader::new(title).show(ui, |ui| {
+                            if group.files[0].thumbnail.is_some() {
+                                let rect = ui.allocate_space(egui::vec2(64.0, 64.0));
+                                if let Some(texture) = textures.get(&(group.size, group.hash)) {
+                                    crate::ui_pass::paint_user_texture(
+                                        ui.painter(),
+                                        *texture,
+                                        rect,
+                                        egui::color::WHITE,
+                                    );
+                                }
+                            }
+                            if ui.button("Select all but first").clicked {
+                                for (index, entry) in group.files.iter_mut().enumerate() {
+                                    entry.selected = index > 0;
+                                }
+                            }
+                            for entry in group.files.iter_mut() {
+                                ui.checkbox(&mut entry.selected, entry.path.display().to_string());
+                            }
+                            if group.files.iter().all(|entry| entry.selected) {
+                                ui.label("Every copy is selected, this group will be skipped");
+                            }
+                        });
+                    }
+                });
+
+                let deletable = groups
+                    .iter()
+                    .filter(|group| !group.files.iter().all(|entry| entry.selected));
+                let selected = deletable
+                    .flat_map(|group| group.files.iter())
+                    .filter(|entry| entry.selected)
+                    .count();
+                let trash = trash_directory()
+                    .map_or(String::new(), |directory| directory.display().to_string());
+                ui.label(format!(
+                    "Deleted files are moved to this app's own trash folder, not the system trash: {}",
+                    trash
+                ));
+                if *confirm_delete {
+                    ui.label(format!("Move {} files to {}?", selected, trash));
+                    ui.horizontal(|ui| {
+                        if ui.button("Delete").clicked {
+                            *delete_requested = true;
+                            *confirm_delete = false;
+                        }
+                        if ui.button("Keep").clicked {
+                            *confirm_delete = false;
+                        }
+                    });
+                } else {
+                    ui.horizontal(|ui| {
+                        if selected > 0
+                            && ui.button(format!("Delete {} selected", selected)).clicked
+                        {
+                            *confirm_delete = true;
+                        }
+                        if can_undo && ui.button("Undo delete").clicked {
+                            *undo_requested = true;
+                        }
+                    });
+                }
+            });
+        self.open = open;
+    }
+}
@@ -0,0 +1,1826 @@
+const VISIBLE_ROWS: f32 = 24.0;
+const EDITOR_WIDTH: f32 = 560.0;
+const GUTTER_PADDING: f32 = 8.0;
+const PAGE_ROWS: usize = 20;
+const INDENT: &str = "    ";
+const UNDO_LIMIT: usize = 200;
+const MAX_RESULTS: usize = 200;
+const THEME: &str = "base16-ocean.dark";
+const SAMPLE_RUST: &str = "fn main() {
+    let numbers = vec![1, 2, 3];
+    // print the sum of the numbers
+    let sum: i32 = numbers.iter().sum();
+    println!(\"sum = {}\", sum);
+}
+";
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Language {
+    Rust,
+    Glsl,
+    Json,
+    Markdown,
+    Plain,
+}
+
+impl Language {
+    const ALL: [Language; 5] = [
+        Language::Rust,
+        Language::Glsl,
+        Language::Json,
+        Language::Markdown,
+        Language::Plain,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Language::Rust => "Rust",
+            Language::Glsl => "GLSL",
+            Language::Json => "JSON",
+            Language::Markdown => "Markdown",
+            Language::Plain => "Plain text",
+        }
+    }
+
+    fn syntax_extension(self) -> &'static str {
+        match self {
+            Language::Rust => "rs",
+            Language::Glsl => "c",
+            Language::Json => "json",
+            Language::Markdown => "md",
+            Language::Plain => "txt",
+        }
+    }
+
+    pub fn from_path(path: &std::path::Path) -> Self {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_lowercase())
+            .unwrap_or_default();
+        match extension.as_str() {
+            "rs" => Language::Rust,
+            "glsl" | "vert" | "frag" | "comp" | "wgsl" => Language::Glsl,
+            "json" => Language::Json,
+            "md" => Language::Markdown,
+            _ => Language::Plain,
+        }
+    }
+}
+
+type Line = Vec<(egui::Srgba, String)>;
+
+struct Highlighter {
+    syntaxes: syntect::parsing::SyntaxSet,
+    theme: syntect::highlighting::Theme,
+}
+
+impl Default for Highlighter {
+    fn default() -> Self {
+        let syntaxes = syntect::parsing::SyntaxSet::load_defaults_newlines();
+        let mut themes = syntect::highlighting::ThemeSet::load_defaults();
+        let theme = themes.themes.remove(THEME).unwrap();
+        log::info!("loaded {} syntax definitions", syntaxes.syntaxes().len());
+        Self { syntaxes, theme }
+    }
+}
+
+impl Highlighter {
+    fn highlight(&self, text: &str, language: Language) -> Vec<Line> {
+        let syntax = self
+            .syntaxes
+            .find_syntax_by_extension(language.syntax_extension())
+            .unwrap_or_else(|| self.syntaxes.find_syntax_plain_text());
+        let mut highlighter = syntect::easy::HighlightLines::new(syntax, &self.theme);
+        text.split('\n')
+            .map(|line| {
+                let line = format!("{}\n", line);
+                highlighter
+                    .highlight(&line, &self.syntaxes)
+                    .into_iter()
+                    .map(|(style, span)| {
+                        let color = style.foreground;
+                        (
+                            egui::Srgba::new(color.r, color.g, color.b, 255),
+                            span.trim_end_matches('\n').to_owned(),
+                        )
+                    })
+                    .filter(|(_, span)| !span.is_empty())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum EditKind {
+    Insert,
+    Delete,
+    Other,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct Selection {
+    anchor: usize,
+    cursor: usize,
+    preferred_column: Option<usize>,
+}
+
+impl Selection {
+    fn new(anchor: usize, cursor: usize) -> Self {
+        Self {
+            anchor,
+            cursor,
+            preferred_column: None,
+        }
+    }
+
+    fn range(&self) -> std::ops::Range<usize> {
+        self.cursor.min(self.anchor)..self.cursor.max(self.anchor)
+    }
+}
+
+struct Snapshot {
+    text: String,
+    selections: Vec<Selection>,
+    primary: usize,
+}
+
+pub struct Document {
+    pub name: String,
+    pub path: Option<std::path::PathBuf>,
+    pub language: Language,
+    text: String,
+    selections: Vec<Selection>,
+    primary: usize,
+    revision: u64,
+    saved_revision: u64,
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    last_edit: EditKind,
+    highlighted: Option<(u64, Language, Vec<Line>)>,
+    pub spell_check: bool,
+    misspelled: Option<(u64, Vec<std::ops::Range<usize>>)>,
+}
+
+impl Document {
+    pub fn new(name: &str, text: &str, language: Language) -> Self {
+        Self {
+            name: name.to_owned(),
+            path: None,
+            language,
+            text: text.replace('\t', INDENT).replace("\r\n", "\n"),
+            selections: vec![Selection::new(0, 0)],
+            primary: 0,
+            revision: 0,
+            saved_revision: 0,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            last_edit: EditKind::Other,
+            highlighted: None,
+            spell_check: language == Language::Markdown || language == Language::Plain,
+            misspelled: None,
+        }
+    }
+
+    pub fn open(path: &std::path::Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        let mut document = Self::new(&name, &text, Language::from_path(path));
+        document.path = Some(path.to_owned());
+        Ok(document)
+    }
+
+    pub fn save(&mut self) -> Result<std::path::PathBuf, String> {
+        let path = self.path.clone().ok_or_else(|| "no file name".to_owned())?;
+        std::fs::write(&path, &self.text).map_err(|err| err.to_string())?;
+        self.saved_revision = self.revision;
+        log::info!("saved {}", path.display());
+        Ok(path)
+    }
+
+    pub fn dirty(&self) -> bool {
+        self.revision != self.saved_revision
+    }
+
+    pub fn selection(&self) -> std::ops::Range<usize> {
+        self.selections[self.primary].range()
+    }
+
+    pub fn selected_text(&self) -> &str {
+        &self.text[self.selection()]
+    }
+
+    pub fn cursor_count(&self) -> usize {
+        self.selections.len()
+    }
+
+    fn copied_text(&self) -> String {
+        self.selections
+            .iter()
+            .map(|selection| &self.text[selection.range()])
+            .collect::<Vec<&str>>()
+            .join("\n")
+    }
+
+    pub fn set_selection(&mut self, range: std::ops::Range<usize>) {
+        let length = self.text.len();
+        self.selections = vec![Selection::new(
+            range.start.min(length),
+            range.end.min(length),
+        )];
+        self.primary = 0;
+    }
+
+    fn collapse(&mut self) -> bool {
+        if self.selections.len() == 1 {
+            return false;
+        }
+        self.selections = vec![self.selections[self.primary]];
+        self.primary = 0;
+        true
+    }
+
+    fn normalize(&mut self) {
+        let mut tagged: Vec<(Selection, bool)> = self
+            .selections
+            .iter()
+            .enumerate()
+            .map(|(index, selection)| (*selection, index == self.primary))
+            .collect();
+        tagged.sort_by_key(|(selection, _)| selection.range().start);
+        let mut merged: Vec<(Selection, bool)> = Vec::with_capacity(tagged.len());
+        for (selection, primary) in tagged {
+            if let Some((last, last_primary)) = merged.last_mut() {
+                let (a, b) = (last.range(), selection.range());
+                if b.start < a.end || b.start == a.start {
+                    let (start, end) = (a.start, a.end.max(b.end));
+                    *last = if last.cursor >= last.anchor {
+                        Selection::new(start, end)
+                    } else {
+                        Selection::new(end, start)
+                    };
+                    *last_primary |= primary;
+                    continue;
+                }
+            }
+            merged.push((selection, primary));
+        }
+        self.primary = merged.iter().position(|(_, primary)| *primary).unwrap_or(0);
+        self.selections = merged.into_iter().map(|(selection, _)| selection).collect();
+    }
+
+    fn checkpoint(&mut self, kind: EditKind) {
+        if kind == EditKind::Other || kind != self.last_edit {
+            if self.undo.len() == UNDO_LIMIT {
+                self.undo.remove(0);
+            }
+            self.undo.push(Snapshot {
+                text: self.text.clone(),
+                selections: self.selections.clone(),
+                primary: self.primary,
+            });
+            self.redo.clear();
+        }
+        self.last_edit = kind;
+    }
+
+    fn apply(&mut self, kind: EditKind, edits: Vec<(std::ops::Range<usize>, String)>) {
+        if edits
+            .iter()
+            .all(|(range, text)| range.is_empty() && text.is_empty())
+        {
+            return;
+        }
+        self.checkpoint(kind);
+        let mut order: Vec<usize> = (0..edits.len()).collect();
+        order.sort_by_key(|index| edits[*index].0.start);
+        let mut text = String::with_capacity(self.text.len());
+        let mut last = 0;
+        let mut carets = vec![0; edits.len()];
+        for index in order {
+            let (range, insert) = &edits[index];
+            let start = range.start.max(last);
+            text.push_str(&self.text[last..start]);
+            text.push_str(&insert.replace('\t', INDENT).replace("\r\n", "\n"));
+            carets[index] = text.len();
+            last = range.end.max(start);
+        }
+        text.push_str(&self.text[last..]);
+        self.text = text;
+        self.selections = carets
+            .into_iter()
+            .map(|caret| Selection::new(caret, caret))
+            .collect();
+        self.primary = self.primary.min(self.selections.len() - 1);
+        self.revision += 1;
+        self.normalize();
+    }
+
+    pub fn insert(&mut self, text: &str) {
+        let kind = if text.chars().count() == 1 && text != "\n" {
+            EditKind::Insert
+        } else {
+            EditKind::Other
+        };
+        let pieces: Vec<&str> = text.trim_end_matches('\n').split('\n').collect();
+        let distribute = self.selections.len() > 1 && pieces.len() == self.selections.len();
+        let edits = self
+            .selections
+            .iter()
+            .enumerate()
+            .map(|(index, selection)| {
+                let piece = if distribute { pieces[index] } else { text };
+                (selection.range(), piece.to_owned())
+            })
+            .collect();
+        self.apply(kind, edits);
+    }
+
+    fn insert_line_break(&mut self) {
+        let edits = self
+            .selections
+            .iter()
+            .map(|selection| {
+                let (line, _) = self.line_column(selection.cursor);
+                let start = self.line_starts()[line];
+                let indent: String = self.text[start..]
+                    .chars()
+                    .take_while(|character| *character == ' ')
+                    .collect();
+                (selection.range(), format!("\n{}", indent))
+            })
+            .collect();
+        self.apply(EditKind::Other, edits);
+    }
+
+    pub fn replace_text(&mut self, range: std::ops::Range<usize>, text: &str) {
+        self.set_selection(range.clone());
+        self.apply(EditKind::Other, vec![(range, text.to_owned())]);
+    }
+
+    fn delete(&mut self, forward: bool) {
+        let edits = self
+            .selections
+            .iter()
+            .map(|selection| {
+                let range = selection.range();
+                let range = if !range.is_empty() {
+                    range
+                } else if forward {
+                    selection.cursor..self.next_boundary(selection.cursor)
+                } else {
+                    self.previous_boundary(selection.cursor)..selection.cursor
+                };
+                (range, String::new())
+            })
+            .collect();
+        self.apply(EditKind::Delete, edits);
+    }
+
+    fn delete_selections(&mut self) {
+        let edits = self
+            .selections
+            .iter()
+            .map(|selection| (selection.range(), String::new()))
+            .collect();
+        self.apply(EditKind::Other, edits);
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) -> Snapshot {
+        let current = Snapshot {
+            text: std::mem::replace(&mut self.text, snapshot.text),
+            selections: std::mem::replace(&mut self.selections, snapshot.selections),
+            primary: self.primary,
+        };
+        self.primary = snapshot.primary;
+        self.revision += 1;
+        self.last_edit = EditKind::Other;
+        current
+    }
+
+    pub fn undo(&mut self) {
+        if let Some(snapshot) = self.undo.pop() {
+            let current = self.restore(snapshot);
+            self.redo.push(current);
+        }
+    }
+
+    pub fn redo(&mut self) {
+        if let Some(snapshot) = self.redo.pop() {
+            let current = self.restore(snapshot);
+            self.undo.push(current);
+        }
+    }
+
+    fn previous_boundary(&self, position: usize) -> usize {
+        self.text[..position]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_boundary(&self, position: usize) -> usize {
+        self.text[position..]
+            .chars()
+            .next()
+            .map_or(position, |character| position + character.len_utf8())
+    }
+
+    fn line_starts(&self) -> Vec<usize> {
+        std::iter::once(0)
+            .chain(self.text.match_indices('\n').map(|(index, _)| index + 1))
+            .collect()
+    }
+
+    fn line_end(&self, start: usize) -> usize {
+        self.text[start..]
+            .find('\n')
+            .map_or(self.text.len(), |offset| start + offset)
+    }
+
+    fn line_column(&self, position: usize) -> (usize, usize) {
+        let starts = self.line_starts();
+        let line = match starts.binary_search(&position) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        };
+        let column = self.text[starts[line]..position].chars().count();
+        (line, column)
+    }
+
+    fn position(&self, line: usize, column: usize) -> usize {
+        let starts = self.line_starts();
+        let start = match starts.get(line) {
+            Some(start) => *start,
+            None => return self.text.len(),
+        };
+        let end = self.line_end(start);
+        self.text[start..end]
+            .char_indices()
+            .nth(column)
+            .map_or(end, |(offset, _)| start + offset)
+    }
+
+    fn word_range(&self, position: usize) -> std::ops::Range<usize> {
+        let word = |character: char| character.is_alphanumeric() || character == '_';
+        let start = self.text[..position]
+            .char_indices()
+            .rev()
+            .take_while(|(_, character)| word(*character))
+            .last()
+            .map_or(position, |(index, _)| index);
+        let end = self.text[position..]
+            .char_indices()
+            .find(|(_, character)| !word(*character))
+            .map_or(self.text.len(), |(offset, _)| position + offset);
+        start..end
+    }
+
+    fn move_all<F>(&mut self, extend: bool, target: F)
+    where
+        F: Fn(&Self, &Selection) -> usize,
+    {
+        let positions: Vec<usize> = self
+            .selections
+            .iter()
+            .map(|selection| target(self, selection))
+            .collect();
+        for (selection, position) in self.selections.iter_mut().zip(positions) {
+            selection.cursor = position;
+            if !extend {
+                selection.anchor = position;
+            }
+            selection.preferred_column = None;
+        }
+        self.last_edit = EditKind::Other;
+        self.normalize();
+    }
+
+    fn move_vertical(&mut self, rows: isize, extend: bool) {
+        let lines = self.line_starts().len() as isize;
+        let moved: Vec<(usize, usize)> = self
+            .selections
+            .iter()
+            .map(|selection| {
+                let (line, column) = self.line_column(selection.cursor);
+                let column = selection.preferred_column.unwrap_or(column);
+                let target = (line as isize + rows).max(0).min(lines - 1) as usize;
+                (self.position(target, column), column)
+            })
+            .collect();
+        for (selection, (position, column)) in self.selections.iter_mut().zip(moved) {
+            selection.cursor = position;
+            if !extend {
+                selection.anchor = position;
+            }
+            selection.preferred_column = Some(column);
+        }
+        self.last_edit = EditKind::Other;
+        self.normalize();
+    }
+
+    fn extend_primary(&mut self, position: usize) {
+        self.selections[self.primary].cursor = position;
+        self.selections[self.primary].preferred_column = None;
+        self.normalize();
+    }
+
+    fn toggle_caret(&mut self, position: usize) {
+        if self.selections.len() > 1 {
+            if let Some(index) = self.selections.iter().position(|selection| {
+                let range = selection.range();
+                range.start <= position && position <= range.end
+            }) {
+                self.selections.remove(index);
+                self.primary = self.selections.len() - 1;
+                return;
+            }
+        }
+        self.selections.push(Selection::new(position, position));
+        self.primary = self.selections.len() - 1;
+        self.normalize();
+    }
+
+    fn add_caret_vertical(&mut self, rows: isize) {
+        let selection = self.selections[self.primary];
+        let (line, column) = self.line_column(selection.cursor);
+        let column = selection.preferred_column.unwrap_or(column);
+        let target = line as isize + rows;
+        if target < 0 || target >= self.line_starts().len() as isize {
+            return;
+        }
+        let position = self.position(target as usize, column);
+        let mut caret = Selection::new(position, position);
+        caret.preferred_column = Some(column);
+        self.selections.push(caret);
+        self.primary = self.selections.len() - 1;
+        self.normalize();
+    }
+
+    fn select_next_occurrence(&mut self) {
+        let primary = self.selections[self.primary];
+        if primary.range().is_empty() {
+            let word = self.word_range(primary.cursor);
+            if !word.is_empty() {
+                self.selections[self.primary] = Selection::new(word.start, word.end);
+            }
+            return;
+        }
+        let needle = self.text[primary.range()].to_owned();
+        let after = self
+            .selections
+            .iter()
+            .map(|selection| selection.range().end)
+            .max()
+            .unwrap_or(0);
+        let found = self.text[after..]
+            .find(&needle)
+            .map(|offset| after + offset)
+            .or_else(|| self.text.find(&needle));
+        if let Some(start) = found {
+            let range = start..start + needle.len();
+            if self
+                .selections
+                .iter()
+                .any(|selection| selection.range() == range)
+            {
+                return;
+            }
+            self.selections.push(Selection::new(range.start, range.end));
+            self.primary = self.selections.len() - 1;
+            self.normalize();
+        }
+    }
+
+    fn column_select(&mut self, from: (usize, usize), to: (usize, usize)) {
+        let (first, last) = (from.0.min(to.0), from.0.max(to.0));
+        self.selections = (first..=last)
+            .map(|line| Selection::new(self.position(line, from.1), self.position(line, to.1)))
+            .collect();
+        self.primary = if to.0 >= from.0 {
+            self.selections.len() - 1
+        } else {
+            0
+        };
+    }
+
+    fn highlighted(&mut self, highlighter: &Highlighter) -> &[Line] {
+        let stale = self
+            .highlighted
+            .as_ref()
+            .is_none_or(|(revision, language, _)| {
+                *revision != self.revision || *language != self.language
+            });
+        if stale {
+            let lines = highlighter.highlight(&self.text, self.language);
+            self.highlighted = Some((self.revision, self.language, lines));
+        }
+        &self.highlighted.as_ref().unwrap().2
+    }
+
+    fn misspelled(&mut self, dictionary: &crate::spell::Dictionary) -> &[std::ops::Range<usize>] {
+        if self.misspelled.as_ref().map(|(revision, _)| *revision) != Some(self.revision) {
+            let ranges = crate::spell::words(&self.text)
+                .filter(|range| !dictionary.check(&self.text[range.clone()]))
+                .collect();
+            self.misspelled = Some((self.revision, ranges));
+        }
+        &self.misspelled.as_ref().unwrap().1
+    }
+
+    pub fn find_all(&self, pattern: &regex::Regex) -> Vec<std::ops::Range<usize>> {
+        pattern
+            .find_iter(&self.text)
+            .map(|found| found.range())
+            .collect()
+    }
+}
+
+#[derive(Clone)]
+enum EditorAction {
+    Correct(std::ops::Range<usize>, String),
+    Learn(String),
+    Cut,
+    Copy,
+    Paste,
+    SelectAll,
+    SelectNextOccurrence,
+    CollapseCursors,
+    SetLanguage(Language),
+    Find,
+    InsertSnippet,
+}
+
+fn context_items(
+    document: &Document,
+    position: usize,
+    misspelled: &[std::ops::Range<usize>],
+    dictionary: Option<&crate::spell::Dictionary>,
+) -> Vec<crate::menu::Item<EditorAction>> {
+    let mut items = Vec::new();
+    let word = misspelled
+        .iter()
+        .find(|range| range.start <= position && position <= range.end);
+    if let (Some(range), Some(dictionary)) = (word, dictionary) {
+        let text = &document.text[range.clone()];
+        let suggestions = dictionary.suggest(text);
+        if suggestions.is_empty() {
+            items.push(crate::menu::Item::label("No suggestions"));
+        }
+        for suggestion in suggestions {
+            items.push(crate::menu::Item::action(
+                suggestion.clone(),
+                EditorAction::Correct(range.clone(), suggestion),
+            ));
+        }
+        items.push(
+            crate::menu::Item::action(
+                format!("Add \"{}\" to dictionary", text),
+                EditorAction::Learn(text.to_owned()),
+            )
+            .icon('+'),
+        );
+        items.push(crate::menu::Item::separator());
+    }
+    let has_selection = document
+        .selections
+        .iter()
+        .any(|selection| !selection.range().is_empty());
+    items.push(
+        crate::menu::Item::action("Cut", EditorAction::Cut)
+            .icon('✂')
+            .shortcut(crate::menu::command_shortcut("X"))
+            .enabled(has_selection),
+    );
+    items.push(
+        crate::menu::Item::action("Copy", EditorAction::Copy)
+            .icon('⎘')
+            .shortcut(crate::menu::command_shortcut("C"))
+            .enabled(has_selection),
+    );
+    items.push(
+        crate::menu::Item::action("Paste", EditorAction::Paste)
+            .icon('📋')
+            .shortcut(crate::menu::command_shortcut("V")),
+    );
+    items.push(
+        crate::menu::Item::action("Select all", EditorAction::SelectAll)
+            .shortcut(crate::menu::command_shortcut("A")),
+    );
+    items.push(crate::menu::Item::submenu(
+        "Selection",
+        vec![
+            crate::menu::Item::action("Add next occurrence", EditorAction::SelectNextOccurrence)
+                .shortcut(crate::menu::command_shortcut("D")),
+            crate::menu::Item::action("Collapse cursors", EditorAction::CollapseCursors)
+                .shortcut("Escape")
+                .enabled(document.cursor_count() > 1 || has_selection),
+        ],
+    ));
+    items.push(crate::menu::Item::separator());
+    items.push(crate::menu::Item::submenu(
+        "Language",
+        Language::ALL
+            .iter()
+            .map(|language| {
+                let item = crate::menu::Item::action(
+                    language.name(),
+                    EditorAction::SetLanguage(*language),
+                );
+                if *language == document.language {
+                    item.icon('✔')
+                } else {
+                    item
+                }
+            })
+            .collect(),
+    ));
+    items.push(
+        crate::menu::Item::action("Find and replace", EditorAction::Find)
+            .icon('🔍')
+            .shortcut(crate::menu::command_shortcut("F")),
+    );
+    items.push(
+        crate::menu::Item::action("Insert snippet", EditorAction::InsertSnippet)
+            .shortcut(crate::menu::command_shortcut("J")),
+    );
+    items
+}
+
+pub struct Editor {
+    pub open: bool,
+    pub snippet_requested: bool,
+    pub paste_requested: bool,
+    documents: Vec<Document>,
+    active: usize,
+    highlighter: Option<Highlighter>,
+    focused: bool,
+    show_find: bool,
+    find: String,
+    replace: String,
+    case_sensitive: bool,
+    regex: bool,
+    all_documents: bool,
+    path: String,
+    status: String,
+    copied: Option<String>,
+    caret: Option<egui::Pos2>,
+    drag: Option<(usize, usize, bool)>,
+    spell: Option<Result<crate::spell::Dictionary, String>>,
+    context_menu: crate::menu::ContextMenu<EditorAction>,
+    context_revision: (usize, u64),
+}
+
+impl Default for Editor {
+    fn default() -> Self {
+        Self {
+            open: false,
+            snippet_requested: false,
+            paste_requested: false,
+            documents: vec![
+                Document::new("sample.rs", SAMPLE_RUST, Language::Rust),
+                Document::new(
+                    "blit.frag",
+                    include_str!("shaders/blit.frag"),
+                    Language::Glsl,
+                ),
+            ],
+            active: 0,
+            highlighter: None,
+            focused: false,
+            show_find: false,
+            find: String::new(),
+            replace: String::new(),
+            case_sensitive: false,
+            regex: false,
+            all_documents: false,
+            path: String::new(),
+            status: String::new(),
+            copied: None,
+            caret: None,
+            drag: None,
+            spell: None,
+            context_menu: crate::menu::ContextMenu::default(),
+            context_revision: (0, 0),
+        }
+    }
+}
+
+impl Editor {
+    pub fn focused(&self) -> bool {
+        self.open && self.focused
+    }
+
+    pub fn caret(&self) -> Option<egui::Pos2> {
+        self.caret.filter(|_| self.focused())
+    }
+
+    pub fn active_document(&mut self) -> Option<&mut Document> {
+        self.documents.get_mut(self.active)
+    }
+
+    pub fn documents(&self) -> &[Document] {
+        &self.documents
+    }
+
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if index < self.documents.len() {
+            self.active = index;
+            self.open = true;
+        }
+    }
+
+    pub fn close_document(&mut self, index: usize) {
+        if self.documents.len() <= 1 || index >= self.documents.len() {
+            return;
+        }
+        self.documents.remove(index);
+        if self.active > index {
+            self.active -= 1;
+        }
+        self.active = self.active.min(self.documents.len() - 1);
+    }
+
+    pub fn move_document(&mut self, from: usize, to: usize) {
+        if from >= self.documents.len() || to >= self.documents.len() {
+            return;
+        }
+        let document = self.documents.remove(from);
+        self.documents.insert(to, document);
+        self.active = if self.active == from {
+            to
+        } else if from < self.active && self.active <= to {
+            self.active - 1
+        } else if to <= self.active && self.active < from {
+            self.active + 1
+        } else {
+            self.active
+        };
+    }
+
+    pub fn open_path(&mut self, path: &std::path::Path) {
+        if let Some(index) = self
+            .documents
+            .iter()
+            .position(|document| document.path.as_deref() == Some(path))
+        {
+            self.active = index;
+            self.open = true;
+            return;
+        }
+        match Document::open(path) {
+            Ok(document) => {
+                self.status = format!("Opened {}", path.display());
+                self.documents.push(document);
+                self.active = self.documents.len() - 1;
+                self.open = true;
+            }
+            Err(err) => self.status = format!("Cannot open {}: {}", path.display(), err),
+        }
+    }
+
+    pub fn paste(&mut self, text: &str) {
+        if let Some(document) = self.active_document() {
+            document.insert(text);
+        }
+    }
+
+    pub fn received_character(&mut self, character: char) {
+        if character.is_control() {
+            return;
+        }
+        if let Some(document) = self.active_document() {
+            let mut buffer = [0u8; 4];
+            document.insert(character.encode_utf8(&mut buffer));
+        }
+    }
+
+    fn save(&mut self) {
+        let document = match self.documents.get_mut(self.active) {
+            Some(document) => document,
+            None => return,
+        };
+        if !self.path.is_empty() {
+            document.path = Some(std::path::PathBuf::from(&self.path));
+            document.name = document
+                .path
+                .as_ref()
+                .and_then(|path| path.file_name())
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+        }
+        self.status = match document.save() {
+            Ok(path) => format!("Saved {}", path.display()),
+            Err(err) => format!("Cannot save {}: {}", document.name, err),
+        };
+    }
+
+    fn pattern(&self) -> Result<Option<regex::Regex>, String> {
+        if self.find.is_empty() {
+            return Ok(None);
+        }
+        let source = if self.regex {
+            self.find.clone()
+        } else {
+            regex::escape(&self.find)
+        };
+        regex::RegexBuilder::new(&source)
+            .case_insensitive(!self.case_sensitive)
+            .multi_line(true)
+            .build()
+            .map(Some)
+            .map_err(|err| err.to_string())
+    }
+
+    fn expand(&self, pattern: &regex::Regex, text: &str, range: &std::ops::Range<usize>) -> String {
+        if !self.regex {
+            return self.replace.clone();
+        }
+        let mut expanded = String::new();
+        if let Some(captures) = pattern
+            .captures_iter(text)
+            .find(|captures| captures.get(0).map(|found| found.range()) == Some(range.clone()))
+        {
+            captures.expand(&self.replace, &mut expanded);
+        }
+        expanded
+    }
+
+    fn find_next(&mut self, backwards: bool) {
+        let pattern = match self.pattern() {
+            Ok(Some(pattern)) => pattern,
+            Ok(None) => return,
+            Err(err) => {
+                self.status = err;
+                return;
+            }
+        };
+        let document = match self.documents.get_mut(self.active) {
+            Some(document) => document,
+            None => return,
+        };
+        let matches = document.find_all(&pattern);
+        let selection = document.selection();
+        let found = if backwards {
+            matches
+                .iter()
+                .rev()
+                .find(|range| range.start < selection.start)
+                .or_else(|| matches.last())
+        } else {
+            matches
+                .iter()
+                .find(|range| range.start > selection.start)
+                .or_else(|| matches.first())
+        };
+        match found {
+            Some(range) => document.set_selection(range.clone()),
+            None => self.status = format!("{:?} not found", self.find),
+        }
+    }
+
+    fn replace_one(&mut self) {
+        let pattern = match self.pattern() {
+            Ok(Some(pattern)) => pattern,
+            _ => return,
+        };
+        if let Some(document) = self.documents.get(self.active) {
+            let selection = document.selection();
+            if document.find_all(&pattern).contains(&selection) {
+                let replacement = self.expand(&pattern, &document.text, &selection);
+                self.documents[self.active].replace_text(selection, &replacement);
+            }
+        }
+        self.find_next(false);
+    }
+
+    fn replace_all(&mut self) {
+        let pattern = match self.pattern() {
+            Ok(Some(pattern)) => pattern,
+            Ok(None) => return,
+            Err(err) => {
+                self.status = err;
+                return;
+            }
+        };
+        let document = match self.documents.get_mut(self.active) {
+            Some(document) => document,
+            None => return,
+        };
+        let count = document.find_all(&pattern).len();
+        if count == 0 {
+            self.status = format!("{:?} not found", self.find);
+            return;
+        }
+        let text = if self.regex {
+            pattern.replace_all(&document.text, self.replace.as_str())
+        } else {
+            pattern.replace_all(&document.text, regex::NoExpand(&self.replace))
+        }
+        .into_owned();
+        let length = document.text.len();
+        document.replace_text(0..length, &text);
+        document.set_selection(0..0);
+        self.status = format!("Replaced {} occurrences", count);
+    }
+
+    pub fn key_input(
+        &mut self,
+        input: &winit::event::KeyboardInput,
+        modifiers: winit::event::ModifiersState,
+    ) {
+        if input.state != winit::event::ElementState::Pressed {
+            return;
+        }
+        let key = match input.virtual_keycode {
+            Some(key) => key,
+            None => return,
+        };
+        let extend = modifiers.shift();
+        let command = crate::clipboard::command(modifiers);
+        match key {
+            winit::event::VirtualKeyCode::F if command => {
+                self.show_find = !self.show_find;
+                return;
+            }
+            winit::event::VirtualKeyCode::S if command => {
+                self.save();
+                return;
+            }
+            winit::event::VirtualKeyCode::J if command => {
+                self.snippet_requested = true;
+                return;
+            }
+            _ => {}
+        }
+        let document = match self.documents.get_mut(self.active) {
+            Some(document) => document,
+            None => return,
+        };
+        match key {
+            winit::event::VirtualKeyCode::A if command => {
+                let length = document.text.len();
+                document.set_selection(0..length);
+            }
+            winit::event::VirtualKeyCode::C if command => {
+                self.copied = Some(document.copied_text());
+            }
+            winit::event::VirtualKeyCode::X if command => {
+                let copied = document.copied_text();
+                if !copied.is_empty() {
+                    self.copied = Some(copied);
+                    document.delete_selections();
+                }
+            }
+            winit::event::VirtualKeyCode::D if command => document.select_next_occurrence(),
+            winit::event::VirtualKeyCode::Z if command && extend => document.redo(),
+            winit::event::VirtualKeyCode::Z if command => document.undo(),
+            winit::event::VirtualKeyCode::Y if command => document.redo(),
+            winit::event::VirtualKeyCode::Up if command && modifiers.alt() => {
+                document.add_caret_vertical(-1)
+            }
+            winit::event::VirtualKeyCode::Down if command && modifiers.alt() => {
+                document.add_caret_vertical(1)
+            }
+            winit::event::VirtualKeyCode::Left => {
+                document.move_all(extend, |document, selection| {
+                    let range = selection.range();
+                    if !extend && !range.is_empty() {
+                        range.start
+                    } else {
+                        document.previous_boundary(selection.cursor)
+                    }
+                })
+            }
+            winit::event::VirtualKeyCode::Right => {
+                document.move_all(extend, |document, selection| {
+                    let range = selection.range();
+                    if !extend && !range.is_empty() {
+                        range.end
+                    } else {
+                        document.next_boundary(selection.cursor)
+                    }
+                })
+            }
+            winit::event::VirtualKeyCode::Up => document.move_vertical(-1, extend),
+            winit::event::VirtualKeyCode::Down => document.move_vertical(1, extend),
+            winit::event::VirtualKeyCode::PageUp => {
+                document.move_vertical(-(PAGE_ROWS as isize), extend)
+            }
+            winit::event::VirtualKeyCode::PageDown => {
+                document.move_vertical(PAGE_ROWS as isize, extend)
+            }
+            winit::event::VirtualKeyCode::Home => {
+                document.move_all(extend, |document, selection| {
+                    if command {
+                        0
+                    } else {
+                        let (line, _) = document.line_column(selection.cursor);
+                        document.line_starts()[line]
+                    }
+                })
+            }
+            winit::event::VirtualKeyCode::End => {
+                document.move_all(extend, |document, selection| {
+                    if command {
+                        document.text.len()
+                    } else {
+                        document.line_end(selection.cursor)
+                    }
+                })
+            }
+            winit::event::VirtualKeyCode::Back => document.delete(false),
+            winit::event::VirtualKeyCode::Delete => document.delete(true),
+            winit::event::VirtualKeyCode::Return | winit::event::VirtualKeyCode::NumpadEnter => {
+                document.insert_line_break()
+            }
+            winit::event::VirtualKeyCode::Tab => document.insert(INDENT),
+            winit::event::VirtualKeyCode::Escape if !document.collapse() => self.focused = false,
+            _ => {}
+        }
+    }
+
+    fn run_context_action(&mut self, action: EditorAction) {
+        if let EditorAction::Learn(word) = &action {
+            if let Some(Ok(dictionary)) = &mut self.spell {
+                dictionary.add_personal(word);
+            }
+            for document in self.documents.iter_mut() {
+                document.misspelled = None;
+            }
+            return;
+        }
+        match action {
+            EditorAction::Paste => self.paste_requested = true,
+            EditorAction::Find => self.show_find = true,
+            EditorAction::InsertSnippet => self.snippet_requested = true,
+            _ => {}
+        }
+        let document = match self.documents.get_mut(self.active) {
+            Some(document) => document,
+            None => return,
+        };
+        match action {
+            EditorAction::Correct(range, suggestion) => document.replace_text(range, &suggestion),
+            EditorAction::Cut => {
+                self.copied = Some(document.copied_text());
+                document.delete_selections();
+            }
+            EditorAction::Copy => self.copied = Some(document.copied_text()),
+            EditorAction::SelectAll => {
+                let length = document.text.len();
+                document.set_selection(0..length);
+            }
+            EditorAction::SelectNextOccurrence => document.select_next_occurrence(),
+            EditorAction::CollapseCursors => {
+                document.collapse();
+            }
+            EditorAction::SetLanguage(language) => document.language = language,
+            _ => {}
+        }
+        self.focused = true;
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &std::sync::Arc<egui::Context>,
+        modifiers: winit::event::ModifiersState,
+    ) {
+        if !self.open {
+            self.focused = false;
+            return;
+        }
+        if self.spell.is_none() && self.documents.iter().any(|document| document.spell_check) {
+            self.spell = Some(crate::spell::Dictionary::load());
+        }
+        let dictionary = match &self.spell {
+            Some(Ok(dictionary)) => Some(dictionary),
+            _ => None,
+        };
+        let spell_error = match &self.spell {
+            Some(Err(err)) => Some(err.as_str()),
+            _ => None,
+        };
+        let secondary_clicked = crate::menu::secondary_clicked();
+        if self.context_menu.is_open()
+            && self
+                .documents
+                .get(self.context_revision.0)
+                .map(|document| (self.active, document.revision))
+                != Some(self.context_revision)
+        {
+            self.context_menu.close();
+        }
+        if let Some(copied) = self.copied.take() {
+            ctx.output().copied_text = copied;
+        }
+
+        let pattern = if self.show_find {
+            self.pattern()
+        } else {
+            Ok(None)
+        };
+        let highlighter = self.highlighter.get_or_insert_with(Highlighter::default);
+
+        let mut open = self.open;
+        let mut jump = None;
+        let mut context_menu = None;
+        let mut caret = None;
+        let mut find_next = false;
+        let mut find_previous = false;
+        let mut replace_one = false;
+        let mut replace_all = false;
+        let mut open_requested = false;
+        let mut save_requested = false;
+        let mut new_requested = false;
+        let mut close_requested = false;
+        let documents = &mut self.documents;
+        let active = &mut self.active;
+        let focused = &mut self.focused;
+        let drag = &mut self.drag;
+        let show_find = &mut self.show_find;
+        let find = &mut self.find;
+        let replace = &mut self.replace;
+        let case_sensitive = &mut self.case_sensitive;
+        let regex = &mut self.regex;
+        let all_documents = &mut self.all_documents;
+        let path = &mut self.path;
+        let status = &self.status;
+        crate::dock::Panel::new("Code Editor")
+            .open(&mut open)
+            .default_pos(egui::pos2(300.0, 80.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    for (index, document) in documents.iter().enumerate() {
+                        let label = format!(
+                            "{}{}",
+                            document.name,
+                            if document.dirty() { " *" } else { "" }
+                        );
+                        if ui.radio(*active == index, label).clicked {
+                            *active = index;
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::new(path));
+                    if ui.button("Open").clicked {
+                        open_requested = true;
+                    }
+                    if ui.button("Save").clicked {
+                        save_requested = true;
+                    }
+                    if ui.button("New").clicked {
+                        new_requested = true;
+                    }
+                    if documents.len() > 1 && ui.button("Close").clicked {
+                        close_requested = true;
+                    }
+                    if ui.button("Find").clicked {
+                        *show_find = !*show_find;
+                    }
+                });
+                let document = match documents.get_mut(*active) {
+                    Some(document) => document,
+                    None => return,
+                };
+                ui.horizontal(|ui| {
+                    for language in Language::ALL.iter() {
+                        ui.radio_value(&mut document.language, *language, language.name());
+                    }
+                    ui.checkbox(&mut document.spell_check, "Spell check");
+                });
+                if let (true, Some(err)) = (document.spell_check, spell_error) {
+                    ui.label(format!("Spell check unavailable: {}", err));
+                }
+
+                let matches = if *show_find {
+                    ui.horizontal(|ui| {
+                        ui.label("Find");
+                        ui.add(egui::TextEdit::new(find));
+                        if ui.button("Previous").clicked {
+                            find_previous = true;
+                        }
+                        if ui.button("Next").clicked {
+                            find_next = true;
+                        }
+                        ui.checkbox(case_sensitive, "Match case");
+                        ui.checkbox(regex, "Regex");
+                        ui.checkbox(all_documents, "All documents");
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Replace");
+                        ui.add(egui::TextEdit::new(replace));
+                        if ui.button("Replace").clicked {
+                            replace_one = true;
+                        }
+                        if ui.button("Replace all").clicked {
+                            replace_all = true;
+                        }
+                    });
+                    match &pattern {
+                        Ok(Some(pattern)) => document.find_all(pattern),
+                        _ => Vec::new(),
+                    }
+                } else {
+                    Vec::new()
+                };
+                match &pattern {
+                    Ok(Some(_)) => {
+                        ui.label(format!("{} matches", matches.len()));
+                    }
+                    Err(err) => {
+                        ui.add(
+                            egui::Label::new(format!("Invalid pattern: {}", err))
+                                .text_color(egui::Srgba::new(230, 110, 100, 255)),
+                        );
+                    }
+                    Ok(None) => {}
+                }
+
+                let font = &ui.fonts()[egui::TextStyle::Monospace];
+                let row_height = font.line_spacing();
+                let char_width = font.layout_single_line("0".repeat(8)).size.x / 8.0;
+                let selections: Vec<std::ops::Range<usize>> = document
+                    .selections
+                    .iter()
+                    .map(|selection| selection.range())
+                    .collect();
+                let carets: Vec<(usize, usize)> = document
+                    .selections
+                    .iter()
+                    .map(|selection| document.line_column(selection.cursor))
+                    .collect();
+                let starts = document.line_starts();
+                let (cursor_line, cursor_column) = carets[document.primary];
+                let cursors = carets.len();
+                let digits = starts.len().to_string().len();
+                let gutter = char_width * digits as f32 + GUTTER_PADDING * 2.0;
+                let misspelled = match dictionary.filter(|_| document.spell_check) {
+                    Some(dictionary) => document.misspelled(dictionary).to_vec(),
+                    None => Vec::new(),
+                };
+                let text = document.text.clone();
+                let lines = document.highlighted(highlighter);
+                let longest = text
+                    .split('\n')
+                    .map(|line| line.chars().count())
+                    .max()
+                    .unwrap_or(0);
+                let mut clicked = None;
+                let mut context_clicked = None;
+                egui::ScrollArea::from_max_height(row_height * VISIBLE_ROWS).show(ui, |ui| {
+                    let size = egui::vec2(
+                        EDITOR_WIDTH.max(gutter + (longest + 2) as f32 * char_width),
+                        row_height * starts.len() as f32,
+                    );
+                    let rect = ui.allocate_space(size);
+                    let response = ui.interact(
+                        rect,
+                        ui.make_persistent_id("editor_text"),
+                        egui::Sense::click_and_drag(),
+                    );
+                    let text_x = rect.min.x + gutter;
+                    let locate = |pos: egui::Pos2| {
+                        let line = ((pos.y - rect.min.y) / row_height).max(0.0) as usize;
+                        let column = ((pos.x - text_x) / char_width).round().max(0.0) as usize;
+                        (line.min(starts.len() - 1), column)
+                    };
+                    let (pressed, pointer) = {
+                        let input = ui.input();
+                        (input.mouse.pressed, input.mouse.pos)
+                    };
+                    if pressed && !response.hovered {
+                        *focused = false;
+                    }
+                    if let (true, true, Some(pos)) = (secondary_clicked, response.hovered, pointer)
+                    {
+                        let (line, column) = locate(pos);
+                        context_clicked = Some((line, column, pos));
+                    }
+                    if response.active {
+                        *focused = true;
+                        if let Some(pos) = pointer {
+                            let (line, column) = locate(pos);
+                            clicked = Some((line, column, pressed));
+                        }
+                    }
+
+                    let painter = ui.painter();
+                    painter.rect_filled(rect, 0.0, egui::Srgba::new(30, 32, 40, 255));
+                    painter.rect_filled(
+                        egui::Rect::from_min_size(rect.min, egui::vec2(gutter, rect.height())),
+                        0.0,
+                        egui::Srgba::new(24, 26, 32, 255),
+                    );
+                    let clip = ui.clip_rect();
+                    let first = ((clip.min.y - rect.min.y) / row_height).max(0.0) as usize;
+                    let last = (((clip.max.y - rect.min.y) / row_height).ceil().max(0.0) as usize)
+                        .min(starts.len());
+                    let column_x = |line_start: usize, position: usize| {
+                        text_x + text[line_start..position].chars().count() as f32 * char_width
+                    };
+                    for line in first..last {
+                        let start = starts[line];
+                        let end = starts.get(line + 1).map_or(text.len(), |next| next - 1);
+                        let top = rect.min.y + line as f32 * row_height;
+                        let highlight = |range: &std::ops::Range<usize>, color: egui::Srgba| {
+                            let from = range.start.max(start);
+                            let to = range.end.min(end);
+                            if from > to || (from == to && range.end <= end) {
+                                return;
+                            }
+                            let right = if range.end > end {
+                                column_x(start, to) + char_width * 0.5
+                            } else {
+                                column_x(start, to)
+                            };
+                            painter.rect_filled(
+                                egui::Rect::from_min_max(
+                                    egui::pos2(column_x(start, from), top),
+                                    egui::pos2(right, top + row_height),
+                                ),
+                                0.0,
+                                color,
+                            );
+                        };
+                        for range in matches.iter() {
+                            highlight(range, egui::Srgba::new(90, 80, 30, 255));
+                        }
+                        for range in selections.iter() {
+                            highlight(range, egui::Srgba::new(50, 80, 130, 255));
+                        }
+                        painter.text(
+                            egui::pos2(text_x - GUTTER_PADDING, top),
+                            (egui::Align::Max, egui::Align::Min),
+                            (line + 1).to_string(),
+                            egui::TextStyle::Monospace,
+                            if carets.iter().any(|(caret_line, _)| *caret_line == line) {
+                                egui::Srgba::new(220, 220, 220, 255)
+                            } else {
+                                egui::Srgba::new(110, 110, 120, 255)
+                            },
+                        );
+                        let mut x = text_x;
+                        for (color, span) in lines.get(line).map_or(&[][..], |spans| &spans[..]) {
+                            painter.text(
+                                egui::pos2(x, top),
+                                (egui::Align::Min, egui::Align::Min),
+                                span.clone(),
+                                egui::TextStyle::Monospace,
+                                *color,
+                            );
+                            x += span.chars().count() as f32 * char_width;
+                        }
+                        for range in misspelled.iter() {
+                            if range.start < start || range.end > end {
+                                continue;
+                            }
+                            let (left, right) =
+                                (column_x(start, range.start), column_x(start, range.end));
+                            let bottom = top + row_height - 1.0;
+                            let mut x = left;
+                            let mut up = true;
+                            while x < right {
+                                let next = (x + 2.0).min(right);
+                                let (from, to) = if up {
+                                    (bottom, bottom - 2.0)
+                                } else {
+                                    (bottom - 2.0, bottom)
+                                };
+                                painter.line_segment(
+                                    [egui::pos2(x, from), egui::pos2(next, to)],
+                                    egui::Stroke::new(1.0, egui::Srgba::new(230, 70, 60, 255)),
+                                );
+                                x = next;
+                                up = !up;
+                            }
+                        }
+                    }
+                    let primary = egui::pos2(
+                        text_x + cursor_column as f32 * char_width,
+                        rect.min.y + (cursor_line + 1) as f32 * row_height,
+                    );
+                    if clip.contains(primary) {
+                        caret = Some(primary);
+                    }
+                    if *focused {
+                        for (line, column) in carets.iter() {
+                            let x = text_x + *column as f32 * char_width;
+                            let top = rect.min.y + *line as f32 * row_height;
+                            painter.line_segment(
+                                [egui::pos2(x, top), egui::pos2(x, top + row_height)],
+                                egui::Stroke::new(2.0, egui::Srgba::new(240, 240, 240, 255)),
+                            );
+                        }
+                    }
+                });
+                if let Some((line, column, pos)) = context_clicked {
+                    let position = document.position(line, column);
+                    if !document.selections.iter().any(|selection| {
+                        let range = selection.range();
+                        range.start <= position && position <= range.end
+                    }) {
+                        document.set_selection(position..position);
+                    }
+                    *focused = true;
+                    context_menu = Some((
+                        pos,
+                        (*active, document.revision),
+                        context_items(document, position, &misspelled, dictionary),
+                    ));
+                }
+                match clicked {
+                    Some((line, column, true)) => {
+                        let position = document.position(line, column);
+                        if crate::clipboard::command(modifiers) {
+                            document.toggle_caret(position);
+                            *drag = None;
+                        } else if modifiers.alt() {
+                            document.column_select((line, column), (line, column));
+                            *drag = Some((line, column, true));
+                        } else {
+                            if modifiers.shift() {
+                                document.extend_primary(position);
+                            } else {
+                                document.set_selection(position..position);
+                            }
+                            *drag = Some((line, column, false));
+                        }
+                    }
+                    Some((line, column, false)) => match *drag {
+                        Some((from_line, from_column, true)) => {
+                            document.column_select((from_line, from_column), (line, column))
+                        }
+                        Some(_) => {
+                            let position = document.position(line, column);
+                            document.extend_primary(position);
+                        }
+                        None => {}
+                    },
+                    None => *drag = None,
+                }
+                ui.label(format!(
+                    "Ln {}, Col {}  {} lines  {}{}",
+                    cursor_line + 1,
+                    cursor_column + 1,
+                    starts.len(),
+                    if cursors > 1 {
+                        format!("{} cursors  ", cursors)
+                    } else {
+                        String::new()
+                    },
+                    if *focused {
+                        "editing"
+                    } else {
+                        "click the text to edit"
+                    }
+                ));
+                if !status.is_empty() {
+                    ui.label(status.as_str());
+                }
+
+                let pattern = match (&pattern, *all_documents) {
+                    (Ok(Some(pattern)), true) => pattern,
+                    _ => return,
+                };
+                ui.separator();
+                ui.collapsing("Results in all documents", |ui| {
+                    let mut total = 0;
+                    egui::ScrollArea::from_max_height(160.0).show(ui, |ui| {
+                        for (index, document) in documents.iter().enumerate() {
+                            let starts = document.line_starts();
+                            for range in document.find_all(pattern) {
+                                total += 1;
+                                if total > MAX_RESULTS {
+                                    continue;
+                                }
+                                let (line, column) = document.line_column(range.start);
+                                let text =
+                                    &document.text[starts[line]..document.line_end(starts[line])];
+                                let label = format!(
+                                    "{}:{}:{}  {}",
+                                    document.name,
+                                    line + 1,
+                                    column + 1,
+                                    text.trim()
+                                );
+                                if ui.button(label).clicked {
+                                    jump = Some((index, range));
+                                }
+                            }
+                        }
+                    });
+                    ui.label(if total > MAX_RESULTS {
+                        format!("{} matches, showing the first {}", total, MAX_RESULTS)
+                    } else {
+                        format!("{} matches", total)
+                    });
+                });
+            });
+        self.open = open;
+        self.caret = caret;
+        if let Some((pos, revision, items)) = context_menu {
+            self.context_revision = revision;
+            self.context_menu.open(pos, items);
+        }
+        if !self.open {
+            self.context_menu.close();
+        }
+        if let Some(action) = self.context_menu.show(ctx) {
+            self.run_context_action(action);
+        }
+        if let Some((index, range)) = jump {
+            self.active = index;
+            self.documents[index].set_selection(range);
+            self.focused = true;
+        }
+        if find_next {
+            self.find_next(false);
+        }
+        if find_previous {
+            self.find_next(true);
+        }
+        if replace_one {
+            self.replace_one();
+        }
+        if replace_all {
+            self.replace_all();
+        }
+        if open_requested {
+            let path = std::path::PathBuf::from(&self.path);
+            self.open_path(&path);
+        }
+        if save_requested {
+            self.save();
+        }
+        if new_requested {
+            let name = format!("untitled-{}", self.documents.len() + 1);
+            self.documents
+                .push(Document::new(&name, "", Language::Plain));
+            self.active = self.documents.len() - 1;
+        }
+        if close_requested {
+            self.close_document(self.active);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Document, Editor, Language};
+
+    fn editor(text: &str, find: &str, replace: &str) -> Editor {
+        Editor {
+            documents: vec![Document::new("test.txt", text, Language::Plain)],
+            active: 0,
+            find: find.to_owned(),
+            replace: replace.to_owned(),
+            ..Editor::default()
+        }
+    }
+
+    fn text(editor: &Editor) -> &str {
+        &editor.documents[0].text
+    }
+
+    #[test]
+    fn find_next_wraps_around_in_both_directions() {
+        let mut editor = editor("one two one TWO", "two", "");
+        editor.find_next(false);
+        assert_eq!(editor.documents[0].selection(), 4..7);
+        editor.find_next(false);
+        assert_eq!(editor.documents[0].selection(), 12..15);
+        editor.find_next(false);
+        assert_eq!(editor.documents[0].selection(), 4..7);
+        editor.find_next(true);
+        assert_eq!(editor.documents[0].selection(), 12..15);
+    }
+
+    #[test]
+    fn case_sensitive_search_reports_missing_matches() {
+        let mut editor = editor("one two", "TWO", "");
+        editor.case_sensitive = true;
+        editor.find_next(false);
+        assert_eq!(editor.documents[0].selection(), 0..0);
+        assert_eq!(editor.status, "\"TWO\" not found");
+    }
+
+    #[test]
+    fn invalid_patterns_are_reported() {
+        let mut editor = editor("one two", "(", "");
+        editor.regex = true;
+        editor.find_next(false);
+        assert!(!editor.status.is_empty());
+        editor.status.clear();
+        editor.replace_all();
+        assert!(!editor.status.is_empty());
+        assert_eq!(text(&editor), "one two");
+    }
+
+    #[test]
+    fn replace_one_only_replaces_the_selected_match() {
+        let mut editor = editor("cat cat cat", "cat", "dog");
+        editor.replace_one();
+        assert_eq!(text(&editor), "cat cat cat");
+        assert_eq!(editor.documents[0].selection(), 4..7);
+        editor.replace_one();
+        assert_eq!(text(&editor), "cat dog cat");
+        assert_eq!(editor.documents[0].selection(), 8..11);
+    }
+
+    #[test]
+    fn replace_all_expands_captures_only_in_regex_mode() {
+        let mut regex = editor("a@b c@d", r"(\w)@(\w)", "$2-$1");
+        regex.regex = true;
+        regex.replace_all();
+        assert_eq!(text(&regex), "b-a d-c");
+        assert_eq!(regex.status, "Replaced 2 occurrences");
+
+        let mut literal = editor("1+1 = 2", "1+1", "$0");
+        literal.replace_all();
+        assert_eq!(text(&literal), "$0 = 2");
+    }
+
+    #[test]
+    fn replace_all_can_be_undone() {
+        let mut editor = editor("x x x", "x", "yy");
+        editor.replace_all();
+        assert_eq!(text(&editor), "yy yy yy");
+        editor.documents[0].undo();
+        assert_eq!(text(&editor), "x x x");
+    }
+
+    fn document(text: &str) -> Document {
+        Document::new("test.txt", text, Language::Plain)
+    }
+
+    #[test]
+    fn carets_added_below_edit_every_line() {
+        let mut document = document("a\nb\nc");
+        document.add_caret_vertical(1);
+        document.add_caret_vertical(1);
+        document.add_caret_vertical(1);
+        assert_eq!(document.cursor_count(), 3);
+        document.insert("x");
+        assert_eq!(document.text, "xa\nxb\nxc");
+        document.delete(false);
+        assert_eq!(document.text, "a\nb\nc");
+    }
+
+    #[test]
+    fn pasting_one_line_per_caret_distributes_the_lines() {
+        let mut document = document("a\nb\nc");
+        document.column_select((0, 1), (2, 1));
+        document.insert("1\n2\n3\n");
+        assert_eq!(document.text, "a1\nb2\nc3");
+        document.insert("!");
+        assert_eq!(document.text, "a1!\nb2!\nc3!");
+    }
+
+    #[test]
+    fn column_selection_copies_and_deletes_each_line() {
+        let mut document = document("abc\ndef\nghi");
+        document.column_select((0, 1), (2, 2));
+        assert_eq!(document.copied_text(), "b\ne\nh");
+        document.delete_selections();
+        assert_eq!(document.text, "ac\ndf\ngi");
+        document.undo();
+        assert_eq!(document.text, "abc\ndef\nghi");
+    }
+
+    #[test]
+    fn select_next_occurrence_adds_each_match_once() {
+        let mut document = document("foo bar foo");
+        document.set_selection(1..1);
+        document.select_next_occurrence();
+        assert_eq!(document.selection(), 0..3);
+        document.select_next_occurrence();
+        assert_eq!(document.cursor_count(), 2);
+        document.select_next_occurrence();
+        assert_eq!(document.cursor_count(), 2);
+        document.insert("baz");
+        assert_eq!(document.text, "baz bar baz");
+    }
+
+    #[test]
+    fn overlapping_carets_merge() {
+        let mut document = document("hello");
+        document.set_selection(2..2);
+        document.toggle_caret(2);
+        assert_eq!(document.cursor_count(), 1);
+        document.toggle_caret(4);
+        assert_eq!(document.cursor_count(), 2);
+        document.toggle_caret(4);
+        assert_eq!(document.cursor_count(), 1);
+        assert!(!document.collapse());
+    }
+}
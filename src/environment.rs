@@ -0,0 +1,250 @@
+const BACKEND_VARIABLES: [&str; 8] = [
+    "WGPU_BACKEND",
+    "WGPU_ADAPTER_NAME",
+    "WGPU_POWER_PREF",
+    "RUST_LOG",
+    "RUST_BACKTRACE",
+    "VK_ICD_FILENAMES",
+    "VK_INSTANCE_LAYERS",
+    "WINIT_UNIX_BACKEND",
+];
+
+fn snapshot() -> Vec<(String, String)> {
+    let mut variables: Vec<(String, String)> = std::env::vars_os()
+        .map(|(name, value)| {
+            (
+                name.to_string_lossy().into_owned(),
+                value.to_string_lossy().into_owned(),
+            )
+        })
+        .collect();
+    variables.sort();
+    variables
+}
+
+fn validate_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        Err("name is empty".to_owned())
+    } else if name.contains('=') || name.contains('\0') {
+        Err("name cannot contain '=' or NUL".to_owned())
+    } else {
+        Ok(())
+    }
+}
+
+pub struct EnvironmentPanel {
+    pub open: bool,
+    filter: String,
+    variables: Vec<(String, String)>,
+    originals: std::collections::BTreeMap<String, Option<String>>,
+    name: String,
+    value: String,
+    status: String,
+}
+
+impl Default for EnvironmentPanel {
+    fn default() -> Self {
+        Self {
+            open: false,
+            filter: String::new(),
+            variables: snapshot(),
+            originals: std::collections::BTreeMap::new(),
+            name: String::new(),
+            value: String::new(),
+            status: String::new(),
+        }
+    }
+}
+
+impl EnvironmentPanel {
+    fn remember(&mut self, name: &str) {
+        if !self.originals.contains_key(name) {
+            self.originals
+                .insert(name.to_owned(), std::env::var(name).ok());
+        }
+    }
+
+    fn set(&mut self) {
+        let name = self.name.trim().to_owned();
+        if let Err(err) = validate_name(&name) {
+            self.status = format!("Cannot set variable: {}", err);
+            return;
+        }
+        if self.value.contains('\0') {
+            self.status = "Cannot set variable: value cannot contain NUL".to_owned();
+            return;
+        }
+        self.remember(&name);
+        std::env::set_var(&name, &self.value);
+        log::info!("set {}={}", name, self.value);
+        self.status = format!("Set {} for this process", name);
+        self.variables = snapshot();
+    }
+
+    fn remove(&mut self) {
+        let name = self.name.trim().to_owned();
+        if let Err(err) = validate_name(&name) {
+            self.status = format!("Cannot remove variable: {}", err);
+            return;
+        }
+        self.remember(&name);
+        std::env::remove_var(&name);
+        log::info!("removed {}", name);
+        self.status = format!("Removed {} from this process", name);
+        self.variables = snapshot();
+    }
+
+    fn revert(&mut self) {
+        for (name, value) in std::mem::take(&mut self.originals) {
+            match value {
+                Some(value) => std::env::set_var(&name, value),
+                None => std::env::remove_var(&name),
+            }
+        }
+        self.status = "Restored the startup environment".to_owned();
+        self.variables = snapshot();
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        let mut open = self.open;
+        let mut refresh_requested = false;
+        let mut set_requested = false;
+        let mut remove_requested = false;
+        let mut revert_requested = false;
+        let mut copied = None;
+        let needle = self.filter.to_lowercase();
+        let variables = &self.variables;
+        let originals = &self.originals;
+        let filter = &mut self.filter;
+        let name = &mut self.name;
+        let value = &mut self.value;
+        let status = &self.status;
+        crate::dock::Panel::new("Environment")
+            .open(&mut open)
+            .default_pos(egui::pos2(340.0, 100.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Search");
+                    ui.add(egui::TextEdit::new(filter));
+                    if ui.button("Refresh").clicked {
+                        refresh_requested = true;
+                    }
+                });
+
+                egui::CollapsingHeader::new("Graphics and logging")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        for variable in BACKEND_VARIABLES.iter() {
+                            let current = variables
+                                .iter()
+                                .find(|(name, _)| name == variable)
+                                .map(|(_, value)| value.as_str());
+                            ui.horizontal(|ui| {
+                                if ui.button("Edit").clicked {
+                                    *name = variable.to_string();
+                                    *value = current.unwrap_or_default().to_owned();
+                                }
+                                ui.label(match current {
+                                    Some(current) => format!("{}={}", variable, current),
+                                    None => format!("{} (unset)", variable),
+                                });
+                            });
+                        }
+                        ui.label("wgpu and winit read these at startup, so changes apply to the next launch or to code that reads them later.");
+                    });
+
+                egui::CollapsingHeader::new("Variables")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        let matching: Vec<_> = variables
+                            .iter()
+                            .filter(|(name, value)| {
+                                needle.is_empty()
+                                    || name.to_lowercase().contains(&needle)
+                                    || value.to_lowercase().contains(&needle)
+                            })
+                            .collect();
+                        ui.label(format!("{} of {} variables", matching.len(), variables.len()));
+                        egui::ScrollArea::from_max_height(240.0).show(ui, |ui| {
+                            for (variable, current) in matching {
+                                ui.horizontal(|ui| {
+                                    if ui.button("Copy").clicked {
+                                        copied = Some(format!("{}={}", variable, current));
+                                    }
+                                    if ui.button("Edit").clicked {
+                                        *name = variable.clone();
+                                        *value = current.clone();
+                                    }
+                                    let marker = if originals.contains_key(variable) {
+                                        " *"
+                                    } else {
+                                        ""
+                                    };
+                                    ui.label(format!("{}={}{}", variable, current, marker));
+                                });
+                            }
+                        });
+                    });
+
+                egui::CollapsingHeader::new("PATH").show(ui, |ui| {
+                    let path = variables
+                        .iter()
+                        .find(|(name, _)| name == "PATH" || name == "Path")
+                        .map(|(_, value)| value.as_str())
+                        .unwrap_or_default();
+                    egui::ScrollArea::from_max_height(200.0).show(ui, |ui| {
+                        for (index, entry) in std::env::split_paths(path).enumerate() {
+                            ui.horizontal(|ui| {
+                                if ui.button("Copy").clicked {
+                                    copied = Some(entry.display().to_string());
+                                }
+                                let missing = if entry.is_dir() { "" } else { " (missing)" };
+                                ui.label(format!("{}: {}{}", index + 1, entry.display(), missing));
+                            });
+                        }
+                    });
+                });
+
+                ui.separator();
+                ui.label("Add or override (this process only)");
+                ui.horizontal(|ui| {
+                    ui.label("Name");
+                    ui.add(egui::TextEdit::new(name));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Value");
+                    ui.add(egui::TextEdit::new(value));
+                });
+                ui.horizontal(|ui| {
+                    if ui.button("Set").clicked {
+                        set_requested = true;
+                    }
+                    if ui.button("Remove").clicked {
+                        remove_requested = true;
+                    }
+                    if !originals.is_empty() && ui.button("Revert all").clicked {
+                        revert_requested = true;
+                    }
+                });
+                if !status.is_empty() {
+                    ui.label(status.as_str());
+                }
+                if let Some(text) = copied.take() {
+                    ui.output().copied_text = text;
+                }
+            });
+        if refresh_requested {
+            self.variables = snapshot();
+        }
+        if set_requested {
+            self.set();
+        }
+        if remove_requested {
+            self.remove();
+        }
+        if revert_requested {
+            self.revert();
+        }
+        self.open = open;
+    }
+}
@@ -0,0 +1,273 @@
+const HISTORY: usize = 8;
+const SWATCH: f32 = 20.0;
+const LOUPE_OFFSET: f32 = 18.0;
+
+type Readback =
+    std::pin::Pin<Box<dyn std::future::Future<Output = Result<(), wgpu::BufferAsyncError>> + Send>>;
+
+fn half_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f32;
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => sign * f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
+
+fn aces(x: f32) -> f32 {
+    ((x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14)).clamp(0.0, 1.0)
+}
+
+fn encode_srgb(linear: f32) -> u8 {
+    let encoded = if linear <= 0.003_130_8 {
+        linear * 12.92
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    };
+    (encoded * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+fn hex(color: egui::Srgba) -> String {
+    let [r, g, b, _] = color.to_array();
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+fn swatch(ui: &mut egui::Ui, color: egui::Srgba) -> egui::Response {
+    let rect = ui.allocate_space(egui::vec2(SWATCH, SWATCH));
+    let response = ui.interact(rect, ui.id().with(color.to_array()), egui::Sense::click());
+    ui.painter().rect_filled(rect, 2.0, color);
+    response
+}
+
+pub struct Eyedropper {
+    pub open: bool,
+    active: bool,
+    cursor: Option<winit::dpi::PhysicalPosition<f64>>,
+    hovered: Option<([f32; 3], egui::Srgba)>,
+    color: egui::Srgba,
+    history: Vec<egui::Srgba>,
+    readback_buffer: wgpu::Buffer,
+    encoded: bool,
+    pending: Option<Readback>,
+}
+
+impl Eyedropper {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Eyedropper Readback"),
+            size: wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::MAP_READ | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        Self {
+            open: false,
+            active: false,
+            cursor: None,
+            hovered: None,
+            color: egui::Srgba::new(255, 255, 255, 255),
+            history: Vec::new(),
+            readback_buffer,
+            encoded: false,
+            pending: None,
+        }
+    }
+
+    pub fn recreate_gpu_resources(&mut self, device: &wgpu::Device) {
+        let open = self.open;
+        let color = self.color;
+        let history = std::mem::take(&mut self.history);
+        *self = Self::new(device);
+        self.open = open;
+        self.color = color;
+        self.history = history;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn set_cursor(&mut self, cursor: Option<winit::dpi::PhysicalPosition<f64>>) {
+        self.cursor = cursor;
+        if cursor.is_none() {
+            self.hovered = None;
+        }
+    }
+
+    pub fn pick(&mut self) {
+        if let Some((_, color)) = self.hovered {
+            self.color = color;
+            self.history.retain(|previous| *previous != color);
+            self.history.insert(0, color);
+            self.history.truncate(HISTORY);
+        }
+        self.active = false;
+        self.hovered = None;
+    }
+
+    pub fn key_input(&mut self, input: &winit::event::KeyboardInput) -> bool {
+        if !self.active
+            || input.state != winit::event::ElementState::Pressed
+            || input.virtual_keycode != Some(winit::event::VirtualKeyCode::Escape)
+        {
+            return false;
+        }
+        self.active = false;
+        self.hovered = None;
+        true
+    }
+
+    pub fn sample(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        scene: &crate::scene::Scene,
+        window_size: winit::dpi::PhysicalSize<u32>,
+    ) {
+        let cursor = match self.cursor {
+            Some(cursor) if self.active && self.pending.is_none() => cursor,
+            _ => return,
+        };
+        let (_, size) = scene.color_target();
+        let scale_x = size.width as f64 / window_size.width.max(1) as f64;
+        let scale_y = size.height as f64 / window_size.height.max(1) as f64;
+        let x = ((cursor.x * scale_x) as u32).min(size.width.max(1) - 1);
+        let y = ((cursor.y * scale_y) as u32).min(size.height.max(1) - 1);
+        encoder.copy_texture_to_buffer(
+            wgpu::TextureCopyView {
+                texture: scene.color_texture(),
+                mip_level: 0,
+                origin: wgpu::Origin3d { x, y, z: 0 },
+            },
+            wgpu::BufferCopyView {
+                buffer: &self.readback_buffer,
+                layout: wgpu::TextureDataLayout {
+                    offset: 0,
+                    bytes_per_row: wgpu::COPY_BYTES_PER_ROW_ALIGNMENT,
+                    rows_per_image: 1,
+                },
+            },
+            wgpu::Extent3d {
+                width: 1,
+                height: 1,
+                depth: 1,
+            },
+        );
+        self.encoded = true;
+    }
+
+    pub fn after_submit(&mut self) {
+        if self.encoded {
+            self.encoded = false;
+            self.pending = Some(Box::pin(
+                self.readback_buffer
+                    .slice(..)
+                    .map_async(wgpu::MapMode::Read),
+            ));
+        }
+    }
+
+    pub fn poll(&mut self, device: &wgpu::Device) {
+        let pending = match &mut self.pending {
+            Some(pending) => pending,
+            None => return,
+        };
+        device.poll(wgpu::Maintain::Poll);
+        match futures::FutureExt::now_or_never(pending.as_mut()) {
+            Some(Ok(())) => {
+                let linear = {
+                    let data = self.readback_buffer.slice(..).get_mapped_range();
+                    let channel = |index: usize| {
+                        half_to_f32(u16::from_ne_bytes([data[index * 2], data[index * 2 + 1]]))
+                    };
+                    [channel(0), channel(1), channel(2)]
+                };
+                self.readback_buffer.unmap();
+                self.pending = None;
+                if self.active && self.cursor.is_some() {
+                    let [r, g, b] = linear;
+                    let displayed = egui::Srgba::new(
+                        encode_srgb(aces(r)),
+                        encode_srgb(aces(g)),
+                        encode_srgb(aces(b)),
+                        255,
+                    );
+                    self.hovered = Some((linear, displayed));
+                }
+            }
+            Some(Err(_)) => {
+                log::warn!("eyedropper readback failed");
+                self.pending = None;
+            }
+            None => {}
+        }
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, available: bool) {
+        if self.active {
+            crate::status::hint("Click to pick a color, Escape to cancel");
+            if let (Some((_, color)), Some(pos)) = (self.hovered, ctx.input().mouse.pos) {
+                egui::Area::new("eyedropper_loupe")
+                    .order(egui::Order::Foreground)
+                    .interactable(false)
+                    .fixed_pos(pos + egui::vec2(LOUPE_OFFSET, LOUPE_OFFSET))
+                    .show(ctx, |ui| {
+                        egui::Frame::popup(ui.style()).show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                swatch(ui, color);
+                                ui.label(hex(color));
+                            });
+                        });
+                    });
+            }
+        }
+
+        let mut open = self.open;
+        let mut start = false;
+        let mut restore = None;
+        crate::dock::Panel::new("Eyedropper")
+            .open(&mut open)
+            .default_pos(egui::pos2(640.0, 320.0))
+            .show(ctx, |ui| {
+                if !available {
+                    ui.label("The scene is still loading");
+                } else if self.active {
+                    ui.label("Picking... click anywhere in the scene");
+                } else {
+                    start = ui.button("Pick from frame").clicked;
+                }
+                ui.horizontal(|ui| {
+                    ui.label("Color");
+                    egui::color_picker::color_edit_button_srgba(ui, &mut self.color);
+                    ui.label(hex(self.color));
+                });
+                if let Some(([r, g, b], _)) = self.hovered {
+                    ui.label(format!("Scene radiance {:.3} {:.3} {:.3}", r, g, b));
+                }
+                if !self.history.is_empty() {
+                    ui.label("Recent");
+                    ui.horizontal(|ui| {
+                        for color in self.history.iter() {
+                            let response = swatch(ui, *color);
+                            crate::status::hint_on_hover(&response, &hex(*color));
+                            if response.clicked {
+                                restore = Some(*color);
+                            }
+                        }
+                    });
+                }
+            });
+        if start {
+            self.active = true;
+        }
+        if let Some(color) = restore {
+            self.color = color;
+        }
+        if !open {
+            self.active = false;
+            self.hovered = None;
+        }
+        self.open = open;
+    }
+}
@@ -0,0 +1,176 @@
+const OUTLINE: egui::Srgba = egui::Srgba::new(90, 170, 250, 255);
+
+#[derive(Clone, Copy, PartialEq)]
+enum Navigation {
+    Next,
+    Previous,
+    Up,
+    Down,
+    Left,
+    Right,
+    Activate,
+    Clear,
+}
+
+#[derive(Default)]
+struct State {
+    widgets: Vec<(egui::Id, egui::Rect)>,
+    previous: Vec<(egui::Id, egui::Rect)>,
+    focused: Option<egui::Id>,
+    activated: Option<egui::Id>,
+}
+
+thread_local! {
+    static STATE: std::cell::RefCell<State> = std::cell::RefCell::new(State::default());
+}
+
+fn nearest(
+    widgets: &[(egui::Id, egui::Rect)],
+    from: egui::Rect,
+    direction: egui::Vec2,
+) -> Option<egui::Id> {
+    widgets
+        .iter()
+        .filter_map(|(id, rect)| {
+            let offset = rect.center() - from.center();
+            let along = offset.x * direction.x + offset.y * direction.y;
+            let across = (offset.x * direction.y - offset.y * direction.x).abs();
+            if along <= 0.0 {
+                return None;
+            }
+            Some((*id, along + across * 2.0))
+        })
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(id, _)| id)
+}
+
+fn navigate(state: &mut State, navigation: Navigation) {
+    let index = state
+        .focused
+        .and_then(|focused| state.previous.iter().position(|(id, _)| *id == focused));
+    let count = state.previous.len();
+    if count == 0 {
+        return;
+    }
+    let direction = match navigation {
+        Navigation::Next => {
+            state.focused = Some(state.previous[index.map_or(0, |index| (index + 1) % count)].0);
+            return;
+        }
+        Navigation::Previous => {
+            state.focused = Some(
+                state.previous[index.map_or(count - 1, |index| (index + count - 1) % count)].0,
+            );
+            return;
+        }
+        Navigation::Activate => {
+            state.activated = state.focused;
+            return;
+        }
+        Navigation::Clear => {
+            state.focused = None;
+            return;
+        }
+        Navigation::Up => egui::vec2(0.0, -1.0),
+        Navigation::Down => egui::vec2(0.0, 1.0),
+        Navigation::Left => egui::vec2(-1.0, 0.0),
+        Navigation::Right => egui::vec2(1.0, 0.0),
+    };
+    if let Some(index) = index {
+        let from = state.previous[index].1;
+        if let Some(id) = nearest(&state.previous, from, direction) {
+            state.focused = Some(id);
+        }
+    }
+}
+
+pub fn key_input(
+    input: &winit::event::KeyboardInput,
+    modifiers: winit::event::ModifiersState,
+) -> bool {
+    let navigation = match input.virtual_keycode {
+        Some(winit::event::VirtualKeyCode::Tab) if modifiers.shift() => Navigation::Previous,
+        Some(winit::event::VirtualKeyCode::Tab) => Navigation::Next,
+        Some(winit::event::VirtualKeyCode::Up) => Navigation::Up,
+        Some(winit::event::VirtualKeyCode::Down) => Navigation::Down,
+        Some(winit::event::VirtualKeyCode::Left) => Navigation::Left,
+        Some(winit::event::VirtualKeyCode::Right) => Navigation::Right,
+        Some(winit::event::VirtualKeyCode::Return)
+        | Some(winit::event::VirtualKeyCode::NumpadEnter)
+        | Some(winit::event::VirtualKeyCode::Space) => Navigation::Activate,
+        Some(winit::event::VirtualKeyCode::Escape) => Navigation::Clear,
+        _ => return false,
+    };
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        if navigation != Navigation::Next
+            && navigation != Navigation::Previous
+            && state.focused.is_none()
+        {
+            return false;
+        }
+        if input.state == winit::event::ElementState::Pressed {
+            navigate(&mut state, navigation);
+        }
+        true
+    })
+}
+
+pub fn request(id: egui::Id) {
+    STATE.with(|state| state.borrow_mut().focused = Some(id));
+}
+
+pub fn clear() {
+    STATE.with(|state| state.borrow_mut().focused = None);
+}
+
+pub fn end_frame() {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.previous = std::mem::take(&mut state.widgets);
+        let focused = state.focused;
+        if !state.previous.iter().any(|(id, _)| Some(*id) == focused) {
+            state.focused = None;
+        }
+        state.activated = None;
+    });
+}
+
+fn track(ui: &egui::Ui, id: egui::Id, rect: egui::Rect) -> bool {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.widgets.push((id, rect));
+        if state.focused == Some(id) {
+            ui.painter()
+                .rect_stroke(rect.expand(2.0), 3.0, egui::Stroke::new(2.0, OUTLINE));
+        }
+        if state.activated == Some(id) {
+            state.activated = None;
+            true
+        } else {
+            false
+        }
+    })
+}
+
+pub fn button(ui: &mut egui::Ui, text: &str) -> egui::Response {
+    let id = ui.id().with(text);
+    let mut response = ui.button(text);
+    response.clicked |= track(ui, id, response.rect);
+    response
+}
+
+pub fn checkbox(ui: &mut egui::Ui, checked: &mut bool, text: &str) -> egui::Response {
+    let id = ui.id().with(text);
+    let response = ui.checkbox(checked, text);
+    if track(ui, id, response.rect) {
+        *checked = !*checked;
+    }
+    response
+}
+
+pub fn text_edit(ui: &mut egui::Ui, id: egui::Id, text: &mut String) -> egui::Response {
+    let response = ui.add(egui::TextEdit::new(text));
+    track(ui, id, response.rect);
+    response
+}
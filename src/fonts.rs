@@ -0,0 +1,340 @@
+const SAMPLE: &str = "The quick brown fox jumps over the lazy dog 0123456789";
+const UNICODE_SAMPLE: &str = "中文 日本語 한국어 Ελληνικά Русский ☃ ❤";
+const BUNDLED_FONTS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fonts");
+const SYSTEM_FALLBACKS: [&str; 16] = [
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/wqy/wqy-microhei.ttc",
+    "/usr/share/fonts/truetype/droid/DroidSansFallbackFull.ttf",
+    "/usr/share/fonts/truetype/noto/NotoEmoji-Regular.ttf",
+    "/usr/share/fonts/truetype/ancient-scripts/Symbola_hint.ttf",
+    "/System/Library/Fonts/PingFang.ttc",
+    "/System/Library/Fonts/AppleSDGothicNeo.ttc",
+    "/System/Library/Fonts/Apple Symbols.ttf",
+    "/Library/Fonts/Arial Unicode.ttf",
+    "C:\\Windows\\Fonts\\msyh.ttc",
+    "C:\\Windows\\Fonts\\msgothic.ttc",
+    "C:\\Windows\\Fonts\\malgun.ttf",
+    "C:\\Windows\\Fonts\\seguisym.ttf",
+    "C:\\Windows\\Fonts\\seguiemj.ttf",
+];
+
+pub fn is_font(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            let extension = extension.to_lowercase();
+            extension == "ttf" || extension == "otf" || extension == "ttc"
+        })
+}
+
+fn validate(data: &[u8]) -> Result<(), String> {
+    match data.get(..4) {
+        Some([0, 1, 0, 0]) | Some(b"OTTO") | Some(b"true") | Some(b"ttcf") => Ok(()),
+        _ => Err("not a TrueType or OpenType font".to_owned()),
+    }
+}
+
+type Discovered = Vec<(std::path::PathBuf, Vec<u8>)>;
+
+fn discover_fallbacks() -> Discovered {
+    let mut candidates: Vec<std::path::PathBuf> = std::fs::read_dir(BUNDLED_FONTS)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| is_font(path))
+                .collect()
+        })
+        .unwrap_or_default();
+    candidates.sort();
+    candidates.extend(SYSTEM_FALLBACKS.iter().map(std::path::PathBuf::from));
+    candidates
+        .into_iter()
+        .filter_map(|path| {
+            let data = std::fs::read(&path).ok()?;
+            match validate(&data) {
+                Ok(()) => Some((path, data)),
+                Err(err) => {
+                    log::warn!("skipping fallback font {}: {}", path.display(), err);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+struct LoadedFont {
+    name: String,
+    data: &'static [u8],
+}
+
+pub struct FontsPanel {
+    pub open: bool,
+    path: String,
+    font: Option<LoadedFont>,
+    proportional: bool,
+    monospace: bool,
+    defaults: Option<std::collections::BTreeMap<egui::paint::fonts::FontFamily, &'static [u8]>>,
+    installed: Option<String>,
+    status: String,
+    apply_requested: bool,
+    reset_requested: bool,
+    discovery: Option<std::sync::mpsc::Receiver<Discovered>>,
+    fallbacks: Vec<(std::path::PathBuf, &'static [u8])>,
+    default_proportional: Option<&'static [u8]>,
+    use_fallbacks: bool,
+    fallbacks_installed: bool,
+    interned: std::collections::HashMap<std::path::PathBuf, &'static [u8]>,
+}
+
+impl Default for FontsPanel {
+    fn default() -> Self {
+        Self {
+            open: false,
+            path: String::new(),
+            font: None,
+            proportional: true,
+            monospace: false,
+            defaults: None,
+            installed: None,
+            status: String::new(),
+            apply_requested: false,
+            reset_requested: false,
+            discovery: None,
+            fallbacks: Vec::new(),
+            default_proportional: None,
+            use_fallbacks: true,
+            fallbacks_installed: false,
+            interned: std::collections::HashMap::new(),
+        }
+    }
+}
+
+impl FontsPanel {
+    fn intern(&mut self, path: std::path::PathBuf, data: Vec<u8>) -> &'static [u8] {
+        match self.interned.get(&path) {
+            Some(&interned) if *interned == *data => interned,
+            _ => {
+                let interned = &*Box::leak(data.into_boxed_slice());
+                self.interned.insert(path, interned);
+                interned
+            }
+        }
+    }
+
+    pub fn drop_path(&mut self, path: &std::path::Path) {
+        self.path = path.display().to_string();
+        self.load();
+        self.open = true;
+    }
+
+    fn load(&mut self) {
+        let path = std::path::PathBuf::from(&self.path);
+        let loaded = std::fs::read(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|data| validate(&data).map(|_| data));
+        match loaded {
+            Ok(data) => {
+                let name = path
+                    .file_stem()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                self.status = format!("Loaded {} ({} KiB)", name, data.len() / 1024);
+                let data = self.intern(path, data);
+                self.font = Some(LoadedFont { name, data });
+            }
+            Err(err) => {
+                self.status = format!("Cannot load {}: {}", path.display(), err);
+                self.font = None;
+            }
+        }
+    }
+
+    fn apply(&mut self, ctx: &egui::Context) {
+        if !self.proportional && !self.monospace {
+            self.status = "Pick at least one family".to_owned();
+            return;
+        }
+        let font = match &self.font {
+            Some(font) => font,
+            None => return,
+        };
+        let mut definitions = ctx.fonts().definitions().clone();
+        if self.defaults.is_none() {
+            self.defaults = Some(definitions.ttf_data.clone());
+        }
+        let data = font.data;
+        let mut families = Vec::new();
+        if self.proportional {
+            definitions
+                .ttf_data
+                .insert(egui::paint::fonts::FontFamily::VariableWidth, data);
+            families.push("proportional");
+        }
+        if self.monospace {
+            definitions
+                .ttf_data
+                .insert(egui::paint::fonts::FontFamily::Monospace, data);
+            families.push("monospace");
+        }
+        ctx.set_fonts(definitions);
+        log::info!(
+            "installed font {} for {}",
+            font.name,
+            families.join(" and ")
+        );
+        self.installed = Some(format!("{} ({})", font.name, families.join(", ")));
+        self.status.clear();
+    }
+
+    pub fn describe_font(&self, data: &[u8]) -> Option<String> {
+        if let Some((path, _)) = self
+            .fallbacks
+            .iter()
+            .find(|(_, fallback)| fallback.as_ptr() == data.as_ptr())
+        {
+            return Some(path.display().to_string());
+        }
+        self.font
+            .as_ref()
+            .filter(|font| self.installed.is_some() && font.data == data)
+            .map(|font| font.name.clone())
+    }
+
+    fn poll_fallbacks(&mut self, ctx: &egui::Context) {
+        let discovery = match &self.discovery {
+            Some(discovery) => discovery,
+            None => {
+                let (sender, receiver) = std::sync::mpsc::channel();
+                std::thread::Builder::new()
+                    .name("font discovery".to_owned())
+                    .spawn(move || {
+                        sender.send(discover_fallbacks()).ok();
+                    })
+                    .unwrap();
+                self.discovery = Some(receiver);
+                return;
+            }
+        };
+        if let Ok(found) = discovery.try_recv() {
+            log::info!("found {} fallback fonts", found.len());
+            self.fallbacks = found
+                .into_iter()
+                .map(|(path, data)| (path.clone(), self.intern(path, data)))
+                .collect();
+        }
+        if self.use_fallbacks == self.fallbacks_installed || self.fallbacks.is_empty() {
+            return;
+        }
+        // egui 0.3 renders each family from a single font, so the fallback replaces the
+        // proportional font instead of filling in its missing glyphs.
+        let mut definitions = ctx.fonts().definitions().clone();
+        let family = egui::paint::fonts::FontFamily::VariableWidth;
+        if self.default_proportional.is_none() {
+            self.default_proportional = definitions.ttf_data.get(&family).copied();
+        }
+        let data = if self.use_fallbacks {
+            Some(self.fallbacks[0].1)
+        } else {
+            self.default_proportional
+        };
+        if let Some(data) = data {
+            definitions.ttf_data.insert(family, data);
+            ctx.set_fonts(definitions);
+        }
+        self.fallbacks_installed = self.use_fallbacks;
+    }
+
+    fn reset(&mut self, ctx: &egui::Context) {
+        if let Some(defaults) = &self.defaults {
+            let mut definitions = ctx.fonts().definitions().clone();
+            definitions.ttf_data = defaults.clone();
+            ctx.set_fonts(definitions);
+        }
+        self.installed = None;
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        self.poll_fallbacks(ctx);
+        if self.apply_requested {
+            self.apply_requested = false;
+            self.apply(ctx);
+        }
+        if self.reset_requested {
+            self.reset_requested = false;
+            self.reset(ctx);
+        }
+
+        let mut open = self.open;
+        let mut load_requested = false;
+        let path = &mut self.path;
+        let font = &self.font;
+        let proportional = &mut self.proportional;
+        let monospace = &mut self.monospace;
+        let installed = &self.installed;
+        let status = &self.status;
+        let apply_requested = &mut self.apply_requested;
+        let reset_requested = &mut self.reset_requested;
+        let fallbacks = &self.fallbacks;
+        let searching = self.fallbacks.is_empty() && self.discovery.is_some();
+        let use_fallbacks = &mut self.use_fallbacks;
+        crate::dock::Panel::new("Fonts")
+            .open(&mut open)
+            .default_pos(egui::pos2(320.0, 140.0))
+            .show(ctx, |ui| {
+                ui.label("Drop a .ttf or .otf file onto the window, or type its path.");
+                ui.horizontal(|ui| {
+                    ui.label("File");
+                    ui.add(egui::TextEdit::new(path));
+                    if ui.button("Load").clicked {
+                        load_requested = true;
+                    }
+                });
+                if let Some(font) = font {
+                    ui.label(format!("Selected: {}", font.name));
+                    ui.checkbox(proportional, "Use for proportional text");
+                    ui.checkbox(monospace, "Use for monospace text");
+                    if ui.button("Apply").clicked {
+                        *apply_requested = true;
+                    }
+                }
+                ui.separator();
+                ui.label(match installed {
+                    Some(installed) => format!("Installed: {}", installed),
+                    None => "Using the built-in fonts".to_owned(),
+                });
+                if installed.is_some() && ui.button("Restore built-in fonts").clicked {
+                    *reset_requested = true;
+                }
+                ui.add(egui::Label::new(SAMPLE).text_style(egui::TextStyle::Body));
+                ui.add(egui::Label::new(SAMPLE).text_style(egui::TextStyle::Monospace));
+                ui.add(egui::Label::new(UNICODE_SAMPLE).text_style(egui::TextStyle::Body));
+
+                egui::CollapsingHeader::new("Fallback fonts").show(ui, |ui| {
+                    ui.checkbox(
+                        use_fallbacks,
+                        "Use the first fallback font for proportional text",
+                    );
+                    if fallbacks.is_empty() {
+                        ui.label(if searching {
+                            "Searching for system fonts..."
+                        } else {
+                            "No fallback fonts found"
+                        });
+                    }
+                    for (path, data) in fallbacks.iter() {
+                        ui.label(format!("{} ({} KiB)", path.display(), data.len() / 1024));
+                    }
+                    ui.label(format!("Extra fonts can be bundled in {}", BUNDLED_FONTS));
+                });
+                if !status.is_empty() {
+                    ui.label(status.as_str());
+                }
+            });
+        if load_requested {
+            self.load();
+        }
+        self.open = open;
+    }
+}
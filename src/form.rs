@@ -0,0 +1,149 @@
+const ERROR_COLOR: egui::Srgba = egui::Srgba::new(230, 110, 100, 255);
+
+pub enum Rule {
+    Required,
+    Range(f32, f32),
+    Pattern(regex::Regex, &'static str),
+}
+
+impl Rule {
+    fn check(&self, value: &str) -> Result<(), String> {
+        let value = value.trim();
+        match self {
+            Rule::Required if value.is_empty() => Err("This field is required".to_owned()),
+            Rule::Required => Ok(()),
+            Rule::Range(..) | Rule::Pattern(..) if value.is_empty() => Ok(()),
+            Rule::Range(min, max) => match value.parse::<f32>() {
+                Ok(number) if number >= *min && number <= *max => Ok(()),
+                Ok(_) => Err(format!("Must be between {} and {}", min, max)),
+                Err(_) => Err("Must be a number".to_owned()),
+            },
+            Rule::Pattern(pattern, _) if pattern.is_match(value) => Ok(()),
+            Rule::Pattern(_, description) => Err(format!("Must be {}", description)),
+        }
+    }
+}
+
+pub struct Field {
+    label: &'static str,
+    value: String,
+    rules: Vec<Rule>,
+    touched: bool,
+}
+
+impl Field {
+    pub fn new(label: &'static str) -> Self {
+        Self {
+            label,
+            value: String::new(),
+            rules: Vec::new(),
+            touched: false,
+        }
+    }
+
+    pub fn required(mut self) -> Self {
+        self.rules.push(Rule::Required);
+        self
+    }
+
+    pub fn range(mut self, min: f32, max: f32) -> Self {
+        self.rules.push(Rule::Range(min, max));
+        self
+    }
+
+    pub fn pattern(mut self, pattern: &str, description: &'static str) -> Self {
+        self.rules.push(Rule::Pattern(
+            regex::Regex::new(pattern).unwrap(),
+            description,
+        ));
+        self
+    }
+
+    fn error(&self) -> Option<String> {
+        self.rules
+            .iter()
+            .find_map(|rule| rule.check(&self.value).err())
+    }
+}
+
+pub struct Form {
+    id: &'static str,
+    fields: Vec<Field>,
+    attempted: bool,
+}
+
+impl Form {
+    pub fn new(id: &'static str, fields: Vec<Field>) -> Self {
+        Self {
+            id,
+            fields,
+            attempted: false,
+        }
+    }
+
+    pub fn value(&self, label: &str) -> &str {
+        self.fields
+            .iter()
+            .find(|field| field.label == label)
+            .map_or("", |field| field.value.trim())
+    }
+
+    pub fn reset(&mut self) {
+        self.attempted = false;
+        for field in self.fields.iter_mut() {
+            field.touched = false;
+        }
+    }
+
+    fn field_id(&self, field: &Field) -> egui::Id {
+        egui::Id::new((self.id, field.label))
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, submit: &str) -> bool {
+        let attempted = self.attempted;
+        let ids: Vec<egui::Id> = self
+            .fields
+            .iter()
+            .map(|field| self.field_id(field))
+            .collect();
+        for (field, id) in self.fields.iter_mut().zip(ids.iter()) {
+            ui.horizontal(|ui| {
+                ui.label(field.label);
+                let before = field.value.clone();
+                crate::focus::text_edit(ui, *id, &mut field.value);
+                field.touched |= field.value != before;
+            });
+            if let (true, Some(error)) = (field.touched || attempted, field.error()) {
+                ui.add(egui::Label::new(error).text_color(ERROR_COLOR));
+            }
+        }
+
+        let first_error = self.fields.iter().position(|field| field.error().is_some());
+        let errors = self
+            .fields
+            .iter()
+            .filter(|field| field.error().is_some())
+            .count();
+        let mut clicked = false;
+        ui.horizontal(|ui| {
+            clicked = crate::focus::button(ui, submit).clicked;
+            if attempted && errors > 0 {
+                ui.add(
+                    egui::Label::new(format!("Fix {} field(s) to continue", errors))
+                        .text_color(ERROR_COLOR),
+                );
+            }
+        });
+        if !clicked {
+            return false;
+        }
+        self.attempted = true;
+        match first_error {
+            Some(index) => {
+                crate::focus::request(ids[index]);
+                false
+            }
+            None => true,
+        }
+    }
+}
@@ -0,0 +1,371 @@
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+const VIEW_SIZE: [f32; 2] = [400.0, 300.0];
+const DEFAULT_CENTER: [f64; 2] = [-0.5, 0.0];
+const DEFAULT_HEIGHT: f64 = 2.5;
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    center: [f32; 2],
+    extent: [f32; 2],
+    julia: [f32; 2],
+    iterations: u32,
+    palette: u32,
+    kind: u32,
+    padding: [u32; 3],
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Kind {
+    Mandelbrot,
+    Julia,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Palette {
+    Rainbow,
+    Fire,
+    Ocean,
+    Grayscale,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 4] = [
+        Palette::Rainbow,
+        Palette::Fire,
+        Palette::Ocean,
+        Palette::Grayscale,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Palette::Rainbow => "Rainbow",
+            Palette::Fire => "Fire",
+            Palette::Ocean => "Ocean",
+            Palette::Grayscale => "Grayscale",
+        }
+    }
+}
+
+pub struct Settings {
+    pub kind: Kind,
+    pub palette: Palette,
+    pub iterations: f32,
+    pub julia: [f32; 2],
+    pub center: [f64; 2],
+    pub height: f64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            kind: Kind::Mandelbrot,
+            palette: Palette::Rainbow,
+            iterations: 256.0,
+            julia: [-0.8, 0.156],
+            center: DEFAULT_CENTER,
+            height: DEFAULT_HEIGHT,
+        }
+    }
+}
+
+struct Offscreen {
+    size: [u32; 2],
+    view: wgpu::TextureView,
+    bind_group: wgpu::BindGroup,
+}
+
+pub struct Fractal {
+    pub open: bool,
+    pub settings: Settings,
+    pub renders: u64,
+    pub reuses: u64,
+    cursor: Option<winit::dpi::PhysicalPosition<f64>>,
+    dragging: bool,
+    viewport: Option<egui::Rect>,
+    physical_viewport: Option<egui::Rect>,
+    canvas: crate::blit::Canvas,
+    rendered: Option<Uniforms>,
+    pipeline: wgpu::RenderPipeline,
+    bind_group: wgpu::BindGroup,
+    uniform_buffer: wgpu::Buffer,
+    blitter: crate::blit::Blitter,
+    offscreen: Option<Offscreen>,
+}
+
+impl Fractal {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Fractal Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStage::FRAGMENT,
+                ty: wgpu::BindingType::UniformBuffer {
+                    dynamic: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let pipeline = crate::blit::fullscreen_pipeline(
+            device,
+            "Fractal Pipeline",
+            &[&layout],
+            "fractal.frag",
+            include_str!("shaders/fractal.frag"),
+            FORMAT,
+            wgpu::BlendDescriptor::REPLACE,
+        );
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Fractal Uniforms"),
+            size: std::mem::size_of::<Uniforms>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Fractal Bind Group"),
+            layout: &layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer(uniform_buffer.slice(..)),
+            }],
+        });
+        let blitter = crate::blit::Blitter::new(
+            device,
+            format,
+            "copy.frag",
+            include_str!("shaders/copy.frag"),
+        );
+
+        Self {
+            open: false,
+            settings: Settings::default(),
+            renders: 0,
+            reuses: 0,
+            cursor: None,
+            dragging: false,
+            viewport: None,
+            physical_viewport: None,
+            canvas: crate::blit::Canvas::new(format),
+            rendered: None,
+            pipeline,
+            bind_group,
+            uniform_buffer,
+            blitter,
+            offscreen: None,
+        }
+    }
+
+    pub fn recreate_gpu_resources(&mut self, device: &wgpu::Device, format: wgpu::TextureFormat) {
+        let settings = std::mem::take(&mut self.settings);
+        let open = self.open;
+        *self = Self::new(device, format);
+        self.settings = settings;
+        self.open = open;
+    }
+
+    fn units_per_pixel(&self) -> Option<f64> {
+        self.physical_viewport
+            .map(|rect| self.settings.height / rect.height().max(1.0) as f64)
+    }
+
+    fn hovered(&self) -> Option<egui::Pos2> {
+        let cursor = self.cursor?;
+        let cursor = egui::pos2(cursor.x as f32, cursor.y as f32);
+        self.physical_viewport
+            .filter(|rect| rect.contains(cursor))
+            .map(|_| cursor)
+    }
+
+    pub fn set_cursor(&mut self, cursor: Option<winit::dpi::PhysicalPosition<f64>>) {
+        if let (true, Some(previous), Some(cursor), Some(scale)) =
+            (self.dragging, self.cursor, cursor, self.units_per_pixel())
+        {
+            self.settings.center[0] -= (cursor.x - previous.x) * scale;
+            self.settings.center[1] += (cursor.y - previous.y) * scale;
+        }
+        self.cursor = cursor;
+        if cursor.is_none() {
+            self.dragging = false;
+        }
+    }
+
+    pub fn mouse_input(
+        &mut self,
+        state: winit::event::ElementState,
+        button: winit::event::MouseButton,
+    ) {
+        if button != winit::event::MouseButton::Left {
+            return;
+        }
+        self.dragging = state == winit::event::ElementState::Pressed && self.hovered().is_some();
+    }
+
+    pub fn mouse_wheel(&mut self, delta: winit::event::MouseScrollDelta) {
+        let lines = match delta {
+            winit::event::MouseScrollDelta::LineDelta(_, y) => y as f64,
+            winit::event::MouseScrollDelta::PixelDelta(position) => position.y / 40.0,
+        };
+        let (cursor, rect, scale) = match (
+            self.hovered(),
+            self.physical_viewport,
+            self.units_per_pixel(),
+        ) {
+            (Some(cursor), Some(rect), Some(scale)) => (cursor, rect, scale),
+            _ => return,
+        };
+        let height = (self.settings.height * 0.85f64.powf(lines)).clamp(1e-5, 8.0);
+        let zoom = height / self.settings.height;
+        let offset = [
+            (cursor.x - rect.center().x) as f64 * scale,
+            (rect.center().y - cursor.y) as f64 * scale,
+        ];
+        let center = &mut self.settings.center;
+        center[0] += offset[0] * (1.0 - zoom);
+        center[1] += offset[1] * (1.0 - zoom);
+        self.settings.height = height;
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        let mut open = self.open;
+        let mut viewport = None;
+        let (renders, reuses) = (self.renders, self.reuses);
+        let settings = &mut self.settings;
+        let canvas = &self.canvas;
+        crate::dock::Panel::new("Fractal Explorer")
+            .open(&mut open)
+            .default_pos(egui::pos2(420.0, 120.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut settings.kind, Kind::Mandelbrot, "Mandelbrot");
+                    ui.radio_value(&mut settings.kind, Kind::Julia, "Julia");
+                });
+                if settings.kind == Kind::Julia {
+                    ui.add(egui::Slider::f32(&mut settings.julia[0], -2.0..=2.0).text("c real"));
+                    ui.add(egui::Slider::f32(&mut settings.julia[1], -2.0..=2.0).text("c imag"));
+                }
+                ui.add(
+                    egui::Slider::f32(&mut settings.iterations, 16.0..=2048.0).text("iterations"),
+                );
+                ui.horizontal(|ui| {
+                    for palette in Palette::ALL.iter() {
+                        ui.radio_value(&mut settings.palette, *palette, palette.name());
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "Center {:.6}, {:.6}  Zoom {:.1}x",
+                        settings.center[0],
+                        settings.center[1],
+                        DEFAULT_HEIGHT / settings.height
+                    ));
+                    if ui.button("Reset view").clicked {
+                        settings.center = DEFAULT_CENTER;
+                        settings.height = DEFAULT_HEIGHT;
+                    }
+                });
+                ui.label(format!("Rendered {} frames, reused {}", renders, reuses));
+                ui.label("Drag to pan, scroll to zoom");
+                let rect = ui.allocate_space(egui::vec2(VIEW_SIZE[0], VIEW_SIZE[1]));
+                ui.interact(
+                    rect,
+                    ui.make_persistent_id("fractal_view"),
+                    egui::Sense::drag(),
+                );
+                canvas.paint(ui, rect);
+                viewport = Some(rect);
+            });
+        self.open = open;
+        self.viewport = viewport.filter(|_| open);
+    }
+
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        ui_pass: &mut crate::ui_pass::RenderPass,
+        size: winit::dpi::PhysicalSize<u32>,
+        scale_factor: f64,
+    ) {
+        self.physical_viewport = self
+            .viewport
+            .map(|rect| crate::blit::to_physical(rect, scale_factor));
+        let viewport = match self.physical_viewport {
+            Some(viewport) if crate::blit::fits(viewport, size) => viewport,
+            _ => return,
+        };
+
+        let texture_size = [viewport.width() as u32, viewport.height() as u32];
+        if self.offscreen.as_ref().map(|offscreen| offscreen.size) != Some(texture_size) {
+            let view = device
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: Some("Fractal Target"),
+                    size: wgpu::Extent3d {
+                        width: texture_size[0],
+                        height: texture_size[1],
+                        depth: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: FORMAT,
+                    usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::SAMPLED,
+                })
+                .create_view(&wgpu::TextureViewDescriptor::default());
+            let bind_group = self.blitter.bind(device, &view);
+            self.offscreen = Some(Offscreen {
+                size: texture_size,
+                view,
+                bind_group,
+            });
+            self.rendered = None;
+        }
+        let offscreen = self.offscreen.as_ref().unwrap();
+
+        let settings = &self.settings;
+        let aspect = viewport.width() as f64 / viewport.height() as f64;
+        let uniforms = Uniforms {
+            center: [settings.center[0] as f32, settings.center[1] as f32],
+            extent: [(settings.height * aspect) as f32, settings.height as f32],
+            julia: settings.julia,
+            iterations: settings.iterations as u32,
+            palette: settings.palette as u32,
+            kind: settings.kind as u32,
+            padding: [0; 3],
+        };
+        if self.rendered == Some(uniforms) {
+            self.reuses += 1;
+        } else {
+            uploader.write_buffer(
+                device,
+                encoder,
+                &self.uniform_buffer,
+                0,
+                bytemuck::bytes_of(&uniforms),
+            );
+            {
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                        attachment: &offscreen.view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                            store: true,
+                        },
+                    }],
+                    depth_stencil_attachment: None,
+                });
+                render_pass.set_pipeline(&self.pipeline);
+                render_pass.set_bind_group(0, &self.bind_group, &[]);
+                render_pass.draw(0..3, 0..1);
+            }
+            self.rendered = Some(uniforms);
+            self.renders += 1;
+        }
+
+        let target = self.canvas.begin(device, encoder, ui_pass, size);
+        self.blitter
+            .draw_in(encoder, &offscreen.bind_group, target, viewport);
+    }
+}
@@ -0,0 +1,283 @@
+#[derive(Clone, Copy, PartialEq)]
+enum Tab {
+    Inputs,
+    Selection,
+    Text,
+    Color,
+    Layout,
+    Icons,
+}
+
+impl Tab {
+    const ALL: [Tab; 6] = [
+        Tab::Inputs,
+        Tab::Selection,
+        Tab::Text,
+        Tab::Color,
+        Tab::Layout,
+        Tab::Icons,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Tab::Inputs => "Inputs",
+            Tab::Selection => "Selection",
+            Tab::Text => "Text",
+            Tab::Color => "Color",
+            Tab::Layout => "Layout",
+            Tab::Icons => "Icons",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Fruit {
+    Apple,
+    Banana,
+    Cherry,
+    Durian,
+}
+
+impl Fruit {
+    const ALL: [Fruit; 4] = [Fruit::Apple, Fruit::Banana, Fruit::Cherry, Fruit::Durian];
+
+    fn name(self) -> &'static str {
+        match self {
+            Fruit::Apple => "Apple",
+            Fruit::Banana => "Banana",
+            Fruit::Cherry => "Cherry",
+            Fruit::Durian => "Durian",
+        }
+    }
+}
+
+pub struct WidgetGallery {
+    pub open: bool,
+    tab: Tab,
+    value: f32,
+    range: f32,
+    count: f32,
+    checked: bool,
+    flags: [bool; 3],
+    fruit: Fruit,
+    combo: Fruit,
+    single_line: String,
+    multi_line: String,
+    clicks: u32,
+    color: egui::Srgba,
+    background: egui::Srgba,
+    rows: f32,
+    icons: Vec<crate::svg::SvgImage>,
+    icon_size: f32,
+}
+
+impl Default for WidgetGallery {
+    fn default() -> Self {
+        Self {
+            open: false,
+            tab: Tab::Inputs,
+            value: 0.5,
+            range: 42.0,
+            count: 3.0,
+            checked: true,
+            flags: [true, false, true],
+            fruit: Fruit::Apple,
+            combo: Fruit::Cherry,
+            single_line: "Edit me".to_owned(),
+            multi_line: "Multiple\nlines\nof text".to_owned(),
+            clicks: 0,
+            color: egui::Srgba::new(220, 120, 60, 255),
+            background: egui::Srgba::new(40, 60, 90, 255),
+            rows: 50.0,
+            icons: crate::svg::icon_set(),
+            icon_size: 32.0,
+        }
+    }
+}
+
+impl WidgetGallery {
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        let mut open = self.open;
+        crate::dock::Panel::new("Widget Gallery")
+            .open(&mut open)
+            .default_pos(egui::pos2(300.0, 80.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    for tab in Tab::ALL.iter() {
+                        ui.radio_value(&mut self.tab, *tab, tab.name());
+                    }
+                });
+                ui.separator();
+                match self.tab {
+                    Tab::Inputs => self.inputs(ui),
+                    Tab::Selection => self.selection(ui),
+                    Tab::Text => self.text(ui),
+                    Tab::Color => self.color(ui),
+                    Tab::Layout => self.layout(ui),
+                    Tab::Icons => self.icons(ui),
+                }
+            });
+        self.open = open;
+    }
+
+    pub fn recreate_gpu_resources(&mut self) {
+        for icon in self.icons.iter_mut() {
+            icon.recreate_gpu_resources();
+        }
+    }
+
+    pub fn upload(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        ui_pass: &mut crate::ui_pass::RenderPass,
+        pixels_per_point: f32,
+    ) {
+        for icon in self.icons.iter_mut() {
+            icon.upload(device, queue, ui_pass, pixels_per_point);
+        }
+    }
+
+    fn inputs(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Sliders");
+        ui.add(egui::Slider::f32(&mut self.value, 0.0..=1.0).text("value"));
+        ui.add(egui::Slider::f32(&mut self.range, -100.0..=100.0).text("wide range"));
+        ui.add(egui::Slider::f32(&mut self.count, 0.0..=10.0).text("whole numbers"));
+        self.count = self.count.round();
+
+        ui.heading("Checkboxes");
+        ui.checkbox(&mut self.checked, "Checked");
+        ui.horizontal(|ui| {
+            for (index, flag) in self.flags.iter_mut().enumerate() {
+                ui.checkbox(flag, format!("Flag {}", index + 1));
+            }
+        });
+
+        ui.heading("Buttons");
+        ui.horizontal(|ui| {
+            if ui.button("Click me").clicked {
+                self.clicks += 1;
+            }
+            if ui.button("Reset").clicked {
+                self.clicks = 0;
+            }
+            ui.label(format!("clicked {} times", self.clicks));
+        });
+    }
+
+    fn selection(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Radio buttons");
+        ui.horizontal(|ui| {
+            for fruit in Fruit::ALL.iter() {
+                ui.radio_value(&mut self.fruit, *fruit, fruit.name());
+            }
+        });
+        ui.horizontal(|ui| {
+            for fruit in Fruit::ALL.iter() {
+                if ui.radio(self.fruit == *fruit, fruit.name()).clicked {
+                    self.fruit = *fruit;
+                }
+            }
+        });
+        ui.label(format!("Selected: {}", self.fruit.name()));
+
+        ui.heading("Combo box");
+        let combo = &mut self.combo;
+        egui::combo_box_with_label(ui, "Fruit", combo.name(), |ui| {
+            for fruit in Fruit::ALL.iter() {
+                ui.radio_value(combo, *fruit, fruit.name());
+            }
+        });
+    }
+
+    fn text(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Single line");
+        ui.add(egui::TextEdit::new(&mut self.single_line));
+        ui.label(format!("{} characters", self.single_line.chars().count()));
+
+        ui.heading("Multi line");
+        ui.add(egui::TextEdit::new(&mut self.multi_line).multiline(true));
+        ui.label(format!("{} lines", self.multi_line.lines().count()));
+
+        ui.heading("Labels");
+        ui.label("A plain label");
+        ui.add(egui::Label::new("Monospace").text_style(egui::TextStyle::Monospace));
+        ui.add(egui::Label::new("Small").text_style(egui::TextStyle::Small));
+        ui.label("中文 日本語 한국어 Ελληνικά ☃");
+        ui.add(egui::Hyperlink::new("https://github.com/emilk/egui").text("egui on GitHub"));
+    }
+
+    fn color(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Color pickers");
+        ui.horizontal(|ui| {
+            egui::color_picker::color_edit_button_srgba(ui, &mut self.color);
+            ui.label("Foreground");
+        });
+        ui.horizontal(|ui| {
+            egui::color_picker::color_edit_button_srgba(ui, &mut self.background);
+            ui.label("Background");
+        });
+
+        let rect = ui.allocate_space(egui::vec2(240.0, 60.0));
+        ui.painter().rect_filled(rect, 4.0, self.background);
+        ui.painter().text(
+            rect.center(),
+            (egui::Align::Center, egui::Align::Center),
+            "Preview".to_owned(),
+            egui::TextStyle::Heading,
+            self.color,
+        );
+        let [r, g, b, a] = self.color.to_array();
+        ui.label(format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a));
+    }
+
+    fn layout(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Collapsing headers");
+        egui::CollapsingHeader::new("Closed by default").show(ui, |ui| {
+            ui.label("Hidden until opened.");
+            egui::CollapsingHeader::new("Nested").show(ui, |ui| {
+                ui.label("Headers can nest.");
+            });
+        });
+        egui::CollapsingHeader::new("Open by default")
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.label("Visible on first show.");
+            });
+
+        ui.heading("Grid");
+        let flags = self.flags;
+        ui.columns(3, |columns| {
+            for (column, header) in ["Name", "Value", "Enabled"].iter().enumerate() {
+                columns[column].label(*header);
+            }
+            for (index, flag) in flags.iter().enumerate() {
+                columns[0].label(format!("Flag {}", index + 1));
+                columns[1].label(format!("{}", *flag as u32));
+                columns[2].label(if *flag { "yes" } else { "no" });
+            }
+        });
+
+        ui.heading("Scroll area");
+        ui.add(egui::Slider::f32(&mut self.rows, 10.0..=500.0).text("rows"));
+        egui::ScrollArea::from_max_height(160.0).show(ui, |ui| {
+            for row in 0..self.rows as u32 {
+                ui.label(format!("Row {}", row));
+            }
+        });
+    }
+
+    fn icons(&mut self, ui: &mut egui::Ui) {
+        ui.heading("SVG icons");
+        ui.label("Rasterized at the current scale, so they stay sharp at any size.");
+        ui.add(egui::Slider::f32(&mut self.icon_size, 12.0..=128.0).text("size"));
+        self.icon_size = self.icon_size.round();
+        let size = self.icon_size;
+        ui.horizontal(|ui| {
+            for icon in self.icons.iter_mut() {
+                let response = icon.paint(ui, size);
+                crate::status::hint_on_hover(&response, icon.name());
+            }
+        });
+    }
+}
@@ -0,0 +1,377 @@
+const MAX_DEVICES: usize = 8;
+const STICK_SIZE: f32 = 90.0;
+
+enum Event {
+    Connected {
+        index: usize,
+        name: String,
+    },
+    Axis {
+        index: usize,
+        number: usize,
+        value: f32,
+    },
+    Button {
+        index: usize,
+        number: usize,
+        pressed: bool,
+    },
+    Disconnected {
+        index: usize,
+    },
+}
+
+#[derive(Default)]
+struct Pad {
+    name: String,
+    connected: bool,
+    axes: Vec<f32>,
+    buttons: Vec<bool>,
+    presses: Vec<u32>,
+}
+
+#[cfg(target_os = "linux")]
+fn read_joystick(index: usize, sender: std::sync::mpsc::Sender<Event>) {
+    let path = format!("/dev/input/js{}", index);
+    let mut file = match std::fs::File::open(&path) {
+        Ok(file) => file,
+        Err(_) => return,
+    };
+    let name = std::fs::read_to_string(format!("/sys/class/input/js{}/device/name", index))
+        .map(|name| name.trim().to_owned())
+        .unwrap_or_else(|_| path.clone());
+    log::info!("gamepad connected: {} ({})", name, path);
+    if sender.send(Event::Connected { index, name }).is_err() {
+        return;
+    }
+    let mut event = [0u8; 8];
+    while std::io::Read::read_exact(&mut file, &mut event).is_ok() {
+        let value = i16::from_le_bytes([event[4], event[5]]);
+        let kind = event[6] & !0x80;
+        let number = event[7] as usize;
+        let event = match kind {
+            0x01 => Event::Button {
+                index,
+                number,
+                pressed: value != 0,
+            },
+            0x02 => Event::Axis {
+                index,
+                number,
+                value: value as f32 / 32767.0,
+            },
+            _ => continue,
+        };
+        if sender.send(event).is_err() {
+            return;
+        }
+    }
+    log::info!("gamepad disconnected: {}", path);
+    sender.send(Event::Disconnected { index }).ok();
+}
+
+#[cfg(target_os = "linux")]
+fn start_backend(sender: std::sync::mpsc::Sender<Event>) -> bool {
+    let open = std::sync::Arc::new(std::sync::Mutex::new([false; MAX_DEVICES]));
+    std::thread::Builder::new()
+        .name("gamepad scanner".to_owned())
+        .spawn(move || loop {
+            for index in 0..MAX_DEVICES {
+                let present = std::path::Path::new(&format!("/dev/input/js{}", index)).exists();
+                let mut open_devices = open.lock().unwrap();
+                if present && !open_devices[index] {
+                    open_devices[index] = true;
+                    let sender = sender.clone();
+                    let open = open.clone();
+                    std::thread::Builder::new()
+                        .name(format!("gamepad {}", index))
+                        .spawn(move || {
+                            read_joystick(index, sender);
+                            open.lock().unwrap()[index] = false;
+                        })
+                        .unwrap();
+                }
+            }
+            std::thread::sleep(std::time::Duration::from_secs(1));
+        })
+        .unwrap();
+    true
+}
+
+#[cfg(not(target_os = "linux"))]
+fn start_backend(_sender: std::sync::mpsc::Sender<Event>) -> bool {
+    false
+}
+
+#[cfg(all(target_os = "linux", target_pointer_width = "64"))]
+fn rumble(index: usize, strong: f32, weak: f32, duration: u16) -> Result<(), String> {
+    extern "C" {
+        fn ioctl(
+            fd: std::os::raw::c_int,
+            request: std::os::raw::c_ulong,
+            ...
+        ) -> std::os::raw::c_int;
+    }
+    const EVIOCSFF: std::os::raw::c_ulong = 0x4030_4580;
+    const FF_RUMBLE: u16 = 0x50;
+    const EV_FF: u16 = 0x15;
+
+    let device = std::fs::read_dir(format!("/sys/class/input/js{}/device", index))
+        .map_err(|err| err.to_string())?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .find(|name| name.starts_with("event"))
+        .ok_or_else(|| "no event device for this gamepad".to_owned())?;
+    let mut file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(format!("/dev/input/{}", device))
+        .map_err(|err| err.to_string())?;
+
+    let mut effect = [0u8; 48];
+    effect[0..2].copy_from_slice(&FF_RUMBLE.to_ne_bytes());
+    effect[2..4].copy_from_slice(&(-1i16).to_ne_bytes());
+    effect[10..12].copy_from_slice(&duration.to_ne_bytes());
+    effect[16..18].copy_from_slice(&((strong * 65535.0) as u16).to_ne_bytes());
+    effect[18..20].copy_from_slice(&((weak * 65535.0) as u16).to_ne_bytes());
+    let fd = std::os::unix::io::AsRawFd::as_raw_fd(&file);
+    if unsafe { ioctl(fd, EVIOCSFF, effect.as_mut_ptr()) } < 0 {
+        return Err(format!(
+            "force feedback upload failed: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+    let id = u16::from_ne_bytes([effect[2], effect[3]]);
+
+    let mut play = [0u8; 24];
+    play[16..18].copy_from_slice(&EV_FF.to_ne_bytes());
+    play[18..20].copy_from_slice(&id.to_ne_bytes());
+    play[20..24].copy_from_slice(&1i32.to_ne_bytes());
+    std::io::Write::write_all(&mut file, &play).map_err(|err| err.to_string())?;
+    std::thread::Builder::new()
+        .name("gamepad rumble".to_owned())
+        .spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(duration as u64 + 50));
+            drop(file);
+        })
+        .unwrap();
+    Ok(())
+}
+
+#[cfg(not(all(target_os = "linux", target_pointer_width = "64")))]
+fn rumble(_index: usize, _strong: f32, _weak: f32, _duration: u16) -> Result<(), String> {
+    Err("rumble is only supported through Linux force feedback".to_owned())
+}
+
+fn apply_deadzone(value: f32, deadzone: f32) -> f32 {
+    if value.abs() <= deadzone {
+        0.0
+    } else {
+        value.signum() * (value.abs() - deadzone) / (1.0 - deadzone)
+    }
+}
+
+pub struct GamepadPanel {
+    pub open: bool,
+    pub deadzone: f32,
+    receiver: Option<std::sync::mpsc::Receiver<Event>>,
+    supported: bool,
+    pads: Vec<Pad>,
+    duration: f32,
+    status: String,
+}
+
+impl Default for GamepadPanel {
+    fn default() -> Self {
+        Self {
+            open: false,
+            deadzone: 0.1,
+            receiver: None,
+            supported: true,
+            pads: (0..MAX_DEVICES).map(|_| Pad::default()).collect(),
+            duration: 400.0,
+            status: String::new(),
+        }
+    }
+}
+
+impl GamepadPanel {
+    fn poll(&mut self) {
+        let receiver = match &self.receiver {
+            Some(receiver) => receiver,
+            None => {
+                let (sender, receiver) = std::sync::mpsc::channel();
+                self.supported = start_backend(sender);
+                self.receiver = Some(receiver);
+                return;
+            }
+        };
+        for event in receiver.try_iter() {
+            match event {
+                Event::Connected { index, name } => {
+                    self.pads[index] = Pad {
+                        name,
+                        connected: true,
+                        ..Pad::default()
+                    };
+                }
+                Event::Axis {
+                    index,
+                    number,
+                    value,
+                } => {
+                    let axes = &mut self.pads[index].axes;
+                    if axes.len() <= number {
+                        axes.resize(number + 1, 0.0);
+                    }
+                    axes[number] = value;
+                }
+                Event::Button {
+                    index,
+                    number,
+                    pressed,
+                } => {
+                    let pad = &mut self.pads[index];
+                    if pad.buttons.len() <= number {
+                        pad.buttons.resize(number + 1, false);
+                        pad.presses.resize(number + 1, 0);
+                    }
+                    if pressed && !pad.buttons[number] {
+                        pad.presses[number] += 1;
+                    }
+                    pad.buttons[number] = pressed;
+                }
+                Event::Disconnected { index } => self.pads[index].connected = false,
+            }
+        }
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        if !self.open {
+            return;
+        }
+        self.poll();
+
+        let mut open = self.open;
+        let mut rumble_request = None;
+        let deadzone = &mut self.deadzone;
+        let duration = &mut self.duration;
+        let pads = &self.pads;
+        let supported = self.supported;
+        let status = &self.status;
+        crate::dock::Panel::new("Gamepads")
+            .open(&mut open)
+            .default_pos(egui::pos2(420.0, 80.0))
+            .show(ctx, |ui| {
+                if !supported {
+                    ui.label("Gamepad input needs the Linux joystick interface, which this platform does not have.");
+                    return;
+                }
+                ui.add(egui::Slider::f32(deadzone, 0.0..=0.5).text("deadzone"));
+                ui.add(egui::Slider::f32(duration, 50.0..=2000.0).text("rumble ms"));
+                let connected = pads.iter().filter(|pad| pad.connected).count();
+                if connected == 0 {
+                    ui.label("No gamepads connected. Devices are picked up from /dev/input/js*.");
+                }
+                for (index, pad) in pads.iter().enumerate().filter(|(_, pad)| pad.connected) {
+                    egui::CollapsingHeader::new(format!("{} (js{})", pad.name, index))
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                for pair in pad.axes.chunks(2).filter(|pair| pair.len() == 2) {
+                                    let rect = ui.allocate_space(egui::vec2(STICK_SIZE, STICK_SIZE));
+                                    let painter = ui.painter();
+                                    let radius = STICK_SIZE * 0.5;
+                                    painter.rect_filled(rect, radius, egui::Srgba::new(50, 50, 50, 255));
+                                    let inner = egui::Rect::from_center_size(
+                                        rect.center(),
+                                        egui::vec2(STICK_SIZE, STICK_SIZE) * *deadzone,
+                                    );
+                                    painter.rect_filled(inner, inner.width() * 0.5, egui::Srgba::new(80, 60, 60, 255));
+                                    let raw = rect.center() + egui::vec2(pair[0], pair[1]) * radius;
+                                    let filtered = rect.center()
+                                        + egui::vec2(
+                                            apply_deadzone(pair[0], *deadzone),
+                                            apply_deadzone(pair[1], *deadzone),
+                                        ) * radius;
+                                    painter.rect_filled(
+                                        egui::Rect::from_center_size(raw, egui::vec2(6.0, 6.0)),
+                                        3.0,
+                                        egui::Srgba::new(120, 120, 120, 255),
+                                    );
+                                    painter.rect_filled(
+                                        egui::Rect::from_center_size(filtered, egui::vec2(10.0, 10.0)),
+                                        5.0,
+                                        egui::Srgba::new(90, 170, 250, 255),
+                                    );
+                                }
+                            });
+                            for (number, value) in pad.axes.iter().enumerate() {
+                                ui.horizontal(|ui| {
+                                    let rect = ui.allocate_space(egui::vec2(160.0, 10.0));
+                                    let filtered = apply_deadzone(*value, *deadzone);
+                                    let center = rect.center().x;
+                                    let end = center + filtered * rect.width() * 0.5;
+                                    ui.painter().rect_filled(rect, 2.0, egui::Srgba::new(50, 50, 50, 255));
+                                    ui.painter().rect_filled(
+                                        egui::Rect::from_min_max(
+                                            egui::pos2(center.min(end), rect.min.y),
+                                            egui::pos2(center.max(end), rect.max.y),
+                                        ),
+                                        2.0,
+                                        egui::Srgba::new(90, 170, 250, 255),
+                                    );
+                                    ui.label(format!("axis {} {:+.3} ({:+.3})", number, filtered, value));
+                                });
+                            }
+                            ui.horizontal(|ui| {
+                                for (number, pressed) in pad.buttons.iter().enumerate() {
+                                    let rect = ui.allocate_space(egui::vec2(22.0, 22.0));
+                                    let fill = if *pressed {
+                                        egui::Srgba::new(90, 200, 120, 255)
+                                    } else {
+                                        egui::Srgba::new(60, 60, 60, 255)
+                                    };
+                                    ui.painter().rect_filled(rect, 11.0, fill);
+                                    ui.painter().text(
+                                        rect.center(),
+                                        (egui::Align::Center, egui::Align::Center),
+                                        number.to_string(),
+                                        egui::TextStyle::Small,
+                                        egui::Srgba::new(255, 255, 255, 255),
+                                    );
+                                }
+                            });
+                            let presses: u32 = pad.presses.iter().sum();
+                            ui.label(format!(
+                                "{} axes, {} buttons, {} presses",
+                                pad.axes.len(),
+                                pad.buttons.len(),
+                                presses
+                            ));
+                            ui.horizontal(|ui| {
+                                if ui.button("Rumble weak").clicked {
+                                    rumble_request = Some((index, 0.0, 0.8));
+                                }
+                                if ui.button("Rumble strong").clicked {
+                                    rumble_request = Some((index, 1.0, 0.0));
+                                }
+                                if ui.button("Rumble both").clicked {
+                                    rumble_request = Some((index, 1.0, 1.0));
+                                }
+                            });
+                        });
+                }
+                if !status.is_empty() {
+                    ui.label(status.as_str());
+                }
+            });
+        if let Some((index, strong, weak)) = rumble_request {
+            self.status = match rumble(index, strong, weak, self.duration as u16) {
+                Ok(()) => String::new(),
+                Err(err) => format!("Rumble failed: {}", err),
+            };
+        }
+        self.open = open;
+    }
+}
@@ -0,0 +1,181 @@
+pub const USAGE: &str = concat!(
+    "usage: ",
+    env!("CARGO_PKG_NAME"),
+    " --headless [--frames N] [--output PATH] [--size WIDTHxHEIGHT] [--turntable FRAMES]"
+);
+
+pub struct Options {
+    pub frames: u32,
+    pub output: std::path::PathBuf,
+    pub size: winit::dpi::PhysicalSize<u32>,
+    pub turntable: Option<u32>,
+}
+
+impl Options {
+    pub fn from_args(args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut headless = false;
+        let mut output_set = false;
+        let mut options = Self {
+            frames: 60,
+            output: std::path::PathBuf::from("headless.png"),
+            size: winit::dpi::PhysicalSize::new(800, 600),
+            turntable: None,
+        };
+
+        let mut args = args.skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--headless" => headless = true,
+                "--frames" => {
+                    options.frames = args
+                        .next()
+                        .and_then(|value| value.parse().ok())
+                        .ok_or_else(|| "--frames expects a number".to_owned())?;
+                }
+                "--output" => {
+                    options.output = args
+                        .next()
+                        .ok_or_else(|| "--output expects a path".to_owned())?
+                        .into();
+                    output_set = true;
+                }
+                "--size" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| "--size expects WIDTHxHEIGHT".to_owned())?;
+                    let mut parts = value.split('x').map(|part| part.parse::<u32>());
+                    match (parts.next(), parts.next()) {
+                        (Some(Ok(width)), Some(Ok(height))) => {
+                            options.size = winit::dpi::PhysicalSize::new(width, height);
+                        }
+                        _ => return Err(format!("--size expects WIDTHxHEIGHT, got {}", value)),
+                    }
+                }
+                "--turntable" => {
+                    options.turntable = Some(
+                        args.next()
+                            .and_then(|value| value.parse().ok())
+                            .ok_or_else(|| "--turntable expects a frame count".to_owned())?,
+                    );
+                }
+                _ => {}
+            }
+        }
+        if options.turntable.is_some() && !output_set {
+            options.output = std::path::PathBuf::from("turntable");
+        }
+
+        if headless {
+            Ok(Some(options))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+pub fn run(options: Options) -> Result<(), String> {
+    log::info!("initializing headless");
+    let time = std::time::Instant::now();
+
+    let mut engine = futures::executor::block_on(crate::Engine::new_headless(options.size, 1.0));
+
+    log::info!("initialized, took {} ms", time.elapsed().as_millis());
+
+    for _ in 0..options.frames {
+        engine.update();
+        engine.render();
+    }
+
+    if let Some(frames) = options.turntable {
+        let format = match options.output.extension() {
+            Some(extension) if extension == "gif" => crate::turntable::Format::Gif,
+            _ => crate::turntable::Format::Png,
+        };
+        engine.turntable.settings = crate::turntable::Settings {
+            frames: frames as f32,
+            width: options.size.width as f32,
+            height: options.size.height as f32,
+            format,
+            output: options.output.to_string_lossy().into_owned(),
+            ..crate::turntable::Settings::default()
+        };
+        engine.turntable.start_requested = true;
+        engine.update();
+        while engine.turntable.is_running() {
+            engine.update();
+        }
+        return Ok(());
+    }
+
+    let image = engine.capture()?;
+    image
+        .save(&options.output)
+        .map_err(|err| format!("cannot write {}: {}", options.output.display(), err))?;
+    log::info!(
+        "rendered {} frames, wrote {}",
+        options.frames,
+        options.output.display()
+    );
+    Ok(())
+}
+
+pub fn read_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+    size: winit::dpi::PhysicalSize<u32>,
+) -> Result<image::RgbaImage, String> {
+    let unpadded_bytes_per_row = size.width * 4;
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(align) * align;
+
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Readback Buffer"),
+        size: (padded_bytes_per_row * size.height) as wgpu::BufferAddress,
+        usage: wgpu::BufferUsage::MAP_READ | wgpu::BufferUsage::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Readback Encoder"),
+    });
+    encoder.copy_texture_to_buffer(
+        wgpu::TextureCopyView {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+        },
+        wgpu::BufferCopyView {
+            buffer: &buffer,
+            layout: wgpu::TextureDataLayout {
+                offset: 0,
+                bytes_per_row: padded_bytes_per_row,
+                rows_per_image: size.height,
+            },
+        },
+        wgpu::Extent3d {
+            width: size.width,
+            height: size.height,
+            depth: 1,
+        },
+    );
+    queue.submit(std::iter::once(encoder.finish()));
+
+    let slice = buffer.slice(..);
+    let mapping = slice.map_async(wgpu::MapMode::Read);
+    device.poll(wgpu::Maintain::Wait);
+    futures::executor::block_on(mapping)
+        .map_err(|err| format!("cannot map the readback buffer: {:?}", err))?;
+
+    let mut pixels = Vec::with_capacity((unpadded_bytes_per_row * size.height) as usize);
+    {
+        let data = slice.get_mapped_range();
+        for row in data.chunks(padded_bytes_per_row as usize) {
+            pixels.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+        }
+    }
+    buffer.unmap();
+
+    image::RgbaImage::from_raw(size.width, size.height, pixels)
+        .ok_or_else(|| "the readback buffer has an unexpected size".to_owned())
+}
@@ -0,0 +1,103 @@
+#[derive(Default)]
+pub struct SceneHud {
+    expanded: bool,
+}
+
+impl SceneHud {
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, scene: &mut crate::scene::Scene) {
+        let stats = scene.stats;
+
+        egui::Area::new("scene_hud")
+            .fixed_pos(egui::pos2(8.0, 8.0))
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(format!(
+                            "{} draws | {} tris | {} inst | {} culled | {} lights | {:.0}% res",
+                            stats.draw_calls,
+                            stats.triangles,
+                            stats.instances,
+                            stats.culled,
+                            stats.visible_lights,
+                            scene.render_scale() * 100.0,
+                        ));
+                        let toggle = if self.expanded { "⏶" } else { "⏷" };
+                        if ui.button(toggle).clicked {
+                            self.expanded = !self.expanded;
+                        }
+                    });
+                });
+            });
+
+        let mut expanded = self.expanded;
+        egui::Window::new("Scene Statistics")
+            .open(&mut expanded)
+            .default_pos(egui::pos2(8.0, 48.0))
+            .show(ctx, |ui| {
+                egui::CollapsingHeader::new("Rendering")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        ui.label(format!("Draw calls: {}", stats.draw_calls));
+                        ui.label(format!("Triangles: {}", stats.triangles));
+                        ui.label(format!("Instances: {}", stats.instances));
+                        ui.label(format!("Encode time: {:.2} ms", stats.encode_time * 1000.0));
+                    });
+                egui::CollapsingHeader::new("Instancing")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        ui.checkbox(&mut scene.instancing, "Batch identical meshes");
+                        ui.horizontal(|ui| {
+                            if ui.button("Scatter 10k cubes").clicked {
+                                scene.scatter_cubes(10_000);
+                            }
+                            if ui.button("Clear").clicked {
+                                scene.clear_scattered();
+                            }
+                        });
+                    });
+                egui::CollapsingHeader::new("Objects")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        ui.label(format!("Total: {}", stats.objects));
+                        ui.label(format!(
+                            "Visible: {}",
+                            stats.objects.saturating_sub(stats.culled)
+                        ));
+                        ui.label(format!("Frustum culled: {}", stats.culled));
+                        ui.label(format!("Meshes: {}", scene.meshes.len()));
+                        ui.checkbox(&mut scene.frustum_culling, "Frustum culling");
+                    });
+                egui::CollapsingHeader::new("Level of detail")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        for (level, count) in stats.lod_counts.iter().enumerate() {
+                            ui.label(format!("LOD{}: {} objects", level, count));
+                        }
+                        ui.add(
+                            egui::Slider::f32(&mut scene.lod_threshold, 0.1..=16.0)
+                                .text("error threshold (px)"),
+                        );
+                        ui.checkbox(&mut scene.lod_debug, "Color by LOD");
+                    });
+                egui::CollapsingHeader::new("Lights")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        ui.label(format!("Total: {}", stats.lights));
+                        ui.label(format!("Visible: {}", stats.visible_lights));
+                    });
+                egui::CollapsingHeader::new("Camera")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        let eye = scene.camera.eye();
+                        ui.label(format!("Eye: {:.2}, {:.2}, {:.2}", eye.x, eye.y, eye.z));
+                        ui.label(format!("Distance: {:.2}", scene.camera.distance));
+                        ui.label(format!(
+                            "Yaw / pitch: {:.1}° / {:.1}°",
+                            scene.camera.yaw.to_degrees(),
+                            scene.camera.pitch.to_degrees()
+                        ));
+                    });
+            });
+        self.expanded = expanded;
+    }
+}
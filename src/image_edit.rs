@@ -0,0 +1,225 @@
+const UNDO_LIMIT: usize = 100;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Operation {
+    Crop {
+        left: f32,
+        top: f32,
+        right: f32,
+        bottom: f32,
+    },
+    RotateLeft,
+    RotateRight,
+    Rotate180,
+    FlipHorizontal,
+    FlipVertical,
+    Adjust {
+        brightness: f32,
+        contrast: f32,
+    },
+    Resize(f32),
+}
+
+impl Operation {
+    pub fn name(self) -> String {
+        match self {
+            Operation::Crop {
+                left,
+                top,
+                right,
+                bottom,
+            } => format!(
+                "Crop {:.0}% {:.0}% {:.0}% {:.0}%",
+                left * 100.0,
+                top * 100.0,
+                right * 100.0,
+                bottom * 100.0
+            ),
+            Operation::RotateLeft => "Rotate left".to_owned(),
+            Operation::RotateRight => "Rotate right".to_owned(),
+            Operation::Rotate180 => "Rotate 180°".to_owned(),
+            Operation::FlipHorizontal => "Flip horizontally".to_owned(),
+            Operation::FlipVertical => "Flip vertically".to_owned(),
+            Operation::Adjust {
+                brightness,
+                contrast,
+            } => format!("Brightness {:+.0}, contrast {:+.0}", brightness, contrast),
+            Operation::Resize(scale) => format!("Resize to {:.0}%", scale * 100.0),
+        }
+    }
+
+    fn crop_rect(self, size: [u32; 2]) -> Option<[u32; 4]> {
+        let (left, top, right, bottom) = match self {
+            Operation::Crop {
+                left,
+                top,
+                right,
+                bottom,
+            } => (left, top, right, bottom),
+            _ => return None,
+        };
+        let [width, height] = size;
+        let x = ((left * width as f32).round() as u32).min(width.saturating_sub(1));
+        let y = ((top * height as f32).round() as u32).min(height.saturating_sub(1));
+        let cropped_width = ((1.0 - left - right) * width as f32).round() as u32;
+        let cropped_height = ((1.0 - top - bottom) * height as f32).round() as u32;
+        Some([
+            x,
+            y,
+            cropped_width.max(1).min(width - x),
+            cropped_height.max(1).min(height - y),
+        ])
+    }
+
+    pub fn output_size(self, size: [u32; 2]) -> [u32; 2] {
+        match self {
+            Operation::Crop { .. } => {
+                let [_, _, width, height] = self.crop_rect(size).unwrap();
+                [width, height]
+            }
+            Operation::RotateLeft | Operation::RotateRight => [size[1], size[0]],
+            Operation::Resize(scale) => [
+                ((size[0] as f32 * scale).round() as u32).max(1),
+                ((size[1] as f32 * scale).round() as u32).max(1),
+            ],
+            _ => size,
+        }
+    }
+
+    pub fn apply(self, image: &image::RgbaImage) -> image::RgbaImage {
+        match self {
+            Operation::Crop { .. } => {
+                let [x, y, width, height] =
+                    self.crop_rect([image.width(), image.height()]).unwrap();
+                image::imageops::crop_imm(image, x, y, width, height).to_image()
+            }
+            Operation::RotateLeft => image::imageops::rotate270(image),
+            Operation::RotateRight => image::imageops::rotate90(image),
+            Operation::Rotate180 => image::imageops::rotate180(image),
+            Operation::FlipHorizontal => image::imageops::flip_horizontal(image),
+            Operation::FlipVertical => image::imageops::flip_vertical(image),
+            Operation::Adjust {
+                brightness,
+                contrast,
+            } => {
+                let brightened = image::imageops::brighten(image, brightness.round() as i32);
+                image::imageops::contrast(&brightened, contrast)
+            }
+            Operation::Resize(_) => {
+                let [width, height] = self.output_size([image.width(), image.height()]);
+                image::imageops::resize(image, width, height, image::imageops::FilterType::Triangle)
+            }
+        }
+    }
+}
+
+pub fn apply_all<'a>(
+    image: &image::RgbaImage,
+    operations: impl IntoIterator<Item = &'a Operation>,
+) -> image::RgbaImage {
+    operations
+        .into_iter()
+        .fold(image.clone(), |image, operation| operation.apply(&image))
+}
+
+pub fn output_size<'a>(
+    size: [u32; 2],
+    operations: impl IntoIterator<Item = &'a Operation>,
+) -> [u32; 2] {
+    operations
+        .into_iter()
+        .fold(size, |size, operation| operation.output_size(size))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Draft {
+    pub crop: [f32; 4],
+    pub brightness: f32,
+    pub contrast: f32,
+    pub resize: f32,
+}
+
+impl Default for Draft {
+    fn default() -> Self {
+        Self {
+            crop: [0.0; 4],
+            brightness: 0.0,
+            contrast: 0.0,
+            resize: 1.0,
+        }
+    }
+}
+
+impl Draft {
+    pub fn operations(&self) -> Vec<Operation> {
+        let mut operations = Vec::new();
+        if self.crop.iter().any(|edge| *edge > 0.0) {
+            let [left, top, right, bottom] = self.crop;
+            operations.push(Operation::Crop {
+                left,
+                top,
+                right,
+                bottom,
+            });
+        }
+        if self.brightness != 0.0 || self.contrast != 0.0 {
+            operations.push(Operation::Adjust {
+                brightness: self.brightness,
+                contrast: self.contrast,
+            });
+        }
+        if (self.resize - 1.0).abs() > f32::EPSILON {
+            operations.push(Operation::Resize(self.resize));
+        }
+        operations
+    }
+}
+
+#[derive(Default)]
+pub struct Edits {
+    pub operations: Vec<Operation>,
+    pub draft: Draft,
+    redo: Vec<Operation>,
+}
+
+impl Edits {
+    pub fn push(&mut self, operation: Operation) {
+        if self.operations.len() == UNDO_LIMIT {
+            self.operations.remove(0);
+        }
+        self.operations.push(operation);
+        self.redo.clear();
+    }
+
+    pub fn commit_draft(&mut self) {
+        for operation in std::mem::take(&mut self.draft).operations() {
+            self.push(operation);
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.operations.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn undo(&mut self) {
+        if let Some(operation) = self.operations.pop() {
+            self.redo.push(operation);
+        }
+    }
+
+    pub fn redo(&mut self) {
+        if let Some(operation) = self.redo.pop() {
+            self.operations.push(operation);
+        }
+    }
+
+    pub fn preview(&self) -> Vec<Operation> {
+        let mut operations = self.operations.clone();
+        operations.extend(self.draft.operations());
+        operations
+    }
+}
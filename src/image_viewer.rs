@@ -0,0 +1,910 @@
+const PREVIEW_SIZE: u32 = 96;
+const ANALYSIS_SIZE: u32 = 512;
+const PREVIEW_AREA: [f32; 2] = [360.0, 270.0];
+const MIN_ZOOM: f32 = 0.05;
+const MAX_ZOOM: f32 = 32.0;
+const CAMERA_TAGS: [exif::Tag; 6] = [
+    exif::Tag::Make,
+    exif::Tag::Model,
+    exif::Tag::LensModel,
+    exif::Tag::Software,
+    exif::Tag::DateTimeOriginal,
+    exif::Tag::Artist,
+];
+const EXPOSURE_TAGS: [exif::Tag; 9] = [
+    exif::Tag::ExposureTime,
+    exif::Tag::FNumber,
+    exif::Tag::PhotographicSensitivity,
+    exif::Tag::FocalLength,
+    exif::Tag::FocalLengthIn35mmFilm,
+    exif::Tag::ExposureBiasValue,
+    exif::Tag::ExposureProgram,
+    exif::Tag::MeteringMode,
+    exif::Tag::Flash,
+];
+const IPTC_DATASETS: [(u8, &str); 9] = [
+    (5, "Title"),
+    (25, "Keywords"),
+    (80, "By-line"),
+    (90, "City"),
+    (95, "Province/State"),
+    (101, "Country"),
+    (105, "Headline"),
+    (116, "Copyright"),
+    (120, "Caption"),
+];
+
+#[derive(Default)]
+struct Metadata {
+    camera: Vec<(String, String)>,
+    exposure: Vec<(String, String)>,
+    gps: Option<(f64, f64, Option<f64>)>,
+    iptc: Vec<(String, String)>,
+    all: Vec<(String, String)>,
+    error: Option<String>,
+}
+
+fn rationals(field: &exif::Field) -> Option<Vec<f64>> {
+    match &field.value {
+        exif::Value::Rational(values) => Some(values.iter().map(|value| value.to_f64()).collect()),
+        _ => None,
+    }
+}
+
+fn coordinate(exif: &exif::Exif, tag: exif::Tag, reference: exif::Tag) -> Option<f64> {
+    let parts = rationals(exif.get_field(tag, exif::In::PRIMARY)?)?;
+    let degrees = parts
+        .iter()
+        .zip([1.0, 60.0, 3600.0].iter())
+        .fold(0.0, |total, (part, divisor)| total + part / divisor);
+    let negative = match &exif.get_field(reference, exif::In::PRIMARY)?.value {
+        exif::Value::Ascii(values) => values
+            .first()
+            .is_some_and(|value| value.starts_with(b"S") || value.starts_with(b"W")),
+        _ => false,
+    };
+    Some(if negative { -degrees } else { degrees })
+}
+
+fn read_exif(bytes: &[u8], metadata: &mut Metadata) {
+    let exif = match exif::Reader::new().read_from_container(&mut std::io::Cursor::new(bytes)) {
+        Ok(exif) => exif,
+        Err(exif::Error::NotFound(_)) => return,
+        Err(err) => {
+            metadata.error = Some(err.to_string());
+            return;
+        }
+    };
+    let describe = |tag: exif::Tag| {
+        exif.get_field(tag, exif::In::PRIMARY).map(|field| {
+            (
+                tag.to_string(),
+                field.display_value().with_unit(&exif).to_string(),
+            )
+        })
+    };
+    metadata.camera = CAMERA_TAGS
+        .iter()
+        .filter_map(|tag| describe(*tag))
+        .collect();
+    metadata.exposure = EXPOSURE_TAGS
+        .iter()
+        .filter_map(|tag| describe(*tag))
+        .collect();
+    let latitude = coordinate(&exif, exif::Tag::GPSLatitude, exif::Tag::GPSLatitudeRef);
+    let longitude = coordinate(&exif, exif::Tag::GPSLongitude, exif::Tag::GPSLongitudeRef);
+    if let (Some(latitude), Some(longitude)) = (latitude, longitude) {
+        let altitude = exif
+            .get_field(exif::Tag::GPSAltitude, exif::In::PRIMARY)
+            .and_then(rationals)
+            .and_then(|values| values.first().copied());
+        metadata.gps = Some((latitude, longitude, altitude));
+    }
+    metadata.all = exif
+        .fields()
+        .map(|field| {
+            (
+                format!("{} ({})", field.tag, field.ifd_num),
+                field.display_value().with_unit(&exif).to_string(),
+            )
+        })
+        .collect();
+}
+
+fn iptc_block(bytes: &[u8]) -> Option<&[u8]> {
+    if !bytes.starts_with(&[0xff, 0xd8]) {
+        return None;
+    }
+    let mut offset = 2;
+    while offset + 4 <= bytes.len() && bytes[offset] == 0xff {
+        let marker = bytes[offset + 1];
+        let length = u16::from_be_bytes([bytes[offset + 2], bytes[offset + 3]]) as usize;
+        let segment = bytes.get(offset + 4..offset + 2 + length)?;
+        if marker == 0xda {
+            return None;
+        }
+        if marker == 0xed && segment.starts_with(b"Photoshop 3.0\0") {
+            let mut resources = &segment[14..];
+            while resources.len() >= 12 && resources.starts_with(b"8BIM") {
+                let id = u16::from_be_bytes([resources[4], resources[5]]);
+                let name = (1 + resources[6] as usize + 1) & !1;
+                let header = 6 + name;
+                let size = u32::from_be_bytes([
+                    *resources.get(header)?,
+                    *resources.get(header + 1)?,
+                    *resources.get(header + 2)?,
+                    *resources.get(header + 3)?,
+                ]) as usize;
+                let data = resources.get(header + 4..header + 4 + size)?;
+                if id == 0x0404 {
+                    return Some(data);
+                }
+                resources = resources.get(header + 4 + ((size + 1) & !1)..)?;
+            }
+        }
+        offset += 2 + length;
+    }
+    None
+}
+
+fn read_iptc(bytes: &[u8], metadata: &mut Metadata) {
+    let mut data = match iptc_block(bytes) {
+        Some(data) => data,
+        None => return,
+    };
+    let mut values: Vec<(u8, String)> = Vec::new();
+    while data.len() >= 5 && data[0] == 0x1c {
+        let record = data[1];
+        let dataset = data[2];
+        let size = u16::from_be_bytes([data[3], data[4]]) as usize;
+        if size & 0x8000 != 0 || data.len() < 5 + size {
+            break;
+        }
+        if record == 2 {
+            values.push((
+                dataset,
+                String::from_utf8_lossy(&data[5..5 + size]).into_owned(),
+            ));
+        }
+        data = &data[5 + size..];
+    }
+    metadata.iptc = IPTC_DATASETS
+        .iter()
+        .filter_map(|(dataset, name)| {
+            let matching: Vec<&str> = values
+                .iter()
+                .filter(|(candidate, _)| candidate == dataset)
+                .map(|(_, value)| value.as_str())
+                .collect();
+            if matching.is_empty() {
+                None
+            } else {
+                Some((name.to_string(), matching.join(", ")))
+            }
+        })
+        .collect();
+}
+
+struct Loaded {
+    name: String,
+    dimensions: [u32; 2],
+    bytes: u64,
+    original: image::RgbaImage,
+    base: image::RgbaImage,
+    preview: image::RgbaImage,
+    previewed: Vec<crate::image_edit::Operation>,
+    analysis_base: image::RgbaImage,
+    analysis: Option<(Vec<crate::image_edit::Operation>, image::RgbaImage)>,
+    metadata: Metadata,
+}
+
+fn load(path: &std::path::Path, progress: &crate::jobs::Progress) -> Result<Loaded, String> {
+    progress.set(0.0, "reading");
+    let bytes = std::fs::read(path).map_err(|err| err.to_string())?;
+    progress.check()?;
+    progress.set(0.3, "decoding");
+    let image = image::load_from_memory(&bytes).map_err(|err| err.to_string())?;
+    progress.check()?;
+    progress.set(0.7, "building previews");
+    let mut metadata = Metadata::default();
+    read_exif(&bytes, &mut metadata);
+    read_iptc(&bytes, &mut metadata);
+    let base = image.thumbnail(PREVIEW_SIZE, PREVIEW_SIZE).to_rgba();
+    Ok(Loaded {
+        name: path
+            .file_name()
+            .map_or(String::new(), |name| name.to_string_lossy().into_owned()),
+        dimensions: [
+            image::GenericImageView::width(&image),
+            image::GenericImageView::height(&image),
+        ],
+        bytes: bytes.len() as u64,
+        original: image.to_rgba(),
+        preview: base.clone(),
+        base,
+        previewed: Vec::new(),
+        analysis_base: image.thumbnail(ANALYSIS_SIZE, ANALYSIS_SIZE).to_rgba(),
+        analysis: None,
+        metadata,
+    })
+}
+
+fn refresh_analysis(
+    loaded: &mut Loaded,
+    operations: Vec<crate::image_edit::Operation>,
+    revision: &mut u64,
+) {
+    if loaded.analysis.as_ref().map(|(applied, _)| applied) != Some(&operations) {
+        let image = crate::image_edit::apply_all(&loaded.analysis_base, &operations);
+        loaded.analysis = Some((operations, image));
+        *revision += 1;
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Channel {
+    All,
+    Red,
+    Green,
+    Blue,
+    Alpha,
+}
+
+impl Channel {
+    fn mask(self) -> [u8; 4] {
+        match self {
+            Channel::All => [255, 255, 255, 255],
+            Channel::Red => [255, 0, 0, 0],
+            Channel::Green => [0, 255, 0, 0],
+            Channel::Blue => [0, 0, 255, 0],
+            Channel::Alpha => [0, 0, 0, 255],
+        }
+    }
+}
+
+fn fit(dimensions: [u32; 2]) -> egui::Vec2 {
+    let [width, height] = [dimensions[0].max(1) as f32, dimensions[1].max(1) as f32];
+    let scale = (PREVIEW_AREA[0] / width).min(PREVIEW_AREA[1] / height);
+    egui::vec2(width * scale, height * scale)
+}
+
+fn save(image: &image::RgbaImage, path: &std::path::Path) -> Result<(), String> {
+    let jpeg = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| ["jpg", "jpeg"].contains(&extension.to_lowercase().as_str()));
+    let image = image::DynamicImage::ImageRgba8(image.clone());
+    if jpeg {
+        image::DynamicImage::ImageRgb8(image.to_rgb()).save(path)
+    } else {
+        image.save(path)
+    }
+    .map_err(|err| err.to_string())
+}
+
+fn show_edits(ui: &mut egui::Ui, edits: &mut crate::image_edit::Edits, dimensions: [u32; 2]) {
+    ui.horizontal(|ui| {
+        if ui.button("Rotate left").clicked {
+            edits.push(crate::image_edit::Operation::RotateLeft);
+        }
+        if ui.button("Rotate right").clicked {
+            edits.push(crate::image_edit::Operation::RotateRight);
+        }
+        if ui.button("180°").clicked {
+            edits.push(crate::image_edit::Operation::Rotate180);
+        }
+        if ui.button("Flip ↔").clicked {
+            edits.push(crate::image_edit::Operation::FlipHorizontal);
+        }
+        if ui.button("Flip ↕").clicked {
+            edits.push(crate::image_edit::Operation::FlipVertical);
+        }
+    });
+    let draft = &mut edits.draft;
+    ui.collapsing("Crop", |ui| {
+        for (edge, name) in draft
+            .crop
+            .iter_mut()
+            .zip(["Left", "Top", "Right", "Bottom"].iter())
+        {
+            ui.add(egui::Slider::f32(edge, 0.0..=0.45).text(*name));
+        }
+    });
+    ui.collapsing("Brightness and contrast", |ui| {
+        ui.add(egui::Slider::f32(&mut draft.brightness, -100.0..=100.0).text("Brightness"));
+        ui.add(egui::Slider::f32(&mut draft.contrast, -100.0..=100.0).text("Contrast"));
+    });
+    ui.collapsing("Resize", |ui| {
+        ui.add(egui::Slider::f32(&mut draft.resize, 0.1..=2.0).text("Scale"));
+    });
+    let pending = !edits.draft.operations().is_empty();
+    let [width, height] = crate::image_edit::output_size(dimensions, &edits.preview());
+    ui.label(format!("Result: {} x {}", width, height));
+    ui.horizontal(|ui| {
+        if pending && ui.button("Apply").clicked {
+            edits.commit_draft();
+        }
+        if pending && ui.button("Discard").clicked {
+            edits.draft = crate::image_edit::Draft::default();
+        }
+        if edits.can_undo() && ui.button("Undo").clicked {
+            edits.undo();
+        }
+        if edits.can_redo() && ui.button("Redo").clicked {
+            edits.redo();
+        }
+    });
+    if !edits.operations.is_empty() {
+        ui.collapsing(format!("History ({})", edits.operations.len()), |ui| {
+            for operation in edits.operations.iter() {
+                ui.label(operation.name());
+            }
+        });
+    }
+}
+
+fn table(ui: &mut egui::Ui, rows: &[(String, String)]) {
+    for (name, value) in rows.iter() {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::Label::new(name.as_str()).text_color(egui::Srgba::new(150, 150, 160, 255)),
+            );
+            ui.label(value.as_str());
+        });
+    }
+}
+
+fn show_metadata(ui: &mut egui::Ui, metadata: &Metadata) {
+    if let Some(err) = &metadata.error {
+        ui.label(format!("Cannot read EXIF: {}", err));
+    }
+    if metadata.all.is_empty() && metadata.iptc.is_empty() && metadata.error.is_none() {
+        ui.label("No EXIF or IPTC metadata");
+        return;
+    }
+    if !metadata.camera.is_empty() {
+        ui.collapsing("Camera", |ui| table(ui, &metadata.camera));
+    }
+    if !metadata.exposure.is_empty() {
+        ui.collapsing("Exposure", |ui| table(ui, &metadata.exposure));
+    }
+    if let Some((latitude, longitude, altitude)) = metadata.gps {
+        ui.collapsing("Location", |ui| {
+            ui.label(format!("{:.6}, {:.6}", latitude, longitude));
+            if let Some(altitude) = altitude {
+                ui.label(format!("{:.1} m above sea level", altitude));
+            }
+            ui.add(
+                egui::Hyperlink::new(format!(
+                    "https://www.openstreetmap.org/?mlat={0:.6}&mlon={1:.6}#map=15/{0:.6}/{1:.6}",
+                    latitude, longitude
+                ))
+                .text("Open in maps"),
+            );
+        });
+    }
+    if !metadata.iptc.is_empty() {
+        ui.collapsing("IPTC", |ui| table(ui, &metadata.iptc));
+    }
+    if !metadata.all.is_empty() {
+        ui.collapsing(format!("All EXIF fields ({})", metadata.all.len()), |ui| {
+            egui::ScrollArea::from_max_height(200.0).show(ui, |ui| table(ui, &metadata.all));
+        });
+    }
+}
+
+struct CompareGpu {
+    wipe: crate::wipe::Wipe,
+    revision: u64,
+    textures: Vec<(wgpu::Texture, wgpu::TextureView)>,
+    bind_group: Option<wgpu::BindGroup>,
+}
+
+pub struct ImageViewer {
+    pub open: bool,
+    pub save_as_requested: bool,
+    path: String,
+    image: Option<Result<Loaded, String>>,
+    sidebar: bool,
+    compare: bool,
+    comparison: crate::wipe::Comparison,
+    compare_gpu: Option<CompareGpu>,
+    edits: crate::image_edit::Edits,
+    revision: u64,
+    saving: Option<std::sync::mpsc::Receiver<Result<std::path::PathBuf, String>>>,
+    loading: Option<crate::jobs::Handle<Loaded>>,
+    status: String,
+    zoom: Option<f32>,
+    pan: egui::Vec2,
+    channel: Channel,
+    loads: u64,
+    texture: Option<(u64, Vec<crate::image_edit::Operation>, usize)>,
+    draft: Option<(u64, Vec<crate::image_edit::Operation>, usize)>,
+    visible: bool,
+}
+
+impl Default for ImageViewer {
+    fn default() -> Self {
+        Self {
+            open: false,
+            save_as_requested: false,
+            path: String::new(),
+            image: None,
+            sidebar: true,
+            compare: false,
+            comparison: crate::wipe::Comparison::default(),
+            compare_gpu: None,
+            edits: crate::image_edit::Edits::default(),
+            revision: 0,
+            saving: None,
+            loading: None,
+            status: String::new(),
+            zoom: None,
+            pan: egui::Vec2::default(),
+            channel: Channel::All,
+            loads: 0,
+            texture: None,
+            draft: None,
+            visible: false,
+        }
+    }
+}
+
+impl ImageViewer {
+    pub fn open_path(&mut self, path: &std::path::Path) {
+        self.path = path.display().to_string();
+        let name = path
+            .file_name()
+            .map_or(String::new(), |name| name.to_string_lossy().into_owned());
+        let path = path.to_path_buf();
+        self.loading = Some(crate::jobs::spawn(
+            format!("Load {}", name),
+            move |progress| load(&path, progress),
+        ));
+        self.image = None;
+        self.edits = crate::image_edit::Edits::default();
+        self.revision += 1;
+        self.loads += 1;
+        self.zoom = None;
+        self.pan = egui::Vec2::default();
+        self.status = format!("Loading {}", name);
+        self.open = true;
+    }
+
+    pub fn close(&mut self) {
+        self.loading = None;
+        self.image = None;
+        self.edits = crate::image_edit::Edits::default();
+        self.revision += 1;
+        self.loads += 1;
+        self.status.clear();
+        self.open = false;
+    }
+
+    pub fn title(&self) -> Option<String> {
+        self.image.as_ref()?;
+        let path = std::path::Path::new(&self.path);
+        Some(path.file_name().map_or_else(
+            || self.path.clone(),
+            |name| name.to_string_lossy().into_owned(),
+        ))
+    }
+
+    fn preview_operations(&self) -> Vec<crate::image_edit::Operation> {
+        self.edits
+            .preview()
+            .into_iter()
+            .filter(|operation| !matches!(operation, crate::image_edit::Operation::Resize(_)))
+            .collect()
+    }
+
+    pub fn has_image(&self) -> bool {
+        matches!(self.image, Some(Ok(_)))
+    }
+
+    pub fn analysis(&mut self) -> Option<(u64, &image::RgbaImage)> {
+        let operations = self.preview_operations();
+        let loaded = match &mut self.image {
+            Some(Ok(loaded)) => loaded,
+            _ => return None,
+        };
+        refresh_analysis(loaded, operations, &mut self.revision);
+        Some((self.revision, &loaded.analysis.as_ref().unwrap().1))
+    }
+
+    pub fn recreate_gpu_resources(&mut self) {
+        self.compare_gpu = None;
+        self.texture = None;
+        self.draft = None;
+    }
+
+    pub fn upload(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        ui_pass: &mut crate::ui_pass::RenderPass,
+    ) {
+        let shown = match &self.image {
+            Some(Ok(loaded)) if self.visible => Some(loaded),
+            _ => None,
+        };
+        let stale = match &self.texture {
+            Some((loads, operations, _)) => {
+                shown.is_none() || *loads != self.loads || *operations != self.edits.operations
+            }
+            None => false,
+        };
+        if stale {
+            if let Some((_, _, id)) = self.texture.take() {
+                ui_pass.free_user_texture(id);
+            }
+        }
+        let loaded = match shown {
+            Some(loaded) => loaded,
+            None => {
+                if let Some((_, _, id)) = self.draft.take() {
+                    ui_pass.free_user_texture(id);
+                }
+                return;
+            }
+        };
+        if self.texture.is_none() {
+            let image = crate::image_edit::apply_all(&loaded.original, &self.edits.operations);
+            let id = ui_pass.register_user_texture(device, queue, &image);
+            self.texture = Some((self.loads, self.edits.operations.clone(), id));
+        }
+        let drafting = !self.edits.draft.operations().is_empty();
+        let stale = match &self.draft {
+            Some((loads, operations, _)) => {
+                !drafting || *loads != self.loads || *operations != loaded.previewed
+            }
+            None => false,
+        };
+        if stale {
+            if let Some((_, _, id)) = self.draft.take() {
+                ui_pass.free_user_texture(id);
+            }
+        }
+        if drafting && self.draft.is_none() {
+            let id = ui_pass.register_user_texture(device, queue, &loaded.preview);
+            self.draft = Some((self.loads, loaded.previewed.clone(), id));
+        }
+    }
+
+    pub fn prepare(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        format: wgpu::TextureFormat,
+    ) {
+        if !self.comparison.visible() {
+            return;
+        }
+        let operations = self.preview_operations();
+        let loaded = match &mut self.image {
+            Some(Ok(loaded)) => loaded,
+            _ => return,
+        };
+        refresh_analysis(loaded, operations, &mut self.revision);
+        let gpu = self.compare_gpu.get_or_insert_with(|| CompareGpu {
+            wipe: crate::wipe::Wipe::new(device, format),
+            revision: 0,
+            textures: Vec::new(),
+            bind_group: None,
+        });
+        if gpu.revision == self.revision && gpu.bind_group.is_some() {
+            return;
+        }
+        let original =
+            crate::wipe::image_texture(device, queue, "Image Original", &loaded.analysis_base);
+        let edited = crate::wipe::image_texture(
+            device,
+            queue,
+            "Image Edited",
+            &loaded.analysis.as_ref().unwrap().1,
+        );
+        gpu.bind_group = Some(gpu.wipe.bind(device, &original.1, &edited.1));
+        gpu.textures = vec![original, edited];
+        gpu.revision = self.revision;
+    }
+
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        ui_pass: &mut crate::ui_pass::RenderPass,
+        size: winit::dpi::PhysicalSize<u32>,
+        scale_factor: f64,
+    ) {
+        if !self.comparison.prepare(size, scale_factor) {
+            return;
+        }
+        if let Some(CompareGpu {
+            wipe,
+            bind_group: Some(bind_group),
+            ..
+        }) = &mut self.compare_gpu
+        {
+            wipe.draw(
+                device,
+                uploader,
+                encoder,
+                ui_pass,
+                size,
+                bind_group,
+                &self.comparison,
+            );
+        }
+    }
+
+    pub fn key_input(
+        &mut self,
+        input: &winit::event::KeyboardInput,
+        modifiers: winit::event::ModifiersState,
+    ) -> bool {
+        if !self.open
+            || input.state != winit::event::ElementState::Pressed
+            || !crate::clipboard::command(modifiers)
+        {
+            return false;
+        }
+        match input.virtual_keycode {
+            Some(winit::event::VirtualKeyCode::Z)
+                if !modifiers.shift() && self.edits.can_undo() =>
+            {
+                self.edits.undo();
+                true
+            }
+            Some(winit::event::VirtualKeyCode::Z) | Some(winit::event::VirtualKeyCode::Y)
+                if self.edits.can_redo() =>
+            {
+                self.edits.redo();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn save_as(&mut self, path: &std::path::Path) {
+        let loaded = match &self.image {
+            Some(Ok(loaded)) => loaded,
+            _ => return,
+        };
+        let original = loaded.original.clone();
+        let operations = self.edits.operations.clone();
+        let path = path.to_path_buf();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::Builder::new()
+            .name("image save".to_owned())
+            .spawn(move || {
+                let image = crate::image_edit::apply_all(&original, &operations);
+                sender.send(save(&image, &path).map(|_| path)).ok();
+            })
+            .unwrap();
+        self.saving = Some(receiver);
+        self.status = "Saving...".to_owned();
+    }
+
+    fn poll(&mut self) {
+        if let Some(result) = self.loading.as_ref().and_then(crate::jobs::Handle::poll) {
+            self.loading = None;
+            if let Err(err) = &result {
+                log::warn!("cannot open {}: {}", self.path, err);
+            }
+            self.image = Some(result);
+            self.revision += 1;
+            self.loads += 1;
+            self.status.clear();
+        }
+        let result = match self.saving.as_ref().map(|saving| saving.try_recv()) {
+            Some(Ok(result)) => result,
+            Some(Err(std::sync::mpsc::TryRecvError::Empty)) | None => return,
+            Some(Err(std::sync::mpsc::TryRecvError::Disconnected)) => {
+                Err("the save thread stopped".to_owned())
+            }
+        };
+        self.saving = None;
+        self.status = match result {
+            Ok(path) => {
+                log::info!("saved {}", path.display());
+                let message = format!("Saved {}", path.display());
+                crate::notify::success(message.clone());
+                message
+            }
+            Err(err) => {
+                log::warn!("cannot save the image: {}", err);
+                let message = format!("Cannot save: {}", err);
+                crate::notify::error(message.clone());
+                message
+            }
+        };
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        self.poll();
+        let operations = self.preview_operations();
+        if let Some(Ok(loaded)) = &mut self.image {
+            if operations != loaded.previewed {
+                loaded.preview = crate::image_edit::apply_all(&loaded.base, &operations);
+                loaded.previewed = operations;
+            }
+        }
+        let mut open = self.open;
+        let mut open_requested = false;
+        let mut save_as_requested = false;
+        let mut visible = false;
+        let texture = match &self.texture {
+            Some((loads, operations, id))
+                if *loads == self.loads && *operations == self.edits.operations =>
+            {
+                Some(*id)
+            }
+            _ => None,
+        };
+        let draft = match (&self.draft, &self.image) {
+            (Some((loads, operations, id)), Some(Ok(loaded)))
+                if *loads == self.loads && *operations == loaded.previewed =>
+            {
+                Some(*id)
+            }
+            _ => None,
+        };
+        let pixels_per_point = ctx.pixels_per_point();
+        let zoom = &mut self.zoom;
+        let pan = &mut self.pan;
+        let channel = &mut self.channel;
+        let path = &mut self.path;
+        let image = &self.image;
+        let sidebar = &mut self.sidebar;
+        let compare = &mut self.compare;
+        let comparison = &mut self.comparison;
+        let canvas = self.compare_gpu.as_ref().map(|gpu| gpu.wipe.canvas());
+        let edits = &mut self.edits;
+        let saving = self.saving.is_some();
+        let status = &self.status;
+        crate::dock::Panel::new("Image Viewer")
+            .open(&mut open)
+            .default_pos(egui::pos2(340.0, 100.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::new(path));
+                    if ui.button("Open").clicked {
+                        open_requested = true;
+                    }
+                    ui.checkbox(sidebar, "Metadata");
+                    ui.checkbox(compare, "Compare");
+                });
+                let loaded = match image {
+                    Some(Ok(loaded)) => loaded,
+                    Some(Err(err)) => {
+                        ui.label(format!("Cannot open the image: {}", err));
+                        return;
+                    }
+                    None => {
+                        ui.label("Open an image, or click one in the Disk Usage grid");
+                        return;
+                    }
+                };
+                ui.label(format!(
+                    "{}  {} x {}  {}",
+                    loaded.name,
+                    loaded.dimensions[0],
+                    loaded.dimensions[1],
+                    crate::duplicates::format_size(loaded.bytes)
+                ));
+                let compare = *compare;
+                let drafting = !edits.draft.operations().is_empty();
+                let [width, height] =
+                    crate::image_edit::output_size(loaded.dimensions, &edits.operations);
+                let size = egui::vec2(width.max(1) as f32, height.max(1) as f32);
+                let fit_scale = (PREVIEW_AREA[0] / size.x).min(PREVIEW_AREA[1] / size.y);
+                let scale = zoom.map_or(fit_scale, |zoom| zoom / pixels_per_point);
+                if !compare && !drafting {
+                    ui.horizontal(|ui| {
+                        if ui.button("Fit").clicked {
+                            *zoom = None;
+                            *pan = egui::Vec2::default();
+                        }
+                        if ui.button("1:1").clicked {
+                            *zoom = Some(1.0);
+                        }
+                        ui.label(format!("{:.0}%", scale * pixels_per_point * 100.0));
+                        ui.label("|");
+                        ui.radio_value(channel, Channel::All, "RGBA");
+                        ui.radio_value(channel, Channel::Red, "R");
+                        ui.radio_value(channel, Channel::Green, "G");
+                        ui.radio_value(channel, Channel::Blue, "B");
+                        ui.radio_value(channel, Channel::Alpha, "A");
+                    });
+                }
+                let mut preview = |ui: &mut egui::Ui| {
+                    if compare {
+                        comparison.show(
+                            ui,
+                            canvas,
+                            "image_compare",
+                            fit(loaded.dimensions),
+                            ["Original", "Edited"],
+                        );
+                        return;
+                    }
+                    let rect = ui.allocate_space(egui::vec2(PREVIEW_AREA[0], PREVIEW_AREA[1]));
+                    ui.painter()
+                        .rect_filled(rect, 0.0, egui::Srgba::new(24, 24, 28, 255));
+                    visible = true;
+                    if drafting {
+                        if let Some(draft) = draft {
+                            let size = egui::vec2(
+                                loaded.preview.width().max(1) as f32,
+                                loaded.preview.height().max(1) as f32,
+                            );
+                            let scale = (rect.width() / size.x).min(rect.height() / size.y);
+                            crate::ui_pass::paint_user_texture(
+                                ui.painter(),
+                                draft,
+                                egui::Rect::from_center_size(rect.center(), size * scale),
+                                egui::color::WHITE,
+                            );
+                        }
+                        return;
+                    }
+                    let response = ui.interact(
+                        rect,
+                        ui.make_persistent_id("image_view"),
+                        egui::Sense::drag(),
+                    );
+                    if response.active {
+                        *pan -= ui.input().mouse.delta / scale;
+                    }
+                    let scroll = ui.input().scroll_delta.y;
+                    let scale = if response.hovered && scroll != 0.0 {
+                        let zoomed = (scale * pixels_per_point * (1.0 + scroll * 0.002))
+                            .clamp(MIN_ZOOM, MAX_ZOOM);
+                        *zoom = Some(zoomed);
+                        zoomed / pixels_per_point
+                    } else {
+                        scale
+                    };
+                    pan.x = pan.x.max(-size.x / 2.0).min(size.x / 2.0);
+                    pan.y = pan.y.max(-size.y / 2.0).min(size.y / 2.0);
+                    crate::status::hint_on_hover(&response, "Drag to pan, scroll to zoom");
+                    if let Some(texture) = texture {
+                        crate::ui_pass::paint_user_texture(
+                            &ui.painter_at(rect),
+                            texture,
+                            egui::Rect::from_center_size(
+                                rect.center() - *pan * scale,
+                                size * scale,
+                            ),
+                            crate::style::srgba(channel.mask()),
+                        );
+                    }
+                };
+                if *sidebar {
+                    ui.columns(2, |columns| {
+                        preview(&mut columns[0]);
+                        show_metadata(&mut columns[1], &loaded.metadata);
+                    });
+                } else {
+                    preview(ui);
+                }
+                ui.collapsing("Edit", |ui| show_edits(ui, edits, loaded.dimensions));
+                ui.horizontal(|ui| {
+                    if !saving && ui.button("Save as...").clicked {
+                        save_as_requested = true;
+                    }
+                    if !status.is_empty() {
+                        ui.label(status.as_str());
+                    }
+                });
+            });
+        self.open = open;
+        self.visible = visible && open;
+        self.save_as_requested |= save_as_requested;
+        if open_requested {
+            let path = std::path::PathBuf::from(&self.path);
+            self.open_path(&path);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+const FIELD_OFFSET: f32 = 18.0;
+
+#[derive(Default)]
+pub struct Ime {
+    field: Option<egui::Pos2>,
+    position: Option<egui::Pos2>,
+    applied: Option<egui::Pos2>,
+}
+
+impl Ime {
+    pub fn update(&mut self, ctx: &std::sync::Arc<egui::Context>, caret: Option<egui::Pos2>) {
+        let (pressed, pointer) = {
+            let input = ctx.input();
+            (input.mouse.pressed, input.mouse.pos)
+        };
+        if pressed {
+            self.field = pointer.map(|pos| pos + egui::vec2(0.0, FIELD_OFFSET));
+        }
+        self.position = match caret {
+            Some(caret) => Some(caret),
+            None if ctx.wants_keyboard_input() => self.field,
+            None => None,
+        };
+    }
+
+    pub fn take_position(&mut self) -> Option<winit::dpi::LogicalPosition<f32>> {
+        let position = self
+            .position
+            .filter(|position| self.applied != Some(*position))?;
+        self.applied = Some(position);
+        Some(winit::dpi::LogicalPosition::new(position.x, position.y))
+    }
+}
@@ -0,0 +1,166 @@
+const COLORS: [(&str, [f32; 3]); 5] = [
+    ("White", [0.9, 0.9, 0.9]),
+    ("Red", [0.8, 0.2, 0.2]),
+    ("Green", [0.2, 0.7, 0.3]),
+    ("Blue", [0.2, 0.4, 0.8]),
+    ("Gold", [0.9, 0.7, 0.2]),
+];
+
+#[derive(Clone)]
+enum ObjectAction {
+    Select(usize),
+    Focus(usize),
+    Color(usize, [f32; 3]),
+    Duplicate(usize),
+    Delete(usize),
+}
+
+impl ObjectAction {
+    fn index(&self) -> usize {
+        match self {
+            ObjectAction::Select(index)
+            | ObjectAction::Focus(index)
+            | ObjectAction::Color(index, _)
+            | ObjectAction::Duplicate(index)
+            | ObjectAction::Delete(index) => *index,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Inspector {
+    pub open: bool,
+    menu: crate::menu::ContextMenu<ObjectAction>,
+}
+
+impl Inspector {
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, scene: &mut crate::scene::Scene) {
+        let mut context_menu = None;
+        crate::dock::Panel::new("Inspector")
+            .open(&mut self.open)
+            .default_pos(egui::pos2(560.0, 360.0))
+            .show(ctx, |ui| {
+                let hovered = scene
+                    .picker
+                    .hovered
+                    .and_then(|index| scene.objects.get(index))
+                    .map(|object| object.name.as_str())
+                    .unwrap_or("-");
+                ui.label(format!("Hovered: {}", hovered));
+                ui.label(format!("Pick latency: {} frames", scene.picker.latency));
+                ui.collapsing("Hierarchy", |ui| {
+                    egui::ScrollArea::from_max_height(160.0).show(ui, |ui| {
+                        for (index, object) in scene.objects.iter().enumerate() {
+                            let selected = scene.picker.selected == Some(index);
+                            let response = ui.radio(selected, object.name.as_str());
+                            if response.clicked {
+                                scene.picker.selected = Some(index);
+                            }
+                            let pointer = ui.input().mouse.pos;
+                            if let (true, true, Some(pos)) =
+                                (crate::menu::secondary_clicked(), response.hovered, pointer)
+                            {
+                                context_menu = Some((pos, object_items(index)));
+                            }
+                        }
+                    });
+                    ui.label("Right-click an object for more actions");
+                });
+                ui.separator();
+
+                let index = match scene.picker.selected {
+                    Some(index) if index < scene.objects.len() => index,
+                    _ => {
+                        ui.label("Click an object to select it");
+                        return;
+                    }
+                };
+                let object = &mut scene.objects[index];
+                let mesh = &scene.meshes[object.mesh];
+                ui.label(format!("Name: {}", object.name));
+                ui.label(format!(
+                    "Mesh: {} ({} triangles)",
+                    mesh.name,
+                    mesh.indices.len() / 3
+                ));
+
+                let mut columns = object.transform.to_cols_array_2d();
+                ui.add(egui::Slider::f32(&mut columns[3][0], -15.0..=15.0).text("x"));
+                ui.add(egui::Slider::f32(&mut columns[3][1], -15.0..=15.0).text("y"));
+                ui.add(egui::Slider::f32(&mut columns[3][2], -15.0..=15.0).text("z"));
+                object.transform = glam::Mat4::from_cols_array_2d(&columns);
+
+                ui.add(egui::Slider::f32(&mut object.color[0], 0.0..=1.0).text("red"));
+                ui.add(egui::Slider::f32(&mut object.color[1], 0.0..=1.0).text("green"));
+                ui.add(egui::Slider::f32(&mut object.color[2], 0.0..=1.0).text("blue"));
+                ui.add(egui::Slider::f32(&mut object.color[3], 0.0..=1.0).text("alpha"));
+                ui.add(egui::Slider::f32(&mut object.metallic, 0.0..=1.0).text("metallic"));
+                ui.add(egui::Slider::f32(&mut object.roughness, 0.0..=1.0).text("roughness"));
+                if ui.button("Deselect").clicked {
+                    scene.picker.selected = None;
+                }
+            });
+        if let Some((pos, items)) = context_menu {
+            self.menu.open(pos, items);
+        }
+        if !self.open {
+            self.menu.close();
+        }
+        let action = match self.menu.show(ctx) {
+            Some(action) if action.index() < scene.objects.len() => action,
+            _ => return,
+        };
+        match action {
+            ObjectAction::Select(index) => scene.picker.selected = Some(index),
+            ObjectAction::Focus(index) => {
+                let columns = scene.objects[index].transform.to_cols_array_2d();
+                scene.camera.target = glam::Vec3::new(columns[3][0], columns[3][1], columns[3][2]);
+                scene.picker.selected = Some(index);
+            }
+            ObjectAction::Color(index, [red, green, blue]) => {
+                let color = &mut scene.objects[index].color;
+                *color = [red, green, blue, color[3]];
+            }
+            ObjectAction::Duplicate(index) => {
+                let original = &scene.objects[index];
+                let mut columns = original.transform.to_cols_array_2d();
+                columns[3][0] += 1.5;
+                let copy = crate::scene::Object {
+                    name: format!("{} copy", original.name),
+                    mesh: original.mesh,
+                    transform: glam::Mat4::from_cols_array_2d(&columns),
+                    color: original.color,
+                    metallic: original.metallic,
+                    roughness: original.roughness,
+                };
+                scene.objects.push(copy);
+                scene.picker.selected = Some(scene.objects.len() - 1);
+            }
+            ObjectAction::Delete(index) => {
+                let object = scene.objects.remove(index);
+                log::info!("deleted {}", object.name);
+                scene.picker.selected = None;
+                scene.picker.hovered = None;
+            }
+        }
+    }
+}
+
+fn object_items(index: usize) -> Vec<crate::menu::Item<ObjectAction>> {
+    vec![
+        crate::menu::Item::action("Select", ObjectAction::Select(index)),
+        crate::menu::Item::action("Focus camera", ObjectAction::Focus(index)).icon('🎥'),
+        crate::menu::Item::submenu(
+            "Color",
+            COLORS
+                .iter()
+                .map(|(name, color)| {
+                    crate::menu::Item::action(*name, ObjectAction::Color(index, *color))
+                })
+                .collect(),
+        ),
+        crate::menu::Item::separator(),
+        crate::menu::Item::action("Duplicate", ObjectAction::Duplicate(index)),
+        crate::menu::Item::action("Delete", ObjectAction::Delete(index)).icon('🗑'),
+    ]
+}
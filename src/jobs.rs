@@ -0,0 +1,314 @@
+const WORKERS: usize = 3;
+const HISTORY: usize = 20;
+const BAR_WIDTH: f32 = 220.0;
+const BAR_HEIGHT: f32 = 16.0;
+const BAR_BACKGROUND: egui::Srgba = egui::Srgba::new(40, 40, 46, 255);
+const BAR_FILL: egui::Srgba = egui::Srgba::new(70, 130, 200, 255);
+const ERROR_COLOR: egui::Srgba = egui::Srgba::new(230, 110, 100, 255);
+const DONE_COLOR: egui::Srgba = egui::Srgba::new(110, 200, 120, 255);
+
+type Work = Box<dyn FnOnce() + Send>;
+
+struct Shared {
+    queue: std::sync::Mutex<std::collections::VecDeque<Work>>,
+    available: std::sync::Condvar,
+}
+
+fn work(shared: &Shared) {
+    loop {
+        let job = {
+            let mut queue = shared.queue.lock().unwrap();
+            loop {
+                match queue.pop_front() {
+                    Some(job) => break job,
+                    None => queue = shared.available.wait(queue).unwrap(),
+                }
+            }
+        };
+        job();
+    }
+}
+
+enum Update {
+    Started(u64),
+    Progress(u64, f32, String),
+    Finished(u64, Result<(), String>),
+}
+
+pub struct Progress {
+    id: u64,
+    sender: std::sync::mpsc::Sender<Update>,
+    cancelled: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl Progress {
+    pub fn set(&self, fraction: f32, message: impl Into<String>) {
+        self.sender
+            .send(Update::Progress(self.id, fraction, message.into()))
+            .ok();
+    }
+
+    pub fn check(&self) -> Result<(), String> {
+        if self.cancelled.load(std::sync::atomic::Ordering::Relaxed) {
+            Err("cancelled".to_owned())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+pub struct Handle<T> {
+    receiver: std::sync::mpsc::Receiver<Result<T, String>>,
+}
+
+impl<T> Handle<T> {
+    pub fn poll(&self) -> Option<Result<T, String>> {
+        match self.receiver.try_recv() {
+            Ok(result) => Some(result),
+            Err(std::sync::mpsc::TryRecvError::Empty) => None,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                Some(Err("the job stopped".to_owned()))
+            }
+        }
+    }
+}
+
+enum Stage {
+    Queued,
+    Running,
+    Done(Result<(), String>),
+}
+
+struct Task {
+    id: u64,
+    name: String,
+    stage: Stage,
+    fraction: f32,
+    message: String,
+    cancelled: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    started: std::time::Instant,
+    elapsed: Option<std::time::Duration>,
+}
+
+struct State {
+    shared: Option<std::sync::Arc<Shared>>,
+    sender: std::sync::mpsc::Sender<Update>,
+    receiver: std::sync::mpsc::Receiver<Update>,
+    tasks: Vec<Task>,
+    next_id: u64,
+}
+
+impl State {
+    fn new() -> Self {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        Self {
+            shared: None,
+            sender,
+            receiver,
+            tasks: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    fn shared(&mut self) -> std::sync::Arc<Shared> {
+        self.shared
+            .get_or_insert_with(|| {
+                let shared = std::sync::Arc::new(Shared {
+                    queue: std::sync::Mutex::new(std::collections::VecDeque::new()),
+                    available: std::sync::Condvar::new(),
+                });
+                for worker in 0..WORKERS {
+                    let shared = shared.clone();
+                    std::thread::Builder::new()
+                        .name(format!("job worker {}", worker))
+                        .spawn(move || work(&shared))
+                        .unwrap();
+                }
+                shared
+            })
+            .clone()
+    }
+
+    fn poll(&mut self) {
+        let updates: Vec<Update> = self.receiver.try_iter().collect();
+        for update in updates {
+            let id = match &update {
+                Update::Started(id) | Update::Progress(id, ..) | Update::Finished(id, _) => *id,
+            };
+            let task = match self.tasks.iter_mut().find(|task| task.id == id) {
+                Some(task) => task,
+                None => continue,
+            };
+            match update {
+                Update::Started(_) => task.stage = Stage::Running,
+                Update::Progress(_, fraction, message) => {
+                    task.fraction = fraction.clamp(0.0, 1.0);
+                    task.message = message;
+                }
+                Update::Finished(_, result) => {
+                    if let Err(err) = &result {
+                        log::warn!("{} failed: {}", task.name, err);
+                    }
+                    task.elapsed = Some(task.started.elapsed());
+                    task.stage = Stage::Done(result);
+                }
+            }
+        }
+        let finished = self
+            .tasks
+            .iter()
+            .filter(|task| matches!(task.stage, Stage::Done(_)))
+            .count();
+        let mut excess = finished.saturating_sub(HISTORY);
+        self.tasks.retain(|task| {
+            let remove = excess > 0 && matches!(task.stage, Stage::Done(_));
+            if remove {
+                excess -= 1;
+            }
+            !remove
+        });
+    }
+}
+
+thread_local! {
+    static STATE: std::cell::RefCell<State> = std::cell::RefCell::new(State::new());
+}
+
+pub fn spawn<T: Send + 'static>(
+    name: impl Into<String>,
+    job: impl FnOnce(&Progress) -> Result<T, String> + Send + 'static,
+) -> Handle<T> {
+    let (result_sender, receiver) = std::sync::mpsc::channel();
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let id = state.next_id;
+        state.next_id += 1;
+        let cancelled = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let progress = Progress {
+            id,
+            sender: state.sender.clone(),
+            cancelled: cancelled.clone(),
+        };
+        state.tasks.push(Task {
+            id,
+            name: name.into(),
+            stage: Stage::Queued,
+            fraction: 0.0,
+            message: String::new(),
+            cancelled,
+            started: std::time::Instant::now(),
+            elapsed: None,
+        });
+        let shared = state.shared();
+        shared.queue.lock().unwrap().push_back(Box::new(move || {
+            progress.sender.send(Update::Started(id)).ok();
+            let result = progress.check().and_then(|()| job(&progress));
+            let summary = result.as_ref().map(|_| ()).map_err(Clone::clone);
+            progress.sender.send(Update::Finished(id, summary)).ok();
+            result_sender.send(result).ok();
+        }));
+        shared.available.notify_one();
+    });
+    Handle { receiver }
+}
+
+pub fn running() -> usize {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.poll();
+        state
+            .tasks
+            .iter()
+            .filter(|task| !matches!(task.stage, Stage::Done(_)))
+            .count()
+    })
+}
+
+fn progress_bar(ui: &mut egui::Ui, fraction: f32, text: String) {
+    let rect = ui.allocate_space(egui::vec2(BAR_WIDTH, BAR_HEIGHT));
+    let painter = ui.painter();
+    painter.rect_filled(rect, 3.0, BAR_BACKGROUND);
+    painter.rect_filled(
+        egui::Rect::from_min_size(rect.min, egui::vec2(rect.width() * fraction, rect.height())),
+        3.0,
+        BAR_FILL,
+    );
+    painter.text(
+        rect.center(),
+        (egui::Align::Center, egui::Align::Center),
+        text,
+        egui::TextStyle::Small,
+        egui::Srgba::new(255, 255, 255, 255),
+    );
+}
+
+#[derive(Default)]
+pub struct TasksPanel {
+    pub open: bool,
+}
+
+impl TasksPanel {
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            state.poll();
+            let mut clear = false;
+            crate::dock::Panel::new("Tasks")
+                .open(&mut self.open)
+                .show(ctx, |ui| {
+                    if state.tasks.is_empty() {
+                        ui.label("No background tasks yet");
+                    }
+                    for task in state.tasks.iter().rev() {
+                        ui.horizontal(|ui| {
+                            ui.label(task.name.as_str());
+                            match &task.stage {
+                                Stage::Queued => {
+                                    progress_bar(ui, 0.0, "Queued".to_owned());
+                                }
+                                Stage::Running => {
+                                    progress_bar(
+                                        ui,
+                                        task.fraction,
+                                        format!("{:.0}% {}", task.fraction * 100.0, task.message),
+                                    );
+                                }
+                                Stage::Done(Ok(())) => {
+                                    ui.add(
+                                        egui::Label::new(format!(
+                                            "Done in {:.1} s",
+                                            task.elapsed.unwrap_or_default().as_secs_f32()
+                                        ))
+                                        .text_color(DONE_COLOR),
+                                    );
+                                }
+                                Stage::Done(Err(err)) => {
+                                    ui.add(
+                                        egui::Label::new(format!("Failed: {}", err))
+                                            .text_color(ERROR_COLOR),
+                                    );
+                                }
+                            }
+                            let cancelled =
+                                task.cancelled.load(std::sync::atomic::Ordering::Relaxed);
+                            if !matches!(task.stage, Stage::Done(_))
+                                && !cancelled
+                                && ui.button("Cancel").clicked
+                            {
+                                task.cancelled
+                                    .store(true, std::sync::atomic::Ordering::Relaxed);
+                            }
+                        });
+                    }
+                    if ui.button("Clear finished").clicked {
+                        clear = true;
+                    }
+                });
+            if clear {
+                state
+                    .tasks
+                    .retain(|task| !matches!(task.stage, Stage::Done(_)));
+            }
+        });
+    }
+}
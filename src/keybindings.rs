@@ -0,0 +1,394 @@
+#[derive(
+    Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, serde::Serialize, serde::Deserialize,
+)]
+pub enum Action {
+    Quit,
+    OpenImage,
+    SaveSettings,
+    Fullscreen,
+    Screenshot,
+    Console,
+    Settings,
+    Profiler,
+    CodeEditor,
+    Log,
+    About,
+}
+
+impl Action {
+    pub const ALL: [Action; 11] = [
+        Action::Quit,
+        Action::OpenImage,
+        Action::SaveSettings,
+        Action::Fullscreen,
+        Action::Screenshot,
+        Action::Console,
+        Action::Settings,
+        Action::Profiler,
+        Action::CodeEditor,
+        Action::Log,
+        Action::About,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::OpenImage => "Open an image",
+            Action::SaveSettings => "Save settings",
+            Action::Fullscreen => "Toggle fullscreen",
+            Action::Screenshot => "Save a screenshot",
+            Action::Console => "Toggle console",
+            Action::Settings => "Toggle settings",
+            Action::Profiler => "Toggle profiler",
+            Action::CodeEditor => "Toggle code editor",
+            Action::Log => "Toggle log",
+            Action::About => "About",
+        }
+    }
+
+    fn default_chord(self) -> Option<Chord> {
+        let key = |key| Some(Chord::new(key, winit::event::ModifiersState::empty()));
+        let command = |key| {
+            let modifiers = if cfg!(target_os = "macos") {
+                winit::event::ModifiersState::LOGO
+            } else {
+                winit::event::ModifiersState::CTRL
+            };
+            Some(Chord::new(key, modifiers))
+        };
+        match self {
+            Action::Quit => key(winit::event::VirtualKeyCode::Escape),
+            Action::OpenImage => command(winit::event::VirtualKeyCode::O),
+            Action::SaveSettings => command(winit::event::VirtualKeyCode::S),
+            Action::Fullscreen => key(winit::event::VirtualKeyCode::F11),
+            Action::Screenshot => key(winit::event::VirtualKeyCode::F12),
+            Action::Console => None,
+            Action::Settings => command(winit::event::VirtualKeyCode::Comma),
+            Action::Profiler => key(winit::event::VirtualKeyCode::F3),
+            Action::CodeEditor => command(winit::event::VirtualKeyCode::E),
+            Action::Log => command(winit::event::VirtualKeyCode::L),
+            Action::About => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Chord {
+    pub key: winit::event::VirtualKeyCode,
+    pub modifiers: winit::event::ModifiersState,
+}
+
+impl Chord {
+    fn new(key: winit::event::VirtualKeyCode, modifiers: winit::event::ModifiersState) -> Self {
+        Self { key, modifiers }
+    }
+
+    fn from_input(
+        input: &winit::event::KeyboardInput,
+        modifiers: winit::event::ModifiersState,
+    ) -> Option<Self> {
+        if input.state != winit::event::ElementState::Pressed {
+            return None;
+        }
+        match input.virtual_keycode? {
+            winit::event::VirtualKeyCode::LShift
+            | winit::event::VirtualKeyCode::RShift
+            | winit::event::VirtualKeyCode::LControl
+            | winit::event::VirtualKeyCode::RControl
+            | winit::event::VirtualKeyCode::LAlt
+            | winit::event::VirtualKeyCode::RAlt
+            | winit::event::VirtualKeyCode::LWin
+            | winit::event::VirtualKeyCode::RWin => None,
+            key => Some(Self::new(key, modifiers)),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        let mut parts = Vec::new();
+        if self.modifiers.ctrl() {
+            parts.push("Ctrl".to_owned());
+        }
+        if self.modifiers.alt() {
+            parts.push("Alt".to_owned());
+        }
+        if self.modifiers.shift() {
+            parts.push("Shift".to_owned());
+        }
+        if self.modifiers.logo() {
+            parts.push(
+                if cfg!(target_os = "macos") {
+                    "Cmd"
+                } else {
+                    "Super"
+                }
+                .to_owned(),
+            );
+        }
+        parts.push(format!("{:?}", self.key));
+        parts.join("+")
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Keybindings {
+    bindings: std::collections::BTreeMap<Action, Option<Chord>>,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        Self {
+            bindings: Action::ALL
+                .iter()
+                .map(|action| (*action, action.default_chord()))
+                .collect(),
+        }
+    }
+}
+
+impl Keybindings {
+    fn path() -> Option<std::path::PathBuf> {
+        dirs::config_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")).join("keybindings.json"))
+    }
+
+    pub fn load() -> Self {
+        let path = match Self::path() {
+            Some(path) => path,
+            None => return Self::default(),
+        };
+        let mut keybindings: Self = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|err| {
+                log::warn!(
+                    "ignoring invalid keybindings in {}: {}",
+                    path.display(),
+                    err
+                );
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        };
+        for action in Action::ALL.iter() {
+            keybindings
+                .bindings
+                .entry(*action)
+                .or_insert_with(|| action.default_chord());
+        }
+        keybindings
+    }
+
+    pub fn save(&self) {
+        let path = match Self::path() {
+            Some(path) => path,
+            None => return,
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&path, serde_json::to_string_pretty(self).unwrap()));
+        match result {
+            Ok(()) => log::info!("saved keybindings to {}", path.display()),
+            Err(err) => log::warn!("cannot save keybindings to {}: {}", path.display(), err),
+        }
+    }
+
+    pub fn chord(&self, action: Action) -> Option<Chord> {
+        self.bindings.get(&action).copied().flatten()
+    }
+
+    pub fn action(
+        &self,
+        input: &winit::event::KeyboardInput,
+        modifiers: winit::event::ModifiersState,
+    ) -> Option<Action> {
+        let chord = Chord::from_input(input, modifiers)?;
+        self.bindings
+            .iter()
+            .find(|(_, bound)| **bound == Some(chord))
+            .map(|(action, _)| *action)
+    }
+
+    fn bind(&mut self, action: Action, chord: Option<Chord>) -> Option<Action> {
+        let previous = self
+            .bindings
+            .iter_mut()
+            .find(|(other, bound)| **other != action && chord.is_some() && **bound == chord)
+            .map(|(other, bound)| {
+                *bound = None;
+                *other
+            });
+        self.bindings.insert(action, chord);
+        previous
+    }
+}
+
+#[derive(Default)]
+pub struct ShortcutsPanel {
+    pub open: bool,
+    capturing: Option<Action>,
+    status: String,
+}
+
+impl ShortcutsPanel {
+    pub fn capturing(&self) -> bool {
+        self.open && self.capturing.is_some()
+    }
+
+    pub fn capture(
+        &mut self,
+        input: &winit::event::KeyboardInput,
+        modifiers: winit::event::ModifiersState,
+        keybindings: &mut Keybindings,
+    ) {
+        let chord = match Chord::from_input(input, modifiers) {
+            Some(chord) => chord,
+            None => return,
+        };
+        let action = match self.capturing.take() {
+            Some(action) => action,
+            None => return,
+        };
+        if chord.key == winit::event::VirtualKeyCode::Escape && modifiers.is_empty() {
+            self.status = "Cancelled".to_owned();
+            return;
+        }
+        self.status = match keybindings.bind(action, Some(chord)) {
+            Some(previous) => format!(
+                "{} is now {}, {} is unbound",
+                chord.label(),
+                action.name(),
+                previous.name()
+            ),
+            None => format!("{} is now {}", chord.label(), action.name()),
+        };
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, keybindings: &mut Keybindings) {
+        let mut open = self.open;
+        let capturing = &mut self.capturing;
+        let status = &mut self.status;
+        egui::Window::new("Shortcuts")
+            .open(&mut open)
+            .default_pos(egui::pos2(360.0, 140.0))
+            .show(ctx, |ui| {
+                for action in Action::ALL.iter() {
+                    ui.horizontal(|ui| {
+                        ui.label(action.name());
+                        let label = if *capturing == Some(*action) {
+                            "Press a key...".to_owned()
+                        } else {
+                            keybindings
+                                .chord(*action)
+                                .map_or("Unbound".to_owned(), |chord| chord.label())
+                        };
+                        if ui.button(label).clicked {
+                            *capturing = Some(*action);
+                        }
+                        if ui.button("Clear").clicked {
+                            keybindings.bind(*action, None);
+                        }
+                        if ui.button("Default").clicked {
+                            if let Some(previous) =
+                                keybindings.bind(*action, action.default_chord())
+                            {
+                                *status = format!("{} is unbound", previous.name());
+                            }
+                        }
+                    });
+                }
+                ui.label("Escape cancels rebinding. Shortcuts are ignored while typing.");
+                if ui.button("Reset all").clicked {
+                    *keybindings = Keybindings::default();
+                }
+                if !status.is_empty() {
+                    ui.label(status.as_str());
+                }
+            });
+        self.open = open;
+        if !self.open {
+            self.capturing = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Action, Chord, Keybindings};
+    use winit::event::{ElementState, KeyboardInput, ModifiersState, VirtualKeyCode};
+
+    #[allow(deprecated)]
+    fn input(key: VirtualKeyCode, state: ElementState) -> KeyboardInput {
+        KeyboardInput {
+            scancode: 0,
+            state,
+            virtual_keycode: Some(key),
+            modifiers: ModifiersState::empty(),
+        }
+    }
+
+    #[test]
+    fn chords_come_from_presses_of_non_modifier_keys() {
+        let chord = Chord::from_input(
+            &input(VirtualKeyCode::K, ElementState::Pressed),
+            ModifiersState::CTRL,
+        );
+        assert!(chord == Some(Chord::new(VirtualKeyCode::K, ModifiersState::CTRL)));
+        assert!(Chord::from_input(
+            &input(VirtualKeyCode::K, ElementState::Released),
+            ModifiersState::CTRL
+        )
+        .is_none());
+        assert!(Chord::from_input(
+            &input(VirtualKeyCode::LShift, ElementState::Pressed),
+            ModifiersState::SHIFT
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn labels_list_modifiers_before_the_key() {
+        let chord = Chord::new(
+            VirtualKeyCode::S,
+            ModifiersState::CTRL | ModifiersState::SHIFT,
+        );
+        assert_eq!(chord.label(), "Ctrl+Shift+S");
+        assert_eq!(
+            Chord::new(VirtualKeyCode::F3, ModifiersState::empty()).label(),
+            "F3"
+        );
+    }
+
+    #[test]
+    fn defaults_resolve_to_their_actions() {
+        let keybindings = Keybindings::default();
+        let action = keybindings.action(
+            &input(VirtualKeyCode::F3, ElementState::Pressed),
+            ModifiersState::empty(),
+        );
+        assert_eq!(action, Some(Action::Profiler));
+        let action = keybindings.action(
+            &input(VirtualKeyCode::F3, ElementState::Pressed),
+            ModifiersState::SHIFT,
+        );
+        assert_eq!(action, None);
+    }
+
+    #[test]
+    fn rebinding_a_taken_chord_unbinds_the_previous_action() {
+        let mut keybindings = Keybindings::default();
+        let chord = keybindings.chord(Action::Profiler);
+        assert!(chord.is_some());
+        assert_eq!(keybindings.bind(Action::Log, chord), Some(Action::Profiler));
+        assert!(keybindings.chord(Action::Profiler).is_none());
+        assert!(keybindings.chord(Action::Log) == chord);
+    }
+
+    #[test]
+    fn clearing_a_binding_leaves_the_others_alone() {
+        let mut keybindings = Keybindings::default();
+        let quit = keybindings.chord(Action::Quit);
+        assert_eq!(keybindings.bind(Action::Profiler, None), None);
+        assert_eq!(keybindings.bind(Action::Log, None), None);
+        assert!(keybindings.chord(Action::Profiler).is_none());
+        assert!(keybindings.chord(Action::Quit) == quit);
+    }
+}
@@ -0,0 +1,133 @@
+const MAX_LABELS: usize = 64;
+const MAX_DISTANCE: f32 = 40.0;
+const OCCLUDED_OPACITY: f32 = 0.2;
+const FADE_SPEED: f32 = 8.0;
+
+struct Anchor {
+    position: glam::Vec3,
+    text: String,
+    object: Option<usize>,
+}
+
+pub struct SceneLabels {
+    pub enabled: bool,
+    fades: Vec<f32>,
+    last_frame: Option<std::time::Instant>,
+}
+
+impl Default for SceneLabels {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            fades: Vec::new(),
+            last_frame: None,
+        }
+    }
+}
+
+impl SceneLabels {
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, scene: &crate::scene::Scene) {
+        let now = std::time::Instant::now();
+        let dt = self
+            .last_frame
+            .map(|last| (now - last).as_secs_f32())
+            .unwrap_or(0.0);
+        self.last_frame = Some(now);
+        if !self.enabled {
+            self.fades.clear();
+            return;
+        }
+
+        let bounds: Vec<crate::culling::Aabb> = scene
+            .objects
+            .iter()
+            .map(|object| scene.object_bounds(object))
+            .collect();
+        let mut anchors: Vec<Anchor> = scene
+            .objects
+            .iter()
+            .zip(bounds.iter())
+            .enumerate()
+            .map(|(index, (object, bounds))| Anchor {
+                position: glam::Vec3::new(bounds.center().x, bounds.max.y + 0.2, bounds.center().z),
+                text: object.name.clone(),
+                object: Some(index),
+            })
+            .collect();
+        for (index, light) in scene.lights.iter().enumerate() {
+            let kind = match light.kind {
+                crate::scene::LightKind::Directional => continue,
+                crate::scene::LightKind::Point => "Point light",
+                crate::scene::LightKind::Spot => "Spot light",
+            };
+            anchors.push(Anchor {
+                position: light.position + glam::Vec3::unit_y() * 0.4,
+                text: format!("{} {}", kind, index),
+                object: None,
+            });
+        }
+        self.fades.resize(anchors.len(), 0.0);
+
+        let eye = scene.camera.eye();
+        let points = ctx.pixels_per_point();
+        let mut nearest: Vec<(usize, f32, egui::Pos2)> = anchors
+            .iter()
+            .enumerate()
+            .filter_map(|(index, anchor)| {
+                let distance = (anchor.position - eye).length();
+                if distance > MAX_DISTANCE {
+                    return None;
+                }
+                let pos = scene.world_to_screen(anchor.position, points)?;
+                Some((index, distance, pos))
+            })
+            .collect();
+        nearest.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+        nearest.truncate(MAX_LABELS);
+
+        let step = (dt * FADE_SPEED).min(1.0);
+        let mut shown = vec![false; anchors.len()];
+        let painter = egui::Painter::new(
+            ctx.clone(),
+            egui::LayerId::background(),
+            ctx.input().screen_rect(),
+        );
+        for (index, distance, pos) in nearest {
+            let anchor = &anchors[index];
+            let direction = (anchor.position - eye) / distance;
+            let occluded = bounds.iter().enumerate().any(|(object, bounds)| {
+                Some(object) != anchor.object
+                    && bounds
+                        .ray_distance(eye, direction)
+                        .is_some_and(|hit| hit < distance - 0.05)
+            });
+            let target = if occluded { OCCLUDED_OPACITY } else { 1.0 };
+            let fade = &mut self.fades[index];
+            *fade += (target - *fade) * step;
+            shown[index] = true;
+
+            let distance_fade = (1.0 - distance / MAX_DISTANCE).min(0.25) * 4.0;
+            let alpha = (*fade * distance_fade * 255.0) as u8;
+            let anchor_align = (egui::Align::Center, egui::Align::Max);
+            painter.text(
+                pos + egui::vec2(1.0, 1.0),
+                anchor_align,
+                anchor.text.clone(),
+                egui::TextStyle::Body,
+                egui::Srgba::new(0, 0, 0, alpha),
+            );
+            painter.text(
+                pos,
+                anchor_align,
+                anchor.text.clone(),
+                egui::TextStyle::Body,
+                egui::Srgba::new(alpha, alpha, alpha, alpha),
+            );
+        }
+        for (fade, shown) in self.fades.iter_mut().zip(shown) {
+            if !shown {
+                *fade = 0.0;
+            }
+        }
+    }
+}
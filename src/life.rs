@@ -0,0 +1,481 @@
+pub const GRID_SIZES: [u32; 4] = [64, 128, 256, 512];
+const MAX_STEPS_PER_FRAME: u32 = 16;
+const WORKGROUP_SIZE: u32 = 8;
+const VIEW_SIZE: f32 = 320.0;
+
+const MODE_NONE: u32 = 0;
+const MODE_DRAW: u32 = 1;
+const MODE_ERASE: u32 = 2;
+const MODE_RANDOMIZE: u32 = 3;
+const MODE_CLEAR: u32 = 4;
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    size: [u32; 2],
+    brush: [i32; 2],
+    radius: u32,
+    mode: u32,
+    seed: u32,
+    padding: u32,
+}
+
+pub struct Settings {
+    pub grid_size: u32,
+    pub steps_per_second: f32,
+    pub paused: bool,
+    pub brush_radius: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            grid_size: 128,
+            steps_per_second: 10.0,
+            paused: false,
+            brush_radius: 1.0,
+        }
+    }
+}
+
+struct Grid {
+    size: u32,
+    textures: Vec<wgpu::Texture>,
+    step_bind_groups: [wgpu::BindGroup; 2],
+    display_bind_groups: [wgpu::BindGroup; 2],
+}
+
+impl Grid {
+    fn new(
+        device: &wgpu::Device,
+        size: u32,
+        step_layout: &wgpu::BindGroupLayout,
+        display_layout: &wgpu::BindGroupLayout,
+        uniform_buffer: &wgpu::Buffer,
+        sampler: &wgpu::Sampler,
+    ) -> Self {
+        let textures: Vec<wgpu::Texture> = (0..2)
+            .map(|_| {
+                device.create_texture(&wgpu::TextureDescriptor {
+                    label: Some("Life Cells"),
+                    size: wgpu::Extent3d {
+                        width: size,
+                        height: size,
+                        depth: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: wgpu::TextureFormat::R32Uint,
+                    usage: wgpu::TextureUsage::STORAGE | wgpu::TextureUsage::SAMPLED,
+                })
+            })
+            .collect();
+        let views: Vec<wgpu::TextureView> = textures
+            .iter()
+            .map(|texture| texture.create_view(&wgpu::TextureViewDescriptor::default()))
+            .collect();
+        let step_bind_group = |source: usize| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Life Step Bind Group"),
+                layout: step_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::Buffer(uniform_buffer.slice(..)),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&views[source]),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::TextureView(&views[1 - source]),
+                    },
+                ],
+            })
+        };
+        let display_bind_group = |source: usize| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Life Display Bind Group"),
+                layout: display_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&views[source]),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(sampler),
+                    },
+                ],
+            })
+        };
+
+        Self {
+            size,
+            textures,
+            step_bind_groups: [step_bind_group(0), step_bind_group(1)],
+            display_bind_groups: [display_bind_group(0), display_bind_group(1)],
+        }
+    }
+}
+
+pub struct Life {
+    pub open: bool,
+    pub settings: Settings,
+    pub generation: u64,
+    step_requested: bool,
+    randomize_requested: bool,
+    clear_requested: bool,
+    cursor: Option<winit::dpi::PhysicalPosition<f64>>,
+    drawing: bool,
+    erasing: bool,
+    viewport: Option<egui::Rect>,
+    canvas: crate::blit::Canvas,
+    accumulator: f32,
+    last_frame: Option<std::time::Instant>,
+    seed: u32,
+    current: usize,
+    step_pipeline: wgpu::ComputePipeline,
+    paint_pipeline: wgpu::ComputePipeline,
+    display_pipeline: wgpu::RenderPipeline,
+    step_layout: wgpu::BindGroupLayout,
+    display_layout: wgpu::BindGroupLayout,
+    uniform_buffer: wgpu::Buffer,
+    sampler: wgpu::Sampler,
+    grid: Grid,
+}
+
+impl Life {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let settings = Settings::default();
+        let storage_entry = |binding, readonly| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStage::COMPUTE,
+            ty: wgpu::BindingType::StorageTexture {
+                dimension: wgpu::TextureViewDimension::D2,
+                format: wgpu::TextureFormat::R32Uint,
+                readonly,
+            },
+            count: None,
+        };
+        let step_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Life Step Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::COMPUTE,
+                    ty: wgpu::BindingType::UniformBuffer {
+                        dynamic: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                storage_entry(1, true),
+                storage_entry(2, false),
+            ],
+        });
+        let display_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Life Display Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::SampledTexture {
+                        dimension: wgpu::TextureViewDimension::D2,
+                        component_type: wgpu::TextureComponentType::Uint,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler { comparison: false },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Life Step Pipeline Layout"),
+            bind_group_layouts: &[&step_layout],
+            push_constant_ranges: &[],
+        });
+        let compute_pipeline = |name: &str, source: &str| {
+            let module =
+                crate::shader::compile_builtin(device, name, source, shaderc::ShaderKind::Compute);
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(name),
+                layout: Some(&pipeline_layout),
+                compute_stage: wgpu::ProgrammableStageDescriptor {
+                    module: &module,
+                    entry_point: "main",
+                },
+            })
+        };
+        let step_pipeline =
+            compute_pipeline("life_step.comp", include_str!("shaders/life_step.comp"));
+        let paint_pipeline =
+            compute_pipeline("life_paint.comp", include_str!("shaders/life_paint.comp"));
+        let display_pipeline = crate::blit::fullscreen_pipeline(
+            device,
+            "Life Display Pipeline",
+            &[&display_layout],
+            "life.frag",
+            include_str!("shaders/life.frag"),
+            format,
+            wgpu::BlendDescriptor::REPLACE,
+        );
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Life Uniforms"),
+            size: std::mem::size_of::<Uniforms>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Life Sampler"),
+            ..Default::default()
+        });
+        let grid = Grid::new(
+            device,
+            settings.grid_size,
+            &step_layout,
+            &display_layout,
+            &uniform_buffer,
+            &sampler,
+        );
+
+        Self {
+            open: false,
+            settings,
+            generation: 0,
+            step_requested: false,
+            randomize_requested: true,
+            clear_requested: false,
+            cursor: None,
+            drawing: false,
+            erasing: false,
+            viewport: None,
+            canvas: crate::blit::Canvas::new(format),
+            accumulator: 0.0,
+            last_frame: None,
+            seed: 1,
+            current: 0,
+            step_pipeline,
+            paint_pipeline,
+            display_pipeline,
+            step_layout,
+            display_layout,
+            uniform_buffer,
+            sampler,
+            grid,
+        }
+    }
+
+    pub fn recreate_gpu_resources(&mut self, device: &wgpu::Device, format: wgpu::TextureFormat) {
+        let settings = std::mem::take(&mut self.settings);
+        let open = self.open;
+        *self = Self::new(device, format);
+        self.settings = settings;
+        self.open = open;
+    }
+
+    pub fn set_cursor(&mut self, cursor: Option<winit::dpi::PhysicalPosition<f64>>) {
+        self.cursor = cursor;
+    }
+
+    pub fn mouse_input(
+        &mut self,
+        state: winit::event::ElementState,
+        button: winit::event::MouseButton,
+    ) {
+        let pressed = state == winit::event::ElementState::Pressed;
+        match button {
+            winit::event::MouseButton::Left => self.drawing = pressed,
+            winit::event::MouseButton::Right => self.erasing = pressed,
+            _ => {}
+        }
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        let mut open = self.open;
+        let mut viewport = None;
+        let generation = self.generation;
+        let settings = &mut self.settings;
+        let step_requested = &mut self.step_requested;
+        let randomize_requested = &mut self.randomize_requested;
+        let clear_requested = &mut self.clear_requested;
+        let canvas = &self.canvas;
+        crate::dock::Panel::new("Game of Life")
+            .open(&mut open)
+            .default_pos(egui::pos2(400.0, 80.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    for size in GRID_SIZES.iter() {
+                        ui.radio_value(&mut settings.grid_size, *size, format!("{0}x{0}", size));
+                    }
+                });
+                ui.add(
+                    egui::Slider::f32(&mut settings.steps_per_second, 1.0..=240.0)
+                        .text("steps / s"),
+                );
+                ui.add(
+                    egui::Slider::f32(&mut settings.brush_radius, 0.0..=8.0).text("brush radius"),
+                );
+                ui.horizontal(|ui| {
+                    let pause = if settings.paused { "Resume" } else { "Pause" };
+                    if ui.button(pause).clicked {
+                        settings.paused = !settings.paused;
+                    }
+                    if ui.button("Step").clicked {
+                        *step_requested = true;
+                    }
+                    if ui.button("Randomize").clicked {
+                        *randomize_requested = true;
+                    }
+                    if ui.button("Clear").clicked {
+                        *clear_requested = true;
+                    }
+                });
+                ui.label(format!("Generation: {}", generation));
+                ui.label("Left drag draws cells, right drag erases");
+                let rect = ui.allocate_space(egui::vec2(VIEW_SIZE, VIEW_SIZE));
+                ui.interact(
+                    rect,
+                    ui.make_persistent_id("life_grid"),
+                    egui::Sense::drag(),
+                );
+                canvas.paint(ui, rect);
+                viewport = Some(rect);
+            });
+        self.open = open;
+        self.viewport = viewport.filter(|_| open);
+    }
+
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        ui_pass: &mut crate::ui_pass::RenderPass,
+        size: winit::dpi::PhysicalSize<u32>,
+        scale_factor: f64,
+    ) {
+        let now = std::time::Instant::now();
+        let dt = self
+            .last_frame
+            .map(|last| (now - last).as_secs_f32())
+            .unwrap_or(0.0);
+        self.last_frame = Some(now);
+        let viewport = match self.viewport {
+            Some(rect) => crate::blit::to_physical(rect, scale_factor),
+            None => return,
+        };
+
+        if self.grid.size != self.settings.grid_size {
+            self.grid = Grid::new(
+                device,
+                self.settings.grid_size,
+                &self.step_layout,
+                &self.display_layout,
+                &self.uniform_buffer,
+                &self.sampler,
+            );
+            self.current = 0;
+            self.generation = 0;
+            self.randomize_requested = true;
+        }
+
+        let grid_size = self.grid.size;
+        let mut brush = [0, 0];
+        let mut mode = MODE_NONE;
+        if self.clear_requested {
+            mode = MODE_CLEAR;
+            self.generation = 0;
+        } else if self.randomize_requested {
+            mode = MODE_RANDOMIZE;
+            self.seed = self
+                .seed
+                .wrapping_mul(1_664_525)
+                .wrapping_add(1_013_904_223);
+            self.generation = 0;
+        } else if let Some(cursor) = self.cursor.filter(|_| self.drawing || self.erasing) {
+            let cursor = egui::pos2(cursor.x as f32, cursor.y as f32);
+            if viewport.contains(cursor) {
+                let cell = (cursor - viewport.min) / viewport.width() * grid_size as f32;
+                brush = [cell.x as i32, cell.y as i32];
+                mode = if self.drawing { MODE_DRAW } else { MODE_ERASE };
+            }
+        }
+        self.clear_requested = false;
+        self.randomize_requested = false;
+
+        let mut steps = 0;
+        if !self.settings.paused {
+            self.accumulator += dt * self.settings.steps_per_second;
+            steps = (self.accumulator as u32).min(MAX_STEPS_PER_FRAME);
+            self.accumulator = (self.accumulator - steps as f32).min(1.0);
+        }
+        if self.step_requested {
+            steps += 1;
+            self.step_requested = false;
+        }
+
+        let uniforms = Uniforms {
+            size: [grid_size, grid_size],
+            brush,
+            radius: self.settings.brush_radius.round() as u32,
+            mode,
+            seed: self.seed,
+            padding: 0,
+        };
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+
+        let groups = grid_size.div_ceil(WORKGROUP_SIZE);
+        {
+            let mut compute_pass = encoder.begin_compute_pass();
+            if mode != MODE_NONE {
+                compute_pass.set_pipeline(&self.paint_pipeline);
+                compute_pass.set_bind_group(0, &self.grid.step_bind_groups[self.current], &[]);
+                compute_pass.dispatch(groups, groups, 1);
+                self.current = 1 - self.current;
+            }
+            compute_pass.set_pipeline(&self.step_pipeline);
+            for _ in 0..steps {
+                compute_pass.set_bind_group(0, &self.grid.step_bind_groups[self.current], &[]);
+                compute_pass.dispatch(groups, groups, 1);
+                self.current = 1 - self.current;
+            }
+        }
+        self.generation += steps as u64;
+
+        if !crate::blit::fits(viewport, size) {
+            return;
+        }
+        let target = self.canvas.begin(device, encoder, ui_pass, size);
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+        crate::blit::set_viewport(&mut render_pass, viewport);
+        render_pass.set_pipeline(&self.display_pipeline);
+        render_pass.set_bind_group(0, &self.grid.display_bind_groups[self.current], &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
@@ -0,0 +1,97 @@
+#[derive(Default)]
+pub struct LightsPanel {
+    pub open: bool,
+    selected: Option<usize>,
+}
+
+impl LightsPanel {
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, scene: &mut crate::scene::Scene) {
+        let selected = &mut self.selected;
+        crate::dock::Panel::new("Lights")
+            .open(&mut self.open)
+            .default_pos(egui::pos2(560.0, 48.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("Add point").clicked {
+                        scene.lights.push(crate::scene::Light::point(
+                            glam::Vec3::new(0.0, 2.0, 0.0),
+                            glam::Vec3::one(),
+                            8.0,
+                            6.0,
+                        ));
+                        *selected = Some(scene.lights.len() - 1);
+                    }
+                    if ui.button("Add spot").clicked {
+                        scene.lights.push(crate::scene::Light::spot(
+                            glam::Vec3::new(0.0, 5.0, 0.0),
+                            -glam::Vec3::unit_y(),
+                            glam::Vec3::one(),
+                            20.0,
+                            10.0,
+                        ));
+                        *selected = Some(scene.lights.len() - 1);
+                    }
+                    if ui.button("Scatter 64").clicked {
+                        scene.scatter_lights(64);
+                    }
+                });
+                ui.checkbox(&mut scene.clusters.heatmap, "Light count heatmap");
+                ui.label(format!(
+                    "{} lights, {} visible, {} max per cluster",
+                    scene.stats.lights,
+                    scene.stats.visible_lights,
+                    crate::clustered::MAX_LIGHTS_PER_CLUSTER
+                ));
+                ui.separator();
+
+                for (i, light) in scene.lights.iter().enumerate() {
+                    let kind = match light.kind {
+                        crate::scene::LightKind::Directional => "Directional",
+                        crate::scene::LightKind::Point => "Point",
+                        crate::scene::LightKind::Spot => "Spot",
+                    };
+                    ui.radio_value(selected, Some(i), format!("{} {}", kind, i));
+                }
+
+                let index = match *selected {
+                    Some(index) if index < scene.lights.len() => index,
+                    _ => return,
+                };
+                ui.separator();
+                let light = &mut scene.lights[index];
+                if light.kind != crate::scene::LightKind::Directional {
+                    ui.add(egui::Slider::f32(&mut light.position.x, -15.0..=15.0).text("x"));
+                    ui.add(egui::Slider::f32(&mut light.position.y, 0.0..=15.0).text("y"));
+                    ui.add(egui::Slider::f32(&mut light.position.z, -15.0..=15.0).text("z"));
+                    ui.add(egui::Slider::f32(&mut light.range, 0.5..=30.0).text("range"));
+                }
+                if light.kind != crate::scene::LightKind::Point {
+                    ui.add(
+                        egui::Slider::f32(&mut light.direction.x, -1.0..=1.0).text("direction x"),
+                    );
+                    ui.add(
+                        egui::Slider::f32(&mut light.direction.y, -1.0..=1.0).text("direction y"),
+                    );
+                    ui.add(
+                        egui::Slider::f32(&mut light.direction.z, -1.0..=1.0).text("direction z"),
+                    );
+                }
+                if light.kind == crate::scene::LightKind::Spot {
+                    let mut inner = light.inner_angle.to_degrees();
+                    let mut outer = light.outer_angle.to_degrees();
+                    ui.add(egui::Slider::f32(&mut inner, 1.0..=89.0).text("inner angle"));
+                    ui.add(egui::Slider::f32(&mut outer, 1.0..=89.0).text("outer angle"));
+                    light.outer_angle = outer.to_radians();
+                    light.inner_angle = inner.min(outer).to_radians();
+                }
+                ui.add(egui::Slider::f32(&mut light.color.x, 0.0..=1.0).text("red"));
+                ui.add(egui::Slider::f32(&mut light.color.y, 0.0..=1.0).text("green"));
+                ui.add(egui::Slider::f32(&mut light.color.z, 0.0..=1.0).text("blue"));
+                ui.add(egui::Slider::f32(&mut light.intensity, 0.0..=50.0).text("intensity"));
+                if ui.button("Remove").clicked {
+                    scene.lights.remove(index);
+                    *selected = None;
+                }
+            });
+    }
+}
@@ -0,0 +1,77 @@
+enum Message {
+    Progress(f32, &'static str),
+    Done(Box<crate::scene::Scene>),
+}
+
+pub struct Loader {
+    receiver: std::sync::mpsc::Receiver<Message>,
+    progress: f32,
+    stage: &'static str,
+    started: std::time::Instant,
+}
+
+impl Loader {
+    pub fn start(
+        device: std::sync::Arc<wgpu::Device>,
+        format: wgpu::TextureFormat,
+        size: winit::dpi::PhysicalSize<u32>,
+    ) -> Self {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::Builder::new()
+            .name("scene loader".to_owned())
+            .spawn(move || {
+                let time = std::time::Instant::now();
+                sender
+                    .send(Message::Progress(0.0, "Compiling shaders and pipelines"))
+                    .ok();
+                let mut scene = crate::scene::Scene::new(&device, format, size);
+                sender
+                    .send(Message::Progress(0.7, "Building demo meshes"))
+                    .ok();
+                scene.populate_demo(&device);
+                log::info!("scene loaded, took {} ms", time.elapsed().as_millis());
+                sender.send(Message::Done(Box::new(scene))).ok();
+            })
+            .unwrap();
+
+        Self {
+            receiver,
+            progress: 0.0,
+            stage: "Starting",
+            started: std::time::Instant::now(),
+        }
+    }
+
+    pub fn poll(&mut self) -> Option<crate::scene::Scene> {
+        while let Ok(message) = self.receiver.try_recv() {
+            match message {
+                Message::Progress(progress, stage) => {
+                    self.progress = progress;
+                    self.stage = stage;
+                }
+                Message::Done(scene) => return Some(*scene),
+            }
+        }
+        None
+    }
+
+    pub fn show(&self, ctx: &std::sync::Arc<egui::Context>) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading(env!("CARGO_PKG_NAME"));
+            ui.label(format!(
+                "{}... ({:.1} s)",
+                self.stage,
+                self.started.elapsed().as_secs_f32()
+            ));
+            let rect = ui.allocate_space(egui::vec2(240.0, 8.0));
+            let filled = egui::Rect::from_min_size(
+                rect.min,
+                egui::vec2(rect.width() * self.progress, rect.height()),
+            );
+            ui.painter()
+                .rect_filled(rect, 2.0, egui::Srgba::new(60, 60, 60, 255));
+            ui.painter()
+                .rect_filled(filled, 2.0, egui::Srgba::new(90, 170, 250, 255));
+        });
+    }
+}
@@ -0,0 +1,104 @@
+pub const MAX_LEVELS: usize = 4;
+
+pub const DEBUG_COLORS: [[f32; 4]; MAX_LEVELS] = [
+    [0.2, 0.8, 0.2, 1.0],
+    [0.9, 0.9, 0.2, 1.0],
+    [0.95, 0.5, 0.1, 1.0],
+    [0.9, 0.15, 0.15, 1.0],
+];
+
+pub struct Lod {
+    pub indices: Vec<u32>,
+    pub error: f32,
+}
+
+pub fn generate(mesh: &crate::scene::Mesh) -> Vec<Lod> {
+    let bounds = mesh.bounds();
+    let size = bounds.max - bounds.min;
+    let extent = size.x.max(size.y).max(size.z);
+
+    let mut lods = vec![Lod {
+        indices: mesh.indices.clone(),
+        error: 0.0,
+    }];
+    if extent <= 0.0 {
+        return lods;
+    }
+
+    let mut grid = 64;
+    while lods.len() < MAX_LEVELS && grid >= 2 {
+        let cell_size = extent / grid as f32;
+        let indices = simplify(mesh, bounds.min, cell_size);
+        let previous = lods.last().unwrap().indices.len();
+        if indices.is_empty() {
+            break;
+        }
+        if indices.len() * 4 <= previous * 3 {
+            lods.push(Lod {
+                indices,
+                error: cell_size * 3f32.sqrt(),
+            });
+        }
+        grid /= 2;
+    }
+
+    log::debug!(
+        "{}: generated {} lods ({} triangles)",
+        mesh.name,
+        lods.len(),
+        lods.iter()
+            .map(|lod| (lod.indices.len() / 3).to_string())
+            .collect::<Vec<_>>()
+            .join(" / ")
+    );
+    lods
+}
+
+fn simplify(mesh: &crate::scene::Mesh, origin: glam::Vec3, cell_size: f32) -> Vec<u32> {
+    let mut representatives = std::collections::HashMap::new();
+    let remap: Vec<u32> = mesh
+        .vertices
+        .iter()
+        .enumerate()
+        .map(|(index, vertex)| {
+            let cell = (glam::Vec3::from(vertex.position) - origin) / cell_size;
+            let key = (cell.x as i32, cell.y as i32, cell.z as i32);
+            *representatives.entry(key).or_insert(index as u32)
+        })
+        .collect();
+
+    let mut seen = std::collections::HashSet::new();
+    let mut indices = Vec::new();
+    for triangle in mesh.indices.chunks(3) {
+        let a = remap[triangle[0] as usize];
+        let b = remap[triangle[1] as usize];
+        let c = remap[triangle[2] as usize];
+        if a == b || b == c || a == c {
+            continue;
+        }
+        let rotated = if a < b && a < c {
+            (a, b, c)
+        } else if b < c {
+            (b, c, a)
+        } else {
+            (c, a, b)
+        };
+        if seen.insert(rotated) {
+            indices.extend_from_slice(&[a, b, c]);
+        }
+    }
+    indices
+}
+
+pub fn select(
+    lods: &[f32],
+    scale: f32,
+    distance: f32,
+    pixels_per_unit: f32,
+    threshold: f32,
+) -> usize {
+    let distance = distance.max(1e-3);
+    lods.iter()
+        .rposition(|error| error * scale / distance * pixels_per_unit <= threshold)
+        .unwrap_or(0)
+}
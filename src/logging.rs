@@ -0,0 +1,191 @@
+const CAPACITY: usize = 2000;
+const TAIL_ROWS: usize = 24;
+const LEVELS: [log::LevelFilter; 5] = [
+    log::LevelFilter::Error,
+    log::LevelFilter::Warn,
+    log::LevelFilter::Info,
+    log::LevelFilter::Debug,
+    log::LevelFilter::Trace,
+];
+
+pub struct Entry {
+    pub elapsed: f32,
+    pub level: log::Level,
+    pub target: String,
+    pub message: String,
+}
+
+impl Entry {
+    fn line(&self) -> String {
+        format!(
+            "{:9.3} {:5} {}: {}",
+            self.elapsed, self.level, self.target, self.message
+        )
+    }
+
+    fn color(&self) -> egui::Srgba {
+        match self.level {
+            log::Level::Error => egui::Srgba::new(240, 100, 90, 255),
+            log::Level::Warn => egui::Srgba::new(230, 190, 80, 255),
+            log::Level::Info => egui::Srgba::new(210, 210, 210, 255),
+            log::Level::Debug => egui::Srgba::new(140, 170, 210, 255),
+            log::Level::Trace => egui::Srgba::new(130, 130, 140, 255),
+        }
+    }
+}
+
+pub type Records = std::sync::Arc<std::sync::Mutex<std::collections::VecDeque<Entry>>>;
+
+struct Logger {
+    terminal: env_logger::Logger,
+    records: Records,
+    start: std::time::Instant,
+}
+
+impl log::Log for Logger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Info || self.terminal.enabled(metadata)
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.terminal.matches(record) {
+            self.terminal.log(record);
+        }
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let mut records = self.records.lock().unwrap();
+        if records.len() == CAPACITY {
+            records.pop_front();
+        }
+        records.push_back(Entry {
+            elapsed: self.start.elapsed().as_secs_f32(),
+            level: record.level(),
+            target: record.target().to_owned(),
+            message: record.args().to_string(),
+        });
+    }
+
+    fn flush(&self) {
+        self.terminal.flush();
+    }
+}
+
+pub fn init() -> Records {
+    let terminal = env_logger::builder().format_timestamp(None).build();
+    let max_level = terminal.filter().max(log::LevelFilter::Info);
+    let records = Records::default();
+    log::set_boxed_logger(Box::new(Logger {
+        terminal,
+        records: records.clone(),
+        start: std::time::Instant::now(),
+    }))
+    .unwrap();
+    log::set_max_level(max_level);
+    records
+}
+
+pub struct LogPanel {
+    pub open: bool,
+    records: Records,
+    level: log::LevelFilter,
+    query: String,
+    auto_scroll: bool,
+}
+
+impl Default for LogPanel {
+    fn default() -> Self {
+        Self {
+            open: false,
+            records: Records::default(),
+            level: log::LevelFilter::Info,
+            query: String::new(),
+            auto_scroll: true,
+        }
+    }
+}
+
+impl LogPanel {
+    pub fn set_records(&mut self, records: Records) {
+        self.records = records;
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        if !self.open {
+            return;
+        }
+        let mut open = self.open;
+        let mut clear_requested = false;
+        let mut copy_requested = false;
+        let level = &mut self.level;
+        let query = &mut self.query;
+        let auto_scroll = &mut self.auto_scroll;
+        let records = self.records.lock().unwrap();
+        let needle = query.to_lowercase();
+        let visible: Vec<&Entry> = records
+            .iter()
+            .filter(|entry| entry.level <= *level)
+            .filter(|entry| {
+                needle.is_empty()
+                    || entry.message.to_lowercase().contains(&needle)
+                    || entry.target.to_lowercase().contains(&needle)
+            })
+            .collect();
+        crate::dock::Panel::new("Log")
+            .open(&mut open)
+            .default_pos(egui::pos2(320.0, 360.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    for filter in LEVELS.iter() {
+                        ui.radio_value(level, *filter, filter.to_string());
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Search");
+                    ui.add(egui::TextEdit::new(query));
+                    ui.checkbox(auto_scroll, "Auto-scroll");
+                    if ui.button("Copy").clicked {
+                        copy_requested = true;
+                    }
+                    if ui.button("Clear").clicked {
+                        clear_requested = true;
+                    }
+                });
+                ui.label(format!(
+                    "{} of {} records (keeps the last {})",
+                    visible.len(),
+                    records.len(),
+                    CAPACITY
+                ));
+                let row = |ui: &mut egui::Ui, entry: &Entry| {
+                    ui.add(
+                        egui::Label::new(entry.line())
+                            .text_style(egui::TextStyle::Monospace)
+                            .text_color(entry.color()),
+                    );
+                };
+                if *auto_scroll {
+                    let skip = visible.len().saturating_sub(TAIL_ROWS);
+                    for entry in visible.iter().skip(skip) {
+                        row(ui, entry);
+                    }
+                } else {
+                    egui::ScrollArea::from_max_height(400.0).show(ui, |ui| {
+                        for entry in visible.iter() {
+                            row(ui, entry);
+                        }
+                    });
+                }
+            });
+        if copy_requested {
+            let text: Vec<String> = visible.iter().map(|entry| entry.line()).collect();
+            ctx.output().copied_text = text.join("\n");
+        }
+        drop(visible);
+        drop(records);
+        if clear_requested {
+            self.records.lock().unwrap().clear();
+        }
+        self.open = open;
+    }
+}
@@ -1,67 +1,492 @@
 #![allow(unused)]
 
-struct Engine {
-    size: winit::dpi::PhysicalSize<u32>,
-    surface: wgpu::Surface,
+mod about;
+mod allocation;
+mod animation;
+mod benchmark;
+mod blit;
+mod bloom;
+mod boids;
+mod bookmarks;
+mod cache;
+mod charmap;
+mod clipboard;
+mod clustered;
+mod color_management;
+mod console;
+mod converter;
+mod culling;
+mod debug;
+mod dialogs;
+mod dock;
+mod dropzone;
+mod duplicates;
+mod editor;
+mod environment;
+mod eyedropper;
+mod focus;
+mod fonts;
+mod form;
+mod fractal;
+mod gallery;
+mod gamepad;
+mod headless;
+mod hud;
+mod image_edit;
+mod image_viewer;
+mod ime;
+mod inspector;
+mod jobs;
+mod keybindings;
+mod labels;
+mod life;
+mod lights;
+mod loading;
+mod lod;
+mod logging;
+mod markdown;
+mod material_graph;
+mod measure;
+mod menu;
+mod modal;
+mod network;
+mod noise;
+mod noise_texture;
+mod notes;
+mod notify;
+mod oit;
+mod onboarding;
+mod pathtrace;
+mod persistence;
+mod picking;
+mod plots;
+mod probe;
+mod profiler;
+mod rendering;
+mod resolution;
+mod scene;
+mod scopes;
+mod sdf;
+mod settings;
+mod setup;
+mod shader;
+mod snippets;
+mod spell;
+mod sprites;
+mod ssao;
+mod status;
+mod stereo;
+mod store;
+mod style;
+mod svg;
+mod taa;
+mod tabs;
+mod terrain;
+mod thumbnails;
+mod touch;
+mod tour;
+mod treemap;
+mod turntable;
+mod typing;
+mod ui_instance;
+mod ui_pass;
+mod upload;
+mod visualizer;
+mod voxel;
+mod windows;
+mod wipe;
+mod wizard;
+mod zoom;
+
+#[cfg(feature = "alloc-profiler")]
+#[global_allocator]
+static ALLOCATOR: allocation::Counting = allocation::Counting;
+
+pub enum UserEvent {
+    FileDialog(dialogs::Response),
+}
+
+enum Target {
+    Window {
+        surface: wgpu::Surface,
+        swap_chain: wgpu::SwapChain,
+    },
+    Offscreen {
+        texture: wgpu::Texture,
+        view: wgpu::TextureView,
+    },
+}
+
+struct Gpu {
+    instance: wgpu::Instance,
+    surface: Option<wgpu::Surface>,
     device: wgpu::Device,
     queue: wgpu::Queue,
+}
+
+async fn init_gpu(window: Option<&winit::window::Window>) -> Gpu {
+    let instance = wgpu::Instance::new(wgpu::BackendBit::PRIMARY);
+    let surface = window.map(|window| unsafe { instance.create_surface(window) });
+    let adapter = instance
+        .request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            compatible_surface: surface.as_ref(),
+        })
+        .await
+        .unwrap();
+    log::info!("using {}", adapter.get_info().name);
+    let (device, queue) = adapter
+        .request_device(
+            &wgpu::DeviceDescriptor {
+                features: wgpu::Features::empty(),
+                limits: wgpu::Limits::default(),
+                shader_validation: true,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    Gpu {
+        instance,
+        surface,
+        device,
+        queue,
+    }
+}
+
+pub struct Engine {
+    instance: wgpu::Instance,
+    size: winit::dpi::PhysicalSize<u32>,
+    device: std::sync::Arc<wgpu::Device>,
+    queue: wgpu::Queue,
     swap_chain_desc: wgpu::SwapChainDescriptor,
-    swap_chain: wgpu::SwapChain,
-    ui_instance: egui_winit::Instance,
-    ui_render_pass: egui_wgpu::RenderPass,
+    target: Target,
+    ui_instance: ui_instance::Instance,
+    ui_render_pass: ui_pass::RenderPass,
     scale_factor: f64,
+    window_scale_factor: f64,
+    scene: Option<scene::Scene>,
+    loader: Option<loading::Loader>,
+    scene_hud: hud::SceneHud,
+    scene_labels: labels::SceneLabels,
+    profiler: profiler::Profiler,
+    font_texture_version: Option<u64>,
+    uploader: upload::Uploader,
+    resolution: resolution::Controller,
+    rendering_panel: rendering::RenderingPanel,
+    debug_panel: debug::DebugPanel,
+    inspector: inspector::Inspector,
+    measure_panel: measure::MeasurePanel,
+    voxel_panel: voxel::VoxelPanel,
+    lights_panel: lights::LightsPanel,
+    life: life::Life,
+    boids: boids::Boids,
+    fractal: fractal::Fractal,
+    sdf: sdf::SdfPlayground,
+    material_graph: material_graph::MaterialGraph,
+    noise_texture: noise_texture::NoiseTexture,
+    sprites: sprites::SpriteDemo,
+    scopes: scopes::Scopes,
+    eyedropper: eyedropper::Eyedropper,
+    frame_compare: wipe::FrameCompare,
+    color_management: color_management::ColorManagement,
+    benchmark: benchmark::Benchmark,
+    settings: settings::AppSettings,
+    onboarding: onboarding::Onboarding,
+    bookmarks: bookmarks::Bookmarks,
+    bookmarks_panel: bookmarks::BookmarksPanel,
+    turntable: turntable::Turntable,
+    converter: converter::Converter,
+    gallery: gallery::WidgetGallery,
+    duplicates: duplicates::DuplicateFinder,
+    disk_usage: treemap::DiskUsage,
+    image_viewer: image_viewer::ImageViewer,
+    animation: animation::AnimationPlayer,
+    about: about::About,
+    style_editor: style::StyleEditor,
+    fonts_panel: fonts::FontsPanel,
+    charmap: charmap::CharMap,
+    notes: notes::NotesPanel,
+    gamepads: gamepad::GamepadPanel,
+    typing: typing::TypingTest,
+    environment: environment::EnvironmentPanel,
+    plots: plots::PlotsPanel,
+    allocations: allocation::AllocationPanel,
+    dropzone: dropzone::DropZone,
+    network: network::NetworkMonitor,
+    clipboard: clipboard::Clipboard,
+    editor: editor::Editor,
+    tabs: tabs::TabStrip,
+    image_tab: bool,
+    snippets: snippets::Snippets,
+    snippets_panel: snippets::SnippetsPanel,
+    markdown: markdown::MarkdownPanel,
+    log: logging::LogPanel,
+    console: console::Console,
+    ime: ime::Ime,
+    touch: touch::TouchInput,
+    zoom: zoom::Zoom,
+    settings_panel: settings::SettingsPanel,
+    keybindings: keybindings::Keybindings,
+    shortcuts: keybindings::ShortcutsPanel,
+    tasks: jobs::TasksPanel,
+    windows: Vec<windows::SecondaryWindow>,
+    new_window_requested: bool,
+    fullscreen_requested: bool,
+    quit_requested: bool,
+    confirm_quit: modal::ConfirmQuit,
+    dialogs: dialogs::Dialogs,
+    device_lost: bool,
+    system_theme: winit::window::Theme,
+    modifiers: winit::event::ModifiersState,
 }
 
 impl Engine {
-    pub async fn new(window: &winit::window::Window) -> Self {
+    pub async fn new(window: &winit::window::Window, settings: settings::AppSettings) -> Self {
         let size = window.inner_size();
-        let instance = wgpu::Instance::new(wgpu::BackendBit::PRIMARY);
-        let surface = unsafe { instance.create_surface(window) };
-        let adapter = instance
-            .request_adapter(&wgpu::RequestAdapterOptions {
-                power_preference: wgpu::PowerPreference::HighPerformance,
-                compatible_surface: Some(&surface),
-            })
-            .await
-            .unwrap();
-        log::info!("using {}", adapter.get_info().name);
-        let (device, queue) = adapter
-            .request_device(
-                &wgpu::DeviceDescriptor {
-                    features: wgpu::Features::empty(),
-                    limits: wgpu::Limits::default(),
-                    shader_validation: true,
-                },
-                None,
-            )
-            .await
-            .unwrap();
+        let Gpu {
+            instance,
+            surface,
+            device,
+            queue,
+        } = init_gpu(Some(window)).await;
+        let device = std::sync::Arc::new(device);
+        let surface = surface.unwrap();
 
         let swap_chain_desc = wgpu::SwapChainDescriptor {
             usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT,
             format: wgpu::TextureFormat::Bgra8UnormSrgb,
             width: size.width,
             height: size.height,
-            present_mode: wgpu::PresentMode::Fifo,
+            present_mode: settings.present_mode(),
         };
 
         let swap_chain = device.create_swap_chain(&surface, &swap_chain_desc);
 
-        let ui_instance = egui_winit::Instance::new(size, window.scale_factor());
-        let ui_render_pass = egui_wgpu::RenderPass::new(&device, swap_chain_desc.format);
+        let window_scale_factor = window.scale_factor();
+        let scale_factor = window_scale_factor * settings.ui_scale as f64;
+        let ui_instance = ui_instance::Instance::new(size, scale_factor);
+        persistence::load_memory(ui_instance.context());
+        let ui_render_pass = ui_pass::RenderPass::new(&device, swap_chain_desc.format);
 
-        let scale_factor = window.scale_factor();
+        let loader = loading::Loader::start(device.clone(), swap_chain_desc.format, size);
+        let onboarding =
+            onboarding::Onboarding::new(&settings, settings::AppSettings::first_launch());
 
         Self {
+            instance,
             size,
-            surface,
-            device,
             queue,
+            target: Target::Window {
+                surface,
+                swap_chain,
+            },
+            ui_instance,
+            ui_render_pass,
+            scale_factor,
+            window_scale_factor,
+            scene: None,
+            loader: Some(loader),
+            scene_hud: hud::SceneHud::default(),
+            scene_labels: labels::SceneLabels::default(),
+            profiler: profiler::Profiler::new(),
+            font_texture_version: None,
+            uploader: upload::Uploader::new(),
+            resolution: resolution::Controller::new(),
+            rendering_panel: rendering::RenderingPanel::default(),
+            debug_panel: debug::DebugPanel::default(),
+            inspector: inspector::Inspector::default(),
+            measure_panel: measure::MeasurePanel::default(),
+            voxel_panel: voxel::VoxelPanel::default(),
+            lights_panel: lights::LightsPanel::default(),
+            life: life::Life::new(&device, swap_chain_desc.format),
+            boids: boids::Boids::new(&device, swap_chain_desc.format),
+            fractal: fractal::Fractal::new(&device, swap_chain_desc.format),
+            sdf: sdf::SdfPlayground::new(&device, swap_chain_desc.format),
+            material_graph: material_graph::MaterialGraph::new(&device, swap_chain_desc.format),
+            noise_texture: noise_texture::NoiseTexture::new(&device, swap_chain_desc.format),
+            sprites: sprites::SpriteDemo::new(&device, swap_chain_desc.format),
+            scopes: scopes::Scopes::new(&device),
+            eyedropper: eyedropper::Eyedropper::new(&device),
+            frame_compare: wipe::FrameCompare::new(&device, swap_chain_desc.format),
+            color_management: color_management::ColorManagement::new(
+                &device,
+                swap_chain_desc.format,
+            ),
+            benchmark: benchmark::Benchmark::new(&device),
+            settings,
+            onboarding,
+            bookmarks: bookmarks::Bookmarks::load(),
+            bookmarks_panel: bookmarks::BookmarksPanel::default(),
+            turntable: turntable::Turntable::default(),
+            converter: converter::Converter::default(),
+            gallery: gallery::WidgetGallery::default(),
+            duplicates: duplicates::DuplicateFinder::default(),
+            disk_usage: treemap::DiskUsage::load(),
+            image_viewer: image_viewer::ImageViewer::default(),
+            animation: animation::AnimationPlayer::default(),
+            about: about::About::default(),
+            style_editor: style::StyleEditor::default(),
+            fonts_panel: fonts::FontsPanel::default(),
+            charmap: charmap::CharMap::default(),
+            notes: notes::NotesPanel::default(),
+            gamepads: gamepad::GamepadPanel::default(),
+            typing: typing::TypingTest::default(),
+            environment: environment::EnvironmentPanel::default(),
+            plots: plots::PlotsPanel::default(),
+            allocations: allocation::AllocationPanel::default(),
+            dropzone: dropzone::DropZone::default(),
+            network: network::NetworkMonitor::default(),
+            clipboard: clipboard::Clipboard::default(),
+            editor: editor::Editor::default(),
+            tabs: tabs::TabStrip::default(),
+            image_tab: false,
+            snippets: snippets::Snippets::load(),
+            snippets_panel: snippets::SnippetsPanel::default(),
+            markdown: markdown::MarkdownPanel::default(),
+            log: logging::LogPanel::default(),
+            console: console::Console::default(),
+            ime: ime::Ime::default(),
+            touch: touch::TouchInput::default(),
+            zoom: zoom::Zoom::default(),
+            settings_panel: settings::SettingsPanel::default(),
+            keybindings: keybindings::Keybindings::load(),
+            shortcuts: keybindings::ShortcutsPanel::default(),
+            tasks: jobs::TasksPanel::default(),
+            windows: Vec::new(),
+            new_window_requested: false,
+            fullscreen_requested: false,
+            quit_requested: false,
+            confirm_quit: modal::ConfirmQuit::default(),
+            dialogs: dialogs::Dialogs::default(),
+            device_lost: false,
+            system_theme: settings::AppSettings::system_theme(window)
+                .unwrap_or(winit::window::Theme::Dark),
+            modifiers: winit::event::ModifiersState::empty(),
+            device,
             swap_chain_desc,
-            swap_chain,
+        }
+    }
+
+    pub async fn new_headless(size: winit::dpi::PhysicalSize<u32>, scale_factor: f64) -> Self {
+        let Gpu {
+            instance,
+            device,
+            queue,
+            ..
+        } = init_gpu(None).await;
+        let device = std::sync::Arc::new(device);
+
+        let swap_chain_desc = wgpu::SwapChainDescriptor {
+            usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::COPY_SRC,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            width: size.width,
+            height: size.height,
+            present_mode: wgpu::PresentMode::Fifo,
+        };
+
+        let (texture, view) = create_offscreen_target(&device, &swap_chain_desc);
+
+        let ui_instance = ui_instance::Instance::new(size, scale_factor);
+        let ui_render_pass = ui_pass::RenderPass::new(&device, swap_chain_desc.format);
+
+        let mut scene = scene::Scene::new(&device, swap_chain_desc.format, size);
+        scene.populate_demo(&device);
+
+        Self {
+            instance,
+            size,
+            queue,
+            target: Target::Offscreen { texture, view },
             ui_instance,
             ui_render_pass,
             scale_factor,
+            window_scale_factor: scale_factor,
+            scene: Some(scene),
+            loader: None,
+            scene_hud: hud::SceneHud::default(),
+            scene_labels: labels::SceneLabels::default(),
+            profiler: profiler::Profiler::new(),
+            font_texture_version: None,
+            uploader: upload::Uploader::new(),
+            resolution: resolution::Controller::new(),
+            rendering_panel: rendering::RenderingPanel::default(),
+            debug_panel: debug::DebugPanel::default(),
+            inspector: inspector::Inspector::default(),
+            measure_panel: measure::MeasurePanel::default(),
+            voxel_panel: voxel::VoxelPanel::default(),
+            lights_panel: lights::LightsPanel::default(),
+            life: life::Life::new(&device, swap_chain_desc.format),
+            boids: boids::Boids::new(&device, swap_chain_desc.format),
+            fractal: fractal::Fractal::new(&device, swap_chain_desc.format),
+            sdf: sdf::SdfPlayground::new(&device, swap_chain_desc.format),
+            material_graph: material_graph::MaterialGraph::new(&device, swap_chain_desc.format),
+            noise_texture: noise_texture::NoiseTexture::new(&device, swap_chain_desc.format),
+            sprites: sprites::SpriteDemo::new(&device, swap_chain_desc.format),
+            scopes: scopes::Scopes::new(&device),
+            eyedropper: eyedropper::Eyedropper::new(&device),
+            frame_compare: wipe::FrameCompare::new(&device, swap_chain_desc.format),
+            color_management: color_management::ColorManagement::new(
+                &device,
+                swap_chain_desc.format,
+            ),
+            benchmark: benchmark::Benchmark::new(&device),
+            settings: settings::AppSettings::default(),
+            onboarding: onboarding::Onboarding::new(
+                &settings::AppSettings {
+                    setup_complete: true,
+                    ..settings::AppSettings::default()
+                },
+                false,
+            ),
+            bookmarks: bookmarks::Bookmarks::default(),
+            bookmarks_panel: bookmarks::BookmarksPanel::default(),
+            turntable: turntable::Turntable::default(),
+            converter: converter::Converter::default(),
+            gallery: gallery::WidgetGallery::default(),
+            duplicates: duplicates::DuplicateFinder::default(),
+            disk_usage: treemap::DiskUsage::default(),
+            image_viewer: image_viewer::ImageViewer::default(),
+            animation: animation::AnimationPlayer::default(),
+            about: about::About::default(),
+            style_editor: style::StyleEditor::default(),
+            fonts_panel: fonts::FontsPanel::default(),
+            charmap: charmap::CharMap::default(),
+            notes: notes::NotesPanel::default(),
+            gamepads: gamepad::GamepadPanel::default(),
+            typing: typing::TypingTest::default(),
+            environment: environment::EnvironmentPanel::default(),
+            plots: plots::PlotsPanel::default(),
+            allocations: allocation::AllocationPanel::default(),
+            dropzone: dropzone::DropZone::default(),
+            network: network::NetworkMonitor::default(),
+            clipboard: clipboard::Clipboard::default(),
+            editor: editor::Editor::default(),
+            tabs: tabs::TabStrip::default(),
+            image_tab: false,
+            snippets: snippets::Snippets::default(),
+            snippets_panel: snippets::SnippetsPanel::default(),
+            markdown: markdown::MarkdownPanel::default(),
+            log: logging::LogPanel::default(),
+            console: console::Console::default(),
+            ime: ime::Ime::default(),
+            touch: touch::TouchInput::default(),
+            zoom: zoom::Zoom::default(),
+            settings_panel: settings::SettingsPanel::default(),
+            keybindings: keybindings::Keybindings::default(),
+            shortcuts: keybindings::ShortcutsPanel::default(),
+            tasks: jobs::TasksPanel::default(),
+            windows: Vec::new(),
+            new_window_requested: false,
+            fullscreen_requested: false,
+            quit_requested: false,
+            confirm_quit: modal::ConfirmQuit::default(),
+            dialogs: dialogs::Dialogs::default(),
+            device_lost: false,
+            system_theme: winit::window::Theme::Dark,
+            modifiers: winit::event::ModifiersState::empty(),
+            device,
+            swap_chain_desc,
         }
     }
 
@@ -69,9 +494,25 @@ impl Engine {
         self.size.clone_from(new_size);
         self.swap_chain_desc.width = self.size.width;
         self.swap_chain_desc.height = self.size.height;
-        self.swap_chain = self
-            .device
-            .create_swap_chain(&self.surface, &self.swap_chain_desc);
+        match &mut self.target {
+            Target::Window {
+                surface,
+                swap_chain,
+            } => {
+                *swap_chain = self
+                    .device
+                    .create_swap_chain(surface, &self.swap_chain_desc);
+            }
+            Target::Offscreen { texture, view } => {
+                let (new_texture, new_view) =
+                    create_offscreen_target(&self.device, &self.swap_chain_desc);
+                *texture = new_texture;
+                *view = new_view;
+            }
+        }
+        if let Some(scene) = &mut self.scene {
+            scene.resize(&self.device, self.size);
+        }
         log::info!(
             "swap chain resized to {}, {}",
             self.size.width,
@@ -80,44 +521,204 @@ impl Engine {
     }
 
     fn input(&mut self, event: &winit::event::WindowEvent) {
+        if console::Console::is_toggle(event)
+            && !self.editor.focused()
+            && (self.console.open || !self.ui_instance.context().wants_keyboard_input())
+        {
+            self.console.open = !self.console.open;
+            return;
+        }
+        if self
+            .zoom
+            .input(event, self.modifiers, &mut self.settings.ui_scale)
+        {
+            for event in self.touch.cancel() {
+                self.input(&event);
+            }
+            return;
+        }
         self.ui_instance.input(event);
         match event {
             winit::event::WindowEvent::Resized(new_inner_size) => {
                 self.resize(new_inner_size);
             }
             winit::event::WindowEvent::Moved(_) => {}
-            winit::event::WindowEvent::CloseRequested => {}
+            winit::event::WindowEvent::CloseRequested => self.confirm_quit.request(),
             winit::event::WindowEvent::Destroyed => {}
-            winit::event::WindowEvent::DroppedFile(_) => {}
-            winit::event::WindowEvent::HoveredFile(_) => {}
-            winit::event::WindowEvent::HoveredFileCancelled => {}
-            winit::event::WindowEvent::ReceivedCharacter(_) => {}
+            winit::event::WindowEvent::DroppedFile(path) => match self.dropzone.drop_path(path) {
+                dropzone::Destination::Animation => self.animation.open_path(path),
+                dropzone::Destination::Folder => self.converter.drop_path(path),
+                dropzone::Destination::Font => self.fonts_panel.drop_path(path),
+                dropzone::Destination::Image => self.image_viewer.open_path(path),
+                dropzone::Destination::Text => self.editor.open_path(path),
+                dropzone::Destination::Model | dropzone::Destination::Unknown => {}
+            },
+            winit::event::WindowEvent::HoveredFile(path) => {
+                self.dropzone.hover(path);
+            }
+            winit::event::WindowEvent::HoveredFileCancelled => {
+                self.dropzone.cancel();
+            }
+            winit::event::WindowEvent::ReceivedCharacter(character) => {
+                if self.editor.focused() {
+                    self.editor.received_character(*character);
+                } else if !self.ui_instance.context().wants_keyboard_input() {
+                    self.typing.received_character(*character);
+                }
+            }
             winit::event::WindowEvent::Focused(_) => {}
             winit::event::WindowEvent::KeyboardInput {
                 device_id,
                 input,
                 is_synthetic,
-            } => {}
-            winit::event::WindowEvent::ModifiersChanged(_) => {}
+            } => {
+                if self.shortcuts.capturing() {
+                    self.shortcuts
+                        .capture(input, self.modifiers, &mut self.keybindings);
+                    return;
+                }
+                if self.confirm_quit.key_input(input) || self.eyedropper.key_input(input) {
+                    return;
+                }
+                if menu::key_input(input) {
+                    return;
+                }
+                if let Some(step) = tabs::key_input(input, self.modifiers) {
+                    self.cycle_documents(step);
+                    return;
+                }
+                if input.state == winit::event::ElementState::Released
+                    || !(self.editor.focused()
+                        || self.console.open
+                        || self.ui_instance.context().wants_keyboard_input())
+                {
+                    self.typing.key_input(input);
+                }
+                if self.editor.focused() {
+                    if self.clipboard.is_paste(input, self.modifiers) {
+                        if let Some(text) = self.clipboard.text() {
+                            self.editor.paste(&text);
+                        }
+                    } else {
+                        self.editor.key_input(input, self.modifiers);
+                    }
+                    return;
+                }
+                let console_open = self.console.open;
+                if console_open {
+                    self.console.key_input(input);
+                }
+                let wants_keyboard_input = self.ui_instance.context().wants_keyboard_input();
+                if !wants_keyboard_input && !console_open && focus::key_input(input, self.modifiers)
+                {
+                    return;
+                }
+                if !wants_keyboard_input
+                    && !console_open
+                    && self.image_viewer.key_input(input, self.modifiers)
+                {
+                    return;
+                }
+                if let Some(action) = self
+                    .keybindings
+                    .action(input, self.modifiers)
+                    .filter(|_| !wants_keyboard_input && !console_open)
+                {
+                    self.run_action(action);
+                }
+                if wants_keyboard_input && self.clipboard.is_paste(input, self.modifiers) {
+                    if let Some(text) = self.clipboard.text() {
+                        for character in text.chars().filter(|character| *character != '\r') {
+                            self.ui_instance
+                                .input(&winit::event::WindowEvent::ReceivedCharacter(character));
+                        }
+                    }
+                }
+                let index = input
+                    .virtual_keycode
+                    .and_then(bookmarks::key_index)
+                    .filter(|_| input.state == winit::event::ElementState::Pressed)
+                    .filter(|_| !wants_keyboard_input);
+                if let (Some(index), Some(scene)) = (index, &self.scene) {
+                    self.bookmarks.fly_to(index, &scene.camera);
+                }
+            }
+            winit::event::WindowEvent::ModifiersChanged(modifiers) => {
+                self.modifiers = *modifiers;
+            }
             winit::event::WindowEvent::CursorMoved {
                 device_id,
                 position,
                 ..
-            } => {}
+            } => {
+                if let Some(scene) = &mut self.scene {
+                    scene.picker.set_cursor(Some(*position));
+                }
+                self.eyedropper.set_cursor(Some(*position));
+                self.life.set_cursor(Some(*position));
+                self.fractal.set_cursor(Some(*position));
+                self.sdf.set_cursor(Some(*position));
+            }
             winit::event::WindowEvent::CursorEntered { device_id } => {}
-            winit::event::WindowEvent::CursorLeft { device_id } => {}
+            winit::event::WindowEvent::CursorLeft { device_id } => {
+                if let Some(scene) = &mut self.scene {
+                    scene.picker.set_cursor(None);
+                }
+                self.eyedropper.set_cursor(None);
+                self.life.set_cursor(None);
+                self.fractal.set_cursor(None);
+                self.sdf.set_cursor(None);
+            }
             winit::event::WindowEvent::MouseWheel {
                 device_id,
                 delta,
                 phase,
                 ..
-            } => {}
+            } => {
+                self.fractal.mouse_wheel(*delta);
+            }
             winit::event::WindowEvent::MouseInput {
                 device_id,
-                state,
-                button,
+                state: winit::event::ElementState::Pressed,
+                button: winit::event::MouseButton::Left,
                 ..
-            } => {}
+            } => {
+                focus::clear();
+                self.life.mouse_input(
+                    winit::event::ElementState::Pressed,
+                    winit::event::MouseButton::Left,
+                );
+                self.fractal.mouse_input(
+                    winit::event::ElementState::Pressed,
+                    winit::event::MouseButton::Left,
+                );
+                let wants_mouse_input = self.ui_instance.context().wants_mouse_input();
+                if let Some(scene) = self.scene.as_mut().filter(|_| !wants_mouse_input) {
+                    if self.eyedropper.is_active() {
+                        self.eyedropper.pick();
+                    } else if scene.measurements.tool != measure::Tool::Off {
+                        scene.measure_at_cursor();
+                    } else {
+                        scene.picker.select_hovered();
+                        if scene.picker.selected.is_some() {
+                            self.inspector.open = true;
+                        }
+                    }
+                }
+            }
+            winit::event::WindowEvent::MouseInput { state, button, .. } => {
+                if *state == winit::event::ElementState::Pressed
+                    && *button == winit::event::MouseButton::Right
+                {
+                    menu::secondary_click();
+                }
+                if *state == winit::event::ElementState::Pressed {
+                    self.disk_usage.mouse_input(*button);
+                }
+                self.life.mouse_input(*state, *button);
+                self.fractal.mouse_input(*state, *button);
+                self.sdf.mouse_input(*state, *button);
+            }
             winit::event::WindowEvent::TouchpadPressure {
                 device_id,
                 pressure,
@@ -128,88 +729,1037 @@ impl Engine {
                 axis,
                 value,
             } => {}
-            winit::event::WindowEvent::Touch(_) => {}
+            winit::event::WindowEvent::Touch(touch) => {
+                self.notes.touch(touch);
+                for event in self.touch.translate(touch) {
+                    self.input(&event);
+                }
+            }
             winit::event::WindowEvent::ScaleFactorChanged {
                 scale_factor,
                 new_inner_size,
-            } => {}
-            winit::event::WindowEvent::ThemeChanged(_) => {}
+            } => {
+                self.window_scale_factor = *scale_factor;
+                self.scale_factor = *scale_factor;
+            }
+            winit::event::WindowEvent::ThemeChanged(theme) => {
+                self.system_theme = theme.clone();
+            }
         }
     }
 
     fn draw_ui(&mut self) {
         self.ui_instance.begin_frame();
-        egui::CentralPanel::default().show(self.ui_instance.context(), |ui| {
-            ui.button("1234567890");
-            ui.button("numerous");
-            ui.button("1234567890");
-        });
-        egui::Window::new("hello").show(self.ui_instance.context(), |ui| {
-            if ui.button("fuckyou").clicked {
-                println!("this");
+        let ctx = self.ui_instance.context().clone();
+        if let Some(loader) = &self.loader {
+            loader.show(&ctx);
+            self.ui_instance.end_frame();
+            return;
+        }
+        self.style_editor
+            .set_dark_mode(&ctx, self.settings.dark_mode(self.system_theme.clone()));
+        let screen = egui::vec2(
+            self.size.width as f32 / self.scale_factor as f32,
+            self.size.height as f32 / self.scale_factor as f32,
+        );
+        dock::begin_frame(screen);
+        if let Some(action) = self.show_menu_bar(&ctx) {
+            self.run_action(action);
+        }
+        let (documents, active) = self.documents();
+        if let Some(event) = self.tabs.show(&ctx, dock::central(), &documents, active) {
+            self.document_event(event);
+        }
+        if let Some(scene) = &mut self.scene {
+            self.scene_labels.show(&ctx, scene);
+            self.scene_hud.show(&ctx, scene);
+            self.rendering_panel.show(&ctx, scene);
+            self.debug_panel.show(&ctx, scene);
+            self.lights_panel.show(&ctx, scene);
+            self.inspector.show(&ctx, scene);
+            self.measure_panel.show(&ctx, scene);
+            self.voxel_panel.show(&ctx, scene);
+            self.bookmarks_panel.show(&ctx, &mut self.bookmarks, scene);
+            self.turntable.show(&ctx, scene);
+        }
+        self.settings_panel.show(&ctx, &mut self.settings);
+        if std::mem::take(&mut self.settings_panel.shortcuts_requested) {
+            self.shortcuts.open = true;
+        }
+        self.shortcuts.show(&ctx, &mut self.keybindings);
+        self.tasks.show(&ctx);
+        if std::mem::take(&mut self.settings_panel.save_as_requested) {
+            self.dialogs.save(
+                dialogs::Purpose::SaveSettingsAs,
+                "Settings",
+                &dialogs::JSON_EXTENSIONS,
+            );
+        }
+        if std::mem::take(&mut self.settings_panel.load_requested) {
+            self.dialogs.open(
+                dialogs::Purpose::LoadSettings,
+                "Settings",
+                &dialogs::JSON_EXTENSIONS,
+            );
+        }
+        if std::mem::take(&mut self.image_viewer.save_as_requested) {
+            self.dialogs.save(
+                dialogs::Purpose::SaveImageAs,
+                "Images",
+                &dialogs::IMAGE_EXTENSIONS,
+            );
+        }
+        if std::mem::take(&mut self.converter.browse_requested) {
+            self.dialogs.open(
+                dialogs::Purpose::OpenImage,
+                "Images",
+                &dialogs::IMAGE_EXTENSIONS,
+            );
+        }
+        if self.settings.present_mode() != self.swap_chain_desc.present_mode {
+            self.swap_chain_desc.present_mode = self.settings.present_mode();
+            let size = self.size;
+            self.resize(&size);
+        }
+        self.life.show(&ctx);
+        self.boids.show(&ctx);
+        self.fractal.show(&ctx);
+        self.sdf.show(&ctx);
+        self.material_graph.show(&ctx);
+        self.noise_texture.show(&ctx);
+        self.sprites.show(&ctx);
+        self.benchmark.show(&ctx);
+        self.converter.show(&ctx);
+        self.gallery.show(&ctx);
+        self.duplicates.show(&ctx);
+        self.disk_usage.show(&ctx);
+        if let Some(path) = self.disk_usage.take_open_request() {
+            self.image_viewer.open_path(&path);
+        }
+        self.image_viewer.show(&ctx);
+        self.animation.show(&ctx);
+        self.scopes
+            .show(&ctx, [self.image_viewer.has_image(), self.scene.is_some()]);
+        self.eyedropper.show(&ctx, self.scene.is_some());
+        self.frame_compare.show(&ctx);
+        self.color_management.show(&ctx, &mut self.settings);
+        self.about.show(&ctx);
+        self.style_editor.show(&ctx);
+        self.fonts_panel.show(&ctx);
+        self.charmap.show(&ctx, &self.fonts_panel);
+        self.notes.show(&ctx);
+        self.gamepads.show(&ctx);
+        self.typing.show(&ctx);
+        self.markdown.show(&ctx);
+        self.log.show(&ctx);
+        self.editor.show(&ctx, self.modifiers);
+        self.ime.update(&ctx, self.editor.caret());
+        if std::mem::take(&mut self.editor.paste_requested) {
+            if let Some(text) = self.clipboard.text() {
+                self.editor.paste(&text);
             }
-        });
+        }
+        if std::mem::take(&mut self.editor.snippet_requested) {
+            self.snippets_panel.quick_insert();
+        }
+        self.snippets_panel.show(&ctx, &mut self.snippets);
+        if let Some(body) = self.snippets_panel.take_insert() {
+            self.editor.paste(&body);
+            self.editor.open = true;
+        }
+        if std::mem::take(&mut self.snippets_panel.capture_requested) {
+            let selection = self
+                .editor
+                .active_document()
+                .map(|document| document.selected_text().to_owned())
+                .unwrap_or_default();
+            let index = self.snippets.add(&selection);
+            self.snippets_panel.select(&self.snippets, index);
+        }
+        self.environment.show(&ctx);
+        self.plots.show(&ctx, self.profiler.recent_frame_time(1));
+        self.allocations.show(&ctx);
+        self.dropzone.show(&ctx, screen);
+        self.network.show(&ctx, screen);
+        self.console.show(&ctx, screen);
+        if let Some(line) = self.console.take_command() {
+            console::execute(self, &line);
+        }
+        self.profiler.show(
+            &ctx,
+            &self.ui_render_pass.buffer_stats,
+            self.uploader.bytes_last_frame,
+            &mut self.resolution,
+        );
+        let tool = self
+            .scene
+            .as_ref()
+            .and_then(|scene| match scene.measurements.tool {
+                measure::Tool::Off => None,
+                measure::Tool::Distance => Some("Measure distance"),
+                measure::Tool::Angle => Some("Measure angle"),
+            });
+        if tool.is_some() && !ctx.wants_mouse_input() {
+            status::hint("Click in the scene to place a measurement point");
+        }
+        let zoom = &self.zoom;
+        let ui_scale = &mut self.settings.ui_scale;
+        status::show(
+            &ctx,
+            screen,
+            self.profiler.recent_frame_time(30),
+            tool,
+            |ui| {
+                zoom.show(ui, ui_scale);
+                let running = jobs::running();
+                if running > 0 {
+                    ui.label("|");
+                    ui.label(format!("{} task(s) running", running));
+                }
+            },
+        );
+        notify::show(&ctx, screen);
+        dock::end_frame(&ctx);
+        let unsaved: Vec<String> = self
+            .editor
+            .documents()
+            .iter()
+            .filter(|document| document.dirty())
+            .map(|document| document.name.clone())
+            .collect();
+        self.onboarding.show(&ctx, screen, &mut self.settings);
+        self.confirm_quit.show(&ctx, screen, &unsaved);
+        if self.confirm_quit.take_confirmed() {
+            self.quit_requested = true;
+        }
 
+        let copied = std::mem::take(&mut ctx.output().copied_text);
+        self.clipboard.set_text(copied);
+        menu::end_frame();
+        focus::end_frame();
         self.ui_instance.end_frame();
     }
 
+    fn apply_ui_scale(&mut self) {
+        self.settings.ui_scale = zoom::clamp(self.settings.ui_scale);
+        let scale_factor = self.window_scale_factor * self.settings.ui_scale as f64;
+        if (scale_factor - self.scale_factor).abs() < 1e-6 {
+            return;
+        }
+        self.scale_factor = scale_factor;
+        let mut size = self.size;
+        self.ui_instance
+            .input(&winit::event::WindowEvent::ScaleFactorChanged {
+                scale_factor,
+                new_inner_size: &mut size,
+            });
+    }
+
     fn update(&mut self) {
+        self.profiler.begin_frame();
+        if !self.zoom.pinching() {
+            for event in self.touch.poll() {
+                self.input(&event);
+            }
+        }
+        self.apply_ui_scale();
+        self.allocations.end_frame();
+        let scope = allocation::scope(allocation::Tag::Scene);
+        if let Some(mut scene) = self.loader.as_mut().and_then(|loader| loader.poll()) {
+            scene.resize(&self.device, self.size);
+            self.scene = Some(scene);
+            self.loader = None;
+        }
+        let render_scale = self.resolution.update(self.profiler.recent_frame_time(10));
+        self.scopes.poll(&self.device);
+        self.eyedropper.poll(&self.device);
+        if let Some(scene) = &mut self.scene {
+            scene.picker.poll(&self.device);
+            if self.turntable.start_requested {
+                self.turntable
+                    .start(&self.device, scene, self.swap_chain_desc.format);
+            }
+            if self.turntable.cancel_requested {
+                self.turntable.cancel(&self.device, scene, self.size);
+            }
+            if self.turntable.is_running() {
+                self.turntable.step(
+                    &self.device,
+                    &self.queue,
+                    &mut self.uploader,
+                    scene,
+                    self.size,
+                    self.settings.clear_color(),
+                );
+            } else {
+                self.bookmarks.update(&mut scene.camera);
+                scene.set_render_scale(&self.device, render_scale);
+            }
+        }
+        if self.noise_texture.export_requested {
+            self.noise_texture
+                .export(&self.device, &self.queue, &mut self.uploader);
+        }
+        if self.benchmark.running() {
+            self.benchmark.step(&self.device, &self.queue);
+        }
+        drop(scope);
+        let _scope = allocation::scope(allocation::Tag::Ui);
         self.ui_instance.update_time();
         self.draw_ui();
-        self.ui_render_pass.upload_buffers(
-            &mut self.device,
-            &mut self.queue,
-            egui::Vec2::new(self.size.width as f32, self.size.height as f32),
-            self.ui_instance.paint_jobs(),
-        );
-        self.ui_render_pass.upload_texture(
-            &self.device,
-            &self.queue,
-            self.ui_instance.context().texture(),
-        );
+        let texture = self.ui_instance.context().texture();
+        if self.font_texture_version == Some(texture.version) {
+            self.profiler.font_uploads_skipped += 1;
+        } else {
+            self.ui_render_pass
+                .upload_texture(&self.device, &self.queue, &texture);
+            self.font_texture_version = Some(texture.version);
+            self.profiler.font_uploads += 1;
+        }
     }
 
     fn render(&mut self) {
-        let frame = self.swap_chain.get_current_frame().unwrap().output;
+        let _scope = allocation::scope(allocation::Tag::Render);
+        let frame = match &mut self.target {
+            Target::Window {
+                surface,
+                swap_chain,
+            } => match swap_chain.get_current_frame() {
+                Ok(frame) => Some(frame.output),
+                Err(wgpu::SwapChainError::Outdated) | Err(wgpu::SwapChainError::Lost) => {
+                    log::warn!("swap chain lost, recreating");
+                    *swap_chain = self
+                        .device
+                        .create_swap_chain(surface, &self.swap_chain_desc);
+                    return;
+                }
+                Err(wgpu::SwapChainError::Timeout) => return,
+                Err(wgpu::SwapChainError::OutOfMemory) => {
+                    log::warn!("device lost");
+                    self.device_lost = true;
+                    return;
+                }
+            },
+            Target::Offscreen { .. } => None,
+        };
+        self.color_management
+            .prepare(&self.device, self.size, &self.settings);
+        let output = match (&frame, &self.target) {
+            (Some(frame), _) => &frame.view,
+            (None, Target::Offscreen { view, .. }) => view,
+            (None, Target::Window { .. }) => unreachable!(),
+        };
+
         let mut encoder = self
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
                 label: Some("Main Encoder"),
             });
-        self.ui_render_pass
-            .encode(&mut encoder, &frame.view, Some(wgpu::Color::BLUE));
+        self.color_management.render_pattern(
+            &self.device,
+            &mut encoder,
+            &mut self.ui_render_pass,
+            self.size,
+            self.scale_factor,
+        );
+        let view = self.color_management.target().unwrap_or(output);
+        let clear_color = self.settings.clear_color();
+        if let Some(scene) = &mut self.scene {
+            scene.render(
+                &self.device,
+                &mut self.uploader,
+                &mut encoder,
+                view,
+                clear_color,
+            );
+            self.frame_compare.update(&self.device, &mut encoder, scene);
+            self.eyedropper.sample(&mut encoder, scene, self.size);
+        }
+        self.image_viewer
+            .prepare(&self.device, &self.queue, self.swap_chain_desc.format);
+        match self.scopes.wants_input() {
+            Some(scopes::Source::Image) => {
+                if let Some((revision, image)) = self.image_viewer.analysis() {
+                    self.scopes.dispatch(
+                        &self.device,
+                        &self.queue,
+                        &mut self.uploader,
+                        &mut encoder,
+                        scopes::Input::Image(revision, image),
+                    );
+                }
+            }
+            Some(scopes::Source::Frame) => {
+                if let Some(scene) = &self.scene {
+                    let (color_view, size) = scene.color_target();
+                    self.scopes.dispatch(
+                        &self.device,
+                        &self.queue,
+                        &mut self.uploader,
+                        &mut encoder,
+                        scopes::Input::Frame(color_view, size),
+                    );
+                }
+            }
+            None => {}
+        }
+        self.life.render(
+            &self.device,
+            &mut self.uploader,
+            &mut encoder,
+            &mut self.ui_render_pass,
+            self.size,
+            self.scale_factor,
+        );
+        self.boids.render(
+            &self.device,
+            &mut self.uploader,
+            &mut encoder,
+            &mut self.ui_render_pass,
+            self.size,
+            self.scale_factor,
+        );
+        self.fractal.render(
+            &self.device,
+            &mut self.uploader,
+            &mut encoder,
+            &mut self.ui_render_pass,
+            self.size,
+            self.scale_factor,
+        );
+        self.sdf.render(
+            &self.device,
+            &mut self.uploader,
+            &mut encoder,
+            &mut self.ui_render_pass,
+            self.size,
+            self.scale_factor,
+        );
+        self.material_graph.render(
+            &self.device,
+            &mut self.uploader,
+            &mut encoder,
+            &mut self.ui_render_pass,
+            self.size,
+            self.scale_factor,
+        );
+        self.noise_texture.render(
+            &self.device,
+            &mut self.uploader,
+            &mut encoder,
+            &mut self.ui_render_pass,
+            self.size,
+            self.scale_factor,
+        );
+        self.sprites.render(
+            &self.device,
+            &mut self.uploader,
+            &mut encoder,
+            &mut self.ui_render_pass,
+            self.size,
+            self.scale_factor,
+        );
+        self.image_viewer.render(
+            &self.device,
+            &mut self.uploader,
+            &mut encoder,
+            &mut self.ui_render_pass,
+            self.size,
+            self.scale_factor,
+        );
+        self.frame_compare.render(
+            &self.device,
+            &mut self.uploader,
+            &mut encoder,
+            &mut self.ui_render_pass,
+            self.size,
+            self.scale_factor,
+        );
+        self.image_viewer
+            .upload(&self.device, &self.queue, &mut self.ui_render_pass);
+        self.animation
+            .upload(&self.device, &self.queue, &mut self.ui_render_pass);
+        self.duplicates
+            .upload(&self.device, &self.queue, &mut self.ui_render_pass);
+        self.gallery.upload(
+            &self.device,
+            &self.queue,
+            &mut self.ui_render_pass,
+            self.ui_instance.context().pixels_per_point(),
+        );
+        self.ui_render_pass.set_filtering(
+            &self.device,
+            self.settings.ui_filter_mode(self.scale_factor as f32),
+            self.settings.pixel_snap,
+        );
+        self.ui_render_pass.upload_buffers(
+            &self.device,
+            &mut self.uploader,
+            &mut encoder,
+            egui::Vec2::new(self.size.width as f32, self.size.height as f32),
+            self.scale_factor as f32,
+            self.ui_instance.paint_jobs(),
+        );
+        let ui_clear = match self.scene {
+            Some(_) => None,
+            None => Some(clear_color),
+        };
+        self.ui_render_pass.encode(&mut encoder, view, ui_clear);
+        self.color_management
+            .apply(&self.device, &mut self.uploader, &mut encoder, output);
+
+        self.uploader.finish();
         self.queue.submit(std::iter::once(encoder.finish()));
+        self.uploader.recall();
+        if let Some(scene) = &mut self.scene {
+            scene.picker.after_submit();
+        }
+        self.scopes.after_submit();
+        self.eyedropper.after_submit();
+    }
+
+    fn recover(&mut self, window: Option<&winit::window::Window>) {
+        log::warn!("re-initializing GPU resources");
+        notify::warning("The GPU device was lost, recreating resources");
+        let Gpu {
+            instance,
+            surface,
+            device,
+            queue,
+        } = futures::executor::block_on(init_gpu(window));
+        let device = std::sync::Arc::new(device);
+
+        self.target = match surface {
+            Some(surface) => {
+                let swap_chain = device.create_swap_chain(&surface, &self.swap_chain_desc);
+                Target::Window {
+                    surface,
+                    swap_chain,
+                }
+            }
+            None => {
+                let (texture, view) = create_offscreen_target(&device, &self.swap_chain_desc);
+                Target::Offscreen { texture, view }
+            }
+        };
+        self.ui_render_pass = ui_pass::RenderPass::new(&device, self.swap_chain_desc.format);
+        self.font_texture_version = None;
+        self.uploader = upload::Uploader::new();
+        self.life
+            .recreate_gpu_resources(&device, self.swap_chain_desc.format);
+        self.boids
+            .recreate_gpu_resources(&device, self.swap_chain_desc.format);
+        self.fractal
+            .recreate_gpu_resources(&device, self.swap_chain_desc.format);
+        self.sdf
+            .recreate_gpu_resources(&device, self.swap_chain_desc.format);
+        self.material_graph
+            .recreate_gpu_resources(&device, self.swap_chain_desc.format);
+        self.noise_texture
+            .recreate_gpu_resources(&device, self.swap_chain_desc.format);
+        self.sprites
+            .recreate_gpu_resources(&device, self.swap_chain_desc.format);
+        self.benchmark.recreate_gpu_resources(&device);
+        self.scopes.recreate_gpu_resources(&device);
+        self.eyedropper.recreate_gpu_resources(&device);
+        self.frame_compare
+            .recreate_gpu_resources(&device, self.swap_chain_desc.format);
+        self.color_management
+            .recreate_gpu_resources(&device, self.swap_chain_desc.format);
+        self.image_viewer.recreate_gpu_resources();
+        self.animation.recreate_gpu_resources();
+        self.duplicates.recreate_gpu_resources();
+        self.gallery.recreate_gpu_resources();
+        match &mut self.scene {
+            Some(scene) => {
+                scene.recreate_gpu_resources(&device);
+                self.turntable.cancel(&device, scene, self.size);
+            }
+            None => {
+                self.loader = Some(loading::Loader::start(
+                    device.clone(),
+                    self.swap_chain_desc.format,
+                    self.size,
+                ));
+            }
+        }
+        for window in self.windows.iter_mut() {
+            window.recreate_gpu_resources(&instance, &device);
+        }
+
+        self.instance = instance;
+        self.device = device;
+        self.queue = queue;
+        self.device_lost = false;
+        log::info!("GPU re-initialized");
+    }
+
+    fn open_window(&mut self, window: winit::window::Window) {
+        let title = format!("Window {}", self.windows.len() + 1);
+        log::info!("opening {}", title);
+        self.windows.push(windows::SecondaryWindow::new(
+            &self.instance,
+            &self.device,
+            window,
+            title,
+        ));
+    }
+
+    fn panels(&mut self) -> Vec<(&'static str, Vec<(&'static str, &mut bool)>)> {
+        vec![
+            (
+                "Scene",
+                vec![
+                    ("Rendering", &mut self.rendering_panel.open),
+                    ("Lights", &mut self.lights_panel.open),
+                    ("Debug", &mut self.debug_panel.open),
+                    ("Inspector", &mut self.inspector.open),
+                    ("Measure", &mut self.measure_panel.open),
+                    ("Voxel terrain", &mut self.voxel_panel.open),
+                    ("Bookmarks", &mut self.bookmarks_panel.open),
+                    ("Turntable", &mut self.turntable.open),
+                    ("Frame compare", &mut self.frame_compare.open),
+                    ("Color management", &mut self.color_management.open),
+                    ("Scene labels", &mut self.scene_labels.enabled),
+                ],
+            ),
+            (
+                "Demos",
+                vec![
+                    ("Game of Life", &mut self.life.open),
+                    ("Boids", &mut self.boids.open),
+                    ("Fractal", &mut self.fractal.open),
+                    ("SDF playground", &mut self.sdf.open),
+                    ("Material graph", &mut self.material_graph.open),
+                    ("Noise texture", &mut self.noise_texture.open),
+                    ("Sprites", &mut self.sprites.open),
+                    ("GPU benchmark", &mut self.benchmark.open),
+                    ("Widget gallery", &mut self.gallery.open),
+                    ("Typing test", &mut self.typing.open),
+                ],
+            ),
+            (
+                "Tools",
+                vec![
+                    ("Image viewer", &mut self.image_viewer.open),
+                    ("Animation player", &mut self.animation.open),
+                    ("Scopes", &mut self.scopes.open),
+                    ("Eyedropper", &mut self.eyedropper.open),
+                    ("Image converter", &mut self.converter.open),
+                    ("Duplicate finder", &mut self.duplicates.open),
+                    ("Disk usage", &mut self.disk_usage.open),
+                    ("Environment", &mut self.environment.open),
+                    ("Network", &mut self.network.open),
+                ],
+            ),
+            (
+                "Text",
+                vec![
+                    ("Code editor", &mut self.editor.open),
+                    ("Snippets", &mut self.snippets_panel.open),
+                    ("Markdown", &mut self.markdown.open),
+                    ("Notes", &mut self.notes.open),
+                    ("Fonts", &mut self.fonts_panel.open),
+                    ("Character map", &mut self.charmap.open),
+                    ("Style", &mut self.style_editor.open),
+                ],
+            ),
+            (
+                "Diagnostics",
+                vec![
+                    ("Profiler", &mut self.profiler.open),
+                    ("Log", &mut self.log.open),
+                    ("Console", &mut self.console.open),
+                    ("Plots", &mut self.plots.open),
+                    ("Allocations", &mut self.allocations.open),
+                    ("Tasks", &mut self.tasks.open),
+                    ("Gamepads", &mut self.gamepads.open),
+                    ("Settings", &mut self.settings_panel.open),
+                    ("Import settings", &mut self.settings_panel.import.open),
+                ],
+            ),
+        ]
+    }
+
+    fn show_menu_bar(
+        &mut self,
+        ctx: &std::sync::Arc<egui::Context>,
+    ) -> Option<keybindings::Action> {
+        let keybindings = &self.keybindings;
+        let label = |action: keybindings::Action, text: &str| match keybindings.chord(action) {
+            Some(chord) => format!("{}    {}", text, chord.label()),
+            None => text.to_owned(),
+        };
+        let labels = [
+            label(keybindings::Action::OpenImage, "Open image..."),
+            label(keybindings::Action::SaveSettings, "Save settings"),
+            label(keybindings::Action::Quit, "Quit"),
+            label(keybindings::Action::Fullscreen, "Fullscreen"),
+            label(keybindings::Action::Screenshot, "Screenshot"),
+        ];
+        let mut action = None;
+        let mut appearance = self.settings.appearance;
+        let mut load_settings = false;
+        let mut save_settings_as = false;
+        let mut new_window = false;
+        let mut device_lost = false;
+        let mut shortcuts = false;
+        let mut onboarding = false;
+        let mut panels = self.panels();
+        egui::TopPanel::top(egui::Id::new("menu_bar")).show(ctx, |ui| {
+            egui::menu::bar(ui, |ui| {
+                egui::menu::menu(ui, "File", |ui| {
+                    if ui.button(&labels[0]).clicked {
+                        action = Some(keybindings::Action::OpenImage);
+                    }
+                    if ui.button("New window").clicked {
+                        new_window = true;
+                    }
+                    ui.separator();
+                    if ui.button(&labels[1]).clicked {
+                        action = Some(keybindings::Action::SaveSettings);
+                    }
+                    if ui.button("Save settings as...").clicked {
+                        save_settings_as = true;
+                    }
+                    if ui.button("Load settings...").clicked {
+                        load_settings = true;
+                    }
+                    ui.separator();
+                    if ui.button(&labels[4]).clicked {
+                        action = Some(keybindings::Action::Screenshot);
+                    }
+                    if ui.button(&labels[2]).clicked {
+                        action = Some(keybindings::Action::Quit);
+                    }
+                });
+                egui::menu::menu(ui, "View", |ui| {
+                    ui.radio_value(
+                        &mut appearance,
+                        settings::Appearance::System,
+                        "Follow system",
+                    );
+                    ui.radio_value(&mut appearance, settings::Appearance::Dark, "Dark");
+                    ui.radio_value(&mut appearance, settings::Appearance::Light, "Light");
+                    if ui.button(&labels[3]).clicked {
+                        action = Some(keybindings::Action::Fullscreen);
+                    }
+                    if ui.button("Reset panel layout").clicked {
+                        dock::reset();
+                    }
+                    egui::ScrollArea::from_max_height(420.0).show(ui, |ui| {
+                        for (group, panels) in panels.iter_mut() {
+                            ui.separator();
+                            ui.add(
+                                egui::Label::new(*group)
+                                    .text_color(egui::Srgba::new(150, 150, 160, 255)),
+                            );
+                            for (name, open) in panels.iter_mut() {
+                                ui.checkbox(open, *name);
+                            }
+                        }
+                        if ui.button("Simulate device loss").clicked {
+                            device_lost = true;
+                        }
+                    });
+                });
+                egui::menu::menu(ui, "Help", |ui| {
+                    if ui.button("Keyboard shortcuts").clicked {
+                        shortcuts = true;
+                    }
+                    if ui.button("Replay onboarding").clicked {
+                        onboarding = true;
+                    }
+                    if ui.button("About").clicked {
+                        action = Some(keybindings::Action::About);
+                    }
+                });
+            });
+        });
+        self.settings.appearance = appearance;
+        self.settings_panel.load_requested |= load_settings;
+        self.settings_panel.save_as_requested |= save_settings_as;
+        self.new_window_requested |= new_window;
+        self.device_lost |= device_lost;
+        self.shortcuts.open |= shortcuts;
+        if onboarding {
+            self.onboarding.replay(&self.settings);
+        }
+        action
+    }
+
+    fn documents(&self) -> (Vec<tabs::Tab>, Option<usize>) {
+        let texts = self.editor.documents();
+        let mut documents: Vec<tabs::Tab> = texts
+            .iter()
+            .map(|document| tabs::Tab {
+                title: document.name.clone(),
+                kind: match document.language {
+                    editor::Language::Glsl => tabs::Kind::Shader,
+                    _ => tabs::Kind::Text,
+                },
+                dirty: document.dirty(),
+                closable: texts.len() > 1,
+            })
+            .collect();
+        if let Some(title) = self.image_viewer.title() {
+            documents.push(tabs::Tab {
+                title,
+                kind: tabs::Kind::Image,
+                dirty: false,
+                closable: true,
+            });
+        }
+        let image_active = self.image_viewer.open && (self.image_tab || !self.editor.open);
+        let active = if image_active && documents.len() > texts.len() {
+            Some(texts.len())
+        } else if self.editor.open {
+            Some(self.editor.active_index())
+        } else {
+            None
+        };
+        (documents, active)
+    }
+
+    fn document_event(&mut self, event: tabs::Event) {
+        let texts = self.editor.documents().len();
+        match event {
+            tabs::Event::Select(index) if index < texts => {
+                self.editor.select(index);
+                self.image_tab = false;
+            }
+            tabs::Event::Select(_) => {
+                self.image_viewer.open = true;
+                self.image_tab = true;
+            }
+            tabs::Event::Close(index) if index < texts => self.editor.close_document(index),
+            tabs::Event::Close(_) => {
+                self.image_viewer.close();
+                self.image_tab = false;
+            }
+            tabs::Event::Move(from, to) if from < texts && to < texts => {
+                self.editor.move_document(from, to)
+            }
+            tabs::Event::Move(..) => {}
+        }
+    }
+
+    fn cycle_documents(&mut self, step: isize) {
+        let (documents, active) = self.documents();
+        if documents.is_empty() {
+            return;
+        }
+        let next = match active {
+            Some(active) => tabs::cycle(active, documents.len(), step),
+            None => 0,
+        };
+        self.document_event(tabs::Event::Select(next));
+    }
+
+    fn run_action(&mut self, action: keybindings::Action) {
+        log::info!("shortcut {:?}", action);
+        match action {
+            keybindings::Action::Quit => self.confirm_quit.request(),
+            keybindings::Action::OpenImage => self.dialogs.open(
+                dialogs::Purpose::ViewImage,
+                "Images",
+                &dialogs::IMAGE_EXTENSIONS,
+            ),
+            keybindings::Action::SaveSettings => match self.settings.save() {
+                Ok(()) => notify::info("Saved settings"),
+                Err(err) => {
+                    log::warn!("cannot save settings: {}", err);
+                    notify::error(format!("Cannot save settings: {}", err));
+                }
+            },
+            keybindings::Action::Fullscreen => self.fullscreen_requested = true,
+            keybindings::Action::Screenshot => {
+                let seconds = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map_or(0, |elapsed| elapsed.as_secs());
+                let path = format!("screenshot-{}.png", seconds);
+                match turntable::screenshot(self, &[&path]) {
+                    Ok(message) => {
+                        log::info!("{}", message);
+                        notify::success(message);
+                    }
+                    Err(err) => {
+                        log::warn!("cannot save a screenshot: {}", err);
+                        notify::error(format!("Cannot save a screenshot: {}", err));
+                    }
+                }
+            }
+            keybindings::Action::Console => self.console.open = !self.console.open,
+            keybindings::Action::Settings => self.settings_panel.open = !self.settings_panel.open,
+            keybindings::Action::Profiler => self.profiler.open = !self.profiler.open,
+            keybindings::Action::CodeEditor => self.editor.open = !self.editor.open,
+            keybindings::Action::Log => self.log.open = !self.log.open,
+            keybindings::Action::About => self.about.open = true,
+        }
+    }
+
+    fn user_event(&mut self, event: UserEvent) {
+        match event {
+            UserEvent::FileDialog(response) => {
+                self.dialogs.finish(&response);
+                let path = match &response.path {
+                    Some(path) => path,
+                    None => return,
+                };
+                match response.purpose {
+                    dialogs::Purpose::OpenImage => self.converter.drop_path(path),
+                    dialogs::Purpose::ViewImage => self.image_viewer.open_path(path),
+                    dialogs::Purpose::LoadSettings => {
+                        self.settings_panel.status = match settings::AppSettings::load_from(path) {
+                            Ok(settings) => {
+                                self.settings = settings;
+                                format!("Loaded {}", path.display())
+                            }
+                            Err(err) => format!("Cannot load {}: {}", path.display(), err),
+                        };
+                    }
+                    dialogs::Purpose::SaveImageAs => self.image_viewer.save_as(path),
+                    dialogs::Purpose::SaveSettingsAs => {
+                        self.settings_panel.status = match self.settings.save_to(path) {
+                            Ok(()) => format!("Saved to {}", path.display()),
+                            Err(err) => format!("Cannot save {}: {}", path.display(), err),
+                        };
+                    }
+                }
+            }
+        }
+    }
+
+    fn window_input(
+        &mut self,
+        window_id: winit::window::WindowId,
+        event: &winit::event::WindowEvent,
+    ) {
+        if let Some(window) = self
+            .windows
+            .iter_mut()
+            .find(|window| window.id() == window_id)
+        {
+            window.input(&self.device, event);
+        }
+        self.windows.retain(|window| !window.should_close());
+    }
+
+    fn redraw_window(&mut self, window_id: winit::window::WindowId) {
+        let stats = self
+            .scene
+            .as_ref()
+            .map(|scene| scene.stats)
+            .unwrap_or_default();
+        let clear_color = self.settings.clear_color();
+        if let Some(window) = self
+            .windows
+            .iter_mut()
+            .find(|window| window.id() == window_id)
+        {
+            window.redraw(
+                &self.device,
+                &self.queue,
+                &mut self.uploader,
+                &stats,
+                clear_color,
+            );
+        }
+        self.windows.retain(|window| !window.should_close());
+    }
+
+    fn capture(&self) -> Result<image::RgbaImage, String> {
+        match &self.target {
+            Target::Window { .. } => Err("only offscreen targets can be captured".to_owned()),
+            Target::Offscreen { texture, .. } => {
+                headless::read_texture(&self.device, &self.queue, texture, self.size)
+            }
+        }
     }
 }
 
+fn create_offscreen_target(
+    device: &wgpu::Device,
+    desc: &wgpu::SwapChainDescriptor,
+) -> (wgpu::Texture, wgpu::TextureView) {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Offscreen Target"),
+        size: wgpu::Extent3d {
+            width: desc.width,
+            height: desc.height,
+            depth: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: desc.format,
+        usage: desc.usage,
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    (texture, view)
+}
+
 fn main() {
-    env_logger::builder().format_timestamp(None).init();
+    let log_records = logging::init();
+
+    match headless::Options::from_args(std::env::args()) {
+        Ok(Some(options)) => {
+            if let Err(err) = headless::run(options) {
+                eprintln!("error: {}", err);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Ok(None) => {}
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, headless::USAGE);
+            std::process::exit(2);
+        }
+    }
 
     log::info!("initializing");
     let time = std::time::Instant::now();
 
-    let event_loop = winit::event_loop::EventLoop::new();
+    let event_loop = winit::event_loop::EventLoop::<UserEvent>::with_user_event();
+    let settings = settings::AppSettings::load();
     let window = winit::window::WindowBuilder::new()
-        .with_inner_size(winit::dpi::PhysicalSize::new(800, 600))
+        .with_inner_size(settings.window_size())
         .with_title(env!("CARGO_PKG_NAME"))
         .build(&event_loop)
         .unwrap();
 
-    let mut engine = futures::executor::block_on(Engine::new(&window));
+    let mut engine = futures::executor::block_on(Engine::new(&window, settings));
+    if let Some(monitor) = window.current_monitor() {
+        engine.color_management.detect(&monitor);
+    }
+    engine.dialogs.set_proxy(event_loop.create_proxy());
+    engine.log.set_records(log_records);
 
     log::info!("initialized, took {} ms", time.elapsed().as_millis());
-    drop(time);
 
-    event_loop.run(move |event, _, control_flow| match event {
+    event_loop.run(move |event, event_loop, control_flow| match event {
         winit::event::Event::NewEvents(_) => {}
+        winit::event::Event::WindowEvent { window_id, event } if window_id != window.id() => {
+            engine.window_input(window_id, &event);
+        }
         winit::event::Event::WindowEvent { window_id, event } => {
             engine.input(&event);
             match event {
-                winit::event::WindowEvent::Resized(_) => {}
-                winit::event::WindowEvent::Moved(_) => {}
-                winit::event::WindowEvent::CloseRequested => {
-                    *control_flow = winit::event_loop::ControlFlow::Exit;
+                winit::event::WindowEvent::Resized(size) => {
+                    if engine.settings.smooth_resize && size.width > 0 && size.height > 0 {
+                        engine.update();
+                        engine.render();
+                        if engine.device_lost {
+                            engine.recover(Some(&window));
+                        }
+                    }
                 }
+                winit::event::WindowEvent::Moved(_) => {
+                    if let Some(monitor) = window.current_monitor() {
+                        engine.color_management.detect(&monitor);
+                    }
+                }
+                winit::event::WindowEvent::CloseRequested => {}
                 winit::event::WindowEvent::Destroyed => {}
                 winit::event::WindowEvent::DroppedFile(_) => {}
                 winit::event::WindowEvent::HoveredFile(_) => {}
@@ -220,16 +1770,7 @@ fn main() {
                     device_id,
                     input,
                     is_synthetic,
-                } => match input {
-                    winit::event::KeyboardInput {
-                        virtual_keycode: Some(winit::event::VirtualKeyCode::Escape),
-                        state: winit::event::ElementState::Pressed,
-                        ..
-                    } => {
-                        *control_flow = winit::event_loop::ControlFlow::Exit;
-                    }
-                    _ => {}
-                },
+                } => {}
                 winit::event::WindowEvent::ModifiersChanged(_) => {}
                 winit::event::WindowEvent::CursorMoved {
                     device_id,
@@ -269,17 +1810,64 @@ fn main() {
             }
         }
         winit::event::Event::DeviceEvent { device_id, event } => {}
-        winit::event::Event::UserEvent(_) => {}
+        winit::event::Event::UserEvent(event) => {
+            engine.user_event(event);
+        }
         winit::event::Event::Suspended => {}
         winit::event::Event::Resumed => {}
         winit::event::Event::MainEventsCleared => {
+            if std::mem::take(&mut engine.quit_requested) {
+                *control_flow = winit::event_loop::ControlFlow::Exit;
+                return;
+            }
+            if std::mem::take(&mut engine.new_window_requested) {
+                let new_window = winit::window::WindowBuilder::new()
+                    .with_inner_size(winit::dpi::PhysicalSize::new(480, 360))
+                    .with_title(env!("CARGO_PKG_NAME"))
+                    .build(event_loop)
+                    .unwrap();
+                engine.open_window(new_window);
+            }
+            if std::mem::take(&mut engine.fullscreen_requested) {
+                window.set_fullscreen(match window.fullscreen() {
+                    Some(_) => None,
+                    None => Some(winit::window::Fullscreen::Borderless(
+                        window.current_monitor(),
+                    )),
+                });
+            }
+            if let Some(position) = engine.ime.take_position() {
+                window.set_ime_position(position);
+            }
             window.request_redraw();
+            for secondary in engine.windows.iter() {
+                secondary.window.request_redraw();
+            }
+        }
+        winit::event::Event::RedrawRequested(window_id) if window_id != window.id() => {
+            engine.redraw_window(window_id);
         }
         winit::event::Event::RedrawRequested(_) => {
             engine.update();
             engine.render();
+            if engine.device_lost {
+                engine.recover(Some(&window));
+            }
         }
         winit::event::Event::RedrawEventsCleared => {}
-        winit::event::Event::LoopDestroyed => {}
+        winit::event::Event::LoopDestroyed => {
+            let size = window.inner_size();
+            if size.width > 0 && size.height > 0 {
+                engine.settings.window_size = [size.width, size.height];
+            }
+            if let Err(err) = engine.settings.save() {
+                log::warn!("cannot save settings: {}", err);
+            }
+            persistence::save_memory(engine.ui_instance.context());
+            engine.bookmarks.save();
+            engine.snippets.save();
+            engine.keybindings.save();
+            engine.disk_usage.save();
+        }
     });
 }
@@ -0,0 +1,240 @@
+const SAMPLE: &str = "# Markdown preview
+
+Type on the left, the preview updates on the right.
+
+## Lists
+
+- wgpu 0.6 renderer
+- egui 0.3 widgets
+  - nested items
+1. ordered
+2. lists
+
+## Code
+
+```rust
+fn main() {
+    println!(\"hello\");
+}
+```
+
+> Block quotes are indented.
+
+---
+
+Links open in the browser: [egui](https://github.com/emilk/egui).
+";
+const QUOTE_COLOR: egui::Srgba = egui::Srgba::new(150, 150, 160, 255);
+
+struct Renderer {
+    text: String,
+    heading: Option<u32>,
+    quote: usize,
+    lists: Vec<Option<u64>>,
+    link: Option<String>,
+}
+
+impl Renderer {
+    fn indent(&self) -> String {
+        let depth = self.lists.len().saturating_sub(1);
+        format!("{}{}", "    ".repeat(depth), "| ".repeat(self.quote))
+    }
+
+    fn flush(&mut self, ui: &mut egui::Ui) {
+        if self.text.trim().is_empty() {
+            self.text.clear();
+            return;
+        }
+        let text = format!("{}{}", self.indent(), std::mem::take(&mut self.text));
+        let label = match self.heading {
+            Some(1) | Some(2) => egui::Label::new(text).text_style(egui::TextStyle::Heading),
+            Some(_) => egui::Label::new(text)
+                .text_style(egui::TextStyle::Button)
+                .text_color(egui::Srgba::new(255, 255, 255, 255)),
+            None if self.quote > 0 => egui::Label::new(text).text_color(QUOTE_COLOR),
+            None => egui::Label::new(text),
+        };
+        ui.add(label);
+    }
+
+    fn start(&mut self, ui: &mut egui::Ui, tag: pulldown_cmark::Tag) {
+        match tag {
+            pulldown_cmark::Tag::Heading(level) => {
+                self.flush(ui);
+                self.heading = Some(level);
+            }
+            pulldown_cmark::Tag::BlockQuote => {
+                self.flush(ui);
+                self.quote += 1;
+            }
+            pulldown_cmark::Tag::CodeBlock(_) => self.flush(ui),
+            pulldown_cmark::Tag::List(first) => {
+                self.flush(ui);
+                self.lists.push(first);
+            }
+            pulldown_cmark::Tag::Item => {
+                self.flush(ui);
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "• ".to_owned(),
+                };
+                self.text.push_str(&marker);
+            }
+            pulldown_cmark::Tag::Link(_, url, _) => {
+                self.flush(ui);
+                self.link = Some(url.to_string());
+            }
+            pulldown_cmark::Tag::Image(_, url, _) => {
+                self.text.push_str(&format!("[image: {}] ", url));
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, ui: &mut egui::Ui, tag: pulldown_cmark::Tag) {
+        match tag {
+            pulldown_cmark::Tag::Heading(_) => {
+                self.flush(ui);
+                self.heading = None;
+            }
+            pulldown_cmark::Tag::Paragraph | pulldown_cmark::Tag::Item => self.flush(ui),
+            pulldown_cmark::Tag::BlockQuote => {
+                self.flush(ui);
+                self.quote = self.quote.saturating_sub(1);
+            }
+            pulldown_cmark::Tag::CodeBlock(_) => {
+                let code = std::mem::take(&mut self.text);
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.add(
+                        egui::Label::new(code.trim_end()).text_style(egui::TextStyle::Monospace),
+                    );
+                });
+            }
+            pulldown_cmark::Tag::List(_) => {
+                self.flush(ui);
+                self.lists.pop();
+            }
+            pulldown_cmark::Tag::Link(..) => {
+                let text = std::mem::take(&mut self.text);
+                if let Some(url) = self.link.take() {
+                    let text = if text.is_empty() { url.clone() } else { text };
+                    ui.add(egui::Hyperlink::new(url).text(format!("{}{}", self.indent(), text)));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn event(&mut self, ui: &mut egui::Ui, event: pulldown_cmark::Event) {
+        match event {
+            pulldown_cmark::Event::Start(tag) => self.start(ui, tag),
+            pulldown_cmark::Event::End(tag) => self.end(ui, tag),
+            pulldown_cmark::Event::Text(text) => self.text.push_str(&text),
+            pulldown_cmark::Event::Code(code) => {
+                self.text.push('`');
+                self.text.push_str(&code);
+                self.text.push('`');
+            }
+            pulldown_cmark::Event::SoftBreak => self.text.push(' '),
+            pulldown_cmark::Event::HardBreak => self.text.push('\n'),
+            pulldown_cmark::Event::Rule => {
+                self.flush(ui);
+                ui.separator();
+            }
+            pulldown_cmark::Event::TaskListMarker(done) => {
+                self.text.push_str(if done { "[x] " } else { "[ ] " });
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn show(ui: &mut egui::Ui, source: &str) {
+    let mut renderer = Renderer {
+        text: String::new(),
+        heading: None,
+        quote: 0,
+        lists: Vec::new(),
+        link: None,
+    };
+    let mut options = pulldown_cmark::Options::empty();
+    options.insert(pulldown_cmark::Options::ENABLE_TASKLISTS);
+    options.insert(pulldown_cmark::Options::ENABLE_STRIKETHROUGH);
+    for event in pulldown_cmark::Parser::new_ext(source, options) {
+        renderer.event(ui, event);
+    }
+    renderer.flush(ui);
+}
+
+pub struct MarkdownPanel {
+    pub open: bool,
+    source: String,
+    path: String,
+    status: String,
+}
+
+impl Default for MarkdownPanel {
+    fn default() -> Self {
+        Self {
+            open: false,
+            source: SAMPLE.to_owned(),
+            path: "README.md".to_owned(),
+            status: String::new(),
+        }
+    }
+}
+
+impl MarkdownPanel {
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        let mut open = self.open;
+        let mut load_requested = false;
+        let mut save_requested = false;
+        let source = &mut self.source;
+        let path = &mut self.path;
+        let status = &self.status;
+        crate::dock::Panel::new("Markdown")
+            .open(&mut open)
+            .default_pos(egui::pos2(260.0, 80.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::new(path));
+                    if ui.button("Load").clicked {
+                        load_requested = true;
+                    }
+                    if ui.button("Save").clicked {
+                        save_requested = true;
+                    }
+                });
+                if !status.is_empty() {
+                    ui.label(status.as_str());
+                }
+                ui.columns(2, |columns| {
+                    columns[0].add(egui::TextEdit::new(source).multiline(true));
+                    egui::ScrollArea::from_max_height(480.0).show(&mut columns[1], |ui| {
+                        show(ui, source);
+                    });
+                });
+            });
+        self.open = open;
+        if load_requested {
+            let path = std::path::PathBuf::from(&self.path);
+            match std::fs::read_to_string(&path) {
+                Ok(contents) => {
+                    self.source = contents;
+                    self.status = format!("Loaded {}", path.display());
+                }
+                Err(err) => self.status = format!("Cannot read {}: {}", path.display(), err),
+            }
+        }
+        if save_requested {
+            let path = std::path::PathBuf::from(&self.path);
+            self.status = match std::fs::write(&path, &self.source) {
+                Ok(()) => format!("Saved {}", path.display()),
+                Err(err) => format!("Cannot write {}: {}", path.display(), err),
+            };
+        }
+    }
+}
@@ -0,0 +1,887 @@
+const CANVAS_SIZE: [f32; 2] = [480.0, 260.0];
+const PREVIEW_SIZE: f32 = 200.0;
+const NODE_WIDTH: f32 = 110.0;
+const HEADER_HEIGHT: f32 = 20.0;
+const ROW_HEIGHT: f32 = 18.0;
+const SOCKET_SIZE: f32 = 10.0;
+const TEXTURE_SIZE: u32 = 256;
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    view_proj: [f32; 16],
+    model: [f32; 16],
+    eye: [f32; 4],
+    light: [f32; 4],
+    params: [f32; 4],
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum MathOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+    Sine,
+}
+
+impl MathOp {
+    pub const ALL: [MathOp; 6] = [
+        MathOp::Add,
+        MathOp::Subtract,
+        MathOp::Multiply,
+        MathOp::Divide,
+        MathOp::Power,
+        MathOp::Sine,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MathOp::Add => "Add",
+            MathOp::Subtract => "Subtract",
+            MathOp::Multiply => "Multiply",
+            MathOp::Divide => "Divide",
+            MathOp::Power => "Power",
+            MathOp::Sine => "Sine",
+        }
+    }
+
+    fn expression(self, a: &str, b: &str) -> String {
+        match self {
+            MathOp::Add => format!("{} + {}", a, b),
+            MathOp::Subtract => format!("{} - {}", a, b),
+            MathOp::Multiply => format!("{} * {}", a, b),
+            MathOp::Divide => format!("{} / max({}, vec4(1e-4))", a, b),
+            MathOp::Power => format!("pow(abs({}), {})", a, b),
+            MathOp::Sine => format!("sin({})", a),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum NodeKind {
+    Output,
+    Color([f32; 3]),
+    Scalar(f32),
+    TexCoord,
+    Normal,
+    Time,
+    Texture { scale: f32 },
+    Math(MathOp),
+    Mix,
+}
+
+impl NodeKind {
+    pub const ADDABLE: [NodeKind; 8] = [
+        NodeKind::Color([0.8, 0.8, 0.8]),
+        NodeKind::Scalar(0.5),
+        NodeKind::TexCoord,
+        NodeKind::Normal,
+        NodeKind::Time,
+        NodeKind::Texture { scale: 1.0 },
+        NodeKind::Math(MathOp::Multiply),
+        NodeKind::Mix,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            NodeKind::Output => "Output",
+            NodeKind::Color(_) => "Color",
+            NodeKind::Scalar(_) => "Scalar",
+            NodeKind::TexCoord => "UV",
+            NodeKind::Normal => "Normal",
+            NodeKind::Time => "Time",
+            NodeKind::Texture { .. } => "Texture",
+            NodeKind::Math(op) => op.name(),
+            NodeKind::Mix => "Mix",
+        }
+    }
+
+    pub fn inputs(&self) -> &'static [&'static str] {
+        match self {
+            NodeKind::Output => &["albedo", "roughness", "emission"],
+            NodeKind::Texture { .. } => &["uv"],
+            NodeKind::Math(MathOp::Sine) => &["a"],
+            NodeKind::Math(_) => &["a", "b"],
+            NodeKind::Mix => &["a", "b", "t"],
+            _ => &[],
+        }
+    }
+
+    fn default_input(&self, input: usize) -> Option<&'static str> {
+        match (self, input) {
+            (NodeKind::Output, 1) => Some("vec4(0.5)"),
+            (NodeKind::Output, 2) => Some("vec4(0.0)"),
+            (NodeKind::Texture { .. }, 0) => Some("vec4(v_uv, 0.0, 1.0)"),
+            _ => None,
+        }
+    }
+}
+
+pub struct Node {
+    pub id: u64,
+    pub kind: NodeKind,
+    pub position: egui::Vec2,
+    pub inputs: Vec<Option<u64>>,
+}
+
+pub struct Graph {
+    pub nodes: Vec<Node>,
+    next_id: u64,
+}
+
+impl Default for Graph {
+    fn default() -> Self {
+        let mut graph = Self {
+            nodes: Vec::new(),
+            next_id: 0,
+        };
+        let output = graph.add(NodeKind::Output, egui::vec2(350.0, 60.0));
+        let uv = graph.add(NodeKind::TexCoord, egui::vec2(10.0, 20.0));
+        let texture = graph.add(NodeKind::Texture { scale: 4.0 }, egui::vec2(10.0, 80.0));
+        let warm = graph.add(NodeKind::Color([0.9, 0.45, 0.2]), egui::vec2(10.0, 160.0));
+        let cool = graph.add(NodeKind::Color([0.15, 0.3, 0.7]), egui::vec2(10.0, 210.0));
+        let mix = graph.add(NodeKind::Mix, egui::vec2(190.0, 40.0));
+        let roughness = graph.add(NodeKind::Scalar(0.35), egui::vec2(190.0, 160.0));
+        graph.connect(uv, texture, 0);
+        graph.connect(warm, mix, 0);
+        graph.connect(cool, mix, 1);
+        graph.connect(texture, mix, 2);
+        graph.connect(mix, output, 0);
+        graph.connect(roughness, output, 1);
+        graph
+    }
+}
+
+impl Graph {
+    pub fn add(&mut self, kind: NodeKind, position: egui::Vec2) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.nodes.push(Node {
+            id,
+            kind,
+            position,
+            inputs: vec![None; kind.inputs().len()],
+        });
+        id
+    }
+
+    pub fn remove(&mut self, id: u64) {
+        self.nodes.retain(|node| node.id != id);
+        for node in self.nodes.iter_mut() {
+            for input in node.inputs.iter_mut() {
+                if *input == Some(id) {
+                    *input = None;
+                }
+            }
+        }
+    }
+
+    pub fn connect(&mut self, from: u64, to: u64, input: usize) {
+        if let Some(node) = self.nodes.iter_mut().find(|node| node.id == to) {
+            if input < node.inputs.len() {
+                node.inputs[input] = Some(from);
+            }
+        }
+    }
+
+    fn node(&self, id: u64) -> Option<&Node> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    fn emit(
+        &self,
+        id: u64,
+        lines: &mut Vec<String>,
+        emitted: &mut Vec<u64>,
+        visiting: &mut Vec<u64>,
+    ) -> Result<String, String> {
+        let name = format!("n{}", id);
+        if emitted.contains(&id) {
+            return Ok(name);
+        }
+        if visiting.contains(&id) {
+            return Err("the graph contains a cycle".to_owned());
+        }
+        let node = self
+            .node(id)
+            .ok_or_else(|| format!("node {} no longer exists", id))?;
+        visiting.push(id);
+        let mut inputs = Vec::with_capacity(node.inputs.len());
+        for (index, input) in node.inputs.iter().enumerate() {
+            let value = match (input, node.kind.default_input(index)) {
+                (Some(source), _) => self.emit(*source, lines, emitted, visiting)?,
+                (None, Some(default)) => default.to_owned(),
+                (None, None) => {
+                    return Err(format!(
+                        "{} input '{}' is not connected",
+                        node.kind.name(),
+                        node.kind.inputs()[index]
+                    ))
+                }
+            };
+            inputs.push(value);
+        }
+        visiting.pop();
+
+        let expression = match node.kind {
+            NodeKind::Output => return Err("the output node cannot be used as an input".to_owned()),
+            NodeKind::Color(color) => {
+                format!("vec4({:?}, {:?}, {:?}, 1.0)", color[0], color[1], color[2])
+            }
+            NodeKind::Scalar(value) => format!("vec4({:?})", value),
+            NodeKind::TexCoord => "vec4(v_uv, 0.0, 1.0)".to_owned(),
+            NodeKind::Normal => "vec4(normal * 0.5 + 0.5, 1.0)".to_owned(),
+            NodeKind::Time => "vec4(u_params.x)".to_owned(),
+            NodeKind::Texture { scale } => format!(
+                "texture(sampler2D(t_texture, s_texture), {}.xy * {:?})",
+                inputs[0], scale
+            ),
+            NodeKind::Math(op) => op.expression(&inputs[0], inputs.get(1).map_or("", |b| b)),
+            NodeKind::Mix => format!("mix({}, {}, {})", inputs[0], inputs[1], inputs[2]),
+        };
+        lines.push(format!("    vec4 {} = {};", name, expression));
+        emitted.push(id);
+        Ok(name)
+    }
+
+    pub fn generate(&self) -> Result<String, String> {
+        let output = self
+            .nodes
+            .iter()
+            .find(|node| node.kind == NodeKind::Output)
+            .ok_or_else(|| "the graph has no output node".to_owned())?;
+        let mut lines = Vec::new();
+        let mut emitted = Vec::new();
+        let mut values = Vec::with_capacity(output.inputs.len());
+        for (index, input) in output.inputs.iter().enumerate() {
+            let value = match (input, output.kind.default_input(index)) {
+                (Some(source), _) => self.emit(*source, &mut lines, &mut emitted, &mut vec![])?,
+                (None, Some(default)) => default.to_owned(),
+                (None, None) => {
+                    return Err(format!(
+                        "Output input '{}' is not connected",
+                        output.kind.inputs()[index]
+                    ))
+                }
+            };
+            values.push(value);
+        }
+        Ok(include_str!("shaders/material_graph.frag")
+            .replace("    // NODES\n", &(lines.join("\n") + "\n"))
+            .replace("ALBEDO", &values[0])
+            .replace("ROUGHNESS", &values[1])
+            .replace("EMISSION", &values[2]))
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Action {
+    Select(u64),
+    Move(u64, egui::Vec2),
+    BeginLink(u64),
+    Connect(u64, usize),
+    Disconnect(u64, usize),
+}
+
+fn node_rect(canvas: egui::Rect, node: &Node) -> egui::Rect {
+    let rows = node.kind.inputs().len().max(1) as f32;
+    egui::Rect::from_min_size(
+        canvas.min + node.position,
+        egui::vec2(NODE_WIDTH, HEADER_HEIGHT + rows * ROW_HEIGHT + 4.0),
+    )
+}
+
+fn output_socket(rect: egui::Rect) -> egui::Pos2 {
+    egui::pos2(rect.max.x, rect.min.y + HEADER_HEIGHT * 0.5)
+}
+
+fn input_socket(rect: egui::Rect, input: usize) -> egui::Pos2 {
+    egui::pos2(
+        rect.min.x,
+        rect.min.y + HEADER_HEIGHT + (input as f32 + 0.5) * ROW_HEIGHT,
+    )
+}
+
+fn socket_rect(center: egui::Pos2) -> egui::Rect {
+    egui::Rect::from_center_size(center, egui::vec2(SOCKET_SIZE, SOCKET_SIZE))
+}
+
+fn texture_pixels() -> Vec<u8> {
+    let perlin = crate::noise::Perlin::new(7);
+    let noise = crate::noise::Fbm {
+        frequency: 0.03,
+        ..crate::noise::Fbm::default()
+    };
+    let mut pixels = Vec::with_capacity((TEXTURE_SIZE * TEXTURE_SIZE * 4) as usize);
+    for y in 0..TEXTURE_SIZE {
+        for x in 0..TEXTURE_SIZE {
+            let checker = ((x / 32) + (y / 32)) % 2 == 0;
+            let detail = noise.sample2(&perlin, x as f32, y as f32) * 0.5 + 0.5;
+            let base = if checker { 0.9 } else { 0.25 };
+            let value = (base * (0.7 + 0.3 * detail) * 255.0) as u8;
+            pixels.extend_from_slice(&[value, value, value, 255]);
+        }
+    }
+    pixels
+}
+
+pub struct MaterialGraph {
+    pub open: bool,
+    pub graph: Graph,
+    pub source: String,
+    pub error: Option<String>,
+    pub compiles: u32,
+    selected: Option<u64>,
+    pending_link: Option<u64>,
+    dirty: bool,
+    viewport: Option<egui::Rect>,
+    preview: crate::blit::Canvas,
+    started: std::time::Instant,
+    format: wgpu::TextureFormat,
+    vs_module: wgpu::ShaderModule,
+    pipeline_layout: wgpu::PipelineLayout,
+    pipeline: Option<wgpu::RenderPipeline>,
+    uniform_buffer: wgpu::Buffer,
+    texture: wgpu::Texture,
+    texture_uploaded: bool,
+    bind_group: wgpu::BindGroup,
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    index_count: u32,
+}
+
+impl MaterialGraph {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Material Graph Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::VERTEX | wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::UniformBuffer {
+                        dynamic: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::SampledTexture {
+                        dimension: wgpu::TextureViewDimension::D2,
+                        component_type: wgpu::TextureComponentType::Float,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler { comparison: false },
+                    count: None,
+                },
+            ],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Material Graph Pipeline Layout"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let vs_module = crate::shader::compile_builtin(
+            device,
+            "material_graph.vert",
+            include_str!("shaders/material_graph.vert"),
+            shaderc::ShaderKind::Vertex,
+        );
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Material Graph Uniforms"),
+            size: std::mem::size_of::<Uniforms>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Material Graph Texture"),
+            size: wgpu::Extent3d {
+                width: TEXTURE_SIZE,
+                height: TEXTURE_SIZE,
+                depth: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST,
+        });
+        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Material Graph Sampler"),
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::Repeat,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Material Graph Bind Group"),
+            layout: &layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(uniform_buffer.slice(..)),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&texture_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+            ],
+        });
+        let sphere = crate::scene::Mesh::sphere("Material Preview", 48, 32);
+        let vertex_buffer = wgpu::util::DeviceExt::create_buffer_init(
+            device,
+            &wgpu::util::BufferInitDescriptor {
+                label: Some("Material Preview Vertices"),
+                contents: bytemuck::cast_slice(&sphere.vertices),
+                usage: wgpu::BufferUsage::VERTEX,
+            },
+        );
+        let index_buffer = wgpu::util::DeviceExt::create_buffer_init(
+            device,
+            &wgpu::util::BufferInitDescriptor {
+                label: Some("Material Preview Indices"),
+                contents: bytemuck::cast_slice(&sphere.indices),
+                usage: wgpu::BufferUsage::INDEX,
+            },
+        );
+
+        Self {
+            open: false,
+            graph: Graph::default(),
+            source: String::new(),
+            error: None,
+            compiles: 0,
+            selected: None,
+            pending_link: None,
+            dirty: true,
+            viewport: None,
+            preview: crate::blit::Canvas::new(format),
+            started: std::time::Instant::now(),
+            format,
+            vs_module,
+            pipeline_layout,
+            pipeline: None,
+            uniform_buffer,
+            texture,
+            texture_uploaded: false,
+            bind_group,
+            vertex_buffer,
+            index_buffer,
+            index_count: sphere.indices.len() as u32,
+        }
+    }
+
+    pub fn recreate_gpu_resources(&mut self, device: &wgpu::Device, format: wgpu::TextureFormat) {
+        let graph = std::mem::take(&mut self.graph);
+        let open = self.open;
+        *self = Self::new(device, format);
+        self.graph = graph;
+        self.open = open;
+    }
+
+    fn create_pipeline(
+        &self,
+        device: &wgpu::Device,
+        fs_module: &wgpu::ShaderModule,
+    ) -> wgpu::RenderPipeline {
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Material Graph Pipeline"),
+            layout: Some(&self.pipeline_layout),
+            vertex_stage: wgpu::ProgrammableStageDescriptor {
+                module: &self.vs_module,
+                entry_point: "main",
+            },
+            fragment_stage: Some(wgpu::ProgrammableStageDescriptor {
+                module: fs_module,
+                entry_point: "main",
+            }),
+            rasterization_state: Some(wgpu::RasterizationStateDescriptor {
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: wgpu::CullMode::Back,
+                ..Default::default()
+            }),
+            primitive_topology: wgpu::PrimitiveTopology::TriangleList,
+            color_states: &[wgpu::ColorStateDescriptor {
+                format: self.format,
+                color_blend: wgpu::BlendDescriptor::REPLACE,
+                alpha_blend: wgpu::BlendDescriptor::REPLACE,
+                write_mask: wgpu::ColorWrite::ALL,
+            }],
+            depth_stencil_state: None,
+            vertex_state: wgpu::VertexStateDescriptor {
+                index_format: wgpu::IndexFormat::Uint32,
+                vertex_buffers: &[wgpu::VertexBufferDescriptor {
+                    stride: std::mem::size_of::<crate::scene::Vertex>() as wgpu::BufferAddress,
+                    step_mode: wgpu::InputStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![0 => Float3, 1 => Float3],
+                }],
+            },
+            sample_count: 1,
+            sample_mask: !0,
+            alpha_to_coverage_enabled: false,
+        })
+    }
+
+    fn compile(&mut self, device: &wgpu::Device) {
+        self.dirty = false;
+        match self.graph.generate() {
+            Ok(source) => {
+                match crate::shader::compile(
+                    device,
+                    "material_graph.frag",
+                    &source,
+                    shaderc::ShaderKind::Fragment,
+                ) {
+                    Ok(module) => {
+                        self.pipeline = Some(self.create_pipeline(device, &module));
+                        self.error = None;
+                        self.compiles += 1;
+                    }
+                    Err(err) => {
+                        log::warn!("material graph failed to compile: {}", err);
+                        self.error = Some(err);
+                    }
+                }
+                self.source = source;
+            }
+            Err(err) => self.error = Some(format!("Invalid graph: {}", err)),
+        }
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        let mut open = self.open;
+        let mut viewport = None;
+        let compiles = self.compiles;
+        let error = &self.error;
+        let source = &self.source;
+        let graph = &mut self.graph;
+        let selected = &mut self.selected;
+        let pending_link = &mut self.pending_link;
+        let dirty = &mut self.dirty;
+        let preview = &self.preview;
+        crate::dock::Panel::new("Material Graph")
+            .open(&mut open)
+            .default_pos(egui::pos2(300.0, 60.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Add");
+                    for kind in NodeKind::ADDABLE.iter() {
+                        if ui.button(kind.name()).clicked {
+                            let offset = (graph.nodes.len() % 6) as f32 * 12.0;
+                            let id = graph.add(*kind, egui::vec2(140.0 + offset, 20.0 + offset));
+                            *selected = Some(id);
+                        }
+                    }
+                });
+
+                let canvas = ui.allocate_space(egui::vec2(CANVAS_SIZE[0], CANVAS_SIZE[1]));
+                ui.painter()
+                    .rect_filled(canvas, 2.0, egui::Srgba::new(24, 24, 28, 255));
+                let link_stroke = egui::Stroke::new(2.0, egui::Srgba::new(200, 200, 120, 255));
+                for node in graph.nodes.iter() {
+                    let rect = node_rect(canvas, node);
+                    for (input, source) in node.inputs.iter().enumerate() {
+                        if let Some(source) = source.and_then(|id| graph.node(id)) {
+                            let from = output_socket(node_rect(canvas, source));
+                            ui.painter()
+                                .line_segment([from, input_socket(rect, input)], link_stroke);
+                        }
+                    }
+                }
+                if let (Some(from), Some(mouse)) = (
+                    pending_link.and_then(|id| graph.node(id)),
+                    ui.input().mouse.pos,
+                ) {
+                    ui.painter().line_segment(
+                        [output_socket(node_rect(canvas, from)), mouse],
+                        link_stroke,
+                    );
+                }
+
+                let mut action = None;
+                for node in graph.nodes.iter() {
+                    let rect = node_rect(canvas, node);
+                    let header = egui::Rect::from_min_size(
+                        rect.min,
+                        egui::vec2(rect.width(), HEADER_HEIGHT),
+                    );
+                    let is_selected = *selected == Some(node.id);
+                    let body_color = if is_selected {
+                        egui::Srgba::new(70, 70, 90, 255)
+                    } else {
+                        egui::Srgba::new(50, 50, 56, 255)
+                    };
+                    ui.painter().rect_filled(rect, 4.0, body_color);
+                    ui.painter()
+                        .rect_filled(header, 4.0, egui::Srgba::new(80, 110, 160, 255));
+                    ui.painter().text(
+                        header.min + egui::vec2(6.0, 2.0),
+                        (egui::Align::Min, egui::Align::Min),
+                        node.kind.name().to_owned(),
+                        egui::TextStyle::Body,
+                        egui::Srgba::new(240, 240, 240, 255),
+                    );
+                    let dragged = ui.interact(
+                        header,
+                        ui.make_persistent_id(("material_node", node.id)),
+                        egui::Sense::drag(),
+                    );
+                    if dragged.active {
+                        action = Some(Action::Move(node.id, ui.input().mouse.delta));
+                        if !is_selected {
+                            action = Some(Action::Select(node.id));
+                        }
+                    }
+
+                    if node.kind != NodeKind::Output {
+                        let socket = socket_rect(output_socket(rect));
+                        ui.painter()
+                            .rect_filled(socket, 2.0, egui::Srgba::new(200, 200, 120, 255));
+                        let response = ui.interact(
+                            socket,
+                            ui.make_persistent_id(("material_output", node.id)),
+                            egui::Sense::click(),
+                        );
+                        if response.clicked {
+                            action = Some(Action::BeginLink(node.id));
+                        }
+                    }
+                    for (input, name) in node.kind.inputs().iter().enumerate() {
+                        let center = input_socket(rect, input);
+                        let socket = socket_rect(center);
+                        let connected = node.inputs[input].is_some();
+                        let color = if connected {
+                            egui::Srgba::new(200, 200, 120, 255)
+                        } else {
+                            egui::Srgba::new(110, 110, 110, 255)
+                        };
+                        ui.painter().rect_filled(socket, 2.0, color);
+                        ui.painter().text(
+                            center + egui::vec2(SOCKET_SIZE, 0.0),
+                            (egui::Align::Min, egui::Align::Center),
+                            (*name).to_owned(),
+                            egui::TextStyle::Body,
+                            egui::Srgba::new(200, 200, 200, 255),
+                        );
+                        let response = ui.interact(
+                            socket,
+                            ui.make_persistent_id(("material_input", node.id, input)),
+                            egui::Sense::click(),
+                        );
+                        if response.clicked {
+                            action = Some(if pending_link.is_some() {
+                                Action::Connect(node.id, input)
+                            } else {
+                                Action::Disconnect(node.id, input)
+                            });
+                        }
+                    }
+                }
+                match action {
+                    Some(Action::Select(id)) => *selected = Some(id),
+                    Some(Action::Move(id, delta)) => {
+                        if let Some(node) = graph.nodes.iter_mut().find(|node| node.id == id) {
+                            let max = egui::vec2(
+                                CANVAS_SIZE[0] - NODE_WIDTH,
+                                CANVAS_SIZE[1] - HEADER_HEIGHT,
+                            );
+                            node.position += delta;
+                            node.position.x = node.position.x.max(0.0).min(max.x);
+                            node.position.y = node.position.y.max(0.0).min(max.y);
+                        }
+                    }
+                    Some(Action::BeginLink(id)) => *pending_link = Some(id),
+                    Some(Action::Connect(id, input)) => {
+                        if let Some(from) = pending_link.take() {
+                            graph.connect(from, id, input);
+                            *dirty = true;
+                        }
+                    }
+                    Some(Action::Disconnect(id, input)) => {
+                        if let Some(node) = graph.nodes.iter_mut().find(|node| node.id == id) {
+                            node.inputs[input] = None;
+                            *dirty = true;
+                        }
+                    }
+                    None => {}
+                }
+                ui.label(if pending_link.is_some() {
+                    "Click an input to connect, or Cancel"
+                } else {
+                    "Drag headers to move; click an output then an input to link, an input to unlink"
+                });
+                if pending_link.is_some() && ui.button("Cancel link").clicked {
+                    *pending_link = None;
+                }
+
+                if let Some(node) = selected.and_then(|id| graph.nodes.iter_mut().find(|node| node.id == id)) {
+                    let before = node.kind;
+                    ui.separator();
+                    ui.label(format!("{} node", node.kind.name()));
+                    match &mut node.kind {
+                        NodeKind::Color(color) => {
+                            ui.add(egui::Slider::f32(&mut color[0], 0.0..=1.0).text("red"));
+                            ui.add(egui::Slider::f32(&mut color[1], 0.0..=1.0).text("green"));
+                            ui.add(egui::Slider::f32(&mut color[2], 0.0..=1.0).text("blue"));
+                        }
+                        NodeKind::Scalar(value) => {
+                            ui.add(egui::Slider::f32(value, -4.0..=4.0).text("value"));
+                        }
+                        NodeKind::Texture { scale } => {
+                            ui.add(egui::Slider::f32(scale, 0.25..=16.0).text("scale"));
+                        }
+                        NodeKind::Math(op) => {
+                            ui.horizontal(|ui| {
+                                for candidate in MathOp::ALL.iter() {
+                                    ui.radio_value(op, *candidate, candidate.name());
+                                }
+                            });
+                        }
+                        _ => {}
+                    }
+                    if node.kind != before {
+                        node.inputs.resize(node.kind.inputs().len(), None);
+                        *dirty = true;
+                    }
+                    let id = node.id;
+                    if node.kind != NodeKind::Output && ui.button("Delete node").clicked {
+                        graph.remove(id);
+                        *selected = None;
+                        if *pending_link == Some(id) {
+                            *pending_link = None;
+                        }
+                        *dirty = true;
+                    }
+                }
+
+                ui.separator();
+                let rect = ui.allocate_space(egui::vec2(PREVIEW_SIZE, PREVIEW_SIZE));
+                preview.paint(ui, rect);
+                viewport = Some(rect);
+                match error {
+                    Some(error) => {
+                        ui.label(format!("Error: {}", error));
+                        ui.label("The preview keeps the last working material");
+                    }
+                    None => {
+                        ui.label(format!("Compiled {} times", compiles));
+                    }
+                }
+                egui::CollapsingHeader::new("Generated GLSL").show(ui, |ui| {
+                    ui.label(source.as_str());
+                });
+            });
+        self.open = open;
+        self.viewport = viewport.filter(|_| open);
+    }
+
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        ui_pass: &mut crate::ui_pass::RenderPass,
+        size: winit::dpi::PhysicalSize<u32>,
+        scale_factor: f64,
+    ) {
+        let viewport = match self.viewport {
+            Some(rect) => crate::blit::to_physical(rect, scale_factor),
+            None => return,
+        };
+        if !crate::blit::fits(viewport, size) {
+            return;
+        }
+        if !self.texture_uploaded {
+            let staging = wgpu::util::DeviceExt::create_buffer_init(
+                device,
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Material Graph Texture Staging"),
+                    contents: &texture_pixels(),
+                    usage: wgpu::BufferUsage::COPY_SRC,
+                },
+            );
+            encoder.copy_buffer_to_texture(
+                wgpu::BufferCopyView {
+                    buffer: &staging,
+                    layout: wgpu::TextureDataLayout {
+                        offset: 0,
+                        bytes_per_row: TEXTURE_SIZE * 4,
+                        rows_per_image: TEXTURE_SIZE,
+                    },
+                },
+                wgpu::TextureCopyView {
+                    texture: &self.texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                },
+                wgpu::Extent3d {
+                    width: TEXTURE_SIZE,
+                    height: TEXTURE_SIZE,
+                    depth: 1,
+                },
+            );
+            self.texture_uploaded = true;
+        }
+        if self.dirty {
+            self.compile(device);
+        }
+        let pipeline = match &self.pipeline {
+            Some(pipeline) => pipeline,
+            None => return,
+        };
+
+        let time = self.started.elapsed().as_secs_f32();
+        let eye = glam::Vec3::new(0.0, 0.4, 2.2);
+        let view = glam::Mat4::look_at_rh(eye, glam::Vec3::zero(), glam::Vec3::unit_y());
+        let projection = glam::Mat4::perspective_rh(0.6, 1.0, 0.1, 10.0);
+        let uniforms = Uniforms {
+            view_proj: (projection * view).to_cols_array(),
+            model: (glam::Mat4::from_rotation_y(time * 0.4)
+                * glam::Mat4::from_scale(glam::Vec3::splat(1.6)))
+            .to_cols_array(),
+            eye: [eye.x, eye.y, eye.z, 1.0],
+            light: [0.5, 0.7, 0.6, 0.0],
+            params: [time, 0.0, 0.0, 0.0],
+        };
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+
+        let target = self.preview.begin(device, encoder, ui_pass, size);
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+        crate::blit::set_viewport(&mut render_pass, viewport);
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(self.index_buffer.slice(..));
+        render_pass.draw_indexed(0..self.index_count, 0, 0..1);
+    }
+}
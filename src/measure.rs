@@ -0,0 +1,234 @@
+#[derive(Clone, Copy, PartialEq)]
+pub enum Tool {
+    Off,
+    Distance,
+    Angle,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Unit {
+    Meters,
+    Centimeters,
+    Feet,
+    Inches,
+}
+
+impl Unit {
+    pub const ALL: [Unit; 4] = [Unit::Meters, Unit::Centimeters, Unit::Feet, Unit::Inches];
+
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Unit::Meters => "m",
+            Unit::Centimeters => "cm",
+            Unit::Feet => "ft",
+            Unit::Inches => "in",
+        }
+    }
+
+    fn per_meter(self) -> f32 {
+        match self {
+            Unit::Meters => 1.0,
+            Unit::Centimeters => 100.0,
+            Unit::Feet => 3.280_84,
+            Unit::Inches => 39.370_08,
+        }
+    }
+
+    pub fn format(self, meters: f32) -> String {
+        format!("{:.2} {}", meters * self.per_meter(), self.suffix())
+    }
+}
+
+pub enum Measurement {
+    Distance([glam::Vec3; 2]),
+    Angle([glam::Vec3; 3]),
+}
+
+impl Measurement {
+    pub fn points(&self) -> &[glam::Vec3] {
+        match self {
+            Measurement::Distance(points) => points,
+            Measurement::Angle(points) => points,
+        }
+    }
+
+    pub fn anchor(&self) -> glam::Vec3 {
+        match self {
+            Measurement::Distance([a, b]) => (*a + *b) * 0.5,
+            Measurement::Angle([_, vertex, _]) => *vertex,
+        }
+    }
+
+    pub fn text(&self, unit: Unit) -> String {
+        match self {
+            Measurement::Distance([a, b]) => unit.format((*b - *a).length()),
+            Measurement::Angle([a, vertex, b]) => {
+                let first = (*a - *vertex).normalize();
+                let second = (*b - *vertex).normalize();
+                let angle = first.dot(second).clamp(-1.0, 1.0).acos();
+                format!("{:.1}°", angle.to_degrees())
+            }
+        }
+    }
+}
+
+pub struct Measurements {
+    pub tool: Tool,
+    pub unit: Unit,
+    pub items: Vec<Measurement>,
+    pending: Vec<glam::Vec3>,
+}
+
+impl Default for Measurements {
+    fn default() -> Self {
+        Self {
+            tool: Tool::Off,
+            unit: Unit::Meters,
+            items: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl Measurements {
+    pub fn add_point(&mut self, point: glam::Vec3) {
+        self.pending.push(point);
+        let measurement = match (self.tool, self.pending.as_slice()) {
+            (Tool::Distance, [a, b]) => Measurement::Distance([*a, *b]),
+            (Tool::Angle, [a, vertex, b]) => Measurement::Angle([*a, *vertex, *b]),
+            (Tool::Off, _) => {
+                self.pending.clear();
+                return;
+            }
+            _ => return,
+        };
+        self.items.push(measurement);
+        self.pending.clear();
+    }
+
+    pub fn set_tool(&mut self, tool: Tool) {
+        if self.tool != tool {
+            self.tool = tool;
+            self.pending.clear();
+        }
+    }
+
+    pub fn pending(&self) -> &[glam::Vec3] {
+        &self.pending
+    }
+
+    pub fn emit_lines(&self, debug: &mut crate::debug::DebugDraw) {
+        let color = [1.0, 0.85, 0.2, 1.0];
+        for measurement in self.items.iter() {
+            for pair in measurement.points().windows(2) {
+                debug.line(pair[0], pair[1], color);
+            }
+        }
+        for pair in self.pending.windows(2) {
+            debug.line(pair[0], pair[1], [1.0, 1.0, 1.0, 1.0]);
+        }
+        for point in self
+            .items
+            .iter()
+            .flat_map(|measurement| measurement.points().iter())
+            .chain(self.pending.iter())
+        {
+            for axis in [
+                glam::Vec3::unit_x(),
+                glam::Vec3::unit_y(),
+                glam::Vec3::unit_z(),
+            ]
+            .iter()
+            {
+                debug.line(*point - *axis * 0.1, *point + *axis * 0.1, color);
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct MeasurePanel {
+    pub open: bool,
+}
+
+impl MeasurePanel {
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, scene: &mut crate::scene::Scene) {
+        let points = ctx.pixels_per_point();
+        let painter = egui::Painter::new(
+            ctx.clone(),
+            egui::LayerId::background(),
+            ctx.input().screen_rect(),
+        );
+        let unit = scene.measurements.unit;
+        for measurement in scene.measurements.items.iter() {
+            if let Some(pos) = scene.world_to_screen(measurement.anchor(), points) {
+                painter.text(
+                    pos,
+                    (egui::Align::Center, egui::Align::Max),
+                    measurement.text(unit),
+                    egui::TextStyle::Body,
+                    egui::Srgba::new(255, 217, 51, 255),
+                );
+            }
+        }
+
+        crate::dock::Panel::new("Measurements")
+            .open(&mut self.open)
+            .default_pos(egui::pos2(560.0, 80.0))
+            .show(ctx, |ui| {
+                let measurements = &mut scene.measurements;
+                let mut tool = measurements.tool;
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut tool, Tool::Off, "Off");
+                    ui.radio_value(&mut tool, Tool::Distance, "Distance");
+                    ui.radio_value(&mut tool, Tool::Angle, "Angle");
+                });
+                measurements.set_tool(tool);
+                ui.horizontal(|ui| {
+                    for unit in Unit::ALL.iter() {
+                        ui.radio_value(&mut measurements.unit, *unit, unit.suffix());
+                    }
+                });
+                let needed = match measurements.tool {
+                    Tool::Off => 0,
+                    Tool::Distance => 2,
+                    Tool::Angle => 3,
+                };
+                if needed > 0 {
+                    ui.label(format!(
+                        "Click the scene to place points ({} of {})",
+                        measurements.pending().len(),
+                        needed
+                    ));
+                }
+                ui.separator();
+
+                let mut removed = None;
+                for (index, measurement) in measurements.items.iter().enumerate() {
+                    let kind = match measurement {
+                        Measurement::Distance(_) => "Distance",
+                        Measurement::Angle(_) => "Angle",
+                    };
+                    ui.horizontal(|ui| {
+                        ui.label(format!(
+                            "{}. {}: {}",
+                            index + 1,
+                            kind,
+                            measurement.text(measurements.unit)
+                        ));
+                        if ui.button("Remove").clicked {
+                            removed = Some(index);
+                        }
+                    });
+                }
+                if let Some(index) = removed {
+                    measurements.items.remove(index);
+                }
+                if measurements.items.is_empty() {
+                    ui.label("No measurements");
+                } else if ui.button("Clear all").clicked {
+                    measurements.items.clear();
+                }
+            });
+    }
+}
@@ -0,0 +1,432 @@
+const WIDTH: f32 = 210.0;
+const ROW_HEIGHT: f32 = 20.0;
+const SEPARATOR_HEIGHT: f32 = 7.0;
+const PADDING: f32 = 3.0;
+const TEXT_COLOR: egui::Srgba = egui::Srgba::new(220, 220, 220, 255);
+const DISABLED_COLOR: egui::Srgba = egui::Srgba::new(110, 110, 115, 255);
+const SHORTCUT_COLOR: egui::Srgba = egui::Srgba::new(150, 150, 160, 255);
+
+static NEXT_ID: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(1);
+static ACTIVE: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+static SHOWN: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+static SECONDARY: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+static PENDING_KEY: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+#[derive(Clone, Copy, PartialEq)]
+enum Navigation {
+    Up = 1,
+    Down,
+    Left,
+    Right,
+    Activate,
+    Close,
+}
+
+impl Navigation {
+    fn from_code(code: usize) -> Option<Self> {
+        [
+            Navigation::Up,
+            Navigation::Down,
+            Navigation::Left,
+            Navigation::Right,
+            Navigation::Activate,
+            Navigation::Close,
+        ]
+        .iter()
+        .copied()
+        .find(|navigation| *navigation as usize == code)
+    }
+}
+
+pub fn secondary_click() {
+    SECONDARY.store(true, std::sync::atomic::Ordering::Relaxed);
+}
+
+pub fn secondary_clicked() -> bool {
+    SECONDARY.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+pub fn command_shortcut(key: &str) -> String {
+    if cfg!(target_os = "macos") {
+        format!("Cmd+{}", key)
+    } else {
+        format!("Ctrl+{}", key)
+    }
+}
+
+pub fn key_input(input: &winit::event::KeyboardInput) -> bool {
+    if ACTIVE.load(std::sync::atomic::Ordering::Relaxed) == 0 {
+        return false;
+    }
+    let navigation = match input.virtual_keycode {
+        Some(winit::event::VirtualKeyCode::Up) => Navigation::Up,
+        Some(winit::event::VirtualKeyCode::Down) => Navigation::Down,
+        Some(winit::event::VirtualKeyCode::Left) => Navigation::Left,
+        Some(winit::event::VirtualKeyCode::Right) => Navigation::Right,
+        Some(winit::event::VirtualKeyCode::Return)
+        | Some(winit::event::VirtualKeyCode::NumpadEnter)
+        | Some(winit::event::VirtualKeyCode::Space) => Navigation::Activate,
+        Some(winit::event::VirtualKeyCode::Escape) => Navigation::Close,
+        _ => return false,
+    };
+    if input.state == winit::event::ElementState::Pressed {
+        PENDING_KEY.store(navigation as usize, std::sync::atomic::Ordering::Relaxed);
+    }
+    true
+}
+
+pub fn end_frame() {
+    SECONDARY.store(false, std::sync::atomic::Ordering::Relaxed);
+    PENDING_KEY.store(0, std::sync::atomic::Ordering::Relaxed);
+    if !SHOWN.swap(false, std::sync::atomic::Ordering::Relaxed) {
+        ACTIVE.store(0, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
+enum Kind<A> {
+    Action(A),
+    Submenu(Vec<Item<A>>),
+    Label,
+    Separator,
+}
+
+pub struct Item<A> {
+    label: String,
+    icon: Option<char>,
+    shortcut: Option<String>,
+    enabled: bool,
+    kind: Kind<A>,
+}
+
+impl<A> Item<A> {
+    fn new(label: String, kind: Kind<A>) -> Self {
+        Self {
+            label,
+            icon: None,
+            shortcut: None,
+            enabled: true,
+            kind,
+        }
+    }
+
+    pub fn action(label: impl Into<String>, action: A) -> Self {
+        Self::new(label.into(), Kind::Action(action))
+    }
+
+    pub fn submenu(label: impl Into<String>, items: Vec<Item<A>>) -> Self {
+        let enabled = !items.is_empty();
+        Self::new(label.into(), Kind::Submenu(items)).enabled(enabled)
+    }
+
+    pub fn label(label: impl Into<String>) -> Self {
+        Self::new(label.into(), Kind::Label).enabled(false)
+    }
+
+    pub fn separator() -> Self {
+        Self::new(String::new(), Kind::Separator).enabled(false)
+    }
+
+    pub fn icon(mut self, icon: char) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    pub fn shortcut(mut self, shortcut: impl Into<String>) -> Self {
+        self.shortcut = Some(shortcut.into());
+        self
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    fn height(&self) -> f32 {
+        match self.kind {
+            Kind::Separator => SEPARATOR_HEIGHT,
+            _ => ROW_HEIGHT,
+        }
+    }
+}
+
+fn selectable<A>(items: &[Item<A>], from: Option<usize>, forward: bool) -> Option<usize> {
+    let count = items.len();
+    (1..=count)
+        .map(|step| match (from, forward) {
+            (Some(from), true) => (from + step) % count,
+            (Some(from), false) => (from + count - step % count) % count,
+            (None, true) => step - 1,
+            (None, false) => count - step,
+        })
+        .find(|index| items[*index].enabled)
+}
+
+struct State<A> {
+    pos: egui::Pos2,
+    items: Vec<Item<A>>,
+    highlight: Vec<Option<usize>>,
+}
+
+impl<A> State<A> {
+    fn level_items(&self, level: usize) -> &[Item<A>] {
+        let mut items = &self.items[..];
+        for highlight in self.highlight[..level].iter() {
+            match highlight.map(|index| &items[index].kind) {
+                Some(Kind::Submenu(children)) => items = children,
+                _ => return &[],
+            }
+        }
+        items
+    }
+
+    fn level_rects(&self) -> Vec<egui::Rect> {
+        let mut rects: Vec<egui::Rect> = Vec::new();
+        for level in 0..self.highlight.len() {
+            let items = self.level_items(level);
+            let height: f32 = items.iter().map(Item::height).sum::<f32>() + PADDING * 2.0;
+            let pos = match rects.last() {
+                None => self.pos,
+                Some(parent) => {
+                    let parent_items = self.level_items(level - 1);
+                    let offset: f32 = parent_items[..self.highlight[level - 1].unwrap_or(0)]
+                        .iter()
+                        .map(Item::height)
+                        .sum();
+                    egui::pos2(parent.max.x - 2.0, parent.min.y + offset)
+                }
+            };
+            rects.push(egui::Rect::from_min_size(pos, egui::vec2(WIDTH, height)));
+        }
+        rects
+    }
+
+    fn open_submenu(&mut self, level: usize) -> bool {
+        let index = match self.highlight[level] {
+            Some(index) => index,
+            None => return false,
+        };
+        let first = match &self.level_items(level)[index].kind {
+            Kind::Submenu(children) => selectable(children, None, true),
+            _ => return false,
+        };
+        self.highlight.truncate(level + 1);
+        self.highlight.push(first);
+        true
+    }
+}
+
+impl<A: Clone> State<A> {
+    fn navigate(&mut self, navigation: Navigation) -> Option<Option<A>> {
+        let level = self.highlight.len() - 1;
+        match navigation {
+            Navigation::Up | Navigation::Down => {
+                let next = selectable(
+                    self.level_items(level),
+                    self.highlight[level],
+                    navigation == Navigation::Down,
+                );
+                self.highlight[level] = next;
+            }
+            Navigation::Right => {
+                self.open_submenu(level);
+            }
+            Navigation::Left if level > 0 => {
+                self.highlight.pop();
+            }
+            Navigation::Left => {}
+            Navigation::Close if level > 0 => {
+                self.highlight.pop();
+            }
+            Navigation::Close => return Some(None),
+            Navigation::Activate => {
+                let index = self.highlight[level]?;
+                if !self.open_submenu(level) {
+                    return self.activate(level, index);
+                }
+            }
+        }
+        None
+    }
+
+    fn activate(&self, level: usize, index: usize) -> Option<Option<A>> {
+        let item = &self.level_items(level)[index];
+        match &item.kind {
+            Kind::Action(action) if item.enabled => Some(Some(action.clone())),
+            _ => None,
+        }
+    }
+}
+
+pub struct ContextMenu<A> {
+    id: usize,
+    state: Option<State<A>>,
+}
+
+impl<A> Default for ContextMenu<A> {
+    fn default() -> Self {
+        Self {
+            id: NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+            state: None,
+        }
+    }
+}
+
+impl<A: Clone> ContextMenu<A> {
+    pub fn is_open(&self) -> bool {
+        self.state.is_some()
+    }
+
+    pub fn open(&mut self, pos: egui::Pos2, items: Vec<Item<A>>) {
+        if items.is_empty() {
+            return;
+        }
+        self.state = Some(State {
+            pos,
+            items,
+            highlight: vec![None],
+        });
+        ACTIVE.store(self.id, std::sync::atomic::Ordering::Relaxed);
+        SHOWN.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub fn close(&mut self) {
+        if self.state.take().is_some() {
+            let _ = ACTIVE.compare_exchange(
+                self.id,
+                0,
+                std::sync::atomic::Ordering::Relaxed,
+                std::sync::atomic::Ordering::Relaxed,
+            );
+        }
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) -> Option<A> {
+        self.state.as_ref()?;
+        if ACTIVE.load(std::sync::atomic::Ordering::Relaxed) != self.id {
+            self.state = None;
+            return None;
+        }
+        SHOWN.store(true, std::sync::atomic::Ordering::Relaxed);
+        let state = self.state.as_mut().unwrap();
+
+        let mut result =
+            Navigation::from_code(PENDING_KEY.swap(0, std::sync::atomic::Ordering::Relaxed))
+                .and_then(|navigation| state.navigate(navigation));
+
+        let rects = state.level_rects();
+        let (pressed, pointer) = {
+            let input = ctx.input();
+            (input.mouse.pressed, input.mouse.pos)
+        };
+        if pressed && !pointer.is_some_and(|pos| rects.iter().any(|rect| rect.contains(pos))) {
+            result = Some(None);
+        }
+
+        for (level, rect) in rects.iter().enumerate() {
+            let mut hovered = None;
+            let mut clicked = None;
+            let items = state.level_items(level);
+            egui::Area::new(format!("context_menu_{}", level))
+                .fixed_pos(rect.min)
+                .show(ctx, |ui| {
+                    ui.allocate_space(rect.size());
+                    ui.painter()
+                        .rect_filled(*rect, 3.0, egui::Srgba::new(38, 40, 48, 250));
+                    ui.painter().rect_stroke(
+                        *rect,
+                        3.0,
+                        egui::Stroke::new(1.0, egui::Srgba::new(80, 84, 96, 255)),
+                    );
+                    let mut top = rect.min.y + PADDING;
+                    for (index, item) in items.iter().enumerate() {
+                        let row = egui::Rect::from_min_size(
+                            egui::pos2(rect.min.x + PADDING, top),
+                            egui::vec2(WIDTH - PADDING * 2.0, item.height()),
+                        );
+                        top += item.height();
+                        if let Kind::Separator = item.kind {
+                            ui.painter().line_segment(
+                                [row.left_center(), row.right_center()],
+                                egui::Stroke::new(1.0, egui::Srgba::new(70, 72, 82, 255)),
+                            );
+                            continue;
+                        }
+                        let response = ui.interact(
+                            row,
+                            ui.make_persistent_id(("context_menu_item", level, index)),
+                            egui::Sense::click(),
+                        );
+                        if response.hovered && item.enabled {
+                            hovered = Some(index);
+                        }
+                        if response.clicked && item.enabled {
+                            clicked = Some(index);
+                        }
+                        if state.highlight[level] == Some(index) {
+                            ui.painter()
+                                .rect_filled(row, 2.0, egui::Srgba::new(60, 90, 140, 255));
+                        }
+                        let color = if item.enabled {
+                            TEXT_COLOR
+                        } else {
+                            DISABLED_COLOR
+                        };
+                        let middle = row.center().y;
+                        if let Some(icon) = item.icon {
+                            ui.painter().text(
+                                egui::pos2(row.min.x + 10.0, middle),
+                                (egui::Align::Center, egui::Align::Center),
+                                icon.to_string(),
+                                egui::TextStyle::Body,
+                                color,
+                            );
+                        }
+                        ui.painter().text(
+                            egui::pos2(row.min.x + 24.0, middle),
+                            (egui::Align::Min, egui::Align::Center),
+                            item.label.clone(),
+                            egui::TextStyle::Body,
+                            color,
+                        );
+                        let right = match (&item.kind, &item.shortcut) {
+                            (Kind::Submenu(_), _) => Some("▸".to_owned()),
+                            (_, Some(shortcut)) => Some(shortcut.clone()),
+                            _ => None,
+                        };
+                        if let Some(right) = right {
+                            ui.painter().text(
+                                egui::pos2(row.max.x - 6.0, middle),
+                                (egui::Align::Max, egui::Align::Center),
+                                right,
+                                egui::TextStyle::Small,
+                                if item.enabled {
+                                    SHORTCUT_COLOR
+                                } else {
+                                    DISABLED_COLOR
+                                },
+                            );
+                        }
+                    }
+                });
+            if let Some(index) = hovered.filter(|index| state.highlight[level] != Some(*index)) {
+                state.highlight.truncate(level + 1);
+                state.highlight[level] = Some(index);
+                state.open_submenu(level);
+                break;
+            }
+            if let Some(index) = clicked {
+                if let Some(chosen) = state.activate(level, index) {
+                    result = Some(chosen);
+                }
+            }
+        }
+
+        match result {
+            Some(chosen) => {
+                self.close();
+                chosen
+            }
+            None => None,
+        }
+    }
+}
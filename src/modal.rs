@@ -0,0 +1,102 @@
+const BACKDROP: egui::Srgba = egui::Srgba::new(0, 0, 0, 140);
+const WARNING_COLOR: egui::Srgba = egui::Srgba::new(230, 180, 90, 255);
+const CONFIRM_WIDTH: f32 = 300.0;
+
+pub fn show(
+    ctx: &std::sync::Arc<egui::Context>,
+    screen: egui::Vec2,
+    id: &str,
+    width: f32,
+    add_contents: impl FnOnce(&mut egui::Ui),
+) {
+    let rect = egui::Rect::from_min_size(egui::Pos2::default(), screen);
+    egui::Area::new(format!("{}_backdrop", id))
+        .order(egui::Order::Foreground)
+        .interactable(false)
+        .fixed_pos(rect.min)
+        .show(ctx, |ui| {
+            ui.painter().rect_filled(rect, 0.0, BACKDROP);
+        });
+    egui::Area::new(id)
+        .order(egui::Order::Foreground)
+        .fixed_pos(rect.center() - egui::vec2(width / 2.0, 120.0))
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.allocate_space(egui::vec2(width, 0.0));
+                add_contents(ui);
+            });
+        });
+}
+
+#[derive(Default)]
+pub struct ConfirmQuit {
+    open: bool,
+    confirmed: bool,
+}
+
+impl ConfirmQuit {
+    pub fn request(&mut self) {
+        self.open = true;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn take_confirmed(&mut self) -> bool {
+        std::mem::take(&mut self.confirmed)
+    }
+
+    pub fn key_input(&mut self, input: &winit::event::KeyboardInput) -> bool {
+        if !self.open {
+            return false;
+        }
+        if input.state == winit::event::ElementState::Pressed {
+            match input.virtual_keycode {
+                Some(winit::event::VirtualKeyCode::Return)
+                | Some(winit::event::VirtualKeyCode::NumpadEnter) => {
+                    self.open = false;
+                    self.confirmed = true;
+                }
+                Some(winit::event::VirtualKeyCode::Escape) => self.open = false,
+                _ => {}
+            }
+        }
+        true
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &std::sync::Arc<egui::Context>,
+        screen: egui::Vec2,
+        unsaved: &[String],
+    ) {
+        if !self.open {
+            return;
+        }
+        let mut quit = false;
+        let mut cancel = false;
+        show(ctx, screen, "confirm_quit", CONFIRM_WIDTH, |ui| {
+            ui.heading("Quit?");
+            ui.label("Unsaved changes will be lost.");
+            for name in unsaved {
+                ui.add(egui::Label::new(format!("  {}", name)).text_color(WARNING_COLOR));
+            }
+            ui.horizontal(|ui| {
+                if ui.button("Quit").clicked {
+                    quit = true;
+                }
+                if ui.button("Cancel").clicked {
+                    cancel = true;
+                }
+            });
+            ui.label("Enter to quit, Escape to cancel");
+        });
+        if quit {
+            self.open = false;
+            self.confirmed = true;
+        } else if cancel {
+            self.open = false;
+        }
+    }
+}
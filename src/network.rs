@@ -0,0 +1,340 @@
+const HISTORY: usize = 300;
+const MAX_ALERTS: usize = 20;
+const PLOT_WIDTH: f32 = 420.0;
+const PLOT_HEIGHT: f32 = 160.0;
+const RX_COLOR: egui::Srgba = egui::Srgba::new(90, 170, 250, 255);
+const TX_COLOR: egui::Srgba = egui::Srgba::new(240, 140, 60, 255);
+
+#[derive(Clone, Copy)]
+struct Counters {
+    rx: u64,
+    tx: u64,
+}
+
+struct Rate {
+    time: f64,
+    rx: f64,
+    tx: f64,
+}
+
+struct Interface {
+    last: Counters,
+    history: std::collections::VecDeque<Rate>,
+    alerting: bool,
+}
+
+impl Interface {
+    fn latest(&self) -> (f64, f64) {
+        self.history
+            .back()
+            .map_or((0.0, 0.0), |rate| (rate.rx, rate.tx))
+    }
+}
+
+fn read_counters() -> Result<Vec<(String, Counters)>, String> {
+    let contents = std::fs::read_to_string("/proc/net/dev").map_err(|err| err.to_string())?;
+    let mut interfaces = Vec::new();
+    for line in contents.lines().skip(2) {
+        let mut parts = line.splitn(2, ':');
+        let name = parts.next().unwrap_or("").trim();
+        let fields: Vec<u64> = parts
+            .next()
+            .unwrap_or("")
+            .split_whitespace()
+            .filter_map(|field| field.parse().ok())
+            .collect();
+        if name.is_empty() || fields.len() < 9 {
+            continue;
+        }
+        interfaces.push((
+            name.to_owned(),
+            Counters {
+                rx: fields[0],
+                tx: fields[8],
+            },
+        ));
+    }
+    Ok(interfaces)
+}
+
+fn format_rate(bytes_per_second: f64) -> String {
+    format!(
+        "{}/s",
+        crate::duplicates::format_size(bytes_per_second.max(0.0) as u64)
+    )
+}
+
+pub struct NetworkMonitor {
+    pub open: bool,
+    interfaces: std::collections::BTreeMap<String, Interface>,
+    selected: Option<String>,
+    started: std::time::Instant,
+    last_poll: Option<std::time::Instant>,
+    error: Option<String>,
+    alerts_enabled: bool,
+    threshold: f32,
+    alerts: std::collections::VecDeque<String>,
+    path: String,
+    status: String,
+}
+
+impl Default for NetworkMonitor {
+    fn default() -> Self {
+        Self {
+            open: false,
+            interfaces: std::collections::BTreeMap::new(),
+            selected: None,
+            started: std::time::Instant::now(),
+            last_poll: None,
+            error: None,
+            alerts_enabled: false,
+            threshold: 10.0,
+            alerts: std::collections::VecDeque::with_capacity(MAX_ALERTS),
+            path: "network.csv".to_owned(),
+            status: String::new(),
+        }
+    }
+}
+
+impl NetworkMonitor {
+    fn poll(&mut self) {
+        let now = std::time::Instant::now();
+        let elapsed = match self.last_poll {
+            Some(last) if now.duration_since(last).as_secs_f64() < 1.0 => return,
+            Some(last) => now.duration_since(last).as_secs_f64(),
+            None => 0.0,
+        };
+        self.last_poll = Some(now);
+        let counters = match read_counters() {
+            Ok(counters) => counters,
+            Err(err) => {
+                if self.error.is_none() {
+                    log::warn!("cannot read network counters: {}", err);
+                }
+                self.error = Some(err);
+                return;
+            }
+        };
+        self.error = None;
+        let time = self.started.elapsed().as_secs_f64();
+        let threshold = self.threshold as f64 * 1024.0 * 1024.0;
+        for (name, current) in counters {
+            let interface = self.interfaces.entry(name.clone()).or_insert(Interface {
+                last: current,
+                history: std::collections::VecDeque::with_capacity(HISTORY),
+                alerting: false,
+            });
+            if elapsed > 0.0 {
+                let rate = Rate {
+                    time,
+                    rx: current.rx.saturating_sub(interface.last.rx) as f64 / elapsed,
+                    tx: current.tx.saturating_sub(interface.last.tx) as f64 / elapsed,
+                };
+                let over = self.alerts_enabled && rate.rx.max(rate.tx) > threshold;
+                if over && !interface.alerting {
+                    let message = format!(
+                        "{:.0}s {}: down {} up {}",
+                        time,
+                        name,
+                        format_rate(rate.rx),
+                        format_rate(rate.tx)
+                    );
+                    log::warn!("network alert: {}", message);
+                    if self.alerts.len() == MAX_ALERTS {
+                        self.alerts.pop_front();
+                    }
+                    self.alerts.push_back(message);
+                }
+                interface.alerting = over;
+                if interface.history.len() == HISTORY {
+                    interface.history.pop_front();
+                }
+                interface.history.push_back(rate);
+            }
+            interface.last = current;
+        }
+        if self.selected.is_none() {
+            self.selected = self
+                .interfaces
+                .keys()
+                .find(|name| name.as_str() != "lo")
+                .cloned();
+        }
+    }
+
+    fn export(&mut self) {
+        let path = std::path::PathBuf::from(&self.path);
+        let mut csv = String::from("time,interface,rx_bytes_per_second,tx_bytes_per_second\n");
+        let mut rows = 0;
+        for (name, interface) in self.interfaces.iter() {
+            for rate in interface.history.iter() {
+                csv.push_str(&format!(
+                    "{:.1},{},{:.0},{:.0}\n",
+                    rate.time, name, rate.rx, rate.tx
+                ));
+                rows += 1;
+            }
+        }
+        self.status = match std::fs::write(&path, csv) {
+            Ok(()) => {
+                log::info!("exported {} network samples to {}", rows, path.display());
+                format!("Saved {} samples to {}", rows, path.display())
+            }
+            Err(err) => format!("Cannot write {}: {}", path.display(), err),
+        };
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, screen: egui::Vec2) {
+        self.poll();
+
+        let (rx, tx) = self
+            .interfaces
+            .iter()
+            .filter(|(name, _)| name.as_str() != "lo")
+            .fold((0.0, 0.0), |(rx, tx), (_, interface)| {
+                let (interface_rx, interface_tx) = interface.latest();
+                (rx + interface_rx, tx + interface_tx)
+            });
+        let alerting = self.interfaces.values().any(|interface| interface.alerting);
+        let mut toggle = false;
+        egui::Area::new("network_status")
+            .fixed_pos(egui::pos2(
+                screen.x - 230.0,
+                screen.y - 32.0 - crate::status::HEIGHT,
+            ))
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        let color = if alerting {
+                            egui::Srgba::new(240, 80, 80, 255)
+                        } else {
+                            egui::Srgba::new(200, 200, 200, 255)
+                        };
+                        ui.add(
+                            egui::Label::new(format!(
+                                "down {}  up {}",
+                                format_rate(rx),
+                                format_rate(tx)
+                            ))
+                            .text_style(egui::TextStyle::Small)
+                            .text_color(color),
+                        );
+                        if ui.button("Details").clicked {
+                            toggle = true;
+                        }
+                    });
+                });
+            });
+        if toggle {
+            self.open = !self.open;
+        }
+
+        let mut open = self.open;
+        let mut export_requested = false;
+        let mut clear_requested = false;
+        let interfaces = &self.interfaces;
+        let selected = &mut self.selected;
+        let error = &self.error;
+        let alerts_enabled = &mut self.alerts_enabled;
+        let threshold = &mut self.threshold;
+        let alerts = &self.alerts;
+        let path = &mut self.path;
+        let status = &self.status;
+        crate::dock::Panel::new("Network")
+            .open(&mut open)
+            .default_pos(egui::pos2(380.0, 160.0))
+            .show(ctx, |ui| {
+                if let Some(error) = error {
+                    ui.label(format!(
+                        "Interface counters are read from /proc/net/dev: {}",
+                        error
+                    ));
+                    return;
+                }
+                for (name, interface) in interfaces.iter() {
+                    let (rx, tx) = interface.latest();
+                    let label =
+                        format!("{}  down {}  up {}", name, format_rate(rx), format_rate(tx));
+                    if ui
+                        .radio(selected.as_deref() == Some(name.as_str()), label)
+                        .clicked
+                    {
+                        *selected = Some(name.clone());
+                    }
+                }
+
+                if let Some(interface) = selected.as_ref().and_then(|name| interfaces.get(name)) {
+                    let rect = ui.allocate_space(egui::vec2(PLOT_WIDTH, PLOT_HEIGHT));
+                    let x_end = interface.history.back().map_or(0.0, |rate| rate.time);
+                    let peak = interface
+                        .history
+                        .iter()
+                        .fold(1024.0f64, |peak, rate| peak.max(rate.rx).max(rate.tx));
+                    let axes = crate::plots::Axes {
+                        rect,
+                        x_range: (x_end - HISTORY as f64, x_end),
+                        y_range: (0.0, peak * 1.1),
+                    };
+                    let painter = ui.painter();
+                    axes.paint_grid(
+                        painter,
+                        |x| format!("{:.0}s", x),
+                        |y| crate::duplicates::format_size(y.max(0.0) as u64),
+                    );
+                    axes.paint_line(
+                        painter,
+                        interface.history.iter().map(|rate| (rate.time, rate.rx)),
+                        RX_COLOR,
+                    );
+                    axes.paint_line(
+                        painter,
+                        interface.history.iter().map(|rate| (rate.time, rate.tx)),
+                        TX_COLOR,
+                    );
+                    crate::plots::paint_legend(
+                        painter,
+                        rect,
+                        &[("download", RX_COLOR), ("upload", TX_COLOR)],
+                    );
+                    ui.label(format!("peak {}", format_rate(peak)));
+                }
+
+                ui.separator();
+                ui.checkbox(alerts_enabled, "Alert when an interface exceeds");
+                ui.add(egui::Slider::f32(threshold, 0.1..=100.0).text("MB/s"));
+                if alerts.is_empty() {
+                    ui.label("No alerts.");
+                } else {
+                    egui::ScrollArea::from_max_height(100.0).show(ui, |ui| {
+                        for alert in alerts.iter().rev() {
+                            ui.label(alert.as_str());
+                        }
+                    });
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::new(path));
+                    if ui.button("Export CSV").clicked {
+                        export_requested = true;
+                    }
+                    if ui.button("Clear").clicked {
+                        clear_requested = true;
+                    }
+                });
+                if !status.is_empty() {
+                    ui.label(status.as_str());
+                }
+            });
+        self.open = open;
+        if export_requested {
+            self.export();
+        }
+        if clear_requested {
+            for interface in self.interfaces.values_mut() {
+                interface.history.clear();
+            }
+            self.alerts.clear();
+        }
+    }
+}
@@ -0,0 +1,115 @@
+pub struct Perlin {
+    permutation: [u8; 512],
+}
+
+impl Perlin {
+    pub fn new(seed: u32) -> Self {
+        let mut table = [0u8; 256];
+        for (index, value) in table.iter_mut().enumerate() {
+            *value = index as u8;
+        }
+        let mut state = seed.wrapping_mul(747_796_405).wrapping_add(2_891_336_453) | 1;
+        for index in (1..256).rev() {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            table.swap(index, state as usize % (index + 1));
+        }
+        let mut permutation = [0u8; 512];
+        for (index, value) in permutation.iter_mut().enumerate() {
+            *value = table[index & 255];
+        }
+        Self { permutation }
+    }
+
+    fn hash(&self, x: i32, y: i32, z: i32) -> u8 {
+        let p = &self.permutation;
+        p[(p[(p[(x & 255) as usize] as i32 + (y & 255)) as usize] as i32 + (z & 255)) as usize]
+    }
+
+    pub fn sample2(&self, x: f32, y: f32) -> f32 {
+        self.sample3(x, y, 0.5)
+    }
+
+    pub fn sample3(&self, x: f32, y: f32, z: f32) -> f32 {
+        let (xi, yi, zi) = (x.floor() as i32, y.floor() as i32, z.floor() as i32);
+        let (xf, yf, zf) = (x - x.floor(), y - y.floor(), z - z.floor());
+        let (u, v, w) = (fade(xf), fade(yf), fade(zf));
+
+        let corner = |dx: i32, dy: i32, dz: i32| {
+            gradient(
+                self.hash(xi + dx, yi + dy, zi + dz),
+                xf - dx as f32,
+                yf - dy as f32,
+                zf - dz as f32,
+            )
+        };
+        let x00 = lerp(corner(0, 0, 0), corner(1, 0, 0), u);
+        let x10 = lerp(corner(0, 1, 0), corner(1, 1, 0), u);
+        let x01 = lerp(corner(0, 0, 1), corner(1, 0, 1), u);
+        let x11 = lerp(corner(0, 1, 1), corner(1, 1, 1), u);
+        lerp(lerp(x00, x10, v), lerp(x01, x11, v), w)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Fbm {
+    pub seed: u32,
+    pub frequency: f32,
+    pub octaves: u32,
+    pub lacunarity: f32,
+    pub persistence: f32,
+}
+
+impl Default for Fbm {
+    fn default() -> Self {
+        Self {
+            seed: 1,
+            frequency: 0.05,
+            octaves: 5,
+            lacunarity: 2.0,
+            persistence: 0.5,
+        }
+    }
+}
+
+impl Fbm {
+    pub fn sample2(&self, perlin: &Perlin, x: f32, y: f32) -> f32 {
+        self.sample3(perlin, x, y, 0.5)
+    }
+
+    pub fn sample3(&self, perlin: &Perlin, x: f32, y: f32, z: f32) -> f32 {
+        let mut frequency = self.frequency;
+        let mut amplitude = 1.0;
+        let mut total = 0.0;
+        let mut norm = 0.0;
+        for _ in 0..self.octaves.max(1) {
+            total += perlin.sample3(x * frequency, y * frequency, z * frequency) * amplitude;
+            norm += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.persistence;
+        }
+        total / norm
+    }
+}
+
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+fn gradient(hash: u8, x: f32, y: f32, z: f32) -> f32 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
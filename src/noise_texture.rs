@@ -0,0 +1,467 @@
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+const PREVIEW_SIZE: f32 = 256.0;
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    params: [u32; 4],
+    fractal: [f32; 4],
+    options: [u32; 4],
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Kind {
+    Perlin,
+    Simplex,
+    Worley,
+}
+
+impl Kind {
+    pub const ALL: [Kind; 3] = [Kind::Perlin, Kind::Simplex, Kind::Worley];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Kind::Perlin => "Perlin",
+            Kind::Simplex => "Simplex",
+            Kind::Worley => "Worley",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Fractal {
+    Fbm,
+    Turbulence,
+    Ridged,
+}
+
+impl Fractal {
+    pub const ALL: [Fractal; 3] = [Fractal::Fbm, Fractal::Turbulence, Fractal::Ridged];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Fractal::Fbm => "fBm",
+            Fractal::Turbulence => "Turbulence",
+            Fractal::Ridged => "Ridged",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Ramp {
+    Grayscale,
+    Terrain,
+    Heat,
+}
+
+impl Ramp {
+    pub const ALL: [Ramp; 3] = [Ramp::Grayscale, Ramp::Terrain, Ramp::Heat];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Ramp::Grayscale => "Grayscale",
+            Ramp::Terrain => "Terrain",
+            Ramp::Heat => "Heat",
+        }
+    }
+}
+
+pub struct Settings {
+    pub kind: Kind,
+    pub fractal: Fractal,
+    pub ramp: Ramp,
+    pub seed: f32,
+    pub frequency: f32,
+    pub octaves: f32,
+    pub lacunarity: f32,
+    pub persistence: f32,
+    pub tileable: bool,
+    pub resolution: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            kind: Kind::Perlin,
+            fractal: Fractal::Fbm,
+            ramp: Ramp::Grayscale,
+            seed: 1.0,
+            frequency: 4.0,
+            octaves: 4.0,
+            lacunarity: 2.0,
+            persistence: 0.5,
+            tileable: false,
+            resolution: 512,
+        }
+    }
+}
+
+impl Settings {
+    fn uniforms(&self) -> Uniforms {
+        Uniforms {
+            params: [
+                self.kind as u32,
+                self.octaves as u32,
+                self.seed as u32,
+                self.resolution,
+            ],
+            fractal: [
+                self.frequency,
+                self.lacunarity,
+                self.persistence,
+                self.fractal as u32 as f32,
+            ],
+            options: [self.tileable() as u32, self.ramp as u32, 0, 0],
+        }
+    }
+
+    pub fn tileable(&self) -> bool {
+        self.tileable && self.kind != Kind::Simplex
+    }
+}
+
+struct Output {
+    resolution: u32,
+    texture: wgpu::Texture,
+    compute_bind_group: wgpu::BindGroup,
+    preview_bind_group: wgpu::BindGroup,
+}
+
+pub struct NoiseTexture {
+    pub open: bool,
+    pub settings: crate::store::Value<Settings>,
+    pub live: bool,
+    pub export_path: String,
+    pub export_requested: bool,
+    pub generate_requested: bool,
+    pub status: String,
+    pub generate_time: f32,
+    viewport: Option<egui::Rect>,
+    canvas: crate::blit::Canvas,
+    uniforms: crate::store::Derived<Uniforms>,
+    generated: crate::store::Watch,
+    pipeline: wgpu::ComputePipeline,
+    layout: wgpu::BindGroupLayout,
+    uniform_buffer: wgpu::Buffer,
+    blitter: crate::blit::Blitter,
+    output: Option<Output>,
+}
+
+impl NoiseTexture {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Noise Texture Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::COMPUTE,
+                    ty: wgpu::BindingType::UniformBuffer {
+                        dynamic: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStage::COMPUTE,
+                    ty: wgpu::BindingType::StorageTexture {
+                        dimension: wgpu::TextureViewDimension::D2,
+                        format: FORMAT,
+                        readonly: false,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let cs_module = crate::shader::compile_builtin(
+            device,
+            "noise.comp",
+            include_str!("shaders/noise.comp"),
+            shaderc::ShaderKind::Compute,
+        );
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Noise Texture Pipeline Layout"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Noise Texture Pipeline"),
+            layout: Some(&pipeline_layout),
+            compute_stage: wgpu::ProgrammableStageDescriptor {
+                module: &cs_module,
+                entry_point: "main",
+            },
+        });
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Noise Texture Uniforms"),
+            size: std::mem::size_of::<Uniforms>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let blitter = crate::blit::Blitter::new(
+            device,
+            format,
+            "noise_preview.frag",
+            include_str!("shaders/noise_preview.frag"),
+        );
+
+        Self {
+            open: false,
+            settings: crate::store::Value::default(),
+            live: true,
+            export_path: "noise.png".to_owned(),
+            export_requested: false,
+            generate_requested: false,
+            status: String::new(),
+            generate_time: 0.0,
+            viewport: None,
+            canvas: crate::blit::Canvas::new(format),
+            uniforms: crate::store::Derived::default(),
+            generated: crate::store::Watch::default(),
+            pipeline,
+            layout,
+            uniform_buffer,
+            blitter,
+            output: None,
+        }
+    }
+
+    pub fn recreate_gpu_resources(&mut self, device: &wgpu::Device, format: wgpu::TextureFormat) {
+        let settings = std::mem::take(&mut self.settings);
+        let export_path = std::mem::take(&mut self.export_path);
+        let (open, live) = (self.open, self.live);
+        *self = Self::new(device, format);
+        self.settings = settings;
+        self.export_path = export_path;
+        self.open = open;
+        self.live = live;
+    }
+
+    fn ensure_output(&mut self, device: &wgpu::Device) {
+        let resolution = self.settings.resolution;
+        if self.output.as_ref().map(|output| output.resolution) == Some(resolution) {
+            return;
+        }
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Noise Texture"),
+            size: wgpu::Extent3d {
+                width: resolution,
+                height: resolution,
+                depth: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: FORMAT,
+            usage: wgpu::TextureUsage::STORAGE
+                | wgpu::TextureUsage::SAMPLED
+                | wgpu::TextureUsage::COPY_SRC,
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let compute_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Noise Texture Bind Group"),
+            layout: &self.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(self.uniform_buffer.slice(..)),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+            ],
+        });
+        let preview_bind_group = self.blitter.bind(device, &view);
+        self.output = Some(Output {
+            resolution,
+            texture,
+            compute_bind_group,
+            preview_bind_group,
+        });
+        self.generated.reset();
+    }
+
+    fn generate(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        self.ensure_output(device);
+        self.generate_requested = false;
+        if !self.generated.changed(&[self.settings.version()]) {
+            return;
+        }
+        let settings = &self.settings;
+        let uniforms = *self
+            .uniforms
+            .get(&[settings.version()], || settings.uniforms());
+        let time = std::time::Instant::now();
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+        let output = self.output.as_ref().unwrap();
+        {
+            let groups = output.resolution.div_ceil(8);
+            let mut compute_pass = encoder.begin_compute_pass();
+            compute_pass.set_pipeline(&self.pipeline);
+            compute_pass.set_bind_group(0, &output.compute_bind_group, &[]);
+            compute_pass.dispatch(groups, groups, 1);
+        }
+        self.generate_time = time.elapsed().as_secs_f32();
+    }
+
+    pub fn export(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        uploader: &mut crate::upload::Uploader,
+    ) {
+        self.export_requested = false;
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Noise Texture Export Encoder"),
+        });
+        self.generate(device, uploader, &mut encoder);
+        uploader.finish();
+        queue.submit(std::iter::once(encoder.finish()));
+        uploader.recall();
+
+        let output = self.output.as_ref().unwrap();
+        let size = winit::dpi::PhysicalSize::new(output.resolution, output.resolution);
+        let path = std::path::PathBuf::from(&self.export_path);
+        let saved = crate::headless::read_texture(device, queue, &output.texture, size)
+            .and_then(|image| image.save(&path).map_err(|err| err.to_string()));
+        self.status = match saved {
+            Ok(()) => {
+                log::info!("exported noise texture to {}", path.display());
+                format!("Saved {}", path.display())
+            }
+            Err(err) => {
+                log::warn!("failed to export noise texture: {}", err);
+                format!("Export failed: {}", err)
+            }
+        };
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        let mut open = self.open;
+        let mut viewport = None;
+        let generate_time = self.generate_time;
+        let status = &self.status;
+        let export_requested = &mut self.export_requested;
+        let generate_requested = &mut self.generate_requested;
+        let settings = &mut self.settings;
+        let live = &mut self.live;
+        let export_path = &mut self.export_path;
+        let canvas = &self.canvas;
+        crate::dock::Panel::new("Noise Texture")
+            .open(&mut open)
+            .default_pos(egui::pos2(440.0, 100.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    for kind in Kind::ALL.iter() {
+                        crate::store::radio(ui, settings, |s| &mut s.kind, *kind, kind.name());
+                    }
+                });
+                crate::store::slider(ui, settings, |s| &mut s.seed, 0.0..=1000.0, "seed");
+                crate::store::slider(ui, settings, |s| &mut s.frequency, 1.0..=32.0, "frequency");
+                crate::store::slider(ui, settings, |s| &mut s.octaves, 1.0..=8.0, "octaves");
+                crate::store::slider(ui, settings, |s| &mut s.lacunarity, 1.0..=4.0, "lacunarity");
+                crate::store::slider(
+                    ui,
+                    settings,
+                    |s| &mut s.persistence,
+                    0.1..=1.0,
+                    "persistence",
+                );
+                ui.horizontal(|ui| {
+                    for fractal in Fractal::ALL.iter() {
+                        crate::store::radio(
+                            ui,
+                            settings,
+                            |s| &mut s.fractal,
+                            *fractal,
+                            fractal.name(),
+                        );
+                    }
+                });
+                ui.horizontal(|ui| {
+                    for ramp in Ramp::ALL.iter() {
+                        crate::store::radio(ui, settings, |s| &mut s.ramp, *ramp, ramp.name());
+                    }
+                });
+                crate::store::checkbox(
+                    ui,
+                    settings,
+                    |s| &mut s.tileable,
+                    "Tileable (rounds each octave to whole periods)",
+                );
+                if settings.tileable && settings.kind == Kind::Simplex {
+                    ui.label("Simplex noise does not tile; switch to Perlin or Worley");
+                }
+                ui.horizontal(|ui| {
+                    ui.label("Resolution");
+                    for resolution in [256, 512, 1024, 2048].iter() {
+                        crate::store::radio(
+                            ui,
+                            settings,
+                            |s| &mut s.resolution,
+                            *resolution,
+                            resolution.to_string(),
+                        );
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(live, "Live preview");
+                    if !*live && ui.button("Generate").clicked {
+                        *generate_requested = true;
+                    }
+                });
+                ui.label(format!("Encoded in {:.3} ms", generate_time * 1000.0));
+                let rect = ui.allocate_space(egui::vec2(PREVIEW_SIZE, PREVIEW_SIZE));
+                canvas.paint(ui, rect);
+                viewport = Some(rect);
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::new(export_path));
+                    if ui.button("Export PNG").clicked {
+                        *export_requested = true;
+                    }
+                });
+                if !status.is_empty() {
+                    ui.label(status.as_str());
+                }
+            });
+        self.open = open;
+        self.viewport = viewport.filter(|_| open);
+    }
+
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        ui_pass: &mut crate::ui_pass::RenderPass,
+        size: winit::dpi::PhysicalSize<u32>,
+        scale_factor: f64,
+    ) {
+        let viewport = match self.viewport {
+            Some(rect) => crate::blit::to_physical(rect, scale_factor),
+            None => return,
+        };
+        if !crate::blit::fits(viewport, size) {
+            return;
+        }
+        if self.live || self.generate_requested || self.output.is_none() {
+            self.generate(device, uploader, encoder);
+        }
+        let output = self.output.as_ref().unwrap();
+        let target = self.canvas.begin(device, encoder, ui_pass, size);
+        self.blitter
+            .draw_in(encoder, &output.preview_bind_group, target, viewport);
+    }
+}
@@ -0,0 +1,822 @@
+const PAGE_SIZE: [f32; 2] = [420.0, 560.0];
+const EXPORT_SCALE: f32 = 2.0;
+const ONION_PREVIOUS: [u8; 3] = [220, 60, 60];
+const ONION_NEXT: [u8; 3] = [40, 150, 80];
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct InkStroke {
+    points: Vec<[f32; 3]>,
+    color: [u8; 4],
+    width: f32,
+}
+
+impl InkStroke {
+    fn bounds(&self) -> egui::Rect {
+        let mut rect = egui::Rect::nothing();
+        for [x, y, _] in self.points.iter() {
+            rect.extend_with(egui::pos2(*x, *y));
+        }
+        rect
+    }
+
+    fn smooth(&mut self) {
+        if self.points.len() < 3 {
+            return;
+        }
+        let mut smoothed = vec![self.points[0]];
+        for pair in self.points.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let lerp = |t: f32| {
+                [
+                    a[0] + (b[0] - a[0]) * t,
+                    a[1] + (b[1] - a[1]) * t,
+                    a[2] + (b[2] - a[2]) * t,
+                ]
+            };
+            smoothed.push(lerp(0.25));
+            smoothed.push(lerp(0.75));
+        }
+        smoothed.push(*self.points.last().unwrap());
+        self.points = smoothed;
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+struct Layer {
+    name: String,
+    strokes: Vec<InkStroke>,
+    opacity: f32,
+    visible: bool,
+}
+
+impl Default for Layer {
+    fn default() -> Self {
+        Self {
+            name: "Layer 1".to_owned(),
+            strokes: Vec::new(),
+            opacity: 1.0,
+            visible: true,
+        }
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct Page {
+    #[serde(default, skip_serializing)]
+    strokes: Vec<InkStroke>,
+    #[serde(default)]
+    layers: Vec<Layer>,
+}
+
+impl Default for Page {
+    fn default() -> Self {
+        Self {
+            strokes: Vec::new(),
+            layers: vec![Layer::default()],
+        }
+    }
+}
+
+impl Page {
+    fn migrate(&mut self) {
+        if self.layers.is_empty() {
+            self.layers.push(Layer::default());
+        }
+        let strokes = std::mem::take(&mut self.strokes);
+        self.layers[0].strokes.extend(strokes);
+    }
+
+    fn blank(&self) -> Self {
+        Self {
+            strokes: Vec::new(),
+            layers: self
+                .layers
+                .iter()
+                .map(|layer| Layer {
+                    strokes: Vec::new(),
+                    ..layer.clone()
+                })
+                .collect(),
+        }
+    }
+
+    fn visible_strokes(&self) -> impl Iterator<Item = (&InkStroke, f32)> {
+        self.layers
+            .iter()
+            .filter(|layer| layer.visible)
+            .flat_map(|layer| {
+                layer
+                    .strokes
+                    .iter()
+                    .map(move |stroke| (stroke, layer.opacity))
+            })
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Notebook {
+    pages: Vec<Page>,
+}
+
+impl Default for Notebook {
+    fn default() -> Self {
+        Self {
+            pages: vec![Page::default()],
+        }
+    }
+}
+
+fn faded(color: [u8; 4], opacity: f32) -> egui::Srgba {
+    let scale = |channel: u8| (channel as f32 * opacity) as u8;
+    egui::Srgba::new(
+        scale(color[0]),
+        scale(color[1]),
+        scale(color[2]),
+        scale(color[3]),
+    )
+}
+
+fn notebook_directory() -> Option<std::path::PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")).join("notebooks"))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Tool {
+    Pen,
+    Eraser,
+    Lasso,
+}
+
+enum Gesture {
+    Drawing(InkStroke),
+    Lasso(Vec<egui::Pos2>),
+    Moving(egui::Pos2),
+    Erasing,
+}
+
+fn inside(polygon: &[egui::Pos2], point: egui::Pos2) -> bool {
+    let mut inside = false;
+    let mut previous = match polygon.last() {
+        Some(previous) => *previous,
+        None => return false,
+    };
+    for current in polygon.iter() {
+        if (current.y > point.y) != (previous.y > point.y)
+            && point.x
+                < (previous.x - current.x) * (point.y - current.y) / (previous.y - current.y)
+                    + current.x
+        {
+            inside = !inside;
+        }
+        previous = *current;
+    }
+    inside
+}
+
+fn stamp(image: &mut image::RgbaImage, center: [f32; 2], radius: f32, color: [u8; 4]) {
+    let (width, height) = image.dimensions();
+    let min_x = (center[0] - radius).floor().max(0.0) as u32;
+    let min_y = (center[1] - radius).floor().max(0.0) as u32;
+    let max_x = ((center[0] + radius).ceil() as u32).min(width.saturating_sub(1));
+    let max_y = ((center[1] + radius).ceil() as u32).min(height.saturating_sub(1));
+    for y in min_y..=max_y {
+        for x in min_x..=max_x {
+            let dx = x as f32 + 0.5 - center[0];
+            let dy = y as f32 + 0.5 - center[1];
+            let coverage = (radius + 0.5 - (dx * dx + dy * dy).sqrt()).clamp(0.0, 1.0);
+            if coverage <= 0.0 {
+                continue;
+            }
+            let pixel = image.get_pixel_mut(x, y);
+            let alpha = coverage * color[3] as f32 / 255.0;
+            for (value, source) in pixel.0.iter_mut().zip(color.iter()).take(3) {
+                *value = (*value as f32 * (1.0 - alpha) + *source as f32 * alpha) as u8;
+            }
+        }
+    }
+}
+
+fn render_png(page: &Page, scale: f32) -> image::RgbaImage {
+    let width = (PAGE_SIZE[0] * scale) as u32;
+    let height = (PAGE_SIZE[1] * scale) as u32;
+    let mut image = image::RgbaImage::from_pixel(width, height, image::Rgba([255, 255, 255, 255]));
+    for (stroke, opacity) in page.visible_strokes() {
+        let [r, g, b, a] = stroke.color;
+        let color = [r, g, b, (a as f32 * opacity) as u8];
+        for pair in stroke.points.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let length = ((b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2)).sqrt() * scale;
+            let steps = length.ceil().max(1.0) as u32;
+            for step in 0..=steps {
+                let t = step as f32 / steps as f32;
+                let pressure = a[2] + (b[2] - a[2]) * t;
+                stamp(
+                    &mut image,
+                    [
+                        (a[0] + (b[0] - a[0]) * t) * scale,
+                        (a[1] + (b[1] - a[1]) * t) * scale,
+                    ],
+                    stroke.width * pressure * scale * 0.5,
+                    color,
+                );
+            }
+        }
+    }
+    image
+}
+
+fn render_svg(page: &Page) -> String {
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+        PAGE_SIZE[0], PAGE_SIZE[1], PAGE_SIZE[0], PAGE_SIZE[1]
+    );
+    svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+    for layer in page.layers.iter().filter(|layer| layer.visible) {
+        svg.push_str(&format!("<g opacity=\"{:.3}\">\n", layer.opacity));
+        for stroke in layer.strokes.iter() {
+            let [r, g, b, a] = stroke.color;
+            svg.push_str(&format!(
+                "<g stroke=\"rgb({},{},{})\" stroke-opacity=\"{:.3}\" stroke-linecap=\"round\">\n",
+                r,
+                g,
+                b,
+                a as f32 / 255.0
+            ));
+            for pair in stroke.points.windows(2) {
+                let (p, q) = (pair[0], pair[1]);
+                svg.push_str(&format!(
+                "<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke-width=\"{:.2}\"/>\n",
+                p[0],
+                p[1],
+                q[0],
+                q[1],
+                stroke.width * (p[2] + q[2]) * 0.5
+            ));
+            }
+            svg.push_str("</g>\n");
+        }
+        svg.push_str("</g>\n");
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+pub struct NotesPanel {
+    pub open: bool,
+    name: String,
+    notebook: Option<Notebook>,
+    page: usize,
+    layer: usize,
+    onion_skin: bool,
+    onion_frames: f32,
+    frame_rate: f32,
+    tool: Tool,
+    color: egui::Srgba,
+    width: f32,
+    smoothing: f32,
+    gesture: Option<Gesture>,
+    selection: Vec<usize>,
+    force: Option<f32>,
+    last_sample: Option<(egui::Pos2, std::time::Instant, f32)>,
+    status: String,
+    exporting: Option<(std::path::PathBuf, usize, crate::jobs::Handle<()>)>,
+}
+
+impl Default for NotesPanel {
+    fn default() -> Self {
+        Self {
+            open: false,
+            name: "notes".to_owned(),
+            notebook: None,
+            page: 0,
+            layer: 0,
+            onion_skin: false,
+            onion_frames: 1.0,
+            frame_rate: 8.0,
+            tool: Tool::Pen,
+            color: egui::Srgba::new(20, 20, 40, 255),
+            width: 3.0,
+            smoothing: 0.5,
+            gesture: None,
+            selection: Vec::new(),
+            force: None,
+            last_sample: None,
+            status: String::new(),
+            exporting: None,
+        }
+    }
+}
+
+impl NotesPanel {
+    pub fn touch(&mut self, touch: &winit::event::Touch) {
+        self.force = match touch.phase {
+            winit::event::TouchPhase::Started | winit::event::TouchPhase::Moved => {
+                touch.force.map(|force| force.normalized() as f32)
+            }
+            winit::event::TouchPhase::Ended | winit::event::TouchPhase::Cancelled => None,
+        };
+    }
+
+    fn path(&self) -> Option<std::path::PathBuf> {
+        notebook_directory().map(|dir| dir.join(format!("{}.json", self.name)))
+    }
+
+    fn load(&mut self) {
+        self.notebook = Some(
+            self.path()
+                .and_then(|path| std::fs::read_to_string(&path).ok())
+                .and_then(|contents| {
+                    serde_json::from_str(&contents)
+                        .map_err(|err| {
+                            log::warn!("ignoring invalid notebook {}: {}", self.name, err)
+                        })
+                        .ok()
+                })
+                .unwrap_or_default(),
+        );
+        for page in self.notebook.as_mut().unwrap().pages.iter_mut() {
+            page.migrate();
+        }
+        self.page = 0;
+        self.layer = 0;
+        self.selection.clear();
+        self.gesture = None;
+    }
+
+    fn save(&mut self) {
+        let (path, notebook) = match (self.path(), &self.notebook) {
+            (Some(path), Some(notebook)) => (path, notebook),
+            _ => return,
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&path, serde_json::to_string(notebook).unwrap()));
+        match result {
+            Ok(()) => log::info!("saved notebook to {}", path.display()),
+            Err(err) => self.status = format!("Cannot save {}: {}", path.display(), err),
+        }
+    }
+
+    fn export(&mut self, svg: bool) {
+        let page = &self.notebook.as_ref().unwrap().pages[self.page];
+        let directory = match notebook_directory() {
+            Some(directory) => directory,
+            None => return,
+        };
+        let path = directory.join(format!(
+            "{}-page{}.{}",
+            self.name,
+            self.page + 1,
+            if svg { "svg" } else { "png" }
+        ));
+        if let Err(err) = std::fs::create_dir_all(&directory) {
+            self.status = format!("Cannot create {}: {}", directory.display(), err);
+            return;
+        }
+        let result = if svg {
+            std::fs::write(&path, render_svg(page)).map_err(|err| err.to_string())
+        } else {
+            render_png(page, EXPORT_SCALE)
+                .save(&path)
+                .map_err(|err| err.to_string())
+        };
+        self.status = match result {
+            Ok(()) => format!("Exported {}", path.display()),
+            Err(err) => format!("Cannot export {}: {}", path.display(), err),
+        };
+    }
+
+    fn export_gif(&mut self) {
+        let directory = match notebook_directory() {
+            Some(directory) => directory,
+            None => return,
+        };
+        let path = directory.join(format!("{}.gif", self.name));
+        if let Err(err) = std::fs::create_dir_all(&directory) {
+            self.status = format!("Cannot create {}: {}", directory.display(), err);
+            return;
+        }
+        let frames = self
+            .notebook
+            .as_ref()
+            .unwrap()
+            .pages
+            .iter()
+            .map(|page| render_png(page, 1.0))
+            .collect::<Vec<_>>();
+        let count = frames.len();
+        let frame_rate = self.frame_rate;
+        let output = path.clone();
+        let handle = crate::jobs::spawn(format!("Export {}.gif", self.name), move |progress| {
+            crate::turntable::write_gif(&output, frames, frame_rate, progress)
+        });
+        self.exporting = Some((path, count, handle));
+        self.status = format!("Encoding {} frames", count);
+    }
+
+    fn poll_export(&mut self) {
+        let result = match &self.exporting {
+            Some((_, _, handle)) => handle.poll(),
+            None => return,
+        };
+        if let Some(result) = result {
+            let (path, count, _) = self.exporting.take().unwrap();
+            self.status = match result {
+                Ok(()) => format!("Exported {} frames to {}", count, path.display()),
+                Err(err) => format!("Cannot export {}: {}", path.display(), err),
+            };
+        }
+    }
+
+    fn layers(&mut self, ui: &mut egui::Ui) -> bool {
+        let layers = &mut self.notebook.as_mut().unwrap().pages[self.page].layers;
+        let mut changed = false;
+        let mut moved = None;
+        let mut removed = None;
+        let selected = self.layer;
+        let active = &mut self.layer;
+        for index in (0..layers.len()).rev() {
+            let count = layers.len();
+            let layer = &mut layers[index];
+            ui.horizontal(|ui| {
+                ui.radio_value(active, index, "");
+                changed |= ui.checkbox(&mut layer.visible, "").clicked;
+                let previous = layer.opacity;
+                ui.add(egui::Slider::f32(&mut layer.opacity, 0.0..=1.0).text("opacity"));
+                changed |= (layer.opacity - previous).abs() > f32::EPSILON;
+                if index + 1 < count && ui.button("⏶").clicked {
+                    moved = Some((index, index + 1));
+                }
+                if index > 0 && ui.button("⏷").clicked {
+                    moved = Some((index, index - 1));
+                }
+                if count > 1 && ui.button("x").clicked {
+                    removed = Some(index);
+                }
+                ui.add(egui::TextEdit::new(&mut layer.name));
+            });
+        }
+        if let Some((from, to)) = moved {
+            layers.swap(from, to);
+            if self.layer == from {
+                self.layer = to;
+            } else if self.layer == to {
+                self.layer = from;
+            }
+            changed = true;
+        }
+        if let Some(index) = removed {
+            layers.remove(index);
+            if self.layer >= index && self.layer > 0 {
+                self.layer -= 1;
+            }
+            changed = true;
+        }
+        if ui.button("Add layer").clicked {
+            layers.insert(
+                self.layer + 1,
+                Layer {
+                    name: format!("Layer {}", layers.len() + 1),
+                    ..Layer::default()
+                },
+            );
+            self.layer += 1;
+            changed = true;
+        }
+        if changed || self.layer != selected {
+            self.selection.clear();
+        }
+        changed
+    }
+
+    fn pressure(&mut self, position: egui::Pos2) -> f32 {
+        let now = std::time::Instant::now();
+        let pressure = match (self.force, self.last_sample) {
+            (Some(force), _) => force.max(0.05),
+            (None, Some((last, time, previous))) => {
+                let elapsed = (now - time).as_secs_f32().max(1.0 / 240.0);
+                let speed = (position - last).length() / elapsed;
+                let target = (1.2 - speed / 1500.0).clamp(0.35, 1.0);
+                previous + (target - previous) * 0.3
+            }
+            (None, None) => 0.8,
+        };
+        self.last_sample = Some((position, now, pressure));
+        pressure
+    }
+
+    fn canvas(&mut self, ui: &mut egui::Ui) -> bool {
+        let rect = ui.allocate_space(egui::vec2(PAGE_SIZE[0], PAGE_SIZE[1]));
+        let response = ui.interact(
+            rect,
+            ui.make_persistent_id("notes_canvas"),
+            egui::Sense::drag(),
+        );
+        let mouse = ui.input().mouse.pos.map(|pos| pos - rect.min);
+        let mouse = mouse.map(|offset| egui::pos2(offset.x, offset.y));
+        let mut changed = false;
+
+        match (self.gesture.is_some(), response.active, mouse) {
+            (false, true, Some(point)) => {
+                self.last_sample = None;
+                self.gesture = Some(match self.tool {
+                    Tool::Pen => {
+                        let pressure = self.pressure(point);
+                        Gesture::Drawing(InkStroke {
+                            points: vec![[point.x, point.y, pressure]],
+                            color: self.color.to_array(),
+                            width: self.width,
+                        })
+                    }
+                    Tool::Eraser => Gesture::Erasing,
+                    Tool::Lasso => {
+                        let strokes = &self.notebook.as_ref().unwrap().pages[self.page].layers
+                            [self.layer]
+                            .strokes;
+                        let grabbed = self
+                            .selection
+                            .iter()
+                            .any(|index| strokes[*index].bounds().expand(4.0).contains(point));
+                        if grabbed {
+                            Gesture::Moving(point)
+                        } else {
+                            self.selection.clear();
+                            Gesture::Lasso(vec![point])
+                        }
+                    }
+                });
+            }
+            (true, true, Some(point)) => {
+                let smoothing = self.smoothing;
+                let pressure = self.pressure(point);
+                let strokes = &mut self.notebook.as_mut().unwrap().pages[self.page].layers
+                    [self.layer]
+                    .strokes;
+                match self.gesture.as_mut().unwrap() {
+                    Gesture::Drawing(stroke) => {
+                        let [x, y, _] = *stroke.points.last().unwrap();
+                        let x = point.x + (x - point.x) * smoothing * 0.8;
+                        let y = point.y + (y - point.y) * smoothing * 0.8;
+                        stroke.points.push([x, y, pressure]);
+                    }
+                    Gesture::Lasso(points) => points.push(point),
+                    Gesture::Moving(last) => {
+                        let delta = point - *last;
+                        for index in self.selection.iter() {
+                            for p in strokes[*index].points.iter_mut() {
+                                p[0] += delta.x;
+                                p[1] += delta.y;
+                            }
+                        }
+                        *last = point;
+                    }
+                    Gesture::Erasing => {
+                        let before = strokes.len();
+                        strokes.retain(|stroke| {
+                            !stroke.points.iter().any(|p| {
+                                (egui::pos2(p[0], p[1]) - point).length() < stroke.width + 4.0
+                            })
+                        });
+                        if strokes.len() != before {
+                            self.selection.clear();
+                            changed = true;
+                        }
+                    }
+                }
+            }
+            (true, false, _) => {
+                let strokes = &mut self.notebook.as_mut().unwrap().pages[self.page].layers
+                    [self.layer]
+                    .strokes;
+                match self.gesture.take().unwrap() {
+                    Gesture::Drawing(mut stroke) => {
+                        if self.smoothing > 0.0 {
+                            stroke.smooth();
+                        }
+                        strokes.push(stroke);
+                        changed = true;
+                    }
+                    Gesture::Lasso(polygon) => {
+                        self.selection = strokes
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, stroke)| {
+                                stroke
+                                    .points
+                                    .iter()
+                                    .all(|p| inside(&polygon, egui::pos2(p[0], p[1])))
+                            })
+                            .map(|(index, _)| index)
+                            .collect();
+                    }
+                    Gesture::Moving(_) => changed = true,
+                    Gesture::Erasing => {}
+                }
+            }
+            _ => {}
+        }
+
+        let pages = &self.notebook.as_ref().unwrap().pages;
+        let page = &pages[self.page];
+        let painter = ui.painter();
+        painter.rect_filled(rect, 2.0, egui::Srgba::new(250, 250, 245, 255));
+        let to_screen = |p: &[f32; 3]| rect.min + egui::vec2(p[0], p[1]);
+        let draw = |stroke: &InkStroke, color: egui::Srgba| {
+            if stroke.points.len() == 1 {
+                let center = to_screen(&stroke.points[0]);
+                painter.rect_filled(
+                    egui::Rect::from_center_size(center, egui::vec2(stroke.width, stroke.width)),
+                    stroke.width * 0.5,
+                    color,
+                );
+            }
+            for pair in stroke.points.windows(2) {
+                let width = stroke.width * (pair[0][2] + pair[1][2]) * 0.5;
+                painter.line_segment(
+                    [to_screen(&pair[0]), to_screen(&pair[1])],
+                    egui::Stroke::new(width, color),
+                );
+            }
+        };
+        if self.onion_skin {
+            for distance in (1..=self.onion_frames as usize).rev() {
+                let opacity = 0.35 / distance as f32;
+                let neighbours = [
+                    (self.page.checked_sub(distance), ONION_PREVIOUS),
+                    (Some(self.page + distance), ONION_NEXT),
+                ];
+                for (index, [r, g, b]) in neighbours.iter() {
+                    let neighbour = match index.and_then(|index| pages.get(index)) {
+                        Some(neighbour) => neighbour,
+                        None => continue,
+                    };
+                    for (stroke, layer_opacity) in neighbour.visible_strokes() {
+                        draw(stroke, faded([*r, *g, *b, 255], opacity * layer_opacity));
+                    }
+                }
+            }
+        }
+        for (layer_index, layer) in page.layers.iter().enumerate() {
+            if !layer.visible {
+                continue;
+            }
+            for (index, stroke) in layer.strokes.iter().enumerate() {
+                if layer_index == self.layer && self.selection.contains(&index) {
+                    let bounds = stroke.bounds().expand(3.0);
+                    painter.rect_filled(
+                        egui::Rect::from_min_max(
+                            rect.min + egui::vec2(bounds.min.x, bounds.min.y),
+                            rect.min + egui::vec2(bounds.max.x, bounds.max.y),
+                        ),
+                        2.0,
+                        egui::Srgba::new(150, 200, 255, 60),
+                    );
+                }
+                draw(stroke, faded(stroke.color, layer.opacity));
+            }
+        }
+        match &self.gesture {
+            Some(Gesture::Drawing(stroke)) => {
+                draw(stroke, faded(stroke.color, page.layers[self.layer].opacity))
+            }
+            Some(Gesture::Lasso(points)) => {
+                for pair in points.windows(2) {
+                    painter.line_segment(
+                        [
+                            rect.min + egui::vec2(pair[0].x, pair[0].y),
+                            rect.min + egui::vec2(pair[1].x, pair[1].y),
+                        ],
+                        egui::Stroke::new(1.0, egui::Srgba::new(60, 120, 220, 255)),
+                    );
+                }
+            }
+            _ => {}
+        }
+        changed
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        self.poll_export();
+        if !self.open {
+            return;
+        }
+        if self.notebook.is_none() {
+            self.load();
+        }
+
+        let mut open = self.open;
+        crate::dock::Panel::new("Notes")
+            .open(&mut open)
+            .default_pos(egui::pos2(400.0, 40.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Notebook");
+                    ui.add(egui::TextEdit::new(&mut self.name));
+                    if ui.button("Open").clicked {
+                        self.load();
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.tool, Tool::Pen, "Pen");
+                    ui.radio_value(&mut self.tool, Tool::Eraser, "Eraser");
+                    ui.radio_value(&mut self.tool, Tool::Lasso, "Lasso");
+                    egui::color_picker::color_edit_button_srgba(ui, &mut self.color);
+                });
+                ui.add(egui::Slider::f32(&mut self.width, 0.5..=16.0).text("width"));
+                ui.add(egui::Slider::f32(&mut self.smoothing, 0.0..=1.0).text("smoothing"));
+
+                let pages = self.notebook.as_ref().unwrap().pages.len();
+                let mut changed = false;
+                ui.horizontal(|ui| {
+                    if ui.button("<").clicked && self.page > 0 {
+                        self.page -= 1;
+                        self.selection.clear();
+                    }
+                    ui.label(format!("Page {} / {}", self.page + 1, pages));
+                    if ui.button(">").clicked && self.page + 1 < pages {
+                        self.page += 1;
+                        self.selection.clear();
+                    }
+                    if ui.button("New page").clicked {
+                        let notebook = self.notebook.as_mut().unwrap();
+                        let page = notebook.pages[self.page].blank();
+                        notebook.pages.insert(self.page + 1, page);
+                        self.page += 1;
+                        self.selection.clear();
+                        changed = true;
+                    }
+                    if ui.button("Duplicate").clicked {
+                        let notebook = self.notebook.as_mut().unwrap();
+                        let page = notebook.pages[self.page].clone();
+                        notebook.pages.insert(self.page + 1, page);
+                        self.page += 1;
+                        self.selection.clear();
+                        changed = true;
+                    }
+                    if pages > 1 && ui.button("Delete page").clicked {
+                        let notebook = self.notebook.as_mut().unwrap();
+                        notebook.pages.remove(self.page);
+                        self.page = self.page.min(notebook.pages.len() - 1);
+                        self.selection.clear();
+                        changed = true;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    if !self.selection.is_empty() && ui.button("Delete selection").clicked {
+                        let layer = &mut self.notebook.as_mut().unwrap().pages[self.page].layers
+                            [self.layer];
+                        let selection = std::mem::take(&mut self.selection);
+                        let mut index = 0;
+                        layer.strokes.retain(|_| {
+                            index += 1;
+                            !selection.contains(&(index - 1))
+                        });
+                        changed = true;
+                    }
+                    if ui.button("Clear layer").clicked {
+                        self.notebook.as_mut().unwrap().pages[self.page].layers[self.layer]
+                            .strokes
+                            .clear();
+                        self.selection.clear();
+                        changed = true;
+                    }
+                    if ui.button("Export PNG").clicked {
+                        self.export(false);
+                    }
+                    if ui.button("Export SVG").clicked {
+                        self.export(true);
+                    }
+                });
+                let layers = self.notebook.as_ref().unwrap().pages[self.page]
+                    .layers
+                    .len();
+                self.layer = self.layer.min(layers - 1);
+                ui.collapsing("Layers", |ui| changed |= self.layers(ui));
+                ui.collapsing("Animation", |ui| {
+                    ui.checkbox(&mut self.onion_skin, "Onion skin");
+                    if self.onion_skin {
+                        ui.add(egui::Slider::f32(&mut self.onion_frames, 1.0..=3.0).text("frames"));
+                    }
+                    ui.add(egui::Slider::f32(&mut self.frame_rate, 1.0..=24.0).text("fps"));
+                    if ui.button("Export GIF").clicked {
+                        self.export_gif();
+                    }
+                });
+
+                changed |= self.canvas(ui);
+                if changed {
+                    self.save();
+                }
+                ui.label(match self.force {
+                    Some(force) => format!("Pen pressure {:.2}", force),
+                    None => "No pen pressure, width follows stroke speed".to_owned(),
+                });
+                if !self.status.is_empty() {
+                    ui.label(self.status.as_str());
+                }
+            });
+        self.open = open;
+    }
+}
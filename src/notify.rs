@@ -0,0 +1,140 @@
+const TOAST_SECONDS: f32 = 5.0;
+const FADE_SECONDS: f32 = 0.5;
+const MAX_TOASTS: usize = 5;
+const WIDTH: f32 = 280.0;
+const PADDING: f32 = 8.0;
+const SPACING: f32 = 6.0;
+const MARGIN: f32 = 12.0;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Severity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    fn color(self) -> egui::Srgba {
+        match self {
+            Severity::Info => egui::Srgba::new(90, 170, 250, 255),
+            Severity::Success => egui::Srgba::new(110, 200, 120, 255),
+            Severity::Warning => egui::Srgba::new(230, 180, 90, 255),
+            Severity::Error => egui::Srgba::new(230, 110, 100, 255),
+        }
+    }
+}
+
+struct Toast {
+    id: u64,
+    severity: Severity,
+    text: String,
+    time: std::time::Instant,
+}
+
+#[derive(Default)]
+struct State {
+    toasts: Vec<Toast>,
+    next_id: u64,
+}
+
+thread_local! {
+    static STATE: std::cell::RefCell<State> = std::cell::RefCell::new(State::default());
+}
+
+fn push(severity: Severity, text: String) {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.toasts.push(Toast {
+            id,
+            severity,
+            text,
+            time: std::time::Instant::now(),
+        });
+        let overflow = state.toasts.len().saturating_sub(MAX_TOASTS);
+        state.toasts.drain(..overflow);
+    });
+}
+
+pub fn info(text: impl Into<String>) {
+    push(Severity::Info, text.into());
+}
+
+pub fn success(text: impl Into<String>) {
+    push(Severity::Success, text.into());
+}
+
+pub fn warning(text: impl Into<String>) {
+    push(Severity::Warning, text.into());
+}
+
+pub fn error(text: impl Into<String>) {
+    push(Severity::Error, text.into());
+}
+
+fn faded(color: egui::Srgba, alpha: f32) -> egui::Srgba {
+    let [r, g, b, a] = color.to_array();
+    let scale = |channel: u8| (channel as f32 * alpha) as u8;
+    egui::Srgba::new(scale(r), scale(g), scale(b), scale(a))
+}
+
+pub fn show(ctx: &std::sync::Arc<egui::Context>, screen: egui::Vec2) {
+    let toasts: Vec<(u64, Severity, String, f32)> = STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state
+            .toasts
+            .retain(|toast| toast.time.elapsed().as_secs_f32() < TOAST_SECONDS);
+        state
+            .toasts
+            .iter()
+            .map(|toast| {
+                let age = toast.time.elapsed().as_secs_f32();
+                let alpha = ((TOAST_SECONDS - age) / FADE_SECONDS).min(1.0);
+                (toast.id, toast.severity, toast.text.clone(), alpha)
+            })
+            .collect()
+    });
+    let mut bottom = screen.y - crate::status::HEIGHT - MARGIN;
+    let mut dismissed = Vec::new();
+    for (id, severity, text, alpha) in toasts.into_iter().rev() {
+        let galley =
+            ctx.fonts()[egui::TextStyle::Body].layout_multiline(text, WIDTH - PADDING * 2.0);
+        let size = egui::vec2(WIDTH, galley.size.y + PADDING * 2.0);
+        let pos = egui::pos2(screen.x - MARGIN - size.x, bottom - size.y);
+        bottom = pos.y - SPACING;
+        egui::Area::new(format!("toast_{}", id))
+            .order(egui::Order::Foreground)
+            .fixed_pos(pos)
+            .show(ctx, |ui| {
+                let rect = ui.allocate_space(size);
+                let response = ui.interact(rect, ui.id().with("dismiss"), egui::Sense::click());
+                let painter = ui.painter();
+                painter.rect_filled(rect, 4.0, faded(egui::Srgba::new(30, 30, 34, 240), alpha));
+                painter.rect_filled(
+                    egui::Rect::from_min_size(rect.min, egui::vec2(4.0, rect.height())),
+                    2.0,
+                    faded(severity.color(), alpha),
+                );
+                painter.galley(
+                    rect.min + egui::vec2(PADDING, PADDING),
+                    galley,
+                    egui::TextStyle::Body,
+                    faded(egui::Srgba::new(230, 230, 230, 255), alpha),
+                );
+                crate::status::hint_on_hover(&response, "Click to dismiss");
+                if response.clicked {
+                    dismissed.push(id);
+                }
+            });
+    }
+    if !dismissed.is_empty() {
+        STATE.with(|state| {
+            state
+                .borrow_mut()
+                .toasts
+                .retain(|toast| !dismissed.contains(&toast.id))
+        });
+    }
+}
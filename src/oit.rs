@@ -0,0 +1,269 @@
+pub const ACCUM_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+pub const REVEALAGE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R16Float;
+
+struct Targets {
+    accum_view: wgpu::TextureView,
+    revealage_view: wgpu::TextureView,
+    composite_bind_group: wgpu::BindGroup,
+}
+
+pub struct WeightedBlended {
+    accumulate_pipeline: wgpu::RenderPipeline,
+    composite_pipeline: wgpu::RenderPipeline,
+    composite_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    targets: Targets,
+}
+
+impl WeightedBlended {
+    pub fn new(
+        device: &wgpu::Device,
+        scene_layout: &wgpu::PipelineLayout,
+        scene_vs: &wgpu::ShaderModule,
+        color_format: wgpu::TextureFormat,
+        size: winit::dpi::PhysicalSize<u32>,
+    ) -> Self {
+        let accumulate_fs = crate::shader::compile_builtin(
+            device,
+            "scene_oit.frag",
+            include_str!("shaders/scene_oit.frag"),
+            shaderc::ShaderKind::Fragment,
+        );
+        let accumulate_pipeline = crate::scene::create_pipeline(
+            device,
+            "OIT Accumulate Pipeline",
+            scene_layout,
+            scene_vs,
+            &accumulate_fs,
+            &[
+                wgpu::ColorStateDescriptor {
+                    format: ACCUM_FORMAT,
+                    color_blend: wgpu::BlendDescriptor {
+                        src_factor: wgpu::BlendFactor::One,
+                        dst_factor: wgpu::BlendFactor::One,
+                        operation: wgpu::BlendOperation::Add,
+                    },
+                    alpha_blend: wgpu::BlendDescriptor {
+                        src_factor: wgpu::BlendFactor::One,
+                        dst_factor: wgpu::BlendFactor::One,
+                        operation: wgpu::BlendOperation::Add,
+                    },
+                    write_mask: wgpu::ColorWrite::ALL,
+                },
+                wgpu::ColorStateDescriptor {
+                    format: REVEALAGE_FORMAT,
+                    color_blend: wgpu::BlendDescriptor {
+                        src_factor: wgpu::BlendFactor::Zero,
+                        dst_factor: wgpu::BlendFactor::OneMinusSrcColor,
+                        operation: wgpu::BlendOperation::Add,
+                    },
+                    alpha_blend: wgpu::BlendDescriptor::REPLACE,
+                    write_mask: wgpu::ColorWrite::ALL,
+                },
+            ],
+            false,
+            1,
+        );
+
+        let composite_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("OIT Composite Bind Group Layout"),
+            entries: &[
+                texture_entry(0),
+                texture_entry(1),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler { comparison: false },
+                    count: None,
+                },
+            ],
+        });
+        let composite_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("OIT Composite Pipeline Layout"),
+                bind_group_layouts: &[&composite_layout],
+                push_constant_ranges: &[],
+            });
+        let fullscreen_vs = crate::shader::compile_builtin(
+            device,
+            "fullscreen.vert",
+            include_str!("shaders/fullscreen.vert"),
+            shaderc::ShaderKind::Vertex,
+        );
+        let composite_fs = crate::shader::compile_builtin(
+            device,
+            "oit_composite.frag",
+            include_str!("shaders/oit_composite.frag"),
+            shaderc::ShaderKind::Fragment,
+        );
+        let composite_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("OIT Composite Pipeline"),
+            layout: Some(&composite_pipeline_layout),
+            vertex_stage: wgpu::ProgrammableStageDescriptor {
+                module: &fullscreen_vs,
+                entry_point: "main",
+            },
+            fragment_stage: Some(wgpu::ProgrammableStageDescriptor {
+                module: &composite_fs,
+                entry_point: "main",
+            }),
+            rasterization_state: Some(wgpu::RasterizationStateDescriptor::default()),
+            primitive_topology: wgpu::PrimitiveTopology::TriangleList,
+            color_states: &[wgpu::ColorStateDescriptor {
+                format: color_format,
+                color_blend: wgpu::BlendDescriptor {
+                    src_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                    dst_factor: wgpu::BlendFactor::SrcAlpha,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha_blend: wgpu::BlendDescriptor::REPLACE,
+                write_mask: wgpu::ColorWrite::COLOR,
+            }],
+            depth_stencil_state: None,
+            vertex_state: wgpu::VertexStateDescriptor {
+                index_format: wgpu::IndexFormat::Uint16,
+                vertex_buffers: &[],
+            },
+            sample_count: 1,
+            sample_mask: !0,
+            alpha_to_coverage_enabled: false,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("OIT Sampler"),
+            ..Default::default()
+        });
+        let targets = create_targets(device, &composite_layout, &sampler, size);
+
+        Self {
+            accumulate_pipeline,
+            composite_pipeline,
+            composite_layout,
+            sampler,
+            targets,
+        }
+    }
+
+    pub fn resize(&mut self, device: &wgpu::Device, size: winit::dpi::PhysicalSize<u32>) {
+        self.targets = create_targets(device, &self.composite_layout, &self.sampler, size);
+    }
+
+    pub fn begin_accumulate<'a>(
+        &'a self,
+        encoder: &'a mut wgpu::CommandEncoder,
+        depth_view: &'a wgpu::TextureView,
+    ) -> wgpu::RenderPass<'a> {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[
+                wgpu::RenderPassColorAttachmentDescriptor {
+                    attachment: &self.targets.accum_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: true,
+                    },
+                },
+                wgpu::RenderPassColorAttachmentDescriptor {
+                    attachment: &self.targets.revealage_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::WHITE),
+                        store: true,
+                    },
+                },
+            ],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
+                attachment: depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: false,
+                }),
+                stencil_ops: None,
+            }),
+        });
+        render_pass.set_pipeline(&self.accumulate_pipeline);
+        render_pass
+    }
+
+    pub fn composite(&self, encoder: &mut wgpu::CommandEncoder, color_view: &wgpu::TextureView) {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: color_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+        render_pass.set_pipeline(&self.composite_pipeline);
+        render_pass.set_bind_group(0, &self.targets.composite_bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+fn texture_entry(binding: u32) -> wgpu::BindGroupLayoutEntry {
+    wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStage::FRAGMENT,
+        ty: wgpu::BindingType::SampledTexture {
+            dimension: wgpu::TextureViewDimension::D2,
+            component_type: wgpu::TextureComponentType::Float,
+            multisampled: false,
+        },
+        count: None,
+    }
+}
+
+fn create_targets(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    sampler: &wgpu::Sampler,
+    size: winit::dpi::PhysicalSize<u32>,
+) -> Targets {
+    let create_view = |label, format| {
+        device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width: size.width.max(1),
+                    height: size.height.max(1),
+                    depth: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::SAMPLED,
+            })
+            .create_view(&wgpu::TextureViewDescriptor::default())
+    };
+    let accum_view = create_view("OIT Accum", ACCUM_FORMAT);
+    let revealage_view = create_view("OIT Revealage", REVEALAGE_FORMAT);
+
+    let composite_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("OIT Composite Bind Group"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&accum_view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::TextureView(&revealage_view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+        ],
+    });
+
+    Targets {
+        accum_view,
+        revealage_view,
+        composite_bind_group,
+    }
+}
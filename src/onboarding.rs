@@ -0,0 +1,61 @@
+enum Stage {
+    Setup,
+    Tour,
+}
+
+pub struct Onboarding {
+    stage: Option<Stage>,
+    setup: crate::setup::FirstRunSetup,
+    tour: crate::tour::Tour,
+}
+
+impl Onboarding {
+    pub fn new(settings: &crate::settings::AppSettings, first_launch: bool) -> Self {
+        Self {
+            stage: if first_launch || !settings.setup_complete {
+                Some(Stage::Setup)
+            } else {
+                None
+            },
+            setup: crate::setup::FirstRunSetup::new(settings),
+            tour: crate::tour::Tour::default(),
+        }
+    }
+
+    pub fn replay(&mut self, settings: &crate::settings::AppSettings) {
+        self.setup = crate::setup::FirstRunSetup::new(settings);
+        self.tour.restart();
+        self.stage = Some(Stage::Setup);
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &std::sync::Arc<egui::Context>,
+        screen: egui::Vec2,
+        settings: &mut crate::settings::AppSettings,
+    ) {
+        match self.stage {
+            Some(Stage::Setup) => match self.setup.show(ctx, screen, settings) {
+                Some(crate::wizard::Outcome::Finished) => self.stage = Some(Stage::Tour),
+                Some(crate::wizard::Outcome::Cancelled) => {
+                    self.finish(settings);
+                    crate::notify::info("Onboarding skipped, replay it from the Help menu");
+                }
+                None => {}
+            },
+            Some(Stage::Tour) if self.tour.show(ctx, screen) => {
+                self.finish(settings);
+                crate::notify::success("You're all set");
+            }
+            Some(Stage::Tour) | None => {}
+        }
+    }
+
+    fn finish(&mut self, settings: &mut crate::settings::AppSettings) {
+        self.stage = None;
+        settings.setup_complete = true;
+        if let Err(err) = settings.save() {
+            log::warn!("cannot save settings: {}", err);
+        }
+    }
+}
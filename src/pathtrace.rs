@@ -0,0 +1,392 @@
+pub const MAX_PRIMITIVES: usize = 1024;
+const WORKGROUP_SIZE: u32 = 8;
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba32Float;
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct GpuPrimitive {
+    pub inverse_transform: [f32; 16],
+    pub color: [f32; 4],
+    pub material: [f32; 4],
+    pub center: [f32; 4],
+    pub extent: [f32; 4],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    inverse_view_proj: [f32; 16],
+    camera_position: [f32; 4],
+    sun_direction: [f32; 4],
+    sun_color: [f32; 4],
+    sky_color: [f32; 4],
+    counts: [u32; 4],
+}
+
+pub struct Frame<'a> {
+    pub view_proj: glam::Mat4,
+    pub camera_position: glam::Vec3,
+    pub sun_direction: glam::Vec3,
+    pub sun_color: glam::Vec3,
+    pub sky_color: glam::Vec3,
+    pub primitives: &'a [GpuPrimitive],
+}
+
+pub struct Settings {
+    pub enabled: bool,
+    pub max_samples: f32,
+    pub samples_per_frame: f32,
+    pub bounces: f32,
+    pub resolution_scale: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_samples: 1024.0,
+            samples_per_frame: 1.0,
+            bounces: 4.0,
+            resolution_scale: 0.5,
+        }
+    }
+}
+
+struct Accumulation {
+    size: winit::dpi::PhysicalSize<u32>,
+    textures: Vec<wgpu::Texture>,
+    trace_bind_groups: [wgpu::BindGroup; 2],
+    display_bind_groups: [wgpu::BindGroup; 2],
+}
+
+pub struct PathTracer {
+    pub settings: Settings,
+    pub samples: u32,
+    pub primitives: usize,
+    pub truncated: bool,
+    pub samples_per_second: f32,
+    frame: u32,
+    current: usize,
+    last_state: Vec<u8>,
+    rate_start: std::time::Instant,
+    rate_samples: u32,
+    trace_pipeline: wgpu::ComputePipeline,
+    display_pipeline: wgpu::RenderPipeline,
+    trace_layout: wgpu::BindGroupLayout,
+    display_layout: wgpu::BindGroupLayout,
+    uniform_buffer: wgpu::Buffer,
+    primitive_buffer: wgpu::Buffer,
+    sampler: wgpu::Sampler,
+    accumulation: Option<Accumulation>,
+}
+
+impl PathTracer {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let storage_entry = |binding, readonly| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStage::COMPUTE,
+            ty: wgpu::BindingType::StorageTexture {
+                dimension: wgpu::TextureViewDimension::D2,
+                format: FORMAT,
+                readonly,
+            },
+            count: None,
+        };
+        let trace_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Path Trace Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::COMPUTE,
+                    ty: wgpu::BindingType::UniformBuffer {
+                        dynamic: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStage::COMPUTE,
+                    ty: wgpu::BindingType::StorageBuffer {
+                        dynamic: false,
+                        min_binding_size: None,
+                        readonly: true,
+                    },
+                    count: None,
+                },
+                storage_entry(2, true),
+                storage_entry(3, false),
+            ],
+        });
+        let display_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Path Trace Display Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::SampledTexture {
+                        dimension: wgpu::TextureViewDimension::D2,
+                        component_type: wgpu::TextureComponentType::Float,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler { comparison: false },
+                    count: None,
+                },
+            ],
+        });
+
+        let cs_module = crate::shader::compile_builtin(
+            device,
+            "pathtrace.comp",
+            include_str!("shaders/pathtrace.comp"),
+            shaderc::ShaderKind::Compute,
+        );
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Path Trace Pipeline Layout"),
+            bind_group_layouts: &[&trace_layout],
+            push_constant_ranges: &[],
+        });
+        let trace_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Path Trace Pipeline"),
+            layout: Some(&pipeline_layout),
+            compute_stage: wgpu::ProgrammableStageDescriptor {
+                module: &cs_module,
+                entry_point: "main",
+            },
+        });
+        let display_pipeline = crate::blit::fullscreen_pipeline(
+            device,
+            "Path Trace Display Pipeline",
+            &[&display_layout],
+            "pathtrace_display.frag",
+            include_str!("shaders/pathtrace_display.frag"),
+            format,
+            wgpu::BlendDescriptor::REPLACE,
+        );
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Path Trace Uniforms"),
+            size: std::mem::size_of::<Uniforms>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let primitive_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Path Trace Primitives"),
+            size: (MAX_PRIMITIVES * std::mem::size_of::<GpuPrimitive>()) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::STORAGE | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Path Trace Sampler"),
+            ..Default::default()
+        });
+
+        Self {
+            settings: Settings::default(),
+            samples: 0,
+            primitives: 0,
+            truncated: false,
+            samples_per_second: 0.0,
+            frame: 0,
+            current: 0,
+            last_state: Vec::new(),
+            rate_start: std::time::Instant::now(),
+            rate_samples: 0,
+            trace_pipeline,
+            display_pipeline,
+            trace_layout,
+            display_layout,
+            uniform_buffer,
+            primitive_buffer,
+            sampler,
+            accumulation: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.samples = 0;
+    }
+
+    pub fn noise_estimate(&self) -> f32 {
+        1.0 / (self.samples.max(1) as f32).sqrt()
+    }
+
+    fn create_accumulation(
+        &self,
+        device: &wgpu::Device,
+        size: winit::dpi::PhysicalSize<u32>,
+    ) -> Accumulation {
+        let textures: Vec<wgpu::Texture> = (0..2)
+            .map(|_| {
+                device.create_texture(&wgpu::TextureDescriptor {
+                    label: Some("Path Trace Accumulation"),
+                    size: wgpu::Extent3d {
+                        width: size.width,
+                        height: size.height,
+                        depth: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: FORMAT,
+                    usage: wgpu::TextureUsage::STORAGE | wgpu::TextureUsage::SAMPLED,
+                })
+            })
+            .collect();
+        let views: Vec<wgpu::TextureView> = textures
+            .iter()
+            .map(|texture| texture.create_view(&wgpu::TextureViewDescriptor::default()))
+            .collect();
+        let trace_bind_group = |source: usize| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Path Trace Bind Group"),
+                layout: &self.trace_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::Buffer(self.uniform_buffer.slice(..)),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Buffer(self.primitive_buffer.slice(..)),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::TextureView(&views[source]),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: wgpu::BindingResource::TextureView(&views[1 - source]),
+                    },
+                ],
+            })
+        };
+        let display_bind_group = |source: usize| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Path Trace Display Bind Group"),
+                layout: &self.display_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&views[source]),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&self.sampler),
+                    },
+                ],
+            })
+        };
+
+        Accumulation {
+            size,
+            textures,
+            trace_bind_groups: [trace_bind_group(0), trace_bind_group(1)],
+            display_bind_groups: [display_bind_group(0), display_bind_group(1)],
+        }
+    }
+
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+        size: winit::dpi::PhysicalSize<u32>,
+        frame: &Frame,
+    ) {
+        let scale = self.settings.resolution_scale;
+        let size = winit::dpi::PhysicalSize::new(
+            ((size.width as f32 * scale) as u32).max(1),
+            ((size.height as f32 * scale) as u32).max(1),
+        );
+        if self
+            .accumulation
+            .as_ref()
+            .map(|accumulation| accumulation.size)
+            != Some(size)
+        {
+            self.accumulation = Some(self.create_accumulation(device, size));
+            self.current = 0;
+            self.samples = 0;
+        }
+
+        let primitives = &frame.primitives[..frame.primitives.len().min(MAX_PRIMITIVES)];
+        self.primitives = primitives.len();
+        self.truncated = frame.primitives.len() > MAX_PRIMITIVES;
+        let mut uniforms = Uniforms {
+            inverse_view_proj: frame.view_proj.inverse().to_cols_array(),
+            camera_position: frame.camera_position.extend(1.0).into(),
+            sun_direction: frame.sun_direction.extend(0.0).into(),
+            sun_color: frame.sun_color.extend(1.0).into(),
+            sky_color: frame.sky_color.extend(1.0).into(),
+            counts: [0, primitives.len() as u32, self.settings.bounces as u32, 0],
+        };
+
+        let mut state = bytemuck::bytes_of(&uniforms).to_vec();
+        state.extend_from_slice(bytemuck::cast_slice(primitives));
+        if state != self.last_state {
+            self.last_state = state;
+            self.samples = 0;
+            uploader.write_buffer(
+                device,
+                encoder,
+                &self.primitive_buffer,
+                0,
+                bytemuck::cast_slice(primitives),
+            );
+        }
+
+        let accumulation = self.accumulation.as_ref().unwrap();
+        let remaining = (self.settings.max_samples as u32).saturating_sub(self.samples);
+        let passes = remaining.min(self.settings.samples_per_frame as u32);
+        let groups_x = size.width.div_ceil(WORKGROUP_SIZE);
+        let groups_y = size.height.div_ceil(WORKGROUP_SIZE);
+        for _ in 0..passes {
+            uniforms.counts[0] = self.samples;
+            uniforms.counts[3] = self.frame;
+            uploader.write_buffer(
+                device,
+                encoder,
+                &self.uniform_buffer,
+                0,
+                bytemuck::bytes_of(&uniforms),
+            );
+            let mut compute_pass = encoder.begin_compute_pass();
+            compute_pass.set_pipeline(&self.trace_pipeline);
+            compute_pass.set_bind_group(0, &accumulation.trace_bind_groups[self.current], &[]);
+            compute_pass.dispatch(groups_x, groups_y, 1);
+            self.current = 1 - self.current;
+            self.samples += 1;
+            self.frame = self.frame.wrapping_add(1);
+        }
+
+        self.rate_samples += passes;
+        let elapsed = self.rate_start.elapsed().as_secs_f32();
+        if elapsed >= 0.5 {
+            self.samples_per_second = self.rate_samples as f32 / elapsed;
+            self.rate_samples = 0;
+            self.rate_start = std::time::Instant::now();
+        }
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+        render_pass.set_pipeline(&self.display_pipeline);
+        render_pass.set_bind_group(0, &accumulation.display_bind_groups[self.current], &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
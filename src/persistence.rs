@@ -0,0 +1,37 @@
+fn memory_path() -> Option<std::path::PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")).join("memory.json"))
+}
+
+pub fn load_memory(ctx: &egui::Context) {
+    let path = match memory_path() {
+        Some(path) => path,
+        None => return,
+    };
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(_) => return,
+    };
+    match serde_json::from_str::<egui::Memory>(&contents) {
+        Ok(memory) => {
+            *ctx.memory() = memory;
+            log::info!("restored ui memory from {}", path.display());
+        }
+        Err(err) => log::warn!("ignoring invalid ui memory in {}: {}", path.display(), err),
+    }
+}
+
+pub fn save_memory(ctx: &egui::Context) {
+    let path = match memory_path() {
+        Some(path) => path,
+        None => return,
+    };
+    let contents = serde_json::to_string(&*ctx.memory()).unwrap();
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| std::fs::write(&path, contents));
+    match result {
+        Ok(()) => log::info!("saved ui memory to {}", path.display()),
+        Err(err) => log::warn!("cannot save ui memory to {}: {}", path.display(), err),
+    }
+}
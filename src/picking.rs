@@ -0,0 +1,273 @@
+const ID_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R32Uint;
+
+type Readback =
+    std::pin::Pin<Box<dyn std::future::Future<Output = Result<(), wgpu::BufferAsyncError>> + Send>>;
+
+pub struct Picker {
+    pub hovered: Option<usize>,
+    pub selected: Option<usize>,
+    pub latency: u32,
+    cursor: Option<winit::dpi::PhysicalPosition<f64>>,
+    pipeline: wgpu::RenderPipeline,
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    id_texture: wgpu::Texture,
+    id_view: wgpu::TextureView,
+    depth_view: wgpu::TextureView,
+    readback_buffer: wgpu::Buffer,
+    encoded: bool,
+    pending: Option<Readback>,
+    frames_pending: u32,
+}
+
+impl Picker {
+    pub fn new(device: &wgpu::Device, scene_layout: &wgpu::BindGroupLayout) -> Self {
+        let vs_module = crate::shader::compile_builtin(
+            device,
+            "pick.vert",
+            include_str!("shaders/pick.vert"),
+            shaderc::ShaderKind::Vertex,
+        );
+        let fs_module = crate::shader::compile_builtin(
+            device,
+            "pick.frag",
+            include_str!("shaders/pick.frag"),
+            shaderc::ShaderKind::Fragment,
+        );
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Pick Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStage::VERTEX,
+                ty: wgpu::BindingType::UniformBuffer {
+                    dynamic: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Pick Pipeline Layout"),
+            bind_group_layouts: &[scene_layout, &bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = crate::scene::create_pipeline(
+            device,
+            "Pick Pipeline",
+            &pipeline_layout,
+            &vs_module,
+            &fs_module,
+            &[wgpu::ColorStateDescriptor {
+                format: ID_FORMAT,
+                color_blend: wgpu::BlendDescriptor::REPLACE,
+                alpha_blend: wgpu::BlendDescriptor::REPLACE,
+                write_mask: wgpu::ColorWrite::ALL,
+            }],
+            true,
+            1,
+        );
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Pick Uniforms"),
+            size: std::mem::size_of::<[f32; 16]>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Pick Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer(uniform_buffer.slice(..)),
+            }],
+        });
+
+        let extent = wgpu::Extent3d {
+            width: 1,
+            height: 1,
+            depth: 1,
+        };
+        let id_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Pick IDs"),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: ID_FORMAT,
+            usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::COPY_SRC,
+        });
+        let id_view = id_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let depth_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Pick Depth"),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: crate::scene::DEPTH_FORMAT,
+            usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+        });
+        let depth_view = depth_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Pick Readback"),
+            size: wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::MAP_READ | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            hovered: None,
+            selected: None,
+            latency: 0,
+            cursor: None,
+            pipeline,
+            uniform_buffer,
+            bind_group,
+            id_texture,
+            id_view,
+            depth_view,
+            readback_buffer,
+            encoded: false,
+            pending: None,
+            frames_pending: 0,
+        }
+    }
+
+    pub fn set_cursor(&mut self, cursor: Option<winit::dpi::PhysicalPosition<f64>>) {
+        self.cursor = cursor;
+        if cursor.is_none() {
+            self.hovered = None;
+        }
+    }
+
+    pub fn cursor(&self) -> Option<winit::dpi::PhysicalPosition<f64>> {
+        self.cursor
+    }
+
+    pub fn select_hovered(&mut self) {
+        self.selected = self.hovered;
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.cursor.is_none() || self.encoded || self.pending.is_some()
+    }
+
+    pub fn pick_view_proj(
+        &self,
+        view_proj: &glam::Mat4,
+        size: winit::dpi::PhysicalSize<u32>,
+    ) -> Option<glam::Mat4> {
+        let cursor = self.cursor?;
+        let width = size.width.max(1) as f32;
+        let height = size.height.max(1) as f32;
+        let x = (cursor.x as f32 + 0.5) / width * 2.0 - 1.0;
+        let y = 1.0 - (cursor.y as f32 + 0.5) / height * 2.0;
+        let pick = glam::Mat4::from_scale(glam::Vec3::new(width, height, 1.0))
+            * glam::Mat4::from_translation(glam::Vec3::new(-x, -y, 0.0));
+        Some(pick * *view_proj)
+    }
+
+    pub fn begin_pass<'a>(
+        &'a mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &'a mut wgpu::CommandEncoder,
+        pick_view_proj: &glam::Mat4,
+    ) -> wgpu::RenderPass<'a> {
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.uniform_buffer,
+            0,
+            bytemuck::cast_slice(&pick_view_proj.to_cols_array()),
+        );
+        self.encoded = true;
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: &self.id_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
+                attachment: &self.depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: false,
+                }),
+                stencil_ops: None,
+            }),
+        });
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(1, &self.bind_group, &[]);
+        render_pass
+    }
+
+    pub fn copy_to_readback(&self, encoder: &mut wgpu::CommandEncoder) {
+        encoder.copy_texture_to_buffer(
+            wgpu::TextureCopyView {
+                texture: &self.id_texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            wgpu::BufferCopyView {
+                buffer: &self.readback_buffer,
+                layout: wgpu::TextureDataLayout {
+                    offset: 0,
+                    bytes_per_row: wgpu::COPY_BYTES_PER_ROW_ALIGNMENT,
+                    rows_per_image: 1,
+                },
+            },
+            wgpu::Extent3d {
+                width: 1,
+                height: 1,
+                depth: 1,
+            },
+        );
+    }
+
+    pub fn after_submit(&mut self) {
+        if self.encoded {
+            self.encoded = false;
+            self.frames_pending = 0;
+            self.pending = Some(Box::pin(
+                self.readback_buffer
+                    .slice(..)
+                    .map_async(wgpu::MapMode::Read),
+            ));
+        }
+    }
+
+    pub fn poll(&mut self, device: &wgpu::Device) {
+        let pending = match &mut self.pending {
+            Some(pending) => pending,
+            None => return,
+        };
+        device.poll(wgpu::Maintain::Poll);
+        self.frames_pending += 1;
+        match futures::FutureExt::now_or_never(pending.as_mut()) {
+            Some(Ok(())) => {
+                let id = {
+                    let data = self.readback_buffer.slice(..).get_mapped_range();
+                    u32::from_ne_bytes([data[0], data[1], data[2], data[3]])
+                };
+                self.readback_buffer.unmap();
+                self.pending = None;
+                self.latency = self.frames_pending;
+                self.hovered = if id == 0 || self.cursor.is_none() {
+                    None
+                } else {
+                    Some(id as usize - 1)
+                };
+            }
+            Some(Err(_)) => {
+                log::warn!("pick readback failed");
+                self.pending = None;
+            }
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,409 @@
+const CAPACITY: usize = 5000;
+const PLOT_WIDTH: f32 = 420.0;
+const PLOT_HEIGHT: f32 = 220.0;
+const SERIES: [(&str, [u8; 3]); 4] = [
+    ("frame time (ms)", [250, 200, 60]),
+    ("sine", [90, 170, 250]),
+    ("square", [120, 220, 120]),
+    ("random walk", [240, 100, 100]),
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Line,
+    Scatter,
+    Bar,
+}
+
+impl Kind {
+    const ALL: [Kind; 3] = [Kind::Line, Kind::Scatter, Kind::Bar];
+
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Line => "Line",
+            Kind::Scatter => "Scatter",
+            Kind::Bar => "Bar",
+        }
+    }
+}
+
+pub struct Axes {
+    pub rect: egui::Rect,
+    pub x_range: (f64, f64),
+    pub y_range: (f64, f64),
+}
+
+impl Axes {
+    pub fn to_screen(&self, x: f64, y: f64) -> egui::Pos2 {
+        let (x_min, x_max) = self.x_range;
+        let (y_min, y_max) = self.y_range;
+        egui::pos2(
+            self.rect.min.x + ((x - x_min) / (x_max - x_min)) as f32 * self.rect.width(),
+            self.rect.max.y - ((y - y_min) / (y_max - y_min)) as f32 * self.rect.height(),
+        )
+    }
+
+    pub fn clamp(&self, pos: egui::Pos2) -> egui::Pos2 {
+        egui::pos2(
+            pos.x.max(self.rect.min.x).min(self.rect.max.x),
+            pos.y.max(self.rect.min.y).min(self.rect.max.y),
+        )
+    }
+
+    pub fn paint_grid(
+        &self,
+        painter: &egui::Painter,
+        x_label: impl Fn(f64) -> String,
+        y_label: impl Fn(f64) -> String,
+    ) {
+        let rect = self.rect;
+        let (x_min, x_max) = self.x_range;
+        let (y_min, y_max) = self.y_range;
+        painter.rect_filled(rect, 2.0, egui::Srgba::new(20, 20, 24, 255));
+        let grid = egui::Stroke::new(1.0, egui::Srgba::new(50, 50, 58, 255));
+        for step in 0..=4 {
+            let t = step as f32 / 4.0;
+            let y = rect.min.y + t * rect.height();
+            painter.line_segment([egui::pos2(rect.min.x, y), egui::pos2(rect.max.x, y)], grid);
+            painter.text(
+                egui::pos2(rect.min.x + 2.0, y),
+                (egui::Align::Min, egui::Align::Center),
+                y_label(y_max - (y_max - y_min) * t as f64),
+                egui::TextStyle::Small,
+                egui::Srgba::new(150, 150, 150, 255),
+            );
+            let x = rect.min.x + t * rect.width();
+            painter.line_segment([egui::pos2(x, rect.min.y), egui::pos2(x, rect.max.y)], grid);
+            painter.text(
+                egui::pos2(x, rect.max.y - 2.0),
+                (egui::Align::Center, egui::Align::Max),
+                x_label(x_min + (x_max - x_min) * t as f64),
+                egui::TextStyle::Small,
+                egui::Srgba::new(150, 150, 150, 255),
+            );
+        }
+    }
+
+    pub fn paint_line(
+        &self,
+        painter: &egui::Painter,
+        points: impl Iterator<Item = (f64, f64)>,
+        color: egui::Srgba,
+    ) {
+        let mut previous: Option<egui::Pos2> = None;
+        for (x, y) in points {
+            let pos = self.clamp(self.to_screen(x, y));
+            if let Some(previous) = previous {
+                painter.line_segment([previous, pos], egui::Stroke::new(1.5, color));
+            }
+            previous = Some(pos);
+        }
+    }
+}
+
+pub fn paint_legend(painter: &egui::Painter, rect: egui::Rect, entries: &[(&str, egui::Srgba)]) {
+    for (row, (name, color)) in entries.iter().enumerate() {
+        let y = rect.min.y + 10.0 + row as f32 * 14.0;
+        let swatch = egui::Rect::from_min_size(
+            egui::pos2(rect.max.x - 130.0, y - 4.0),
+            egui::vec2(8.0, 8.0),
+        );
+        painter.rect_filled(swatch, 1.0, *color);
+        painter.text(
+            egui::pos2(swatch.max.x + 4.0, y),
+            (egui::Align::Min, egui::Align::Center),
+            (*name).to_owned(),
+            egui::TextStyle::Small,
+            egui::Srgba::new(220, 220, 220, 255),
+        );
+    }
+}
+
+fn series_color(index: usize) -> egui::Srgba {
+    let [r, g, b] = SERIES[index].1;
+    egui::Srgba::new(r, g, b, 255)
+}
+
+struct Sample {
+    time: f64,
+    values: [f64; 4],
+}
+
+struct View {
+    x_end: Option<f64>,
+    x_span: f64,
+    y_range: Option<(f64, f64)>,
+}
+
+impl Default for View {
+    fn default() -> Self {
+        Self {
+            x_end: None,
+            x_span: 10.0,
+            y_range: None,
+        }
+    }
+}
+
+pub struct PlotsPanel {
+    pub open: bool,
+    kind: Kind,
+    paused: bool,
+    visible: [bool; 4],
+    frequency: f32,
+    samples: std::collections::VecDeque<Sample>,
+    started: std::time::Instant,
+    walk: f64,
+    rng: u32,
+    view: View,
+    path: String,
+    status: String,
+}
+
+impl Default for PlotsPanel {
+    fn default() -> Self {
+        Self {
+            open: false,
+            kind: Kind::Line,
+            paused: false,
+            visible: [true; 4],
+            frequency: 0.5,
+            samples: std::collections::VecDeque::with_capacity(CAPACITY),
+            started: std::time::Instant::now(),
+            walk: 0.0,
+            rng: 0x9e37_79b9,
+            view: View::default(),
+            path: "plot.csv".to_owned(),
+            status: String::new(),
+        }
+    }
+}
+
+impl PlotsPanel {
+    fn random(&mut self) -> f64 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+        self.rng as f64 / u32::MAX as f64
+    }
+
+    fn sample(&mut self, frame_time: f32) {
+        let time = self.started.elapsed().as_secs_f64();
+        let phase = time * self.frequency as f64 * std::f64::consts::PI * 2.0;
+        self.walk = (self.walk + (self.random() - 0.5) * 0.2).clamp(-2.0, 2.0);
+        if self.samples.len() == CAPACITY {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(Sample {
+            time,
+            values: [
+                frame_time as f64 * 1000.0,
+                phase.sin(),
+                if phase.sin() >= 0.0 { 1.0 } else { -1.0 },
+                self.walk,
+            ],
+        });
+    }
+
+    fn export(&mut self) {
+        let path = std::path::PathBuf::from(&self.path);
+        let mut csv = String::from("time");
+        for (name, _) in SERIES.iter() {
+            csv.push(',');
+            csv.push_str(name);
+        }
+        csv.push('\n');
+        for sample in self.samples.iter() {
+            csv.push_str(&format!("{:.4}", sample.time));
+            for value in sample.values.iter() {
+                csv.push_str(&format!(",{}", value));
+            }
+            csv.push('\n');
+        }
+        self.status = match std::fs::write(&path, csv) {
+            Ok(()) => {
+                log::info!(
+                    "exported {} samples to {}",
+                    self.samples.len(),
+                    path.display()
+                );
+                format!("Saved {} samples to {}", self.samples.len(), path.display())
+            }
+            Err(err) => format!("Cannot write {}: {}", path.display(), err),
+        };
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, frame_time: f32) {
+        if !self.paused {
+            self.sample(frame_time);
+        }
+
+        let mut open = self.open;
+        let mut export_requested = false;
+        let mut clear_requested = false;
+        let kind = &mut self.kind;
+        let paused = &mut self.paused;
+        let visible = &mut self.visible;
+        let frequency = &mut self.frequency;
+        let samples = &self.samples;
+        let view = &mut self.view;
+        let path = &mut self.path;
+        let status = &self.status;
+        crate::dock::Panel::new("Plots")
+            .open(&mut open)
+            .default_pos(egui::pos2(360.0, 140.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    for candidate in Kind::ALL.iter() {
+                        ui.radio_value(kind, *candidate, candidate.name());
+                    }
+                });
+                ui.horizontal(|ui| {
+                    for ((name, _), shown) in SERIES.iter().zip(visible.iter_mut()) {
+                        ui.checkbox(shown, *name);
+                    }
+                });
+                ui.add(egui::Slider::f32(frequency, 0.1..=5.0).text("signal frequency (Hz)"));
+                ui.horizontal(|ui| {
+                    if ui.button(if *paused { "Resume" } else { "Pause" }).clicked {
+                        *paused = !*paused;
+                    }
+                    if ui.button("Reset view").clicked {
+                        *view = View::default();
+                    }
+                    if ui.button("Clear").clicked {
+                        clear_requested = true;
+                    }
+                });
+
+                let rect = ui.allocate_space(egui::vec2(PLOT_WIDTH, PLOT_HEIGHT));
+                let response =
+                    ui.interact(rect, ui.make_persistent_id("plot"), egui::Sense::drag());
+                let latest = samples.back().map_or(0.0, |sample| sample.time);
+                let x_end = view.x_end.unwrap_or(latest);
+                let x_span = view.x_span;
+                let x_start = x_end - x_span;
+                let in_view: Vec<&Sample> = samples
+                    .iter()
+                    .filter(|sample| sample.time >= x_start && sample.time <= x_end)
+                    .collect();
+                let (y_min, y_max) = view.y_range.unwrap_or_else(|| {
+                    let mut range = (f64::MAX, f64::MIN);
+                    for sample in in_view.iter() {
+                        for (value, shown) in sample.values.iter().zip(visible.iter()) {
+                            if *shown {
+                                range = (range.0.min(*value), range.1.max(*value));
+                            }
+                        }
+                    }
+                    if range.0 > range.1 {
+                        (-1.0, 1.0)
+                    } else {
+                        let margin = ((range.1 - range.0) * 0.1).max(0.1);
+                        (range.0 - margin, range.1 + margin)
+                    }
+                });
+
+                if response.active {
+                    let delta = ui.input().mouse.delta;
+                    let x_per_point = x_span / rect.width() as f64;
+                    let y_per_point = (y_max - y_min) / rect.height() as f64;
+                    view.x_end = Some(x_end - delta.x as f64 * x_per_point);
+                    view.y_range = Some((
+                        y_min + delta.y as f64 * y_per_point,
+                        y_max + delta.y as f64 * y_per_point,
+                    ));
+                }
+                let scroll = ui.input().scroll_delta.y;
+                if response.hovered && scroll != 0.0 {
+                    let factor = (1.0 - scroll as f64 * 0.002).clamp(0.5, 2.0);
+                    view.x_span = (view.x_span * factor).clamp(0.1, 600.0);
+                    let center = (y_min + y_max) * 0.5;
+                    let half = (y_max - y_min) * 0.5 * factor;
+                    view.y_range = Some((center - half, center + half));
+                }
+
+                let axes = Axes {
+                    rect,
+                    x_range: (x_start, x_end),
+                    y_range: (y_min, y_max),
+                };
+                let painter = ui.painter();
+                axes.paint_grid(painter, |x| format!("{:.1}s", x), |y| format!("{:.2}", y));
+
+                let shown: Vec<usize> = (0..SERIES.len()).filter(|index| visible[*index]).collect();
+                let stride = (in_view.len() / (rect.width() as usize / 3).max(1)).max(1);
+                for (slot, index) in shown.iter().enumerate() {
+                    let color = series_color(*index);
+                    match *kind {
+                        Kind::Line => {
+                            let points = in_view
+                                .iter()
+                                .map(|sample| (sample.time, sample.values[*index]));
+                            axes.paint_line(painter, points, color);
+                        }
+                        Kind::Scatter => {
+                            for sample in in_view.iter() {
+                                let pos = axes.to_screen(sample.time, sample.values[*index]);
+                                if rect.contains(pos) {
+                                    painter.rect_filled(
+                                        egui::Rect::from_center_size(pos, egui::vec2(3.0, 3.0)),
+                                        1.0,
+                                        color,
+                                    );
+                                }
+                            }
+                        }
+                        Kind::Bar => {
+                            let bars = in_view.len() / stride;
+                            let width = rect.width() / bars.max(1) as f32 / shown.len() as f32;
+                            let baseline = axes.clamp(axes.to_screen(x_start, 0.0)).y;
+                            for sample in in_view.iter().step_by(stride) {
+                                let top =
+                                    axes.clamp(axes.to_screen(sample.time, sample.values[*index]));
+                                let x = top.x + slot as f32 * width;
+                                let bar = egui::Rect::from_min_max(
+                                    egui::pos2(x, top.y.min(baseline)),
+                                    egui::pos2(
+                                        (x + width * 0.8).min(rect.max.x),
+                                        top.y.max(baseline),
+                                    ),
+                                );
+                                painter.rect_filled(bar, 0.0, color);
+                            }
+                        }
+                    }
+                }
+                let legend: Vec<(&str, egui::Srgba)> = shown
+                    .iter()
+                    .map(|index| (SERIES[*index].0, series_color(*index)))
+                    .collect();
+                paint_legend(painter, rect, &legend);
+                ui.label("Drag to pan, scroll to zoom.");
+                ui.label(format!(
+                    "{} samples buffered, {} in view",
+                    samples.len(),
+                    in_view.len()
+                ));
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::new(path));
+                    if ui.button("Export CSV").clicked {
+                        export_requested = true;
+                    }
+                });
+                if !status.is_empty() {
+                    ui.label(status.as_str());
+                }
+            });
+        if export_requested {
+            self.export();
+        }
+        if clear_requested {
+            self.samples.clear();
+            self.view = View::default();
+        }
+        self.open = open;
+    }
+}
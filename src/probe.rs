@@ -0,0 +1,198 @@
+pub const PROBE_SIZE: u32 = 128;
+pub const PROBE_MIPS: u32 = 6;
+
+pub struct Probe {
+    pub position: glam::Vec3,
+    pub intensity: f32,
+    pub enabled: bool,
+    pub show_sphere: bool,
+    pub capture_requested: bool,
+    pub captures: u32,
+    texture: wgpu::Texture,
+    cube_view: wgpu::TextureView,
+    sampler: wgpu::Sampler,
+    capture_texture: wgpu::Texture,
+    face_views: Vec<Vec<wgpu::TextureView>>,
+    depth_view: wgpu::TextureView,
+    downsample: crate::blit::Blitter,
+    downsample_bind_groups: Vec<Vec<wgpu::BindGroup>>,
+}
+
+impl Probe {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let size = wgpu::Extent3d {
+            width: PROBE_SIZE,
+            height: PROBE_SIZE,
+            depth: 6,
+        };
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Reflection Probe"),
+            size,
+            mip_level_count: PROBE_MIPS,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: crate::scene::HDR_FORMAT,
+            usage: wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST,
+        });
+        let cube_view = texture.create_view(&wgpu::TextureViewDescriptor {
+            dimension: Some(wgpu::TextureViewDimension::Cube),
+            ..Default::default()
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Reflection Probe Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let capture_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Reflection Probe Capture"),
+            size,
+            mip_level_count: PROBE_MIPS,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: crate::scene::HDR_FORMAT,
+            usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT
+                | wgpu::TextureUsage::SAMPLED
+                | wgpu::TextureUsage::COPY_SRC,
+        });
+        let face_views: Vec<Vec<wgpu::TextureView>> = (0..PROBE_MIPS)
+            .map(|mip| {
+                (0..6)
+                    .map(|face| {
+                        capture_texture.create_view(&wgpu::TextureViewDescriptor {
+                            label: Some("Reflection Probe Face"),
+                            dimension: Some(wgpu::TextureViewDimension::D2),
+                            base_mip_level: mip,
+                            level_count: std::num::NonZeroU32::new(1),
+                            base_array_layer: face,
+                            array_layer_count: std::num::NonZeroU32::new(1),
+                            ..Default::default()
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+        let depth_view = device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some("Reflection Probe Depth"),
+                size: wgpu::Extent3d {
+                    width: PROBE_SIZE,
+                    height: PROBE_SIZE,
+                    depth: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: crate::scene::DEPTH_FORMAT,
+                usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+            })
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let downsample = crate::blit::Blitter::new(
+            device,
+            crate::scene::HDR_FORMAT,
+            "copy.frag",
+            include_str!("shaders/copy.frag"),
+        );
+        let downsample_bind_groups = face_views[..face_views.len() - 1]
+            .iter()
+            .map(|faces| {
+                faces
+                    .iter()
+                    .map(|view| downsample.bind(device, view))
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            position: glam::Vec3::new(0.0, 1.5, 0.0),
+            intensity: 1.0,
+            enabled: true,
+            show_sphere: true,
+            capture_requested: true,
+            captures: 0,
+            texture,
+            cube_view,
+            sampler,
+            capture_texture,
+            face_views,
+            depth_view,
+            downsample,
+            downsample_bind_groups,
+        }
+    }
+
+    pub fn cube_view(&self) -> &wgpu::TextureView {
+        &self.cube_view
+    }
+
+    pub fn sampler(&self) -> &wgpu::Sampler {
+        &self.sampler
+    }
+
+    pub fn face_target(&self, face: usize) -> &wgpu::TextureView {
+        &self.face_views[0][face]
+    }
+
+    pub fn depth_view(&self) -> &wgpu::TextureView {
+        &self.depth_view
+    }
+
+    pub fn face_matrices(&self, face: usize, near: f32, far: f32) -> (glam::Mat4, glam::Mat4) {
+        let (forward, up) = [
+            (glam::Vec3::unit_x(), -glam::Vec3::unit_y()),
+            (-glam::Vec3::unit_x(), -glam::Vec3::unit_y()),
+            (glam::Vec3::unit_y(), glam::Vec3::unit_z()),
+            (-glam::Vec3::unit_y(), -glam::Vec3::unit_z()),
+            (glam::Vec3::unit_z(), -glam::Vec3::unit_y()),
+            (-glam::Vec3::unit_z(), -glam::Vec3::unit_y()),
+        ][face];
+        let view = glam::Mat4::look_at_rh(self.position, self.position + forward, up);
+        let projection = glam::Mat4::from_scale(glam::Vec3::new(1.0, -1.0, 1.0))
+            * glam::Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, near, far);
+        (view, projection * view)
+    }
+
+    pub fn shader_params(&self) -> [f32; 4] {
+        let intensity = if self.enabled && self.captures > 0 {
+            self.intensity
+        } else {
+            0.0
+        };
+        [intensity, (PROBE_MIPS - 1) as f32, 0.0, 0.0]
+    }
+
+    pub fn finish_capture(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        for (mip, bind_groups) in self.downsample_bind_groups.iter().enumerate() {
+            for (face, bind_group) in bind_groups.iter().enumerate() {
+                self.downsample
+                    .draw(encoder, bind_group, &self.face_views[mip + 1][face]);
+            }
+        }
+        for mip in 0..PROBE_MIPS {
+            encoder.copy_texture_to_texture(
+                wgpu::TextureCopyView {
+                    texture: &self.capture_texture,
+                    mip_level: mip,
+                    origin: wgpu::Origin3d::ZERO,
+                },
+                wgpu::TextureCopyView {
+                    texture: &self.texture,
+                    mip_level: mip,
+                    origin: wgpu::Origin3d::ZERO,
+                },
+                wgpu::Extent3d {
+                    width: PROBE_SIZE >> mip,
+                    height: PROBE_SIZE >> mip,
+                    depth: 6,
+                },
+            );
+        }
+        self.captures += 1;
+    }
+}
@@ -0,0 +1,123 @@
+const HISTORY: usize = 120;
+
+pub struct Profiler {
+    pub open: bool,
+    pub font_uploads: u64,
+    pub font_uploads_skipped: u64,
+    last_frame: std::time::Instant,
+    frame_times: std::collections::VecDeque<f32>,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            font_uploads: 0,
+            font_uploads_skipped: 0,
+            last_frame: std::time::Instant::now(),
+            frame_times: std::collections::VecDeque::with_capacity(HISTORY),
+        }
+    }
+
+    pub fn begin_frame(&mut self) {
+        let now = std::time::Instant::now();
+        let frame_time = now.duration_since(self.last_frame).as_secs_f32();
+        self.last_frame = now;
+        if self.frame_times.len() == HISTORY {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(frame_time);
+    }
+
+    pub fn average_frame_time(&self) -> f32 {
+        if self.frame_times.is_empty() {
+            return 0.0;
+        }
+        self.frame_times.iter().sum::<f32>() / self.frame_times.len() as f32
+    }
+
+    pub fn recent_frame_time(&self, frames: usize) -> f32 {
+        let count = frames.min(self.frame_times.len());
+        if count == 0 {
+            return 0.0;
+        }
+        self.frame_times.iter().rev().take(count).sum::<f32>() / count as f32
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &std::sync::Arc<egui::Context>,
+        ui_buffers: &crate::ui_pass::BufferStats,
+        staged_bytes: u64,
+        resolution: &mut crate::resolution::Controller,
+    ) {
+        let average = self.average_frame_time();
+        let worst = self.frame_times.iter().cloned().fold(0.0, f32::max);
+        let font_uploads = self.font_uploads;
+        let font_uploads_skipped = self.font_uploads_skipped;
+
+        crate::dock::Panel::new("Profiler")
+            .open(&mut self.open)
+            .show(ctx, |ui| {
+                egui::CollapsingHeader::new("Frame")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        ui.label(format!(
+                            "Average: {:.2} ms ({:.0} fps)",
+                            average * 1000.0,
+                            if average > 0.0 { 1.0 / average } else { 0.0 }
+                        ));
+                        ui.label(format!("Worst: {:.2} ms", worst * 1000.0));
+                    });
+                egui::CollapsingHeader::new("Dynamic resolution")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        ui.checkbox(&mut resolution.enabled, "Enabled");
+                        ui.add(
+                            egui::Slider::f32(&mut resolution.target_fps, 30.0..=144.0)
+                                .text("target fps"),
+                        );
+                        ui.add(
+                            egui::Slider::f32(&mut resolution.min_scale, 0.25..=1.0)
+                                .text("minimum scale"),
+                        );
+                        ui.label(format!("Render scale: {:.0}%", resolution.scale() * 100.0));
+                    });
+                egui::CollapsingHeader::new("UI buffers")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        ui.label(format!(
+                            "Vertex capacity: {} KiB",
+                            ui_buffers.vertex_capacity / 1024
+                        ));
+                        ui.label(format!(
+                            "Index capacity: {} KiB",
+                            ui_buffers.index_capacity / 1024
+                        ));
+                        ui.label(format!(
+                            "Uploaded last frame: {} KiB",
+                            ui_buffers.bytes_uploaded_last_frame / 1024
+                        ));
+                        ui.label(format!(
+                            "Allocations last frame: {}",
+                            ui_buffers.allocations_last_frame
+                        ));
+                        ui.label(format!(
+                            "Allocations total: {} (per-frame buffers would need {})",
+                            ui_buffers.allocations, ui_buffers.naive_allocations
+                        ));
+                    });
+                egui::CollapsingHeader::new("Staging belt")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        ui.label(format!("Staged last frame: {} KiB", staged_bytes / 1024));
+                    });
+                egui::CollapsingHeader::new("Font texture")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        ui.label(format!("Uploads: {}", font_uploads));
+                        ui.label(format!("Skipped (unchanged): {}", font_uploads_skipped));
+                    });
+            });
+    }
+}
@@ -0,0 +1,223 @@
+#[derive(Default)]
+pub struct RenderingPanel {
+    pub open: bool,
+}
+
+impl RenderingPanel {
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, scene: &mut crate::scene::Scene) {
+        crate::dock::Panel::new("Rendering")
+            .open(&mut self.open)
+            .show(ctx, |ui| {
+                egui::CollapsingHeader::new("Anti-aliasing")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        ui.radio_value(
+                            &mut scene.anti_aliasing,
+                            crate::scene::AntiAliasing::None,
+                            "Off",
+                        );
+                        ui.radio_value(
+                            &mut scene.anti_aliasing,
+                            crate::scene::AntiAliasing::Msaa,
+                            format!("MSAA {}x", crate::scene::MSAA_SAMPLES),
+                        );
+                        ui.radio_value(
+                            &mut scene.anti_aliasing,
+                            crate::scene::AntiAliasing::Taa,
+                            "TAA",
+                        );
+                        match scene.anti_aliasing {
+                            crate::scene::AntiAliasing::Msaa => {
+                                ui.label("SSAO and weighted OIT are unavailable with MSAA");
+                            }
+                            crate::scene::AntiAliasing::Taa => {
+                                ui.add(
+                                    egui::Slider::f32(&mut scene.taa.blend, 0.02..=1.0)
+                                        .text("history blend"),
+                                );
+                                ui.checkbox(&mut scene.taa.motion_debug, "Show motion vectors");
+                            }
+                            crate::scene::AntiAliasing::None => {}
+                        }
+                    });
+                egui::CollapsingHeader::new("Transparency")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        ui.radio_value(
+                            &mut scene.transparency,
+                            crate::scene::Transparency::Sorted,
+                            "Sorted alpha blending",
+                        );
+                        ui.radio_value(
+                            &mut scene.transparency,
+                            crate::scene::Transparency::WeightedBlended,
+                            "Weighted blended OIT",
+                        );
+                        ui.label(format!("Transparent objects: {}", scene.stats.transparent));
+                    });
+                egui::CollapsingHeader::new("Ambient occlusion")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        let ssao = &mut scene.ssao;
+                        ui.checkbox(&mut ssao.settings.enabled, "Enabled");
+                        ui.add(
+                            egui::Slider::f32(&mut ssao.settings.radius, 0.05..=2.0).text("radius"),
+                        );
+                        ui.add(
+                            egui::Slider::f32(&mut ssao.settings.intensity, 0.0..=4.0)
+                                .text("intensity"),
+                        );
+                        ui.checkbox(&mut ssao.settings.debug, "Show raw AO term");
+                        ui.label(format!("Encode time: {:.3} ms", ssao.encode_time * 1000.0));
+                    });
+                egui::CollapsingHeader::new("Bloom")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        let bloom = &mut scene.bloom;
+                        ui.checkbox(&mut bloom.settings.enabled, "Enabled");
+                        ui.add(
+                            egui::Slider::f32(&mut bloom.settings.threshold, 0.0..=4.0)
+                                .text("threshold"),
+                        );
+                        ui.add(egui::Slider::f32(&mut bloom.settings.knee, 0.0..=1.0).text("knee"));
+                        ui.add(
+                            egui::Slider::f32(&mut bloom.settings.intensity, 0.0..=2.0)
+                                .text("intensity"),
+                        );
+                        ui.label(format!("Mip levels: {}", bloom.levels()));
+                        for (pass, time) in bloom.timings.iter() {
+                            ui.label(format!("{}: {:.3} ms", pass, time * 1000.0));
+                        }
+                    });
+                egui::CollapsingHeader::new("Reflection probe")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        let eye = scene.camera.eye();
+                        let probe = &mut scene.probe;
+                        ui.checkbox(&mut probe.enabled, "Enabled");
+                        ui.checkbox(&mut probe.show_sphere, "Show probe sphere");
+                        ui.add(egui::Slider::f32(&mut probe.position.x, -15.0..=15.0).text("x"));
+                        ui.add(egui::Slider::f32(&mut probe.position.y, 0.0..=10.0).text("y"));
+                        ui.add(egui::Slider::f32(&mut probe.position.z, -15.0..=15.0).text("z"));
+                        ui.add(
+                            egui::Slider::f32(&mut probe.intensity, 0.0..=2.0).text("intensity"),
+                        );
+                        ui.horizontal(|ui| {
+                            if ui.button("Recapture").clicked {
+                                probe.capture_requested = true;
+                            }
+                            if ui.button("Capture at camera").clicked {
+                                probe.position = eye;
+                                probe.capture_requested = true;
+                            }
+                        });
+                        ui.label(format!(
+                            "{0}x{0} cubemap, {1} mips, captured {2} times",
+                            crate::probe::PROBE_SIZE,
+                            crate::probe::PROBE_MIPS,
+                            probe.captures
+                        ));
+                    });
+                egui::CollapsingHeader::new("Path tracer").show(ui, |ui| {
+                    let tracer = &mut scene.path_tracer;
+                    let settings = &mut tracer.settings;
+                    ui.checkbox(&mut settings.enabled, "Enabled");
+                    ui.add(
+                        egui::Slider::f32(&mut settings.samples_per_frame, 1.0..=16.0)
+                            .text("samples per frame"),
+                    );
+                    ui.add(
+                        egui::Slider::f32(&mut settings.max_samples, 16.0..=8192.0)
+                            .text("max samples"),
+                    );
+                    ui.add(egui::Slider::f32(&mut settings.bounces, 1.0..=8.0).text("bounces"));
+                    ui.add(
+                        egui::Slider::f32(&mut settings.resolution_scale, 0.25..=1.0)
+                            .text("resolution"),
+                    );
+                    let max_samples = settings.max_samples as u32;
+                    if !settings.enabled {
+                        return;
+                    }
+                    ui.label(format!(
+                        "Samples {} / {}{}",
+                        tracer.samples,
+                        max_samples,
+                        if tracer.samples >= max_samples {
+                            " (converged)"
+                        } else {
+                            ""
+                        }
+                    ));
+                    ui.label(format!(
+                        "Noise ~{:.1}%, {:.0} samples/s",
+                        tracer.noise_estimate() * 100.0,
+                        tracer.samples_per_second
+                    ));
+                    ui.label(format!("{} primitives", tracer.primitives));
+                    if tracer.truncated {
+                        ui.label(format!(
+                            "Only the first {} objects are traced",
+                            crate::pathtrace::MAX_PRIMITIVES
+                        ));
+                    }
+                    if ui.button("Restart").clicked {
+                        tracer.reset();
+                    }
+                });
+                egui::CollapsingHeader::new("Terrain").show(ui, |ui| {
+                    let terrain = &mut scene.terrain;
+                    let settings = &mut terrain.settings;
+                    ui.checkbox(&mut settings.enabled, "Enabled");
+                    let noise = &mut settings.noise;
+                    let mut seed = noise.seed as f32;
+                    ui.add(egui::Slider::f32(&mut seed, 0.0..=1000.0).text("seed"));
+                    noise.seed = seed as u32;
+                    let mut octaves = noise.octaves as f32;
+                    ui.add(egui::Slider::f32(&mut octaves, 1.0..=8.0).text("octaves"));
+                    noise.octaves = octaves as u32;
+                    ui.add(egui::Slider::f32(&mut noise.frequency, 0.005..=0.2).text("frequency"));
+                    ui.add(egui::Slider::f32(&mut noise.lacunarity, 1.5..=3.0).text("lacunarity"));
+                    ui.add(
+                        egui::Slider::f32(&mut noise.persistence, 0.2..=0.8).text("persistence"),
+                    );
+                    ui.add(egui::Slider::f32(&mut settings.height, 0.5..=30.0).text("height"));
+                    ui.add(egui::Slider::f32(&mut settings.extent, 16.0..=256.0).text("extent"));
+                    ui.horizontal(|ui| {
+                        ui.label("Chunk resolution");
+                        for resolution in [16, 32, 64, 128].iter() {
+                            ui.radio_value(
+                                &mut settings.resolution,
+                                *resolution,
+                                resolution.to_string(),
+                            );
+                        }
+                    });
+                    ui.add(egui::Slider::f32(&mut settings.snow_line, 0.3..=1.0).text("snow line"));
+                    ui.add(
+                        egui::Slider::f32(&mut settings.rock_slope, 0.5..=1.0).text("rock slope"),
+                    );
+                    let generating = terrain.is_generating();
+                    ui.label(format!(
+                        "{} triangles, generated in {:.1} ms{}",
+                        terrain.triangles,
+                        terrain.generation_time.as_secs_f32() * 1000.0,
+                        if generating { ", regenerating..." } else { "" }
+                    ));
+                    if ui.button("Regenerate").clicked {
+                        terrain.regenerate();
+                    }
+                });
+                egui::CollapsingHeader::new("Stereo (experimental)").show(ui, |ui| {
+                    let stereo = &mut scene.stereo;
+                    ui.checkbox(&mut stereo.enabled, "Side-by-side");
+                    ui.add(
+                        egui::Slider::f32(&mut stereo.eye_separation, 0.0..=0.5)
+                            .text("eye separation"),
+                    );
+                    ui.checkbox(&mut stereo.swap_eyes, "Swap eyes (cross-eyed viewing)");
+                    ui.label("TAA is skipped and picking uses the left eye");
+                });
+            });
+    }
+}
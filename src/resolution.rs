@@ -0,0 +1,55 @@
+const STEP: f32 = 0.05;
+const COOLDOWN_FRAMES: u32 = 30;
+
+pub struct Controller {
+    pub enabled: bool,
+    pub target_fps: f32,
+    pub min_scale: f32,
+    pub max_scale: f32,
+    scale: f32,
+    cooldown: u32,
+}
+
+impl Controller {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            target_fps: 60.0,
+            min_scale: 0.5,
+            max_scale: 1.0,
+            scale: 1.0,
+            cooldown: 0,
+        }
+    }
+
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    pub fn update(&mut self, frame_time: f32) -> f32 {
+        if !self.enabled {
+            self.scale = self.max_scale;
+            return self.scale;
+        }
+        if self.cooldown > 0 {
+            self.cooldown -= 1;
+            return self.scale;
+        }
+
+        let target = 1.0 / self.target_fps;
+        let scale = if frame_time > target * 1.05 {
+            self.scale - STEP
+        } else if frame_time < target * 0.85 {
+            self.scale + STEP
+        } else {
+            self.scale
+        };
+        let scale = scale.max(self.min_scale).min(self.max_scale);
+        if (scale - self.scale).abs() > f32::EPSILON {
+            log::debug!("render scale {:.2} -> {:.2}", self.scale, scale);
+            self.scale = scale;
+            self.cooldown = COOLDOWN_FRAMES;
+        }
+        self.scale
+    }
+}
@@ -0,0 +1,2057 @@
+pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+pub const HDR_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+pub const MSAA_SAMPLES: u32 = 4;
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Vertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+}
+
+pub struct Mesh {
+    pub name: String,
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u32>,
+}
+
+impl Mesh {
+    pub fn cube(name: &str) -> Self {
+        let faces = [
+            ([1.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
+            ([-1.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
+            ([0.0, 1.0, 0.0], [0.0, 0.0, 1.0]),
+            ([0.0, -1.0, 0.0], [0.0, 0.0, 1.0]),
+            ([0.0, 0.0, 1.0], [0.0, 1.0, 0.0]),
+            ([0.0, 0.0, -1.0], [0.0, 1.0, 0.0]),
+        ];
+
+        let mut vertices = Vec::with_capacity(24);
+        let mut indices = Vec::with_capacity(36);
+        for (normal, up) in faces.iter() {
+            let normal = glam::Vec3::from(*normal);
+            let up = glam::Vec3::from(*up);
+            let right = up.cross(normal);
+            let base = vertices.len() as u32;
+            for (u, v) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)].iter() {
+                let position = (normal + right * *u + up * *v) * 0.5;
+                vertices.push(Vertex {
+                    position: position.into(),
+                    normal: normal.into(),
+                });
+            }
+            indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+        }
+
+        Self {
+            name: name.to_owned(),
+            vertices,
+            indices,
+        }
+    }
+
+    pub fn plane(name: &str, size: f32) -> Self {
+        let half = size * 0.5;
+        let normal = [0.0, 1.0, 0.0];
+        let vertices = [(-half, half), (half, half), (half, -half), (-half, -half)]
+            .iter()
+            .map(|(x, z)| Vertex {
+                position: [*x, 0.0, *z],
+                normal,
+            })
+            .collect();
+
+        Self {
+            name: name.to_owned(),
+            vertices,
+            indices: vec![0, 1, 2, 0, 2, 3],
+        }
+    }
+
+    pub fn sphere(name: &str, segments: u32, rings: u32) -> Self {
+        let mut vertices = Vec::with_capacity(((segments + 1) * (rings + 1)) as usize);
+        for ring in 0..=rings {
+            let phi = std::f32::consts::PI * ring as f32 / rings as f32;
+            for segment in 0..=segments {
+                let theta = 2.0 * std::f32::consts::PI * segment as f32 / segments as f32;
+                let normal = [phi.sin() * theta.cos(), phi.cos(), phi.sin() * theta.sin()];
+                vertices.push(Vertex {
+                    position: [normal[0] * 0.5, normal[1] * 0.5, normal[2] * 0.5],
+                    normal,
+                });
+            }
+        }
+
+        let mut indices = Vec::with_capacity((segments * rings * 6) as usize);
+        for ring in 0..rings {
+            for segment in 0..segments {
+                let a = ring * (segments + 1) + segment;
+                let b = a + segments + 1;
+                indices.extend_from_slice(&[a, a + 1, b, a + 1, b + 1, b]);
+            }
+        }
+
+        Self {
+            name: name.to_owned(),
+            vertices,
+            indices,
+        }
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    pub fn bounds(&self) -> crate::culling::Aabb {
+        crate::culling::Aabb::from_points(
+            self.vertices
+                .iter()
+                .map(|vertex| glam::Vec3::from(vertex.position)),
+        )
+    }
+}
+
+struct GpuLod {
+    index_buffer: wgpu::Buffer,
+    index_count: u32,
+}
+
+struct GpuMesh {
+    vertex_buffer: wgpu::Buffer,
+    lods: Vec<GpuLod>,
+    lod_errors: Vec<f32>,
+    bounds: crate::culling::Aabb,
+}
+
+impl GpuMesh {
+    fn new(device: &wgpu::Device, mesh: &Mesh) -> Self {
+        let vertex_buffer = wgpu::util::DeviceExt::create_buffer_init(
+            device,
+            &wgpu::util::BufferInitDescriptor {
+                label: Some(&format!("{} Vertices", mesh.name)),
+                contents: bytemuck::cast_slice(&mesh.vertices),
+                usage: wgpu::BufferUsage::VERTEX,
+            },
+        );
+        let lods = crate::lod::generate(mesh);
+        let gpu_lods = lods
+            .iter()
+            .enumerate()
+            .map(|(level, lod)| GpuLod {
+                index_buffer: wgpu::util::DeviceExt::create_buffer_init(
+                    device,
+                    &wgpu::util::BufferInitDescriptor {
+                        label: Some(&format!("{} Indices LOD{}", mesh.name, level)),
+                        contents: bytemuck::cast_slice(&lod.indices),
+                        usage: wgpu::BufferUsage::INDEX,
+                    },
+                ),
+                index_count: lod.indices.len() as u32,
+            })
+            .collect();
+
+        Self {
+            vertex_buffer,
+            lods: gpu_lods,
+            lod_errors: lods.iter().map(|lod| lod.error).collect(),
+            bounds: mesh.bounds(),
+        }
+    }
+}
+
+pub struct Object {
+    pub name: String,
+    pub mesh: usize,
+    pub transform: glam::Mat4,
+    pub color: [f32; 4],
+    pub metallic: f32,
+    pub roughness: f32,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum LightKind {
+    Directional,
+    Point,
+    Spot,
+}
+
+#[derive(Clone)]
+pub struct Light {
+    pub kind: LightKind,
+    pub position: glam::Vec3,
+    pub direction: glam::Vec3,
+    pub color: glam::Vec3,
+    pub intensity: f32,
+    pub range: f32,
+    pub inner_angle: f32,
+    pub outer_angle: f32,
+}
+
+impl Light {
+    pub fn directional(direction: glam::Vec3, color: glam::Vec3) -> Self {
+        Self {
+            kind: LightKind::Directional,
+            position: glam::Vec3::zero(),
+            direction: direction.normalize(),
+            color,
+            intensity: 1.0,
+            range: 0.0,
+            inner_angle: 0.0,
+            outer_angle: 0.0,
+        }
+    }
+
+    pub fn point(position: glam::Vec3, color: glam::Vec3, intensity: f32, range: f32) -> Self {
+        Self {
+            kind: LightKind::Point,
+            position,
+            direction: -glam::Vec3::unit_y(),
+            color,
+            intensity,
+            range,
+            inner_angle: 0.0,
+            outer_angle: 0.0,
+        }
+    }
+
+    pub fn spot(
+        position: glam::Vec3,
+        direction: glam::Vec3,
+        color: glam::Vec3,
+        intensity: f32,
+        range: f32,
+    ) -> Self {
+        Self {
+            kind: LightKind::Spot,
+            position,
+            direction: direction.normalize(),
+            color,
+            intensity,
+            range,
+            inner_angle: 20f32.to_radians(),
+            outer_angle: 30f32.to_radians(),
+        }
+    }
+
+    fn to_gpu(&self) -> crate::clustered::GpuLight {
+        let spot = if self.kind == LightKind::Spot {
+            1.0
+        } else {
+            0.0
+        };
+        let direction = self.direction.normalize();
+        crate::clustered::GpuLight {
+            position_range: self.position.extend(self.range).into(),
+            color_kind: (self.color * self.intensity).extend(spot).into(),
+            direction_cos_outer: direction.extend(self.outer_angle.cos()).into(),
+            cos_inner: [self.inner_angle.cos(), 0.0, 0.0, 0.0],
+        }
+    }
+}
+
+pub struct Camera {
+    pub target: glam::Vec3,
+    pub distance: f32,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub fov_y: f32,
+    pub near: f32,
+    pub far: f32,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            target: glam::Vec3::zero(),
+            distance: 12.0,
+            yaw: 0.6,
+            pitch: 0.5,
+            fov_y: 45f32.to_radians(),
+            near: 0.1,
+            far: 200.0,
+        }
+    }
+}
+
+impl Camera {
+    pub fn eye(&self) -> glam::Vec3 {
+        let direction = glam::Vec3::new(
+            self.pitch.cos() * self.yaw.sin(),
+            self.pitch.sin(),
+            self.pitch.cos() * self.yaw.cos(),
+        );
+        self.target + direction * self.distance
+    }
+
+    pub fn view(&self) -> glam::Mat4 {
+        glam::Mat4::look_at_rh(self.eye(), self.target, glam::Vec3::unit_y())
+    }
+
+    pub fn projection(&self, aspect: f32) -> glam::Mat4 {
+        glam::Mat4::perspective_rh(self.fov_y, aspect, self.near, self.far)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum AntiAliasing {
+    None,
+    Msaa,
+    Taa,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Transparency {
+    Sorted,
+    WeightedBlended,
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct Stats {
+    pub draw_calls: u32,
+    pub triangles: u64,
+    pub instances: u32,
+    pub objects: u32,
+    pub culled: u32,
+    pub lights: u32,
+    pub visible_lights: u32,
+    pub lod_counts: [u32; crate::lod::MAX_LEVELS],
+    pub transparent: u32,
+    pub encode_time: f32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Globals {
+    view_proj: [f32; 16],
+    camera_position: [f32; 4],
+    light_direction: [f32; 4],
+    light_color: [f32; 4],
+    ambient: [f32; 4],
+    view: [f32; 16],
+    cluster_screen: [f32; 4],
+    cluster_options: [u32; 4],
+    probe: [f32; 4],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct ObjectData {
+    model: [f32; 16],
+    color: [f32; 4],
+    material: [f32; 4],
+    id: [u32; 4],
+}
+
+pub struct Scene {
+    pub camera: Camera,
+    pub meshes: Vec<Mesh>,
+    pub objects: Vec<Object>,
+    pub lights: Vec<Light>,
+    pub ambient: glam::Vec3,
+    pub frustum_culling: bool,
+    pub lod_threshold: f32,
+    pub lod_debug: bool,
+    pub instancing: bool,
+    pub transparency: Transparency,
+    pub anti_aliasing: AntiAliasing,
+    pub stats: Stats,
+    pub bloom: crate::bloom::Bloom,
+    pub ssao: crate::ssao::Ssao,
+    pub clusters: crate::clustered::ClusteredLights,
+    pub taa: crate::taa::Taa,
+    pub debug: crate::debug::DebugDraw,
+    pub picker: crate::picking::Picker,
+    pub visualizer: crate::visualizer::Visualizer,
+    pub probe: crate::probe::Probe,
+    pub cache: crate::cache::Cache,
+    pub measurements: crate::measure::Measurements,
+    pub path_tracer: crate::pathtrace::PathTracer,
+    pub stereo: crate::stereo::Stereo,
+    pub terrain: crate::terrain::Terrain,
+    pub voxels: crate::voxel::VoxelWorld,
+    gpu_meshes: Vec<GpuMesh>,
+    size: winit::dpi::PhysicalSize<u32>,
+    format: wgpu::TextureFormat,
+    render_scale: f32,
+    targets_generation: u64,
+    targets: Targets,
+    blitter: crate::blit::Blitter,
+    pipeline: wgpu::RenderPipeline,
+    transparent_pipeline: wgpu::RenderPipeline,
+    msaa_pipeline: wgpu::RenderPipeline,
+    msaa_transparent_pipeline: wgpu::RenderPipeline,
+    probe_pipeline: wgpu::RenderPipeline,
+    oit: crate::oit::WeightedBlended,
+    bind_group_layout: wgpu::BindGroupLayout,
+    globals_buffer: wgpu::Buffer,
+    object_buffer: wgpu::Buffer,
+    object_capacity: usize,
+    bind_group: wgpu::BindGroup,
+}
+
+struct Targets {
+    size: winit::dpi::PhysicalSize<u32>,
+    sample_count: u32,
+    color_texture: wgpu::Texture,
+    color_view: wgpu::TextureView,
+    depth_view: wgpu::TextureView,
+    msaa: Option<(wgpu::TextureView, wgpu::TextureView)>,
+    blit_bind_group: wgpu::BindGroup,
+}
+
+impl Targets {
+    fn new(
+        device: &wgpu::Device,
+        blitter: &crate::blit::Blitter,
+        size: winit::dpi::PhysicalSize<u32>,
+        sample_count: u32,
+    ) -> Self {
+        let color_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Scene Color"),
+            size: wgpu::Extent3d {
+                width: size.width.max(1),
+                height: size.height.max(1),
+                depth: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: HDR_FORMAT,
+            usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT
+                | wgpu::TextureUsage::SAMPLED
+                | wgpu::TextureUsage::COPY_SRC
+                | wgpu::TextureUsage::COPY_DST,
+        });
+        let color_view = color_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let depth_view = create_depth_view(device, size, 1);
+        let msaa = if sample_count > 1 {
+            let msaa_color = device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("Scene Color MSAA"),
+                size: wgpu::Extent3d {
+                    width: size.width.max(1),
+                    height: size.height.max(1),
+                    depth: 1,
+                },
+                mip_level_count: 1,
+                sample_count,
+                dimension: wgpu::TextureDimension::D2,
+                format: HDR_FORMAT,
+                usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+            });
+            Some((
+                msaa_color.create_view(&wgpu::TextureViewDescriptor::default()),
+                create_depth_view(device, size, sample_count),
+            ))
+        } else {
+            None
+        };
+        let blit_bind_group = blitter.bind(device, &color_view);
+
+        Self {
+            size,
+            sample_count,
+            color_texture,
+            color_view,
+            depth_view,
+            msaa,
+            blit_bind_group,
+        }
+    }
+}
+
+impl Scene {
+    pub fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        size: winit::dpi::PhysicalSize<u32>,
+    ) -> Self {
+        let vs_module = crate::shader::compile_builtin(
+            device,
+            "scene.vert",
+            include_str!("shaders/scene.vert"),
+            shaderc::ShaderKind::Vertex,
+        );
+        let fs_module = crate::shader::compile_builtin(
+            device,
+            "scene.frag",
+            include_str!("shaders/scene.frag"),
+            shaderc::ShaderKind::Fragment,
+        );
+
+        let fragment_storage = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStage::FRAGMENT,
+            ty: wgpu::BindingType::StorageBuffer {
+                dynamic: false,
+                min_binding_size: None,
+                readonly: true,
+            },
+            count: None,
+        };
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Scene Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::VERTEX | wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::UniformBuffer {
+                        dynamic: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStage::VERTEX,
+                    ty: wgpu::BindingType::StorageBuffer {
+                        dynamic: false,
+                        min_binding_size: None,
+                        readonly: true,
+                    },
+                    count: None,
+                },
+                fragment_storage(2),
+                fragment_storage(3),
+                fragment_storage(4),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 5,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::SampledTexture {
+                        dimension: wgpu::TextureViewDimension::Cube,
+                        component_type: wgpu::TextureComponentType::Float,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 6,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler { comparison: false },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Scene Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let opaque_states = [wgpu::ColorStateDescriptor {
+            format: HDR_FORMAT,
+            color_blend: wgpu::BlendDescriptor::REPLACE,
+            alpha_blend: wgpu::BlendDescriptor::REPLACE,
+            write_mask: wgpu::ColorWrite::ALL,
+        }];
+        let transparent_states = [wgpu::ColorStateDescriptor {
+            format: HDR_FORMAT,
+            color_blend: wgpu::BlendDescriptor {
+                src_factor: wgpu::BlendFactor::SrcAlpha,
+                dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                operation: wgpu::BlendOperation::Add,
+            },
+            alpha_blend: wgpu::BlendDescriptor::REPLACE,
+            write_mask: wgpu::ColorWrite::COLOR,
+        }];
+        let pipeline = create_pipeline(
+            device,
+            "Scene Pipeline",
+            &pipeline_layout,
+            &vs_module,
+            &fs_module,
+            &opaque_states,
+            true,
+            1,
+        );
+        let transparent_pipeline = create_pipeline(
+            device,
+            "Scene Transparent Pipeline",
+            &pipeline_layout,
+            &vs_module,
+            &fs_module,
+            &transparent_states,
+            false,
+            1,
+        );
+        let msaa_pipeline = create_pipeline(
+            device,
+            "Scene MSAA Pipeline",
+            &pipeline_layout,
+            &vs_module,
+            &fs_module,
+            &opaque_states,
+            true,
+            MSAA_SAMPLES,
+        );
+        let msaa_transparent_pipeline = create_pipeline(
+            device,
+            "Scene MSAA Transparent Pipeline",
+            &pipeline_layout,
+            &vs_module,
+            &fs_module,
+            &transparent_states,
+            false,
+            MSAA_SAMPLES,
+        );
+        let probe_pipeline = create_pipeline_with_winding(
+            device,
+            "Scene Probe Pipeline",
+            &pipeline_layout,
+            &vs_module,
+            &fs_module,
+            &opaque_states,
+            true,
+            1,
+            wgpu::FrontFace::Cw,
+        );
+        let debug = crate::debug::DebugDraw::new(device, &pipeline_layout);
+        let picker = crate::picking::Picker::new(device, &bind_group_layout);
+        let oit = crate::oit::WeightedBlended::new(
+            device,
+            &pipeline_layout,
+            &vs_module,
+            HDR_FORMAT,
+            size,
+        );
+
+        let globals_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Scene Globals"),
+            size: std::mem::size_of::<Globals>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let object_capacity = 64;
+        let object_buffer = create_object_buffer(device, object_capacity);
+        let clusters = crate::clustered::ClusteredLights::new(device);
+        let probe = crate::probe::Probe::new(device);
+        let bind_group = create_bind_group(
+            device,
+            &bind_group_layout,
+            &globals_buffer,
+            &object_buffer,
+            &clusters,
+            &probe,
+        );
+
+        let blitter = crate::blit::Blitter::new(
+            device,
+            format,
+            "tonemap.frag",
+            include_str!("shaders/tonemap.frag"),
+        );
+        let targets = Targets::new(device, &blitter, size, 1);
+        let bloom = crate::bloom::Bloom::new(device, HDR_FORMAT, size, &targets.color_view);
+        let ssao = crate::ssao::Ssao::new(device, HDR_FORMAT, size, &targets.depth_view);
+        let visualizer = crate::visualizer::Visualizer::new(device, format);
+        let path_tracer = crate::pathtrace::PathTracer::new(device, format);
+        let terrain = crate::terrain::Terrain::new(device, &bind_group_layout);
+        let voxels = crate::voxel::VoxelWorld::new(device, &bind_group_layout);
+        let taa = crate::taa::Taa::new(
+            device,
+            HDR_FORMAT,
+            size,
+            &targets.color_view,
+            &targets.depth_view,
+        );
+
+        Self {
+            camera: Camera::default(),
+            meshes: Vec::new(),
+            objects: Vec::new(),
+            lights: vec![Light::directional(
+                glam::Vec3::new(-0.4, -1.0, -0.3),
+                glam::Vec3::new(1.0, 0.95, 0.9),
+            )],
+            ambient: glam::Vec3::new(0.15, 0.15, 0.2),
+            frustum_culling: true,
+            lod_threshold: 1.0,
+            lod_debug: false,
+            instancing: true,
+            transparency: Transparency::WeightedBlended,
+            anti_aliasing: AntiAliasing::None,
+            stats: Stats::default(),
+            bloom,
+            ssao,
+            clusters,
+            taa,
+            debug,
+            picker,
+            visualizer,
+            probe,
+            cache: crate::cache::Cache::default(),
+            measurements: crate::measure::Measurements::default(),
+            path_tracer,
+            stereo: crate::stereo::Stereo::default(),
+            terrain,
+            voxels,
+            gpu_meshes: Vec::new(),
+            size,
+            format,
+            render_scale: 1.0,
+            targets_generation: 0,
+            targets,
+            blitter,
+            pipeline,
+            transparent_pipeline,
+            msaa_pipeline,
+            msaa_transparent_pipeline,
+            probe_pipeline,
+            oit,
+            bind_group_layout,
+            globals_buffer,
+            object_buffer,
+            object_capacity,
+            bind_group,
+        }
+    }
+
+    pub fn recreate_gpu_resources(&mut self, device: &wgpu::Device) {
+        let fresh = Self::new(device, self.format, self.size);
+        self.gpu_meshes = self
+            .meshes
+            .iter()
+            .map(|mesh| GpuMesh::new(device, mesh))
+            .collect();
+        self.targets = fresh.targets;
+        self.targets_generation += 1;
+        self.cache.clear();
+        self.blitter = fresh.blitter;
+        self.pipeline = fresh.pipeline;
+        self.transparent_pipeline = fresh.transparent_pipeline;
+        self.msaa_pipeline = fresh.msaa_pipeline;
+        self.msaa_transparent_pipeline = fresh.msaa_transparent_pipeline;
+        self.probe_pipeline = fresh.probe_pipeline;
+        self.oit = fresh.oit;
+        self.bind_group_layout = fresh.bind_group_layout;
+        self.globals_buffer = fresh.globals_buffer;
+        self.object_buffer = fresh.object_buffer;
+        self.object_capacity = fresh.object_capacity;
+        self.bind_group = fresh.bind_group;
+        let heatmap = self.clusters.heatmap;
+        self.clusters = fresh.clusters;
+        self.clusters.heatmap = heatmap;
+
+        let bloom_settings = std::mem::take(&mut self.bloom.settings);
+        self.bloom = fresh.bloom;
+        self.bloom.settings = bloom_settings;
+        let ssao_settings = std::mem::take(&mut self.ssao.settings);
+        self.ssao = fresh.ssao;
+        self.ssao.settings = ssao_settings;
+        let (taa_blend, motion_debug) = (self.taa.blend, self.taa.motion_debug);
+        self.taa = fresh.taa;
+        self.taa.blend = taa_blend;
+        self.taa.motion_debug = motion_debug;
+        let debug_enabled = self.debug.enabled;
+        self.debug = fresh.debug;
+        self.debug.enabled = debug_enabled;
+        let selected = self.picker.selected;
+        self.picker = fresh.picker;
+        self.picker.selected = selected;
+        let visualizer_settings = std::mem::take(&mut self.visualizer.settings);
+        self.visualizer = fresh.visualizer;
+        self.visualizer.settings = visualizer_settings;
+        let path_tracer_settings = std::mem::take(&mut self.path_tracer.settings);
+        self.path_tracer = fresh.path_tracer;
+        self.path_tracer.settings = path_tracer_settings;
+        let terrain_settings = self.terrain.settings;
+        self.terrain = fresh.terrain;
+        self.terrain.settings = terrain_settings;
+        let voxel_settings = self.voxels.settings;
+        self.voxels = fresh.voxels;
+        self.voxels.settings = voxel_settings;
+        let probe = std::mem::replace(&mut self.probe, fresh.probe);
+        self.probe.position = probe.position;
+        self.probe.intensity = probe.intensity;
+        self.probe.enabled = probe.enabled;
+        self.probe.show_sphere = probe.show_sphere;
+
+        self.recreate_targets(device);
+    }
+
+    pub fn add_mesh(&mut self, device: &wgpu::Device, mesh: Mesh) -> usize {
+        self.gpu_meshes.push(GpuMesh::new(device, &mesh));
+        self.meshes.push(mesh);
+        self.meshes.len() - 1
+    }
+
+    pub fn populate_demo(&mut self, device: &wgpu::Device) {
+        let cube = self.add_mesh(device, Mesh::cube("Cube"));
+        let plane = self.add_mesh(device, Mesh::plane("Ground", 20.0));
+
+        self.objects.push(Object {
+            name: "Ground".to_owned(),
+            mesh: plane,
+            transform: glam::Mat4::identity(),
+            color: [0.4, 0.4, 0.4, 1.0],
+            metallic: 0.0,
+            roughness: 0.9,
+        });
+
+        for x in -2..=2 {
+            for z in -2..=2 {
+                self.objects.push(Object {
+                    name: format!("Cube {} {}", x, z),
+                    mesh: cube,
+                    transform: glam::Mat4::from_translation(glam::Vec3::new(
+                        x as f32 * 2.0,
+                        0.5,
+                        z as f32 * 2.0,
+                    )),
+                    color: [0.5 + x as f32 * 0.1, 0.6, 0.5 + z as f32 * 0.1, 1.0],
+                    metallic: 0.0,
+                    roughness: 0.6,
+                });
+            }
+        }
+
+        let sphere = self.add_mesh(device, Mesh::sphere("Sphere", 96, 64));
+        for i in 0..8 {
+            self.objects.push(Object {
+                name: format!("Sphere {}", i),
+                mesh: sphere,
+                transform: glam::Mat4::from_scale_rotation_translation(
+                    glam::Vec3::splat(1.5),
+                    glam::Quat::identity(),
+                    glam::Vec3::new(-7.0 + i as f32 * 2.0, 0.75, -7.0 - i as f32 * 4.0),
+                ),
+                color: [0.8, 0.3, 0.3, 1.0],
+                metallic: 1.0,
+                roughness: i as f32 / 7.0,
+            });
+        }
+
+        for (i, color) in [
+            [0.2, 0.5, 1.0, 0.35],
+            [1.0, 0.8, 0.2, 0.5],
+            [0.3, 1.0, 0.4, 0.4],
+        ]
+        .iter()
+        .enumerate()
+        {
+            self.objects.push(Object {
+                name: format!("Glass {}", i),
+                mesh: cube,
+                transform: glam::Mat4::from_scale_rotation_translation(
+                    glam::Vec3::new(3.0, 2.0, 0.2),
+                    glam::Quat::identity(),
+                    glam::Vec3::new(0.0, 1.0, 5.0 + i as f32 * 0.8),
+                ),
+                color: *color,
+                metallic: 0.0,
+                roughness: 0.05,
+            });
+        }
+
+        self.objects.push(Object {
+            name: "Lamp".to_owned(),
+            mesh: sphere,
+            transform: glam::Mat4::from_scale_rotation_translation(
+                glam::Vec3::splat(0.4),
+                glam::Quat::identity(),
+                glam::Vec3::new(-4.0, 3.0, 2.0),
+            ),
+            color: [8.0, 6.0, 3.0, 1.0],
+            metallic: 0.0,
+            roughness: 1.0,
+        });
+
+        for (i, color) in [
+            glam::Vec3::new(1.0, 0.3, 0.2),
+            glam::Vec3::new(0.2, 1.0, 0.3),
+            glam::Vec3::new(0.3, 0.4, 1.0),
+            glam::Vec3::new(1.0, 0.8, 0.3),
+        ]
+        .iter()
+        .enumerate()
+        {
+            let angle = i as f32 * std::f32::consts::FRAC_PI_2;
+            self.lights.push(Light::point(
+                glam::Vec3::new(angle.cos() * 4.0, 1.5, angle.sin() * 4.0),
+                *color,
+                8.0,
+                6.0,
+            ));
+        }
+        self.lights.push(Light::spot(
+            glam::Vec3::new(0.0, 6.0, 0.0),
+            -glam::Vec3::unit_y(),
+            glam::Vec3::new(1.0, 1.0, 0.9),
+            20.0,
+            12.0,
+        ));
+    }
+
+    pub fn scatter_lights(&mut self, count: usize) {
+        let mut seed = 0x9e37_79b9_7f4a_7c15_u64 ^ self.lights.len() as u64;
+        let mut random = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed >> 40) as f32 / (1u64 << 24) as f32
+        };
+        for _ in 0..count {
+            let position = glam::Vec3::new(
+                random() * 20.0 - 10.0,
+                0.3 + random() * 2.0,
+                random() * 20.0 - 10.0,
+            );
+            let color = glam::Vec3::new(random(), random(), random()).normalize();
+            self.lights
+                .push(Light::point(position, color, 4.0, 2.0 + random() * 3.0));
+        }
+    }
+
+    pub fn scatter_cubes(&mut self, count: usize) {
+        let cube = match self.meshes.iter().position(|mesh| mesh.name == "Cube") {
+            Some(cube) => cube,
+            None => return,
+        };
+
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64 ^ self.objects.len() as u64;
+        let mut random = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed >> 40) as f32 / (1u64 << 24) as f32
+        };
+
+        for i in 0..count {
+            let angle = random() * std::f32::consts::PI * 2.0;
+            let radius = 12.0 + random().sqrt() * 60.0;
+            let scale = 0.3 + random() * 0.7;
+            self.objects.push(Object {
+                name: format!("Scattered {}", i),
+                mesh: cube,
+                transform: glam::Mat4::from_scale_rotation_translation(
+                    glam::Vec3::splat(scale),
+                    glam::Quat::from_rotation_y(random() * std::f32::consts::PI),
+                    glam::Vec3::new(angle.cos() * radius, scale * 0.5, angle.sin() * radius),
+                ),
+                color: [random(), random(), random(), 1.0],
+                metallic: 0.0,
+                roughness: 0.6,
+            });
+        }
+    }
+
+    pub fn clear_scattered(&mut self) {
+        self.objects
+            .retain(|object| !object.name.starts_with("Scattered "));
+    }
+
+    pub fn resize(&mut self, device: &wgpu::Device, size: winit::dpi::PhysicalSize<u32>) {
+        self.size = size;
+        self.recreate_targets(device);
+    }
+
+    pub fn render_scale(&self) -> f32 {
+        self.render_scale
+    }
+
+    pub fn set_render_scale(&mut self, device: &wgpu::Device, scale: f32) {
+        if (scale - self.render_scale).abs() > f32::EPSILON {
+            self.render_scale = scale;
+            self.recreate_targets(device);
+        }
+    }
+
+    fn stereo_active(&self) -> bool {
+        self.stereo.enabled && !self.path_tracer.settings.enabled
+    }
+
+    pub fn view_size(&self) -> winit::dpi::PhysicalSize<u32> {
+        if self.stereo_active() {
+            crate::stereo::Stereo::eye_size(self.size)
+        } else {
+            self.size
+        }
+    }
+
+    pub fn color_target(&self) -> (&wgpu::TextureView, winit::dpi::PhysicalSize<u32>) {
+        (&self.targets.color_view, self.targets.size)
+    }
+
+    pub fn color_texture(&self) -> &wgpu::Texture {
+        &self.targets.color_texture
+    }
+
+    pub fn internal_size(&self) -> winit::dpi::PhysicalSize<u32> {
+        let size = self.view_size();
+        winit::dpi::PhysicalSize::new(
+            ((size.width as f32 * self.render_scale) as u32).max(1),
+            ((size.height as f32 * self.render_scale) as u32).max(1),
+        )
+    }
+
+    pub fn view_proj(&self) -> glam::Mat4 {
+        let size = self.view_size();
+        let aspect = size.width as f32 / size.height.max(1) as f32;
+        self.camera.projection(aspect) * self.camera.view()
+    }
+
+    pub fn world_to_screen(&self, point: glam::Vec3, pixels_per_point: f32) -> Option<egui::Pos2> {
+        let clip = self.view_proj() * point.extend(1.0);
+        if clip.w <= 0.0 {
+            return None;
+        }
+        let ndc = clip.truncate() / clip.w;
+        if ndc.x.abs() > 1.0 || ndc.y.abs() > 1.0 || ndc.z > 1.0 {
+            return None;
+        }
+        let size = self.view_size();
+        Some(egui::pos2(
+            (ndc.x + 1.0) * 0.5 * size.width as f32 / pixels_per_point,
+            (1.0 - ndc.y) * 0.5 * size.height as f32 / pixels_per_point,
+        ))
+    }
+
+    pub fn raycast(&self, cursor: winit::dpi::PhysicalPosition<f64>) -> Option<glam::Vec3> {
+        let size = self.view_size();
+        let width = size.width.max(1) as f32;
+        let height = size.height.max(1) as f32;
+        let x = (cursor.x as f32 + 0.5) / width * 2.0 - 1.0;
+        let y = 1.0 - (cursor.y as f32 + 0.5) / height * 2.0;
+        let inverse = self.view_proj().inverse();
+        let unproject = |z: f32| {
+            let point = inverse * glam::Vec4::new(x, y, z, 1.0);
+            point.truncate() / point.w
+        };
+        let origin = unproject(0.0);
+        let direction = (unproject(1.0) - origin).normalize();
+
+        let mut candidates: Vec<(f32, &Object)> = self
+            .objects
+            .iter()
+            .filter_map(|object| {
+                self.object_bounds(object)
+                    .ray_distance(origin, direction)
+                    .map(|distance| (distance, object))
+            })
+            .collect();
+        candidates.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut nearest: Option<f32> = None;
+        for (bounds_distance, object) in candidates {
+            if nearest.is_some_and(|nearest| nearest < bounds_distance) {
+                break;
+            }
+            let mesh = &self.meshes[object.mesh];
+            let vertex = |index: u32| {
+                object
+                    .transform
+                    .transform_point3(glam::Vec3::from(mesh.vertices[index as usize].position))
+            };
+            for triangle in mesh.indices.chunks(3) {
+                let hit = ray_triangle(
+                    origin,
+                    direction,
+                    [
+                        vertex(triangle[0]),
+                        vertex(triangle[1]),
+                        vertex(triangle[2]),
+                    ],
+                );
+                if let Some(distance) = hit {
+                    if nearest.is_none_or(|nearest| distance < nearest) {
+                        nearest = Some(distance);
+                    }
+                }
+            }
+        }
+        nearest.map(|distance| origin + direction * distance)
+    }
+
+    pub fn measure_at_cursor(&mut self) {
+        if let Some(point) = self.picker.cursor().and_then(|cursor| self.raycast(cursor)) {
+            self.measurements.add_point(point);
+        }
+    }
+
+    pub fn object_bounds(&self, object: &Object) -> crate::culling::Aabb {
+        self.gpu_meshes[object.mesh]
+            .bounds
+            .transformed(&object.transform)
+    }
+
+    fn trace_primitives(&self) -> Vec<crate::pathtrace::GpuPrimitive> {
+        self.objects
+            .iter()
+            .map(|object| {
+                let bounds = &self.gpu_meshes[object.mesh].bounds;
+                let center = (bounds.min + bounds.max) * 0.5;
+                let extent = (bounds.max - bounds.min) * 0.5;
+                let sphere = self.meshes[object.mesh].name == "Sphere";
+                crate::pathtrace::GpuPrimitive {
+                    inverse_transform: object.transform.inverse().to_cols_array(),
+                    color: object.color,
+                    material: [
+                        object.metallic,
+                        object.roughness,
+                        if sphere { 1.0 } else { 0.0 },
+                        0.0,
+                    ],
+                    center: center.extend(1.0).into(),
+                    extent: extent.extend(0.0).into(),
+                }
+            })
+            .collect()
+    }
+
+    fn emit_debug_lines(&mut self) {
+        self.debug.set_category(crate::debug::Category::Axes);
+        self.debug.line(
+            glam::Vec3::zero(),
+            glam::Vec3::unit_x() * 2.0,
+            [1.0, 0.2, 0.2, 1.0],
+        );
+        self.debug.line(
+            glam::Vec3::zero(),
+            glam::Vec3::unit_y() * 2.0,
+            [0.2, 1.0, 0.2, 1.0],
+        );
+        self.debug.line(
+            glam::Vec3::zero(),
+            glam::Vec3::unit_z() * 2.0,
+            [0.2, 0.4, 1.0, 1.0],
+        );
+
+        self.debug.set_category(crate::debug::Category::Grid);
+        for i in -10..=10 {
+            let t = i as f32;
+            let color = [0.6, 0.6, 0.6, 0.5];
+            self.debug.line(
+                glam::Vec3::new(t, 0.01, -10.0),
+                glam::Vec3::new(t, 0.01, 10.0),
+                color,
+            );
+            self.debug.line(
+                glam::Vec3::new(-10.0, 0.01, t),
+                glam::Vec3::new(10.0, 0.01, t),
+                color,
+            );
+        }
+
+        self.debug.set_category(crate::debug::Category::Lights);
+        let origin = glam::Vec3::new(0.0, 5.0, 0.0);
+        for light in self.lights.iter() {
+            let color = light.color.extend(1.0).into();
+            match light.kind {
+                LightKind::Directional => {
+                    self.debug
+                        .line(origin, origin + light.direction * 3.0, color);
+                }
+                LightKind::Point => {
+                    for axis in [
+                        glam::Vec3::unit_x(),
+                        glam::Vec3::unit_y(),
+                        glam::Vec3::unit_z(),
+                    ]
+                    .iter()
+                    {
+                        self.debug.line(
+                            light.position - *axis * 0.25,
+                            light.position + *axis * 0.25,
+                            color,
+                        );
+                    }
+                }
+                LightKind::Spot => {
+                    self.debug.line(
+                        light.position,
+                        light.position + light.direction * light.range.min(3.0),
+                        color,
+                    );
+                }
+            }
+        }
+    }
+
+    pub fn visualized_view(&self) -> Option<&wgpu::TextureView> {
+        visualizer_source(
+            &self.targets,
+            &self.ssao,
+            &self.bloom,
+            self.visualizer.settings.output,
+        )
+    }
+
+    fn capture_probe(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        globals: &Globals,
+        clear_color: wgpu::Color,
+    ) {
+        self.probe.capture_requested = false;
+        if self.objects.len() > self.object_capacity {
+            self.object_capacity = self.objects.len().next_power_of_two();
+            self.object_buffer = create_object_buffer(device, self.object_capacity);
+            self.bind_group = create_bind_group(
+                device,
+                &self.bind_group_layout,
+                &self.globals_buffer,
+                &self.object_buffer,
+                &self.clusters,
+                &self.probe,
+            );
+        }
+
+        let mut objects: Vec<(&Object, usize, f32, usize)> = self
+            .objects
+            .iter()
+            .enumerate()
+            .filter(|(_, object)| object.color[3] >= 1.0)
+            .map(|(index, object)| (object, 0, 0.0, index))
+            .collect();
+        objects.sort_by_key(|(object, _, _, _)| object.mesh);
+        let object_data: Vec<ObjectData> = objects
+            .iter()
+            .map(|(object, _, _, index)| ObjectData {
+                model: object.transform.to_cols_array(),
+                color: object.color,
+                material: [object.metallic, object.roughness, 0.0, 0.0],
+                id: [*index as u32, 0, 0, 0],
+            })
+            .collect();
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.object_buffer,
+            0,
+            bytemuck::cast_slice(&object_data),
+        );
+
+        for face in 0..6 {
+            let (view, view_proj) =
+                self.probe
+                    .face_matrices(face, self.camera.near, self.camera.far);
+            let face_globals = Globals {
+                view_proj: view_proj.to_cols_array(),
+                camera_position: self.probe.position.extend(1.0).into(),
+                view: view.to_cols_array(),
+                cluster_options: [0, 0, 1, 0],
+                ..*globals
+            };
+            uploader.write_buffer(
+                device,
+                encoder,
+                &self.globals_buffer,
+                0,
+                bytemuck::bytes_of(&face_globals),
+            );
+
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                    attachment: self.probe.face_target(face),
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear_color),
+                        store: true,
+                    },
+                }],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
+                    attachment: self.probe.depth_view(),
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: false,
+                    }),
+                    stencil_ops: None,
+                }),
+            });
+            render_pass.set_pipeline(&self.probe_pipeline);
+            render_pass.set_bind_group(0, &self.bind_group, &[]);
+            draw_batches(
+                &mut render_pass,
+                &self.gpu_meshes,
+                &objects,
+                0,
+                true,
+                &mut Stats::default(),
+            );
+        }
+        self.probe.finish_capture(encoder);
+    }
+
+    fn sample_count(&self) -> u32 {
+        match self.anti_aliasing {
+            AntiAliasing::Msaa => MSAA_SAMPLES,
+            AntiAliasing::None | AntiAliasing::Taa => 1,
+        }
+    }
+
+    fn recreate_targets(&mut self, device: &wgpu::Device) {
+        let size = self.internal_size();
+        let sample_count = self.sample_count();
+        if size != self.targets.size || sample_count != self.targets.sample_count {
+            self.targets = Targets::new(device, &self.blitter, size, sample_count);
+            self.targets_generation += 1;
+            self.oit.resize(device, size);
+            self.bloom
+                .resize(device, HDR_FORMAT, size, &self.targets.color_view);
+            self.ssao.resize(device, size, &self.targets.depth_view);
+            self.taa.resize(
+                device,
+                HDR_FORMAT,
+                size,
+                &self.targets.color_view,
+                &self.targets.depth_view,
+            );
+        }
+    }
+
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        clear_color: wgpu::Color,
+    ) {
+        if !self.stereo_active() {
+            self.render_eye(device, uploader, encoder, view, clear_color, None);
+            return;
+        }
+        let target = self.camera.target;
+        for eye in self.stereo.eyes(&self.camera, self.size).iter() {
+            self.camera.target = target + eye.offset;
+            self.render_eye(
+                device,
+                uploader,
+                encoder,
+                view,
+                clear_color,
+                Some(eye.viewport),
+            );
+        }
+        self.camera.target = target;
+    }
+
+    fn render_eye(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        clear_color: wgpu::Color,
+        viewport: Option<egui::Rect>,
+    ) {
+        let encode_start = std::time::Instant::now();
+        self.recreate_targets(device);
+        let msaa = self.anti_aliasing == AntiAliasing::Msaa;
+        let taa = self.anti_aliasing == AntiAliasing::Taa && viewport.is_none();
+        let size = self.view_size();
+        let aspect = size.width as f32 / size.height.max(1) as f32;
+        let mut projection = self.camera.projection(aspect);
+        let view_proj = projection * self.camera.view();
+        if taa {
+            projection = self.taa.jittered(&projection);
+        }
+        let camera_view = self.camera.view();
+        let (sun_direction, sun_color) = self
+            .lights
+            .iter()
+            .find(|light| light.kind == LightKind::Directional)
+            .map(|light| (light.direction, light.color * light.intensity))
+            .unwrap_or((-glam::Vec3::unit_y(), glam::Vec3::zero()));
+        if self.path_tracer.settings.enabled {
+            let primitives = self.trace_primitives();
+            self.path_tracer.render(
+                device,
+                uploader,
+                encoder,
+                view,
+                self.size,
+                &crate::pathtrace::Frame {
+                    view_proj,
+                    camera_position: self.camera.eye(),
+                    sun_direction,
+                    sun_color,
+                    sky_color: self.ambient,
+                    primitives: &primitives,
+                },
+            );
+            self.cache.end_frame();
+            self.stats = Stats {
+                objects: self.objects.len() as u32,
+                lights: self.lights.len() as u32,
+                encode_time: encode_start.elapsed().as_secs_f32(),
+                ..Stats::default()
+            };
+            return;
+        }
+        let globals = Globals {
+            view_proj: (projection * camera_view).to_cols_array(),
+            camera_position: self.camera.eye().extend(1.0).into(),
+            light_direction: sun_direction.extend(0.0).into(),
+            light_color: sun_color.extend(1.0).into(),
+            ambient: self.ambient.extend(1.0).into(),
+            view: camera_view.to_cols_array(),
+            cluster_screen: [
+                self.targets.size.width as f32,
+                self.targets.size.height as f32,
+                self.camera.near,
+                self.camera.far,
+            ],
+            cluster_options: [0, self.clusters.heatmap as u32, 0, 0],
+            probe: self.probe.shader_params(),
+        };
+        if self.probe.capture_requested {
+            self.capture_probe(device, uploader, encoder, &globals, clear_color);
+        }
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.globals_buffer,
+            0,
+            bytemuck::bytes_of(&globals),
+        );
+
+        self.terrain.update(device, uploader, encoder);
+        self.voxels.update(device);
+        let frustum = crate::culling::Frustum::from_view_proj(&view_proj);
+        let local_lights: Vec<&Light> = self
+            .lights
+            .iter()
+            .filter(|light| light.kind != LightKind::Directional)
+            .take(crate::clustered::MAX_LIGHTS)
+            .collect();
+        let gpu_lights: Vec<crate::clustered::GpuLight> =
+            local_lights.iter().map(|light| light.to_gpu()).collect();
+        let visible_lights = self.lights.len() - local_lights.len()
+            + local_lights
+                .iter()
+                .filter(|light| {
+                    let extent = glam::Vec3::splat(light.range);
+                    frustum.intersects(&crate::culling::Aabb {
+                        min: light.position - extent,
+                        max: light.position + extent,
+                    })
+                })
+                .count();
+        self.clusters.update(
+            device,
+            uploader,
+            encoder,
+            &gpu_lights,
+            &camera_view,
+            &projection,
+            self.targets.size,
+            self.camera.near,
+            self.camera.far,
+        );
+        let eye = self.camera.eye();
+        let pixels_per_unit =
+            self.targets.size.height as f32 / (2.0 * (self.camera.fov_y * 0.5).tan());
+        let probe_sphere = self
+            .meshes
+            .iter()
+            .position(|mesh| mesh.name == "Sphere")
+            .filter(|_| self.probe.show_sphere)
+            .map(|sphere| Object {
+                name: "Reflection probe".to_owned(),
+                mesh: sphere,
+                transform: glam::Mat4::from_scale_rotation_translation(
+                    glam::Vec3::splat(0.5),
+                    glam::Quat::identity(),
+                    self.probe.position,
+                ),
+                color: [1.0, 1.0, 1.0, 1.0],
+                metallic: 1.0,
+                roughness: 0.0,
+            });
+        let mut opaque = Vec::with_capacity(self.objects.len() + 1);
+        let mut transparent = Vec::new();
+        self.emit_debug_lines();
+        self.debug
+            .set_category(crate::debug::Category::Measurements);
+        self.measurements.emit_lines(&mut self.debug);
+        self.debug.set_category(crate::debug::Category::Selection);
+        for (index, color) in [
+            (self.picker.hovered, [1.0, 1.0, 1.0, 0.6]),
+            (self.picker.selected, [1.0, 0.8, 0.1, 1.0]),
+        ]
+        .iter()
+        {
+            if let Some(index) = index {
+                if let Some(object) = self.objects.get(*index) {
+                    let bounds = self.gpu_meshes[object.mesh]
+                        .bounds
+                        .transformed(&object.transform);
+                    self.debug.aabb(&bounds, *color);
+                }
+            }
+        }
+        self.debug.set_category(crate::debug::Category::Bounds);
+        for (index, object) in self.objects.iter().enumerate() {
+            let mesh = &self.gpu_meshes[object.mesh];
+            let bounds = mesh.bounds.transformed(&object.transform);
+            if self.frustum_culling && !frustum.intersects(&bounds) {
+                continue;
+            }
+            self.debug.aabb(&bounds, [0.2, 1.0, 0.2, 1.0]);
+            let columns = object.transform.to_cols_array_2d();
+            let scale = (0..3)
+                .map(|axis| glam::Vec3::new(columns[axis][0], columns[axis][1], columns[axis][2]))
+                .map(|axis| axis.length())
+                .fold(0.0, f32::max);
+            let center_distance = (bounds.center() - eye).length();
+            let distance = center_distance - bounds.extents().length();
+            let level = crate::lod::select(
+                &mesh.lod_errors,
+                scale,
+                distance,
+                pixels_per_unit,
+                self.lod_threshold,
+            );
+            if object.color[3] < 1.0 {
+                transparent.push((object, level, center_distance, index));
+            } else {
+                opaque.push((object, level, center_distance, index));
+            }
+        }
+        if let Some(object) = &probe_sphere {
+            opaque.push((object, 0, 0.0, self.objects.len()));
+        }
+        if self.instancing {
+            opaque.sort_by_key(|(object, level, _, _)| (object.mesh, *level));
+        }
+        let sorted = self.transparency == Transparency::Sorted || msaa;
+        if sorted {
+            transparent.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+        } else if self.instancing {
+            transparent.sort_by_key(|(object, level, _, _)| (object.mesh, *level));
+        }
+
+        let object_data: Vec<ObjectData> = opaque
+            .iter()
+            .chain(transparent.iter())
+            .map(|(object, level, _, index)| ObjectData {
+                model: object.transform.to_cols_array(),
+                color: if self.lod_debug {
+                    let mut color = crate::lod::DEBUG_COLORS[*level];
+                    color[3] = object.color[3];
+                    color
+                } else {
+                    object.color
+                },
+                material: [object.metallic, object.roughness, 0.0, 0.0],
+                id: [*index as u32, 0, 0, 0],
+            })
+            .collect();
+
+        if object_data.len() > self.object_capacity {
+            self.object_capacity = object_data.len().next_power_of_two();
+            self.object_buffer = create_object_buffer(device, self.object_capacity);
+            self.bind_group = create_bind_group(
+                device,
+                &self.bind_group_layout,
+                &self.globals_buffer,
+                &self.object_buffer,
+                &self.clusters,
+                &self.probe,
+            );
+        }
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.object_buffer,
+            0,
+            bytemuck::cast_slice(&object_data),
+        );
+
+        if !self.picker.is_busy() {
+            if let Some(pick_view_proj) = self.picker.pick_view_proj(&view_proj, size) {
+                {
+                    let mut render_pass =
+                        self.picker
+                            .begin_pass(device, uploader, encoder, &pick_view_proj);
+                    render_pass.set_bind_group(0, &self.bind_group, &[]);
+                    let mut pick_stats = Stats::default();
+                    draw_batches(
+                        &mut render_pass,
+                        &self.gpu_meshes,
+                        &opaque,
+                        0,
+                        self.instancing,
+                        &mut pick_stats,
+                    );
+                    draw_batches(
+                        &mut render_pass,
+                        &self.gpu_meshes,
+                        &transparent,
+                        opaque.len(),
+                        self.instancing && !sorted,
+                        &mut pick_stats,
+                    );
+                }
+                self.picker.copy_to_readback(encoder);
+            }
+        }
+
+        let mut stats = Stats {
+            objects: self.objects.len() as u32,
+            culled: (self.objects.len() + probe_sphere.is_some() as usize
+                - opaque.len()
+                - transparent.len()) as u32,
+            lights: self.lights.len() as u32,
+            visible_lights: visible_lights as u32,
+            transparent: transparent.len() as u32,
+            ..Stats::default()
+        };
+
+        let (color_attachment, resolve_target, depth_attachment, opaque_pipeline, blend_pipeline) =
+            match &self.targets.msaa {
+                Some((color, depth)) => (
+                    color,
+                    Some(&self.targets.color_view),
+                    depth,
+                    &self.msaa_pipeline,
+                    &self.msaa_transparent_pipeline,
+                ),
+                None => (
+                    &self.targets.color_view,
+                    None,
+                    &self.targets.depth_view,
+                    &self.pipeline,
+                    &self.transparent_pipeline,
+                ),
+            };
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                    attachment: color_attachment,
+                    resolve_target,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear_color),
+                        store: true,
+                    },
+                }],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
+                    attachment: depth_attachment,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: true,
+                    }),
+                    stencil_ops: None,
+                }),
+            });
+            render_pass.set_pipeline(opaque_pipeline);
+            render_pass.set_bind_group(0, &self.bind_group, &[]);
+            draw_batches(
+                &mut render_pass,
+                &self.gpu_meshes,
+                &opaque,
+                0,
+                self.instancing,
+                &mut stats,
+            );
+            self.terrain
+                .draw(&mut render_pass, msaa, &frustum, &mut stats);
+            self.voxels
+                .draw(&mut render_pass, msaa, &frustum, &mut stats);
+        }
+
+        if !msaa {
+            self.ssao.render(
+                device,
+                uploader,
+                encoder,
+                &projection,
+                &self.targets.color_view,
+            );
+        }
+        let ao_debug = !msaa && self.ssao.settings.enabled && self.ssao.settings.debug;
+
+        if sorted && !transparent.is_empty() && !ao_debug {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                    attachment: color_attachment,
+                    resolve_target,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: true,
+                    },
+                }],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
+                    attachment: depth_attachment,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: true,
+                    }),
+                    stencil_ops: None,
+                }),
+            });
+            render_pass.set_bind_group(0, &self.bind_group, &[]);
+            render_pass.set_pipeline(blend_pipeline);
+            draw_batches(
+                &mut render_pass,
+                &self.gpu_meshes,
+                &transparent,
+                opaque.len(),
+                false,
+                &mut stats,
+            );
+        }
+
+        if !sorted && !transparent.is_empty() && !ao_debug {
+            {
+                let mut render_pass = self.oit.begin_accumulate(encoder, &self.targets.depth_view);
+                render_pass.set_bind_group(0, &self.bind_group, &[]);
+                draw_batches(
+                    &mut render_pass,
+                    &self.gpu_meshes,
+                    &transparent,
+                    opaque.len(),
+                    self.instancing,
+                    &mut stats,
+                );
+            }
+            self.oit.composite(encoder, &self.targets.color_view);
+        }
+
+        if self.debug.upload(device, uploader, encoder) && !ao_debug {
+            let (color_attachment, resolve_target, depth_attachment) = match &self.targets.msaa {
+                Some((color, depth)) => (color, Some(&self.targets.color_view), depth),
+                None => (&self.targets.color_view, None, &self.targets.depth_view),
+            };
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                    attachment: color_attachment,
+                    resolve_target,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: true,
+                    },
+                }],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
+                    attachment: depth_attachment,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: true,
+                    }),
+                    stencil_ops: None,
+                }),
+            });
+            render_pass.set_bind_group(0, &self.bind_group, &[]);
+            self.debug.draw(&mut render_pass, msaa);
+        }
+
+        if taa && !ao_debug {
+            self.taa.resolve(
+                device,
+                uploader,
+                encoder,
+                &view_proj,
+                &self.targets.color_texture,
+            );
+        }
+
+        if !ao_debug {
+            self.bloom
+                .render(device, uploader, encoder, &self.targets.color_view);
+        }
+
+        match viewport {
+            Some(viewport) => {
+                self.blitter
+                    .draw_in(encoder, &self.targets.blit_bind_group, view, viewport)
+            }
+            None => self
+                .blitter
+                .draw(encoder, &self.targets.blit_bind_group, view),
+        }
+        if let Some(source) = visualizer_source(
+            &self.targets,
+            &self.ssao,
+            &self.bloom,
+            self.visualizer.settings.output,
+        ) {
+            self.visualizer.draw(
+                device,
+                uploader,
+                encoder,
+                &mut self.cache,
+                self.targets_generation,
+                source,
+                &projection,
+                view,
+                self.size,
+            );
+        }
+
+        self.cache.end_frame();
+        stats.encode_time = encode_start.elapsed().as_secs_f32();
+        self.stats = stats;
+    }
+}
+
+fn visualizer_source<'a>(
+    targets: &'a Targets,
+    ssao: &'a crate::ssao::Ssao,
+    bloom: &'a crate::bloom::Bloom,
+    output: crate::visualizer::Output,
+) -> Option<&'a wgpu::TextureView> {
+    let single_sampled = targets.msaa.is_none();
+    match output {
+        crate::visualizer::Output::Final => None,
+        crate::visualizer::Output::SceneColor => Some(&targets.color_view),
+        crate::visualizer::Output::Depth | crate::visualizer::Output::Normals if single_sampled => {
+            Some(&targets.depth_view)
+        }
+        crate::visualizer::Output::AmbientOcclusion if single_sampled && ssao.settings.enabled => {
+            Some(ssao.ao_view())
+        }
+        crate::visualizer::Output::Bloom(level) if bloom.settings.enabled => {
+            bloom.level_view(level)
+        }
+        _ => None,
+    }
+}
+
+fn ray_triangle(
+    origin: glam::Vec3,
+    direction: glam::Vec3,
+    [a, b, c]: [glam::Vec3; 3],
+) -> Option<f32> {
+    let edge1 = b - a;
+    let edge2 = c - a;
+    let p = direction.cross(edge2);
+    let determinant = edge1.dot(p);
+    if determinant.abs() < 1e-7 {
+        return None;
+    }
+    let offset = origin - a;
+    let u = offset.dot(p) / determinant;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = offset.cross(edge1);
+    let v = direction.dot(q) / determinant;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let distance = edge2.dot(q) / determinant;
+    if distance > 0.0 {
+        Some(distance)
+    } else {
+        None
+    }
+}
+
+fn draw_batches<'a>(
+    render_pass: &mut wgpu::RenderPass<'a>,
+    gpu_meshes: &'a [GpuMesh],
+    visible: &[(&Object, usize, f32, usize)],
+    first_instance: usize,
+    instancing: bool,
+    stats: &mut Stats,
+) {
+    let mut first = 0;
+    while first < visible.len() {
+        let (object, level, _, _) = visible[first];
+        let mut last = first + 1;
+        if instancing {
+            while last < visible.len()
+                && visible[last].0.mesh == object.mesh
+                && visible[last].1 == level
+            {
+                last += 1;
+            }
+        }
+
+        let mesh = &gpu_meshes[object.mesh];
+        let lod = &mesh.lods[level];
+        let instances = last - first;
+        let base = (first_instance + first) as u32;
+        render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(lod.index_buffer.slice(..));
+        render_pass.draw_indexed(0..lod.index_count, 0, base..base + instances as u32);
+        stats.draw_calls += 1;
+        stats.instances += instances as u32;
+        stats.triangles += (lod.index_count / 3) as u64 * instances as u64;
+        stats.lod_counts[level] += instances as u32;
+        first = last;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_pipeline(
+    device: &wgpu::Device,
+    label: &str,
+    layout: &wgpu::PipelineLayout,
+    vs_module: &wgpu::ShaderModule,
+    fs_module: &wgpu::ShaderModule,
+    color_states: &[wgpu::ColorStateDescriptor],
+    depth_write: bool,
+    sample_count: u32,
+) -> wgpu::RenderPipeline {
+    create_pipeline_with_winding(
+        device,
+        label,
+        layout,
+        vs_module,
+        fs_module,
+        color_states,
+        depth_write,
+        sample_count,
+        wgpu::FrontFace::Ccw,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_pipeline_with_winding(
+    device: &wgpu::Device,
+    label: &str,
+    layout: &wgpu::PipelineLayout,
+    vs_module: &wgpu::ShaderModule,
+    fs_module: &wgpu::ShaderModule,
+    color_states: &[wgpu::ColorStateDescriptor],
+    depth_write: bool,
+    sample_count: u32,
+    front_face: wgpu::FrontFace,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(layout),
+        vertex_stage: wgpu::ProgrammableStageDescriptor {
+            module: vs_module,
+            entry_point: "main",
+        },
+        fragment_stage: Some(wgpu::ProgrammableStageDescriptor {
+            module: fs_module,
+            entry_point: "main",
+        }),
+        rasterization_state: Some(wgpu::RasterizationStateDescriptor {
+            front_face,
+            cull_mode: wgpu::CullMode::Back,
+            ..Default::default()
+        }),
+        primitive_topology: wgpu::PrimitiveTopology::TriangleList,
+        color_states,
+        depth_stencil_state: Some(wgpu::DepthStencilStateDescriptor {
+            format: DEPTH_FORMAT,
+            depth_write_enabled: depth_write,
+            depth_compare: wgpu::CompareFunction::Less,
+            stencil: wgpu::StencilStateDescriptor::default(),
+        }),
+        vertex_state: wgpu::VertexStateDescriptor {
+            index_format: wgpu::IndexFormat::Uint32,
+            vertex_buffers: &[wgpu::VertexBufferDescriptor {
+                stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
+                step_mode: wgpu::InputStepMode::Vertex,
+                attributes: &wgpu::vertex_attr_array![0 => Float3, 1 => Float3],
+            }],
+        },
+        sample_count,
+        sample_mask: !0,
+        alpha_to_coverage_enabled: false,
+    })
+}
+
+fn create_object_buffer(device: &wgpu::Device, capacity: usize) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Scene Objects"),
+        size: (capacity * std::mem::size_of::<ObjectData>()) as wgpu::BufferAddress,
+        usage: wgpu::BufferUsage::STORAGE | wgpu::BufferUsage::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+fn create_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    globals_buffer: &wgpu::Buffer,
+    object_buffer: &wgpu::Buffer,
+    clusters: &crate::clustered::ClusteredLights,
+    probe: &crate::probe::Probe,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Scene Bind Group"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer(globals_buffer.slice(..)),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Buffer(object_buffer.slice(..)),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: wgpu::BindingResource::Buffer(clusters.light_buffer.slice(..)),
+            },
+            wgpu::BindGroupEntry {
+                binding: 3,
+                resource: wgpu::BindingResource::Buffer(clusters.count_buffer.slice(..)),
+            },
+            wgpu::BindGroupEntry {
+                binding: 4,
+                resource: wgpu::BindingResource::Buffer(clusters.index_buffer.slice(..)),
+            },
+            wgpu::BindGroupEntry {
+                binding: 5,
+                resource: wgpu::BindingResource::TextureView(probe.cube_view()),
+            },
+            wgpu::BindGroupEntry {
+                binding: 6,
+                resource: wgpu::BindingResource::Sampler(probe.sampler()),
+            },
+        ],
+    })
+}
+
+fn create_depth_view(
+    device: &wgpu::Device,
+    size: winit::dpi::PhysicalSize<u32>,
+    sample_count: u32,
+) -> wgpu::TextureView {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Scene Depth"),
+        size: wgpu::Extent3d {
+            width: size.width.max(1),
+            height: size.height.max(1),
+            depth: 1,
+        },
+        mip_level_count: 1,
+        sample_count,
+        dimension: wgpu::TextureDimension::D2,
+        format: DEPTH_FORMAT,
+        usage: if sample_count > 1 {
+            wgpu::TextureUsage::OUTPUT_ATTACHMENT
+        } else {
+            wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::SAMPLED
+        },
+    });
+    texture.create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+fn spawn(engine: &mut crate::Engine, args: &[&str]) -> Result<String, String> {
+    let scene = engine.scene.as_mut().ok_or("the scene is still loading")?;
+    let count = crate::console::parse_count(args.get(1).copied())?;
+    match args.first() {
+        Some(&"cube") => scene.scatter_cubes(count),
+        Some(&"light") => scene.scatter_lights(count),
+        _ => return Err("usage: spawn cube|light [count]".to_owned()),
+    }
+    Ok(format!(
+        "{} objects, {} lights",
+        scene.objects.len(),
+        scene.lights.len()
+    ))
+}
+
+fn despawn(engine: &mut crate::Engine, _: &[&str]) -> Result<String, String> {
+    let scene = engine.scene.as_mut().ok_or("the scene is still loading")?;
+    scene.clear_scattered();
+    Ok(format!("{} objects left", scene.objects.len()))
+}
+
+pub fn register_commands(registry: &mut crate::console::Registry) {
+    registry.register("spawn", "spawn cube|light [count]", spawn);
+    registry.register("despawn", "remove every spawned cube", despawn);
+}
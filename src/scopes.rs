@@ -0,0 +1,478 @@
+const BINS: usize = 256;
+const WAVEFORM_COLUMNS: usize = 128;
+const WAVEFORM_ROWS: usize = 48;
+const COUNTS: usize = BINS * 4 + WAVEFORM_COLUMNS * WAVEFORM_ROWS;
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+const PLOT_SIZE: [f32; 2] = [256.0, 96.0];
+const BACKGROUND: egui::Srgba = egui::Srgba::new(24, 24, 28, 255);
+const CHANNEL_COLORS: [egui::Srgba; 4] = [
+    egui::Srgba::new(230, 70, 60, 160),
+    egui::Srgba::new(70, 200, 90, 160),
+    egui::Srgba::new(70, 120, 240, 160),
+    egui::Srgba::new(220, 220, 220, 200),
+];
+
+type Readback =
+    std::pin::Pin<Box<dyn std::future::Future<Output = Result<(), wgpu::BufferAsyncError>> + Send>>;
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    size: [u32; 2],
+    encode: u32,
+    padding: u32,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Source {
+    Image,
+    Frame,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Histogram {
+    Rgb,
+    Luma,
+}
+
+pub enum Input<'a> {
+    Image(u64, &'a image::RgbaImage),
+    Frame(&'a wgpu::TextureView, winit::dpi::PhysicalSize<u32>),
+}
+
+struct ImageTexture {
+    revision: u64,
+    size: [u32; 2],
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+}
+
+pub struct Scopes {
+    pub open: bool,
+    source: Source,
+    histogram: Histogram,
+    latency: u32,
+    counts: Vec<u32>,
+    analyzed: Option<(Source, u64)>,
+    layout: wgpu::BindGroupLayout,
+    pipeline: wgpu::ComputePipeline,
+    sampler: wgpu::Sampler,
+    uniform_buffer: wgpu::Buffer,
+    counts_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    image: Option<ImageTexture>,
+    encoded: bool,
+    pending: Option<Readback>,
+    frames_pending: u32,
+}
+
+impl Scopes {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Scopes Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::COMPUTE,
+                    ty: wgpu::BindingType::UniformBuffer {
+                        dynamic: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStage::COMPUTE,
+                    ty: wgpu::BindingType::SampledTexture {
+                        dimension: wgpu::TextureViewDimension::D2,
+                        component_type: wgpu::TextureComponentType::Float,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStage::COMPUTE,
+                    ty: wgpu::BindingType::Sampler { comparison: false },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStage::COMPUTE,
+                    ty: wgpu::BindingType::StorageBuffer {
+                        dynamic: false,
+                        min_binding_size: None,
+                        readonly: false,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let cs_module = crate::shader::compile_builtin(
+            device,
+            "scopes.comp",
+            include_str!("shaders/scopes.comp"),
+            shaderc::ShaderKind::Compute,
+        );
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Scopes Pipeline Layout"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Scopes Pipeline"),
+            layout: Some(&pipeline_layout),
+            compute_stage: wgpu::ProgrammableStageDescriptor {
+                module: &cs_module,
+                entry_point: "main",
+            },
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Scopes Sampler"),
+            ..Default::default()
+        });
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Scopes Uniforms"),
+            size: std::mem::size_of::<Uniforms>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let counts_size = (COUNTS * std::mem::size_of::<u32>()) as wgpu::BufferAddress;
+        let counts_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Scopes Counts"),
+            size: counts_size,
+            usage: wgpu::BufferUsage::STORAGE
+                | wgpu::BufferUsage::COPY_DST
+                | wgpu::BufferUsage::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Scopes Readback"),
+            size: counts_size,
+            usage: wgpu::BufferUsage::MAP_READ | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            open: false,
+            source: Source::Image,
+            histogram: Histogram::Rgb,
+            latency: 0,
+            counts: Vec::new(),
+            analyzed: None,
+            layout,
+            pipeline,
+            sampler,
+            uniform_buffer,
+            counts_buffer,
+            readback_buffer,
+            image: None,
+            encoded: false,
+            pending: None,
+            frames_pending: 0,
+        }
+    }
+
+    pub fn recreate_gpu_resources(&mut self, device: &wgpu::Device) {
+        let open = self.open;
+        let source = self.source;
+        let histogram = self.histogram;
+        *self = Self::new(device);
+        self.open = open;
+        self.source = source;
+        self.histogram = histogram;
+    }
+
+    pub fn wants_input(&self) -> Option<Source> {
+        if !self.open || self.pending.is_some() {
+            return None;
+        }
+        Some(self.source)
+    }
+
+    fn upload(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        revision: u64,
+        image: &image::RgbaImage,
+    ) {
+        let size = [image.width().max(1), image.height().max(1)];
+        if self.image.as_ref().map(|texture| texture.size) != Some(size) {
+            let texture = device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("Scopes Image"),
+                size: wgpu::Extent3d {
+                    width: size[0],
+                    height: size[1],
+                    depth: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: FORMAT,
+                usage: wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST,
+            });
+            let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+            self.image = Some(ImageTexture {
+                revision: 0,
+                size,
+                texture,
+                view,
+            });
+        }
+        let texture = self.image.as_mut().unwrap();
+        if texture.revision == revision || image.width() == 0 || image.height() == 0 {
+            return;
+        }
+        queue.write_texture(
+            wgpu::TextureCopyView {
+                texture: &texture.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            image.as_raw(),
+            wgpu::TextureDataLayout {
+                offset: 0,
+                bytes_per_row: 4 * size[0],
+                rows_per_image: size[1],
+            },
+            wgpu::Extent3d {
+                width: size[0],
+                height: size[1],
+                depth: 1,
+            },
+        );
+        texture.revision = revision;
+    }
+
+    pub fn dispatch(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        input: Input,
+    ) {
+        let (analyzed, size, encode) = match input {
+            Input::Image(revision, image) => {
+                if self.analyzed == Some((Source::Image, revision)) {
+                    return;
+                }
+                self.upload(device, queue, revision, image);
+                (
+                    (Source::Image, revision),
+                    self.image.as_ref().unwrap().size,
+                    0,
+                )
+            }
+            Input::Frame(_, size) => ((Source::Frame, 0), [size.width, size.height], 1),
+        };
+        let view = match input {
+            Input::Image(..) => &self.image.as_ref().unwrap().view,
+            Input::Frame(view, _) => view,
+        };
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&Uniforms {
+                size,
+                encode,
+                padding: 0,
+            }),
+        );
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.counts_buffer,
+            0,
+            bytemuck::cast_slice(&[0u32; COUNTS]),
+        );
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Scopes Bind Group"),
+            layout: &self.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(self.uniform_buffer.slice(..)),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::Buffer(self.counts_buffer.slice(..)),
+                },
+            ],
+        });
+        {
+            let mut compute_pass = encoder.begin_compute_pass();
+            compute_pass.set_pipeline(&self.pipeline);
+            compute_pass.set_bind_group(0, &bind_group, &[]);
+            compute_pass.dispatch(size[0].div_ceil(16), size[1].div_ceil(16), 1);
+        }
+        encoder.copy_buffer_to_buffer(
+            &self.counts_buffer,
+            0,
+            &self.readback_buffer,
+            0,
+            (COUNTS * std::mem::size_of::<u32>()) as wgpu::BufferAddress,
+        );
+        self.analyzed = Some(analyzed);
+        self.encoded = true;
+    }
+
+    pub fn after_submit(&mut self) {
+        if self.encoded {
+            self.encoded = false;
+            self.frames_pending = 0;
+            self.pending = Some(Box::pin(
+                self.readback_buffer
+                    .slice(..)
+                    .map_async(wgpu::MapMode::Read),
+            ));
+        }
+    }
+
+    pub fn poll(&mut self, device: &wgpu::Device) {
+        let pending = match &mut self.pending {
+            Some(pending) => pending,
+            None => return,
+        };
+        device.poll(wgpu::Maintain::Poll);
+        self.frames_pending += 1;
+        match futures::FutureExt::now_or_never(pending.as_mut()) {
+            Some(Ok(())) => {
+                self.counts = {
+                    let data = self.readback_buffer.slice(..).get_mapped_range();
+                    bytemuck::cast_slice(&data).to_vec()
+                };
+                self.readback_buffer.unmap();
+                self.pending = None;
+                self.latency = self.frames_pending;
+            }
+            Some(Err(_)) => {
+                log::warn!("scopes readback failed");
+                self.pending = None;
+                self.analyzed = None;
+            }
+            None => {}
+        }
+    }
+
+    fn show_histogram(&self, ui: &mut egui::Ui) {
+        let rect = ui.allocate_space(egui::vec2(PLOT_SIZE[0], PLOT_SIZE[1]));
+        ui.painter().rect_filled(rect, 0.0, BACKGROUND);
+        let channels: &[usize] = match self.histogram {
+            Histogram::Rgb => &[0, 1, 2],
+            Histogram::Luma => &[3],
+        };
+        let peak = channels
+            .iter()
+            .flat_map(|channel| self.counts[channel * BINS..(channel + 1) * BINS].iter())
+            .copied()
+            .max()
+            .unwrap_or(0)
+            .max(1) as f32;
+        let width = rect.width() / BINS as f32;
+        for channel in channels.iter() {
+            let bins = &self.counts[channel * BINS..(channel + 1) * BINS];
+            for (index, count) in bins.iter().enumerate().filter(|(_, count)| **count > 0) {
+                let x = rect.left() + (index as f32 + 0.5) * width;
+                let height = (*count as f32 / peak).sqrt() * rect.height();
+                ui.painter().line_segment(
+                    [
+                        egui::pos2(x, rect.bottom()),
+                        egui::pos2(x, rect.bottom() - height),
+                    ],
+                    egui::Stroke::new(width.max(1.0), CHANNEL_COLORS[*channel]),
+                );
+            }
+        }
+    }
+
+    fn show_waveform(&self, ui: &mut egui::Ui) {
+        let rect = ui.allocate_space(egui::vec2(PLOT_SIZE[0], PLOT_SIZE[1]));
+        ui.painter().rect_filled(rect, 0.0, BACKGROUND);
+        let waveform = &self.counts[BINS * 4..];
+        let peak = waveform.iter().copied().max().unwrap_or(0).max(1) as f32;
+        let cell = egui::vec2(
+            rect.width() / WAVEFORM_COLUMNS as f32,
+            rect.height() / WAVEFORM_ROWS as f32,
+        );
+        for (index, count) in waveform.iter().enumerate().filter(|(_, count)| **count > 0) {
+            let column = (index % WAVEFORM_COLUMNS) as f32;
+            let row = (index / WAVEFORM_COLUMNS) as f32;
+            let intensity = (*count as f32 / peak).sqrt();
+            let level = (60.0 + intensity * 195.0) as u8;
+            ui.painter().rect_filled(
+                egui::Rect::from_min_size(
+                    egui::pos2(
+                        rect.left() + column * cell.x,
+                        rect.bottom() - (row + 1.0) * cell.y,
+                    ),
+                    cell,
+                ),
+                0.0,
+                egui::Srgba::new(level / 3, level, level / 3, 255),
+            );
+        }
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, available: [bool; 2]) {
+        let mut open = self.open;
+        let mut source = self.source;
+        let mut histogram = self.histogram;
+        crate::dock::Panel::new("Scopes")
+            .open(&mut open)
+            .default_pos(egui::pos2(640.0, 100.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Source");
+                    ui.radio_value(&mut source, Source::Image, "Image viewer");
+                    ui.radio_value(&mut source, Source::Frame, "Rendered frame");
+                });
+                let available = match source {
+                    Source::Image => available[0],
+                    Source::Frame => available[1],
+                };
+                if !available {
+                    ui.label(match source {
+                        Source::Image => "Open an image in the image viewer",
+                        Source::Frame => "The scene is still loading",
+                    });
+                    return;
+                }
+                if self.counts.len() != COUNTS {
+                    ui.label("Computing...");
+                    return;
+                }
+                ui.horizontal(|ui| {
+                    ui.label("Histogram");
+                    ui.radio_value(&mut histogram, Histogram::Rgb, "RGB");
+                    ui.radio_value(&mut histogram, Histogram::Luma, "Luma");
+                });
+                self.show_histogram(ui);
+                ui.label("Waveform (luma)");
+                self.show_waveform(ui);
+                let pixels: u32 = self.counts[BINS * 3..BINS * 4].iter().sum();
+                ui.label(format!(
+                    "{} pixels, read back after {} frames",
+                    pixels, self.latency
+                ));
+            });
+        if source != self.source {
+            self.counts.clear();
+            self.analyzed = None;
+        }
+        self.open = open;
+        self.source = source;
+        self.histogram = histogram;
+    }
+}
@@ -0,0 +1,348 @@
+const VIEW_SIZE: [f32; 2] = [400.0, 300.0];
+const SOURCE_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/shaders/sdf.frag");
+const RELOAD_INTERVAL: f32 = 0.5;
+const ORBIT_SPEED: f32 = 0.01;
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    eye: [f32; 4],
+    target: [f32; 4],
+    shape: [f32; 4],
+    light: [f32; 4],
+    color: [f32; 4],
+}
+
+pub struct Settings {
+    pub blend: f32,
+    pub smoothness: f32,
+    pub repetition: f32,
+    pub twist: f32,
+    pub light_yaw: f32,
+    pub light_pitch: f32,
+    pub shadow_softness: f32,
+    pub ambient: f32,
+    pub color: [f32; 3],
+    pub yaw: f32,
+    pub pitch: f32,
+    pub distance: f32,
+    pub hot_reload: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            blend: 0.0,
+            smoothness: 0.3,
+            repetition: 0.0,
+            twist: 0.0,
+            light_yaw: 0.6,
+            light_pitch: 0.8,
+            shadow_softness: 8.0,
+            ambient: 0.15,
+            color: [0.9, 0.45, 0.3],
+            yaw: 0.5,
+            pitch: 0.35,
+            distance: 6.0,
+            hot_reload: true,
+        }
+    }
+}
+
+pub struct SdfPlayground {
+    pub open: bool,
+    pub settings: Settings,
+    pub reloads: u32,
+    pub error: Option<String>,
+    cursor: Option<winit::dpi::PhysicalPosition<f64>>,
+    orbiting: bool,
+    viewport: Option<egui::Rect>,
+    physical_viewport: Option<egui::Rect>,
+    canvas: crate::blit::Canvas,
+    modified: Option<std::time::SystemTime>,
+    last_check: std::time::Instant,
+    format: wgpu::TextureFormat,
+    layout: wgpu::BindGroupLayout,
+    pipeline: wgpu::RenderPipeline,
+    bind_group: wgpu::BindGroup,
+    uniform_buffer: wgpu::Buffer,
+}
+
+impl SdfPlayground {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("SDF Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStage::FRAGMENT,
+                ty: wgpu::BindingType::UniformBuffer {
+                    dynamic: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let pipeline = crate::blit::fullscreen_pipeline(
+            device,
+            "SDF Pipeline",
+            &[&layout],
+            "sdf.frag",
+            include_str!("shaders/sdf.frag"),
+            format,
+            wgpu::BlendDescriptor::REPLACE,
+        );
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("SDF Uniforms"),
+            size: std::mem::size_of::<Uniforms>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("SDF Bind Group"),
+            layout: &layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer(uniform_buffer.slice(..)),
+            }],
+        });
+
+        Self {
+            open: false,
+            settings: Settings::default(),
+            reloads: 0,
+            error: None,
+            cursor: None,
+            orbiting: false,
+            viewport: None,
+            physical_viewport: None,
+            canvas: crate::blit::Canvas::new(format),
+            modified: std::fs::metadata(SOURCE_PATH)
+                .and_then(|metadata| metadata.modified())
+                .ok(),
+            last_check: std::time::Instant::now(),
+            format,
+            layout,
+            pipeline,
+            bind_group,
+            uniform_buffer,
+        }
+    }
+
+    pub fn recreate_gpu_resources(&mut self, device: &wgpu::Device, format: wgpu::TextureFormat) {
+        let settings = std::mem::take(&mut self.settings);
+        let open = self.open;
+        *self = Self::new(device, format);
+        self.settings = settings;
+        self.open = open;
+    }
+
+    fn hovered(&self) -> bool {
+        match (self.cursor, self.physical_viewport) {
+            (Some(cursor), Some(rect)) => {
+                rect.contains(egui::pos2(cursor.x as f32, cursor.y as f32))
+            }
+            _ => false,
+        }
+    }
+
+    pub fn set_cursor(&mut self, cursor: Option<winit::dpi::PhysicalPosition<f64>>) {
+        if let (true, Some(previous), Some(cursor)) = (self.orbiting, self.cursor, cursor) {
+            let settings = &mut self.settings;
+            settings.yaw -= (cursor.x - previous.x) as f32 * ORBIT_SPEED;
+            settings.pitch =
+                (settings.pitch + (cursor.y - previous.y) as f32 * ORBIT_SPEED).clamp(-1.5, 1.5);
+        }
+        self.cursor = cursor;
+        if cursor.is_none() {
+            self.orbiting = false;
+        }
+    }
+
+    pub fn mouse_input(
+        &mut self,
+        state: winit::event::ElementState,
+        button: winit::event::MouseButton,
+    ) {
+        if button != winit::event::MouseButton::Right {
+            return;
+        }
+        self.orbiting = state == winit::event::ElementState::Pressed && self.hovered();
+    }
+
+    fn reload(&mut self, device: &wgpu::Device) {
+        if self.last_check.elapsed().as_secs_f32() < RELOAD_INTERVAL {
+            return;
+        }
+        self.last_check = std::time::Instant::now();
+        let modified = match std::fs::metadata(SOURCE_PATH).and_then(|metadata| metadata.modified())
+        {
+            Ok(modified) => modified,
+            Err(_) => return,
+        };
+        if self.modified == Some(modified) {
+            return;
+        }
+        self.modified = Some(modified);
+
+        let source = match std::fs::read_to_string(SOURCE_PATH) {
+            Ok(source) => source,
+            Err(err) => {
+                log::warn!("failed to read {}: {}", SOURCE_PATH, err);
+                return;
+            }
+        };
+        match crate::shader::compile(device, "sdf.frag", &source, shaderc::ShaderKind::Fragment) {
+            Ok(module) => {
+                self.pipeline = crate::blit::fullscreen_pipeline_with_module(
+                    device,
+                    "SDF Pipeline",
+                    &[&self.layout],
+                    &module,
+                    self.format,
+                    wgpu::BlendDescriptor::REPLACE,
+                );
+                self.reloads += 1;
+                self.error = None;
+                log::info!("reloaded sdf.frag");
+            }
+            Err(err) => {
+                log::warn!("failed to reload sdf.frag: {}", err);
+                self.error = Some(err);
+            }
+        }
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        let mut open = self.open;
+        let mut viewport = None;
+        let reloads = self.reloads;
+        let error = &self.error;
+        let settings = &mut self.settings;
+        let canvas = &self.canvas;
+        crate::dock::Panel::new("SDF Playground")
+            .open(&mut open)
+            .default_pos(egui::pos2(440.0, 100.0))
+            .show(ctx, |ui| {
+                egui::CollapsingHeader::new("Shape").show(ui, |ui| {
+                    ui.add(egui::Slider::f32(&mut settings.blend, 0.0..=1.0).text("sphere / box"));
+                    ui.add(
+                        egui::Slider::f32(&mut settings.smoothness, 0.0..=1.0).text("smooth union"),
+                    );
+                    ui.add(
+                        egui::Slider::f32(&mut settings.repetition, 0.0..=8.0)
+                            .text("repetition spacing"),
+                    );
+                    ui.add(egui::Slider::f32(&mut settings.twist, -2.0..=2.0).text("twist"));
+                });
+                egui::CollapsingHeader::new("Lighting").show(ui, |ui| {
+                    ui.add(
+                        egui::Slider::f32(
+                            &mut settings.light_yaw,
+                            -std::f32::consts::PI..=std::f32::consts::PI,
+                        )
+                        .text("light yaw"),
+                    );
+                    ui.add(
+                        egui::Slider::f32(&mut settings.light_pitch, 0.05..=1.5)
+                            .text("light pitch"),
+                    );
+                    ui.add(
+                        egui::Slider::f32(&mut settings.shadow_softness, 1.0..=32.0)
+                            .text("shadow sharpness"),
+                    );
+                    ui.add(egui::Slider::f32(&mut settings.ambient, 0.0..=1.0).text("ambient"));
+                    ui.add(egui::Slider::f32(&mut settings.color[0], 0.0..=1.0).text("red"));
+                    ui.add(egui::Slider::f32(&mut settings.color[1], 0.0..=1.0).text("green"));
+                    ui.add(egui::Slider::f32(&mut settings.color[2], 0.0..=1.0).text("blue"));
+                });
+                ui.add(egui::Slider::f32(&mut settings.distance, 2.0..=30.0).text("distance"));
+                ui.checkbox(&mut settings.hot_reload, "Hot reload sdf.frag");
+                ui.label(format!("Reloaded {} times", reloads));
+                if let Some(error) = error {
+                    ui.label(format!("Compile error, keeping last shader:\n{}", error));
+                }
+                ui.label("Right-drag to orbit");
+                let rect = ui.allocate_space(egui::vec2(VIEW_SIZE[0], VIEW_SIZE[1]));
+                ui.interact(rect, ui.make_persistent_id("sdf_view"), egui::Sense::drag());
+                canvas.paint(ui, rect);
+                viewport = Some(rect);
+            });
+        self.open = open;
+        self.viewport = viewport.filter(|_| open);
+    }
+
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        ui_pass: &mut crate::ui_pass::RenderPass,
+        size: winit::dpi::PhysicalSize<u32>,
+        scale_factor: f64,
+    ) {
+        self.physical_viewport = self
+            .viewport
+            .map(|rect| crate::blit::to_physical(rect, scale_factor));
+        let viewport = match self.physical_viewport {
+            Some(viewport) if crate::blit::fits(viewport, size) => viewport,
+            _ => return,
+        };
+        if self.settings.hot_reload {
+            self.reload(device);
+        }
+
+        let settings = &self.settings;
+        let eye = glam::Vec3::new(
+            settings.pitch.cos() * settings.yaw.sin(),
+            settings.pitch.sin(),
+            settings.pitch.cos() * settings.yaw.cos(),
+        ) * settings.distance;
+        let light = glam::Vec3::new(
+            settings.light_pitch.cos() * settings.light_yaw.sin(),
+            settings.light_pitch.sin(),
+            settings.light_pitch.cos() * settings.light_yaw.cos(),
+        );
+        let aspect = viewport.width() / viewport.height();
+        let uniforms = Uniforms {
+            eye: eye.extend(aspect).into(),
+            target: [0.0, 0.0, 0.0, (std::f32::consts::FRAC_PI_4 * 0.5).tan()],
+            shape: [
+                settings.blend,
+                settings.smoothness,
+                settings.repetition,
+                settings.twist,
+            ],
+            light: light.extend(settings.shadow_softness).into(),
+            color: [
+                settings.color[0],
+                settings.color[1],
+                settings.color[2],
+                settings.ambient,
+            ],
+        };
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+
+        let target = self.canvas.begin(device, encoder, ui_pass, size);
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+        crate::blit::set_viewport(&mut render_pass, viewport);
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
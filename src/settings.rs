@@ -0,0 +1,409 @@
+#[derive(Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum Appearance {
+    System,
+    Dark,
+    Light,
+}
+
+#[derive(Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum TextureFilter {
+    Auto,
+    Linear,
+    Nearest,
+}
+
+#[derive(Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum ColorOutput {
+    Auto,
+    Srgb,
+    DisplayP3,
+    Gamma,
+}
+
+#[derive(Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    pub clear_color: [u8; 4],
+    pub appearance: Appearance,
+    pub vsync: bool,
+    pub ui_scale: f32,
+    pub ui_filter: TextureFilter,
+    pub pixel_snap: bool,
+    pub smooth_resize: bool,
+    pub color_output: ColorOutput,
+    pub output_gamma: f32,
+    pub window_size: [u32; 2],
+    pub telemetry: bool,
+    pub workspace: String,
+    pub setup_complete: bool,
+}
+
+pub fn default_workspace() -> String {
+    dirs::document_dir()
+        .or_else(dirs::home_dir)
+        .map(|dir| dir.join(env!("CARGO_PKG_NAME")).display().to_string())
+        .unwrap_or_default()
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            clear_color: [0, 0, 255, 255],
+            appearance: Appearance::System,
+            vsync: true,
+            ui_scale: 1.0,
+            ui_filter: TextureFilter::Linear,
+            pixel_snap: false,
+            smooth_resize: true,
+            color_output: ColorOutput::Auto,
+            output_gamma: 2.2,
+            window_size: [800, 600],
+            telemetry: false,
+            workspace: default_workspace(),
+            setup_complete: false,
+        }
+    }
+}
+
+impl AppSettings {
+    fn path() -> Option<std::path::PathBuf> {
+        dirs::config_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")).join("settings.json"))
+    }
+
+    pub fn load() -> Self {
+        let path = match Self::path() {
+            Some(path) => path,
+            None => return Self::default(),
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|err| {
+                log::warn!("ignoring invalid settings in {}: {}", path.display(), err);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn first_launch() -> bool {
+        Self::path().is_some_and(|path| !path.exists())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = match Self::path() {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        self.save_to(&path)?;
+        log::info!("saved settings to {}", path.display());
+        Ok(())
+    }
+
+    pub fn load_from(path: &std::path::Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+        serde_json::from_str(&contents).map_err(|err| err.to_string())
+    }
+
+    pub fn save_to(&self, path: &std::path::Path) -> Result<(), String> {
+        std::fs::write(path, serde_json::to_string_pretty(self).unwrap())
+            .map_err(|err| err.to_string())
+    }
+
+    pub fn present_mode(&self) -> wgpu::PresentMode {
+        if self.vsync {
+            wgpu::PresentMode::Fifo
+        } else {
+            wgpu::PresentMode::Immediate
+        }
+    }
+
+    pub fn ui_filter_mode(&self, pixels_per_point: f32) -> wgpu::FilterMode {
+        match self.ui_filter {
+            TextureFilter::Auto if (pixels_per_point - pixels_per_point.round()).abs() < 0.01 => {
+                wgpu::FilterMode::Nearest
+            }
+            TextureFilter::Auto | TextureFilter::Linear => wgpu::FilterMode::Linear,
+            TextureFilter::Nearest => wgpu::FilterMode::Nearest,
+        }
+    }
+
+    pub fn window_size(&self) -> winit::dpi::PhysicalSize<u32> {
+        let [width, height] = self.window_size;
+        winit::dpi::PhysicalSize::new(width.max(200), height.max(150))
+    }
+
+    pub fn system_theme(window: &winit::window::Window) -> Option<winit::window::Theme> {
+        #[cfg(target_os = "windows")]
+        return Some(winit::platform::windows::WindowExtWindows::theme(window));
+        #[cfg(not(target_os = "windows"))]
+        None
+    }
+
+    pub fn dark_mode(&self, system: winit::window::Theme) -> bool {
+        match self.appearance {
+            Appearance::System => system == winit::window::Theme::Dark,
+            Appearance::Dark => true,
+            Appearance::Light => false,
+        }
+    }
+
+    pub fn clear_color(&self) -> wgpu::Color {
+        let linear = |c: u8| {
+            let c = c as f64 / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        let [r, g, b, a] = self.clear_color;
+        wgpu::Color {
+            r: linear(r),
+            g: linear(g),
+            b: linear(b),
+            a: a as f64 / 255.0,
+        }
+    }
+}
+
+fn parse_color(hex: &str) -> [u8; 4] {
+    let value = u32::from_str_radix(hex.trim_start_matches('#'), 16).unwrap();
+    [(value >> 16) as u8, (value >> 8) as u8, value as u8, 255]
+}
+
+pub struct ImportPanel {
+    pub open: bool,
+    form: crate::form::Form,
+    status: String,
+}
+
+impl Default for ImportPanel {
+    fn default() -> Self {
+        Self {
+            open: false,
+            form: crate::form::Form::new(
+                "settings_import",
+                vec![
+                    crate::form::Field::new("File")
+                        .required()
+                        .pattern(r"(?i)\.json$", "a .json file"),
+                    crate::form::Field::new("UI scale")
+                        .range(crate::zoom::MIN_SCALE, crate::zoom::MAX_SCALE),
+                    crate::form::Field::new("Clear color")
+                        .pattern(r"^#?[0-9a-fA-F]{6}$", "a hex color like #0000ff"),
+                ],
+            ),
+            status: String::new(),
+        }
+    }
+}
+
+impl ImportPanel {
+    fn import(&self) -> Result<AppSettings, String> {
+        let mut settings = AppSettings::load_from(std::path::Path::new(self.form.value("File")))?;
+        if let Ok(scale) = self.form.value("UI scale").parse() {
+            settings.ui_scale = scale;
+        }
+        let color = self.form.value("Clear color");
+        if !color.is_empty() {
+            settings.clear_color = parse_color(color);
+        }
+        Ok(settings)
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, settings: &mut AppSettings) {
+        let mut submitted = false;
+        let form = &mut self.form;
+        let status = &self.status;
+        crate::dock::Panel::new("Import Settings")
+            .open(&mut self.open)
+            .show(ctx, |ui| {
+                ui.label("Import settings from a file, optionally overriding some values.");
+                submitted = form.show(ui, "Import");
+                if !status.is_empty() {
+                    ui.label(status.as_str());
+                }
+            });
+        if !submitted {
+            return;
+        }
+        let path = self.form.value("File").to_owned();
+        self.status = match self.import() {
+            Ok(imported) => {
+                *settings = imported;
+                self.form.reset();
+                log::info!("imported settings from {}", path);
+                crate::notify::success(format!("Imported settings from {}", path));
+                format!("Imported {}", path)
+            }
+            Err(err) => {
+                crate::notify::error(format!("Cannot import {}", path));
+                format!("Cannot import {}: {}", path, err)
+            }
+        };
+    }
+}
+
+#[derive(Default)]
+pub struct SettingsPanel {
+    pub open: bool,
+    pub save_as_requested: bool,
+    pub load_requested: bool,
+    pub shortcuts_requested: bool,
+    pub status: String,
+    pub import: ImportPanel,
+}
+
+impl SettingsPanel {
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, settings: &mut AppSettings) {
+        let save_as_requested = &mut self.save_as_requested;
+        let load_requested = &mut self.load_requested;
+        let shortcuts_requested = &mut self.shortcuts_requested;
+        let import_open = &mut self.import.open;
+        let status = &self.status;
+        crate::dock::Panel::new("Settings")
+            .open(&mut self.open)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Clear color");
+                    let [r, g, b, a] = settings.clear_color;
+                    let mut color = egui::Srgba::new(r, g, b, a);
+                    egui::color_picker::color_edit_button_srgba(ui, &mut color);
+                    settings.clear_color = color.to_array();
+                });
+                crate::focus::checkbox(ui, &mut settings.vsync, "Vertical sync");
+                ui.horizontal(|ui| {
+                    ui.label("Theme");
+                    ui.radio_value(
+                        &mut settings.appearance,
+                        Appearance::System,
+                        "Follow system",
+                    );
+                    ui.radio_value(&mut settings.appearance, Appearance::Dark, "Dark");
+                    ui.radio_value(&mut settings.appearance, Appearance::Light, "Light");
+                });
+                if !cfg!(target_os = "windows") && settings.appearance == Appearance::System {
+                    ui.label("Applies after the OS theme next changes, dark until then.");
+                }
+                ui.add(
+                    egui::Slider::f32(
+                        &mut settings.ui_scale,
+                        crate::zoom::MIN_SCALE..=crate::zoom::MAX_SCALE,
+                    )
+                    .text("UI scale"),
+                );
+                ui.label("Ctrl+scroll or pinch to zoom the UI.");
+                ui.horizontal(|ui| {
+                    ui.label("UI texture filtering");
+                    ui.radio_value(&mut settings.ui_filter, TextureFilter::Auto, "Auto");
+                    ui.radio_value(&mut settings.ui_filter, TextureFilter::Linear, "Linear");
+                    ui.radio_value(&mut settings.ui_filter, TextureFilter::Nearest, "Nearest");
+                });
+                ui.label("Auto uses nearest at whole-number scales and linear otherwise.");
+                crate::focus::checkbox(ui, &mut settings.pixel_snap, "Snap UI to the pixel grid");
+                crate::focus::checkbox(ui, &mut settings.smooth_resize, "Redraw while resizing");
+                ui.label(format!(
+                    "Window size: {} x {} (saved on exit)",
+                    settings.window_size[0], settings.window_size[1]
+                ));
+                ui.label(format!("Workspace: {}", settings.workspace));
+                crate::focus::checkbox(ui, &mut settings.telemetry, "Share anonymous usage data");
+                match AppSettings::path() {
+                    Some(path) => ui.label(format!("Stored in {}", path.display())),
+                    None => ui.label("No config directory, settings are not saved"),
+                };
+                ui.horizontal(|ui| {
+                    if crate::focus::button(ui, "Save settings as...").clicked {
+                        *save_as_requested = true;
+                    }
+                    if crate::focus::button(ui, "Load settings...").clicked {
+                        *load_requested = true;
+                    }
+                    if crate::focus::button(ui, "Import settings...").clicked {
+                        *import_open = true;
+                    }
+                    if crate::focus::button(ui, "Keyboard shortcuts...").clicked {
+                        *shortcuts_requested = true;
+                    }
+                    if crate::focus::button(ui, "Reset to defaults").clicked {
+                        *settings = AppSettings::default();
+                    }
+                });
+                if !status.is_empty() {
+                    ui.label(status.as_str());
+                }
+            });
+        self.import.show(ctx, settings);
+    }
+}
+
+fn set(engine: &mut crate::Engine, args: &[&str]) -> Result<String, String> {
+    let settings = &mut engine.settings;
+    match args {
+        [] => Ok(format!(
+            "vsync {}\nappearance {}\nui_scale {}\nui_filter {}\npixel_snap {}\nsmooth_resize {}",
+            if settings.vsync { "on" } else { "off" },
+            match settings.appearance {
+                Appearance::System => "system",
+                Appearance::Dark => "dark",
+                Appearance::Light => "light",
+            },
+            settings.ui_scale,
+            match settings.ui_filter {
+                TextureFilter::Auto => "auto",
+                TextureFilter::Linear => "linear",
+                TextureFilter::Nearest => "nearest",
+            },
+            if settings.pixel_snap { "on" } else { "off" },
+            if settings.smooth_resize { "on" } else { "off" }
+        )),
+        ["vsync", value] => {
+            settings.vsync = crate::console::parse_bool(value)?;
+            Ok(format!("vsync {}", value))
+        }
+        ["appearance", value] => {
+            settings.appearance = match *value {
+                "system" => Appearance::System,
+                "dark" => Appearance::Dark,
+                "light" => Appearance::Light,
+                _ => return Err(format!("unknown appearance {:?}", value)),
+            };
+            Ok(format!("appearance {}", value))
+        }
+        ["ui_scale", value] => {
+            let scale: f32 = value
+                .parse()
+                .map_err(|_| format!("expected a number, got {:?}", value))?;
+            settings.ui_scale = crate::zoom::clamp(scale);
+            Ok(format!("ui_scale {}", settings.ui_scale))
+        }
+        ["ui_filter", value] => {
+            settings.ui_filter = match *value {
+                "auto" => TextureFilter::Auto,
+                "linear" => TextureFilter::Linear,
+                "nearest" => TextureFilter::Nearest,
+                _ => return Err(format!("unknown filter {:?}", value)),
+            };
+            Ok(format!("ui_filter {}", value))
+        }
+        ["pixel_snap", value] => {
+            settings.pixel_snap = crate::console::parse_bool(value)?;
+            Ok(format!("pixel_snap {}", value))
+        }
+        ["smooth_resize", value] => {
+            settings.smooth_resize = crate::console::parse_bool(value)?;
+            Ok(format!("smooth_resize {}", value))
+        }
+        _ => Err(format!("cannot set {:?}", args.join(" "))),
+    }
+}
+
+pub fn register_commands(registry: &mut crate::console::Registry) {
+    registry.register(
+        "set",
+        "set [vsync on|off | appearance system|dark|light | ui_scale <scale> | ui_filter auto|linear|nearest | pixel_snap on|off | smooth_resize on|off]",
+        set,
+    );
+}
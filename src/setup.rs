@@ -0,0 +1,100 @@
+const THEME: usize = 0;
+const TELEMETRY: usize = 1;
+const WORKSPACE: usize = 2;
+
+fn check_workspace(workspace: &str) -> Result<(), String> {
+    let path = std::path::Path::new(workspace.trim());
+    if workspace.trim().is_empty() {
+        return Err("Choose a workspace folder".to_owned());
+    }
+    if path.is_file() {
+        return Err(format!("{} is a file, not a folder", path.display()));
+    }
+    match path.parent() {
+        Some(parent) if path.is_dir() || parent.is_dir() => Ok(()),
+        _ => Err(format!("The parent of {} does not exist", path.display())),
+    }
+}
+
+pub struct FirstRunSetup {
+    wizard: crate::wizard::Wizard,
+    appearance: crate::settings::Appearance,
+    telemetry: bool,
+    workspace: String,
+}
+
+impl FirstRunSetup {
+    pub fn new(settings: &crate::settings::AppSettings) -> Self {
+        Self {
+            wizard: crate::wizard::Wizard::new(
+                "first_run_setup",
+                "Welcome! Let's set things up",
+                vec!["Theme", "Telemetry", "Workspace", "Summary"],
+            ),
+            appearance: settings.appearance,
+            telemetry: settings.telemetry,
+            workspace: settings.workspace.clone(),
+        }
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &std::sync::Arc<egui::Context>,
+        screen: egui::Vec2,
+        settings: &mut crate::settings::AppSettings,
+    ) -> Option<crate::wizard::Outcome> {
+        let appearance = &mut self.appearance;
+        let telemetry = &mut self.telemetry;
+        let workspace = &mut self.workspace;
+        let outcome = self.wizard.show(ctx, screen, |ui, step| match step {
+            THEME => {
+                ui.label("Pick how the interface should look.");
+                ui.radio_value(
+                    appearance,
+                    crate::settings::Appearance::System,
+                    "Follow system",
+                );
+                ui.radio_value(appearance, crate::settings::Appearance::Dark, "Dark");
+                ui.radio_value(appearance, crate::settings::Appearance::Light, "Light");
+                Ok(())
+            }
+            TELEMETRY => {
+                ui.label("Anonymous usage data helps decide which demos to improve.");
+                ui.label("Nothing is sent unless you opt in.");
+                ui.checkbox(telemetry, "Share anonymous usage data");
+                Ok(())
+            }
+            WORKSPACE => {
+                ui.label("Exports and screenshots go to the workspace folder.");
+                ui.add(egui::TextEdit::new(workspace));
+                if ui.button("Use the default").clicked {
+                    *workspace = crate::settings::default_workspace();
+                }
+                check_workspace(workspace)
+            }
+            _ => {
+                ui.label(format!(
+                    "Theme: {}",
+                    match appearance {
+                        crate::settings::Appearance::System => "follow system",
+                        crate::settings::Appearance::Dark => "dark",
+                        crate::settings::Appearance::Light => "light",
+                    }
+                ));
+                ui.label(format!(
+                    "Telemetry: {}",
+                    if *telemetry { "on" } else { "off" }
+                ));
+                ui.label(format!("Workspace: {}", workspace.trim()));
+                ui.label("You can change these later in Settings.");
+                Ok(())
+            }
+        });
+        if let Some(crate::wizard::Outcome::Finished) = outcome {
+            settings.appearance = self.appearance;
+            settings.telemetry = self.telemetry;
+            settings.workspace = self.workspace.trim().to_owned();
+        }
+        outcome
+    }
+}
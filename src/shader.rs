@@ -0,0 +1,46 @@
+pub fn compile(
+    device: &wgpu::Device,
+    name: &str,
+    source: &str,
+    kind: shaderc::ShaderKind,
+) -> Result<wgpu::ShaderModule, String> {
+    let mut compiler = shaderc::Compiler::new().unwrap();
+    let mut options = shaderc::CompileOptions::new().unwrap();
+    options.set_include_callback(|requested, _, _, _| match builtin_include(requested) {
+        Some(content) => Ok(shaderc::ResolvedInclude {
+            resolved_name: requested.to_owned(),
+            content: content.to_owned(),
+        }),
+        None => Err(format!("unknown include {}", requested)),
+    });
+    let artifact = compiler
+        .compile_into_spirv(source, kind, name, "main", Some(&options))
+        .map_err(|err| err.to_string())?;
+    if artifact.get_num_warnings() > 0 {
+        log::warn!("{}: {}", name, artifact.get_warning_messages());
+    }
+    Ok(
+        device.create_shader_module(wgpu::ShaderModuleSource::SpirV(std::borrow::Cow::Borrowed(
+            artifact.as_binary(),
+        ))),
+    )
+}
+
+pub fn compile_builtin(
+    device: &wgpu::Device,
+    name: &str,
+    source: &str,
+    kind: shaderc::ShaderKind,
+) -> wgpu::ShaderModule {
+    match compile(device, name, source, kind) {
+        Ok(module) => module,
+        Err(err) => panic!("failed to compile {}: {}", name, err),
+    }
+}
+
+fn builtin_include(name: &str) -> Option<&'static str> {
+    match name {
+        "lighting.glsl" => Some(include_str!("shaders/lighting.glsl")),
+        _ => None,
+    }
+}
@@ -0,0 +1,17 @@
+#version 450
+
+layout(location = 0) in vec2 v_tex_coord;
+
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D t_source;
+layout(set = 0, binding = 1) uniform sampler s_source;
+
+void main() {
+    vec4 value = vec4(v_tex_coord, gl_FragCoord.xy * 0.001);
+    for (int i = 0; i < 256; i++) {
+        value = fract(value * 1.0001 + vec4(0.3, 0.7, 0.1, 0.9));
+        value = value * value * 0.5 + value.yzwx * 0.5;
+    }
+    f_color = value;
+}
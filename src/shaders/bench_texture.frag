@@ -0,0 +1,19 @@
+#version 450
+
+layout(location = 0) in vec2 v_tex_coord;
+
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D t_source;
+layout(set = 0, binding = 1) uniform sampler s_source;
+
+void main() {
+    vec4 sum = vec4(0.0);
+    vec2 coord = v_tex_coord;
+    for (int i = 0; i < 16; i++) {
+        vec4 texel = texture(sampler2D(t_source, s_source), coord);
+        sum += texel;
+        coord = fract(coord + texel.xy * 0.37 + vec2(0.013, 0.029));
+    }
+    f_color = sum / 16.0;
+}
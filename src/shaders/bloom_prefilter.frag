@@ -0,0 +1,23 @@
+#version 450
+
+layout(location = 0) in vec2 v_tex_coord;
+
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D t_source;
+layout(set = 0, binding = 1) uniform sampler s_source;
+layout(set = 0, binding = 2) uniform Bloom {
+    float u_threshold;
+    float u_knee;
+    float u_intensity;
+    float u_padding;
+};
+
+void main() {
+    vec3 color = texture(sampler2D(t_source, s_source), v_tex_coord).rgb;
+    float brightness = max(color.r, max(color.g, color.b));
+    float soft = clamp(brightness - u_threshold + u_knee, 0.0, 2.0 * u_knee);
+    soft = soft * soft / (4.0 * u_knee + 1e-4);
+    float contribution = max(soft, brightness - u_threshold) / max(brightness, 1e-4);
+    f_color = vec4(color * contribution, 1.0);
+}
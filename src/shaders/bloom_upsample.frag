@@ -0,0 +1,24 @@
+#version 450
+
+layout(location = 0) in vec2 v_tex_coord;
+
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D t_source;
+layout(set = 0, binding = 1) uniform sampler s_source;
+layout(set = 0, binding = 2) uniform Bloom {
+    float u_threshold;
+    float u_knee;
+    float u_intensity;
+    float u_padding;
+};
+
+void main() {
+    vec2 texel = 1.0 / vec2(textureSize(sampler2D(t_source, s_source), 0));
+    vec4 offset = texel.xyxy * vec4(-1.0, -1.0, 1.0, 1.0) * 0.5;
+    vec3 color = texture(sampler2D(t_source, s_source), v_tex_coord + offset.xy).rgb
+        + texture(sampler2D(t_source, s_source), v_tex_coord + offset.zy).rgb
+        + texture(sampler2D(t_source, s_source), v_tex_coord + offset.xw).rgb
+        + texture(sampler2D(t_source, s_source), v_tex_coord + offset.zw).rgb;
+    f_color = vec4(color * 0.25, 1.0);
+}
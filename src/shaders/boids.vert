@@ -0,0 +1,17 @@
+#version 450
+
+layout(location = 0) in vec2 a_position;
+layout(location = 1) in vec2 a_velocity;
+layout(location = 2) in vec2 a_shape;
+
+layout(location = 0) out vec3 v_color;
+
+void main() {
+    vec2 forward = length(a_velocity) > 0.0 ? normalize(a_velocity) : vec2(1.0, 0.0);
+    vec2 side = vec2(-forward.y, forward.x);
+    vec2 position = a_position + forward * a_shape.x + side * a_shape.y;
+
+    float angle = atan(forward.y, forward.x);
+    v_color = 0.55 + 0.45 * cos(angle + vec3(0.0, 2.094, 4.188));
+    gl_Position = vec4(position, 0.0, 1.0);
+}
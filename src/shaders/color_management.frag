@@ -0,0 +1,41 @@
+#version 450
+
+layout(location = 0) in vec2 v_tex_coord;
+
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D t_source;
+layout(set = 0, binding = 1) uniform sampler s_source;
+layout(set = 0, binding = 2) uniform Uniforms {
+    uint u_mode;
+    float u_gamma;
+};
+
+const uint MODE_DISPLAY_P3 = 1;
+const uint MODE_GAMMA = 2;
+
+// Linear sRGB to linear Display P3, both with a D65 white point.
+const mat3 SRGB_TO_DISPLAY_P3 = mat3(
+    0.8225, 0.0332, 0.0171,
+    0.1774, 0.9669, 0.0724,
+    0.0000, 0.0000, 0.9108
+);
+
+vec3 linear_from_srgb(vec3 srgb) {
+    bvec3 cutoff = lessThan(srgb, vec3(0.04045));
+    vec3 lower = srgb / vec3(12.92);
+    vec3 higher = pow((srgb + vec3(0.055)) / vec3(1.055), vec3(2.4));
+    return mix(higher, lower, cutoff);
+}
+
+void main() {
+    vec4 color = texture(sampler2D(t_source, s_source), v_tex_coord);
+    vec3 rgb = color.rgb;
+    if (u_mode == MODE_DISPLAY_P3) {
+        rgb = SRGB_TO_DISPLAY_P3 * rgb;
+    } else if (u_mode == MODE_GAMMA) {
+        // The swap chain applies the sRGB curve, so undo it after the power law.
+        rgb = linear_from_srgb(pow(max(rgb, vec3(0.0)), vec3(1.0 / u_gamma)));
+    }
+    f_color = vec4(rgb, color.a);
+}
@@ -0,0 +1,19 @@
+#version 450
+
+layout(location = 0) in vec3 a_position;
+layout(location = 1) in vec4 a_color;
+
+layout(location = 0) out vec4 v_color;
+
+layout(set = 0, binding = 0) uniform Globals {
+    mat4 u_view_proj;
+    vec4 u_camera_position;
+    vec4 u_light_direction;
+    vec4 u_light_color;
+    vec4 u_ambient;
+};
+
+void main() {
+    v_color = a_color;
+    gl_Position = u_view_proj * vec4(a_position, 1.0);
+}
@@ -0,0 +1,54 @@
+#version 450
+
+#define KIND_JULIA 1u
+
+layout(location = 0) in vec2 v_tex_coord;
+
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform Fractal {
+    vec2 u_center;
+    vec2 u_extent;
+    vec2 u_julia;
+    uint u_iterations;
+    uint u_palette;
+    uint u_kind;
+};
+
+vec3 palette(float t) {
+    vec3 a = vec3(0.5);
+    vec3 b = vec3(0.5);
+    vec3 c = vec3(1.0);
+    vec3 d = vec3(0.0, 0.33, 0.67);
+    if (u_palette == 1u) {
+        c = vec3(1.0, 1.0, 0.5);
+        d = vec3(0.0, 0.1, 0.2);
+    } else if (u_palette == 2u) {
+        a = vec3(0.2, 0.4, 0.6);
+        b = vec3(0.2, 0.3, 0.4);
+        d = vec3(0.6, 0.5, 0.4);
+    } else if (u_palette == 3u) {
+        return vec3(0.5 + 0.5 * cos(6.28318 * t));
+    }
+    return a + b * cos(6.28318 * (c * t + d));
+}
+
+void main() {
+    vec2 point = u_center + (v_tex_coord - 0.5) * vec2(1.0, -1.0) * u_extent;
+    vec2 z = u_kind == KIND_JULIA ? point : vec2(0.0);
+    vec2 c = u_kind == KIND_JULIA ? u_julia : point;
+
+    uint iteration = 0u;
+    for (; iteration < u_iterations; iteration++) {
+        z = vec2(z.x * z.x - z.y * z.y, 2.0 * z.x * z.y) + c;
+        if (dot(z, z) > 256.0) {
+            break;
+        }
+    }
+    if (iteration == u_iterations) {
+        f_color = vec4(0.0, 0.0, 0.0, 1.0);
+        return;
+    }
+    float smoothed = float(iteration) + 1.0 - log2(log2(dot(z, z)) * 0.5);
+    f_color = vec4(palette(smoothed / 64.0), 1.0);
+}
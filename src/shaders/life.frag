@@ -0,0 +1,15 @@
+#version 450
+
+layout(location = 0) in vec2 v_tex_coord;
+
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform utexture2D t_cells;
+layout(set = 0, binding = 1) uniform sampler s_cells;
+
+void main() {
+    ivec2 size = textureSize(usampler2D(t_cells, s_cells), 0);
+    ivec2 cell = min(ivec2(v_tex_coord * vec2(size)), size - 1);
+    uint alive = texelFetch(usampler2D(t_cells, s_cells), cell, 0).r;
+    f_color = alive == 1u ? vec4(0.55, 0.9, 0.45, 1.0) : vec4(0.04, 0.05, 0.06, 1.0);
+}
@@ -0,0 +1,99 @@
+#define CLUSTER_X 16
+#define CLUSTER_Y 9
+#define CLUSTER_Z 24
+#define MAX_LIGHTS_PER_CLUSTER 64u
+
+layout(set = 0, binding = 0) uniform Globals {
+    mat4 u_view_proj;
+    vec4 u_camera_position;
+    vec4 u_light_direction;
+    vec4 u_light_color;
+    vec4 u_ambient;
+    mat4 u_view;
+    vec4 u_cluster_screen;
+    uvec4 u_cluster_options;
+    vec4 u_probe;
+};
+
+struct LocalLight {
+    vec4 position_range;
+    vec4 color_kind;
+    vec4 direction_cos_outer;
+    vec4 cos_inner;
+};
+
+layout(set = 0, binding = 2) readonly buffer Lights {
+    LocalLight lights[];
+};
+
+layout(set = 0, binding = 3) readonly buffer ClusterCounts {
+    uint cluster_counts[];
+};
+
+layout(set = 0, binding = 4) readonly buffer ClusterIndices {
+    uint cluster_indices[];
+};
+
+layout(set = 0, binding = 5) uniform textureCube t_probe;
+layout(set = 0, binding = 6) uniform sampler s_probe;
+
+uint cluster_index(vec3 world_position) {
+    vec2 tile = floor(gl_FragCoord.xy / u_cluster_screen.xy * vec2(CLUSTER_X, CLUSTER_Y));
+    float view_z = -(u_view * vec4(world_position, 1.0)).z;
+    float near = u_cluster_screen.z;
+    float far = u_cluster_screen.w;
+    float slice = floor(log(max(view_z, near) / near) / log(far / near) * float(CLUSTER_Z));
+    uvec3 cluster = uvec3(
+        clamp(tile, vec2(0.0), vec2(CLUSTER_X - 1, CLUSTER_Y - 1)),
+        clamp(slice, 0.0, float(CLUSTER_Z - 1))
+    );
+    return cluster.x + cluster.y * CLUSTER_X + cluster.z * CLUSTER_X * CLUSTER_Y;
+}
+
+vec3 heatmap(uint count) {
+    float t = clamp(float(count) / 16.0, 0.0, 1.0);
+    return vec3(t, 1.0 - abs(t * 2.0 - 1.0), 1.0 - t);
+}
+
+vec3 shade(vec3 world_position, vec3 normal, vec3 albedo, vec2 material) {
+    float diffuse = max(dot(normal, -u_light_direction.xyz), 0.0);
+    vec3 light = u_ambient.rgb + u_light_color.rgb * diffuse;
+
+    uint cluster = cluster_index(world_position);
+    uint count = u_cluster_options.z != 0u
+        ? 0u
+        : min(cluster_counts[cluster], MAX_LIGHTS_PER_CLUSTER);
+    for (uint i = 0u; i < count; i++) {
+        LocalLight local = lights[cluster_indices[cluster * MAX_LIGHTS_PER_CLUSTER + i]];
+        vec3 to_light = local.position_range.xyz - world_position;
+        float distance = length(to_light);
+        vec3 direction = to_light / max(distance, 1e-4);
+        float falloff = clamp(1.0 - pow(distance / local.position_range.w, 4.0), 0.0, 1.0);
+        float attenuation = falloff * falloff / (distance * distance + 1.0);
+        if (local.color_kind.w > 0.5) {
+            float cos_angle = dot(-direction, local.direction_cos_outer.xyz);
+            attenuation *= smoothstep(local.direction_cos_outer.w, local.cos_inner.x, cos_angle);
+        }
+        light += local.color_kind.rgb * max(dot(normal, direction), 0.0) * attenuation;
+    }
+
+    if (u_cluster_options.y != 0u) {
+        return heatmap(count);
+    }
+
+    float metallic = material.x;
+    float roughness = material.y;
+    vec3 view_direction = normalize(u_camera_position.xyz - world_position);
+    float n_dot_v = max(dot(normal, view_direction), 0.0);
+    vec3 f0 = mix(vec3(0.04), albedo, metallic);
+    vec3 fresnel = f0 + (max(vec3(1.0 - roughness), f0) - f0) * pow(1.0 - n_dot_v, 5.0);
+    vec3 color = albedo * (1.0 - metallic) * light;
+    if (u_probe.x > 0.0) {
+        vec3 direction = reflect(-view_direction, normal);
+        vec3 reflection = textureLod(samplerCube(t_probe, s_probe), direction, roughness * u_probe.y).rgb;
+        color += reflection * fresnel * u_probe.x;
+    } else {
+        color += u_ambient.rgb * fresnel;
+    }
+    return color;
+}
@@ -0,0 +1,35 @@
+#version 450
+
+layout(location = 0) in vec3 v_normal;
+layout(location = 1) in vec2 v_uv;
+layout(location = 2) in vec3 v_view;
+
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform Material {
+    mat4 u_view_proj;
+    mat4 u_model;
+    vec4 u_eye;
+    vec4 u_light;
+    vec4 u_params;
+};
+
+layout(set = 0, binding = 1) uniform texture2D t_texture;
+layout(set = 0, binding = 2) uniform sampler s_texture;
+
+void main() {
+    vec3 normal = normalize(v_normal);
+    // NODES
+    vec3 albedo = (ALBEDO).rgb;
+    float roughness = clamp((ROUGHNESS).x, 0.04, 1.0);
+    vec3 emission = (EMISSION).rgb;
+
+    vec3 light = normalize(u_light.xyz);
+    vec3 view = normalize(v_view);
+    vec3 half_vector = normalize(light + view);
+    float diffuse = max(dot(normal, light), 0.0);
+    float shininess = 2.0 / (roughness * roughness);
+    float specular = pow(max(dot(normal, half_vector), 0.0), shininess) * (1.0 - roughness);
+    vec3 color = albedo * (diffuse + 0.15) + vec3(specular) * diffuse + emission;
+    f_color = vec4(color, 1.0);
+}
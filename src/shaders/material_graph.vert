@@ -0,0 +1,26 @@
+#version 450
+
+#define PI 3.14159265
+
+layout(location = 0) in vec3 a_position;
+layout(location = 1) in vec3 a_normal;
+
+layout(location = 0) out vec3 v_normal;
+layout(location = 1) out vec2 v_uv;
+layout(location = 2) out vec3 v_view;
+
+layout(set = 0, binding = 0) uniform Material {
+    mat4 u_view_proj;
+    mat4 u_model;
+    vec4 u_eye;
+    vec4 u_light;
+    vec4 u_params;
+};
+
+void main() {
+    vec4 world = u_model * vec4(a_position, 1.0);
+    v_normal = mat3(u_model) * a_normal;
+    v_uv = vec2(atan(a_normal.z, a_normal.x) / (2.0 * PI) + 0.5, acos(clamp(a_normal.y, -1.0, 1.0)) / PI);
+    v_view = u_eye.xyz - world.xyz;
+    gl_Position = u_view_proj * world;
+}
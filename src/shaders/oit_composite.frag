@@ -0,0 +1,16 @@
+#version 450
+
+layout(location = 0) in vec2 v_tex_coord;
+
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D t_accum;
+layout(set = 0, binding = 1) uniform texture2D t_revealage;
+layout(set = 0, binding = 2) uniform sampler s_nearest;
+
+void main() {
+    ivec2 coord = ivec2(gl_FragCoord.xy);
+    vec4 accum = texelFetch(sampler2D(t_accum, s_nearest), coord, 0);
+    float revealage = texelFetch(sampler2D(t_revealage, s_nearest), coord, 0).r;
+    f_color = vec4(accum.rgb / max(accum.a, 1e-5), revealage);
+}
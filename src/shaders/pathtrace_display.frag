@@ -0,0 +1,24 @@
+#version 450
+
+layout(location = 0) in vec2 v_tex_coord;
+
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D t_accumulation;
+layout(set = 0, binding = 1) uniform sampler s_accumulation;
+
+vec3 aces(vec3 x) {
+    const float a = 2.51;
+    const float b = 0.03;
+    const float c = 2.43;
+    const float d = 0.59;
+    const float e = 0.14;
+    return clamp((x * (a * x + b)) / (x * (c * x + d) + e), 0.0, 1.0);
+}
+
+void main() {
+    ivec2 size = textureSize(sampler2D(t_accumulation, s_accumulation), 0);
+    ivec2 pixel = min(ivec2(v_tex_coord * vec2(size)), size - 1);
+    vec3 color = texelFetch(sampler2D(t_accumulation, s_accumulation), pixel, 0).rgb;
+    f_color = vec4(aces(color), 1.0);
+}
@@ -0,0 +1,9 @@
+#version 450
+
+layout(location = 0) flat in uint v_id;
+
+layout(location = 0) out uint f_id;
+
+void main() {
+    f_id = v_id + 1u;
+}
@@ -0,0 +1,27 @@
+#version 450
+
+layout(location = 0) in vec3 a_position;
+layout(location = 1) in vec3 a_normal;
+
+layout(location = 0) flat out uint v_id;
+
+struct ObjectData {
+    mat4 model;
+    vec4 color;
+    vec4 material;
+    uvec4 id;
+};
+
+layout(set = 0, binding = 1) readonly buffer Objects {
+    ObjectData objects[];
+};
+
+layout(set = 1, binding = 0) uniform Pick {
+    mat4 u_pick_view_proj;
+};
+
+void main() {
+    ObjectData object = objects[gl_InstanceIndex];
+    v_id = object.id.x;
+    gl_Position = u_pick_view_proj * object.model * vec4(a_position, 1.0);
+}
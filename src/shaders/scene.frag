@@ -0,0 +1,15 @@
+#version 450
+
+#include "lighting.glsl"
+
+layout(location = 0) in vec3 v_world_position;
+layout(location = 1) in vec3 v_normal;
+layout(location = 2) in vec4 v_color;
+layout(location = 3) in vec2 v_material;
+
+layout(location = 0) out vec4 f_color;
+
+void main() {
+    vec3 color = shade(v_world_position, normalize(v_normal), v_color.rgb, v_material);
+    f_color = vec4(color, v_color.a);
+}
@@ -0,0 +1,38 @@
+#version 450
+
+layout(location = 0) in vec3 a_position;
+layout(location = 1) in vec3 a_normal;
+
+layout(location = 0) out vec3 v_world_position;
+layout(location = 1) out vec3 v_normal;
+layout(location = 2) out vec4 v_color;
+layout(location = 3) out vec2 v_material;
+
+layout(set = 0, binding = 0) uniform Globals {
+    mat4 u_view_proj;
+    vec4 u_camera_position;
+    vec4 u_light_direction;
+    vec4 u_light_color;
+    vec4 u_ambient;
+};
+
+struct ObjectData {
+    mat4 model;
+    vec4 color;
+    vec4 material;
+    uvec4 id;
+};
+
+layout(set = 0, binding = 1) readonly buffer Objects {
+    ObjectData objects[];
+};
+
+void main() {
+    ObjectData object = objects[gl_InstanceIndex];
+    vec4 world_position = object.model * vec4(a_position, 1.0);
+    v_world_position = world_position.xyz;
+    v_normal = mat3(object.model) * a_normal;
+    v_color = object.color;
+    v_material = object.material.xy;
+    gl_Position = u_view_proj * world_position;
+}
@@ -0,0 +1,24 @@
+#version 450
+
+#include "lighting.glsl"
+
+layout(location = 0) in vec3 v_world_position;
+layout(location = 1) in vec3 v_normal;
+layout(location = 2) in vec4 v_color;
+layout(location = 3) in vec2 v_material;
+
+layout(location = 0) out vec4 f_accum;
+layout(location = 1) out float f_revealage;
+
+void main() {
+    vec3 color = shade(v_world_position, normalize(v_normal), v_color.rgb, v_material);
+    float alpha = v_color.a;
+
+    float weight = clamp(
+        pow(min(1.0, alpha * 10.0) + 0.01, 3.0) * 1e8 * pow(1.0 - gl_FragCoord.z * 0.9, 3.0),
+        1e-2,
+        3e3
+    );
+    f_accum = vec4(color * alpha, alpha) * weight;
+    f_revealage = alpha;
+}
@@ -0,0 +1,145 @@
+#version 450
+
+#define MAX_STEPS 128
+#define MAX_DISTANCE 100.0
+#define SURFACE_DISTANCE 1e-3
+
+layout(location = 0) in vec2 v_tex_coord;
+
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform Sdf {
+    vec4 u_eye;
+    vec4 u_target;
+    vec4 u_shape;
+    vec4 u_light;
+    vec4 u_color;
+};
+
+float smooth_min(float a, float b, float k) {
+    if (k <= 0.0) {
+        return min(a, b);
+    }
+    float h = clamp(0.5 + 0.5 * (b - a) / k, 0.0, 1.0);
+    return mix(b, a, h) - k * h * (1.0 - h);
+}
+
+float sphere(vec3 p, float radius) {
+    return length(p) - radius;
+}
+
+float round_box(vec3 p, vec3 extent, float radius) {
+    vec3 q = abs(p) - extent;
+    return length(max(q, 0.0)) + min(max(q.x, max(q.y, q.z)), 0.0) - radius;
+}
+
+float torus(vec3 p, vec2 radii) {
+    vec2 q = vec2(length(p.xz) - radii.x, p.y);
+    return length(q) - radii.y;
+}
+
+vec3 repeat(vec3 p, float spacing) {
+    if (spacing <= 0.0) {
+        return p;
+    }
+    vec2 cell = p.xz - spacing * round(p.xz / spacing);
+    return vec3(cell.x, p.y, cell.y);
+}
+
+vec3 twist(vec3 p, float amount) {
+    float angle = p.y * amount;
+    float c = cos(angle);
+    float s = sin(angle);
+    return vec3(c * p.x - s * p.z, p.y, s * p.x + c * p.z);
+}
+
+float shape(vec3 p) {
+    vec3 q = twist(repeat(p, u_shape.z), u_shape.w);
+    float morph = mix(sphere(q, 1.0), round_box(q, vec3(0.75), 0.1), u_shape.x);
+    float ring = torus(q - vec3(0.0, -0.2, 0.0), vec2(1.2, 0.25));
+    return smooth_min(morph, ring, u_shape.y);
+}
+
+float map(vec3 p) {
+    float ground = p.y + 1.0;
+    return min(shape(p), ground);
+}
+
+vec3 normal_at(vec3 p) {
+    vec2 e = vec2(1e-3, 0.0);
+    return normalize(vec3(
+        map(p + e.xyy) - map(p - e.xyy),
+        map(p + e.yxy) - map(p - e.yxy),
+        map(p + e.yyx) - map(p - e.yyx)
+    ));
+}
+
+float soft_shadow(vec3 origin, vec3 direction, float softness) {
+    float result = 1.0;
+    float t = 0.02;
+    for (int i = 0; i < 64 && t < 20.0; i++) {
+        float d = map(origin + direction * t);
+        if (d < SURFACE_DISTANCE) {
+            return 0.0;
+        }
+        result = min(result, softness * d / t);
+        t += clamp(d, 0.01, 0.5);
+    }
+    return clamp(result, 0.0, 1.0);
+}
+
+float ambient_occlusion(vec3 p, vec3 n) {
+    float occlusion = 0.0;
+    float weight = 1.0;
+    for (int i = 1; i <= 5; i++) {
+        float h = 0.05 * float(i);
+        occlusion += (h - map(p + n * h)) * weight;
+        weight *= 0.7;
+    }
+    return clamp(1.0 - 3.0 * occlusion, 0.0, 1.0);
+}
+
+void main() {
+    vec3 eye = u_eye.xyz;
+    vec3 forward = normalize(u_target.xyz - eye);
+    vec3 right = normalize(cross(forward, vec3(0.0, 1.0, 0.0)));
+    vec3 up = cross(right, forward);
+    vec2 ndc = vec2(v_tex_coord.x * 2.0 - 1.0, 1.0 - v_tex_coord.y * 2.0);
+    vec3 direction = normalize(forward + (ndc.x * u_eye.w * right + ndc.y * up) * u_target.w);
+
+    vec3 sky = mix(vec3(0.55, 0.65, 0.8), vec3(0.2, 0.3, 0.5), clamp(direction.y, 0.0, 1.0));
+    float t = 0.0;
+    bool hit = false;
+    for (int i = 0; i < MAX_STEPS; i++) {
+        float d = map(eye + direction * t);
+        if (d < SURFACE_DISTANCE * t) {
+            hit = true;
+            break;
+        }
+        t += d;
+        if (t > MAX_DISTANCE) {
+            break;
+        }
+    }
+    if (!hit) {
+        f_color = vec4(sky, 1.0);
+        return;
+    }
+
+    vec3 p = eye + direction * t;
+    vec3 n = normal_at(p);
+    vec3 to_light = normalize(u_light.xyz);
+    bool ground = p.y < -1.0 + 2.0 * SURFACE_DISTANCE && shape(p) > 2.0 * SURFACE_DISTANCE;
+    vec3 albedo = u_color.rgb;
+    if (ground) {
+        vec2 checker = floor(p.xz);
+        albedo = mix(vec3(0.35), vec3(0.6), mod(checker.x + checker.y, 2.0));
+    }
+    float diffuse = max(dot(n, to_light), 0.0) * soft_shadow(p + n * 2e-3, to_light, u_light.w);
+    float specular = pow(max(dot(reflect(-to_light, n), -direction), 0.0), 32.0) * diffuse;
+    float occlusion = ambient_occlusion(p, n);
+
+    vec3 color = albedo * (diffuse + u_color.w * occlusion) + vec3(specular * 0.3);
+    color = mix(color, sky, 1.0 - exp(-0.002 * t * t));
+    f_color = vec4(pow(color, vec3(2.2)), 1.0);
+}
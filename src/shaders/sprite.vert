@@ -0,0 +1,32 @@
+#version 450
+
+layout(location = 0) in vec2 a_position;
+layout(location = 1) in vec2 a_size;
+layout(location = 2) in vec4 a_uv;
+layout(location = 3) in vec4 a_color;
+layout(location = 4) in float a_rotation;
+
+layout(location = 0) out vec2 v_uv;
+layout(location = 1) out vec4 v_color;
+
+layout(set = 0, binding = 0) uniform Sprites {
+    vec4 u_screen;
+};
+
+const vec2 CORNERS[6] = vec2[6](
+    vec2(0.0, 0.0), vec2(1.0, 0.0), vec2(1.0, 1.0),
+    vec2(0.0, 0.0), vec2(1.0, 1.0), vec2(0.0, 1.0)
+);
+
+void main() {
+    vec2 corner = CORNERS[gl_VertexIndex];
+    vec2 local = (corner - 0.5) * a_size;
+    float c = cos(a_rotation);
+    float s = sin(a_rotation);
+    vec2 position = a_position + vec2(c * local.x - s * local.y, s * local.x + c * local.y);
+    vec2 ndc = position / u_screen.xy * 2.0 - 1.0;
+
+    v_uv = mix(a_uv.xy, a_uv.zw, corner);
+    v_color = a_color;
+    gl_Position = vec4(ndc.x, -ndc.y, 0.0, 1.0);
+}
@@ -0,0 +1,68 @@
+#version 450
+
+layout(location = 0) in vec2 v_tex_coord;
+
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D t_depth;
+layout(set = 0, binding = 1) uniform sampler s_depth;
+layout(set = 0, binding = 2) uniform Ssao {
+    mat4 u_projection;
+    mat4 u_inverse_projection;
+    float u_radius;
+    float u_intensity;
+    float u_bias;
+    float u_padding;
+};
+
+const int SAMPLES = 16;
+
+vec3 view_position(vec2 uv) {
+    float depth = texture(sampler2D(t_depth, s_depth), uv).r;
+    vec4 ndc = vec4(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, depth, 1.0);
+    vec4 position = u_inverse_projection * ndc;
+    return position.xyz / position.w;
+}
+
+float hash(vec2 p) {
+    return fract(sin(dot(p, vec2(12.9898, 78.233))) * 43758.5453);
+}
+
+void main() {
+    float depth = texture(sampler2D(t_depth, s_depth), v_tex_coord).r;
+    if (depth >= 1.0) {
+        f_color = vec4(1.0);
+        return;
+    }
+
+    vec3 position = view_position(v_tex_coord);
+    vec3 normal = normalize(cross(dFdx(position), dFdy(position)));
+    if (dot(normal, position) > 0.0) {
+        normal = -normal;
+    }
+
+    float angle = hash(gl_FragCoord.xy) * 6.2831853;
+    vec3 random = vec3(cos(angle), sin(angle), 0.0);
+    vec3 tangent = normalize(random - normal * dot(random, normal));
+    vec3 bitangent = cross(normal, tangent);
+    mat3 tbn = mat3(tangent, bitangent, normal);
+
+    float occlusion = 0.0;
+    for (int i = 0; i < SAMPLES; i++) {
+        float a = hash(vec2(i, 0.5)) * 6.2831853;
+        float z = hash(vec2(i, 1.5));
+        float r = sqrt(1.0 - z * z);
+        float scale = float(i + 1) / float(SAMPLES);
+        vec3 offset = vec3(cos(a) * r, sin(a) * r, z) * mix(0.1, 1.0, scale * scale);
+        vec3 sample_position = position + tbn * offset * u_radius;
+
+        vec4 clip = u_projection * vec4(sample_position, 1.0);
+        vec2 uv = vec2(clip.x, -clip.y) / clip.w * 0.5 + 0.5;
+        float scene_z = view_position(uv).z;
+        float range = smoothstep(0.0, 1.0, u_radius / abs(position.z - scene_z));
+        occlusion += (scene_z >= sample_position.z + u_bias ? 1.0 : 0.0) * range;
+    }
+
+    float ao = 1.0 - occlusion / float(SAMPLES);
+    f_color = vec4(vec3(pow(ao, u_intensity)), 1.0);
+}
@@ -0,0 +1,13 @@
+#version 450
+
+layout(location = 0) in vec2 v_tex_coord;
+
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D t_ao;
+layout(set = 0, binding = 1) uniform sampler s_ao;
+
+void main() {
+    float ao = texture(sampler2D(t_ao, s_ao), v_tex_coord).r;
+    f_color = vec4(vec3(ao), 1.0);
+}
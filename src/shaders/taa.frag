@@ -0,0 +1,53 @@
+#version 450
+
+layout(location = 0) in vec2 v_tex_coord;
+
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D t_color;
+layout(set = 0, binding = 1) uniform texture2D t_history;
+layout(set = 0, binding = 2) uniform texture2D t_depth;
+layout(set = 0, binding = 3) uniform sampler s_linear;
+layout(set = 0, binding = 4) uniform sampler s_point;
+layout(set = 0, binding = 5) uniform Taa {
+    mat4 u_inverse_view_proj;
+    mat4 u_previous_view_proj;
+    vec2 u_jitter;
+    float u_blend;
+    float u_debug;
+};
+
+void main() {
+    vec2 texel = 1.0 / vec2(textureSize(sampler2D(t_color, s_point), 0));
+    vec2 uv = v_tex_coord + vec2(u_jitter.x, -u_jitter.y) * 0.5;
+
+    float depth = texture(sampler2D(t_depth, s_point), v_tex_coord).r;
+    vec4 ndc = vec4(v_tex_coord.x * 2.0 - 1.0, 1.0 - v_tex_coord.y * 2.0, depth, 1.0);
+    vec4 world = u_inverse_view_proj * ndc;
+    vec4 previous = u_previous_view_proj * vec4(world.xyz / world.w, 1.0);
+    vec2 history_uv = vec2(previous.x, -previous.y) / previous.w * 0.5 + 0.5;
+    vec2 motion = v_tex_coord - history_uv;
+
+    if (u_debug > 0.5) {
+        f_color = vec4(abs(motion) * 50.0, 0.0, 1.0);
+        return;
+    }
+
+    vec3 current = texture(sampler2D(t_color, s_point), uv).rgb;
+    vec3 minimum = current;
+    vec3 maximum = current;
+    for (int y = -1; y <= 1; y++) {
+        for (int x = -1; x <= 1; x++) {
+            vec3 neighbor = texture(sampler2D(t_color, s_point), uv + vec2(x, y) * texel).rgb;
+            minimum = min(minimum, neighbor);
+            maximum = max(maximum, neighbor);
+        }
+    }
+
+    vec3 history = texture(sampler2D(t_history, s_linear), history_uv).rgb;
+    history = clamp(history, minimum, maximum);
+
+    bool outside = any(lessThan(history_uv, vec2(0.0))) || any(greaterThan(history_uv, vec2(1.0)));
+    float blend = outside ? 1.0 : u_blend;
+    f_color = vec4(mix(history, current, blend), 1.0);
+}
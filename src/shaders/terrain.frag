@@ -0,0 +1,36 @@
+#version 450
+
+#include "lighting.glsl"
+
+layout(location = 0) in vec3 v_world_position;
+layout(location = 1) in vec3 v_normal;
+
+layout(location = 0) out vec4 f_color;
+
+layout(set = 1, binding = 0) uniform Terrain {
+    vec4 u_terrain;
+};
+
+const vec3 SAND = vec3(0.76, 0.7, 0.5);
+const vec3 GRASS = vec3(0.25, 0.45, 0.18);
+const vec3 ROCK = vec3(0.4, 0.37, 0.35);
+const vec3 SNOW = vec3(0.92, 0.94, 0.97);
+
+void main() {
+    vec3 normal = normalize(v_normal);
+    float height = clamp(v_world_position.y / max(u_terrain.x, 1e-3), 0.0, 1.0);
+    float snow_line = u_terrain.y;
+    float rock_slope = u_terrain.z;
+
+    float sand = 1.0 - smoothstep(0.15, 0.25, height);
+    float snow = smoothstep(snow_line - 0.05, snow_line + 0.05, height);
+    float rock = 1.0 - smoothstep(rock_slope - 0.05, rock_slope + 0.05, normal.y);
+    float grass = max(1.0 - sand - snow, 0.0);
+
+    vec3 albedo = SAND * sand + GRASS * grass + SNOW * snow;
+    albedo /= max(sand + grass + snow, 1e-3);
+    albedo = mix(albedo, ROCK, rock);
+
+    vec3 color = shade(v_world_position, normal, albedo, vec2(0.0, mix(0.9, 0.4, snow)));
+    f_color = vec4(color, 1.0);
+}
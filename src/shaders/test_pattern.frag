@@ -0,0 +1,41 @@
+#version 450
+
+layout(location = 0) in vec2 v_tex_coord;
+
+layout(location = 0) out vec4 f_color;
+
+const int RAMP_STEPS = 16;
+
+const vec3 PATCHES[7] = vec3[](
+    vec3(1.0, 0.0, 0.0),
+    vec3(0.0, 1.0, 0.0),
+    vec3(0.0, 0.0, 1.0),
+    vec3(0.0, 1.0, 1.0),
+    vec3(1.0, 0.0, 1.0),
+    vec3(1.0, 1.0, 0.0),
+    vec3(1.0, 1.0, 1.0)
+);
+
+vec3 linear_from_srgb(vec3 srgb) {
+    bvec3 cutoff = lessThan(srgb, vec3(0.04045));
+    vec3 lower = srgb / vec3(12.92);
+    vec3 higher = pow((srgb + vec3(0.055)) / vec3(1.055), vec3(2.4));
+    return mix(higher, lower, cutoff);
+}
+
+void main() {
+    vec2 uv = v_tex_coord;
+    vec3 color;
+    if (uv.y < 0.25) {
+        float step = floor(uv.x * float(RAMP_STEPS)) / float(RAMP_STEPS - 1);
+        color = linear_from_srgb(vec3(step));
+    } else if (uv.y < 0.5) {
+        color = PATCHES[min(int(uv.x * 7.0), 6)];
+    } else if (uv.x > 1.0 / 3.0 && uv.x < 2.0 / 3.0) {
+        // Half the light of white, which should match the line pattern beside it.
+        color = vec3(0.5);
+    } else {
+        color = vec3(mod(floor(gl_FragCoord.y), 2.0));
+    }
+    f_color = vec4(color, 1.0);
+}
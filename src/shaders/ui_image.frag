@@ -0,0 +1,27 @@
+#version 450
+
+layout(location = 0) in vec2 v_tex_coord;
+layout(location = 1) in vec4 v_color;
+
+layout(location = 0) out vec4 f_color;
+
+layout(set = 1, binding = 0) uniform texture2D t_texture;
+layout(set = 1, binding = 1) uniform sampler s_texture;
+
+vec3 linear_from_srgb(vec3 srgb) {
+    bvec3 cutoff = lessThan(srgb, vec3(0.04045));
+    vec3 lower = srgb / vec3(12.92);
+    vec3 higher = pow((srgb + vec3(0.055)) / vec3(1.055), vec3(2.4));
+    return mix(higher, lower, cutoff);
+}
+
+void main() {
+    vec4 texel = texture(sampler2D(t_texture, s_texture), v_tex_coord);
+    if (all(equal(v_color, vec4(1.0)))) {
+        f_color = vec4(texel.rgb * texel.a, texel.a);
+    } else if (v_color.a == 1.0) {
+        f_color = vec4(linear_from_srgb(vec3(texel.a)), 1.0);
+    } else {
+        f_color = vec4(vec3(dot(texel.rgb, v_color.rgb)), 1.0);
+    }
+}
@@ -0,0 +1,48 @@
+#version 450
+
+layout(location = 0) in vec2 v_tex_coord;
+
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D t_source;
+layout(set = 0, binding = 1) uniform sampler s_source;
+layout(set = 0, binding = 2) uniform Visualize {
+    mat4 u_inverse_projection;
+    vec4 u_channels;
+    vec2 u_range;
+    uint u_mode;
+    uint u_padding;
+};
+
+const uint MODE_COLOR = 0u;
+const uint MODE_DEPTH = 1u;
+const uint MODE_NORMALS = 2u;
+
+vec3 view_position(float depth) {
+    vec4 ndc = vec4(v_tex_coord.x * 2.0 - 1.0, 1.0 - v_tex_coord.y * 2.0, depth, 1.0);
+    vec4 position = u_inverse_projection * ndc;
+    return position.xyz / position.w;
+}
+
+void main() {
+    vec4 value = texture(sampler2D(t_source, s_source), v_tex_coord);
+    if (u_mode == MODE_DEPTH) {
+        value = vec4(-view_position(value.r).z);
+    } else if (u_mode == MODE_NORMALS) {
+        vec3 position = view_position(value.r);
+        vec3 normal = normalize(cross(dFdx(position), dFdy(position)));
+        if (dot(normal, position) > 0.0) {
+            normal = -normal;
+        }
+        value = value.r >= 1.0 ? vec4(0.0) : vec4(normal * 0.5 + 0.5, 1.0);
+    }
+
+    value = (value - u_range.x) / max(u_range.y - u_range.x, 1e-5);
+    vec3 color;
+    if (dot(u_channels, vec4(1.0)) == 1.0) {
+        color = vec3(dot(value, u_channels));
+    } else {
+        color = value.rgb * u_channels.rgb;
+    }
+    f_color = vec4(pow(clamp(color, 0.0, 1.0), vec3(2.2)), 1.0);
+}
@@ -0,0 +1,26 @@
+#version 450
+
+#include "lighting.glsl"
+
+layout(location = 0) in vec3 v_world_position;
+layout(location = 1) in vec3 v_normal;
+layout(location = 2) flat in uint v_material;
+
+layout(location = 0) out vec4 f_color;
+
+const vec3 PALETTE[4] = vec3[](
+    vec3(1.0, 0.0, 1.0),
+    vec3(0.3, 0.55, 0.2),
+    vec3(0.45, 0.32, 0.2),
+    vec3(0.45, 0.45, 0.47)
+);
+
+void main() {
+    vec3 normal = normalize(v_normal);
+    vec3 albedo = PALETTE[min(v_material, 3u)];
+    if (v_material == 1u && normal.y < 0.5) {
+        albedo = PALETTE[2];
+    }
+    vec3 color = shade(v_world_position, normal, albedo, vec2(0.0, 0.85));
+    f_color = vec4(color, 1.0);
+}
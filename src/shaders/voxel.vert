@@ -0,0 +1,24 @@
+#version 450
+
+layout(location = 0) in vec3 a_position;
+layout(location = 1) in vec3 a_normal;
+layout(location = 2) in uint a_material;
+
+layout(location = 0) out vec3 v_world_position;
+layout(location = 1) out vec3 v_normal;
+layout(location = 2) flat out uint v_material;
+
+layout(set = 0, binding = 0) uniform Globals {
+    mat4 u_view_proj;
+    vec4 u_camera_position;
+    vec4 u_light_direction;
+    vec4 u_light_color;
+    vec4 u_ambient;
+};
+
+void main() {
+    v_world_position = a_position;
+    v_normal = a_normal;
+    v_material = a_material;
+    gl_Position = u_view_proj * vec4(a_position, 1.0);
+}
@@ -0,0 +1,28 @@
+#version 450
+
+layout(location = 0) in vec2 v_tex_coord;
+
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform Wipe {
+    float u_split;
+    float u_difference;
+    float u_gain;
+    float u_line;
+};
+layout(set = 0, binding = 1) uniform texture2D t_a;
+layout(set = 0, binding = 2) uniform texture2D t_b;
+layout(set = 0, binding = 3) uniform sampler s_source;
+
+void main() {
+    vec4 a = texture(sampler2D(t_a, s_source), v_tex_coord);
+    vec4 b = texture(sampler2D(t_b, s_source), v_tex_coord);
+    if (u_difference != 0.0) {
+        f_color = vec4(clamp(abs(b.rgb - a.rgb) * u_gain, vec3(0.0), vec3(1.0)), 1.0);
+        return;
+    }
+    f_color = vec4(clamp(v_tex_coord.x < u_split ? a.rgb : b.rgb, vec3(0.0), vec3(1.0)), 1.0);
+    if (abs(v_tex_coord.x - u_split) < u_line) {
+        f_color = vec4(1.0);
+    }
+}
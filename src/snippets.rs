@@ -0,0 +1,317 @@
+const QUICK_RESULTS: usize = 8;
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct Snippet {
+    pub name: String,
+    pub tags: Vec<String>,
+    pub body: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Snippets {
+    pub items: Vec<Snippet>,
+}
+
+impl Default for Snippets {
+    fn default() -> Self {
+        Self {
+            items: vec![
+                Snippet {
+                    name: "Rust test module".to_owned(),
+                    tags: vec!["rust".to_owned(), "test".to_owned()],
+                    body: "#[cfg(test)]\nmod tests {\n    #[test]\n    fn it_works() {\n    }\n}\n"
+                        .to_owned(),
+                },
+                Snippet {
+                    name: "Fragment shader".to_owned(),
+                    tags: vec!["glsl".to_owned(), "shader".to_owned()],
+                    body: "#version 450\n\nlayout(location = 0) in vec2 v_uv;\nlayout(location = 0) out vec4 f_color;\n\nvoid main() {\n    f_color = vec4(v_uv, 0.0, 1.0);\n}\n"
+                        .to_owned(),
+                },
+            ],
+        }
+    }
+}
+
+impl Snippets {
+    fn path() -> Option<std::path::PathBuf> {
+        dirs::config_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")).join("snippets.json"))
+    }
+
+    pub fn load() -> Self {
+        let path = match Self::path() {
+            Some(path) => path,
+            None => return Self::default(),
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|err| {
+                log::warn!("ignoring invalid snippets in {}: {}", path.display(), err);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self) {
+        let path = match Self::path() {
+            Some(path) => path,
+            None => return,
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&path, serde_json::to_string_pretty(self).unwrap()));
+        match result {
+            Ok(()) => log::info!("saved {} snippets to {}", self.items.len(), path.display()),
+            Err(err) => log::warn!("cannot save snippets to {}: {}", path.display(), err),
+        }
+    }
+
+    pub fn add(&mut self, body: &str) -> usize {
+        let name = body
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map_or("New snippet".to_owned(), |line| {
+                line.chars().take(32).collect()
+            });
+        self.items.push(Snippet {
+            name,
+            tags: Vec::new(),
+            body: body.to_owned(),
+        });
+        self.items.len() - 1
+    }
+
+    fn search(&self, query: &str) -> Vec<usize> {
+        let mut scored: Vec<(i32, usize)> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, snippet)| {
+                let tags = snippet
+                    .tags
+                    .iter()
+                    .filter_map(|tag| fuzzy_score(query, tag))
+                    .max();
+                let score = fuzzy_score(query, &snippet.name).max(tags)?;
+                Some((score, index))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        scored.into_iter().map(|(_, index)| index).collect()
+    }
+}
+
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let mut score = 0;
+    let mut previous: Option<usize> = None;
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut position = 0;
+    for wanted in query
+        .to_lowercase()
+        .chars()
+        .filter(|character| !character.is_whitespace())
+    {
+        let found = candidate[position..]
+            .iter()
+            .position(|character| *character == wanted)?
+            + position;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !candidate[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score - candidate.len() as i32 / 8)
+}
+
+#[derive(Default)]
+pub struct SnippetsPanel {
+    pub open: bool,
+    pub capture_requested: bool,
+    query: String,
+    selected: Option<usize>,
+    tags: String,
+    quick_insert: bool,
+    quick_query: String,
+    insert: Option<String>,
+}
+
+impl SnippetsPanel {
+    pub fn quick_insert(&mut self) {
+        self.quick_insert = true;
+        self.quick_query.clear();
+    }
+
+    pub fn take_insert(&mut self) -> Option<String> {
+        self.insert.take()
+    }
+
+    pub fn select(&mut self, snippets: &Snippets, index: usize) {
+        self.selected = Some(index);
+        self.tags = snippets.items[index].tags.join(", ");
+        self.open = true;
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, snippets: &mut Snippets) {
+        let mut quick_insert = self.quick_insert;
+        let mut chosen = None;
+        let quick_query = &mut self.quick_query;
+        egui::Window::new("Insert Snippet")
+            .open(&mut quick_insert)
+            .default_pos(egui::pos2(420.0, 120.0))
+            .show(ctx, |ui| {
+                ui.add(egui::TextEdit::new(quick_query));
+                for index in snippets.search(quick_query).into_iter().take(QUICK_RESULTS) {
+                    let snippet = &snippets.items[index];
+                    let label = if snippet.tags.is_empty() {
+                        snippet.name.clone()
+                    } else {
+                        format!("{}  #{}", snippet.name, snippet.tags.join(" #"))
+                    };
+                    if ui.button(label).clicked {
+                        chosen = Some(index);
+                    }
+                }
+            });
+        if let Some(index) = chosen {
+            self.insert = Some(snippets.items[index].body.clone());
+            quick_insert = false;
+        }
+        self.quick_insert = quick_insert;
+
+        let mut open = self.open;
+        let mut save_requested = false;
+        let mut delete_requested = false;
+        let mut add_requested = false;
+        let mut select = None;
+        let query = &mut self.query;
+        let selected = self.selected.filter(|index| *index < snippets.items.len());
+        let tags = &mut self.tags;
+        let capture_requested = &mut self.capture_requested;
+        let insert = &mut self.insert;
+        crate::dock::Panel::new("Snippets")
+            .open(&mut open)
+            .default_pos(egui::pos2(400.0, 100.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Search");
+                    ui.add(egui::TextEdit::new(query));
+                });
+                egui::ScrollArea::from_max_height(150.0).show(ui, |ui| {
+                    for index in snippets.search(query) {
+                        let snippet = &snippets.items[index];
+                        ui.horizontal(|ui| {
+                            if ui
+                                .radio(selected == Some(index), snippet.name.as_str())
+                                .clicked
+                            {
+                                select = Some(index);
+                            }
+                            for tag in snippet.tags.iter() {
+                                ui.add(
+                                    egui::Label::new(format!("#{}", tag))
+                                        .text_style(egui::TextStyle::Small),
+                                );
+                            }
+                        });
+                    }
+                });
+                ui.horizontal(|ui| {
+                    if ui.button("New").clicked {
+                        add_requested = true;
+                    }
+                    if ui.button("From editor selection").clicked {
+                        *capture_requested = true;
+                    }
+                });
+                ui.label("Ctrl+J in the code editor opens quick insert.");
+
+                let index = match selected {
+                    Some(index) => index,
+                    None => return,
+                };
+                ui.separator();
+                let snippet = &mut snippets.items[index];
+                ui.horizontal(|ui| {
+                    ui.label("Name");
+                    ui.add(egui::TextEdit::new(&mut snippet.name));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Tags");
+                    ui.add(egui::TextEdit::new(tags));
+                });
+                snippet.tags = tags
+                    .split(',')
+                    .map(|tag| tag.trim().to_owned())
+                    .filter(|tag| !tag.is_empty())
+                    .collect();
+                ui.add(egui::TextEdit::new(&mut snippet.body).multiline(true));
+                ui.horizontal(|ui| {
+                    if ui.button("Insert into editor").clicked {
+                        *insert = Some(snippet.body.clone());
+                    }
+                    if ui.button("Save library").clicked {
+                        save_requested = true;
+                    }
+                    if ui.button("Delete").clicked {
+                        delete_requested = true;
+                    }
+                });
+            });
+        self.open = open;
+        if let Some(index) = select {
+            self.select(snippets, index);
+        }
+        if add_requested {
+            let index = snippets.add("");
+            self.select(snippets, index);
+        }
+        if delete_requested {
+            if let Some(index) = selected {
+                snippets.items.remove(index);
+                self.selected = None;
+            }
+        }
+        if save_requested {
+            snippets.save();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fuzzy_score;
+
+    #[test]
+    fn exact_match_scores_every_bonus() {
+        assert_eq!(fuzzy_score("abc", "abc"), Some(16));
+        assert_eq!(fuzzy_score("ABC", "abc"), Some(16));
+    }
+
+    #[test]
+    fn missing_or_reordered_characters_do_not_match() {
+        assert_eq!(fuzzy_score("xyz", "abc"), None);
+        assert_eq!(fuzzy_score("ba", "ab"), None);
+        assert_eq!(fuzzy_score("aa", "a"), None);
+    }
+
+    #[test]
+    fn whitespace_in_the_query_is_ignored() {
+        assert_eq!(fuzzy_score("a b c", "abc"), fuzzy_score("abc", "abc"));
+        assert_eq!(fuzzy_score("", "abcdefghijklmnop"), Some(-2));
+    }
+
+    #[test]
+    fn contiguous_and_word_start_matches_rank_higher() {
+        assert!(fuzzy_score("abc", "abcxxx") > fuzzy_score("abc", "axbxcx"));
+        assert!(fuzzy_score("b", "foo bar") > fuzzy_score("o", "foo bar"));
+        assert!(fuzzy_score("for", "for loop") > fuzzy_score("for", "for loop with a long name"));
+    }
+}
@@ -0,0 +1,382 @@
+const LANGUAGE: &str = "en_US";
+const MAX_SUGGESTIONS: usize = 6;
+const SEARCH_PATHS: [&str; 4] = [
+    "/usr/share/hunspell",
+    "/usr/share/myspell",
+    "/usr/share/myspell/dicts",
+    "/Library/Spelling",
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum FlagMode {
+    Char,
+    Long,
+    Number,
+}
+
+impl FlagMode {
+    fn parse(self, flags: &str) -> Vec<String> {
+        match self {
+            FlagMode::Char => flags.chars().map(|flag| flag.to_string()).collect(),
+            FlagMode::Long => flags
+                .chars()
+                .collect::<Vec<char>>()
+                .chunks(2)
+                .map(|pair| pair.iter().collect())
+                .collect(),
+            FlagMode::Number => flags
+                .split(',')
+                .map(|flag| flag.trim().to_owned())
+                .collect(),
+        }
+    }
+}
+
+enum Pattern {
+    Any,
+    Class(Vec<char>, bool),
+}
+
+impl Pattern {
+    fn matches(&self, character: char) -> bool {
+        match self {
+            Pattern::Any => true,
+            Pattern::Class(characters, negated) => characters.contains(&character) != *negated,
+        }
+    }
+}
+
+fn parse_condition(condition: &str) -> Vec<Pattern> {
+    let mut patterns = Vec::new();
+    let mut characters = condition.chars();
+    while let Some(character) = characters.next() {
+        match character {
+            '.' => patterns.push(Pattern::Any),
+            '[' => {
+                let mut class: Vec<char> = characters
+                    .by_ref()
+                    .take_while(|member| *member != ']')
+                    .collect();
+                let negated = class.first() == Some(&'^');
+                if negated {
+                    class.remove(0);
+                }
+                patterns.push(Pattern::Class(class, negated));
+            }
+            character => patterns.push(Pattern::Class(vec![character], false)),
+        }
+    }
+    patterns
+}
+
+struct Affix {
+    flag: String,
+    strip: String,
+    add: String,
+    condition: Vec<Pattern>,
+    cross: bool,
+}
+
+impl Affix {
+    fn condition_matches(&self, characters: &[char]) -> bool {
+        characters.len() >= self.condition.len()
+            && self
+                .condition
+                .iter()
+                .zip(characters)
+                .all(|(pattern, character)| pattern.matches(*character))
+    }
+}
+
+pub struct Dictionary {
+    pub name: String,
+    words: std::collections::HashMap<String, Vec<String>>,
+    personal: std::collections::BTreeSet<String>,
+    prefixes: Vec<Affix>,
+    suffixes: Vec<Affix>,
+    try_characters: Vec<char>,
+}
+
+fn decode(bytes: Vec<u8>, latin1: bool) -> String {
+    if latin1 {
+        bytes.into_iter().map(char::from).collect()
+    } else {
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+}
+
+fn personal_path() -> Option<std::path::PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")).join("words.txt"))
+}
+
+impl Dictionary {
+    fn find() -> Option<(std::path::PathBuf, std::path::PathBuf)> {
+        let user =
+            dirs::config_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")).join("dictionaries"));
+        user.into_iter()
+            .chain(SEARCH_PATHS.iter().map(std::path::PathBuf::from))
+            .map(|dir| {
+                (
+                    dir.join(format!("{}.aff", LANGUAGE)),
+                    dir.join(format!("{}.dic", LANGUAGE)),
+                )
+            })
+            .find(|(aff, dic)| aff.is_file() && dic.is_file())
+    }
+
+    pub fn load() -> Result<Self, String> {
+        let (aff, dic) = Self::find().ok_or_else(|| {
+            format!(
+                "no {} hunspell dictionary in {} or the config directory",
+                LANGUAGE,
+                SEARCH_PATHS.join(", ")
+            )
+        })?;
+        let affixes = std::fs::read(&aff).map_err(|err| err.to_string())?;
+        let latin1 = !String::from_utf8_lossy(&affixes).contains("SET UTF-8");
+        let affixes = decode(affixes, latin1);
+        let words = decode(std::fs::read(&dic).map_err(|err| err.to_string())?, latin1);
+
+        let mut mode = FlagMode::Char;
+        let mut prefixes = Vec::new();
+        let mut suffixes = Vec::new();
+        let mut try_characters = Vec::new();
+        let mut headers = std::collections::HashMap::new();
+        for line in affixes.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                ["FLAG", "long"] => mode = FlagMode::Long,
+                ["FLAG", "num"] => mode = FlagMode::Number,
+                ["TRY", characters] => try_characters = characters.chars().collect(),
+                [kind @ "PFX", flag, cross, _] | [kind @ "SFX", flag, cross, _] => {
+                    headers.insert((kind.to_string(), flag.to_string()), *cross == "Y");
+                }
+                [kind @ "PFX", flag, strip, add, condition, ..]
+                | [kind @ "SFX", flag, strip, add, condition, ..] => {
+                    let add = add.split('/').next().unwrap_or("");
+                    let mut condition = parse_condition(condition);
+                    if *kind == "SFX" {
+                        condition.reverse();
+                    }
+                    let affix = Affix {
+                        flag: flag.to_string(),
+                        strip: if *strip == "0" {
+                            String::new()
+                        } else {
+                            strip.to_string()
+                        },
+                        add: if add == "0" {
+                            String::new()
+                        } else {
+                            add.to_owned()
+                        },
+                        condition,
+                        cross: headers
+                            .get(&(kind.to_string(), flag.to_string()))
+                            .copied()
+                            .unwrap_or(false),
+                    };
+                    if *kind == "PFX" {
+                        prefixes.push(affix);
+                    } else {
+                        suffixes.push(affix);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let words: std::collections::HashMap<String, Vec<String>> = words
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let entry = line.split_whitespace().next()?;
+                let mut parts = entry.splitn(2, '/');
+                let word = parts.next()?.to_owned();
+                let flags = parts.next().map_or(Vec::new(), |flags| mode.parse(flags));
+                Some((word, flags))
+            })
+            .collect();
+        let personal = personal_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .map(|contents| contents.lines().map(str::to_owned).collect())
+            .unwrap_or_default();
+        log::info!(
+            "loaded {} words and {} affix rules from {}",
+            words.len(),
+            prefixes.len() + suffixes.len(),
+            dic.display()
+        );
+        Ok(Self {
+            name: LANGUAGE.to_owned(),
+            words,
+            personal,
+            prefixes,
+            suffixes,
+            try_characters,
+        })
+    }
+
+    pub fn add_personal(&mut self, word: &str) {
+        self.personal.insert(word.to_owned());
+        let path = match personal_path() {
+            Some(path) => path,
+            None => return,
+        };
+        let words: Vec<&str> = self.personal.iter().map(String::as_str).collect();
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&path, words.join("\n")));
+        match result {
+            Ok(()) => log::info!("saved {} personal words to {}", words.len(), path.display()),
+            Err(err) => log::warn!("cannot save personal words to {}: {}", path.display(), err),
+        }
+    }
+
+    fn has_flag(&self, stem: &str, flag: &str) -> bool {
+        self.words
+            .get(stem)
+            .is_some_and(|flags| flags.iter().any(|candidate| candidate == flag))
+    }
+
+    fn check_suffixes(&self, word: &str, prefix: Option<&Affix>) -> bool {
+        self.suffixes.iter().any(|suffix| {
+            if prefix.is_some() && !suffix.cross {
+                return false;
+            }
+            if !word.ends_with(&suffix.add) || word.len() == suffix.add.len() {
+                return false;
+            }
+            let stem = format!("{}{}", &word[..word.len() - suffix.add.len()], suffix.strip);
+            let reversed: Vec<char> = stem.chars().rev().collect();
+            suffix.condition_matches(&reversed)
+                && self.has_flag(&stem, &suffix.flag)
+                && prefix.is_none_or(|prefix| self.has_flag(&stem, &prefix.flag))
+        })
+    }
+
+    fn check_exact(&self, word: &str) -> bool {
+        if self.words.contains_key(word) || self.personal.contains(word) {
+            return true;
+        }
+        if self.check_suffixes(word, None) {
+            return true;
+        }
+        self.prefixes.iter().any(|prefix| {
+            if !word.starts_with(&prefix.add) || word.len() == prefix.add.len() {
+                return false;
+            }
+            let stem = format!("{}{}", prefix.strip, &word[prefix.add.len()..]);
+            let characters: Vec<char> = stem.chars().collect();
+            prefix.condition_matches(&characters)
+                && (self.has_flag(&stem, &prefix.flag)
+                    || (prefix.cross && self.check_suffixes(&stem, Some(prefix))))
+        })
+    }
+
+    pub fn check(&self, word: &str) -> bool {
+        let word = word.trim_matches('\'');
+        if word.is_empty()
+            || word
+                .chars()
+                .any(|character| !character.is_alphabetic() && character != '\'')
+        {
+            return true;
+        }
+        if self.check_exact(word) {
+            return true;
+        }
+        let lower = word.to_lowercase();
+        lower != word && self.check_exact(&lower)
+    }
+
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        let characters: Vec<char> = word.chars().collect();
+        let capitalized = characters.first().is_some_and(|first| first.is_uppercase());
+        let lower: Vec<char> = word.to_lowercase().chars().collect();
+        let alphabet: Vec<char> = if self.try_characters.is_empty() {
+            ('a'..='z').collect()
+        } else {
+            self.try_characters
+                .iter()
+                .copied()
+                .filter(|character| character.is_lowercase())
+                .collect()
+        };
+        let mut candidates: Vec<String> = Vec::new();
+        let text = |characters: &[char]| characters.iter().collect::<String>();
+        for index in 0..lower.len() {
+            let mut swapped = lower.clone();
+            if index + 1 < lower.len() {
+                swapped.swap(index, index + 1);
+                candidates.push(text(&swapped));
+            }
+            let mut deleted = lower.clone();
+            deleted.remove(index);
+            candidates.push(text(&deleted));
+            for replacement in alphabet.iter() {
+                let mut replaced = lower.clone();
+                replaced[index] = *replacement;
+                candidates.push(text(&replaced));
+            }
+        }
+        for index in 0..=lower.len() {
+            for insertion in alphabet.iter() {
+                let mut inserted = lower.clone();
+                inserted.insert(index, *insertion);
+                candidates.push(text(&inserted));
+            }
+            if index > 1 && index + 1 < lower.len() {
+                candidates.push(format!(
+                    "{} {}",
+                    text(&lower[..index]),
+                    text(&lower[index..])
+                ));
+            }
+        }
+        let mut suggestions: Vec<String> = Vec::new();
+        for candidate in candidates {
+            if suggestions.len() == MAX_SUGGESTIONS {
+                break;
+            }
+            if candidate.split(' ').all(|part| self.check_exact(part))
+                && !suggestions.contains(&candidate)
+            {
+                suggestions.push(candidate);
+            }
+        }
+        if capitalized {
+            for suggestion in suggestions.iter_mut() {
+                let mut characters = suggestion.chars();
+                if let Some(first) = characters.next() {
+                    *suggestion = first.to_uppercase().chain(characters).collect();
+                }
+            }
+        }
+        suggestions
+    }
+}
+
+pub fn words(text: &str) -> impl Iterator<Item = std::ops::Range<usize>> + '_ {
+    let mut start = None;
+    text.char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+        .filter_map(move |(index, character)| {
+            let inside = character.is_alphabetic()
+                || (character == '\''
+                    && start.is_some()
+                    && text[index + 1..].starts_with(char::is_alphabetic));
+            match (inside, start) {
+                (true, None) => {
+                    start = Some(index);
+                    None
+                }
+                (false, Some(from)) => {
+                    start = None;
+                    Some(from..index)
+                }
+                _ => None,
+            }
+        })
+}
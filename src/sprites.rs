@@ -0,0 +1,726 @@
+pub const COUNTS: [u32; 4] = [1000, 2500, 5000, 10000];
+const VIEW_SIZE: [f32; 2] = [480.0, 320.0];
+const ATLAS_SIZE: u32 = 256;
+const FRAME_SIZE: u32 = 32;
+const FRAMES: u32 = 8;
+const LAYERS: u32 = 4;
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Instance {
+    position: [f32; 2],
+    size: [f32; 2],
+    uv: [f32; 4],
+    color: [f32; 4],
+    rotation: f32,
+    padding: [f32; 3],
+}
+
+#[derive(Clone, Copy)]
+pub struct Region {
+    pub atlas: usize,
+    pub uv: [f32; 4],
+}
+
+pub struct Atlas {
+    pixels: Vec<u8>,
+    cursor: [u32; 2],
+    shelf_height: u32,
+    uploaded: bool,
+    texture: wgpu::Texture,
+    bind_group: wgpu::BindGroup,
+}
+
+impl Atlas {
+    fn new(device: &wgpu::Device, layout: &wgpu::BindGroupLayout, sampler: &wgpu::Sampler) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Sprite Atlas"),
+            size: wgpu::Extent3d {
+                width: ATLAS_SIZE,
+                height: ATLAS_SIZE,
+                depth: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST,
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Sprite Atlas Bind Group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+            ],
+        });
+
+        Self {
+            pixels: vec![0; (ATLAS_SIZE * ATLAS_SIZE * 4) as usize],
+            cursor: [0, 0],
+            shelf_height: 0,
+            uploaded: false,
+            texture,
+            bind_group,
+        }
+    }
+
+    fn insert(&mut self, index: usize, width: u32, height: u32, pixels: &[u8]) -> Option<Region> {
+        if self.cursor[0] + width > ATLAS_SIZE {
+            self.cursor = [0, self.cursor[1] + self.shelf_height];
+            self.shelf_height = 0;
+        }
+        if self.cursor[1] + height > ATLAS_SIZE {
+            return None;
+        }
+        let [x, y] = self.cursor;
+        for row in 0..height {
+            let source = (row * width * 4) as usize;
+            let destination = (((y + row) * ATLAS_SIZE + x) * 4) as usize;
+            self.pixels[destination..destination + (width * 4) as usize]
+                .copy_from_slice(&pixels[source..source + (width * 4) as usize]);
+        }
+        self.cursor[0] += width;
+        self.shelf_height = self.shelf_height.max(height);
+        self.uploaded = false;
+        let size = ATLAS_SIZE as f32;
+        Some(Region {
+            atlas: index,
+            uv: [
+                x as f32 / size,
+                y as f32 / size,
+                (x + width) as f32 / size,
+                (y + height) as f32 / size,
+            ],
+        })
+    }
+
+    fn upload(&mut self, device: &wgpu::Device, encoder: &mut wgpu::CommandEncoder) {
+        if self.uploaded {
+            return;
+        }
+        let staging = wgpu::util::DeviceExt::create_buffer_init(
+            device,
+            &wgpu::util::BufferInitDescriptor {
+                label: Some("Sprite Atlas Staging"),
+                contents: &self.pixels,
+                usage: wgpu::BufferUsage::COPY_SRC,
+            },
+        );
+        encoder.copy_buffer_to_texture(
+            wgpu::BufferCopyView {
+                buffer: &staging,
+                layout: wgpu::TextureDataLayout {
+                    offset: 0,
+                    bytes_per_row: ATLAS_SIZE * 4,
+                    rows_per_image: ATLAS_SIZE,
+                },
+            },
+            wgpu::TextureCopyView {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            wgpu::Extent3d {
+                width: ATLAS_SIZE,
+                height: ATLAS_SIZE,
+                depth: 1,
+            },
+        );
+        self.uploaded = true;
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Sprite {
+    pub position: [f32; 2],
+    pub size: [f32; 2],
+    pub rotation: f32,
+    pub color: [f32; 4],
+    pub region: Region,
+    pub layer: u32,
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct BatchStats {
+    pub sprites: usize,
+    pub batches: usize,
+    pub unsorted_batches: usize,
+    pub upload_bytes: usize,
+}
+
+struct Batch {
+    atlas: usize,
+    instances: std::ops::Range<u32>,
+}
+
+pub struct SpriteBatch {
+    pub sort: bool,
+    pub stats: BatchStats,
+    sprites: Vec<Sprite>,
+    batches: Vec<Batch>,
+    instance_buffer: wgpu::Buffer,
+    capacity: usize,
+}
+
+impl SpriteBatch {
+    fn new(device: &wgpu::Device) -> Self {
+        let capacity = 1024;
+        Self {
+            sort: true,
+            stats: BatchStats::default(),
+            sprites: Vec::new(),
+            batches: Vec::new(),
+            instance_buffer: create_instance_buffer(device, capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, sprite: Sprite) {
+        self.sprites.push(sprite);
+    }
+
+    fn count_batches(sprites: &[Sprite]) -> usize {
+        let mut batches = 0;
+        let mut current = None;
+        for sprite in sprites {
+            if current != Some(sprite.region.atlas) {
+                current = Some(sprite.region.atlas);
+                batches += 1;
+            }
+        }
+        batches
+    }
+
+    fn prepare(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        let unsorted_batches = Self::count_batches(&self.sprites);
+        if self.sort {
+            self.sprites
+                .sort_by_key(|sprite| (sprite.layer, sprite.region.atlas));
+        }
+
+        self.batches.clear();
+        let mut instances = Vec::with_capacity(self.sprites.len());
+        for (index, sprite) in self.sprites.iter().enumerate() {
+            let index = index as u32;
+            let extends = self
+                .batches
+                .last()
+                .is_some_and(|batch| batch.atlas == sprite.region.atlas);
+            if extends {
+                self.batches.last_mut().unwrap().instances.end = index + 1;
+            } else {
+                self.batches.push(Batch {
+                    atlas: sprite.region.atlas,
+                    instances: index..index + 1,
+                });
+            }
+            instances.push(Instance {
+                position: sprite.position,
+                size: sprite.size,
+                uv: sprite.region.uv,
+                color: sprite.color,
+                rotation: sprite.rotation,
+                padding: [0.0; 3],
+            });
+        }
+
+        if instances.len() > self.capacity {
+            self.capacity = instances.len().next_power_of_two();
+            self.instance_buffer = create_instance_buffer(device, self.capacity);
+        }
+        let bytes: &[u8] = bytemuck::cast_slice(&instances);
+        uploader.write_buffer(device, encoder, &self.instance_buffer, 0, bytes);
+        self.stats = BatchStats {
+            sprites: instances.len(),
+            batches: self.batches.len(),
+            unsorted_batches,
+            upload_bytes: bytes.len(),
+        };
+        self.sprites.clear();
+    }
+}
+
+fn create_instance_buffer(device: &wgpu::Device, capacity: usize) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Sprite Instances"),
+        size: (capacity * std::mem::size_of::<Instance>()) as wgpu::BufferAddress,
+        usage: wgpu::BufferUsage::VERTEX | wgpu::BufferUsage::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+pub struct SpriteRenderer {
+    pub atlases: Vec<Atlas>,
+    pub batch: SpriteBatch,
+    pipeline: wgpu::RenderPipeline,
+    atlas_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    uniform_buffer: wgpu::Buffer,
+    uniform_bind_group: wgpu::BindGroup,
+}
+
+impl SpriteRenderer {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let uniform_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Sprite Uniform Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStage::VERTEX,
+                ty: wgpu::BindingType::UniformBuffer {
+                    dynamic: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let atlas_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Sprite Atlas Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::SampledTexture {
+                        dimension: wgpu::TextureViewDimension::D2,
+                        component_type: wgpu::TextureComponentType::Float,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler { comparison: false },
+                    count: None,
+                },
+            ],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Sprite Pipeline Layout"),
+            bind_group_layouts: &[&uniform_layout, &atlas_layout],
+            push_constant_ranges: &[],
+        });
+        let vs_module = crate::shader::compile_builtin(
+            device,
+            "sprite.vert",
+            include_str!("shaders/sprite.vert"),
+            shaderc::ShaderKind::Vertex,
+        );
+        let fs_module = crate::shader::compile_builtin(
+            device,
+            "sprite.frag",
+            include_str!("shaders/sprite.frag"),
+            shaderc::ShaderKind::Fragment,
+        );
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Sprite Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex_stage: wgpu::ProgrammableStageDescriptor {
+                module: &vs_module,
+                entry_point: "main",
+            },
+            fragment_stage: Some(wgpu::ProgrammableStageDescriptor {
+                module: &fs_module,
+                entry_point: "main",
+            }),
+            rasterization_state: Some(wgpu::RasterizationStateDescriptor::default()),
+            primitive_topology: wgpu::PrimitiveTopology::TriangleList,
+            color_states: &[wgpu::ColorStateDescriptor {
+                format,
+                color_blend: wgpu::BlendDescriptor {
+                    src_factor: wgpu::BlendFactor::SrcAlpha,
+                    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha_blend: wgpu::BlendDescriptor {
+                    src_factor: wgpu::BlendFactor::One,
+                    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                write_mask: wgpu::ColorWrite::ALL,
+            }],
+            depth_stencil_state: None,
+            vertex_state: wgpu::VertexStateDescriptor {
+                index_format: wgpu::IndexFormat::Uint16,
+                vertex_buffers: &[wgpu::VertexBufferDescriptor {
+                    stride: std::mem::size_of::<Instance>() as wgpu::BufferAddress,
+                    step_mode: wgpu::InputStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![
+                        0 => Float2,
+                        1 => Float2,
+                        2 => Float4,
+                        3 => Float4,
+                        4 => Float
+                    ],
+                }],
+            },
+            sample_count: 1,
+            sample_mask: !0,
+            alpha_to_coverage_enabled: false,
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Sprite Sampler"),
+            ..Default::default()
+        });
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Sprite Uniforms"),
+            size: std::mem::size_of::<[f32; 4]>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Sprite Uniform Bind Group"),
+            layout: &uniform_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer(uniform_buffer.slice(..)),
+            }],
+        });
+
+        Self {
+            atlases: Vec::new(),
+            batch: SpriteBatch::new(device),
+            pipeline,
+            atlas_layout,
+            sampler,
+            uniform_buffer,
+            uniform_bind_group,
+        }
+    }
+
+    pub fn add_atlas(&mut self, device: &wgpu::Device) -> usize {
+        self.atlases
+            .push(Atlas::new(device, &self.atlas_layout, &self.sampler));
+        self.atlases.len() - 1
+    }
+
+    pub fn insert(
+        &mut self,
+        atlas: usize,
+        width: u32,
+        height: u32,
+        pixels: &[u8],
+    ) -> Option<Region> {
+        self.atlases[atlas].insert(atlas, width, height, pixels)
+    }
+
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+        viewport: egui::Rect,
+    ) {
+        for atlas in self.atlases.iter_mut() {
+            atlas.upload(device, encoder);
+        }
+        let screen = [viewport.width(), viewport.height(), 0.0, 0.0];
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&screen),
+        );
+        self.batch.prepare(device, uploader, encoder);
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+        crate::blit::set_viewport(&mut render_pass, viewport);
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.batch.instance_buffer.slice(..));
+        for batch in self.batch.batches.iter() {
+            render_pass.set_bind_group(1, &self.atlases[batch.atlas].bind_group, &[]);
+            render_pass.draw(0..6, batch.instances.clone());
+        }
+    }
+}
+
+fn frame_pixels(kind: u32, frame: u32) -> Vec<u8> {
+    let mut pixels = Vec::with_capacity((FRAME_SIZE * FRAME_SIZE * 4) as usize);
+    let phase = frame as f32 / FRAMES as f32 * 2.0 * std::f32::consts::PI;
+    for y in 0..FRAME_SIZE {
+        for x in 0..FRAME_SIZE {
+            let u = (x as f32 + 0.5) / FRAME_SIZE as f32 * 2.0 - 1.0;
+            let v = (y as f32 + 0.5) / FRAME_SIZE as f32 * 2.0 - 1.0;
+            let (inside, shade) = match kind {
+                0 => {
+                    let width = phase.cos().abs().max(0.15);
+                    let d = (u / width).powi(2) + v * v;
+                    (d < 0.8, 1.0 - d * 0.4)
+                }
+                1 => {
+                    let radius = 0.55 + 0.2 * phase.sin();
+                    let d = (u * u + v * v).sqrt();
+                    ((d - radius).abs() < 0.18, 1.0 - (d - radius).abs() * 3.0)
+                }
+                2 => {
+                    let squash = 1.0 + 0.25 * phase.sin();
+                    let (bu, bv) = (u * squash, (v - 0.2) / squash);
+                    let d = bu * bu + bv * bv;
+                    (d < 0.6 && v > -0.7, 1.0 - d * 0.5)
+                }
+                _ => {
+                    let angle = v.atan2(u) + phase / 5.0;
+                    let radius = 0.55 + 0.3 * (angle * 5.0).cos();
+                    let d = (u * u + v * v).sqrt();
+                    (d < radius, 1.0 - d * 0.4)
+                }
+            };
+            let value = (shade.clamp(0.0, 1.0) * 255.0) as u8;
+            pixels.extend_from_slice(&if inside {
+                [value, value, value, 255]
+            } else {
+                [0, 0, 0, 0]
+            });
+        }
+    }
+    pixels
+}
+
+struct Animation {
+    frames: Vec<Region>,
+    tint: [f32; 4],
+}
+
+struct Agent {
+    position: [f32; 2],
+    velocity: [f32; 2],
+    rotation: f32,
+    spin: f32,
+    size: f32,
+    animation: usize,
+    phase: f32,
+    layer: u32,
+}
+
+pub struct Settings {
+    pub count: u32,
+    pub animate: bool,
+    pub sort: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            count: COUNTS[1],
+            animate: true,
+            sort: true,
+        }
+    }
+}
+
+pub struct SpriteDemo {
+    pub open: bool,
+    pub settings: Settings,
+    renderer: SpriteRenderer,
+    animations: Vec<Animation>,
+    agents: Vec<Agent>,
+    seed: u32,
+    time: f32,
+    viewport: Option<egui::Rect>,
+    canvas: crate::blit::Canvas,
+    last_frame: Option<std::time::Instant>,
+}
+
+impl SpriteDemo {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let mut renderer = SpriteRenderer::new(device, format);
+        let tints = [
+            [1.0, 0.85, 0.3, 1.0],
+            [0.4, 0.8, 1.0, 1.0],
+            [0.45, 0.9, 0.4, 1.0],
+            [1.0, 0.5, 0.7, 1.0],
+        ];
+        let mut animations = Vec::new();
+        for kind in 0..4 {
+            if kind % 2 == 0 {
+                renderer.add_atlas(device);
+            }
+            let atlas = renderer.atlases.len() - 1;
+            let frames = (0..FRAMES)
+                .map(|frame| {
+                    renderer
+                        .insert(atlas, FRAME_SIZE, FRAME_SIZE, &frame_pixels(kind, frame))
+                        .unwrap()
+                })
+                .collect();
+            animations.push(Animation {
+                frames,
+                tint: tints[kind as usize],
+            });
+        }
+
+        Self {
+            open: false,
+            settings: Settings::default(),
+            renderer,
+            animations,
+            agents: Vec::new(),
+            seed: 1,
+            time: 0.0,
+            viewport: None,
+            canvas: crate::blit::Canvas::new(format),
+            last_frame: None,
+        }
+    }
+
+    pub fn recreate_gpu_resources(&mut self, device: &wgpu::Device, format: wgpu::TextureFormat) {
+        let settings = std::mem::take(&mut self.settings);
+        let open = self.open;
+        *self = Self::new(device, format);
+        self.settings = settings;
+        self.open = open;
+    }
+
+    fn random(&mut self) -> f32 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+        self.seed as f32 / u32::MAX as f32
+    }
+
+    fn spawn(&mut self) {
+        self.agents.clear();
+        for _ in 0..self.settings.count {
+            let angle = self.random() * 2.0 * std::f32::consts::PI;
+            let speed = 20.0 + self.random() * 80.0;
+            let agent = Agent {
+                position: [self.random() * VIEW_SIZE[0], self.random() * VIEW_SIZE[1]],
+                velocity: [angle.cos() * speed, angle.sin() * speed],
+                rotation: 0.0,
+                spin: (self.random() - 0.5) * 4.0,
+                size: 8.0 + self.random() * 16.0,
+                animation: (self.random() * self.animations.len() as f32) as usize
+                    % self.animations.len(),
+                phase: self.random() * FRAMES as f32,
+                layer: (self.random() * LAYERS as f32) as u32 % LAYERS,
+            };
+            self.agents.push(agent);
+        }
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        let mut open = self.open;
+        let mut viewport = None;
+        let stats = self.renderer.batch.stats;
+        let atlases = self.renderer.atlases.len();
+        let settings = &mut self.settings;
+        let canvas = &self.canvas;
+        crate::dock::Panel::new("Sprites")
+            .open(&mut open)
+            .default_pos(egui::pos2(380.0, 60.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Sprites");
+                    for candidate in COUNTS.iter() {
+                        ui.radio_value(&mut settings.count, *candidate, candidate.to_string());
+                    }
+                });
+                ui.checkbox(&mut settings.animate, "Animate");
+                ui.checkbox(&mut settings.sort, "Sort by layer and atlas");
+                ui.label(format!(
+                    "{} sprites from {} atlases in {} layers",
+                    stats.sprites, atlases, LAYERS
+                ));
+                ui.label(format!(
+                    "{} batches / draw calls ({} in submission order)",
+                    stats.batches, stats.unsorted_batches
+                ));
+                ui.label(format!(
+                    "{:.1} KiB of instance data per frame",
+                    stats.upload_bytes as f32 / 1024.0
+                ));
+                let rect = ui.allocate_space(egui::vec2(VIEW_SIZE[0], VIEW_SIZE[1]));
+                canvas.paint(ui, rect);
+                viewport = Some(rect);
+            });
+        self.open = open;
+        self.viewport = viewport.filter(|_| open);
+    }
+
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        ui_pass: &mut crate::ui_pass::RenderPass,
+        size: winit::dpi::PhysicalSize<u32>,
+        scale_factor: f64,
+    ) {
+        let now = std::time::Instant::now();
+        let dt = self
+            .last_frame
+            .map(|last| (now - last).as_secs_f32())
+            .unwrap_or(0.0)
+            .min(0.1);
+        self.last_frame = Some(now);
+        let viewport = match self.viewport {
+            Some(rect) => crate::blit::to_physical(rect, scale_factor),
+            None => return,
+        };
+        if !crate::blit::fits(viewport, size) {
+            return;
+        }
+        if self.agents.len() != self.settings.count as usize {
+            self.spawn();
+        }
+
+        let dt = if self.settings.animate { dt } else { 0.0 };
+        self.time += dt;
+        let scale = viewport.width() / VIEW_SIZE[0];
+        for agent in self.agents.iter_mut() {
+            for (axis, size) in VIEW_SIZE.iter().enumerate() {
+                agent.position[axis] += agent.velocity[axis] * dt;
+                if agent.position[axis] < 0.0 || agent.position[axis] > *size {
+                    agent.velocity[axis] = -agent.velocity[axis];
+                    agent.position[axis] = agent.position[axis].clamp(0.0, *size);
+                }
+            }
+            agent.rotation += agent.spin * dt;
+            let animation = &self.animations[agent.animation];
+            let frame = (self.time * 10.0 + agent.phase) as usize % animation.frames.len();
+            let color = animation.tint;
+            self.renderer.batch.push(Sprite {
+                position: [agent.position[0] * scale, agent.position[1] * scale],
+                size: [agent.size * scale, agent.size * scale],
+                rotation: agent.rotation,
+                color: [
+                    color[0] * (0.6 + 0.1 * agent.layer as f32),
+                    color[1] * (0.6 + 0.1 * agent.layer as f32),
+                    color[2] * (0.6 + 0.1 * agent.layer as f32),
+                    color[3],
+                ],
+                region: animation.frames[frame],
+                layer: agent.layer,
+            });
+        }
+        self.renderer.batch.sort = self.settings.sort;
+        let target = self.canvas.begin(device, encoder, ui_pass, size);
+        self.renderer
+            .render(device, uploader, encoder, target, viewport);
+    }
+}
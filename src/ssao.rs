@@ -0,0 +1,263 @@
+const AO_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R8Unorm;
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    projection: [f32; 16],
+    inverse_projection: [f32; 16],
+    radius: f32,
+    intensity: f32,
+    bias: f32,
+    padding: f32,
+}
+
+pub struct Settings {
+    pub enabled: bool,
+    pub radius: f32,
+    pub intensity: f32,
+    pub debug: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            radius: 0.5,
+            intensity: 1.5,
+            debug: false,
+        }
+    }
+}
+
+pub struct Ssao {
+    pub settings: Settings,
+    pub encode_time: f32,
+    layout: wgpu::BindGroupLayout,
+    uniform_buffer: wgpu::Buffer,
+    sampler: wgpu::Sampler,
+    ao_pipeline: wgpu::RenderPipeline,
+    apply: crate::blit::Blitter,
+    debug: crate::blit::Blitter,
+    bind_group: wgpu::BindGroup,
+    ao_view: wgpu::TextureView,
+    apply_bind_group: wgpu::BindGroup,
+}
+
+impl Ssao {
+    pub fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        size: winit::dpi::PhysicalSize<u32>,
+        depth_view: &wgpu::TextureView,
+    ) -> Self {
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("SSAO Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::SampledTexture {
+                        dimension: wgpu::TextureViewDimension::D2,
+                        component_type: wgpu::TextureComponentType::Float,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler { comparison: false },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::UniformBuffer {
+                        dynamic: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let ao_pipeline = crate::blit::fullscreen_pipeline(
+            device,
+            "SSAO Pipeline",
+            &[&layout],
+            "ssao.frag",
+            include_str!("shaders/ssao.frag"),
+            AO_FORMAT,
+            wgpu::BlendDescriptor::REPLACE,
+        );
+        let apply = crate::blit::Blitter::with_blend(
+            device,
+            format,
+            "ssao_apply.frag",
+            include_str!("shaders/ssao_apply.frag"),
+            wgpu::BlendDescriptor {
+                src_factor: wgpu::BlendFactor::Zero,
+                dst_factor: wgpu::BlendFactor::SrcColor,
+                operation: wgpu::BlendOperation::Add,
+            },
+        );
+        let debug = crate::blit::Blitter::new(
+            device,
+            format,
+            "ssao_apply.frag",
+            include_str!("shaders/ssao_apply.frag"),
+        );
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("SSAO Uniforms"),
+            size: std::mem::size_of::<Uniforms>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("SSAO Depth Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            ..Default::default()
+        });
+        let bind_group = create_bind_group(device, &layout, &sampler, &uniform_buffer, depth_view);
+        let ao_view = create_ao_view(device, size);
+        let apply_bind_group = apply.bind(device, &ao_view);
+
+        Self {
+            settings: Settings::default(),
+            encode_time: 0.0,
+            layout,
+            uniform_buffer,
+            sampler,
+            ao_pipeline,
+            apply,
+            debug,
+            bind_group,
+            ao_view,
+            apply_bind_group,
+        }
+    }
+
+    pub fn resize(
+        &mut self,
+        device: &wgpu::Device,
+        size: winit::dpi::PhysicalSize<u32>,
+        depth_view: &wgpu::TextureView,
+    ) {
+        self.bind_group = create_bind_group(
+            device,
+            &self.layout,
+            &self.sampler,
+            &self.uniform_buffer,
+            depth_view,
+        );
+        self.ao_view = create_ao_view(device, size);
+        self.apply_bind_group = self.apply.bind(device, &self.ao_view);
+    }
+
+    pub fn ao_view(&self) -> &wgpu::TextureView {
+        &self.ao_view
+    }
+
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        projection: &glam::Mat4,
+        target: &wgpu::TextureView,
+    ) {
+        self.encode_time = 0.0;
+        if !self.settings.enabled {
+            return;
+        }
+        let start = std::time::Instant::now();
+
+        let uniforms = Uniforms {
+            projection: projection.to_cols_array(),
+            inverse_projection: projection.inverse().to_cols_array(),
+            radius: self.settings.radius,
+            intensity: self.settings.intensity,
+            bias: 0.025,
+            padding: 0.0,
+        };
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: &self.ao_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::WHITE),
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+        render_pass.set_pipeline(&self.ao_pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+        drop(render_pass);
+
+        if self.settings.debug {
+            self.debug.draw(encoder, &self.apply_bind_group, target);
+        } else {
+            self.apply
+                .draw_over(encoder, &self.apply_bind_group, target);
+        }
+
+        self.encode_time = start.elapsed().as_secs_f32();
+    }
+}
+
+fn create_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    sampler: &wgpu::Sampler,
+    uniform_buffer: &wgpu::Buffer,
+    depth_view: &wgpu::TextureView,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("SSAO Bind Group"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(depth_view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: wgpu::BindingResource::Buffer(uniform_buffer.slice(..)),
+            },
+        ],
+    })
+}
+
+fn create_ao_view(device: &wgpu::Device, size: winit::dpi::PhysicalSize<u32>) -> wgpu::TextureView {
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("SSAO"),
+            size: wgpu::Extent3d {
+                width: size.width.max(1),
+                height: size.height.max(1),
+                depth: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: AO_FORMAT,
+            usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::SAMPLED,
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
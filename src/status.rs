@@ -0,0 +1,92 @@
+pub const HEIGHT: f32 = 24.0;
+const MESSAGE_SECONDS: f32 = 4.0;
+const BACKGROUND: egui::Srgba = egui::Srgba::new(30, 30, 34, 240);
+const HINT_COLOR: egui::Srgba = egui::Srgba::new(150, 150, 160, 255);
+
+#[derive(Default)]
+struct State {
+    messages: Vec<(String, std::time::Instant)>,
+    hint: Option<String>,
+}
+
+thread_local! {
+    static STATE: std::cell::RefCell<State> = std::cell::RefCell::new(State::default());
+}
+
+pub fn message(text: impl Into<String>) {
+    STATE.with(|state| {
+        state
+            .borrow_mut()
+            .messages
+            .push((text.into(), std::time::Instant::now()))
+    });
+}
+
+pub fn hint(text: impl Into<String>) {
+    STATE.with(|state| state.borrow_mut().hint = Some(text.into()));
+}
+
+pub fn hint_on_hover(response: &egui::Response, text: &str) {
+    if response.hovered {
+        hint(text);
+    }
+}
+
+pub fn show(
+    ctx: &std::sync::Arc<egui::Context>,
+    screen: egui::Vec2,
+    frame_time: f32,
+    tool: Option<&str>,
+    extra: impl FnOnce(&mut egui::Ui),
+) {
+    let (message, hint) = STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state
+            .messages
+            .retain(|(_, time)| time.elapsed().as_secs_f32() < MESSAGE_SECONDS);
+        (
+            state.messages.last().map(|(text, _)| text.clone()),
+            state.hint.take(),
+        )
+    });
+    let cursor = ctx.input().mouse.pos;
+    let rect = egui::Rect::from_min_size(
+        egui::pos2(0.0, screen.y - HEIGHT),
+        egui::vec2(screen.x, HEIGHT),
+    );
+    egui::Area::new("status_bar")
+        .fixed_pos(rect.min)
+        .show(ctx, |ui| {
+            ui.painter().rect_filled(rect, 0.0, BACKGROUND);
+            ui.horizontal(|ui| {
+                ui.label(if frame_time > 0.0 {
+                    format!("{:.0} FPS", 1.0 / frame_time)
+                } else {
+                    "-- FPS".to_owned()
+                });
+                ui.label("|");
+                ui.label(match cursor {
+                    Some(cursor) => format!("{:.0}, {:.0}", cursor.x, cursor.y),
+                    None => "-, -".to_owned(),
+                });
+                if let Some(tool) = tool {
+                    ui.label("|");
+                    ui.label(format!("Tool: {}", tool));
+                }
+                ui.label("|");
+                extra(ui);
+                ui.label("|");
+                match (message, hint) {
+                    (Some(message), _) => {
+                        ui.label(message);
+                    }
+                    (None, Some(hint)) => {
+                        ui.add(egui::Label::new(hint).text_color(HINT_COLOR));
+                    }
+                    (None, None) => {
+                        ui.add(egui::Label::new("Ready").text_color(HINT_COLOR));
+                    }
+                }
+            });
+        });
+}
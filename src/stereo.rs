@@ -0,0 +1,54 @@
+pub struct Stereo {
+    pub enabled: bool,
+    pub eye_separation: f32,
+    pub swap_eyes: bool,
+}
+
+impl Default for Stereo {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            eye_separation: 0.065,
+            swap_eyes: false,
+        }
+    }
+}
+
+pub struct Eye {
+    pub offset: glam::Vec3,
+    pub viewport: egui::Rect,
+}
+
+impl Stereo {
+    pub fn eye_size(size: winit::dpi::PhysicalSize<u32>) -> winit::dpi::PhysicalSize<u32> {
+        winit::dpi::PhysicalSize::new((size.width / 2).max(1), size.height)
+    }
+
+    pub fn eyes(
+        &self,
+        camera: &crate::scene::Camera,
+        size: winit::dpi::PhysicalSize<u32>,
+    ) -> [Eye; 2] {
+        let forward = (camera.target - camera.eye()).normalize();
+        let right = forward.cross(glam::Vec3::unit_y()).normalize();
+        let half = right * self.eye_separation * 0.5;
+        let eye_size = Self::eye_size(size);
+        let viewport = |column: u32| {
+            egui::Rect::from_min_size(
+                egui::pos2((column * eye_size.width) as f32, 0.0),
+                egui::vec2(eye_size.width as f32, eye_size.height as f32),
+            )
+        };
+        let (left, right) = if self.swap_eyes { (1, 0) } else { (0, 1) };
+        [
+            Eye {
+                offset: -half,
+                viewport: viewport(left),
+            },
+            Eye {
+                offset: half,
+                viewport: viewport(right),
+            },
+        ]
+    }
+}
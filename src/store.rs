@@ -0,0 +1,110 @@
+#[derive(Default)]
+pub struct Value<T> {
+    value: T,
+    version: u64,
+}
+
+impl<T> Value<T> {
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+}
+
+impl<T> std::ops::Deref for Value<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+pub struct Derived<T> {
+    value: Option<T>,
+    inputs: Vec<u64>,
+}
+
+impl<T> Default for Derived<T> {
+    fn default() -> Self {
+        Self {
+            value: None,
+            inputs: Vec::new(),
+        }
+    }
+}
+
+impl<T> Derived<T> {
+    pub fn get(&mut self, inputs: &[u64], compute: impl FnOnce() -> T) -> &T {
+        if self.value.is_none() || self.inputs != inputs {
+            self.value = Some(compute());
+            self.inputs = inputs.to_vec();
+        }
+        self.value.as_ref().unwrap()
+    }
+}
+
+#[derive(Default)]
+pub struct Watch {
+    seen: Option<Vec<u64>>,
+}
+
+impl Watch {
+    pub fn changed(&mut self, inputs: &[u64]) -> bool {
+        if self.seen.as_deref() == Some(inputs) {
+            return false;
+        }
+        self.seen = Some(inputs.to_vec());
+        true
+    }
+
+    pub fn reset(&mut self) {
+        self.seen = None;
+    }
+}
+
+pub fn bind<T, F: Clone + PartialEq, R>(
+    value: &mut Value<T>,
+    field: impl Fn(&mut T) -> &mut F,
+    edit: impl FnOnce(&mut F) -> R,
+) -> R {
+    let mut edited = field(&mut value.value).clone();
+    let result = edit(&mut edited);
+    let current = field(&mut value.value);
+    if *current != edited {
+        *current = edited;
+        value.version += 1;
+    }
+    result
+}
+
+pub fn slider<T>(
+    ui: &mut egui::Ui,
+    value: &mut Value<T>,
+    field: impl Fn(&mut T) -> &mut f32,
+    range: std::ops::RangeInclusive<f32>,
+    text: &str,
+) -> egui::Response {
+    bind(value, field, |number| {
+        ui.add(egui::Slider::f32(number, range).text(text))
+    })
+}
+
+pub fn checkbox<T>(
+    ui: &mut egui::Ui,
+    value: &mut Value<T>,
+    field: impl Fn(&mut T) -> &mut bool,
+    text: &str,
+) -> egui::Response {
+    bind(value, field, |checked| ui.checkbox(checked, text))
+}
+
+pub fn radio<T, F: Clone + PartialEq>(
+    ui: &mut egui::Ui,
+    value: &mut Value<T>,
+    field: impl Fn(&mut T) -> &mut F,
+    option: F,
+    text: impl Into<String>,
+) -> egui::Response {
+    bind(value, field, |current| {
+        ui.radio_value(current, option, text)
+    })
+}
@@ -0,0 +1,303 @@
+const TEXT_STYLES: [(egui::TextStyle, &str); 5] = [
+    (egui::TextStyle::Small, "small"),
+    (egui::TextStyle::Body, "body"),
+    (egui::TextStyle::Button, "button"),
+    (egui::TextStyle::Heading, "heading"),
+    (egui::TextStyle::Monospace, "monospace"),
+];
+
+pub fn srgba([r, g, b, a]: [u8; 4]) -> egui::Srgba {
+    egui::Srgba::new(r, g, b, a)
+}
+
+#[derive(Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WidgetTheme {
+    pub fill: [u8; 4],
+    pub text: [u8; 4],
+    pub rounding: f32,
+}
+
+#[derive(Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Theme {
+    pub item_spacing: [f32; 2],
+    pub button_padding: [f32; 2],
+    pub window_padding: [f32; 2],
+    pub indent: f32,
+    pub window_rounding: f32,
+    pub background: [u8; 4],
+    pub inactive: WidgetTheme,
+    pub hovered: WidgetTheme,
+    pub active: WidgetTheme,
+    pub font_sizes: [f32; 5],
+}
+
+impl Theme {
+    pub fn path() -> Option<std::path::PathBuf> {
+        dirs::config_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")).join("theme.json"))
+    }
+
+    pub fn from_context(ctx: &egui::Context) -> Self {
+        let style = ctx.style();
+        let spacing = &style.spacing;
+        let visuals = &style.visuals;
+        let widgets = &visuals.widgets;
+        let [inactive, hovered, active] = [&widgets.inactive, &widgets.hovered, &widgets.active]
+            .map(|state| WidgetTheme {
+                fill: state.bg_fill.to_array(),
+                text: state.fg_stroke.color.to_array(),
+                rounding: state.corner_radius,
+            });
+        Self {
+            item_spacing: [spacing.item_spacing.x, spacing.item_spacing.y],
+            button_padding: [spacing.button_padding.x, spacing.button_padding.y],
+            window_padding: [spacing.window_padding.x, spacing.window_padding.y],
+            indent: spacing.indent,
+            window_rounding: visuals.window_corner_radius,
+            background: visuals.dark_bg_color.to_array(),
+            inactive,
+            hovered,
+            active,
+            font_sizes: font_sizes(ctx),
+        }
+    }
+
+    pub fn apply(&self, ctx: &egui::Context) {
+        let mut style = (*ctx.style()).clone();
+        let spacing = &mut style.spacing;
+        spacing.item_spacing = egui::vec2(self.item_spacing[0], self.item_spacing[1]);
+        spacing.button_padding = egui::vec2(self.button_padding[0], self.button_padding[1]);
+        spacing.window_padding = egui::vec2(self.window_padding[0], self.window_padding[1]);
+        spacing.indent = self.indent;
+        let visuals = &mut style.visuals;
+        visuals.window_corner_radius = self.window_rounding;
+        visuals.dark_bg_color = srgba(self.background);
+        let widgets = &mut visuals.widgets;
+        let mut states = [
+            (&mut widgets.inactive, self.inactive),
+            (&mut widgets.hovered, self.hovered),
+            (&mut widgets.active, self.active),
+        ];
+        for (state, theme) in states.iter_mut() {
+            state.bg_fill = srgba(theme.fill);
+            state.fg_stroke.color = srgba(theme.text);
+            state.corner_radius = theme.rounding;
+        }
+        ctx.set_style(style);
+
+        if self.font_sizes != font_sizes(ctx) {
+            let mut definitions = ctx.fonts().definitions().clone();
+            for (size, (text_style, _)) in self.font_sizes.iter().zip(TEXT_STYLES.iter()) {
+                if let Some(font) = definitions.fonts.get_mut(text_style) {
+                    font.1 = *size;
+                }
+            }
+            ctx.set_fonts(definitions);
+        }
+    }
+
+    pub fn load(path: &std::path::Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+        serde_json::from_str(&contents).map_err(|err| err.to_string())
+    }
+
+    pub fn save(&self, path: &std::path::Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self).unwrap())
+            .map_err(|err| err.to_string())
+    }
+}
+
+fn set_light_visuals(style: &mut egui::Style) {
+    let visuals = &mut style.visuals;
+    visuals.dark_bg_color = egui::Srgba::new(235, 235, 235, 255);
+    let widgets = &mut visuals.widgets;
+    let mut states = [
+        (&mut widgets.noninteractive, [248, 248, 248], [70, 70, 70]),
+        (&mut widgets.inactive, [222, 222, 222], [40, 40, 40]),
+        (&mut widgets.hovered, [205, 205, 205], [0, 0, 0]),
+        (&mut widgets.active, [185, 185, 185], [0, 0, 0]),
+    ];
+    for (state, [r, g, b], [tr, tg, tb]) in states.iter_mut() {
+        state.bg_fill = egui::Srgba::new(*r, *g, *b, 255);
+        state.fg_stroke.color = egui::Srgba::new(*tr, *tg, *tb, 255);
+    }
+}
+
+fn font_sizes(ctx: &egui::Context) -> [f32; 5] {
+    let definitions = ctx.fonts().definitions();
+    let mut sizes = [0.0; 5];
+    for (size, (text_style, _)) in sizes.iter_mut().zip(TEXT_STYLES.iter()) {
+        *size = definitions
+            .fonts
+            .get(text_style)
+            .map_or(14.0, |(_, size)| *size);
+    }
+    sizes
+}
+
+fn color_row(ui: &mut egui::Ui, label: &str, color: &mut [u8; 4]) {
+    ui.horizontal(|ui| {
+        let mut picked = srgba(*color);
+        egui::color_picker::color_edit_button_srgba(ui, &mut picked);
+        *color = picked.to_array();
+        ui.label(label);
+    });
+}
+
+fn vec2_sliders(ui: &mut egui::Ui, label: &str, value: &mut [f32; 2], max: f32) {
+    ui.add(egui::Slider::f32(&mut value[0], 0.0..=max).text(format!("{} x", label)));
+    ui.add(egui::Slider::f32(&mut value[1], 0.0..=max).text(format!("{} y", label)));
+}
+
+fn widget_section(ui: &mut egui::Ui, title: &str, widget: &mut WidgetTheme) {
+    egui::CollapsingHeader::new(title).show(ui, |ui| {
+        color_row(ui, "fill", &mut widget.fill);
+        color_row(ui, "text", &mut widget.text);
+        ui.add(egui::Slider::f32(&mut widget.rounding, 0.0..=12.0).text("rounding"));
+    });
+}
+
+pub struct StyleEditor {
+    pub open: bool,
+    path: String,
+    theme: Option<Theme>,
+    original: Option<Theme>,
+    dark_mode: Option<bool>,
+    status: String,
+}
+
+impl Default for StyleEditor {
+    fn default() -> Self {
+        Self {
+            open: false,
+            path: Theme::path()
+                .map(|path| path.display().to_string())
+                .unwrap_or_else(|| "theme.json".to_owned()),
+            theme: None,
+            original: None,
+            dark_mode: None,
+            status: String::new(),
+        }
+    }
+}
+
+impl StyleEditor {
+    pub fn set_dark_mode(&mut self, ctx: &std::sync::Arc<egui::Context>, dark: bool) {
+        if self.dark_mode == Some(dark) {
+            return;
+        }
+        let mut style = (*ctx.style()).clone();
+        style.visuals = egui::Style::default().visuals;
+        if !dark {
+            set_light_visuals(&mut style);
+        }
+        ctx.set_style(style);
+        if self.dark_mode.is_some() {
+            self.theme = Some(Theme::from_context(ctx));
+            self.original = self.theme.clone();
+        }
+        self.dark_mode = Some(dark);
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        if self.theme.is_none() {
+            let original = Theme::from_context(ctx);
+            let path = std::path::PathBuf::from(&self.path);
+            let theme = if path.exists() {
+                match Theme::load(&path) {
+                    Ok(theme) => {
+                        log::info!("loaded theme from {}", path.display());
+                        theme.apply(ctx);
+                        theme
+                    }
+                    Err(err) => {
+                        log::warn!("ignoring invalid theme in {}: {}", path.display(), err);
+                        original.clone()
+                    }
+                }
+            } else {
+                original.clone()
+            };
+            self.original = Some(original);
+            self.theme = Some(theme);
+        }
+
+        let mut open = self.open;
+        let theme = self.theme.as_mut().unwrap();
+        let original = self.original.as_ref().unwrap();
+        let path = &mut self.path;
+        let status = &mut self.status;
+        let before = theme.clone();
+        crate::dock::Panel::new("Style")
+            .open(&mut open)
+            .default_pos(egui::pos2(300.0, 120.0))
+            .show(ctx, |ui| {
+                egui::CollapsingHeader::new("Spacing")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        vec2_sliders(ui, "item spacing", &mut theme.item_spacing, 20.0);
+                        vec2_sliders(ui, "button padding", &mut theme.button_padding, 20.0);
+                        vec2_sliders(ui, "window padding", &mut theme.window_padding, 30.0);
+                        ui.add(egui::Slider::f32(&mut theme.indent, 0.0..=40.0).text("indent"));
+                    });
+                egui::CollapsingHeader::new("Rounding").show(ui, |ui| {
+                    ui.add(
+                        egui::Slider::f32(&mut theme.window_rounding, 0.0..=20.0)
+                            .text("window rounding"),
+                    );
+                });
+                egui::CollapsingHeader::new("Colors").show(ui, |ui| {
+                    color_row(ui, "background", &mut theme.background);
+                });
+                widget_section(ui, "Inactive widgets", &mut theme.inactive);
+                widget_section(ui, "Hovered widgets", &mut theme.hovered);
+                widget_section(ui, "Active widgets", &mut theme.active);
+                egui::CollapsingHeader::new("Font sizes").show(ui, |ui| {
+                    for (size, (_, name)) in theme.font_sizes.iter_mut().zip(TEXT_STYLES.iter()) {
+                        ui.add(egui::Slider::f32(size, 6.0..=40.0).text(*name));
+                    }
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("File");
+                    ui.add(egui::TextEdit::new(path));
+                });
+                ui.horizontal(|ui| {
+                    if ui.button("Save").clicked {
+                        let file = std::path::PathBuf::from(path.as_str());
+                        *status = match theme.save(&file) {
+                            Ok(()) => {
+                                log::info!("saved theme to {}", file.display());
+                                format!("Saved to {}", file.display())
+                            }
+                            Err(err) => format!("Cannot save {}: {}", file.display(), err),
+                        };
+                    }
+                    if ui.button("Load").clicked {
+                        let file = std::path::PathBuf::from(path.as_str());
+                        *status = match Theme::load(&file) {
+                            Ok(loaded) => {
+                                *theme = loaded;
+                                format!("Loaded {}", file.display())
+                            }
+                            Err(err) => format!("Cannot load {}: {}", file.display(), err),
+                        };
+                    }
+                    if ui.button("Reset").clicked {
+                        *theme = original.clone();
+                        status.clear();
+                    }
+                });
+                if !status.is_empty() {
+                    ui.label(status.as_str());
+                }
+            });
+        if *theme != before {
+            theme.apply(ctx);
+        }
+        self.open = open;
+    }
+}
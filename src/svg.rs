@@ -0,0 +1,139 @@
+const ICONS: [(&str, &str); 6] = [
+    ("Home", include_str!("icons/home.svg")),
+    ("Search", include_str!("icons/search.svg")),
+    ("Settings", include_str!("icons/settings.svg")),
+    ("Favorite", include_str!("icons/heart.svg")),
+    ("Starred", include_str!("icons/star.svg")),
+    ("Folder", include_str!("icons/folder.svg")),
+];
+
+fn demultiply(pixel: &[u8]) -> [u8; 4] {
+    let alpha = pixel[3] as u32;
+    if alpha == 0 {
+        return [0, 0, 0, 0];
+    }
+    let channel = |value: u8| ((value as u32 * 255 + alpha / 2) / alpha).min(255) as u8;
+    [
+        channel(pixel[0]),
+        channel(pixel[1]),
+        channel(pixel[2]),
+        pixel[3],
+    ]
+}
+
+pub fn rasterize(tree: &usvg::Tree, size: [u32; 2]) -> Result<image::RgbaImage, String> {
+    let [width, height] = [size[0].max(1), size[1].max(1)];
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)
+        .ok_or_else(|| format!("cannot allocate a {} x {} pixmap", width, height))?;
+    resvg::render(tree, usvg::FitTo::Width(width), pixmap.as_mut())
+        .ok_or_else(|| "the document has nothing to render".to_owned())?;
+    let data = pixmap.data().chunks(4).flat_map(demultiply).collect();
+    image::RgbaImage::from_raw(width, height, data)
+        .ok_or_else(|| "the pixmap has an unexpected size".to_owned())
+}
+
+pub struct SvgImage {
+    name: String,
+    tree: usvg::Tree,
+    texture: Option<([u32; 2], usize)>,
+    failed: Option<[u32; 2]>,
+    layout: Option<egui::Rect>,
+}
+
+impl SvgImage {
+    pub fn parse(name: impl Into<String>, data: &[u8]) -> Result<Self, String> {
+        let tree = usvg::Tree::from_data(data, &usvg::Options::default())
+            .map_err(|err| err.to_string())?;
+        Ok(Self {
+            name: name.into(),
+            tree,
+            texture: None,
+            failed: None,
+            layout: None,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn aspect(&self) -> f32 {
+        let size = self.tree.svg_node().size;
+        (size.width() / size.height().max(1.0)) as f32
+    }
+
+    pub fn paint(&mut self, ui: &mut egui::Ui, height: f32) -> egui::Response {
+        let rect = ui.allocate_space(egui::vec2(height * self.aspect(), height));
+        self.layout = Some(rect);
+        if let Some((size, id)) = self.texture {
+            let pixels_per_point = ui.ctx().pixels_per_point();
+            let snap = |value: f32| (value * pixels_per_point).round() / pixels_per_point;
+            crate::ui_pass::paint_user_texture(
+                ui.painter(),
+                id,
+                egui::Rect::from_min_size(
+                    egui::pos2(snap(rect.min.x), snap(rect.min.y)),
+                    egui::vec2(size[0] as f32, size[1] as f32) / pixels_per_point,
+                ),
+                egui::color::WHITE,
+            );
+        }
+        ui.interact(rect, ui.id().with(&self.name), egui::Sense::nothing())
+    }
+
+    pub fn recreate_gpu_resources(&mut self) {
+        self.texture = None;
+    }
+
+    pub fn upload(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        ui_pass: &mut crate::ui_pass::RenderPass,
+        pixels_per_point: f32,
+    ) {
+        let rect = match self.layout.take() {
+            Some(layout) => layout,
+            None => return,
+        };
+        let size = [
+            (rect.width() * pixels_per_point).round().max(1.0) as u32,
+            (rect.height() * pixels_per_point).round().max(1.0) as u32,
+        ];
+        if self.failed == Some(size) {
+            return;
+        }
+        if matches!(self.texture, Some((rasterized, _)) if rasterized == size) {
+            return;
+        }
+        if let Some((_, id)) = self.texture.take() {
+            ui_pass.free_user_texture(id);
+        }
+        match rasterize(&self.tree, size) {
+            Ok(image) => {
+                let id = ui_pass.register_user_texture(device, queue, &image);
+                self.texture = Some((size, id));
+                self.failed = None;
+            }
+            Err(err) => {
+                log::warn!("cannot rasterize {}: {}", self.name, err);
+                self.failed = Some(size);
+            }
+        }
+    }
+}
+
+pub fn icon_set() -> Vec<SvgImage> {
+    ICONS
+        .iter()
+        .filter_map(
+            |(name, data)| match SvgImage::parse(*name, data.as_bytes()) {
+                Ok(icon) => Some(icon),
+                Err(err) => {
+                    log::warn!("cannot parse the {} icon: {}", name, err);
+                    None
+                }
+            },
+        )
+        .collect()
+}
@@ -0,0 +1,294 @@
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    inverse_view_proj: [f32; 16],
+    previous_view_proj: [f32; 16],
+    jitter: [f32; 2],
+    blend: f32,
+    debug: f32,
+}
+
+struct History {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+}
+
+pub struct Taa {
+    pub blend: f32,
+    pub motion_debug: bool,
+    layout: wgpu::BindGroupLayout,
+    uniform_buffer: wgpu::Buffer,
+    linear_sampler: wgpu::Sampler,
+    point_sampler: wgpu::Sampler,
+    pipeline: wgpu::RenderPipeline,
+    size: winit::dpi::PhysicalSize<u32>,
+    history: Vec<History>,
+    bind_groups: Vec<wgpu::BindGroup>,
+    current: usize,
+    valid: bool,
+    frame_index: u32,
+    previous_view_proj: glam::Mat4,
+}
+
+impl Taa {
+    pub fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        size: winit::dpi::PhysicalSize<u32>,
+        color_view: &wgpu::TextureView,
+        depth_view: &wgpu::TextureView,
+    ) -> Self {
+        let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStage::FRAGMENT,
+            ty: wgpu::BindingType::SampledTexture {
+                dimension: wgpu::TextureViewDimension::D2,
+                component_type: wgpu::TextureComponentType::Float,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let sampler_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStage::FRAGMENT,
+            ty: wgpu::BindingType::Sampler { comparison: false },
+            count: None,
+        };
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("TAA Bind Group Layout"),
+            entries: &[
+                texture_entry(0),
+                texture_entry(1),
+                texture_entry(2),
+                sampler_entry(3),
+                sampler_entry(4),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 5,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::UniformBuffer {
+                        dynamic: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline = crate::blit::fullscreen_pipeline(
+            device,
+            "TAA Resolve Pipeline",
+            &[&layout],
+            "taa.frag",
+            include_str!("shaders/taa.frag"),
+            format,
+            wgpu::BlendDescriptor::REPLACE,
+        );
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("TAA Uniforms"),
+            size: std::mem::size_of::<Uniforms>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let linear_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("TAA Linear Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let point_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("TAA Point Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            ..Default::default()
+        });
+
+        let mut taa = Self {
+            blend: 0.1,
+            motion_debug: false,
+            layout,
+            uniform_buffer,
+            linear_sampler,
+            point_sampler,
+            pipeline,
+            size,
+            history: Vec::new(),
+            bind_groups: Vec::new(),
+            current: 0,
+            valid: false,
+            frame_index: 0,
+            previous_view_proj: glam::Mat4::identity(),
+        };
+        taa.resize(device, format, size, color_view, depth_view);
+        taa
+    }
+
+    pub fn resize(
+        &mut self,
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        size: winit::dpi::PhysicalSize<u32>,
+        color_view: &wgpu::TextureView,
+        depth_view: &wgpu::TextureView,
+    ) {
+        self.size = size;
+        self.history = (0..2)
+            .map(|i| {
+                let texture = device.create_texture(&wgpu::TextureDescriptor {
+                    label: Some(&format!("TAA History {}", i)),
+                    size: wgpu::Extent3d {
+                        width: size.width.max(1),
+                        height: size.height.max(1),
+                        depth: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT
+                        | wgpu::TextureUsage::SAMPLED
+                        | wgpu::TextureUsage::COPY_SRC,
+                });
+                let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+                History { texture, view }
+            })
+            .collect();
+        self.bind_groups = (0..2)
+            .map(|i| {
+                device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("TAA Bind Group"),
+                    layout: &self.layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::TextureView(color_view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::TextureView(&self.history[1 - i].view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: wgpu::BindingResource::TextureView(depth_view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 3,
+                            resource: wgpu::BindingResource::Sampler(&self.linear_sampler),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 4,
+                            resource: wgpu::BindingResource::Sampler(&self.point_sampler),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 5,
+                            resource: wgpu::BindingResource::Buffer(self.uniform_buffer.slice(..)),
+                        },
+                    ],
+                })
+            })
+            .collect();
+        self.reset();
+    }
+
+    pub fn reset(&mut self) {
+        self.valid = false;
+    }
+
+    pub fn jitter(&self) -> (f32, f32) {
+        let index = self.frame_index % 8 + 1;
+        let x = halton(index, 2) - 0.5;
+        let y = halton(index, 3) - 0.5;
+        (
+            x * 2.0 / self.size.width.max(1) as f32,
+            y * 2.0 / self.size.height.max(1) as f32,
+        )
+    }
+
+    pub fn jittered(&self, projection: &glam::Mat4) -> glam::Mat4 {
+        let (x, y) = self.jitter();
+        glam::Mat4::from_translation(glam::Vec3::new(x, y, 0.0)) * *projection
+    }
+
+    pub fn resolve(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        view_proj: &glam::Mat4,
+        color_texture: &wgpu::Texture,
+    ) {
+        let (jitter_x, jitter_y) = self.jitter();
+        let uniforms = Uniforms {
+            inverse_view_proj: view_proj.inverse().to_cols_array(),
+            previous_view_proj: if self.valid {
+                self.previous_view_proj
+            } else {
+                *view_proj
+            }
+            .to_cols_array(),
+            jitter: [jitter_x, jitter_y],
+            blend: if self.valid { self.blend } else { 1.0 },
+            debug: if self.motion_debug { 1.0 } else { 0.0 },
+        };
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                    attachment: &self.history[self.current].view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: true,
+                    },
+                }],
+                depth_stencil_attachment: None,
+            });
+            render_pass.set_pipeline(&self.pipeline);
+            render_pass.set_bind_group(0, &self.bind_groups[self.current], &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+
+        encoder.copy_texture_to_texture(
+            wgpu::TextureCopyView {
+                texture: &self.history[self.current].texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            wgpu::TextureCopyView {
+                texture: color_texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            wgpu::Extent3d {
+                width: self.size.width.max(1),
+                height: self.size.height.max(1),
+                depth: 1,
+            },
+        );
+
+        self.current = 1 - self.current;
+        self.previous_view_proj = *view_proj;
+        self.frame_index = self.frame_index.wrapping_add(1);
+        self.valid = true;
+    }
+}
+
+fn halton(mut index: u32, base: u32) -> f32 {
+    let mut result = 0.0;
+    let mut fraction = 1.0;
+    while index > 0 {
+        fraction /= base as f32;
+        result += fraction * (index % base) as f32;
+        index /= base;
+    }
+    result
+}
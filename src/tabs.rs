@@ -0,0 +1,196 @@
+pub const HEIGHT: f32 = 26.0;
+const PADDING: f32 = 8.0;
+const BACKGROUND: egui::Srgba = egui::Srgba::new(30, 30, 34, 240);
+const TAB_FILL: egui::Srgba = egui::Srgba::new(45, 45, 52, 255);
+const ACTIVE_FILL: egui::Srgba = egui::Srgba::new(70, 90, 120, 255);
+const TEXT_COLOR: egui::Srgba = egui::Srgba::new(220, 220, 225, 255);
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Kind {
+    Text,
+    Shader,
+    Image,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Text => "Text file",
+            Kind::Shader => "Shader",
+            Kind::Image => "Image",
+        }
+    }
+}
+
+pub struct Tab {
+    pub title: String,
+    pub kind: Kind,
+    pub dirty: bool,
+    pub closable: bool,
+}
+
+pub enum Event {
+    Select(usize),
+    Close(usize),
+    Move(usize, usize),
+}
+
+pub fn key_input(
+    input: &winit::event::KeyboardInput,
+    modifiers: winit::event::ModifiersState,
+) -> Option<isize> {
+    if input.state != winit::event::ElementState::Pressed
+        || !modifiers.ctrl()
+        || input.virtual_keycode != Some(winit::event::VirtualKeyCode::Tab)
+    {
+        return None;
+    }
+    Some(if modifiers.shift() { -1 } else { 1 })
+}
+
+pub fn cycle(active: usize, len: usize, step: isize) -> usize {
+    (active as isize + step).rem_euclid(len as isize) as usize
+}
+
+#[derive(Default)]
+pub struct TabStrip {
+    dragging: Option<usize>,
+}
+
+impl TabStrip {
+    pub fn show(
+        &mut self,
+        ctx: &std::sync::Arc<egui::Context>,
+        rect: egui::Rect,
+        tabs: &[Tab],
+        active: Option<usize>,
+    ) -> Option<Event> {
+        if tabs.is_empty() {
+            self.dragging = None;
+            return None;
+        }
+        let mut event = None;
+        let mut rects = Vec::new();
+        let mut pressed = None;
+        egui::Area::new("document_tabs")
+            .fixed_pos(rect.min)
+            .show(ctx, |ui| {
+                ui.painter().rect_filled(
+                    egui::Rect::from_min_size(rect.min, egui::vec2(rect.width(), HEIGHT)),
+                    0.0,
+                    BACKGROUND,
+                );
+                let mouse_pressed = ui.input().mouse.pressed;
+                ui.horizontal(|ui| {
+                    for (index, tab) in tabs.iter().enumerate() {
+                        let label = format!("{}{}", tab.title, if tab.dirty { " *" } else { "" });
+                        let size = ui.fonts()[egui::TextStyle::Button]
+                            .layout_single_line(label.clone())
+                            .size;
+                        let tab_rect = ui.allocate_space(size + egui::vec2(PADDING * 2.0, PADDING));
+                        let response = ui.interact(
+                            tab_rect,
+                            ui.make_persistent_id(("document_tab", index)),
+                            egui::Sense::click_and_drag(),
+                        );
+                        ui.painter().rect_filled(
+                            tab_rect,
+                            3.0,
+                            if active == Some(index) {
+                                ACTIVE_FILL
+                            } else {
+                                TAB_FILL
+                            },
+                        );
+                        ui.painter().text(
+                            tab_rect.center(),
+                            (egui::Align::Center, egui::Align::Center),
+                            label,
+                            egui::TextStyle::Button,
+                            TEXT_COLOR,
+                        );
+                        crate::status::hint_on_hover(
+                            &response,
+                            &format!("{}: drag to reorder, Ctrl+Tab to switch", tab.kind.name()),
+                        );
+                        if response.hovered && mouse_pressed {
+                            pressed = Some(index);
+                        }
+                        if tab.closable && ui.button("x").clicked {
+                            event = Some(Event::Close(index));
+                        }
+                        rects.push(tab_rect);
+                    }
+                });
+            });
+        if let Some(index) = pressed {
+            self.dragging = Some(index);
+            return Some(Event::Select(index));
+        }
+        if event.is_some() {
+            return event;
+        }
+
+        let (down, pointer) = {
+            let input = ctx.input();
+            (input.mouse.down, input.mouse.pos)
+        };
+        let from = match self.dragging {
+            Some(from) if down => from,
+            _ => {
+                self.dragging = None;
+                return None;
+            }
+        };
+        let to = pointer.and_then(|pointer| {
+            rects
+                .iter()
+                .position(|rect| rect.left() <= pointer.x && pointer.x <= rect.right())
+        })?;
+        if to == from {
+            return None;
+        }
+        self.dragging = Some(to);
+        Some(Event::Move(from, to))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cycle, key_input};
+    use winit::event::{ElementState, KeyboardInput, ModifiersState, VirtualKeyCode};
+
+    #[allow(deprecated)]
+    fn input(key: VirtualKeyCode, state: ElementState) -> KeyboardInput {
+        KeyboardInput {
+            scancode: 0,
+            state,
+            virtual_keycode: Some(key),
+            modifiers: ModifiersState::empty(),
+        }
+    }
+
+    #[test]
+    fn cycling_wraps_in_both_directions() {
+        assert_eq!(cycle(0, 3, 1), 1);
+        assert_eq!(cycle(2, 3, 1), 0);
+        assert_eq!(cycle(0, 3, -1), 2);
+        assert_eq!(cycle(1, 3, -4), 0);
+        assert_eq!(cycle(0, 1, 1), 0);
+    }
+
+    #[test]
+    fn ctrl_tab_steps_forward_and_shift_steps_back() {
+        let tab = input(VirtualKeyCode::Tab, ElementState::Pressed);
+        assert_eq!(key_input(&tab, ModifiersState::CTRL), Some(1));
+        assert_eq!(
+            key_input(&tab, ModifiersState::CTRL | ModifiersState::SHIFT),
+            Some(-1)
+        );
+        assert_eq!(key_input(&tab, ModifiersState::empty()), None);
+        let released = input(VirtualKeyCode::Tab, ElementState::Released);
+        assert_eq!(key_input(&released, ModifiersState::CTRL), None);
+        let other = input(VirtualKeyCode::Q, ElementState::Pressed);
+        assert_eq!(key_input(&other, ModifiersState::CTRL), None);
+    }
+}
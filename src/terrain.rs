@@ -0,0 +1,372 @@
+const CHUNKS: usize = 4;
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    params: [f32; 4],
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Settings {
+    pub enabled: bool,
+    pub noise: crate::noise::Fbm,
+    pub extent: f32,
+    pub resolution: u32,
+    pub height: f32,
+    pub snow_line: f32,
+    pub rock_slope: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            noise: crate::noise::Fbm::default(),
+            extent: 64.0,
+            resolution: 64,
+            height: 8.0,
+            snow_line: 0.7,
+            rock_slope: 0.75,
+        }
+    }
+}
+
+impl Settings {
+    fn mesh_inputs(&self) -> (crate::noise::Fbm, f32, u32, f32) {
+        (self.noise, self.extent, self.resolution, self.height)
+    }
+}
+
+struct ChunkMesh {
+    vertices: Vec<crate::scene::Vertex>,
+    indices: Vec<u32>,
+    bounds: crate::culling::Aabb,
+}
+
+struct Chunk {
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    index_count: u32,
+    bounds: crate::culling::Aabb,
+}
+
+struct Generated {
+    generation: u64,
+    chunks: Vec<ChunkMesh>,
+    duration: std::time::Duration,
+}
+
+fn generate(
+    settings: &Settings,
+    progress: &crate::jobs::Progress,
+) -> Result<Vec<ChunkMesh>, String> {
+    let perlin = crate::noise::Perlin::new(settings.noise.seed);
+    let resolution = settings.resolution.max(2) as usize;
+    let samples = CHUNKS * resolution + 1;
+    let spacing = settings.extent / (samples - 1) as f32;
+    let origin = -settings.extent * 0.5;
+    let height_at =
+        |x: f32, z: f32| (settings.noise.sample2(&perlin, x, z) * 0.5 + 0.5) * settings.height;
+
+    let mut heights = vec![0.0; samples * samples];
+    for row in 0..samples {
+        for column in 0..samples {
+            let x = origin + column as f32 * spacing;
+            let z = origin + row as f32 * spacing;
+            heights[row * samples + column] = height_at(x, z);
+        }
+    }
+    let height = |column: isize, row: isize| {
+        if column >= 0 && row >= 0 && (column as usize) < samples && (row as usize) < samples {
+            heights[row as usize * samples + column as usize]
+        } else {
+            height_at(
+                origin + column as f32 * spacing,
+                origin + row as f32 * spacing,
+            )
+        }
+    };
+
+    let mut chunks = Vec::with_capacity(CHUNKS * CHUNKS);
+    for chunk_row in 0..CHUNKS {
+        progress.check()?;
+        progress.set(
+            chunk_row as f32 / CHUNKS as f32,
+            format!("row {} / {}", chunk_row + 1, CHUNKS),
+        );
+        for chunk_column in 0..CHUNKS {
+            let mut vertices = Vec::with_capacity((resolution + 1) * (resolution + 1));
+            for row in 0..=resolution {
+                for column in 0..=resolution {
+                    let column = (chunk_column * resolution + column) as isize;
+                    let row = (chunk_row * resolution + row) as isize;
+                    let normal = glam::Vec3::new(
+                        height(column - 1, row) - height(column + 1, row),
+                        2.0 * spacing,
+                        height(column, row - 1) - height(column, row + 1),
+                    )
+                    .normalize();
+                    vertices.push(crate::scene::Vertex {
+                        position: [
+                            origin + column as f32 * spacing,
+                            height(column, row),
+                            origin + row as f32 * spacing,
+                        ],
+                        normal: normal.into(),
+                    });
+                }
+            }
+            let stride = (resolution + 1) as u32;
+            let mut indices = Vec::with_capacity(resolution * resolution * 6);
+            for row in 0..resolution as u32 {
+                for column in 0..resolution as u32 {
+                    let a = row * stride + column;
+                    let b = a + stride;
+                    indices.extend_from_slice(&[a, b, a + 1, a + 1, b, b + 1]);
+                }
+            }
+            let bounds = crate::culling::Aabb::from_points(
+                vertices
+                    .iter()
+                    .map(|vertex| glam::Vec3::from(vertex.position)),
+            );
+            chunks.push(ChunkMesh {
+                vertices,
+                indices,
+                bounds,
+            });
+        }
+    }
+    Ok(chunks)
+}
+
+pub struct Terrain {
+    pub settings: Settings,
+    pub generation_time: std::time::Duration,
+    pub triangles: usize,
+    generation: u64,
+    built: Option<Settings>,
+    pending: Option<crate::jobs::Handle<Generated>>,
+    chunks: Vec<Chunk>,
+    pipeline: wgpu::RenderPipeline,
+    msaa_pipeline: wgpu::RenderPipeline,
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+
+impl Terrain {
+    pub fn new(device: &wgpu::Device, scene_layout: &wgpu::BindGroupLayout) -> Self {
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Terrain Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStage::FRAGMENT,
+                ty: wgpu::BindingType::UniformBuffer {
+                    dynamic: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Terrain Pipeline Layout"),
+            bind_group_layouts: &[scene_layout, &layout],
+            push_constant_ranges: &[],
+        });
+        let vs_module = crate::shader::compile_builtin(
+            device,
+            "terrain.vert",
+            include_str!("shaders/terrain.vert"),
+            shaderc::ShaderKind::Vertex,
+        );
+        let fs_module = crate::shader::compile_builtin(
+            device,
+            "terrain.frag",
+            include_str!("shaders/terrain.frag"),
+            shaderc::ShaderKind::Fragment,
+        );
+        let color_states = [wgpu::ColorStateDescriptor {
+            format: crate::scene::HDR_FORMAT,
+            color_blend: wgpu::BlendDescriptor::REPLACE,
+            alpha_blend: wgpu::BlendDescriptor::REPLACE,
+            write_mask: wgpu::ColorWrite::ALL,
+        }];
+        let create = |label, sample_count| {
+            crate::scene::create_pipeline_with_winding(
+                device,
+                label,
+                &pipeline_layout,
+                &vs_module,
+                &fs_module,
+                &color_states,
+                true,
+                sample_count,
+                wgpu::FrontFace::Ccw,
+            )
+        };
+        let pipeline = create("Terrain Pipeline", 1);
+        let msaa_pipeline = create("Terrain MSAA Pipeline", crate::scene::MSAA_SAMPLES);
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Terrain Uniforms"),
+            size: std::mem::size_of::<Uniforms>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Terrain Bind Group"),
+            layout: &layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer(uniform_buffer.slice(..)),
+            }],
+        });
+
+        Self {
+            settings: Settings::default(),
+            generation_time: std::time::Duration::default(),
+            triangles: 0,
+            generation: 0,
+            built: None,
+            pending: None,
+            chunks: Vec::new(),
+            pipeline,
+            msaa_pipeline,
+            uniform_buffer,
+            bind_group,
+        }
+    }
+
+    pub fn is_generating(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    pub fn regenerate(&mut self) {
+        self.built = None;
+    }
+
+    fn start(&mut self) {
+        self.generation += 1;
+        let generation = self.generation;
+        let settings = self.settings;
+        let handle = crate::jobs::spawn("Generate terrain", move |progress| {
+            let time = std::time::Instant::now();
+            let chunks = generate(&settings, progress)?;
+            Ok(Generated {
+                generation,
+                chunks,
+                duration: time.elapsed(),
+            })
+        });
+        self.built = Some(settings);
+        self.pending = Some(handle);
+    }
+
+    pub fn update(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        if !self.settings.enabled {
+            return;
+        }
+        let uniforms = Uniforms {
+            params: [
+                self.settings.height,
+                self.settings.snow_line,
+                self.settings.rock_slope,
+                0.0,
+            ],
+        };
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+
+        if let Some(handle) = &self.pending {
+            match handle.poll() {
+                Some(Ok(generated)) => {
+                    self.pending = None;
+                    if generated.generation == self.generation {
+                        self.upload(device, generated);
+                    }
+                }
+                Some(Err(err)) => {
+                    log::warn!("terrain generation stopped: {}", err);
+                    self.pending = None;
+                }
+                None => return,
+            }
+        }
+        let changed =
+            self.built.map(|built| built.mesh_inputs()) != Some(self.settings.mesh_inputs());
+        if changed && self.pending.is_none() {
+            self.start();
+        }
+    }
+
+    fn upload(&mut self, device: &wgpu::Device, generated: Generated) {
+        self.generation_time = generated.duration;
+        self.triangles = generated
+            .chunks
+            .iter()
+            .map(|chunk| chunk.indices.len() / 3)
+            .sum();
+        self.chunks = generated
+            .chunks
+            .iter()
+            .map(|chunk| Chunk {
+                vertex_buffer: wgpu::util::DeviceExt::create_buffer_init(
+                    device,
+                    &wgpu::util::BufferInitDescriptor {
+                        label: Some("Terrain Vertices"),
+                        contents: bytemuck::cast_slice(&chunk.vertices),
+                        usage: wgpu::BufferUsage::VERTEX,
+                    },
+                ),
+                index_buffer: wgpu::util::DeviceExt::create_buffer_init(
+                    device,
+                    &wgpu::util::BufferInitDescriptor {
+                        label: Some("Terrain Indices"),
+                        contents: bytemuck::cast_slice(&chunk.indices),
+                        usage: wgpu::BufferUsage::INDEX,
+                    },
+                ),
+                index_count: chunk.indices.len() as u32,
+                bounds: chunk.bounds,
+            })
+            .collect();
+    }
+
+    pub fn draw<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        msaa: bool,
+        frustum: &crate::culling::Frustum,
+        stats: &mut crate::scene::Stats,
+    ) {
+        if !self.settings.enabled || self.chunks.is_empty() {
+            return;
+        }
+        if msaa {
+            render_pass.set_pipeline(&self.msaa_pipeline);
+        } else {
+            render_pass.set_pipeline(&self.pipeline);
+        }
+        render_pass.set_bind_group(1, &self.bind_group, &[]);
+        for chunk in self.chunks.iter() {
+            if !frustum.intersects(&chunk.bounds) {
+                continue;
+            }
+            render_pass.set_vertex_buffer(0, chunk.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(chunk.index_buffer.slice(..));
+            render_pass.draw_indexed(0..chunk.index_count, 0, 0..1);
+            stats.draw_calls += 1;
+            stats.triangles += (chunk.index_count / 3) as u64;
+        }
+    }
+}
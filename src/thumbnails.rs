@@ -0,0 +1,246 @@
+const SIZE: u32 = 32;
+const WORKERS: usize = 2;
+const MAX_IDLE_FRAMES: u64 = 600;
+const EXTENSIONS: [&str; 7] = ["png", "jpg", "jpeg", "bmp", "gif", "tga", "tiff"];
+
+pub fn is_image(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| EXTENSIONS.contains(&extension.to_lowercase().as_str()))
+}
+
+pub struct Thumbnail {
+    pub size: [u32; 2],
+    pub pixels: Vec<egui::Srgba>,
+}
+
+impl Thumbnail {
+    pub fn from_image(image: &image::RgbaImage) -> Self {
+        Self {
+            size: [image.width(), image.height()],
+            pixels: image
+                .pixels()
+                .map(|pixel| egui::Srgba::new(pixel[0], pixel[1], pixel[2], pixel[3]))
+                .collect(),
+        }
+    }
+
+    pub fn paint(&self, painter: &egui::Painter, rect: egui::Rect) {
+        let [width, height] = self.size;
+        if width == 0 || height == 0 {
+            return;
+        }
+        let cell = (rect.width() / width as f32).min(rect.height() / height as f32);
+        let origin = rect.center() - egui::vec2(width as f32, height as f32) * cell * 0.5;
+        for (index, color) in self.pixels.iter().enumerate() {
+            let x = (index as u32 % width) as f32;
+            let y = (index as u32 / width) as f32;
+            painter.rect_filled(
+                egui::Rect::from_min_size(
+                    origin + egui::vec2(x * cell, y * cell),
+                    egui::vec2(cell, cell),
+                ),
+                0.0,
+                *color,
+            );
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct ThumbnailStats {
+    pub cached: usize,
+    pub pending: usize,
+    pub generated: u32,
+    pub from_disk: u32,
+    pub failed: u32,
+    pub evicted: u32,
+}
+
+enum State {
+    Pending,
+    Ready(Thumbnail),
+    Failed,
+}
+
+struct Entry {
+    state: State,
+    last_used: u64,
+}
+
+struct Request {
+    path: std::path::PathBuf,
+    modified: std::time::SystemTime,
+}
+
+type Generated = Result<(Thumbnail, bool), String>;
+
+struct Shared {
+    queue: std::sync::Mutex<Vec<Request>>,
+    available: std::sync::Condvar,
+    results: std::sync::Mutex<Vec<(std::path::PathBuf, Generated)>>,
+}
+
+fn cache_dir() -> Option<std::path::PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")).join("thumbnails"))
+}
+
+fn generate(request: &Request) -> Generated {
+    let cached = cache_dir().map(|dir| {
+        dir.join(format!(
+            "{:016x}.png",
+            crate::cache::key(&(&request.path, request.modified))
+        ))
+    });
+    if let Some(Ok(image)) = cached.as_ref().map(image::open) {
+        return Ok((Thumbnail::from_image(&image.to_rgba()), true));
+    }
+    let image = image::open(&request.path)
+        .map_err(|err| err.to_string())?
+        .thumbnail(SIZE, SIZE)
+        .to_rgba();
+    if let Some(cached) = cached {
+        let saved = std::fs::create_dir_all(cached.parent().unwrap())
+            .map_err(|err| err.to_string())
+            .and_then(|_| image.save(&cached).map_err(|err| err.to_string()));
+        if let Err(err) = saved {
+            log::warn!("cannot cache thumbnail {}: {}", cached.display(), err);
+        }
+    }
+    Ok((Thumbnail::from_image(&image), false))
+}
+
+fn work(shared: &Shared) {
+    loop {
+        let request = {
+            let mut queue = shared.queue.lock().unwrap();
+            loop {
+                match queue.pop() {
+                    Some(request) => break request,
+                    None => queue = shared.available.wait(queue).unwrap(),
+                }
+            }
+        };
+        let result = generate(&request);
+        shared.results.lock().unwrap().push((request.path, result));
+    }
+}
+
+#[derive(Default)]
+pub struct Thumbnails {
+    pub stats: ThumbnailStats,
+    entries: std::collections::HashMap<std::path::PathBuf, Entry>,
+    shared: Option<std::sync::Arc<Shared>>,
+    frame: u64,
+}
+
+impl Thumbnails {
+    fn shared(&mut self) -> &Shared {
+        self.shared.get_or_insert_with(|| {
+            let shared = std::sync::Arc::new(Shared {
+                queue: std::sync::Mutex::new(Vec::new()),
+                available: std::sync::Condvar::new(),
+                results: std::sync::Mutex::new(Vec::new()),
+            });
+            for worker in 0..WORKERS {
+                let shared = shared.clone();
+                std::thread::Builder::new()
+                    .name(format!("thumbnailer {}", worker))
+                    .spawn(move || work(&shared))
+                    .unwrap();
+            }
+            shared
+        })
+    }
+
+    pub fn get(&mut self, path: &std::path::Path) -> Option<&Thumbnail> {
+        let frame = self.frame;
+        if !self.entries.contains_key(path) {
+            let modified = std::fs::metadata(path).and_then(|metadata| metadata.modified());
+            let state = match modified {
+                Ok(modified) => {
+                    let shared = self.shared();
+                    shared.queue.lock().unwrap().push(Request {
+                        path: path.to_path_buf(),
+                        modified,
+                    });
+                    shared.available.notify_one();
+                    State::Pending
+                }
+                Err(_) => State::Failed,
+            };
+            self.entries.insert(
+                path.to_path_buf(),
+                Entry {
+                    state,
+                    last_used: frame,
+                },
+            );
+        }
+        let entry = self.entries.get_mut(path).unwrap();
+        entry.last_used = frame;
+        match &entry.state {
+            State::Ready(thumbnail) => Some(thumbnail),
+            _ => None,
+        }
+    }
+
+    pub fn end_frame(&mut self) {
+        self.frame += 1;
+        if let Some(shared) = &self.shared {
+            for (path, result) in shared.results.lock().unwrap().drain(..) {
+                let state = match result {
+                    Ok((thumbnail, from_disk)) => {
+                        if from_disk {
+                            self.stats.from_disk += 1;
+                        } else {
+                            self.stats.generated += 1;
+                        }
+                        State::Ready(thumbnail)
+                    }
+                    Err(err) => {
+                        log::warn!("cannot make a thumbnail of {}: {}", path.display(), err);
+                        self.stats.failed += 1;
+                        State::Failed
+                    }
+                };
+                if let Some(entry) = self.entries.get_mut(&path) {
+                    entry.state = state;
+                }
+            }
+            let frame = self.frame;
+            let mut queue = shared.queue.lock().unwrap();
+            queue.retain(|request| {
+                self.entries
+                    .get(&request.path)
+                    .is_some_and(|entry| frame - entry.last_used <= 2)
+            });
+        }
+
+        let frame = self.frame;
+        let mut evicted = 0;
+        self.entries.retain(|_, entry| match entry.state {
+            State::Pending => frame - entry.last_used <= 2,
+            _ if frame - entry.last_used <= MAX_IDLE_FRAMES => true,
+            _ => {
+                evicted += 1;
+                false
+            }
+        });
+        self.stats.evicted += evicted;
+        self.stats.cached = self
+            .entries
+            .values()
+            .filter(|entry| matches!(entry.state, State::Ready(_)))
+            .count();
+        self.stats.pending = self
+            .entries
+            .values()
+            .filter(|entry| matches!(entry.state, State::Pending))
+            .count();
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
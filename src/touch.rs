@@ -0,0 +1,149 @@
+const LONG_PRESS: std::time::Duration = std::time::Duration::from_millis(500);
+const SLOP: f64 = 12.0;
+
+struct Contact {
+    id: u64,
+    device_id: winit::event::DeviceId,
+    start: winit::dpi::PhysicalPosition<f64>,
+    started: std::time::Instant,
+    moved: bool,
+    long_pressed: bool,
+}
+
+#[allow(deprecated)]
+fn cursor_moved(
+    device_id: winit::event::DeviceId,
+    position: winit::dpi::PhysicalPosition<f64>,
+) -> winit::event::WindowEvent<'static> {
+    winit::event::WindowEvent::CursorMoved {
+        device_id,
+        position,
+        modifiers: winit::event::ModifiersState::empty(),
+    }
+}
+
+#[allow(deprecated)]
+fn button(
+    device_id: winit::event::DeviceId,
+    state: winit::event::ElementState,
+    button: winit::event::MouseButton,
+) -> winit::event::WindowEvent<'static> {
+    winit::event::WindowEvent::MouseInput {
+        device_id,
+        state,
+        button,
+        modifiers: winit::event::ModifiersState::empty(),
+    }
+}
+
+#[derive(Default)]
+pub struct TouchInput {
+    contact: Option<Contact>,
+}
+
+impl TouchInput {
+    pub fn translate(
+        &mut self,
+        touch: &winit::event::Touch,
+    ) -> Vec<winit::event::WindowEvent<'static>> {
+        let device_id = touch.device_id;
+        let position = touch.location;
+        if touch.phase == winit::event::TouchPhase::Started {
+            if self.contact.is_some() {
+                return Vec::new();
+            }
+            self.contact = Some(Contact {
+                id: touch.id,
+                device_id,
+                start: position,
+                started: std::time::Instant::now(),
+                moved: false,
+                long_pressed: false,
+            });
+            return vec![
+                cursor_moved(device_id, position),
+                button(
+                    device_id,
+                    winit::event::ElementState::Pressed,
+                    winit::event::MouseButton::Left,
+                ),
+            ];
+        }
+        let contact = match self
+            .contact
+            .as_mut()
+            .filter(|contact| contact.id == touch.id)
+        {
+            Some(contact) => contact,
+            None => return Vec::new(),
+        };
+        match touch.phase {
+            winit::event::TouchPhase::Moved => {
+                let (dx, dy) = (position.x - contact.start.x, position.y - contact.start.y);
+                contact.moved |= dx * dx + dy * dy > SLOP * SLOP;
+                vec![cursor_moved(device_id, position)]
+            }
+            _ => {
+                let contact = self.contact.take().unwrap();
+                let mut events = vec![cursor_moved(device_id, position)];
+                if !contact.long_pressed {
+                    events.push(button(
+                        device_id,
+                        winit::event::ElementState::Released,
+                        winit::event::MouseButton::Left,
+                    ));
+                }
+                events.push(winit::event::WindowEvent::CursorLeft { device_id });
+                events
+            }
+        }
+    }
+
+    pub fn cancel(&mut self) -> Vec<winit::event::WindowEvent<'static>> {
+        let contact = match self.contact.take() {
+            Some(contact) => contact,
+            None => return Vec::new(),
+        };
+        let mut events = Vec::new();
+        if !contact.long_pressed {
+            events.push(button(
+                contact.device_id,
+                winit::event::ElementState::Released,
+                winit::event::MouseButton::Left,
+            ));
+        }
+        events.push(winit::event::WindowEvent::CursorLeft {
+            device_id: contact.device_id,
+        });
+        events
+    }
+
+    pub fn poll(&mut self) -> Vec<winit::event::WindowEvent<'static>> {
+        let contact = match self.contact.as_mut() {
+            Some(contact) => contact,
+            None => return Vec::new(),
+        };
+        if contact.moved || contact.long_pressed || contact.started.elapsed() < LONG_PRESS {
+            return Vec::new();
+        }
+        contact.long_pressed = true;
+        let device_id = contact.device_id;
+        vec![
+            button(
+                device_id,
+                winit::event::ElementState::Released,
+                winit::event::MouseButton::Left,
+            ),
+            button(
+                device_id,
+                winit::event::ElementState::Pressed,
+                winit::event::MouseButton::Right,
+            ),
+            button(
+                device_id,
+                winit::event::ElementState::Released,
+                winit::event::MouseButton::Right,
+            ),
+        ]
+    }
+}
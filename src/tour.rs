@@ -0,0 +1,135 @@
+const WIDTH: f32 = 300.0;
+const MARGIN: f32 = 12.0;
+const CALLOUT_HEIGHT: f32 = 160.0;
+const MENU_HEIGHT: f32 = 24.0;
+const HIGHLIGHT: egui::Srgba = egui::Srgba::new(90, 170, 250, 255);
+const DIM: egui::Srgba = egui::Srgba::new(150, 150, 160, 255);
+
+struct Step {
+    title: &'static str,
+    text: &'static str,
+    target: fn(egui::Vec2) -> egui::Rect,
+}
+
+const STEPS: [Step; 5] = [
+    Step {
+        title: "Menu bar",
+        text: "Demos, panels and settings live here.",
+        target: |screen| {
+            egui::Rect::from_min_size(egui::Pos2::default(), egui::vec2(screen.x, MENU_HEIGHT))
+        },
+    },
+    Step {
+        title: "Documents",
+        text: "Open documents get a tab. Drag tabs to reorder them and use Ctrl+Tab to cycle.",
+        target: |_| {
+            let central = crate::dock::central();
+            egui::Rect::from_min_size(
+                central.min,
+                egui::vec2(central.width(), crate::tabs::HEIGHT),
+            )
+        },
+    },
+    Step {
+        title: "Docking",
+        text: "Drag a panel to the left or right edge of the window to dock it there.",
+        target: |_| crate::dock::central(),
+    },
+    Step {
+        title: "Status bar",
+        text: "Hover hints and running tasks show up along the bottom.",
+        target: |screen| {
+            egui::Rect::from_min_size(
+                egui::pos2(0.0, screen.y - crate::status::HEIGHT),
+                egui::vec2(screen.x, crate::status::HEIGHT),
+            )
+        },
+    },
+    Step {
+        title: "Notifications",
+        text: "Messages appear in this corner and fade away on their own. Click one to dismiss it.",
+        target: |screen| {
+            egui::Rect::from_min_max(
+                egui::pos2(screen.x - 300.0, screen.y - crate::status::HEIGHT - 160.0),
+                egui::pos2(screen.x, screen.y - crate::status::HEIGHT),
+            )
+        },
+    },
+];
+
+#[derive(Default)]
+pub struct Tour {
+    step: usize,
+}
+
+impl Tour {
+    pub fn restart(&mut self) {
+        self.step = 0;
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, screen: egui::Vec2) -> bool {
+        let step = &STEPS[self.step];
+        let target = (step.target)(screen);
+        let last = self.step + 1 == STEPS.len();
+        egui::Area::new("tour_highlight")
+            .order(egui::Order::Foreground)
+            .interactable(false)
+            .fixed_pos(egui::Pos2::default())
+            .show(ctx, |ui| {
+                ui.painter().rect_stroke(
+                    target.shrink(1.0),
+                    4.0,
+                    egui::Stroke::new(2.0, HIGHLIGHT),
+                );
+            });
+
+        let below = target.bottom() + MARGIN + CALLOUT_HEIGHT < screen.y;
+        let y = if below {
+            target.bottom() + MARGIN
+        } else {
+            target.top() - MARGIN - CALLOUT_HEIGHT
+        };
+        let x = target
+            .center()
+            .x
+            .min(screen.x - WIDTH - MARGIN)
+            .max(MARGIN + WIDTH / 2.0)
+            - WIDTH / 2.0;
+        let (mut back, mut next, mut skip) = (false, false, false);
+        egui::Area::new("tour_callout")
+            .order(egui::Order::Foreground)
+            .fixed_pos(egui::pos2(x, y.max(MARGIN)))
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.allocate_space(egui::vec2(WIDTH, 0.0));
+                    ui.heading(step.title);
+                    ui.label(step.text);
+                    ui.add(
+                        egui::Label::new(format!("Step {} of {}", self.step + 1, STEPS.len()))
+                            .text_color(DIM),
+                    );
+                    ui.horizontal(|ui| {
+                        if self.step > 0 {
+                            back = ui.button("Back").clicked;
+                        }
+                        next = ui.button(if last { "Done" } else { "Next" }).clicked;
+                        if !last {
+                            skip = ui.button("Skip tour").clicked;
+                        }
+                    });
+                });
+            });
+
+        if back {
+            self.step -= 1;
+        }
+        if skip || (next && last) {
+            self.restart();
+            return true;
+        }
+        if next {
+            self.step += 1;
+        }
+        false
+    }
+}
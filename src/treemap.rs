@@ -0,0 +1,921 @@
+const MAP_SIZE: [f32; 2] = [520.0, 360.0];
+const MAX_DEPTH: usize = 3;
+const MAX_HISTORY: usize = 100;
+const HEADER: f32 = 14.0;
+const GRID_CELL: [f32; 2] = [96.0, 88.0];
+const GRID_IMAGE: f32 = 64.0;
+const PALETTE: [[u8; 3]; 8] = [
+    [66, 133, 180],
+    [214, 120, 60],
+    [90, 160, 90],
+    [190, 80, 90],
+    [140, 110, 180],
+    [150, 120, 90],
+    [200, 120, 170],
+    [120, 150, 150],
+];
+
+struct Node {
+    name: String,
+    size: u64,
+    files: u64,
+    directory: bool,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn get(&self, path: &[usize]) -> &Node {
+        path.iter().fold(self, |node, index| &node.children[*index])
+    }
+
+    fn location(&self, path: &[usize]) -> std::path::PathBuf {
+        let mut location = std::path::PathBuf::from(&self.name);
+        for depth in 1..=path.len() {
+            location.push(&self.get(&path[..depth]).name);
+        }
+        location
+    }
+
+    fn resolve(&self, location: &std::path::Path) -> Option<Vec<usize>> {
+        let relative = location.strip_prefix(&self.name).ok()?;
+        let mut node = self;
+        let mut path = Vec::new();
+        for component in relative.components() {
+            let name = component.as_os_str().to_string_lossy();
+            let index = node
+                .children
+                .iter()
+                .position(|child| child.directory && child.name == name)?;
+            path.push(index);
+            node = &node.children[index];
+        }
+        Some(path)
+    }
+}
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+struct Places {
+    root: Option<String>,
+    bookmarks: Vec<String>,
+}
+
+impl Places {
+    fn path() -> Option<std::path::PathBuf> {
+        dirs::config_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")).join("disk_usage.json"))
+    }
+}
+
+struct Progress {
+    files: std::sync::atomic::AtomicU64,
+    bytes: std::sync::atomic::AtomicU64,
+    cancelled: std::sync::atomic::AtomicBool,
+}
+
+struct Scan {
+    progress: std::sync::Arc<Progress>,
+    receiver: std::sync::mpsc::Receiver<Node>,
+    started: std::time::Instant,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum View {
+    Treemap,
+    Grid,
+}
+
+#[derive(Clone)]
+enum TileAction {
+    Zoom(std::path::PathBuf),
+    Up,
+    Top,
+    Bookmark(String),
+    Copy(String),
+}
+
+struct Cell {
+    path: Vec<usize>,
+    rect: egui::Rect,
+    depth: usize,
+}
+
+pub struct DiskUsage {
+    pub open: bool,
+    pub root: String,
+    tree: Option<Node>,
+    zoom: Vec<usize>,
+    scan: Option<Scan>,
+    status: String,
+    scan_requested: bool,
+    cancel_requested: bool,
+    menu: crate::menu::ContextMenu<TileAction>,
+    view: View,
+    thumbnails: crate::thumbnails::Thumbnails,
+    bookmarks: Vec<String>,
+    open_requested: Option<std::path::PathBuf>,
+    back: Vec<std::path::PathBuf>,
+    forward: Vec<std::path::PathBuf>,
+}
+
+impl Default for DiskUsage {
+    fn default() -> Self {
+        Self {
+            open: false,
+            root: dirs::home_dir()
+                .map(|dir| dir.display().to_string())
+                .unwrap_or_default(),
+            tree: None,
+            zoom: Vec::new(),
+            scan: None,
+            status: String::new(),
+            scan_requested: false,
+            cancel_requested: false,
+            menu: crate::menu::ContextMenu::default(),
+            view: View::Treemap,
+            thumbnails: crate::thumbnails::Thumbnails::default(),
+            bookmarks: Vec::new(),
+            open_requested: None,
+            back: Vec::new(),
+            forward: Vec::new(),
+        }
+    }
+}
+
+fn scan_directory(path: &std::path::Path, name: String, progress: &Progress) -> Node {
+    let mut node = Node {
+        name,
+        size: 0,
+        files: 0,
+        directory: true,
+        children: Vec::new(),
+    };
+    if progress
+        .cancelled
+        .load(std::sync::atomic::Ordering::Relaxed)
+    {
+        return node;
+    }
+    let entries = match std::fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(err) => {
+            log::warn!("skipping {}: {}", path.display(), err);
+            return node;
+        }
+    };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let metadata = match entry.path().symlink_metadata() {
+            Ok(metadata) => metadata,
+            Err(_) => continue,
+        };
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let child = if metadata.is_dir() {
+            scan_directory(&entry.path(), name, progress)
+        } else if metadata.is_file() {
+            progress
+                .files
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            progress
+                .bytes
+                .fetch_add(metadata.len(), std::sync::atomic::Ordering::Relaxed);
+            Node {
+                name,
+                size: metadata.len(),
+                files: 1,
+                directory: false,
+                children: Vec::new(),
+            }
+        } else {
+            continue;
+        };
+        node.size += child.size;
+        node.files += child.files;
+        node.children.push(child);
+    }
+    node.children
+        .sort_by_key(|child| std::cmp::Reverse(child.size));
+    node
+}
+
+fn worst(sum: f32, min: f32, max: f32, side: f32) -> f32 {
+    let side = side * side;
+    let sum = sum * sum;
+    (side * max / sum).max(sum / (side * min))
+}
+
+fn squarify(sizes: &[f32], rect: egui::Rect) -> Vec<egui::Rect> {
+    let total: f32 = sizes.iter().sum();
+    let mut cells = Vec::with_capacity(sizes.len());
+    if total <= 0.0 || rect.width() < 1.0 || rect.height() < 1.0 {
+        return cells;
+    }
+    let scale = rect.width() * rect.height() / total;
+    let mut rect = rect;
+    let mut start = 0;
+    while start < sizes.len() && sizes[start] > 0.0 {
+        let wide = rect.width() >= rect.height();
+        let side = if wide { rect.height() } else { rect.width() };
+        let first = sizes[start] * scale;
+        let mut sum = first;
+        let mut ratio = worst(sum, first, first, side);
+        let mut end = start + 1;
+        while end < sizes.len() && sizes[end] > 0.0 {
+            let area = sizes[end] * scale;
+            let next = worst(sum + area, area, first, side);
+            if next > ratio {
+                break;
+            }
+            ratio = next;
+            sum += area;
+            end += 1;
+        }
+
+        let thickness = sum / side;
+        let mut offset = 0.0;
+        for size in &sizes[start..end] {
+            let length = size * scale / thickness;
+            cells.push(if wide {
+                egui::Rect::from_min_size(
+                    rect.min + egui::vec2(0.0, offset),
+                    egui::vec2(thickness, length),
+                )
+            } else {
+                egui::Rect::from_min_size(
+                    rect.min + egui::vec2(offset, 0.0),
+                    egui::vec2(length, thickness),
+                )
+            });
+            offset += length;
+        }
+        rect = if wide {
+            egui::Rect::from_min_max(egui::pos2(rect.min.x + thickness, rect.min.y), rect.max)
+        } else {
+            egui::Rect::from_min_max(egui::pos2(rect.min.x, rect.min.y + thickness), rect.max)
+        };
+        start = end;
+    }
+    cells
+}
+
+fn layout(
+    node: &Node,
+    rect: egui::Rect,
+    depth: usize,
+    path: &mut Vec<usize>,
+    cells: &mut Vec<Cell>,
+) {
+    let sizes: Vec<f32> = node
+        .children
+        .iter()
+        .map(|child| child.size as f32)
+        .collect();
+    for (index, child_rect) in squarify(&sizes, rect).into_iter().enumerate() {
+        if child_rect.width() < 2.0 || child_rect.height() < 2.0 {
+            continue;
+        }
+        path.push(index);
+        cells.push(Cell {
+            path: path.clone(),
+            rect: child_rect,
+            depth,
+        });
+        let child = &node.children[index];
+        if child.directory
+            && depth + 1 < MAX_DEPTH
+            && child_rect.width() > 24.0
+            && child_rect.height() > HEADER + 12.0
+        {
+            let inner = egui::Rect::from_min_max(
+                child_rect.min + egui::vec2(2.0, HEADER),
+                child_rect.max - egui::vec2(2.0, 2.0),
+            );
+            layout(child, inner, depth + 1, path, cells);
+        }
+        path.pop();
+    }
+}
+
+fn color(path: &[usize], depth: usize, directory: bool) -> egui::Srgba {
+    let [r, g, b] = PALETTE[path[0] % PALETTE.len()];
+    let shade = 1.0 - depth as f32 * 0.18 + if directory { 0.0 } else { 0.1 };
+    let channel = |value: u8| (value as f32 * shade).min(255.0) as u8;
+    egui::Srgba::new(channel(r), channel(g), channel(b), 255)
+}
+
+fn show_treemap(
+    ui: &mut egui::Ui,
+    current: &Node,
+) -> (Option<Vec<usize>>, bool, Option<egui::Pos2>) {
+    let rect = ui.allocate_space(egui::vec2(MAP_SIZE[0], MAP_SIZE[1]));
+    let response = ui.interact(rect, ui.make_persistent_id("treemap"), egui::Sense::click());
+    let mut cells = Vec::new();
+    layout(current, rect, 0, &mut Vec::new(), &mut cells);
+
+    let mouse = ui.input().mouse.pos.filter(|pos| rect.contains(*pos));
+    let hovered = mouse.and_then(|pos| cells.iter().rev().find(|cell| cell.rect.contains(pos)));
+    let painter = ui.painter();
+    painter.rect_filled(rect, 0.0, egui::Srgba::new(30, 30, 30, 255));
+    for cell in cells.iter() {
+        let node = current.get(&cell.path);
+        let highlighted = hovered.is_some_and(|hovered| hovered.path == cell.path);
+        let fill = if highlighted {
+            egui::Srgba::new(240, 220, 120, 255)
+        } else {
+            color(&cell.path, cell.depth, node.directory)
+        };
+        painter.rect_filled(cell.rect.shrink(0.5), 1.0, fill);
+        if cell.rect.width() > 40.0 && cell.rect.height() > HEADER {
+            let characters = (cell.rect.width() / 7.0) as usize;
+            let name: String = node.name.chars().take(characters).collect();
+            painter.text(
+                cell.rect.min + egui::vec2(3.0, 1.0),
+                (egui::Align::Min, egui::Align::Min),
+                name,
+                egui::TextStyle::Small,
+                egui::Srgba::new(255, 255, 255, 255),
+            );
+        }
+    }
+    (
+        hovered.map(|cell| cell.path.clone()),
+        response.clicked,
+        mouse,
+    )
+}
+
+fn show_grid(
+    ui: &mut egui::Ui,
+    tree: &Node,
+    zoom: &[usize],
+    thumbnails: &mut crate::thumbnails::Thumbnails,
+) -> (Option<Vec<usize>>, bool, Option<egui::Pos2>) {
+    let current = tree.get(zoom);
+    let mut result = (None, false, None);
+    egui::ScrollArea::from_max_height(MAP_SIZE[1]).show(ui, |ui| {
+        let columns = ((MAP_SIZE[0] / GRID_CELL[0]) as usize).max(1);
+        let rows = current.children.len().div_ceil(columns);
+        let rect = ui.allocate_space(egui::vec2(MAP_SIZE[0], rows as f32 * GRID_CELL[1]));
+        let response = ui.interact(
+            rect,
+            ui.make_persistent_id("treemap_grid"),
+            egui::Sense::click(),
+        );
+        let clip = ui.clip_rect();
+        let mouse = ui
+            .input()
+            .mouse
+            .pos
+            .filter(|pos| rect.contains(*pos) && clip.contains(*pos));
+        let painter = ui.painter();
+        let mut path = zoom.to_vec();
+        for (index, child) in current.children.iter().enumerate() {
+            let cell = egui::Rect::from_min_size(
+                rect.min
+                    + egui::vec2(
+                        (index % columns) as f32 * GRID_CELL[0],
+                        (index / columns) as f32 * GRID_CELL[1],
+                    ),
+                egui::vec2(GRID_CELL[0], GRID_CELL[1]),
+            );
+            if cell.max.y < clip.min.y || cell.min.y > clip.max.y {
+                continue;
+            }
+            if mouse.is_some_and(|pos| cell.contains(pos)) {
+                result.0 = Some(vec![index]);
+                painter.rect_filled(cell.shrink(1.0), 3.0, egui::Srgba::new(70, 70, 50, 255));
+            }
+            let image = egui::Rect::from_min_size(
+                egui::pos2(cell.center().x - GRID_IMAGE * 0.5, cell.min.y + 4.0),
+                egui::vec2(GRID_IMAGE, GRID_IMAGE),
+            );
+            path.push(index);
+            let location = tree.location(&path);
+            path.pop();
+            let thumbnail = if child.directory || !crate::thumbnails::is_image(&location) {
+                None
+            } else {
+                thumbnails.get(&location)
+            };
+            match thumbnail {
+                Some(thumbnail) => thumbnail.paint(painter, image),
+                None => {
+                    let fill = if child.directory {
+                        color(&[index], 0, true)
+                    } else {
+                        egui::Srgba::new(60, 60, 64, 255)
+                    };
+                    painter.rect_filled(image.shrink(8.0), 3.0, fill);
+                    let label = if child.directory {
+                        "DIR".to_owned()
+                    } else {
+                        location.extension().map_or(String::new(), |extension| {
+                            extension.to_string_lossy().to_uppercase()
+                        })
+                    };
+                    painter.text(
+                        image.center(),
+                        (egui::Align::Center, egui::Align::Center),
+                        label,
+                        egui::TextStyle::Small,
+                        egui::Srgba::new(230, 230, 230, 255),
+                    );
+                }
+            }
+            let characters = (GRID_CELL[0] / 7.0) as usize;
+            let name: String = child.name.chars().take(characters).collect();
+            painter.text(
+                egui::pos2(cell.center().x, image.max.y + 2.0),
+                (egui::Align::Center, egui::Align::Min),
+                name,
+                egui::TextStyle::Small,
+                egui::Srgba::new(255, 255, 255, 255),
+            );
+        }
+        result.1 = response.clicked;
+        result.2 = mouse;
+    });
+    result
+}
+
+impl DiskUsage {
+    pub fn load() -> Self {
+        let mut disk_usage = Self::default();
+        let path = match Places::path() {
+            Some(path) => path,
+            None => return disk_usage,
+        };
+        let places: Places = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|err| {
+                log::warn!("ignoring invalid places in {}: {}", path.display(), err);
+                Places::default()
+            }),
+            Err(_) => Places::default(),
+        };
+        if let Some(root) = places.root {
+            disk_usage.root = root;
+        }
+        disk_usage.bookmarks = places.bookmarks;
+        disk_usage
+    }
+
+    pub fn save(&self) {
+        let path = match Places::path() {
+            Some(path) => path,
+            None => return,
+        };
+        let places = Places {
+            root: Some(self.location().display().to_string()),
+            bookmarks: self.bookmarks.clone(),
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&path, serde_json::to_string_pretty(&places).unwrap()));
+        match result {
+            Ok(()) => log::info!("saved disk usage places to {}", path.display()),
+            Err(err) => log::warn!(
+                "cannot save disk usage places to {}: {}",
+                path.display(),
+                err
+            ),
+        }
+    }
+
+    fn location(&self) -> std::path::PathBuf {
+        match &self.tree {
+            Some(tree) => tree.location(&self.zoom),
+            None => std::path::PathBuf::from(&self.root),
+        }
+    }
+
+    fn go(&mut self, location: std::path::PathBuf) {
+        if let Some(path) = self.tree.as_ref().and_then(|tree| tree.resolve(&location)) {
+            self.zoom = path;
+            return;
+        }
+        if let Some(scan) = &self.scan {
+            scan.progress
+                .cancelled
+                .store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+        self.root = location.display().to_string();
+        self.start();
+    }
+
+    fn remember(&mut self) {
+        let location = self.location();
+        if self.back.last() != Some(&location) {
+            self.back.push(location);
+        }
+        if self.back.len() > MAX_HISTORY {
+            self.back.remove(0);
+        }
+        self.forward.clear();
+    }
+
+    fn navigate(&mut self, location: std::path::PathBuf) {
+        if location == self.location() {
+            return;
+        }
+        self.remember();
+        self.go(location);
+    }
+
+    fn back(&mut self) {
+        if let Some(location) = self.back.pop() {
+            self.forward.push(self.location());
+            self.go(location);
+        }
+    }
+
+    fn forward(&mut self) {
+        if let Some(location) = self.forward.pop() {
+            self.back.push(self.location());
+            self.go(location);
+        }
+    }
+
+    pub fn take_open_request(&mut self) -> Option<std::path::PathBuf> {
+        self.open_requested.take()
+    }
+
+    pub fn mouse_input(&mut self, button: winit::event::MouseButton) {
+        if !self.open {
+            return;
+        }
+        let (back, forward) = if cfg!(target_os = "windows") {
+            (1, 2)
+        } else if cfg!(target_os = "macos") {
+            (3, 4)
+        } else {
+            (8, 9)
+        };
+        match button {
+            winit::event::MouseButton::Other(button) if button == back => self.back(),
+            winit::event::MouseButton::Other(button) if button == forward => self.forward(),
+            _ => {}
+        }
+    }
+
+    fn start(&mut self) {
+        let root = std::path::PathBuf::from(&self.root);
+        if !root.is_dir() {
+            self.status = format!("{} is not a directory", root.display());
+            return;
+        }
+        let progress = std::sync::Arc::new(Progress {
+            files: std::sync::atomic::AtomicU64::new(0),
+            bytes: std::sync::atomic::AtomicU64::new(0),
+            cancelled: std::sync::atomic::AtomicBool::new(false),
+        });
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let thread_progress = progress.clone();
+        std::thread::Builder::new()
+            .name("disk usage scanner".to_owned())
+            .spawn(move || {
+                let name = root.display().to_string();
+                sender
+                    .send(scan_directory(&root, name, &thread_progress))
+                    .ok();
+            })
+            .unwrap();
+        self.status.clear();
+        self.scan = Some(Scan {
+            progress,
+            receiver,
+            started: std::time::Instant::now(),
+        });
+    }
+
+    fn poll(&mut self) {
+        let scan = match &self.scan {
+            Some(scan) => scan,
+            None => return,
+        };
+        if let Ok(tree) = scan.receiver.try_recv() {
+            log::info!(
+                "scanned {} files in {} ms",
+                tree.files,
+                scan.started.elapsed().as_millis()
+            );
+            self.status = format!(
+                "{} files, {} in {:.1} s",
+                tree.files,
+                crate::duplicates::format_size(tree.size),
+                scan.started.elapsed().as_secs_f32()
+            );
+            self.tree = Some(tree);
+            self.zoom.clear();
+            self.scan = None;
+            self.menu.close();
+            self.thumbnails.clear();
+        }
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        if self.scan_requested {
+            self.scan_requested = false;
+            if self.tree.is_some() && self.root != self.location() {
+                self.remember();
+            }
+            self.start();
+        }
+        if self.cancel_requested {
+            self.cancel_requested = false;
+            if let Some(scan) = &self.scan {
+                scan.progress
+                    .cancelled
+                    .store(true, std::sync::atomic::Ordering::Relaxed);
+            }
+        }
+        self.poll();
+
+        let mut open = self.open;
+        let mut context_menu = None;
+        let mut navigate = None;
+        let mut history = None;
+        let location = self.location();
+        let root = &mut self.root;
+        let tree = &self.tree;
+        let zoom = &self.zoom;
+        let view = &mut self.view;
+        let thumbnails = &mut self.thumbnails;
+        let bookmarks = &mut self.bookmarks;
+        let open_requested = &mut self.open_requested;
+        let can_go_back = !self.back.is_empty();
+        let can_go_forward = !self.forward.is_empty();
+        let scan = &self.scan;
+        let status = &self.status;
+        let scan_requested = &mut self.scan_requested;
+        let cancel_requested = &mut self.cancel_requested;
+        crate::dock::Panel::new("Disk Usage")
+            .open(&mut open)
+            .default_pos(egui::pos2(360.0, 80.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Directory");
+                    ui.add(egui::TextEdit::new(root));
+                    match scan {
+                        Some(_) => {
+                            if ui.button("Cancel").clicked {
+                                *cancel_requested = true;
+                            }
+                        }
+                        None => {
+                            let label = if tree.is_some() { "Rescan" } else { "Scan" };
+                            if ui.button(label).clicked {
+                                *scan_requested = true;
+                            }
+                        }
+                    }
+                });
+                if let Some(scan) = scan {
+                    ui.label(format!(
+                        "Scanning: {} files, {} ({:.1} s)",
+                        scan.progress
+                            .files
+                            .load(std::sync::atomic::Ordering::Relaxed),
+                        crate::duplicates::format_size(
+                            scan.progress
+                                .bytes
+                                .load(std::sync::atomic::Ordering::Relaxed)
+                        ),
+                        scan.started.elapsed().as_secs_f32()
+                    ));
+                }
+                if !status.is_empty() {
+                    ui.label(status.as_str());
+                }
+                ui.collapsing("Bookmarks", |ui| {
+                    let mut removed = None;
+                    for (index, bookmark) in bookmarks.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            if ui.button(bookmark.as_str()).clicked {
+                                navigate = Some(std::path::PathBuf::from(bookmark));
+                            }
+                            if ui.button("Remove").clicked {
+                                removed = Some(index);
+                            }
+                        });
+                    }
+                    if let Some(index) = removed {
+                        bookmarks.remove(index);
+                    }
+                    let current = location.display().to_string();
+                    if !bookmarks.contains(&current) && ui.button("Bookmark this location").clicked
+                    {
+                        bookmarks.push(current);
+                    }
+                });
+                let tree = match tree {
+                    Some(tree) => tree,
+                    None => return,
+                };
+
+                ui.horizontal(|ui| {
+                    if can_go_back && ui.button("Back").clicked {
+                        history = Some(false);
+                    }
+                    if can_go_forward && ui.button("Forward").clicked {
+                        history = Some(true);
+                    }
+                    if !zoom.is_empty() && ui.button("Up").clicked {
+                        navigate = Some(tree.location(&zoom[..zoom.len() - 1]));
+                    }
+                    ui.radio_value(view, View::Treemap, "Treemap");
+                    ui.radio_value(view, View::Grid, "Grid");
+                    let mut crumb = std::path::PathBuf::new();
+                    for component in location.components() {
+                        crumb.push(component);
+                        let name = component.as_os_str().to_string_lossy().into_owned();
+                        if crumb == location {
+                            ui.label(name);
+                        } else if ui.button(name).clicked {
+                            navigate = Some(crumb.clone());
+                        }
+                    }
+                });
+                let current = tree.get(zoom);
+
+                let (hovered, clicked, mouse) = match *view {
+                    View::Treemap => show_treemap(ui, current),
+                    View::Grid => show_grid(ui, tree, zoom, thumbnails),
+                };
+                if *view == View::Grid {
+                    let stats = &thumbnails.stats;
+                    ui.label(format!(
+                        "{} thumbnails cached, {} queued, {} generated, {} from the disk cache, \
+                         {} failed, {} evicted",
+                        stats.cached,
+                        stats.pending,
+                        stats.generated,
+                        stats.from_disk,
+                        stats.failed,
+                        stats.evicted
+                    ));
+                }
+                match hovered {
+                    Some(hovered) => {
+                        let node = current.get(&hovered);
+                        let mut target = zoom.clone();
+                        target.extend(hovered);
+                        let path = tree.location(&target);
+                        if let (true, Some(pos)) = (crate::menu::secondary_clicked(), mouse) {
+                            let size = crate::duplicates::format_size(node.size);
+                            let bookmark = if node.directory { &path } else { &location };
+                            context_menu = Some((
+                                pos,
+                                vec![
+                                    crate::menu::Item::action(
+                                        "Zoom in",
+                                        TileAction::Zoom(path.clone()),
+                                    )
+                                    .icon('🔍')
+                                    .enabled(node.directory),
+                                    crate::menu::Item::action("Up", TileAction::Up)
+                                        .enabled(!zoom.is_empty()),
+                                    crate::menu::Item::action("Top", TileAction::Top)
+                                        .enabled(!zoom.is_empty()),
+                                    crate::menu::Item::action(
+                                        "Bookmark",
+                                        TileAction::Bookmark(bookmark.display().to_string()),
+                                    )
+                                    .icon('★'),
+                                    crate::menu::Item::separator(),
+                                    crate::menu::Item::submenu(
+                                        "Copy",
+                                        vec![
+                                            crate::menu::Item::action(
+                                                "Path",
+                                                TileAction::Copy(path.display().to_string()),
+                                            ),
+                                            crate::menu::Item::action(
+                                                "Name",
+                                                TileAction::Copy(node.name.clone()),
+                                            ),
+                                            crate::menu::Item::action(
+                                                format!("Size ({})", size),
+                                                TileAction::Copy(size),
+                                            ),
+                                        ],
+                                    )
+                                    .icon('⎘'),
+                                ],
+                            ));
+                        }
+                        ui.label(path.display().to_string());
+                        ui.label(format!(
+                            "{} ({:.1}% of view){}",
+                            crate::duplicates::format_size(node.size),
+                            node.size as f32 / current.size.max(1) as f32 * 100.0,
+                            if node.directory {
+                                format!(", {} files", node.files)
+                            } else {
+                                String::new()
+                            }
+                        ));
+                        if clicked && !node.directory && crate::thumbnails::is_image(&path) {
+                            *open_requested = Some(path.clone());
+                        }
+                        if clicked {
+                            while !tree.get(&target).directory {
+                                target.pop();
+                            }
+                            navigate = Some(tree.location(&target));
+                        }
+                    }
+                    None => {
+                        ui.label(format!(
+                            "{} in {} files. Click a directory to zoom in, right-click for more.",
+                            crate::duplicates::format_size(current.size),
+                            current.files
+                        ));
+                    }
+                }
+            });
+        self.open = open;
+        self.thumbnails.end_frame();
+        if let Some((pos, items)) = context_menu {
+            self.menu.open(pos, items);
+        }
+        if !self.open {
+            self.menu.close();
+        }
+        match self.menu.show(ctx) {
+            Some(TileAction::Zoom(location)) => navigate = Some(location),
+            Some(TileAction::Up) => {
+                navigate = self.location().parent().map(std::path::Path::to_path_buf)
+            }
+            Some(TileAction::Top) => navigate = self.tree.as_ref().map(|tree| tree.location(&[])),
+            Some(TileAction::Bookmark(location)) if !self.bookmarks.contains(&location) => {
+                self.bookmarks.push(location)
+            }
+            Some(TileAction::Copy(text)) => ctx.output().copied_text = text,
+            Some(TileAction::Bookmark(_)) | None => {}
+        }
+        match history {
+            Some(false) => self.back(),
+            Some(true) => self.forward(),
+            None => {}
+        }
+        if let Some(location) = navigate {
+            self.navigate(location);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::squarify;
+
+    fn rect(width: f32, height: f32) -> egui::Rect {
+        egui::Rect::from_min_size(egui::pos2(10.0, 20.0), egui::vec2(width, height))
+    }
+
+    #[test]
+    fn nothing_to_lay_out_gives_no_cells() {
+        assert!(squarify(&[], rect(100.0, 100.0)).is_empty());
+        assert!(squarify(&[0.0, 0.0], rect(100.0, 100.0)).is_empty());
+        assert!(squarify(&[1.0], rect(0.5, 100.0)).is_empty());
+    }
+
+    #[test]
+    fn a_single_size_fills_the_rect() {
+        let cells = squarify(&[42.0], rect(60.0, 40.0));
+        assert_eq!(cells.len(), 1);
+        assert_eq!(cells[0], rect(60.0, 40.0));
+    }
+
+    #[test]
+    fn zero_sizes_at_the_end_are_skipped() {
+        assert_eq!(squarify(&[3.0, 1.0, 0.0], rect(40.0, 40.0)).len(), 2);
+    }
+
+    #[test]
+    fn cells_tile_the_rect_in_proportion() {
+        let sizes = [6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0];
+        let bounds = rect(60.0, 40.0);
+        let cells = squarify(&sizes, bounds);
+        assert_eq!(cells.len(), sizes.len());
+        let total: f32 = sizes.iter().sum();
+        for (size, cell) in sizes.iter().zip(&cells) {
+            assert!((cell.area() - bounds.area() * size / total).abs() < 1e-2);
+            assert!(cell.min.x >= bounds.min.x - 1e-3 && cell.min.y >= bounds.min.y - 1e-3);
+            assert!(cell.max.x <= bounds.max.x + 1e-3 && cell.max.y <= bounds.max.y + 1e-3);
+        }
+        for (index, a) in cells.iter().enumerate() {
+            for b in &cells[index + 1..] {
+                let overlap = a.intersect(*b);
+                assert!(overlap.is_empty() || overlap.area() < 1e-2);
+            }
+        }
+    }
+
+    #[test]
+    fn rows_stay_close_to_square() {
+        let cells = squarify(&[1.0; 16], rect(80.0, 80.0));
+        for cell in &cells {
+            let ratio = (cell.width() / cell.height()).max(cell.height() / cell.width());
+            assert!(ratio < 2.0, "{:?} is too thin", cell);
+        }
+    }
+}
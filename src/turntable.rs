@@ -0,0 +1,363 @@
+#[derive(Clone, Copy, PartialEq)]
+pub enum Format {
+    Png,
+    Gif,
+}
+
+pub struct Settings {
+    pub frames: f32,
+    pub width: f32,
+    pub height: f32,
+    pub frame_rate: f32,
+    pub format: Format,
+    pub output: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            frames: 120.0,
+            width: 800.0,
+            height: 600.0,
+            frame_rate: 30.0,
+            format: Format::Png,
+            output: "turntable".to_owned(),
+        }
+    }
+}
+
+struct Job {
+    frame: u32,
+    frames: u32,
+    size: winit::dpi::PhysicalSize<u32>,
+    format: Format,
+    texture_format: wgpu::TextureFormat,
+    output: std::path::PathBuf,
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    center: glam::Vec3,
+    start_yaw: f32,
+    saved_target: glam::Vec3,
+    saved_render_scale: f32,
+    images: Vec<image::RgbaImage>,
+}
+
+impl Job {
+    fn restore(
+        &self,
+        device: &wgpu::Device,
+        scene: &mut crate::scene::Scene,
+        window_size: winit::dpi::PhysicalSize<u32>,
+    ) {
+        scene.camera.target = self.saved_target;
+        scene.camera.yaw = self.start_yaw;
+        scene.resize(device, window_size);
+        scene.set_render_scale(device, self.saved_render_scale);
+    }
+}
+
+fn single_image(output: &std::path::Path, frames: u32) -> bool {
+    frames <= 1
+        && output
+            .extension()
+            .is_some_and(|extension| extension == "png")
+}
+
+#[derive(Default)]
+pub struct Turntable {
+    pub open: bool,
+    pub settings: Settings,
+    pub start_requested: bool,
+    pub cancel_requested: bool,
+    pub status: String,
+    job: Option<Job>,
+    encoding: Option<(std::path::PathBuf, crate::jobs::Handle<()>)>,
+}
+
+impl Turntable {
+    pub fn is_running(&self) -> bool {
+        self.job.is_some()
+    }
+
+    pub fn start(
+        &mut self,
+        device: &wgpu::Device,
+        scene: &mut crate::scene::Scene,
+        texture_format: wgpu::TextureFormat,
+    ) {
+        self.start_requested = false;
+        if self.job.is_some() {
+            return;
+        }
+        let settings = &self.settings;
+        let size = winit::dpi::PhysicalSize::new(
+            (settings.width as u32).max(1),
+            (settings.height as u32).max(1),
+        );
+        let output = std::path::PathBuf::from(&settings.output);
+        if settings.format == Format::Png && !single_image(&output, settings.frames as u32) {
+            if let Err(err) = std::fs::create_dir_all(&output) {
+                self.status = format!("Cannot create {}: {}", output.display(), err);
+                log::warn!("{}", self.status);
+                return;
+            }
+        }
+        let center = scene
+            .picker
+            .selected
+            .and_then(|index| scene.objects.get(index))
+            .map(|object| {
+                let bounds = scene.object_bounds(object);
+                (bounds.min + bounds.max) * 0.5
+            })
+            .unwrap_or(scene.camera.target);
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Turntable Target"),
+            size: wgpu::Extent3d {
+                width: size.width,
+                height: size.height,
+                depth: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: texture_format,
+            usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::COPY_SRC,
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        log::info!(
+            "exporting {} turntable frames at {}x{} to {}",
+            settings.frames as u32,
+            size.width,
+            size.height,
+            output.display()
+        );
+        self.job = Some(Job {
+            frame: 0,
+            frames: (settings.frames as u32).max(1),
+            size,
+            format: settings.format,
+            texture_format,
+            output,
+            texture,
+            view,
+            center,
+            start_yaw: scene.camera.yaw,
+            saved_target: scene.camera.target,
+            saved_render_scale: scene.render_scale(),
+            images: Vec::new(),
+        });
+        scene.set_render_scale(device, 1.0);
+    }
+
+    pub fn step(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        uploader: &mut crate::upload::Uploader,
+        scene: &mut crate::scene::Scene,
+        window_size: winit::dpi::PhysicalSize<u32>,
+        clear_color: wgpu::Color,
+    ) {
+        let job = match &mut self.job {
+            Some(job) => job,
+            None => return,
+        };
+        scene.resize(device, job.size);
+        scene.camera.target = job.center;
+        scene.camera.yaw =
+            job.start_yaw + 2.0 * std::f32::consts::PI * job.frame as f32 / job.frames as f32;
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Turntable Encoder"),
+        });
+        scene.render(device, uploader, &mut encoder, &job.view, clear_color);
+        uploader.finish();
+        queue.submit(std::iter::once(encoder.finish()));
+        uploader.recall();
+        scene.picker.after_submit();
+
+        let mut image = match crate::headless::read_texture(device, queue, &job.texture, job.size) {
+            Ok(image) => image,
+            Err(err) => {
+                job.restore(device, scene, window_size);
+                self.status = format!("Export failed at frame {}: {}", job.frame, err);
+                log::warn!("{}", self.status);
+                self.job = None;
+                return;
+            }
+        };
+        if let wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb =
+            job.texture_format
+        {
+            for pixel in image.pixels_mut() {
+                pixel.0.swap(0, 2);
+            }
+        }
+        match job.format {
+            Format::Png => {
+                let path = if single_image(&job.output, job.frames) {
+                    job.output.clone()
+                } else {
+                    job.output.join(format!("frame_{:04}.png", job.frame))
+                };
+                if let Err(err) = image.save(&path) {
+                    job.restore(device, scene, window_size);
+                    self.status = format!("Cannot write {}: {}", path.display(), err);
+                    log::warn!("{}", self.status);
+                    self.job = None;
+                    return;
+                }
+            }
+            Format::Gif => job.images.push(image),
+        }
+        job.frame += 1;
+        self.status = format!("Exported {} / {} frames", job.frame, job.frames);
+
+        if job.frame >= job.frames {
+            let mut job = self.job.take().unwrap();
+            job.restore(device, scene, window_size);
+            if job.format == Format::Gif {
+                let images = std::mem::take(&mut job.images);
+                let output = job.output.clone();
+                let frame_rate = self.settings.frame_rate;
+                let handle = crate::jobs::spawn(
+                    format!("Encode {}", job.output.display()),
+                    move |progress| write_gif(&output, images, frame_rate, progress),
+                );
+                self.encoding = Some((job.output, handle));
+                self.status = format!("Encoding {} frames", job.frames);
+                return;
+            }
+            self.status = format!("Wrote {} frames to {}", job.frames, job.output.display());
+            log::info!("{}", self.status);
+        }
+    }
+
+    pub fn cancel(
+        &mut self,
+        device: &wgpu::Device,
+        scene: &mut crate::scene::Scene,
+        window_size: winit::dpi::PhysicalSize<u32>,
+    ) {
+        self.cancel_requested = false;
+        if let Some(job) = self.job.take() {
+            job.restore(device, scene, window_size);
+            self.status = format!("Export cancelled after {} frames", job.frame);
+            log::warn!("{}", self.status);
+        }
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, scene: &mut crate::scene::Scene) {
+        if let Some(result) = self.encoding.as_ref().and_then(|(_, handle)| handle.poll()) {
+            let (path, _) = self.encoding.take().unwrap();
+            self.status = match result {
+                Ok(()) => format!("Wrote {}", path.display()),
+                Err(err) => format!("Cannot write {}: {}", path.display(), err),
+            };
+            log::info!("{}", self.status);
+        }
+        let running = self.job.is_some();
+        let encoding = self.encoding.is_some();
+        let settings = &mut self.settings;
+        let start_requested = &mut self.start_requested;
+        let cancel_requested = &mut self.cancel_requested;
+        let status = &self.status;
+        crate::dock::Panel::new("Turntable Export")
+            .open(&mut self.open)
+            .default_pos(egui::pos2(560.0, 80.0))
+            .show(ctx, |ui| {
+                ui.add(egui::Slider::f32(&mut settings.frames, 8.0..=720.0).text("frames"));
+                ui.add(egui::Slider::f32(&mut settings.width, 64.0..=3840.0).text("width"));
+                ui.add(egui::Slider::f32(&mut settings.height, 64.0..=2160.0).text("height"));
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut settings.format, Format::Png, "PNG frames");
+                    ui.radio_value(&mut settings.format, Format::Gif, "GIF");
+                });
+                if settings.format == Format::Gif {
+                    ui.add(
+                        egui::Slider::f32(&mut settings.frame_rate, 1.0..=60.0).text("frame rate"),
+                    );
+                }
+                ui.horizontal(|ui| {
+                    ui.label(match settings.format {
+                        Format::Png => "Directory",
+                        Format::Gif => "File",
+                    });
+                    ui.add(egui::TextEdit::new(&mut settings.output));
+                });
+                let selected = scene
+                    .picker
+                    .selected
+                    .and_then(|index| scene.objects.get(index));
+                ui.label(match selected {
+                    Some(object) => format!("Orbiting {}", object.name),
+                    None => "Nothing selected, orbiting the camera target".to_owned(),
+                });
+                if running {
+                    if ui.button("Cancel").clicked {
+                        *cancel_requested = true;
+                    }
+                } else if encoding {
+                    ui.label("Encoding the GIF, see the Tasks panel");
+                } else if ui.button("Export turntable").clicked && !settings.output.is_empty() {
+                    *start_requested = true;
+                }
+                if !status.is_empty() {
+                    ui.label(status.as_str());
+                }
+            });
+    }
+}
+
+pub fn write_gif(
+    path: &std::path::Path,
+    images: Vec<image::RgbaImage>,
+    frame_rate: f32,
+    progress: &crate::jobs::Progress,
+) -> Result<(), String> {
+    let delay = image::Delay::from_numer_denom_ms((1000.0 / frame_rate.max(1.0)) as u32, 1);
+    let file = std::fs::File::create(path).map_err(|err| err.to_string())?;
+    let mut encoder = image::gif::GifEncoder::new(file);
+    let count = images.len();
+    for (index, image) in images.into_iter().enumerate() {
+        progress.check()?;
+        encoder
+            .encode_frame(image::Frame::from_parts(image, 0, 0, delay))
+            .map_err(|err| err.to_string())?;
+        progress.set(
+            (index + 1) as f32 / count as f32,
+            format!("frame {} / {}", index + 1, count),
+        );
+    }
+    Ok(())
+}
+
+pub fn screenshot(engine: &mut crate::Engine, args: &[&str]) -> Result<String, String> {
+    if engine.scene.is_none() {
+        return Err("the scene is still loading".to_owned());
+    }
+    if engine.turntable.is_running() {
+        return Err("a turntable export is running".to_owned());
+    }
+    let output = args.first().copied().unwrap_or("screenshot.png");
+    if !output.ends_with(".png") {
+        return Err("screenshots are saved as .png files".to_owned());
+    }
+    engine.turntable.settings = Settings {
+        frames: 1.0,
+        width: engine.size.width as f32,
+        height: engine.size.height as f32,
+        format: Format::Png,
+        output: output.to_owned(),
+        ..Settings::default()
+    };
+    engine.turntable.start_requested = true;
+    Ok(format!("saving the scene to {}", output))
+}
+
+pub fn register_commands(registry: &mut crate::console::Registry) {
+    registry.register("screenshot", "screenshot [file.png]", screenshot);
+}
@@ -0,0 +1,394 @@
+const KEY_SIZE: f32 = 30.0;
+const KEY_GAP: f32 = 3.0;
+const PROMPTS: [&str; 6] = [
+    "the quick brown fox jumps over the lazy dog",
+    "pack my box with five dozen liquor jugs",
+    "sphinx of black quartz judge my vow",
+    "how vexingly quick daft zebras jump",
+    "a wizard's job is to vex chumps quickly in fog",
+    "crazy fredrick bought many very exquisite opal jewels",
+];
+
+type Key = (winit::event::VirtualKeyCode, &'static str, f32);
+
+const ROWS: [&[Key]; 6] = [
+    &[
+        (winit::event::VirtualKeyCode::Escape, "Esc", 1.0),
+        (winit::event::VirtualKeyCode::F1, "F1", 1.0),
+        (winit::event::VirtualKeyCode::F2, "F2", 1.0),
+        (winit::event::VirtualKeyCode::F3, "F3", 1.0),
+        (winit::event::VirtualKeyCode::F4, "F4", 1.0),
+        (winit::event::VirtualKeyCode::F5, "F5", 1.0),
+        (winit::event::VirtualKeyCode::F6, "F6", 1.0),
+        (winit::event::VirtualKeyCode::F7, "F7", 1.0),
+        (winit::event::VirtualKeyCode::F8, "F8", 1.0),
+        (winit::event::VirtualKeyCode::F9, "F9", 1.0),
+        (winit::event::VirtualKeyCode::F10, "F10", 1.0),
+        (winit::event::VirtualKeyCode::F11, "F11", 1.0),
+        (winit::event::VirtualKeyCode::F12, "F12", 1.0),
+    ],
+    &[
+        (winit::event::VirtualKeyCode::Grave, "`", 1.0),
+        (winit::event::VirtualKeyCode::Key1, "1", 1.0),
+        (winit::event::VirtualKeyCode::Key2, "2", 1.0),
+        (winit::event::VirtualKeyCode::Key3, "3", 1.0),
+        (winit::event::VirtualKeyCode::Key4, "4", 1.0),
+        (winit::event::VirtualKeyCode::Key5, "5", 1.0),
+        (winit::event::VirtualKeyCode::Key6, "6", 1.0),
+        (winit::event::VirtualKeyCode::Key7, "7", 1.0),
+        (winit::event::VirtualKeyCode::Key8, "8", 1.0),
+        (winit::event::VirtualKeyCode::Key9, "9", 1.0),
+        (winit::event::VirtualKeyCode::Key0, "0", 1.0),
+        (winit::event::VirtualKeyCode::Minus, "-", 1.0),
+        (winit::event::VirtualKeyCode::Equals, "=", 1.0),
+        (winit::event::VirtualKeyCode::Back, "Back", 2.0),
+    ],
+    &[
+        (winit::event::VirtualKeyCode::Tab, "Tab", 1.5),
+        (winit::event::VirtualKeyCode::Q, "Q", 1.0),
+        (winit::event::VirtualKeyCode::W, "W", 1.0),
+        (winit::event::VirtualKeyCode::E, "E", 1.0),
+        (winit::event::VirtualKeyCode::R, "R", 1.0),
+        (winit::event::VirtualKeyCode::T, "T", 1.0),
+        (winit::event::VirtualKeyCode::Y, "Y", 1.0),
+        (winit::event::VirtualKeyCode::U, "U", 1.0),
+        (winit::event::VirtualKeyCode::I, "I", 1.0),
+        (winit::event::VirtualKeyCode::O, "O", 1.0),
+        (winit::event::VirtualKeyCode::P, "P", 1.0),
+        (winit::event::VirtualKeyCode::LBracket, "[", 1.0),
+        (winit::event::VirtualKeyCode::RBracket, "]", 1.0),
+        (winit::event::VirtualKeyCode::Backslash, "\\", 1.5),
+    ],
+    &[
+        (winit::event::VirtualKeyCode::Capital, "Caps", 1.75),
+        (winit::event::VirtualKeyCode::A, "A", 1.0),
+        (winit::event::VirtualKeyCode::S, "S", 1.0),
+        (winit::event::VirtualKeyCode::D, "D", 1.0),
+        (winit::event::VirtualKeyCode::F, "F", 1.0),
+        (winit::event::VirtualKeyCode::G, "G", 1.0),
+        (winit::event::VirtualKeyCode::H, "H", 1.0),
+        (winit::event::VirtualKeyCode::J, "J", 1.0),
+        (winit::event::VirtualKeyCode::K, "K", 1.0),
+        (winit::event::VirtualKeyCode::L, "L", 1.0),
+        (winit::event::VirtualKeyCode::Semicolon, ";", 1.0),
+        (winit::event::VirtualKeyCode::Apostrophe, "'", 1.0),
+        (winit::event::VirtualKeyCode::Return, "Enter", 2.25),
+    ],
+    &[
+        (winit::event::VirtualKeyCode::LShift, "Shift", 2.25),
+        (winit::event::VirtualKeyCode::Z, "Z", 1.0),
+        (winit::event::VirtualKeyCode::X, "X", 1.0),
+        (winit::event::VirtualKeyCode::C, "C", 1.0),
+        (winit::event::VirtualKeyCode::V, "V", 1.0),
+        (winit::event::VirtualKeyCode::B, "B", 1.0),
+        (winit::event::VirtualKeyCode::N, "N", 1.0),
+        (winit::event::VirtualKeyCode::M, "M", 1.0),
+        (winit::event::VirtualKeyCode::Comma, ",", 1.0),
+        (winit::event::VirtualKeyCode::Period, ".", 1.0),
+        (winit::event::VirtualKeyCode::Slash, "/", 1.0),
+        (winit::event::VirtualKeyCode::RShift, "Shift", 2.75),
+    ],
+    &[
+        (winit::event::VirtualKeyCode::LControl, "Ctrl", 1.5),
+        (winit::event::VirtualKeyCode::LWin, "Super", 1.25),
+        (winit::event::VirtualKeyCode::LAlt, "Alt", 1.25),
+        (winit::event::VirtualKeyCode::Space, "", 6.25),
+        (winit::event::VirtualKeyCode::RAlt, "Alt", 1.25),
+        (winit::event::VirtualKeyCode::RWin, "Super", 1.25),
+        (winit::event::VirtualKeyCode::RControl, "Ctrl", 1.5),
+    ],
+];
+
+fn layout_key(key: winit::event::VirtualKeyCode) -> winit::event::VirtualKeyCode {
+    match key {
+        winit::event::VirtualKeyCode::Numpad0 => winit::event::VirtualKeyCode::Key0,
+        winit::event::VirtualKeyCode::Numpad1 => winit::event::VirtualKeyCode::Key1,
+        winit::event::VirtualKeyCode::Numpad2 => winit::event::VirtualKeyCode::Key2,
+        winit::event::VirtualKeyCode::Numpad3 => winit::event::VirtualKeyCode::Key3,
+        winit::event::VirtualKeyCode::Numpad4 => winit::event::VirtualKeyCode::Key4,
+        winit::event::VirtualKeyCode::Numpad5 => winit::event::VirtualKeyCode::Key5,
+        winit::event::VirtualKeyCode::Numpad6 => winit::event::VirtualKeyCode::Key6,
+        winit::event::VirtualKeyCode::Numpad7 => winit::event::VirtualKeyCode::Key7,
+        winit::event::VirtualKeyCode::Numpad8 => winit::event::VirtualKeyCode::Key8,
+        winit::event::VirtualKeyCode::Numpad9 => winit::event::VirtualKeyCode::Key9,
+        winit::event::VirtualKeyCode::NumpadEnter => winit::event::VirtualKeyCode::Return,
+        winit::event::VirtualKeyCode::NumpadSubtract => winit::event::VirtualKeyCode::Minus,
+        winit::event::VirtualKeyCode::NumpadEquals => winit::event::VirtualKeyCode::Equals,
+        winit::event::VirtualKeyCode::NumpadDivide => winit::event::VirtualKeyCode::Slash,
+        winit::event::VirtualKeyCode::NumpadDecimal => winit::event::VirtualKeyCode::Period,
+        winit::event::VirtualKeyCode::NumpadComma => winit::event::VirtualKeyCode::Comma,
+        key => key,
+    }
+}
+
+fn heat_color(heat: f32) -> egui::Srgba {
+    let stops = [[40, 50, 70], [40, 110, 200], [240, 200, 40], [230, 60, 40]];
+    let scaled = heat.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+    let index = (scaled as usize).min(stops.len() - 2);
+    let t = scaled - index as f32;
+    let (a, b) = (stops[index], stops[index + 1]);
+    let mix = |i: usize| (a[i] as f32 + (b[i] as f32 - a[i] as f32) * t) as u8;
+    egui::Srgba::new(mix(0), mix(1), mix(2), 255)
+}
+
+struct Run {
+    wpm: f32,
+    accuracy: f32,
+    seconds: f32,
+}
+
+#[derive(Default)]
+pub struct TypingTest {
+    pub open: bool,
+    prompt: usize,
+    typed: String,
+    started: Option<std::time::Instant>,
+    keystrokes: u32,
+    mistakes: u32,
+    counts: std::collections::HashMap<winit::event::VirtualKeyCode, u32>,
+    pressed: std::collections::HashSet<winit::event::VirtualKeyCode>,
+    results: Vec<Run>,
+}
+
+impl TypingTest {
+    pub fn key_input(&mut self, input: &winit::event::KeyboardInput) {
+        if !self.open {
+            return;
+        }
+        let key = match input.virtual_keycode {
+            Some(key) => layout_key(key),
+            None => return,
+        };
+        match input.state {
+            winit::event::ElementState::Pressed => {
+                if self.pressed.insert(key) {
+                    *self.counts.entry(key).or_insert(0) += 1;
+                }
+            }
+            winit::event::ElementState::Released => {
+                self.pressed.remove(&key);
+            }
+        }
+    }
+
+    pub fn received_character(&mut self, character: char) {
+        if !self.open {
+            return;
+        }
+        let target = PROMPTS[self.prompt];
+        if character == '\u{8}' {
+            self.typed.pop();
+            return;
+        }
+        let position = self.typed.chars().count();
+        if character.is_control() || position >= target.chars().count() {
+            return;
+        }
+        if self.started.is_none() {
+            self.started = Some(std::time::Instant::now());
+        }
+        self.keystrokes += 1;
+        if target.chars().nth(position) != Some(character) {
+            self.mistakes += 1;
+        }
+        self.typed.push(character);
+        if self.typed == target {
+            self.finish();
+        }
+    }
+
+    fn elapsed(&self) -> f32 {
+        self.started
+            .map_or(0.0, |started| started.elapsed().as_secs_f32())
+    }
+
+    fn correct_chars(&self) -> usize {
+        self.typed
+            .chars()
+            .zip(PROMPTS[self.prompt].chars())
+            .filter(|(typed, expected)| typed == expected)
+            .count()
+    }
+
+    fn wpm(&self) -> f32 {
+        let minutes = self.elapsed() / 60.0;
+        if minutes <= 0.0 {
+            0.0
+        } else {
+            self.correct_chars() as f32 / 5.0 / minutes
+        }
+    }
+
+    fn accuracy(&self) -> f32 {
+        if self.keystrokes == 0 {
+            1.0
+        } else {
+            1.0 - self.mistakes as f32 / self.keystrokes as f32
+        }
+    }
+
+    fn finish(&mut self) {
+        let result = Run {
+            wpm: self.wpm(),
+            accuracy: self.accuracy(),
+            seconds: self.elapsed(),
+        };
+        log::info!(
+            "typing test finished: {:.0} wpm, {:.0}% accuracy",
+            result.wpm,
+            result.accuracy * 100.0
+        );
+        self.results.push(result);
+        self.prompt = (self.prompt + 1) % PROMPTS.len();
+        self.restart();
+    }
+
+    fn restart(&mut self) {
+        self.typed.clear();
+        self.started = None;
+        self.keystrokes = 0;
+        self.mistakes = 0;
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        let mut open = self.open;
+        let mut restart_requested = false;
+        let mut skip_requested = false;
+        let mut clear_requested = false;
+        let target = PROMPTS[self.prompt];
+        let matched = target
+            .chars()
+            .zip(self.typed.chars())
+            .take_while(|(expected, typed)| expected == typed)
+            .count();
+        let typed_len = self.typed.chars().count();
+        let wpm = self.wpm();
+        let accuracy = self.accuracy();
+        let running = self.started.is_some();
+        let counts = &self.counts;
+        let pressed = &self.pressed;
+        let results = &self.results;
+        crate::dock::Panel::new("Typing Test")
+            .open(&mut open)
+            .default_pos(egui::pos2(320.0, 160.0))
+            .show(ctx, |ui| {
+                ui.label("Type the sentence below while this window is open.");
+                ui.horizontal(|ui| {
+                    let correct: String = target.chars().take(matched).collect();
+                    let wrong: String = target
+                        .chars()
+                        .skip(matched)
+                        .take(typed_len - matched)
+                        .collect();
+                    let rest: String = target.chars().skip(typed_len).collect();
+                    ui.add(
+                        egui::Label::new(correct)
+                            .text_style(egui::TextStyle::Monospace)
+                            .text_color(egui::Srgba::new(110, 220, 110, 255)),
+                    );
+                    ui.add(
+                        egui::Label::new(wrong)
+                            .text_style(egui::TextStyle::Monospace)
+                            .text_color(egui::Srgba::new(240, 80, 80, 255)),
+                    );
+                    ui.add(
+                        egui::Label::new(rest)
+                            .text_style(egui::TextStyle::Monospace)
+                            .text_color(egui::Srgba::new(150, 150, 150, 255)),
+                    );
+                });
+                ui.label(if running {
+                    format!("{:.0} wpm, {:.0}% accuracy", wpm, accuracy * 100.0)
+                } else {
+                    "Start typing to begin".to_owned()
+                });
+                ui.horizontal(|ui| {
+                    if ui.button("Restart").clicked {
+                        restart_requested = true;
+                    }
+                    if ui.button("Next sentence").clicked {
+                        skip_requested = true;
+                    }
+                    if ui.button("Clear heatmap").clicked {
+                        clear_requested = true;
+                    }
+                });
+
+                ui.separator();
+                let width = ROWS
+                    .iter()
+                    .map(|row| row.iter().map(|(_, _, units)| *units).sum::<f32>())
+                    .fold(0.0, f32::max)
+                    * KEY_SIZE;
+                let rect = ui.allocate_space(egui::vec2(width, ROWS.len() as f32 * KEY_SIZE));
+                let max = counts.values().copied().max().unwrap_or(0).max(1) as f32;
+                let painter = ui.painter();
+                for (row_index, row) in ROWS.iter().enumerate() {
+                    let mut x = rect.min.x;
+                    let y = rect.min.y + row_index as f32 * KEY_SIZE;
+                    for (key, label, units) in row.iter() {
+                        let key_rect = egui::Rect::from_min_size(
+                            egui::pos2(x, y),
+                            egui::vec2(units * KEY_SIZE - KEY_GAP, KEY_SIZE - KEY_GAP),
+                        );
+                        x += units * KEY_SIZE;
+                        let count = counts.get(key).copied().unwrap_or(0);
+                        painter.rect_filled(key_rect, 3.0, heat_color(count as f32 / max));
+                        if pressed.contains(key) {
+                            painter.rect_stroke(
+                                key_rect,
+                                3.0,
+                                egui::Stroke::new(2.0, egui::Srgba::new(255, 255, 255, 255)),
+                            );
+                        }
+                        painter.text(
+                            key_rect.center(),
+                            (egui::Align::Center, egui::Align::Center),
+                            label.to_string(),
+                            egui::TextStyle::Small,
+                            egui::Srgba::new(255, 255, 255, 255),
+                        );
+                    }
+                }
+                let total: u32 = counts.values().sum();
+                let mut top: Vec<_> = counts.iter().collect();
+                top.sort_by(|a, b| b.1.cmp(a.1));
+                ui.label(format!(
+                    "{} key presses, most used: {}",
+                    total,
+                    top.iter()
+                        .take(5)
+                        .map(|(key, count)| format!("{:?} ({})", key, count))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+
+                if !results.is_empty() {
+                    egui::CollapsingHeader::new("Results").show(ui, |ui| {
+                        for (index, result) in results.iter().enumerate().rev() {
+                            ui.label(format!(
+                                "#{}: {:.0} wpm, {:.0}% accuracy, {:.1} s",
+                                index + 1,
+                                result.wpm,
+                                result.accuracy * 100.0,
+                                result.seconds
+                            ));
+                        }
+                        let best = results.iter().map(|result| result.wpm).fold(0.0, f32::max);
+                        ui.label(format!("Best: {:.0} wpm", best));
+                    });
+                }
+            });
+        if restart_requested {
+            self.restart();
+        }
+        if skip_requested {
+            self.prompt = (self.prompt + 1) % PROMPTS.len();
+            self.restart();
+        }
+        if clear_requested {
+            self.counts.clear();
+        }
+        self.open = open;
+    }
+}
@@ -0,0 +1,138 @@
+fn key(key: winit::event::VirtualKeyCode) -> Option<egui::Key> {
+    use winit::event::VirtualKeyCode;
+    Some(match key {
+        VirtualKeyCode::LAlt | VirtualKeyCode::RAlt => egui::Key::Alt,
+        VirtualKeyCode::Back => egui::Key::Backspace,
+        VirtualKeyCode::LControl | VirtualKeyCode::RControl => egui::Key::Control,
+        VirtualKeyCode::Delete => egui::Key::Delete,
+        VirtualKeyCode::Down => egui::Key::Down,
+        VirtualKeyCode::End => egui::Key::End,
+        VirtualKeyCode::Escape => egui::Key::Escape,
+        VirtualKeyCode::Home => egui::Key::Home,
+        VirtualKeyCode::Insert => egui::Key::Insert,
+        VirtualKeyCode::Left => egui::Key::Left,
+        VirtualKeyCode::LWin | VirtualKeyCode::RWin => egui::Key::Logo,
+        VirtualKeyCode::PageDown => egui::Key::PageDown,
+        VirtualKeyCode::PageUp => egui::Key::PageUp,
+        VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => egui::Key::Enter,
+        VirtualKeyCode::Right => egui::Key::Right,
+        VirtualKeyCode::LShift | VirtualKeyCode::RShift => egui::Key::Shift,
+        VirtualKeyCode::Tab => egui::Key::Tab,
+        VirtualKeyCode::Up => egui::Key::Up,
+        _ => return None,
+    })
+}
+
+pub struct Instance {
+    context: std::sync::Arc<egui::Context>,
+    raw_input: egui::RawInput,
+    start_time: std::time::Instant,
+    scale_factor: f64,
+    modifiers: winit::event::ModifiersState,
+    paint_jobs: egui::paint::PaintJobs,
+}
+
+impl Instance {
+    pub fn new(size: winit::dpi::PhysicalSize<u32>, scale_factor: f64) -> Self {
+        let mut instance = Self {
+            context: egui::Context::new(),
+            raw_input: egui::RawInput::default(),
+            start_time: std::time::Instant::now(),
+            scale_factor,
+            modifiers: winit::event::ModifiersState::empty(),
+            paint_jobs: egui::paint::PaintJobs::default(),
+        };
+        instance.resize(size, scale_factor);
+        instance
+    }
+
+    pub fn context(&self) -> &std::sync::Arc<egui::Context> {
+        &self.context
+    }
+
+    fn resize(&mut self, size: winit::dpi::PhysicalSize<u32>, scale_factor: f64) {
+        self.scale_factor = scale_factor;
+        self.raw_input.pixels_per_point = Some(scale_factor as f32);
+        self.raw_input.screen_size =
+            egui::vec2(size.width as f32, size.height as f32) / scale_factor as f32;
+    }
+
+    pub fn input(&mut self, event: &winit::event::WindowEvent) {
+        match event {
+            winit::event::WindowEvent::Resized(size) => self.resize(*size, self.scale_factor),
+            winit::event::WindowEvent::ScaleFactorChanged {
+                scale_factor,
+                new_inner_size,
+            } => self.resize(**new_inner_size, *scale_factor),
+            winit::event::WindowEvent::CursorMoved { position, .. } => {
+                self.raw_input.mouse_pos = Some(egui::pos2(
+                    (position.x / self.scale_factor) as f32,
+                    (position.y / self.scale_factor) as f32,
+                ));
+            }
+            winit::event::WindowEvent::CursorLeft { .. } => self.raw_input.mouse_pos = None,
+            winit::event::WindowEvent::MouseInput {
+                state,
+                button: winit::event::MouseButton::Left,
+                ..
+            } => self.raw_input.mouse_down = *state == winit::event::ElementState::Pressed,
+            winit::event::WindowEvent::MouseWheel { delta, .. } => {
+                self.raw_input.scroll_delta += match delta {
+                    winit::event::MouseScrollDelta::LineDelta(x, y) => egui::vec2(*x, *y) * 24.0,
+                    winit::event::MouseScrollDelta::PixelDelta(delta) => egui::vec2(
+                        (delta.x / self.scale_factor) as f32,
+                        (delta.y / self.scale_factor) as f32,
+                    ),
+                };
+            }
+            winit::event::WindowEvent::ModifiersChanged(modifiers) => self.modifiers = *modifiers,
+            winit::event::WindowEvent::ReceivedCharacter(character) if !character.is_control() => {
+                self.raw_input
+                    .events
+                    .push(egui::Event::Text(character.to_string()));
+            }
+            winit::event::WindowEvent::KeyboardInput { input, .. } => {
+                let pressed = input.state == winit::event::ElementState::Pressed;
+                let command = if cfg!(target_os = "macos") {
+                    self.modifiers.logo()
+                } else {
+                    self.modifiers.ctrl()
+                };
+                match input.virtual_keycode {
+                    Some(winit::event::VirtualKeyCode::C) if pressed && command => {
+                        self.raw_input.events.push(egui::Event::Copy);
+                    }
+                    Some(winit::event::VirtualKeyCode::X) if pressed && command => {
+                        self.raw_input.events.push(egui::Event::Cut);
+                    }
+                    Some(code) => {
+                        if let Some(key) = key(code) {
+                            self.raw_input
+                                .events
+                                .push(egui::Event::Key { key, pressed });
+                        }
+                    }
+                    None => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn update_time(&mut self) {
+        self.raw_input.time = self.start_time.elapsed().as_secs_f64();
+    }
+
+    pub fn begin_frame(&mut self) {
+        self.context.begin_frame(self.raw_input.take());
+    }
+
+    pub fn end_frame(&mut self) {
+        let (_, paint_commands) = self.context.end_frame();
+        self.paint_jobs = self.context.tesselate(paint_commands);
+    }
+
+    pub fn paint_jobs(&self) -> &egui::paint::PaintJobs {
+        &self.paint_jobs
+    }
+}
@@ -0,0 +1,663 @@
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Vertex {
+    pos: [f32; 2],
+    tex_coord: [f32; 2],
+    color: [u8; 4],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Globals {
+    screen_size: [f32; 2],
+    padding: [f32; 2],
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct BufferStats {
+    pub allocations: u64,
+    pub allocations_last_frame: u32,
+    pub naive_allocations: u64,
+    pub vertex_capacity: u64,
+    pub index_capacity: u64,
+    pub bytes_uploaded_last_frame: u64,
+}
+
+struct PooledBuffer {
+    label: &'static str,
+    usage: wgpu::BufferUsage,
+    buffer: Option<wgpu::Buffer>,
+    capacity: wgpu::BufferAddress,
+}
+
+impl PooledBuffer {
+    fn new(label: &'static str, usage: wgpu::BufferUsage) -> Self {
+        Self {
+            label,
+            usage: usage | wgpu::BufferUsage::COPY_DST,
+            buffer: None,
+            capacity: 0,
+        }
+    }
+
+    fn reserve(&mut self, device: &wgpu::Device, size: wgpu::BufferAddress) -> bool {
+        if self.buffer.is_some() && size <= self.capacity {
+            return false;
+        }
+        let capacity = size.max(4096).next_power_of_two();
+        log::debug!("growing {} to {} bytes", self.label, capacity);
+        self.buffer = Some(device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(self.label),
+            size: capacity,
+            usage: self.usage,
+            mapped_at_creation: false,
+        }));
+        self.capacity = capacity;
+        true
+    }
+
+    fn buffer(&self) -> &wgpu::Buffer {
+        self.buffer.as_ref().unwrap()
+    }
+}
+
+fn create_sampler(device: &wgpu::Device, filter: wgpu::FilterMode) -> wgpu::Sampler {
+    device.create_sampler(&wgpu::SamplerDescriptor {
+        label: Some("UI Sampler"),
+        mag_filter: filter,
+        min_filter: filter,
+        ..Default::default()
+    })
+}
+
+pub fn paint_user_texture(
+    painter: &egui::Painter,
+    texture: usize,
+    rect: egui::Rect,
+    mask: egui::Srgba,
+) {
+    paint_user_texture_region(
+        painter,
+        texture,
+        rect,
+        egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+        mask,
+    );
+}
+
+pub fn paint_user_texture_region(
+    painter: &egui::Painter,
+    texture: usize,
+    rect: egui::Rect,
+    uv: egui::Rect,
+    mask: egui::Srgba,
+) {
+    let mut triangles = egui::paint::Triangles::with_texture(egui::TextureId::User(texture as u64));
+    triangles.add_rect_with_uv(rect, uv, mask);
+    painter.add(egui::PaintCmd::triangles(triangles));
+}
+
+pub struct AnimatedTexture {
+    frames: Vec<(usize, std::time::Duration)>,
+}
+
+impl AnimatedTexture {
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn texture(&self, frame: usize) -> usize {
+        self.frames[frame].0
+    }
+
+    pub fn delay(&self, frame: usize) -> std::time::Duration {
+        self.frames[frame].1
+    }
+}
+
+struct Draw {
+    clip_rect: egui::Rect,
+    indices: std::ops::Range<u32>,
+    base_vertex: i32,
+    texture: Option<usize>,
+}
+
+fn create_pipeline(
+    device: &wgpu::Device,
+    label: &str,
+    layout: &wgpu::PipelineLayout,
+    vs_module: &wgpu::ShaderModule,
+    fs_module: &wgpu::ShaderModule,
+    format: wgpu::TextureFormat,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(layout),
+        vertex_stage: wgpu::ProgrammableStageDescriptor {
+            module: vs_module,
+            entry_point: "main",
+        },
+        fragment_stage: Some(wgpu::ProgrammableStageDescriptor {
+            module: fs_module,
+            entry_point: "main",
+        }),
+        rasterization_state: Some(wgpu::RasterizationStateDescriptor {
+            front_face: wgpu::FrontFace::Cw,
+            cull_mode: wgpu::CullMode::None,
+            ..Default::default()
+        }),
+        primitive_topology: wgpu::PrimitiveTopology::TriangleList,
+        color_states: &[wgpu::ColorStateDescriptor {
+            format,
+            color_blend: wgpu::BlendDescriptor {
+                src_factor: wgpu::BlendFactor::One,
+                dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                operation: wgpu::BlendOperation::Add,
+            },
+            alpha_blend: wgpu::BlendDescriptor {
+                src_factor: wgpu::BlendFactor::OneMinusDstAlpha,
+                dst_factor: wgpu::BlendFactor::One,
+                operation: wgpu::BlendOperation::Add,
+            },
+            write_mask: wgpu::ColorWrite::ALL,
+        }],
+        depth_stencil_state: None,
+        vertex_state: wgpu::VertexStateDescriptor {
+            index_format: wgpu::IndexFormat::Uint32,
+            vertex_buffers: &[wgpu::VertexBufferDescriptor {
+                stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
+                step_mode: wgpu::InputStepMode::Vertex,
+                attributes: &wgpu::vertex_attr_array![0 => Float2, 1 => Float2, 2 => Uchar4],
+            }],
+        },
+        sample_count: 1,
+        sample_mask: !0,
+        alpha_to_coverage_enabled: false,
+    })
+}
+
+pub struct RenderPass {
+    pipeline: wgpu::RenderPipeline,
+    image_pipeline: wgpu::RenderPipeline,
+    globals_buffer: wgpu::Buffer,
+    globals_bind_group: wgpu::BindGroup,
+    texture_bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    filter: wgpu::FilterMode,
+    pixel_snap: bool,
+    texture: Option<(
+        wgpu::Texture,
+        wgpu::TextureView,
+        wgpu::BindGroup,
+        egui::Vec2,
+    )>,
+    image_sampler: wgpu::Sampler,
+    user_textures: Vec<Option<(Option<wgpu::Texture>, wgpu::BindGroup)>>,
+    vertex_buffer: PooledBuffer,
+    index_buffer: PooledBuffer,
+    vertices: Vec<Vertex>,
+    indices: Vec<u32>,
+    draws: Vec<Draw>,
+    physical_size: egui::Vec2,
+    pixels_per_point: f32,
+    pub buffer_stats: BufferStats,
+}
+
+impl RenderPass {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let vs_module = crate::shader::compile_builtin(
+            device,
+            "ui.vert",
+            include_str!("shaders/ui.vert"),
+            shaderc::ShaderKind::Vertex,
+        );
+        let fs_module = crate::shader::compile_builtin(
+            device,
+            "ui.frag",
+            include_str!("shaders/ui.frag"),
+            shaderc::ShaderKind::Fragment,
+        );
+
+        let globals_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("UI Globals"),
+            size: std::mem::size_of::<Globals>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let globals_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("UI Globals Bind Group Layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::VERTEX,
+                    ty: wgpu::BindingType::UniformBuffer {
+                        dynamic: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                }],
+            });
+        let globals_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("UI Globals Bind Group"),
+            layout: &globals_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer(globals_buffer.slice(..)),
+            }],
+        });
+
+        let texture_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("UI Texture Bind Group Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStage::FRAGMENT,
+                        ty: wgpu::BindingType::SampledTexture {
+                            dimension: wgpu::TextureViewDimension::D2,
+                            component_type: wgpu::TextureComponentType::Float,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStage::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler { comparison: false },
+                        count: None,
+                    },
+                ],
+            });
+
+        let sampler = create_sampler(device, wgpu::FilterMode::Linear);
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("UI Pipeline Layout"),
+            bind_group_layouts: &[&globals_bind_group_layout, &texture_bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = create_pipeline(
+            device,
+            "UI Pipeline",
+            &pipeline_layout,
+            &vs_module,
+            &fs_module,
+            format,
+        );
+        let image_fs_module = crate::shader::compile_builtin(
+            device,
+            "ui_image.frag",
+            include_str!("shaders/ui_image.frag"),
+            shaderc::ShaderKind::Fragment,
+        );
+        let image_pipeline = create_pipeline(
+            device,
+            "UI Image Pipeline",
+            &pipeline_layout,
+            &vs_module,
+            &image_fs_module,
+            format,
+        );
+        let image_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("UI Image Sampler"),
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            pipeline,
+            image_pipeline,
+            globals_buffer,
+            globals_bind_group,
+            texture_bind_group_layout,
+            sampler,
+            filter: wgpu::FilterMode::Linear,
+            pixel_snap: false,
+            texture: None,
+            image_sampler,
+            user_textures: Vec::new(),
+            vertex_buffer: PooledBuffer::new("UI Vertices", wgpu::BufferUsage::VERTEX),
+            index_buffer: PooledBuffer::new("UI Indices", wgpu::BufferUsage::INDEX),
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            draws: Vec::new(),
+            physical_size: egui::Vec2::new(1.0, 1.0),
+            pixels_per_point: 1.0,
+            buffer_stats: BufferStats::default(),
+        }
+    }
+
+    fn create_bind_group(
+        &self,
+        device: &wgpu::Device,
+        label: &str,
+        view: &wgpu::TextureView,
+        sampler: &wgpu::Sampler,
+    ) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(label),
+            layout: &self.texture_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+            ],
+        })
+    }
+
+    pub fn register_user_texture(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        image: &image::RgbaImage,
+    ) -> usize {
+        let (texture, view) = crate::wipe::image_texture(device, queue, "UI User Texture", image);
+        let bind_group =
+            self.create_bind_group(device, "UI User Bind Group", &view, &self.image_sampler);
+        self.insert_user_texture(Some(texture), bind_group)
+    }
+
+    pub fn register_native_texture(
+        &mut self,
+        device: &wgpu::Device,
+        view: &wgpu::TextureView,
+    ) -> usize {
+        let bind_group =
+            self.create_bind_group(device, "UI Native Bind Group", view, &self.image_sampler);
+        self.insert_user_texture(None, bind_group)
+    }
+
+    pub fn update_native_texture(
+        &mut self,
+        device: &wgpu::Device,
+        id: usize,
+        view: &wgpu::TextureView,
+    ) {
+        let bind_group =
+            self.create_bind_group(device, "UI Native Bind Group", view, &self.image_sampler);
+        if let Some(slot) = self.user_textures.get_mut(id) {
+            *slot = Some((None, bind_group));
+        }
+    }
+
+    fn insert_user_texture(
+        &mut self,
+        texture: Option<wgpu::Texture>,
+        bind_group: wgpu::BindGroup,
+    ) -> usize {
+        let slot = Some((texture, bind_group));
+        match self.user_textures.iter().position(Option::is_none) {
+            Some(id) => {
+                self.user_textures[id] = slot;
+                id
+            }
+            None => {
+                self.user_textures.push(slot);
+                self.user_textures.len() - 1
+            }
+        }
+    }
+
+    pub fn free_user_texture(&mut self, id: usize) {
+        if let Some(slot) = self.user_textures.get_mut(id) {
+            *slot = None;
+        }
+    }
+
+    pub fn register_animation(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        frames: &[(image::RgbaImage, std::time::Duration)],
+    ) -> AnimatedTexture {
+        AnimatedTexture {
+            frames: frames
+                .iter()
+                .map(|(image, delay)| (self.register_user_texture(device, queue, image), *delay))
+                .collect(),
+        }
+    }
+
+    pub fn free_animation(&mut self, animation: AnimatedTexture) {
+        for (id, _) in animation.frames {
+            self.free_user_texture(id);
+        }
+    }
+
+    pub fn set_filtering(
+        &mut self,
+        device: &wgpu::Device,
+        filter: wgpu::FilterMode,
+        pixel_snap: bool,
+    ) {
+        self.pixel_snap = pixel_snap;
+        if filter == self.filter {
+            return;
+        }
+        log::debug!("switching UI texture filtering to {:?}", filter);
+        self.filter = filter;
+        self.sampler = create_sampler(device, filter);
+        if let Some((texture, view, _, size)) = self.texture.take() {
+            let bind_group =
+                self.create_bind_group(device, "UI Font Bind Group", &view, &self.sampler);
+            self.texture = Some((texture, view, bind_group, size));
+        }
+    }
+
+    pub fn upload_buffers(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        physical_size: egui::Vec2,
+        pixels_per_point: f32,
+        paint_jobs: &egui::paint::PaintJobs,
+    ) {
+        self.physical_size = physical_size;
+        self.pixels_per_point = pixels_per_point;
+
+        self.vertices.clear();
+        self.indices.clear();
+        self.draws.clear();
+        let pixel_snap = self.pixel_snap;
+        let snap = |coordinate: f32| {
+            if pixel_snap {
+                (coordinate * pixels_per_point).round() / pixels_per_point
+            } else {
+                coordinate
+            }
+        };
+        for (clip_rect, triangles) in paint_jobs.iter() {
+            let base_vertex = self.vertices.len() as i32;
+            let first_index = self.indices.len() as u32;
+            self.vertices
+                .extend(triangles.vertices.iter().map(|vertex| Vertex {
+                    pos: [snap(vertex.pos.x), snap(vertex.pos.y)],
+                    tex_coord: [vertex.uv.x, vertex.uv.y],
+                    color: vertex.color.to_array(),
+                }));
+            self.indices.extend_from_slice(&triangles.indices);
+            self.draws.push(Draw {
+                clip_rect: *clip_rect,
+                indices: first_index..self.indices.len() as u32,
+                base_vertex,
+                texture: match triangles.texture_id {
+                    egui::TextureId::Egui => None,
+                    egui::TextureId::User(id) => Some(id as usize),
+                },
+            });
+        }
+
+        let globals = Globals {
+            screen_size: [
+                physical_size.x / pixels_per_point,
+                physical_size.y / pixels_per_point,
+            ],
+            padding: [0.0; 2],
+        };
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.globals_buffer,
+            0,
+            bytemuck::bytes_of(&globals),
+        );
+
+        let vertex_bytes: &[u8] = bytemuck::cast_slice(&self.vertices);
+        let index_bytes: &[u8] = bytemuck::cast_slice(&self.indices);
+        let mut allocations = 0;
+        if self
+            .vertex_buffer
+            .reserve(device, vertex_bytes.len() as wgpu::BufferAddress)
+        {
+            allocations += 1;
+        }
+        if self
+            .index_buffer
+            .reserve(device, index_bytes.len() as wgpu::BufferAddress)
+        {
+            allocations += 1;
+        }
+        uploader.write_buffer(
+            device,
+            encoder,
+            self.vertex_buffer.buffer(),
+            0,
+            vertex_bytes,
+        );
+        uploader.write_buffer(device, encoder, self.index_buffer.buffer(), 0, index_bytes);
+
+        let stats = &mut self.buffer_stats;
+        stats.allocations += allocations as u64;
+        stats.allocations_last_frame = allocations;
+        stats.naive_allocations += 2 * paint_jobs.len() as u64;
+        stats.vertex_capacity = self.vertex_buffer.capacity;
+        stats.index_capacity = self.index_buffer.capacity;
+        stats.bytes_uploaded_last_frame = (vertex_bytes.len() + index_bytes.len()) as u64;
+    }
+
+    pub fn upload_texture(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture: &egui::Texture,
+    ) {
+        let size = egui::Vec2::new(texture.width as f32, texture.height as f32);
+        let recreate = match &self.texture {
+            Some((_, _, _, current)) => *current != size,
+            None => true,
+        };
+
+        let extent = wgpu::Extent3d {
+            width: texture.width as u32,
+            height: texture.height as u32,
+            depth: 1,
+        };
+
+        if recreate {
+            let gpu_texture = device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("UI Font Texture"),
+                size: extent,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                usage: wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST,
+            });
+            let view = gpu_texture.create_view(&wgpu::TextureViewDescriptor::default());
+            let bind_group =
+                self.create_bind_group(device, "UI Font Bind Group", &view, &self.sampler);
+            self.texture = Some((gpu_texture, view, bind_group, size));
+        }
+
+        let mut pixels = Vec::with_capacity(texture.pixels.len() * 4);
+        for alpha in texture.pixels.iter() {
+            pixels.extend_from_slice(&[255, 255, 255, *alpha]);
+        }
+
+        queue.write_texture(
+            wgpu::TextureCopyView {
+                texture: &self.texture.as_ref().unwrap().0,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            &pixels,
+            wgpu::TextureDataLayout {
+                offset: 0,
+                bytes_per_row: 4 * extent.width,
+                rows_per_image: extent.height,
+            },
+            extent,
+        );
+    }
+
+    pub fn encode(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        clear_color: Option<wgpu::Color>,
+    ) {
+        let load = match clear_color {
+            Some(color) => wgpu::LoadOp::Clear(color),
+            None => wgpu::LoadOp::Load,
+        };
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: view,
+                resolve_target: None,
+                ops: wgpu::Operations { load, store: true },
+            }],
+            depth_stencil_attachment: None,
+        });
+
+        let texture_bind_group = match (&self.texture, self.draws.is_empty()) {
+            (Some((_, _, bind_group, _)), false) => bind_group,
+            _ => return,
+        };
+
+        render_pass.set_bind_group(0, &self.globals_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.buffer().slice(..));
+        render_pass.set_index_buffer(self.index_buffer.buffer().slice(..));
+
+        let width = self.physical_size.x as u32;
+        let height = self.physical_size.y as u32;
+        let mut bound = None;
+        for draw in self.draws.iter() {
+            let ppp = self.pixels_per_point;
+            let x = ((draw.clip_rect.min.x * ppp).max(0.0) as u32).min(width);
+            let y = ((draw.clip_rect.min.y * ppp).max(0.0) as u32).min(height);
+            let right = ((draw.clip_rect.max.x * ppp).max(0.0) as u32).min(width);
+            let bottom = ((draw.clip_rect.max.y * ppp).max(0.0) as u32).min(height);
+            if right <= x || bottom <= y {
+                continue;
+            }
+            if bound != Some(draw.texture) {
+                match draw.texture {
+                    Some(texture) => match self.user_textures.get(texture) {
+                        Some(Some((_, bind_group))) => {
+                            render_pass.set_pipeline(&self.image_pipeline);
+                            render_pass.set_bind_group(1, bind_group, &[]);
+                        }
+                        _ => continue,
+                    },
+                    None => {
+                        render_pass.set_pipeline(&self.pipeline);
+                        render_pass.set_bind_group(1, texture_bind_group, &[]);
+                    }
+                }
+                bound = Some(draw.texture);
+            }
+            render_pass.set_scissor_rect(x, y, right - x, bottom - y);
+            render_pass.draw_indexed(draw.indices.clone(), draw.base_vertex, 0..1);
+        }
+    }
+}
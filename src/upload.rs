@@ -0,0 +1,52 @@
+const CHUNK_SIZE: wgpu::BufferAddress = 1 << 20;
+
+pub struct Uploader {
+    belt: wgpu::util::StagingBelt,
+    pool: futures::executor::LocalPool,
+    spawner: futures::executor::LocalSpawner,
+    pub bytes_last_frame: u64,
+    bytes_this_frame: u64,
+}
+
+impl Uploader {
+    pub fn new() -> Self {
+        let pool = futures::executor::LocalPool::new();
+        let spawner = pool.spawner();
+        Self {
+            belt: wgpu::util::StagingBelt::new(CHUNK_SIZE),
+            pool,
+            spawner,
+            bytes_last_frame: 0,
+            bytes_this_frame: 0,
+        }
+    }
+
+    pub fn write_buffer(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        data: &[u8],
+    ) {
+        let size = match wgpu::BufferSize::new(data.len() as u64) {
+            Some(size) => size,
+            None => return,
+        };
+        self.belt
+            .write_buffer(encoder, target, offset, size, device)
+            .copy_from_slice(data);
+        self.bytes_this_frame += data.len() as u64;
+    }
+
+    pub fn finish(&mut self) {
+        self.belt.finish();
+    }
+
+    pub fn recall(&mut self) {
+        futures::task::SpawnExt::spawn(&self.spawner, self.belt.recall()).unwrap();
+        self.pool.run_until_stalled();
+        self.bytes_last_frame = self.bytes_this_frame;
+        self.bytes_this_frame = 0;
+    }
+}
@@ -0,0 +1,235 @@
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    inverse_projection: [f32; 16],
+    channels: [f32; 4],
+    range: [f32; 2],
+    mode: u32,
+    padding: u32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Output {
+    Final,
+    SceneColor,
+    Depth,
+    Normals,
+    AmbientOcclusion,
+    Bloom(usize),
+}
+
+impl Output {
+    pub fn name(self) -> String {
+        match self {
+            Output::Final => "Final image".to_owned(),
+            Output::SceneColor => "Scene color (HDR)".to_owned(),
+            Output::Depth => "Depth".to_owned(),
+            Output::Normals => "Normals".to_owned(),
+            Output::AmbientOcclusion => "Ambient occlusion".to_owned(),
+            Output::Bloom(level) => format!("Bloom mip {}", level),
+        }
+    }
+
+    fn mode(self) -> u32 {
+        match self {
+            Output::Depth => 1,
+            Output::Normals => 2,
+            _ => 0,
+        }
+    }
+
+    fn default_range(self) -> [f32; 2] {
+        match self {
+            Output::Depth => [0.0, 50.0],
+            _ => [0.0, 1.0],
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Display {
+    Fullscreen,
+    Inset,
+}
+
+pub struct Settings {
+    pub output: Output,
+    pub display: Display,
+    pub channels: [bool; 4],
+    pub range: [f32; 2],
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            output: Output::Final,
+            display: Display::Fullscreen,
+            channels: [true, true, true, false],
+            range: [0.0, 1.0],
+        }
+    }
+}
+
+impl Settings {
+    pub fn set_output(&mut self, output: Output) {
+        if self.output != output {
+            self.output = output;
+            self.range = output.default_range();
+        }
+    }
+}
+
+pub struct Visualizer {
+    pub settings: Settings,
+    layout: wgpu::BindGroupLayout,
+    pipeline: wgpu::RenderPipeline,
+    uniform_buffer: wgpu::Buffer,
+    sampler: wgpu::Sampler,
+}
+
+impl Visualizer {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Visualizer Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::SampledTexture {
+                        dimension: wgpu::TextureViewDimension::D2,
+                        component_type: wgpu::TextureComponentType::Float,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler { comparison: false },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::UniformBuffer {
+                        dynamic: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let pipeline = crate::blit::fullscreen_pipeline(
+            device,
+            "Visualizer Pipeline",
+            &[&layout],
+            "visualize.frag",
+            include_str!("shaders/visualize.frag"),
+            format,
+            wgpu::BlendDescriptor::REPLACE,
+        );
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Visualizer Uniforms"),
+            size: std::mem::size_of::<Uniforms>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Visualizer Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            ..Default::default()
+        });
+
+        Self {
+            settings: Settings::default(),
+            layout,
+            pipeline,
+            uniform_buffer,
+            sampler,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw(
+        &self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        cache: &mut crate::cache::Cache,
+        generation: u64,
+        source: &wgpu::TextureView,
+        projection: &glam::Mat4,
+        target: &wgpu::TextureView,
+        size: winit::dpi::PhysicalSize<u32>,
+    ) {
+        let channel = |enabled: bool| if enabled { 1.0 } else { 0.0 };
+        let uniforms = Uniforms {
+            inverse_projection: projection.inverse().to_cols_array(),
+            channels: [
+                channel(self.settings.channels[0]),
+                channel(self.settings.channels[1]),
+                channel(self.settings.channels[2]),
+                channel(self.settings.channels[3]),
+            ],
+            range: self.settings.range,
+            mode: self.settings.output.mode(),
+            padding: 0,
+        };
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+        let key = crate::cache::key(&("Visualizer", self.settings.output, generation));
+        let bind_group = cache.bind_group(key, || {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Visualizer Bind Group"),
+                layout: &self.layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(source),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&self.sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::Buffer(self.uniform_buffer.slice(..)),
+                    },
+                ],
+            })
+        });
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+        if self.settings.display == Display::Inset {
+            let width = size.width as f32 / 3.0;
+            let height = size.height as f32 / 3.0;
+            render_pass.set_viewport(
+                size.width as f32 - width - 16.0,
+                size.height as f32 - height - 16.0,
+                width,
+                height,
+                0.0,
+                1.0,
+            );
+        }
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
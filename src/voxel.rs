@@ -0,0 +1,652 @@
+const CHUNK_SIZE: usize = 16;
+const VERTICAL_CHUNKS: i32 = 3;
+const WORKERS: usize = 4;
+
+const AIR: u8 = 0;
+const GRASS: u8 = 1;
+const DIRT: u8 = 2;
+const STONE: u8 = 3;
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct VoxelVertex {
+    position: [f32; 3],
+    normal: [f32; 3],
+    material: u32,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Settings {
+    pub enabled: bool,
+    pub noise: crate::noise::Fbm,
+    pub chunks: i32,
+    pub voxel_size: f32,
+    pub ground: f32,
+    pub amplitude: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            noise: crate::noise::Fbm {
+                frequency: 0.04,
+                octaves: 4,
+                ..crate::noise::Fbm::default()
+            },
+            chunks: 4,
+            voxel_size: 0.5,
+            ground: 4.0,
+            amplitude: 12.0,
+        }
+    }
+}
+
+struct ChunkMesh {
+    coord: [i32; 3],
+    vertices: Vec<VoxelVertex>,
+    indices: Vec<u32>,
+    duration: std::time::Duration,
+}
+
+struct Chunk {
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    index_count: u32,
+    bounds: crate::culling::Aabb,
+}
+
+struct Job {
+    generation: u64,
+    settings: Settings,
+    queue: std::sync::Mutex<Vec<[i32; 3]>>,
+    current: std::sync::Arc<std::sync::atomic::AtomicU64>,
+}
+
+fn density(settings: &Settings, perlin: &crate::noise::Perlin, position: [i32; 3]) -> f32 {
+    let [x, y, z] = position;
+    settings.ground - y as f32
+        + settings.noise.sample3(perlin, x as f32, y as f32, z as f32) * settings.amplitude
+}
+
+fn voxels(settings: &Settings, perlin: &crate::noise::Perlin, coord: [i32; 3]) -> Vec<u8> {
+    let padded = CHUNK_SIZE + 2;
+    let origin = [
+        coord[0] * CHUNK_SIZE as i32 - 1,
+        coord[1] * CHUNK_SIZE as i32 - 1,
+        coord[2] * CHUNK_SIZE as i32 - 1,
+    ];
+    let half = settings.chunks / 2 * CHUNK_SIZE as i32;
+    let horizontal = -half..(settings.chunks * CHUNK_SIZE as i32 - half);
+    let vertical = -(CHUNK_SIZE as i32)..(VERTICAL_CHUNKS - 1) * CHUNK_SIZE as i32;
+    let mut voxels = vec![AIR; padded * padded * padded];
+    for z in 0..padded {
+        for x in 0..padded {
+            let (world_x, world_z) = (origin[0] + x as i32, origin[2] + z as i32);
+            if !horizontal.contains(&world_x) || !horizontal.contains(&world_z) {
+                continue;
+            }
+            let mut above = density(
+                settings,
+                perlin,
+                [world_x, origin[1] + padded as i32, world_z],
+            );
+            for y in (0..padded).rev() {
+                let position = [world_x, origin[1] + y as i32, world_z];
+                let value = density(settings, perlin, position);
+                voxels[(z * padded + y) * padded + x] =
+                    if value <= 0.0 || !vertical.contains(&position[1]) {
+                        AIR
+                    } else if above <= 0.0 {
+                        GRASS
+                    } else if value < 3.0 {
+                        DIRT
+                    } else {
+                        STONE
+                    };
+                above = value;
+            }
+        }
+    }
+    voxels
+}
+
+fn greedy_mesh(voxels: &[u8], coord: [i32; 3], voxel_size: f32) -> (Vec<VoxelVertex>, Vec<u32>) {
+    let padded = CHUNK_SIZE + 2;
+    let get = |p: [i32; 3]| {
+        voxels
+            [(((p[2] + 1) as usize * padded) + (p[1] + 1) as usize) * padded + (p[0] + 1) as usize]
+    };
+    let origin = [
+        (coord[0] * CHUNK_SIZE as i32) as f32,
+        (coord[1] * CHUNK_SIZE as i32) as f32,
+        (coord[2] * CHUNK_SIZE as i32) as f32,
+    ];
+    let size = CHUNK_SIZE as i32;
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    let mut mask = vec![0i16; CHUNK_SIZE * CHUNK_SIZE];
+
+    for d in 0..3 {
+        let u = (d + 1) % 3;
+        let v = (d + 2) % 3;
+        let mut q = [0; 3];
+        q[d] = 1;
+        for slice in -1..size {
+            let mut x = [0; 3];
+            x[d] = slice;
+            for j in 0..size {
+                for i in 0..size {
+                    x[u] = i;
+                    x[v] = j;
+                    let a = get(x);
+                    let b = get([x[0] + q[0], x[1] + q[1], x[2] + q[2]]);
+                    mask[(j * size + i) as usize] = if a != AIR && b == AIR && slice >= 0 {
+                        a as i16
+                    } else if a == AIR && b != AIR && slice + 1 < size {
+                        -(b as i16)
+                    } else {
+                        0
+                    };
+                }
+            }
+
+            let mut j = 0;
+            while j < size {
+                let mut i = 0;
+                while i < size {
+                    let value = mask[(j * size + i) as usize];
+                    if value == 0 {
+                        i += 1;
+                        continue;
+                    }
+                    let mut width = 1;
+                    while i + width < size && mask[(j * size + i + width) as usize] == value {
+                        width += 1;
+                    }
+                    let mut height = 1;
+                    'grow: while j + height < size {
+                        for k in 0..width {
+                            if mask[((j + height) * size + i + k) as usize] != value {
+                                break 'grow;
+                            }
+                        }
+                        height += 1;
+                    }
+
+                    let mut base = [0.0; 3];
+                    base[d] = (slice + 1) as f32;
+                    base[u] = i as f32;
+                    base[v] = j as f32;
+                    let mut du = [0.0; 3];
+                    du[u] = width as f32;
+                    let mut dv = [0.0; 3];
+                    dv[v] = height as f32;
+                    let mut normal = [0.0; 3];
+                    normal[d] = if value > 0 { 1.0 } else { -1.0 };
+                    let corner = |offset_u: f32, offset_v: f32| {
+                        let axis = |axis: usize| {
+                            (origin[axis] + base[axis] + du[axis] * offset_u + dv[axis] * offset_v)
+                                * voxel_size
+                        };
+                        VoxelVertex {
+                            position: [axis(0), axis(1), axis(2)],
+                            normal,
+                            material: value.unsigned_abs() as u32,
+                        }
+                    };
+                    let first = vertices.len() as u32;
+                    if value > 0 {
+                        vertices.extend_from_slice(&[
+                            corner(0.0, 0.0),
+                            corner(1.0, 0.0),
+                            corner(1.0, 1.0),
+                            corner(0.0, 1.0),
+                        ]);
+                    } else {
+                        vertices.extend_from_slice(&[
+                            corner(0.0, 0.0),
+                            corner(0.0, 1.0),
+                            corner(1.0, 1.0),
+                            corner(1.0, 0.0),
+                        ]);
+                    }
+                    indices.extend_from_slice(&[
+                        first,
+                        first + 1,
+                        first + 2,
+                        first,
+                        first + 2,
+                        first + 3,
+                    ]);
+
+                    for h in 0..height {
+                        for k in 0..width {
+                            mask[((j + h) * size + i + k) as usize] = 0;
+                        }
+                    }
+                    i += width;
+                }
+                j += 1;
+            }
+        }
+    }
+    (vertices, indices)
+}
+
+fn work(job: std::sync::Arc<Job>, sender: std::sync::mpsc::Sender<(u64, ChunkMesh)>) {
+    let perlin = crate::noise::Perlin::new(job.settings.noise.seed);
+    loop {
+        if job.current.load(std::sync::atomic::Ordering::Relaxed) != job.generation {
+            return;
+        }
+        let coord = match job.queue.lock().unwrap().pop() {
+            Some(coord) => coord,
+            None => return,
+        };
+        let time = std::time::Instant::now();
+        let voxels = voxels(&job.settings, &perlin, coord);
+        let (vertices, indices) = greedy_mesh(&voxels, coord, job.settings.voxel_size);
+        let mesh = ChunkMesh {
+            coord,
+            vertices,
+            indices,
+            duration: time.elapsed(),
+        };
+        if sender.send((job.generation, mesh)).is_err() {
+            return;
+        }
+    }
+}
+
+pub struct VoxelWorld {
+    pub settings: Settings,
+    pub chunks_total: usize,
+    pub chunks_meshed: usize,
+    pub quads: usize,
+    pub meshing_time: std::time::Duration,
+    pub started: Option<std::time::Instant>,
+    pub elapsed: std::time::Duration,
+    regenerate_requested: bool,
+    built: Option<Settings>,
+    generation: std::sync::Arc<std::sync::atomic::AtomicU64>,
+    receiver: Option<std::sync::mpsc::Receiver<(u64, ChunkMesh)>>,
+    chunks: Vec<Chunk>,
+    pipeline: wgpu::RenderPipeline,
+    msaa_pipeline: wgpu::RenderPipeline,
+}
+
+impl VoxelWorld {
+    pub fn new(device: &wgpu::Device, scene_layout: &wgpu::BindGroupLayout) -> Self {
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Voxel Pipeline Layout"),
+            bind_group_layouts: &[scene_layout],
+            push_constant_ranges: &[],
+        });
+        let vs_module = crate::shader::compile_builtin(
+            device,
+            "voxel.vert",
+            include_str!("shaders/voxel.vert"),
+            shaderc::ShaderKind::Vertex,
+        );
+        let fs_module = crate::shader::compile_builtin(
+            device,
+            "voxel.frag",
+            include_str!("shaders/voxel.frag"),
+            shaderc::ShaderKind::Fragment,
+        );
+
+        Self {
+            settings: Settings::default(),
+            chunks_total: 0,
+            chunks_meshed: 0,
+            quads: 0,
+            meshing_time: std::time::Duration::default(),
+            started: None,
+            elapsed: std::time::Duration::default(),
+            regenerate_requested: false,
+            built: None,
+            generation: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            receiver: None,
+            chunks: Vec::new(),
+            pipeline: create_pipeline(device, &layout, &vs_module, &fs_module, 1),
+            msaa_pipeline: create_pipeline(
+                device,
+                &layout,
+                &vs_module,
+                &fs_module,
+                crate::scene::MSAA_SAMPLES,
+            ),
+        }
+    }
+
+    pub fn regenerate(&mut self) {
+        self.regenerate_requested = true;
+    }
+
+    pub fn is_meshing(&self) -> bool {
+        self.started.is_some()
+    }
+
+    fn start(&mut self) {
+        let generation = self
+            .generation
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+            + 1;
+        let settings = self.settings;
+        let half = settings.chunks / 2;
+        let mut queue = Vec::new();
+        for y in -1..VERTICAL_CHUNKS - 1 {
+            for z in -half..settings.chunks - half {
+                for x in -half..settings.chunks - half {
+                    queue.push([x, y, z]);
+                }
+            }
+        }
+        queue.sort_by_key(|coord| std::cmp::Reverse(coord[0].abs() + coord[2].abs()));
+
+        self.chunks_total = queue.len();
+        self.chunks_meshed = 0;
+        self.quads = 0;
+        self.meshing_time = std::time::Duration::default();
+        self.started = Some(std::time::Instant::now());
+        self.chunks.clear();
+        self.built = Some(settings);
+        self.regenerate_requested = false;
+
+        let job = std::sync::Arc::new(Job {
+            generation,
+            settings,
+            queue: std::sync::Mutex::new(queue),
+            current: self.generation.clone(),
+        });
+        let (sender, receiver) = std::sync::mpsc::channel();
+        for index in 0..WORKERS {
+            let job = job.clone();
+            let sender = sender.clone();
+            std::thread::Builder::new()
+                .name(format!("voxel mesher {}", index))
+                .spawn(move || work(job, sender))
+                .unwrap();
+        }
+        self.receiver = Some(receiver);
+    }
+
+    pub fn update(&mut self, device: &wgpu::Device) {
+        if !self.settings.enabled {
+            return;
+        }
+        if self.regenerate_requested || self.built != Some(self.settings) {
+            self.start();
+        }
+        let meshes: Vec<(u64, ChunkMesh)> = match &self.receiver {
+            Some(receiver) => receiver.try_iter().collect(),
+            None => return,
+        };
+        let generation = self.generation.load(std::sync::atomic::Ordering::Relaxed);
+        let voxel_size = self.settings.voxel_size;
+        for (mesh_generation, mesh) in meshes {
+            if mesh_generation != generation {
+                continue;
+            }
+            self.chunks_meshed += 1;
+            self.meshing_time += mesh.duration;
+            self.quads += mesh.indices.len() / 6;
+            if mesh.indices.is_empty() {
+                continue;
+            }
+            let min = glam::Vec3::new(
+                mesh.coord[0] as f32,
+                mesh.coord[1] as f32,
+                mesh.coord[2] as f32,
+            ) * (CHUNK_SIZE as f32 * voxel_size);
+            self.chunks.push(Chunk {
+                vertex_buffer: wgpu::util::DeviceExt::create_buffer_init(
+                    device,
+                    &wgpu::util::BufferInitDescriptor {
+                        label: Some("Voxel Vertices"),
+                        contents: bytemuck::cast_slice(&mesh.vertices),
+                        usage: wgpu::BufferUsage::VERTEX,
+                    },
+                ),
+                index_buffer: wgpu::util::DeviceExt::create_buffer_init(
+                    device,
+                    &wgpu::util::BufferInitDescriptor {
+                        label: Some("Voxel Indices"),
+                        contents: bytemuck::cast_slice(&mesh.indices),
+                        usage: wgpu::BufferUsage::INDEX,
+                    },
+                ),
+                index_count: mesh.indices.len() as u32,
+                bounds: crate::culling::Aabb {
+                    min,
+                    max: min + glam::Vec3::splat(CHUNK_SIZE as f32 * voxel_size),
+                },
+            });
+        }
+        if self.chunks_meshed >= self.chunks_total {
+            if let Some(started) = self.started.take() {
+                self.elapsed = started.elapsed();
+                self.receiver = None;
+                log::info!(
+                    "meshed {} voxel chunks into {} quads in {} ms",
+                    self.chunks_total,
+                    self.quads,
+                    self.elapsed.as_millis()
+                );
+            }
+        }
+    }
+
+    pub fn draw<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        msaa: bool,
+        frustum: &crate::culling::Frustum,
+        stats: &mut crate::scene::Stats,
+    ) {
+        if !self.settings.enabled || self.chunks.is_empty() {
+            return;
+        }
+        if msaa {
+            render_pass.set_pipeline(&self.msaa_pipeline);
+        } else {
+            render_pass.set_pipeline(&self.pipeline);
+        }
+        for chunk in self.chunks.iter() {
+            if !frustum.intersects(&chunk.bounds) {
+                continue;
+            }
+            render_pass.set_vertex_buffer(0, chunk.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(chunk.index_buffer.slice(..));
+            render_pass.draw_indexed(0..chunk.index_count, 0, 0..1);
+            stats.draw_calls += 1;
+            stats.triangles += (chunk.index_count / 3) as u64;
+        }
+    }
+}
+
+fn create_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    vs_module: &wgpu::ShaderModule,
+    fs_module: &wgpu::ShaderModule,
+    sample_count: u32,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Voxel Pipeline"),
+        layout: Some(layout),
+        vertex_stage: wgpu::ProgrammableStageDescriptor {
+            module: vs_module,
+            entry_point: "main",
+        },
+        fragment_stage: Some(wgpu::ProgrammableStageDescriptor {
+            module: fs_module,
+            entry_point: "main",
+        }),
+        rasterization_state: Some(wgpu::RasterizationStateDescriptor {
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: wgpu::CullMode::Back,
+            ..Default::default()
+        }),
+        primitive_topology: wgpu::PrimitiveTopology::TriangleList,
+        color_states: &[wgpu::ColorStateDescriptor {
+            format: crate::scene::HDR_FORMAT,
+            color_blend: wgpu::BlendDescriptor::REPLACE,
+            alpha_blend: wgpu::BlendDescriptor::REPLACE,
+            write_mask: wgpu::ColorWrite::ALL,
+        }],
+        depth_stencil_state: Some(wgpu::DepthStencilStateDescriptor {
+            format: crate::scene::DEPTH_FORMAT,
+            depth_write_enabled: true,
+            depth_compare: wgpu::CompareFunction::Less,
+            stencil: wgpu::StencilStateDescriptor::default(),
+        }),
+        vertex_state: wgpu::VertexStateDescriptor {
+            index_format: wgpu::IndexFormat::Uint32,
+            vertex_buffers: &[wgpu::VertexBufferDescriptor {
+                stride: std::mem::size_of::<VoxelVertex>() as wgpu::BufferAddress,
+                step_mode: wgpu::InputStepMode::Vertex,
+                attributes: &wgpu::vertex_attr_array![0 => Float3, 1 => Float3, 2 => Uint],
+            }],
+        },
+        sample_count,
+        sample_mask: !0,
+        alpha_to_coverage_enabled: false,
+    })
+}
+
+#[derive(Default)]
+pub struct VoxelPanel {
+    pub open: bool,
+}
+
+impl VoxelPanel {
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>, scene: &mut crate::scene::Scene) {
+        crate::dock::Panel::new("Voxel Terrain")
+            .open(&mut self.open)
+            .default_pos(egui::pos2(560.0, 80.0))
+            .show(ctx, |ui| {
+                let world = &mut scene.voxels;
+                let settings = &mut world.settings;
+                ui.checkbox(&mut settings.enabled, "Enabled");
+                let noise = &mut settings.noise;
+                let mut seed = noise.seed as f32;
+                ui.add(egui::Slider::f32(&mut seed, 0.0..=1000.0).text("seed"));
+                noise.seed = seed as u32;
+                let mut octaves = noise.octaves as f32;
+                ui.add(egui::Slider::f32(&mut octaves, 1.0..=6.0).text("octaves"));
+                noise.octaves = octaves as u32;
+                ui.add(egui::Slider::f32(&mut noise.frequency, 0.005..=0.15).text("frequency"));
+                ui.add(egui::Slider::f32(&mut noise.lacunarity, 1.5..=3.0).text("lacunarity"));
+                ui.add(egui::Slider::f32(&mut noise.persistence, 0.2..=0.8).text("persistence"));
+                ui.add(egui::Slider::f32(&mut settings.ground, -12.0..=28.0).text("ground level"));
+                ui.add(egui::Slider::f32(&mut settings.amplitude, 0.0..=32.0).text("amplitude"));
+                ui.add(egui::Slider::f32(&mut settings.voxel_size, 0.1..=1.0).text("voxel size"));
+                ui.horizontal(|ui| {
+                    ui.label("Chunks");
+                    for chunks in [2, 4, 6, 8].iter() {
+                        ui.radio_value(&mut settings.chunks, *chunks, format!("{0}x{0}", chunks));
+                    }
+                });
+                if ui.button("Regenerate").clicked {
+                    world.regenerate();
+                }
+
+                ui.separator();
+                ui.label(format!(
+                    "{} / {} chunks meshed on {} workers",
+                    world.chunks_meshed, world.chunks_total, WORKERS
+                ));
+                ui.label(format!(
+                    "{} quads, {:.1} ms of meshing",
+                    world.quads,
+                    world.meshing_time.as_secs_f32() * 1000.0
+                ));
+                match world.started {
+                    Some(started) => ui.label(format!(
+                        "Meshing... {:.1} s",
+                        started.elapsed().as_secs_f32()
+                    )),
+                    None => ui.label(format!(
+                        "Finished in {:.1} ms",
+                        world.elapsed.as_secs_f32() * 1000.0
+                    )),
+                };
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{greedy_mesh, AIR, CHUNK_SIZE, GRASS, STONE};
+
+    fn grid(fill: impl Fn([i32; 3]) -> u8) -> Vec<u8> {
+        let padded = CHUNK_SIZE + 2;
+        let mut voxels = vec![AIR; padded * padded * padded];
+        for z in 0..padded {
+            for y in 0..padded {
+                for x in 0..padded {
+                    voxels[(z * padded + y) * padded + x] =
+                        fill([x as i32 - 1, y as i32 - 1, z as i32 - 1]);
+                }
+            }
+        }
+        voxels
+    }
+
+    fn inside(p: [i32; 3]) -> bool {
+        p.iter().all(|axis| (0..CHUNK_SIZE as i32).contains(axis))
+    }
+
+    #[test]
+    fn empty_chunks_have_no_faces() {
+        let (vertices, indices) = greedy_mesh(&grid(|_| AIR), [0, 0, 0], 1.0);
+        assert!(vertices.is_empty());
+        assert!(indices.is_empty());
+    }
+
+    #[test]
+    fn a_single_voxel_gets_six_faces() {
+        let voxels = grid(|p| if p == [2, 3, 4] { STONE } else { AIR });
+        let (vertices, indices) = greedy_mesh(&voxels, [0, 0, 0], 0.5);
+        assert_eq!(vertices.len(), 24);
+        assert_eq!(indices.len(), 36);
+        for vertex in &vertices {
+            assert!(vertex.position[0] == 1.0 || vertex.position[0] == 1.5);
+            assert!(vertex.position[1] == 1.5 || vertex.position[1] == 2.0);
+            assert!(vertex.position[2] == 2.0 || vertex.position[2] == 2.5);
+            assert_eq!(vertex.material, STONE as u32);
+        }
+    }
+
+    #[test]
+    fn a_solid_chunk_merges_into_one_quad_per_side() {
+        let voxels = grid(|p| if inside(p) { STONE } else { AIR });
+        let (vertices, _) = greedy_mesh(&voxels, [1, 0, -1], 1.0);
+        assert_eq!(vertices.len(), 6 * 4);
+        let size = CHUNK_SIZE as f32;
+        for vertex in &vertices {
+            assert!(vertex.position[0] == size || vertex.position[0] == 2.0 * size);
+            assert!(vertex.position[1] == 0.0 || vertex.position[1] == size);
+            assert!(vertex.position[2] == -size || vertex.position[2] == 0.0);
+        }
+    }
+
+    #[test]
+    fn faces_against_solid_neighbours_are_skipped() {
+        let (vertices, _) = greedy_mesh(&grid(|_| STONE), [0, 0, 0], 1.0);
+        assert!(vertices.is_empty());
+    }
+
+    #[test]
+    fn different_materials_are_not_merged() {
+        let voxels = grid(|p| match p {
+            [x, 0, _] if inside(p) && x < 8 => GRASS,
+            [_, 0, _] if inside(p) => STONE,
+            _ => AIR,
+        });
+        let (vertices, _) = greedy_mesh(&voxels, [0, 0, 0], 1.0);
+        assert_eq!(vertices.len(), 10 * 4);
+    }
+}
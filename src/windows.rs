@@ -0,0 +1,149 @@
+pub struct SecondaryWindow {
+    pub window: winit::window::Window,
+    title: String,
+    surface: wgpu::Surface,
+    swap_chain_desc: wgpu::SwapChainDescriptor,
+    swap_chain: wgpu::SwapChain,
+    ui_instance: crate::ui_instance::Instance,
+    ui_render_pass: crate::ui_pass::RenderPass,
+    scale_factor: f64,
+    font_texture_version: Option<u64>,
+    close_requested: bool,
+}
+
+impl SecondaryWindow {
+    pub fn new(
+        instance: &wgpu::Instance,
+        device: &wgpu::Device,
+        window: winit::window::Window,
+        title: String,
+    ) -> Self {
+        let size = window.inner_size();
+        let scale_factor = window.scale_factor();
+        let surface = unsafe { instance.create_surface(&window) };
+        let swap_chain_desc = wgpu::SwapChainDescriptor {
+            usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+            format: wgpu::TextureFormat::Bgra8UnormSrgb,
+            width: size.width,
+            height: size.height,
+            present_mode: wgpu::PresentMode::Fifo,
+        };
+        let swap_chain = device.create_swap_chain(&surface, &swap_chain_desc);
+
+        Self {
+            window,
+            title,
+            surface,
+            swap_chain,
+            ui_instance: crate::ui_instance::Instance::new(size, scale_factor),
+            ui_render_pass: crate::ui_pass::RenderPass::new(device, swap_chain_desc.format),
+            swap_chain_desc,
+            scale_factor,
+            font_texture_version: None,
+            close_requested: false,
+        }
+    }
+
+    pub fn recreate_gpu_resources(&mut self, instance: &wgpu::Instance, device: &wgpu::Device) {
+        self.surface = unsafe { instance.create_surface(&self.window) };
+        self.swap_chain = device.create_swap_chain(&self.surface, &self.swap_chain_desc);
+        self.ui_render_pass = crate::ui_pass::RenderPass::new(device, self.swap_chain_desc.format);
+        self.font_texture_version = None;
+    }
+
+    pub fn id(&self) -> winit::window::WindowId {
+        self.window.id()
+    }
+
+    pub fn should_close(&self) -> bool {
+        self.close_requested
+    }
+
+    pub fn input(&mut self, device: &wgpu::Device, event: &winit::event::WindowEvent) {
+        self.ui_instance.input(event);
+        match event {
+            winit::event::WindowEvent::Resized(new_inner_size) => {
+                self.resize(device, *new_inner_size);
+            }
+            winit::event::WindowEvent::ScaleFactorChanged {
+                scale_factor,
+                new_inner_size,
+            } => {
+                self.scale_factor = *scale_factor;
+                self.resize(device, **new_inner_size);
+            }
+            winit::event::WindowEvent::CloseRequested => {
+                self.close_requested = true;
+            }
+            _ => {}
+        }
+    }
+
+    fn resize(&mut self, device: &wgpu::Device, size: winit::dpi::PhysicalSize<u32>) {
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
+        self.swap_chain_desc.width = size.width;
+        self.swap_chain_desc.height = size.height;
+        self.swap_chain = device.create_swap_chain(&self.surface, &self.swap_chain_desc);
+        log::info!(
+            "{} swap chain resized to {}, {}",
+            self.title,
+            size.width,
+            size.height
+        );
+    }
+
+    pub fn redraw(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        uploader: &mut crate::upload::Uploader,
+        stats: &crate::scene::Stats,
+        clear_color: wgpu::Color,
+    ) {
+        self.ui_instance.update_time();
+        self.ui_instance.begin_frame();
+        let ctx = self.ui_instance.context().clone();
+        egui::Window::new(self.title.clone()).show(&ctx, |ui| {
+            ui.label(format!(
+                "{} x {} @ {:.2}x",
+                self.swap_chain_desc.width, self.swap_chain_desc.height, self.scale_factor
+            ));
+            ui.label(format!("Scene draw calls: {}", stats.draw_calls));
+            ui.label(format!("Scene triangles: {}", stats.triangles));
+            if ui.button("Close").clicked {
+                self.close_requested = true;
+            }
+        });
+        self.ui_instance.end_frame();
+
+        let texture = ctx.texture();
+        if self.font_texture_version != Some(texture.version) {
+            self.ui_render_pass.upload_texture(device, queue, &texture);
+            self.font_texture_version = Some(texture.version);
+        }
+
+        let frame = self.swap_chain.get_current_frame().unwrap().output;
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Secondary Window Encoder"),
+        });
+        self.ui_render_pass.upload_buffers(
+            device,
+            uploader,
+            &mut encoder,
+            egui::Vec2::new(
+                self.swap_chain_desc.width as f32,
+                self.swap_chain_desc.height as f32,
+            ),
+            self.scale_factor as f32,
+            self.ui_instance.paint_jobs(),
+        );
+        self.ui_render_pass
+            .encode(&mut encoder, &frame.view, Some(clear_color));
+
+        uploader.finish();
+        queue.submit(std::iter::once(encoder.finish()));
+        uploader.recall();
+    }
+}
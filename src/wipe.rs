@@ -0,0 +1,467 @@
+const IMAGE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+const FRAME_VIEW_WIDTH: f32 = 480.0;
+const BACKGROUND: egui::Srgba = egui::Srgba::new(24, 24, 28, 255);
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    split: f32,
+    difference: f32,
+    gain: f32,
+    line: f32,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Mode {
+    Wipe,
+    Difference,
+}
+
+pub struct Comparison {
+    pub split: f32,
+    pub mode: Mode,
+    pub gain: f32,
+    viewport: Option<egui::Rect>,
+    physical_viewport: Option<egui::Rect>,
+}
+
+impl Default for Comparison {
+    fn default() -> Self {
+        Self {
+            split: 0.5,
+            mode: Mode::Wipe,
+            gain: 1.0,
+            viewport: None,
+            physical_viewport: None,
+        }
+    }
+}
+
+impl Comparison {
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        canvas: Option<&crate::blit::Canvas>,
+        id: &str,
+        size: egui::Vec2,
+        labels: [&str; 2],
+    ) {
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut self.mode, Mode::Wipe, "Wipe");
+            ui.radio_value(&mut self.mode, Mode::Difference, "Difference");
+            if ui.button("Swap side").clicked {
+                self.split = 1.0 - self.split;
+            }
+        });
+        if self.mode == Mode::Difference {
+            ui.add(egui::Slider::f32(&mut self.gain, 1.0..=16.0).text("gain"));
+        }
+        let rect = ui.allocate_space(size);
+        ui.painter().rect_filled(rect, 0.0, BACKGROUND);
+        if let Some(canvas) = canvas {
+            canvas.paint(ui, rect);
+        }
+        let response = ui.interact(rect, ui.make_persistent_id(id), egui::Sense::drag());
+        if response.active && self.mode == Mode::Wipe {
+            if let Some(pos) = ui.input().mouse.pos {
+                self.split = ((pos.x - rect.left()) / rect.width()).clamp(0.0, 1.0);
+            }
+        }
+        ui.horizontal(|ui| match self.mode {
+            Mode::Wipe => {
+                ui.label(format!("A: {}  |  B: {}", labels[0], labels[1]));
+                ui.label(format!("{:.0}%", self.split * 100.0));
+            }
+            Mode::Difference => {
+                ui.label(format!(
+                    "|{} - {}| x {:.1}",
+                    labels[1], labels[0], self.gain
+                ));
+            }
+        });
+        if response.hovered && self.mode == Mode::Wipe {
+            crate::status::hint("Drag to move the divider");
+        }
+        self.viewport = Some(rect);
+    }
+
+    pub fn visible(&self) -> bool {
+        self.viewport.is_some()
+    }
+
+    pub fn prepare(&mut self, size: winit::dpi::PhysicalSize<u32>, scale_factor: f64) -> bool {
+        self.physical_viewport = self
+            .viewport
+            .take()
+            .map(|rect| crate::blit::to_physical(rect, scale_factor))
+            .filter(|viewport| crate::blit::fits(*viewport, size));
+        self.physical_viewport.is_some()
+    }
+}
+
+pub struct Wipe {
+    pipeline: wgpu::RenderPipeline,
+    layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    uniform_buffer: wgpu::Buffer,
+    canvas: crate::blit::Canvas,
+}
+
+fn texture_entry(binding: u32) -> wgpu::BindGroupLayoutEntry {
+    wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStage::FRAGMENT,
+        ty: wgpu::BindingType::SampledTexture {
+            dimension: wgpu::TextureViewDimension::D2,
+            component_type: wgpu::TextureComponentType::Float,
+            multisampled: false,
+        },
+        count: None,
+    }
+}
+
+impl Wipe {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Wipe Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::UniformBuffer {
+                        dynamic: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                texture_entry(1),
+                texture_entry(2),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler { comparison: false },
+                    count: None,
+                },
+            ],
+        });
+        let pipeline = crate::blit::fullscreen_pipeline(
+            device,
+            "Wipe",
+            &[&layout],
+            "wipe.frag",
+            include_str!("shaders/wipe.frag"),
+            format,
+            wgpu::BlendDescriptor::REPLACE,
+        );
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Wipe Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Wipe Uniforms"),
+            size: std::mem::size_of::<Uniforms>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        Self {
+            pipeline,
+            layout,
+            sampler,
+            uniform_buffer,
+            canvas: crate::blit::Canvas::new(format),
+        }
+    }
+
+    pub fn canvas(&self) -> &crate::blit::Canvas {
+        &self.canvas
+    }
+
+    pub fn bind(
+        &self,
+        device: &wgpu::Device,
+        a: &wgpu::TextureView,
+        b: &wgpu::TextureView,
+    ) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Wipe Bind Group"),
+            layout: &self.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(self.uniform_buffer.slice(..)),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(a),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(b),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        ui_pass: &mut crate::ui_pass::RenderPass,
+        size: winit::dpi::PhysicalSize<u32>,
+        bind_group: &wgpu::BindGroup,
+        comparison: &Comparison,
+    ) {
+        let viewport = match comparison.physical_viewport {
+            Some(viewport) => viewport,
+            None => return,
+        };
+        let uniforms = Uniforms {
+            split: comparison.split,
+            difference: (comparison.mode == Mode::Difference) as u32 as f32,
+            gain: comparison.gain,
+            line: 1.0 / viewport.width().max(1.0),
+        };
+        uploader.write_buffer(
+            device,
+            encoder,
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&uniforms),
+        );
+        let target = self.canvas.begin(device, encoder, ui_pass, size);
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+        crate::blit::set_viewport(&mut render_pass, viewport);
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+pub fn image_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    label: &str,
+    image: &image::RgbaImage,
+) -> (wgpu::Texture, wgpu::TextureView) {
+    let size = wgpu::Extent3d {
+        width: image.width().max(1),
+        height: image.height().max(1),
+        depth: 1,
+    };
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some(label),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: IMAGE_FORMAT,
+        usage: wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST,
+    });
+    if image.width() > 0 && image.height() > 0 {
+        queue.write_texture(
+            wgpu::TextureCopyView {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            image.as_raw(),
+            wgpu::TextureDataLayout {
+                offset: 0,
+                bytes_per_row: 4 * size.width,
+                rows_per_image: size.height,
+            },
+            size,
+        );
+    }
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    (texture, view)
+}
+
+struct Snapshot {
+    size: winit::dpi::PhysicalSize<u32>,
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+}
+
+pub struct FrameCompare {
+    pub open: bool,
+    comparison: Comparison,
+    capture_requested: bool,
+    snapshot: Option<Snapshot>,
+    aspect: f32,
+    wipe: Wipe,
+    bind_group: Option<wgpu::BindGroup>,
+}
+
+impl FrameCompare {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        Self {
+            open: false,
+            comparison: Comparison::default(),
+            capture_requested: false,
+            snapshot: None,
+            aspect: 0.75,
+            wipe: Wipe::new(device, format),
+            bind_group: None,
+        }
+    }
+
+    pub fn recreate_gpu_resources(&mut self, device: &wgpu::Device, format: wgpu::TextureFormat) {
+        self.wipe = Wipe::new(device, format);
+        self.snapshot = None;
+        self.bind_group = None;
+    }
+
+    pub fn show(&mut self, ctx: &std::sync::Arc<egui::Context>) {
+        let mut open = self.open;
+        let comparison = &mut self.comparison;
+        let capture_requested = &mut self.capture_requested;
+        let snapshot = &mut self.snapshot;
+        let aspect = self.aspect;
+        let canvas = self.wipe.canvas();
+        crate::dock::Panel::new("Frame Compare")
+            .open(&mut open)
+            .default_pos(egui::pos2(360.0, 120.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("Capture A").clicked {
+                        *capture_requested = true;
+                    }
+                    if snapshot.is_some() && ui.button("Clear").clicked {
+                        *snapshot = None;
+                    }
+                });
+                match snapshot {
+                    Some(snapshot) => {
+                        ui.label(format!(
+                            "A: snapshot {} x {}, B: live scene (before tone mapping)",
+                            snapshot.size.width, snapshot.size.height
+                        ));
+                        comparison.show(
+                            ui,
+                            Some(canvas),
+                            "frame_compare",
+                            egui::vec2(FRAME_VIEW_WIDTH, FRAME_VIEW_WIDTH * aspect),
+                            ["Snapshot", "Live"],
+                        );
+                    }
+                    None => {
+                        ui.label("Capture a snapshot, change render settings, then compare it with the live frame");
+                    }
+                }
+            });
+        self.open = open;
+    }
+
+    fn capture(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        source: &wgpu::Texture,
+        size: winit::dpi::PhysicalSize<u32>,
+    ) {
+        if self.snapshot.as_ref().map(|snapshot| snapshot.size) != Some(size) {
+            let texture = device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("Frame Compare Snapshot"),
+                size: wgpu::Extent3d {
+                    width: size.width.max(1),
+                    height: size.height.max(1),
+                    depth: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: crate::scene::HDR_FORMAT,
+                usage: wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST,
+            });
+            let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+            self.snapshot = Some(Snapshot {
+                size,
+                texture,
+                view,
+            });
+        }
+        let snapshot = self.snapshot.as_ref().unwrap();
+        encoder.copy_texture_to_texture(
+            wgpu::TextureCopyView {
+                texture: source,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            wgpu::TextureCopyView {
+                texture: &snapshot.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            wgpu::Extent3d {
+                width: size.width.max(1),
+                height: size.height.max(1),
+                depth: 1,
+            },
+        );
+        self.aspect = size.height.max(1) as f32 / size.width.max(1) as f32;
+        crate::status::message("Captured frame A");
+    }
+
+    pub fn update(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        scene: &crate::scene::Scene,
+    ) {
+        let (live, size) = scene.color_target();
+        if std::mem::take(&mut self.capture_requested) {
+            self.capture(device, encoder, scene.color_texture(), size);
+        }
+        let visible = self.comparison.visible();
+        let wipe = &self.wipe;
+        self.bind_group = self
+            .snapshot
+            .as_ref()
+            .filter(|_| visible)
+            .map(|snapshot| wipe.bind(device, &snapshot.view, live));
+    }
+
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        uploader: &mut crate::upload::Uploader,
+        encoder: &mut wgpu::CommandEncoder,
+        ui_pass: &mut crate::ui_pass::RenderPass,
+        size: winit::dpi::PhysicalSize<u32>,
+        scale_factor: f64,
+    ) {
+        if !self.comparison.prepare(size, scale_factor) {
+            return;
+        }
+        if let Some(bind_group) = self.bind_group.take() {
+            self.wipe.draw(
+                device,
+                uploader,
+                encoder,
+                ui_pass,
+                size,
+                &bind_group,
+                &self.comparison,
+            );
+        }
+    }
+}
@@ -0,0 +1,120 @@
+const WIDTH: f32 = 520.0;
+const CURRENT_COLOR: egui::Srgba = egui::Srgba::new(90, 170, 250, 255);
+const DONE_COLOR: egui::Srgba = egui::Srgba::new(110, 200, 120, 255);
+const PENDING_COLOR: egui::Srgba = egui::Srgba::new(150, 150, 160, 255);
+const ERROR_COLOR: egui::Srgba = egui::Srgba::new(230, 110, 100, 255);
+
+pub enum Outcome {
+    Finished,
+    Cancelled,
+}
+
+pub struct Wizard {
+    id: &'static str,
+    title: &'static str,
+    steps: Vec<&'static str>,
+    current: usize,
+    error: Option<String>,
+    confirming_cancel: bool,
+}
+
+impl Wizard {
+    pub fn new(id: &'static str, title: &'static str, steps: Vec<&'static str>) -> Self {
+        Self {
+            id,
+            title,
+            steps,
+            current: 0,
+            error: None,
+            confirming_cancel: false,
+        }
+    }
+
+    pub fn restart(&mut self) {
+        self.current = 0;
+        self.error = None;
+        self.confirming_cancel = false;
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &std::sync::Arc<egui::Context>,
+        screen: egui::Vec2,
+        contents: impl FnOnce(&mut egui::Ui, usize) -> Result<(), String>,
+    ) -> Option<Outcome> {
+        let current = self.current;
+        let last = current + 1 == self.steps.len();
+        let steps = &self.steps;
+        let error = &self.error;
+        let confirming_cancel = self.confirming_cancel;
+        let mut validation = Ok(());
+        let (mut back, mut next, mut cancel, mut discard, mut resume) =
+            (false, false, false, false, false);
+        crate::modal::show(ctx, screen, self.id, WIDTH, |ui| {
+            ui.heading(self.title);
+            ui.columns(2, |columns| {
+                for (index, step) in steps.iter().enumerate() {
+                    let color = if index == current {
+                        CURRENT_COLOR
+                    } else if index < current {
+                        DONE_COLOR
+                    } else {
+                        PENDING_COLOR
+                    };
+                    columns[0].add(
+                        egui::Label::new(format!("{}. {}", index + 1, step)).text_color(color),
+                    );
+                }
+                columns[1].heading(steps[current]);
+                validation = contents(&mut columns[1], current);
+            });
+            if let Some(error) = error {
+                ui.add(egui::Label::new(error.as_str()).text_color(ERROR_COLOR));
+            }
+            if confirming_cancel {
+                ui.label("Discard the choices made so far?");
+                ui.horizontal(|ui| {
+                    discard = ui.button("Discard").clicked;
+                    resume = ui.button("Keep going").clicked;
+                });
+                return;
+            }
+            ui.horizontal(|ui| {
+                if current > 0 {
+                    back = ui.button("Back").clicked;
+                }
+                next = ui.button(if last { "Finish" } else { "Next" }).clicked;
+                cancel = ui.button("Cancel").clicked;
+            });
+        });
+
+        if discard {
+            self.restart();
+            return Some(Outcome::Cancelled);
+        }
+        if resume {
+            self.confirming_cancel = false;
+        }
+        if cancel {
+            self.confirming_cancel = true;
+        }
+        if back {
+            self.current -= 1;
+            self.error = None;
+        }
+        if next {
+            match validation {
+                Ok(()) if last => {
+                    self.restart();
+                    return Some(Outcome::Finished);
+                }
+                Ok(()) => {
+                    self.current += 1;
+                    self.error = None;
+                }
+                Err(err) => self.error = Some(err),
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,93 @@
+pub const MIN_SCALE: f32 = 0.5;
+pub const MAX_SCALE: f32 = 3.0;
+const WHEEL_STEP: f32 = 1.1;
+
+pub fn clamp(scale: f32) -> f32 {
+    scale.clamp(MIN_SCALE, MAX_SCALE)
+}
+
+fn distance(
+    first: winit::dpi::PhysicalPosition<f64>,
+    second: winit::dpi::PhysicalPosition<f64>,
+) -> f64 {
+    let (dx, dy) = (first.x - second.x, first.y - second.y);
+    (dx * dx + dy * dy).sqrt()
+}
+
+#[derive(Default)]
+pub struct Zoom {
+    touches: Vec<(u64, winit::dpi::PhysicalPosition<f64>)>,
+    pinch: Option<f64>,
+}
+
+impl Zoom {
+    pub fn pinching(&self) -> bool {
+        self.pinch.is_some()
+    }
+
+    pub fn input(
+        &mut self,
+        event: &winit::event::WindowEvent,
+        modifiers: winit::event::ModifiersState,
+        scale: &mut f32,
+    ) -> bool {
+        match event {
+            winit::event::WindowEvent::MouseWheel { delta, .. } if modifiers.ctrl() => {
+                let lines = match delta {
+                    winit::event::MouseScrollDelta::LineDelta(_, y) => *y,
+                    winit::event::MouseScrollDelta::PixelDelta(position) => {
+                        position.y as f32 / 40.0
+                    }
+                };
+                *scale = clamp(*scale * WHEEL_STEP.powf(lines));
+                true
+            }
+            winit::event::WindowEvent::Touch(touch) => self.touch(touch, scale),
+            _ => false,
+        }
+    }
+
+    fn touch(&mut self, touch: &winit::event::Touch, scale: &mut f32) -> bool {
+        let index = self.touches.iter().position(|(id, _)| *id == touch.id);
+        match (touch.phase, index) {
+            (winit::event::TouchPhase::Started, None) => {
+                self.touches.push((touch.id, touch.location));
+            }
+            (winit::event::TouchPhase::Moved, Some(index)) => {
+                self.touches[index].1 = touch.location;
+            }
+            (winit::event::TouchPhase::Ended, Some(index))
+            | (winit::event::TouchPhase::Cancelled, Some(index)) => {
+                self.touches.remove(index);
+            }
+            _ => {}
+        }
+        let was_pinching = self.pinching();
+        self.pinch = match self.touches.as_slice() {
+            [(_, first), (_, second)] => {
+                let current = distance(*first, *second);
+                if let Some(previous) = self.pinch.filter(|previous| *previous > 0.0) {
+                    *scale = clamp(*scale * (current / previous) as f32);
+                }
+                Some(current)
+            }
+            _ => None,
+        };
+        was_pinching || self.pinching()
+    }
+
+    pub fn show(&self, ui: &mut egui::Ui, scale: &mut f32) {
+        ui.label(format!("Zoom {:.0}%", *scale * 100.0));
+        if ui.button("-").clicked {
+            *scale = clamp(*scale / WHEEL_STEP);
+        }
+        if ui.button("+").clicked {
+            *scale = clamp(*scale * WHEEL_STEP);
+        }
+        let reset = ui.button("Reset");
+        crate::status::hint_on_hover(&reset, "Ctrl+scroll or pinch to zoom the UI");
+        if reset.clicked {
+            *scale = 1.0;
+        }
+    }
+}